@@ -254,6 +254,48 @@ impl Server {
                 }
                 .update_size())
             }
+            Account::Resource(account) => {
+                // Resources use the default user roles so they can receive scheduling messages
+                let tenant_id = account.member_tenant_id.map(|t| t.id() as u32);
+                let permissions = self
+                    .effective_permissions(
+                        &account.permissions,
+                        account
+                            .roles
+                            .role_ids()
+                            .unwrap_or(self.core.network.security.default_role_ids_user.as_slice()),
+                        tenant_id,
+                    )
+                    .await?;
+
+                Ok(AccessTokenInner {
+                    concurrent_imap_requests: self
+                        .core
+                        .imap
+                        .rate_concurrent
+                        .map(ConcurrencyLimiter::new),
+                    concurrent_http_requests: self
+                        .core
+                        .jmap
+                        .request_max_concurrent
+                        .map(ConcurrencyLimiter::new),
+                    concurrent_uploads: self
+                        .core
+                        .jmap
+                        .upload_max_concurrent
+                        .map(ConcurrencyLimiter::new),
+                    obj_size: 0,
+                    revision,
+                    revision_account,
+                    credential_version: 0,
+                    account_id,
+                    tenant_id,
+                    member_of: Default::default(),
+                    access_to: Default::default(),
+                    scopes: Box::new([AccessScope::new(permissions.finalize(), u32::MAX)]),
+                }
+                .update_size())
+            }
         }
     }
 
@@ -884,6 +926,16 @@ fn hash_account(account: &Account) -> u64 {
             }
             hash_permissions(&mut s, &account.permissions);
         }
+        Account::Resource(account) => {
+            account.member_tenant_id.hash(&mut s);
+            match &account.roles {
+                Roles::Default => {}
+                Roles::Custom(custom_roles) => {
+                    custom_roles.role_ids.as_slice().hash(&mut s);
+                }
+            }
+            hash_permissions(&mut s, &account.permissions);
+        }
     }
 
     s.finish()
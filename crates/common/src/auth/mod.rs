@@ -96,6 +96,8 @@ pub const ACCOUNT_FLAG_ENCRYPT_ALGO_AES128: u64 = 1 << 5;
 pub const ACCOUNT_FLAG_ENCRYPT_APPEND: u64 = 1 << 6;
 pub const ACCOUNT_FLAG_ENCRYPT_ALGO_AES256_GCM: u64 = 1 << 7;
 pub const ACCOUNT_FLAG_ENCRYPT_ALGO_CHACHA20_POLY1305: u64 = 1 << 8;
+pub const ACCOUNT_IS_RESOURCE: u64 = 1 << 9;
+pub const ACCOUNT_IS_EQUIPMENT: u64 = 1 << 10;
 
 #[derive(Debug, Clone)]
 pub struct RoleCache {
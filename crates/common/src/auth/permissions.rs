@@ -137,6 +137,14 @@ impl Server {
                     .unwrap_or(self.core.network.security.default_role_ids_group.as_slice()),
                 account.member_tenant_id.map(|t| t.document_id()),
             ),
+            Account::Resource(account) => (
+                &account.permissions,
+                account
+                    .roles
+                    .role_ids()
+                    .unwrap_or(self.core.network.security.default_role_ids_user.as_slice()),
+                account.member_tenant_id.map(|t| t.document_id()),
+            ),
         };
 
         self.effective_permissions(permissions, role_ids, tenant_id)
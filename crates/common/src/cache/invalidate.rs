@@ -111,6 +111,43 @@ impl CacheInvalidationBuilder {
                 }
             }
 
+            (
+                ObjectInner::Account(Account::Resource(current)),
+                ObjectInner::Account(Account::Resource(new)),
+            ) => {
+                let was_renamed =
+                    (current.name != new.name) || (current.domain_id != new.domain_id);
+                let quota_changed = current.quotas != new.quotas;
+                let permissions_changed = current.permissions != new.permissions;
+                let roles_changed = current.roles != new.roles;
+                let tenant_changed = current.member_tenant_id != new.member_tenant_id;
+                let details_changed = current.locale != new.locale
+                    || current.description != new.description
+                    || current.resource_type != new.resource_type;
+                let aliases_changed = current.aliases != new.aliases;
+
+                if was_renamed
+                    || aliases_changed
+                    || tenant_changed
+                    || quota_changed
+                    || details_changed
+                {
+                    self.invalidate(CacheInvalidation::Account(id));
+                }
+
+                if was_renamed || aliases_changed {
+                    self.invalidate_negative_email(&new_object.inner);
+                }
+
+                if tenant_changed || roles_changed || permissions_changed {
+                    self.invalidate(CacheInvalidation::AccessToken(id));
+                }
+
+                if was_renamed {
+                    self.invalidate(CacheInvalidation::DavResources(id));
+                }
+            }
+
             (ObjectInner::Domain(current), ObjectInner::Domain(new)) => {
                 if (current.name != new.name)
                     || (current.aliases != new.aliases)
@@ -228,6 +265,9 @@ impl CacheInvalidationBuilder {
             ObjectInner::Account(Account::Group(account)) => {
                 (&account.name, account.domain_id, &account.aliases)
             }
+            ObjectInner::Account(Account::Resource(account)) => {
+                (&account.name, account.domain_id, &account.aliases)
+            }
             ObjectInner::MailingList(list) => (&list.name, list.domain_id, &list.aliases),
             _ => return,
         };
@@ -335,6 +375,7 @@ impl Server {
         self.inner.cache.files.clear();
         self.inner.cache.contacts.clear();
         self.inner.cache.events.clear();
+        self.inner.cache.publications.clear();
         self.inner.cache.scheduling.clear();
         self.inner.cache.dkim_signers.clear();
         self.inner.cache.accounts.clear();
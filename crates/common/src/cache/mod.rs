@@ -4,7 +4,9 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use crate::{DavResources, HttpAuthCache, MailboxCache, MessageStoreCache, UpdateLock};
+use crate::{
+    DavResources, HttpAuthCache, MailboxCache, MessageStoreCache, PublishedCalendar, UpdateLock,
+};
 use std::sync::atomic::{AtomicU64, Ordering};
 use tokio::sync::{Semaphore, SemaphorePermit};
 use utils::cache::CacheItemWeight;
@@ -92,3 +94,9 @@ impl CacheItemWeight for DavResources {
         self.size
     }
 }
+
+impl CacheItemWeight for PublishedCalendar {
+    fn weight(&self) -> u64 {
+        (std::mem::size_of::<PublishedCalendar>() + self.ical.len()) as u64
+    }
+}
@@ -10,10 +10,11 @@ use crate::{
         ACCOUNT_FLAG_ENCRYPT_ALGO_AES128, ACCOUNT_FLAG_ENCRYPT_ALGO_AES256,
         ACCOUNT_FLAG_ENCRYPT_ALGO_AES256_GCM, ACCOUNT_FLAG_ENCRYPT_ALGO_CHACHA20_POLY1305,
         ACCOUNT_FLAG_ENCRYPT_APPEND, ACCOUNT_FLAG_ENCRYPT_METHOD_PGP,
-        ACCOUNT_FLAG_ENCRYPT_METHOD_SMIME, ACCOUNT_FLAG_ENCRYPT_TRAIN_SPAM_FILTER, ACCOUNT_IS_USER,
-        AccountCache, AccountInfo, AccountTenantIds, DOMAIN_FLAG_RELAY, DOMAIN_FLAG_SUB_ADDRESSING,
-        DomainCache, EmailAddress, EmailAddressRef, EmailCache, MailingListCache, PermissionsGroup,
-        RECOVERY_ADMIN_ID, RoleCache, TenantCache, permissions::BuildPermissions,
+        ACCOUNT_FLAG_ENCRYPT_METHOD_SMIME, ACCOUNT_FLAG_ENCRYPT_TRAIN_SPAM_FILTER,
+        ACCOUNT_IS_EQUIPMENT, ACCOUNT_IS_RESOURCE, ACCOUNT_IS_USER, AccountCache, AccountInfo,
+        AccountTenantIds, DOMAIN_FLAG_RELAY, DOMAIN_FLAG_SUB_ADDRESSING, DomainCache, EmailAddress,
+        EmailAddressRef, EmailCache, MailingListCache, PermissionsGroup, RECOVERY_ADMIN_ID,
+        RoleCache, TenantCache, permissions::BuildPermissions,
     },
     config::smtp::auth::DkimSigners,
     expr::if_block::BootstrapExprExt,
@@ -25,7 +26,7 @@ use crate::{
 };
 use registry::{
     schema::{
-        enums::{DkimRotationStage, Locale, StorageQuota, TenantStorageQuota},
+        enums::{DkimRotationStage, Locale, ResourceType, StorageQuota, TenantStorageQuota},
         prelude::{ObjectType, Property},
         structs::{
             Account, DkimSignature, Domain, EncryptionAtRest, MailingList, MaskedEmail,
@@ -559,6 +560,70 @@ impl Server {
                             flags: 0,
                         }
                     }
+                    Account::Resource(account) => {
+                        let domain = self
+                            .domain_by_id(account.domain_id.document_id())
+                            .await?
+                            .ok_or_else(|| {
+                                trc::AuthEvent::Error
+                                    .into_err()
+                                    .details("Domain not found for resource account.")
+                                    .ctx(trc::Key::AccountId, account_id)
+                                    .ctx(trc::Key::Id, account.domain_id.document_id())
+                                    .caused_by(trc::location!())
+                            })?;
+                        let mut name =
+                            String::with_capacity(domain.names[0].len() + account.name.len() + 1);
+                        name.push_str(account.name.as_ref());
+                        name.push('@');
+                        name.push_str(domain.names[0].as_ref());
+
+                        let mut quota_objects: Option<ObjectQuota> = None;
+                        let mut quota_disk = 0;
+                        for (resource, limit) in account.quotas {
+                            if resource == StorageQuota::MaxDiskQuota {
+                                quota_disk = limit;
+                            } else {
+                                quota_objects
+                                    .get_or_insert_with(|| self.core.email.max_objects.clone())
+                                    .set(resource, limit as u32);
+                            }
+                        }
+
+                        AccountCache {
+                            id: account_id,
+                            name: name.into_boxed_str(),
+                            addresses: [EmailAddress {
+                                local_part: account.name.into(),
+                                domain_id: account.domain_id.document_id(),
+                            }]
+                            .into_iter()
+                            .chain(
+                                account
+                                    .aliases
+                                    .into_iter()
+                                    .filter(|alias| alias.enabled)
+                                    .map(|alias| EmailAddress {
+                                        local_part: alias.name.into(),
+                                        domain_id: alias.domain_id.document_id(),
+                                    }),
+                            )
+                            .collect(),
+                            id_tenant: account.member_tenant_id.map(|id| id.document_id()),
+                            id_member_of: Default::default(),
+                            quota_disk,
+                            quota_objects: quota_objects.map(Box::new),
+                            description: account.description.map(Into::into),
+                            encryption_key: None,
+                            locale: account.locale,
+                            flags: match account.resource_type {
+                                ResourceType::Room => ACCOUNT_IS_RESOURCE,
+                                ResourceType::Equipment => {
+                                    ACCOUNT_IS_RESOURCE | ACCOUNT_IS_EQUIPMENT
+                                }
+                            },
+                        }
+                    }
                 });
 
                 let _ = guard.insert(cache.clone());
@@ -965,6 +1030,11 @@ impl AccountInfo {
         self.account.flags & ACCOUNT_IS_USER != 0
     }
 
+    #[inline(always)]
+    pub fn resource_type(&self) -> Option<ResourceType> {
+        self.account.resource_type()
+    }
+
     #[inline(always)]
     pub fn locale(&self) -> Locale {
         self.account.locale
@@ -1007,6 +1077,17 @@ impl AccountCache {
         self.flags & ACCOUNT_IS_USER != 0
     }
 
+    #[inline(always)]
+    pub fn resource_type(&self) -> Option<ResourceType> {
+        if self.flags & ACCOUNT_IS_EQUIPMENT != 0 {
+            Some(ResourceType::Equipment)
+        } else if self.flags & ACCOUNT_IS_RESOURCE != 0 {
+            Some(ResourceType::Room)
+        } else {
+            None
+        }
+    }
+
     #[inline(always)]
     pub fn disk_quota(&self) -> u64 {
         self.quota_disk
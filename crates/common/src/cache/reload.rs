@@ -13,6 +13,7 @@ use crate::{
     },
     ipc::{QueueEvent, RegistryChange},
     network::security::{BlockedIps, IpWithTtl},
+    storage::compression::load_compression_dictionaries,
 };
 use ahash::AHashMap;
 use directory::Directories;
@@ -21,7 +22,10 @@ use registry::{
     types::error::{Error, Warning},
 };
 use std::sync::Arc;
-use store::{LookupStores, registry::bootstrap::Bootstrap, write::now};
+use store::{
+    LookupStores, dispatch::compression::CompressionDictionaries, registry::bootstrap::Bootstrap,
+    write::now,
+};
 
 pub struct ReloadResult {
     pub errors: Vec<Error>,
@@ -83,12 +87,35 @@ impl Server {
                 }
             }
 
+            ObjectType::CompressionDictionary => {
+                if let RegistryChange::Delete(id) = change {
+                    CompressionDictionaries::unregister(id.id().id());
+                } else {
+                    load_compression_dictionaries(&mut bootstrap, &self.core.storage.blob).await;
+                }
+            }
+            ObjectType::DataStore => {
+                // Data store changes require a restart, only the write fence is reloaded
+                self.registry().sync_write_fence().await?;
+            }
             ObjectType::BlockedIp => {
                 let blocked_ips = BlockedIps::parse(&mut bootstrap).await;
                 if bootstrap.errors.is_empty() {
                     *self.inner.data.blocked_ips.write() = blocked_ips;
                 }
             }
+            // SPDX-SnippetBegin
+            // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+            // SPDX-License-Identifier: LicenseRef-SEL
+            #[cfg(feature = "enterprise")]
+            ObjectType::DataStoreShard => {
+                // New shards are opened on restart, only placements and fences are reloaded
+                if let Some(store) = self.core.storage.data.as_sharded() {
+                    store.reload_placement().await?;
+                    store.acknowledge_fences(self.registry().node_id()).await?;
+                }
+            }
+            // SPDX-SnippetEnd
             ObjectType::Application => {
                 self.inner.data.applications.reload(&mut bootstrap).await;
                 if bootstrap.errors.is_empty() {
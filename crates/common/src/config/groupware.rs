@@ -51,6 +51,8 @@ pub struct GroupwareConfig {
 
     // File storage settings
     pub max_file_size: usize,
+    pub max_file_versions: usize,
+    pub file_version_retention: Option<u64>,
 
     // Sharing settings
     pub max_shares_per_item: usize,
@@ -109,6 +111,8 @@ impl GroupwareConfig {
             max_ical_attendees_per_instance: calendar.max_attendees as usize,
             max_vcard_size: book.max_v_card_size as usize,
             max_file_size: file.max_size as usize,
+            max_file_versions: file.max_versions as usize,
+            file_version_retention: file.version_retention.map(|d| d.into_inner().as_secs()),
             alarms_enabled: alarm.enable,
             alarms_minimum_interval: alarm.min_trigger_interval.into_inner().as_secs() as i64,
             alarms_allow_external_recipients: alarm.allow_external_rcpts,
@@ -7,7 +7,7 @@
 use super::server::tls::build_self_signed_cert;
 use crate::{
     Caches, Data, DavResource, DavResources, MailboxCache, MessageStoreCache, MessageUidCache,
-    TlsConnectors,
+    PublishedCalendar, TlsConnectors,
     auth::{AccessTokenInner, AccountCache, DomainCache, MailingListCache, RoleCache, TenantCache},
     config::{
         mailstore::spamfilter::SpamClassifier,
@@ -89,6 +89,7 @@ impl Data {
             queue_status: true.into(),
             applications,
             logos: Default::default(),
+            top_usage: Default::default(),
             smtp_connectors: TlsConnectors::try_new().failed("Failed to build TLS connectors"),
             asn_geo_data: Default::default(),
         }
@@ -132,6 +133,10 @@ impl Caches {
                 (std::mem::size_of::<DavResources>() + (500 * std::mem::size_of::<DavResource>()))
                     as u64,
             ),
+            publications: Cache::new(
+                cache.published_calendars,
+                (std::mem::size_of::<PublishedCalendar>() + 16384) as u64,
+            ),
             emails: Cache::new(cache.email_addresses, 255u64),
             emails_negative: CacheWithTtl::new(
                 cache.email_addresses_negative,
@@ -229,6 +234,7 @@ impl Default for Data {
             queue_status: true.into(),
             applications: WebApplications::new(),
             logos: Default::default(),
+            top_usage: Default::default(),
             smtp_connectors: TlsConnectors::try_new().unwrap(),
             asn_geo_data: Default::default(),
             lookup_stores: Default::default(),
@@ -8,20 +8,18 @@ use ahash::{AHashMap, AHashSet};
 use nlp::language::Language;
 use registry::{
     schema::{
-        enums::{
-            CompressionAlgo, SearchCalendarField, SearchContactField, SearchEmailField,
-            StorageQuota,
-        },
+        enums::{SearchCalendarField, SearchContactField, SearchEmailField, StorageQuota},
         prelude::ObjectType,
         structs::{
-            AddressBook, Authentication, Calendar, DataRetention, Domain, Email, FileStorage, Jmap,
-            Search, SieveUserInterpreter, SystemSettings,
+            AddressBook, Authentication, Backup, Calendar, DataRetention, Domain, Email,
+            FileStorage, Jmap, Search, SieveUserInterpreter, SystemSettings,
         },
     },
     types::EnumImpl,
 };
 use std::time::Duration;
 use store::{
+    dispatch::compression::BlobCompression,
     registry::bootstrap::Bootstrap,
     search::{CalendarSearchField, ContactSearchField, EmailSearchField, SearchField},
     write::SearchIndex,
@@ -60,11 +58,12 @@ pub struct EmailConfig {
     pub index_fields: AHashMap<SearchIndex, AHashSet<SearchField>>,
 
     pub max_objects: ObjectQuota,
-    pub compression: CompressionAlgo,
+    pub compression: BlobCompression,
 
     pub account_purge_frequency: SimpleCron,
     pub data_purge_frequency: SimpleCron,
     pub blob_purge_frequency: SimpleCron,
+    pub backup_frequency: Option<SimpleCron>,
 }
 
 #[derive(Clone, Debug)]
@@ -87,6 +86,7 @@ impl EmailConfig {
         let calendar = bp.setting_infallible::<Calendar>().await;
         let address_book = bp.setting_infallible::<AddressBook>().await;
         let system = bp.setting_infallible::<SystemSettings>().await;
+        let backup = bp.setting_infallible::<Backup>().await;
         let auth = bp.setting_infallible::<Authentication>().await;
 
         // Obtain default domain name
@@ -121,6 +121,7 @@ impl EmailConfig {
             (StorageQuota::MaxApiKeys, auth.max_api_keys),
             (StorageQuota::MaxPublicKeys, email.max_public_keys),
             (StorageQuota::MaxPushSubscriptions, jmap.max_subscriptions),
+            (StorageQuota::MaxWebhooks, jmap.max_webhooks),
             (StorageQuota::MaxCalendars, calendar.max_calendars),
             (StorageQuota::MaxCalendarEvents, calendar.max_events),
             (
@@ -265,6 +266,7 @@ impl EmailConfig {
                     .collect(),
             );
         }
+        index_fields.insert(SearchIndex::File, AHashSet::new());
 
         EmailConfig {
             default_language: Language::from_iso_639(search.default_language.as_str())
@@ -290,7 +292,14 @@ impl EmailConfig {
             account_purge_frequency: dr.expunge_schedule.into(),
             data_purge_frequency: dr.data_cleanup_schedule.into(),
             blob_purge_frequency: dr.blob_cleanup_schedule.into(),
-            compression: email.compression_algorithm,
+            backup_frequency: match backup {
+                Backup::Enabled(backup) => Some(backup.schedule.into()),
+                Backup::Disabled => None,
+            },
+            compression: BlobCompression::new(
+                email.compression_algorithm,
+                email.compression_level as i32,
+            ),
             default_domain_id: system.default_domain_id.id() as u32,
             default_domain_name,
         }
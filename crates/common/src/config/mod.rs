@@ -13,6 +13,7 @@ use crate::{
     config::mailstore::{
         email::EmailConfig, imap::ImapConfig, scripts::Scripting, spamfilter::SpamFilterConfig,
     },
+    storage::{compression::load_compression_dictionaries, envelope::load_blob_encryption_keys},
 };
 use arc_swap::ArcSwap;
 use groupware::GroupwareConfig;
@@ -50,11 +51,15 @@ impl Core {
                 use store::Store;
 
                 if storage.data.is_enterprise() {
+                    storage.data = storage.data.downgrade_store();
                     bp.build_error(
                         ObjectType::DataStore.singleton(),
-                        "Disabling enterprise-only data store.",
+                        if storage.data.is_enterprise() {
+                            "Accounts are placed on data store shards, move them to the primary store before disabling sharding."
+                        } else {
+                            "Disabling enterprise-only data store."
+                        },
                     );
-                    storage.data = storage.data.downgrade_store();
                 }
                 if storage.blob.is_enterprise() {
                     bp.build_error(
@@ -78,6 +83,9 @@ impl Core {
         };
         // SPDX-SnippetEnd
 
+        load_blob_encryption_keys(bp).await;
+        load_compression_dictionaries(bp, &storage.blob).await;
+
         Self {
             // SPDX-SnippetBegin
             // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
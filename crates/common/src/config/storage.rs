@@ -41,9 +41,18 @@ impl Storage {
             );
         }
 
+        // SPDX-SnippetBegin
+        // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+        // SPDX-License-Identifier: LicenseRef-SEL
+        #[cfg(feature = "enterprise")]
+        let data = Store::build_sharded(bp).await;
+        // SPDX-SnippetEnd
+        #[cfg(not(feature = "enterprise"))]
+        let data = bp.data_store.clone();
+
         Storage {
             registry: bp.registry.clone(),
-            data: bp.data_store.clone(),
+            data,
             blob: BlobStore::build(bp).await.unwrap_or_default(),
             search,
             coordinator: Coordinator::build(bp, &memory).await.unwrap_or_default(),
@@ -134,6 +134,7 @@ pub struct Metrics {
 #[derive(Debug, Clone, Default)]
 pub struct PrometheusMetrics {
     pub auth: Option<String>,
+    pub top_accounts: usize,
 }
 
 impl Telemetry {
@@ -614,6 +615,7 @@ impl Metrics {
                         auth: prom.auth_username.and_then(|user| {
                             secret.map(|secret| STANDARD.encode(format!("{user}:{secret}")))
                         }),
+                        top_accounts: prom.top_accounts as usize,
                     })
                 }
                 MetricsPrometheus::Disabled => None,
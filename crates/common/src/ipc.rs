@@ -43,6 +43,10 @@ pub enum PushEvent {
         account_id: u32,
         broadcast: bool,
     },
+    WebhookRegister {
+        activate: Vec<u32>,
+        expired: Vec<u32>,
+    },
     Stop,
 }
 
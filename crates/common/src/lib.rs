@@ -22,6 +22,7 @@ use crate::{
     },
     ipc::TrainTaskController,
     network::security::BlockedIps,
+    storage::usage::TopUsage,
 };
 use ahash::{AHashMap, AHashSet};
 use arc_swap::ArcSwap;
@@ -127,6 +128,8 @@ pub const KV_LOCK_QUEUE_MESSAGE: u8 = 21;
 pub const KV_LOCK_TASK: u8 = 23;
 pub const KV_LOCK_DAV: u8 = 25;
 pub const KV_SIEVE_ID: u8 = 26;
+pub const KV_UPLOAD_DAV: u8 = 27;
+pub const KV_BLOB_CONVERT: u8 = 28;
 
 #[derive(Clone)]
 pub struct Server {
@@ -161,6 +164,7 @@ pub struct Data {
 
     pub applications: WebApplications,
     pub logos: Mutex<AHashMap<Box<str>, LogoCache>>,
+    pub top_usage: Mutex<TopUsage>,
 
     pub smtp_connectors: TlsConnectors,
 }
@@ -181,6 +185,7 @@ pub struct Caches {
     pub contacts: Cache<u32, Arc<DavResources>>,
     pub events: Cache<u32, Arc<DavResources>>,
     pub scheduling: Cache<u32, Arc<DavResources>>,
+    pub publications: Cache<u64, Arc<PublishedCalendar>>,
 
     pub emails: Cache<EmailAddress, EmailCache>,
     pub emails_negative: CacheWithTtl<EmailAddress, ()>,
@@ -262,6 +267,14 @@ pub struct MailboxCache {
     pub acls: TinyVec<[AclGrant; 2]>,
 }
 
+#[derive(Debug, Clone)]
+pub struct PublishedCalendar {
+    pub change_id: u64,
+    pub is_free_busy: bool,
+    pub expires: u64,
+    pub ical: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct HttpAuthCache {
     pub account_id: u32,
@@ -4,20 +4,35 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use crate::Core;
-use ahash::AHashSet;
+use super::{
+    location::BackupLocation,
+    manifest::{
+        ACCOUNT_SUBSPACES, AccountState, BLOB_INDEX_FILE, BackupFile, BackupIndex, BackupManifest,
+        BackupParent, ChecksumWriter, IndexedBackup, MANIFEST_FILE, load_chain,
+    },
+    restore::KeyValueReader,
+};
+use crate::{Core, DATABASE_SCHEMA_VERSION};
+use ahash::{AHashMap, AHashSet};
 use lz4_flex::frame::FrameEncoder;
+use registry::schema::structs::BackupProperties;
 use std::{
-    io::{BufWriter, Write},
+    collections::BTreeMap,
+    io::Write,
     path::{Path, PathBuf},
-    sync::mpsc::{self, SyncSender},
+    sync::{
+        Arc,
+        mpsc::{self, SyncSender},
+    },
 };
 use store::{
-    write::{AnyClass, AnyKey, ValueClass},
+    write::{AnyClass, AnyKey, ValueClass, key::DeserializeBigEndian, now},
     *,
 };
+use trc::AddContext;
 use types::blob_hash::{BLOB_HASH_LEN, BlobHash};
-use utils::{UnwrapFailure, codec::leb128::Leb128_};
+use utils::{codec::leb128::Leb128_, failed};
+use xxhash_rust::xxh3::{Xxh3, xxh3_64_with_seed};
 
 pub(super) const MAGIC_MARKER: u8 = 123;
 
@@ -33,22 +48,54 @@ pub(super) enum Family {
     Tasks = 8,
 }
 
-type TaskHandle = (tokio::task::JoinHandle<()>, std::thread::JoinHandle<()>);
+type TaskHandle = (
+    tokio::task::JoinHandle<Result<(), String>>,
+    Vec<std::thread::JoinHandle<Result<BackupFile, String>>>,
+);
 
-#[derive(Debug, Default, PartialEq, Eq)]
+#[derive(Default)]
 pub struct BackupParams {
     dest: PathBuf,
     families: AHashSet<Family>,
+    parent: Option<PathBuf>,
+    location: BackupLocation,
+    verbose: bool,
+}
+
+// Keys exported from a subspace. Incremental backups skip accounts whose
+// data did not change since the parent backup.
+struct KeyFilter {
+    ranges: Vec<(Vec<u8>, Vec<u8>)>,
+    unchanged: AHashSet<u32>,
+    logs_after: AHashMap<u32, u64>,
+}
+
+pub struct ScheduledBackup {
+    pub name: String,
+    pub manifest: BackupManifest,
+    pub deleted: Vec<String>,
 }
 
 impl Core {
-    pub async fn backup(&self, mut params: BackupParams) {
-        if !params.dest.exists() {
-            std::fs::create_dir_all(&params.dest).failed("Failed to create backup directory");
-        } else if !params.dest.is_dir() {
-            eprintln!("Backup destination {:?} is not a directory.", params.dest);
-            std::process::exit(1);
+    pub async fn backup(&self, params: BackupParams) {
+        let location = params.location.clone();
+        let dest = params.dest.clone();
+        match self.try_backup(params).await {
+            Ok(manifest) => {
+                println!(
+                    "Backup {} with sequence {} written to {}.",
+                    manifest.id,
+                    manifest.sequence,
+                    location.display(&dest)
+                );
+            }
+            Err(err) => failed(&format!("Backup failed: {err}")),
         }
+    }
+
+    pub async fn try_backup(&self, mut params: BackupParams) -> Result<BackupManifest, String> {
+        let location = params.location.clone();
+        location.create_dir(&params.dest).await?;
 
         let mut sync_handles = Vec::new();
         let schema_version = self
@@ -59,8 +106,46 @@ impl Core {
                 key: vec![0u8],
             })
             .await
-            .failed("Could not retrieve database schema version.")
-            .failed("Could not retrieve database schema version.");
+            .map_err(|err| format!("Could not retrieve database schema version: {err}"))?
+            .ok_or_else(|| "Could not retrieve database schema version.".to_string())?;
+
+        // Load the parent backup and the blobs already exported in its chain
+        let mut parent = None;
+        let mut known_blobs = AHashSet::new();
+        if let Some(parent_path) = &params.parent {
+            let chain = load_chain(&location, parent_path).await?;
+            for (path, manifest) in &chain {
+                for file in manifest.files.iter().filter(|file| file.blob_index) {
+                    let location = location.clone();
+                    let path = path.join(&file.name);
+                    known_blobs = tokio::task::spawn_blocking(move || {
+                        let mut reader = KeyValueReader::try_new(&location, &path)?;
+                        while let Some((key, _)) = reader.try_next()? {
+                            if let Ok(hash) = BlobHash::try_from_hash_slice(&key) {
+                                known_blobs.insert(hash);
+                            }
+                        }
+                        Ok::<_, String>(known_blobs)
+                    })
+                    .await
+                    .map_err(|err| format!("Failed to read blob index: {err}"))??;
+                }
+            }
+
+            let (_, manifest) = chain.into_iter().last().unwrap();
+            if manifest.schema_version != schema_version {
+                return Err(format!(
+                    "Cannot create an incremental backup of {}: the database schema version has changed.",
+                    location.display(parent_path)
+                ));
+            }
+            params.families = manifest
+                .families
+                .iter()
+                .map(|family| Family::parse(family))
+                .collect::<Result<_, _>>()?;
+            parent = Some(manifest);
+        }
 
         if params.families.is_empty() {
             params.families = [
@@ -77,35 +162,205 @@ impl Core {
             .collect();
         }
 
-        for subspace in params
-            .families
-            .into_iter()
-            .flat_map(|f| f.subspaces())
-            .copied()
+        // Account data is digested before exporting any data, accounts modified
+        // while the backup runs are exported again by the next backup
+        let sequence = parent.as_ref().map_or(0, |parent| parent.sequence + 1);
+        let mut accounts = BTreeMap::new();
+        for (account_id, (digest, last_log)) in account_digests(&self.storage.data)
+            .await
+            .map_err(|err| format!("Failed to digest account data: {err}"))?
         {
+            let state = match parent
+                .as_ref()
+                .and_then(|parent| parent.accounts.get(&account_id))
+            {
+                Some(state) => AccountState {
+                    digest,
+                    last_log,
+                    data: if state.digest == digest {
+                        state.data
+                    } else {
+                        sequence
+                    },
+                    logs_from: if state.last_log <= last_log {
+                        state.logs_from
+                    } else {
+                        sequence
+                    },
+                },
+                None => AccountState {
+                    digest,
+                    last_log,
+                    data: sequence,
+                    logs_from: sequence,
+                },
+            };
+            accounts.insert(account_id, state);
+        }
+        let (data_filter, log_filter) = match &parent {
+            Some(parent) => (
+                KeyFilter::incremental(parent, &accounts, sequence, false),
+                KeyFilter::incremental(parent, &accounts, sequence, true),
+            ),
+            None => (KeyFilter::full(), KeyFilter::full()),
+        };
+        let (data_filter, log_filter) = (Arc::new(data_filter), Arc::new(log_filter));
+        let full_filter = Arc::new(KeyFilter::full());
+        let known_blobs = Arc::new(known_blobs);
+
+        for subspace in params.families.iter().flat_map(|f| f.subspaces()).copied() {
             let (async_handle, sync_handle) = if subspace == SUBSPACE_BLOBS {
-                self.backup_blobs(&params.dest, subspace, schema_version)
+                self.backup_blobs(&params, subspace, schema_version, known_blobs.clone())?
+            } else if subspace == SUBSPACE_LOGS {
+                self.backup_subspace(&params, subspace, schema_version, log_filter.clone())?
+            } else if ACCOUNT_SUBSPACES.contains(&subspace) {
+                self.backup_subspace(&params, subspace, schema_version, data_filter.clone())?
             } else {
-                self.backup_subspace(&params.dest, subspace, schema_version)
+                self.backup_subspace(&params, subspace, schema_version, full_filter.clone())?
             };
-            async_handle.await.failed("Task failed");
-            sync_handles.push(sync_handle);
+            let result = async_handle
+                .await
+                .map_err(|err| format!("Backup task failed: {err}"));
+            sync_handles.extend(sync_handle);
+            if let Err(err) = result.and_then(|result| result) {
+                join_writers(sync_handles)?;
+                return Err(err);
+            }
         }
 
-        for handle in sync_handles {
-            handle.join().expect("Failed to join thread");
+        let mut files = join_writers(sync_handles)?;
+        files.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+
+        let mut families = params
+            .families
+            .iter()
+            .map(|family| family.as_str().to_string())
+            .collect::<Vec<_>>();
+        families.sort_unstable();
+
+        let manifest = BackupManifest {
+            id: store::rand::random(),
+            sequence,
+            parent: parent.as_ref().map(|parent| {
+                BackupParent::new(
+                    &location,
+                    parent.id,
+                    params.parent.as_deref().unwrap(),
+                    &params.dest,
+                )
+            }),
+            schema_version,
+            created_at: now(),
+            families,
+            accounts,
+            files,
+        };
+        manifest.write(&location, &params.dest).await?;
+
+        Ok(manifest)
+    }
+
+    // Writes a full or incremental backup to the configured blob store and
+    // removes the backups no longer covered by the retention policy
+    pub async fn scheduled_backup(
+        &self,
+        settings: &BackupProperties,
+    ) -> Result<ScheduledBackup, String> {
+        let location = BackupLocation::from_settings(settings).await?;
+        let mut index = BackupIndex::read(&location).await?;
+
+        // Start a new chain once it reaches the configured length or after
+        // a schema upgrade, otherwise back up the changes since the latest backup
+        let mut parent = None;
+        if let Some(latest) = index
+            .latest()
+            .filter(|latest| (latest.sequence as u64 + 1) < settings.full_backup_interval)
+        {
+            let path = PathBuf::from(&latest.name);
+            if BackupManifest::read(&location, &path).await?.schema_version
+                == DATABASE_SCHEMA_VERSION
+            {
+                parent = Some(path);
+            }
         }
+
+        let created_at = now();
+        let name = format!("backup-{created_at}");
+        let mut params =
+            BackupParams::new_quiet(PathBuf::from(&name)).with_location(location.clone());
+        if let Some(parent) = parent {
+            params = params.with_parent(parent);
+        }
+        let manifest = self.try_backup(params).await?;
+
+        index.backups.push(IndexedBackup {
+            name: name.clone(),
+            id: manifest.id,
+            sequence: manifest.sequence,
+            parent: manifest
+                .parent
+                .as_ref()
+                .map(|parent| parent.path.to_string_lossy().into_owned()),
+            created_at,
+        });
+        index.write(&location).await?;
+
+        // Remove the expired backups from the index before deleting them, a failed
+        // deletion leaves orphaned objects behind but never a broken chain
+        let retained = index.retained(created_at, settings.retain_daily, settings.retain_weekly);
+        let (kept, expired): (Vec<_>, Vec<_>) = std::mem::take(&mut index.backups)
+            .into_iter()
+            .partition(|backup| retained.contains(&backup.name));
+        index.backups = kept;
+        let mut deleted = Vec::with_capacity(expired.len());
+        if !expired.is_empty() {
+            index.write(&location).await?;
+
+            for backup in expired {
+                let path = PathBuf::from(&backup.name);
+                let mut files = BackupManifest::read(&location, &path)
+                    .await?
+                    .files
+                    .into_iter()
+                    .map(|file| file.name)
+                    .collect::<Vec<_>>();
+                files.push(MANIFEST_FILE.to_string());
+                location.delete(&path, &files).await?;
+                deleted.push(backup.name);
+            }
+        }
+
+        Ok(ScheduledBackup {
+            name,
+            manifest,
+            deleted,
+        })
     }
 
-    fn backup_blobs(&self, dest: &Path, subspace: u8, schema_version: u32) -> TaskHandle {
+    fn backup_blobs(
+        &self,
+        params: &BackupParams,
+        subspace: u8,
+        schema_version: u32,
+        known_blobs: Arc<AHashSet<BlobHash>>,
+    ) -> Result<TaskHandle, String> {
         let store = self.storage.data.clone();
         let blob_store = self.storage.blob.clone();
         let (handle, writer) = spawn_writer(
-            dest.join(format!("subspace_{}", char::from(subspace))),
+            params,
+            format!("subspace_{}", char::from(subspace)),
             subspace,
             schema_version,
-        );
-        (
+            false,
+        )?;
+        let (index_handle, index_writer) = spawn_writer(
+            params,
+            BLOB_INDEX_FILE.to_string(),
+            subspace,
+            schema_version,
+            true,
+        )?;
+        Ok((
             tokio::spawn(async move {
                 let mut blobs = Vec::new();
                 let mut last_hash = BlobHash::default();
@@ -131,7 +386,9 @@ impl Core {
                             .unwrap();
 
                             if last_hash != hash {
-                                blobs.push(hash.clone());
+                                if !known_blobs.contains(&hash) {
+                                    blobs.push(hash.clone());
+                                }
                                 last_hash = hash;
                             }
 
@@ -139,146 +396,390 @@ impl Core {
                         },
                     )
                     .await
-                    .failed("Failed to iterate over data store");
+                    .map_err(|err| format!("Failed to iterate over data store: {err}"))?;
 
                 for hash in blobs {
                     if let Some(blob) = blob_store
                         .get_blob(hash.as_slice(), 0..usize::MAX)
                         .await
-                        .failed("Failed to get blob")
+                        .map_err(|err| format!("Failed to get blob: {err}"))?
                     {
-                        writer
-                            .send((hash.as_slice().to_vec(), blob))
-                            .failed("Failed to send key");
+                        // A closed channel means the writer failed, its error
+                        // is returned when the writer is joined
+                        if index_writer
+                            .send((hash.as_slice().to_vec(), vec![]))
+                            .is_err()
+                            || writer.send((hash.as_slice().to_vec(), blob)).is_err()
+                        {
+                            break;
+                        }
                     }
                 }
+
+                Ok(())
             }),
-            handle,
-        )
+            vec![handle, index_handle],
+        ))
     }
 
-    fn backup_subspace(&self, dest: &Path, subspace: u8, schema_version: u32) -> TaskHandle {
+    fn backup_subspace(
+        &self,
+        params: &BackupParams,
+        subspace: u8,
+        schema_version: u32,
+        filter: Arc<KeyFilter>,
+    ) -> Result<TaskHandle, String> {
         let store = self.storage.data.clone();
         let (handle, writer) = spawn_writer(
-            dest.join(format!("subspace_{}", char::from(subspace))),
+            params,
+            format!("subspace_{}", char::from(subspace)),
             subspace,
             schema_version,
-        );
-        (
+            false,
+        )?;
+        Ok((
             tokio::spawn(async move {
-                if !store.is_sql() || (subspace != SUBSPACE_COUNTER && subspace != SUBSPACE_QUOTA) {
-                    store
-                        .iterate(
-                            IterateParams::new(
-                                AnyKey {
-                                    subspace,
-                                    key: vec![0u8],
-                                },
-                                AnyKey {
-                                    subspace,
-                                    key: vec![u8::MAX; 32],
+                for (from_key, to_key) in &filter.ranges {
+                    if !store.is_sql()
+                        || (subspace != SUBSPACE_COUNTER && subspace != SUBSPACE_QUOTA)
+                    {
+                        store
+                            .iterate(
+                                IterateParams::new(
+                                    AnyKey {
+                                        subspace,
+                                        key: from_key.clone(),
+                                    },
+                                    AnyKey {
+                                        subspace,
+                                        key: to_key.clone(),
+                                    },
+                                )
+                                .set_values(
+                                    ![SUBSPACE_INDEXES, SUBSPACE_REGISTRY_IDX].contains(&subspace),
+                                ),
+                                |key, value| {
+                                    Ok(!filter.matches(subspace, key)
+                                        || writer.send((key.to_vec(), value.to_vec())).is_ok())
                                 },
                             )
-                            .set_values(
-                                ![SUBSPACE_INDEXES, SUBSPACE_REGISTRY_IDX].contains(&subspace),
-                            ),
-                            |key, value| {
-                                writer
-                                    .send((key.to_vec(), value.to_vec()))
-                                    .failed("Failed to send key");
-
-                                Ok(true)
-                            },
-                        )
-                        .await
-                        .failed("Failed to iterate over data store");
-                } else {
-                    let mut keys = Vec::with_capacity(128);
-                    store
-                        .iterate(
-                            IterateParams::new(
-                                AnyKey {
-                                    subspace,
-                                    key: vec![0u8],
-                                },
-                                AnyKey {
-                                    subspace,
-                                    key: vec![u8::MAX; 32],
+                            .await
+                            .map_err(|err| format!("Failed to iterate over data store: {err}"))?;
+                    } else {
+                        let mut keys = Vec::with_capacity(128);
+                        store
+                            .iterate(
+                                IterateParams::new(
+                                    AnyKey {
+                                        subspace,
+                                        key: from_key.clone(),
+                                    },
+                                    AnyKey {
+                                        subspace,
+                                        key: to_key.clone(),
+                                    },
+                                )
+                                .no_values(),
+                                |key, _| {
+                                    if filter.matches(subspace, key) {
+                                        keys.push(key.to_vec());
+                                    }
+
+                                    Ok(true)
                                 },
                             )
-                            .no_values(),
-                            |key, _| {
-                                keys.push(key.to_vec());
-
-                                Ok(true)
-                            },
-                        )
-                        .await
-                        .failed("Failed to iterate over data store");
-
-                    for key in keys {
-                        let counter = store
-                            .get_counter(ValueClass::Any(AnyClass {
-                                subspace,
-                                key: key.clone(),
-                            }))
                             .await
-                            .failed("Failed to get counter");
-                        writer
-                            .send((key.to_vec(), (counter as u64).to_le_bytes().to_vec()))
-                            .failed("Failed to send key");
+                            .map_err(|err| format!("Failed to iterate over data store: {err}"))?;
+
+                        for key in keys {
+                            let counter = store
+                                .get_counter(ValueClass::Any(AnyClass {
+                                    subspace,
+                                    key: key.clone(),
+                                }))
+                                .await
+                                .map_err(|err| format!("Failed to get counter: {err}"))?;
+                            if writer
+                                .send((key.to_vec(), (counter as u64).to_le_bytes().to_vec()))
+                                .is_err()
+                            {
+                                break;
+                            }
+                        }
                     }
                 }
+
+                Ok(())
             }),
-            handle,
-        )
+            vec![handle],
+        ))
+    }
+}
+
+impl KeyFilter {
+    fn full() -> Self {
+        KeyFilter {
+            ranges: vec![(vec![0u8], vec![u8::MAX; 32])],
+            unchanged: AHashSet::new(),
+            logs_after: AHashMap::new(),
+        }
+    }
+
+    fn incremental(
+        parent: &BackupManifest,
+        accounts: &BTreeMap<u32, AccountState>,
+        sequence: u32,
+        logs: bool,
+    ) -> Self {
+        let mut filter = KeyFilter {
+            ranges: Vec::new(),
+            unchanged: AHashSet::new(),
+            logs_after: AHashMap::new(),
+        };
+
+        for (account_id, state) in accounts {
+            if !logs {
+                if state.data != sequence {
+                    filter.unchanged.insert(*account_id);
+                }
+            } else if state.logs_from != sequence
+                && let Some(parent_state) = parent.accounts.get(account_id)
+            {
+                if parent_state.last_log == state.last_log {
+                    filter.unchanged.insert(*account_id);
+                } else {
+                    filter.logs_after.insert(*account_id, parent_state.last_log);
+                }
+            }
+        }
+
+        // Skip the key ranges of unchanged accounts, consecutive accounts are merged
+        // unless a shorter key could sort between them
+        let mut unchanged = filter.unchanged.iter().copied().collect::<Vec<_>>();
+        unchanged.sort_unstable();
+        let mut from_key = vec![0u8];
+        let mut unchanged = unchanged.into_iter().peekable();
+        while let Some(first_id) = unchanged.next() {
+            let mut last_id = first_id;
+            while last_id & 0xff != 0xff
+                && unchanged
+                    .peek()
+                    .is_some_and(|next_id| *next_id == last_id + 1)
+            {
+                last_id = unchanged.next().unwrap();
+            }
+
+            let to_key = first_id.to_be_bytes().to_vec();
+            if from_key < to_key {
+                filter.ranges.push((from_key, to_key));
+            }
+            from_key = last_id.to_be_bytes().to_vec();
+            from_key.extend_from_slice(&[u8::MAX; 32]);
+        }
+        filter.ranges.push((from_key, vec![u8::MAX; 32]));
+
+        filter
+    }
+
+    fn matches(&self, subspace: u8, key: &[u8]) -> bool {
+        let Ok(account_id) = key.deserialize_be_u32(0) else {
+            return true;
+        };
+
+        if self.unchanged.contains(&account_id) {
+            false
+        } else if subspace == SUBSPACE_LOGS
+            && let Some(change_id) = self.logs_after.get(&account_id)
+        {
+            key.deserialize_be_u64(U32_LEN + 1)
+                .is_ok_and(|log_change_id| log_change_id > *change_id)
+        } else {
+            true
+        }
+    }
+}
+
+// Returns a digest of the data and the last changelog entry of every account.
+// Incremental backups compare them with the parent backup instead of the change
+// id, which is not bumped by every write.
+async fn account_digests(store: &Store) -> trc::Result<AHashMap<u32, (u64, u64)>> {
+    let mut accounts: AHashMap<u32, (u64, u64)> = AHashMap::new();
+
+    for subspace in ACCOUNT_SUBSPACES.iter().copied() {
+        let is_sql_counter = subspace == SUBSPACE_COUNTER && store.is_sql();
+        let mut digest = AccountDigest::default();
+        let mut counters = Vec::new();
+        store
+            .iterate(
+                IterateParams::new(
+                    AnyKey {
+                        subspace,
+                        key: vec![0u8],
+                    },
+                    AnyKey {
+                        subspace,
+                        key: vec![u8::MAX; 32],
+                    },
+                )
+                .set_values(
+                    !is_sql_counter && ![SUBSPACE_INDEXES, SUBSPACE_LOGS].contains(&subspace),
+                ),
+                |key, value| {
+                    let Ok(account_id) = key.deserialize_be_u32(0) else {
+                        return Ok(true);
+                    };
+
+                    if subspace == SUBSPACE_LOGS {
+                        if let Ok(change_id) = key.deserialize_be_u64(U32_LEN + 1) {
+                            let (_, last_log) = accounts.entry(account_id).or_default();
+                            *last_log = (*last_log).max(change_id);
+                        }
+                    } else if is_sql_counter {
+                        counters.push((account_id, key.to_vec()));
+                    } else {
+                        digest.update(account_id, key, value);
+                    }
+
+                    Ok(true)
+                },
+            )
+            .await
+            .caused_by(trc::location!())?;
+
+        for (account_id, key) in counters {
+            let counter = store
+                .get_counter(ValueClass::Any(AnyClass {
+                    subspace,
+                    key: key.clone(),
+                }))
+                .await
+                .caused_by(trc::location!())?;
+            digest.update(account_id, &key, &counter.to_le_bytes());
+        }
+
+        for (account_id, subspace_digest) in digest.finish() {
+            let (digest, _) = accounts.entry(account_id).or_default();
+            *digest = xxh3_64_with_seed(&subspace_digest.to_le_bytes(), *digest);
+        }
+    }
+
+    Ok(accounts)
+}
+
+#[derive(Default)]
+struct AccountDigest {
+    current: Option<(u32, Xxh3)>,
+    digests: Vec<(u32, u64)>,
+}
+
+impl AccountDigest {
+    fn update(&mut self, account_id: u32, key: &[u8], value: &[u8]) {
+        if self
+            .current
+            .as_ref()
+            .is_none_or(|(id, _)| *id != account_id)
+        {
+            if let Some((id, hasher)) = self.current.take() {
+                self.digests.push((id, hasher.digest()));
+            }
+            self.current = Some((account_id, Xxh3::new()));
+        }
+
+        let (_, hasher) = self.current.as_mut().unwrap();
+        hasher.update(&(key.len() as u32).to_le_bytes());
+        hasher.update(key);
+        hasher.update(&(value.len() as u32).to_le_bytes());
+        hasher.update(value);
+    }
+
+    fn finish(mut self) -> Vec<(u32, u64)> {
+        if let Some((id, hasher)) = self.current.take() {
+            self.digests.push((id, hasher.digest()));
+        }
+        self.digests
     }
 }
 
 #[allow(clippy::type_complexity)]
 fn spawn_writer(
-    path: PathBuf,
+    params: &BackupParams,
+    name: String,
     subspace: u8,
     version: u32,
-) -> (std::thread::JoinHandle<()>, SyncSender<(Vec<u8>, Vec<u8>)>) {
+    blob_index: bool,
+) -> Result<
+    (
+        std::thread::JoinHandle<Result<BackupFile, String>>,
+        SyncSender<(Vec<u8>, Vec<u8>)>,
+    ),
+    String,
+> {
     let (tx, rx) = mpsc::sync_channel::<(Vec<u8>, Vec<u8>)>(10);
+    let path = params.dest.join(&name);
+    let location = params.location.clone();
+    let verbose = params.verbose;
+    let output = location.writer(&path)?;
 
     let handle = std::thread::spawn(move || {
-        println!("Exporting database to {}.", path.to_str().unwrap());
+        if verbose {
+            println!("Exporting database to {}.", location.display(&path));
+        }
 
-        let mut file = FrameEncoder::new(BufWriter::new(
-            std::fs::File::create(path).failed("Failed to create backup file"),
-        ));
+        let write_err = |err: std::io::Error| format!("Failed to write {path:?}: {err}");
+        let mut file = FrameEncoder::new(ChecksumWriter::new(output));
         file.write_all(&[MAGIC_MARKER, subspace])
-            .failed("Failed to write version");
-        file.write_all(&version.to_le_bytes())
-            .failed("Failed to write version");
+            .map_err(write_err)?;
+        file.write_all(&version.to_le_bytes()).map_err(write_err)?;
 
+        let mut keys = 0;
         while let Ok((key, value)) = rx.recv() {
-            key.len()
-                .to_leb128_writer(&mut file)
-                .failed("Failed to write key value");
-            file.write_all(&key).failed("Failed to write key");
-            value
-                .len()
-                .to_leb128_writer(&mut file)
-                .failed("Failed to write key value");
+            key.len().to_leb128_writer(&mut file).map_err(write_err)?;
+            file.write_all(&key).map_err(write_err)?;
+            value.len().to_leb128_writer(&mut file).map_err(write_err)?;
             if !value.is_empty() {
-                file.write_all(&value).failed("Failed to write key value");
+                file.write_all(&value).map_err(write_err)?;
             }
+            keys += 1;
         }
 
-        file.flush().failed("Failed to flush backup file");
+        let (size, sha256) = file
+            .finish()
+            .map_err(|err| format!("Failed to flush {path:?}: {err}"))?
+            .finalize()
+            .map_err(write_err)?;
+
+        Ok(BackupFile {
+            name,
+            subspace,
+            blob_index,
+            keys,
+            size,
+            sha256,
+        })
     });
 
-    (handle, tx)
+    Ok((handle, tx))
+}
+
+fn join_writers(
+    handles: Vec<std::thread::JoinHandle<Result<BackupFile, String>>>,
+) -> Result<Vec<BackupFile>, String> {
+    let mut files = Vec::with_capacity(handles.len());
+    for handle in handles {
+        files.push(
+            handle
+                .join()
+                .map_err(|_| "Backup writer panicked".to_string())??,
+        );
+    }
+    Ok(files)
 }
 
 impl BackupParams {
     pub fn new(dest: PathBuf) -> Self {
         let mut params = Self {
-            dest,
-            families: AHashSet::new(),
+            verbose: true,
+            ..Self::new_quiet(dest)
         };
 
         if let Ok(families) = std::env::var("EXPORT_TYPES") {
@@ -288,6 +789,26 @@ impl BackupParams {
         params
     }
 
+    // Parameters for backups taken by the server, which do not print progress
+    // or read the export environment variables
+    pub fn new_quiet(dest: PathBuf) -> Self {
+        Self {
+            dest,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_location(mut self, location: BackupLocation) -> Self {
+        self.location = location;
+        self
+    }
+
+    // Only exports the changes made since the backup at `parent`
+    pub fn with_parent(mut self, parent: PathBuf) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+
     fn parse_families(&mut self, families: &str) {
         for family in families.split(',') {
             let family = family.trim();
@@ -329,6 +850,19 @@ impl Family {
         }
     }
 
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Family::Data => "data",
+            Family::Registry => "registry",
+            Family::Blob => "blob",
+            Family::Changelog => "changelog",
+            Family::Queue => "queue",
+            Family::Report => "report",
+            Family::Telemetry => "telemetry",
+            Family::Tasks => "tasks",
+        }
+    }
+
     pub fn parse(family: &str) -> Result<Self, String> {
         match family {
             "data" => Ok(Family::Data),
@@ -4,7 +4,13 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use super::{backup::BackupParams, console::store_console};
+use super::{
+    backup::BackupParams,
+    console::store_console,
+    location::BackupLocation,
+    manifest::{BackupIndex, verify_backup},
+    restore::RestoreParams,
+};
 use crate::{
     BuildServer, Caches, Core, Data, IPC_CHANNEL_BUFFER, Inner, Ipc,
     config::{
@@ -14,6 +20,7 @@ use crate::{
     manager::defaults::BootstrapDefaults,
 };
 use arc_swap::ArcSwap;
+use registry::schema::structs::{Backup, BackupProperties};
 use std::{
     net::{IpAddr, Ipv4Addr},
     path::PathBuf,
@@ -47,25 +54,38 @@ Usage: stalwart [OPTIONS]
 Options:
   -c, --config <PATH>              Start server with the specified configuration file
   -e, --export <PATH>              Export all store data to a specific path
+      --incremental <PATH>         Only export the changes made since the backup at <PATH>
   -i, --import <PATH>              Import store data from a specific path
+      --sequence <NUMBER>          Import the backup chain up to the given sequence
+  -v, --verify <PATH>              Verify the integrity of a backup without importing it
   -o, --console                    Open the store console
   -h, --help                       Print help
   -V, --version                    Print version
+
+Paths starting with 'store:' refer to the configured backup destination, for
+example 'store:backup-1700000000'. Use 'store:' alone to export a new backup
+or to import and verify the latest one.
 "#
 );
 
-#[derive(PartialEq, Eq)]
 enum StoreOp {
     Export(BackupParams),
-    Import(PathBuf),
+    Import(RestoreParams),
+    ExportRemote,
+    ImportRemote(String, Option<u32>),
+    VerifyRemote(String),
     Console,
     None,
 }
 
+const REMOTE_PREFIX: &str = "store:";
+
 impl BootManager {
     pub async fn init() -> Self {
         let mut config_path = std::env::var("CONFIG_PATH").ok();
         let mut import_export = StoreOp::None;
+        let mut backup_parent = None;
+        let mut backup_sequence = None;
 
         if config_path.is_none() {
             let mut args = std::env::args().skip(1);
@@ -94,10 +114,47 @@ impl BootManager {
                         config_path = Some(value);
                     }
                     ("export" | "e", Some(value)) => {
-                        import_export = StoreOp::Export(BackupParams::new(value.into()));
+                        import_export = if value.starts_with(REMOTE_PREFIX) {
+                            StoreOp::ExportRemote
+                        } else {
+                            StoreOp::Export(BackupParams::new(value.into()))
+                        };
                     }
                     ("import" | "i", Some(value)) => {
-                        import_export = StoreOp::Import(value.into());
+                        import_export = if let Some(name) = value.strip_prefix(REMOTE_PREFIX) {
+                            StoreOp::ImportRemote(name.to_string(), None)
+                        } else {
+                            StoreOp::Import(RestoreParams::new(value.into()))
+                        };
+                    }
+                    ("verify" | "v", Some(value)) => {
+                        if let Some(name) = value.strip_prefix(REMOTE_PREFIX) {
+                            import_export = StoreOp::VerifyRemote(name.to_string());
+                        } else {
+                            match verify_backup(&BackupLocation::Local, &PathBuf::from(&value))
+                                .await
+                            {
+                                Ok(report) => {
+                                    println!(
+                                        "Verified {} backups, {} files, {} keys and {} bytes.",
+                                        report.backups, report.files, report.keys, report.bytes
+                                    );
+                                    std::process::exit(0);
+                                }
+                                Err(err) => {
+                                    eprintln!("Backup verification failed: {err}");
+                                    std::process::exit(1);
+                                }
+                            }
+                        }
+                    }
+                    ("incremental", Some(value)) => {
+                        backup_parent = Some(PathBuf::from(value));
+                    }
+                    ("sequence", Some(value)) => {
+                        backup_sequence = Some(value.parse::<u32>().unwrap_or_else(|_| {
+                            failed(&format!("Invalid backup sequence '{value}', try '--help'."))
+                        }));
                     }
                     ("console" | "o", None) => {
                         import_export = StoreOp::Console;
@@ -111,8 +168,22 @@ impl BootManager {
                 }
             }
 
+            if let Some(parent) = backup_parent {
+                import_export = match import_export {
+                    StoreOp::Export(params) => StoreOp::Export(params.with_parent(parent)),
+                    _ => failed("Argument '--incremental' requires '--export' to a local path."),
+                };
+            }
+            if let Some(sequence) = backup_sequence {
+                import_export = match import_export {
+                    StoreOp::Import(params) => StoreOp::Import(params.with_sequence(sequence)),
+                    StoreOp::ImportRemote(name, _) => StoreOp::ImportRemote(name, Some(sequence)),
+                    _ => failed("Argument '--sequence' requires '--import'."),
+                };
+            }
+
             if config_path.is_none() {
-                if import_export == StoreOp::None {
+                if matches!(import_export, StoreOp::None) {
                     eprintln!("{HELP}");
                 } else {
                     eprintln!("Missing '--config' argument for import/export.")
@@ -128,7 +199,7 @@ impl BootManager {
         let mut bootstrap = Bootstrap::new(registry).await;
 
         // Add safe defaults if missing
-        if import_export == StoreOp::None {
+        if matches!(import_export, StoreOp::None) {
             bootstrap.insert_safe_defaults().await;
         }
 
@@ -263,6 +334,64 @@ impl BootManager {
                     .await;
                 std::process::exit(0);
             }
+            StoreOp::ExportRemote => {
+                // Enable telemetry
+                telemetry.enable(false);
+
+                // Parse settings and backup to the configured destination
+                let settings = backup_settings(&mut bootstrap).await;
+                match Box::pin(Core::parse(&mut bootstrap, storage))
+                    .await
+                    .scheduled_backup(&settings)
+                    .await
+                {
+                    Ok(backup) => {
+                        println!(
+                            "Backup {} with sequence {} written to {REMOTE_PREFIX}{}.",
+                            backup.manifest.id, backup.manifest.sequence, backup.name
+                        );
+                        for name in backup.deleted {
+                            println!("Expired backup {REMOTE_PREFIX}{name} deleted.");
+                        }
+                    }
+                    Err(err) => failed(&format!("Backup failed: {err}")),
+                }
+                std::process::exit(0);
+            }
+            StoreOp::ImportRemote(name, sequence) => {
+                // Enable telemetry
+                telemetry.enable(false);
+
+                // Parse settings and restore from the configured destination
+                let settings = backup_settings(&mut bootstrap).await;
+                let (location, name) = remote_backup(&settings, name).await;
+                let mut params = RestoreParams::new(name.into()).with_location(location);
+                if let Some(sequence) = sequence {
+                    params = params.with_sequence(sequence);
+                }
+                Box::pin(Core::parse(&mut bootstrap, storage))
+                    .await
+                    .restore(params)
+                    .await;
+                std::process::exit(0);
+            }
+            StoreOp::VerifyRemote(name) => {
+                let settings = backup_settings(&mut bootstrap).await;
+                let (location, name) = remote_backup(&settings, name).await;
+                match verify_backup(&location, &PathBuf::from(&name)).await {
+                    Ok(report) => {
+                        println!(
+                            "Verified {} backups, {} files, {} keys and {} bytes.",
+                            report.backups, report.files, report.keys, report.bytes
+                        );
+                        std::process::exit(0);
+                    }
+                    Err(err) => {
+                        eprintln!("Backup verification failed: {err}");
+                        std::process::exit(1);
+                    }
+                }
+            }
             StoreOp::Console => {
                 // Store console
                 store_console(
@@ -278,6 +407,31 @@ impl BootManager {
     }
 }
 
+async fn backup_settings(bootstrap: &mut Bootstrap) -> BackupProperties {
+    match bootstrap.setting_infallible::<Backup>().await {
+        Backup::Enabled(settings) => settings,
+        Backup::Disabled => failed("No backup destination is configured."),
+    }
+}
+
+// Resolves a backup name at the configured destination, an empty name refers to the latest backup
+async fn remote_backup(settings: &BackupProperties, name: String) -> (BackupLocation, String) {
+    let location = BackupLocation::from_settings(settings)
+        .await
+        .unwrap_or_else(|err| failed(&err));
+    if !name.is_empty() {
+        return (location, name);
+    }
+
+    let name = BackupIndex::read(&location)
+        .await
+        .unwrap_or_else(|err| failed(&err))
+        .latest()
+        .map(|backup| backup.name.clone())
+        .unwrap_or_else(|| failed("No backups found at the configured destination."));
+    (location, name)
+}
+
 pub fn build_ipc(has_pubsub: bool) -> (Ipc, IpcReceivers) {
     // Build ipc receivers
     let (push_tx, push_rx) = mpsc::channel(IPC_CHANNEL_BUFFER);
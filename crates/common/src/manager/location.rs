@@ -0,0 +1,316 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use registry::schema::structs::BackupProperties;
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, ErrorKind, Read, Write},
+    path::Path,
+    sync::Arc,
+};
+use store::{BlobStore, dispatch::encryption::SecretCipher};
+use tokio::runtime::Handle;
+
+// Files written to a blob store are split in chunks stored as separate blobs,
+// each chunk is encrypted on its own when an encryption key is configured
+const CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
+#[derive(Clone, Default)]
+pub enum BackupLocation {
+    #[default]
+    Local,
+    Remote(Arc<RemoteLocation>),
+}
+
+pub struct RemoteLocation {
+    store: BlobStore,
+    prefix: String,
+    cipher: Option<SecretCipher>,
+    handle: Handle,
+}
+
+struct ChunkReader {
+    remote: Arc<RemoteLocation>,
+    path: String,
+    chunk: usize,
+    buf: Vec<u8>,
+    pos: usize,
+    eof: bool,
+}
+
+struct ChunkWriter {
+    remote: Arc<RemoteLocation>,
+    path: String,
+    chunk: usize,
+    buf: Vec<u8>,
+}
+
+impl BackupLocation {
+    pub fn remote(store: BlobStore, prefix: impl Into<String>, secret: Option<&str>) -> Self {
+        BackupLocation::Remote(Arc::new(RemoteLocation {
+            store,
+            prefix: prefix.into(),
+            cipher: secret.map(SecretCipher::new),
+            handle: Handle::current(),
+        }))
+    }
+
+    pub async fn from_settings(settings: &BackupProperties) -> Result<Self, String> {
+        let store = BlobStore::open(settings.destination.clone()).await?;
+        let secret = settings
+            .encryption_key
+            .secret()
+            .await
+            .map_err(|err| format!("Failed to obtain backup encryption key: {err}"))?;
+
+        Ok(Self::remote(
+            store,
+            settings.prefix.clone(),
+            secret.as_deref(),
+        ))
+    }
+
+    pub fn is_remote(&self) -> bool {
+        matches!(self, BackupLocation::Remote(_))
+    }
+
+    pub fn display(&self, path: &Path) -> String {
+        match self {
+            BackupLocation::Local => path.display().to_string(),
+            BackupLocation::Remote(remote) => format!("{}{}", remote.prefix, remote_path(path)),
+        }
+    }
+
+    // Reads a small file such as a manifest, returns None if it does not exist
+    pub async fn read(&self, path: &Path) -> Result<Option<Vec<u8>>, String> {
+        match self {
+            BackupLocation::Local => match std::fs::read(path) {
+                Ok(bytes) => Ok(Some(bytes)),
+                Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+                Err(err) => Err(format!("Failed to read {path:?}: {err}")),
+            },
+            BackupLocation::Remote(remote) => {
+                let path = remote_path(path);
+                let mut bytes = Vec::new();
+                let mut chunk = 0;
+                while let Some(data) = remote.get_chunk(&path, chunk).await? {
+                    bytes.extend_from_slice(&data);
+                    chunk += 1;
+                }
+                Ok((chunk > 0).then_some(bytes))
+            }
+        }
+    }
+
+    pub async fn write(&self, path: &Path, bytes: &[u8]) -> Result<(), String> {
+        match self {
+            BackupLocation::Local => std::fs::write(path, bytes)
+                .map_err(|err| format!("Failed to write {path:?}: {err}")),
+            BackupLocation::Remote(remote) => {
+                let path = remote_path(path);
+                let mut chunks = 0;
+                for (chunk, data) in bytes.chunks(CHUNK_SIZE).enumerate() {
+                    remote.put_chunk(&path, chunk, data).await?;
+                    chunks += 1;
+                }
+
+                // Remove the trailing chunks of a previous, larger version
+                remote.delete_chunks(&path, chunks).await
+            }
+        }
+    }
+
+    pub async fn create_dir(&self, path: &Path) -> Result<(), String> {
+        match self {
+            BackupLocation::Local if !path.exists() => std::fs::create_dir_all(path)
+                .map_err(|err| format!("Failed to create backup directory {path:?}: {err}")),
+            BackupLocation::Local if !path.is_dir() => {
+                Err(format!("Backup destination {path:?} is not a directory."))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // Removes a backup and the files listed in its manifest
+    pub async fn delete(&self, path: &Path, files: &[String]) -> Result<(), String> {
+        match self {
+            BackupLocation::Local => std::fs::remove_dir_all(path)
+                .map_err(|err| format!("Failed to delete {path:?}: {err}")),
+            BackupLocation::Remote(remote) => {
+                for file in files {
+                    remote
+                        .delete_chunks(&remote_path(&path.join(file)), 0)
+                        .await?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    // Readers and writers block on the runtime when the location is remote,
+    // they must be used outside of async tasks
+    pub fn reader(&self, path: &Path) -> Result<Box<dyn Read + Send>, String> {
+        match self {
+            BackupLocation::Local => Ok(Box::new(BufReader::new(
+                File::open(path).map_err(|err| format!("Failed to open {path:?}: {err}"))?,
+            ))),
+            BackupLocation::Remote(remote) => Ok(Box::new(ChunkReader {
+                remote: remote.clone(),
+                path: remote_path(path),
+                chunk: 0,
+                buf: Vec::new(),
+                pos: 0,
+                eof: false,
+            })),
+        }
+    }
+
+    pub fn writer(&self, path: &Path) -> Result<Box<dyn Write + Send>, String> {
+        match self {
+            BackupLocation::Local => Ok(Box::new(BufWriter::new(
+                File::create(path).map_err(|err| format!("Failed to create {path:?}: {err}"))?,
+            ))),
+            BackupLocation::Remote(remote) => Ok(Box::new(ChunkWriter {
+                remote: remote.clone(),
+                path: remote_path(path),
+                chunk: 0,
+                buf: Vec::with_capacity(CHUNK_SIZE),
+            })),
+        }
+    }
+}
+
+impl RemoteLocation {
+    async fn get_chunk(&self, path: &str, chunk: usize) -> Result<Option<Vec<u8>>, String> {
+        let key = self.chunk_key(path, chunk);
+        let Some(data) = self
+            .store
+            .get_stored_blob(key.as_bytes())
+            .await
+            .map_err(|err| format!("Failed to read {key}: {err}"))?
+        else {
+            return Ok(None);
+        };
+
+        match &self.cipher {
+            Some(cipher) => cipher
+                .decrypt(key.as_bytes(), &data)
+                .map(Some)
+                .map_err(|err| format!("Failed to decrypt {key}: {err}")),
+            None => Ok(Some(data)),
+        }
+    }
+
+    async fn put_chunk(&self, path: &str, chunk: usize, data: &[u8]) -> Result<(), String> {
+        let key = self.chunk_key(path, chunk);
+        let data = match &self.cipher {
+            Some(cipher) => cipher
+                .encrypt(key.as_bytes(), data)
+                .map_err(|err| format!("Failed to encrypt {key}: {err}"))?,
+            None => data.to_vec(),
+        };
+
+        self.store
+            .put_stored_blob(key.as_bytes(), &data)
+            .await
+            .map_err(|err| format!("Failed to write {key}: {err}"))
+    }
+
+    async fn delete_chunks(&self, path: &str, from_chunk: usize) -> Result<(), String> {
+        for chunk in from_chunk.. {
+            let key = self.chunk_key(path, chunk);
+            if !self
+                .store
+                .delete_stored_blob(key.as_bytes())
+                .await
+                .map_err(|err| format!("Failed to delete {key}: {err}"))?
+            {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn chunk_key(&self, path: &str, chunk: usize) -> String {
+        format!("{}{path}.{chunk:06}", self.prefix)
+    }
+}
+
+impl Read for ChunkReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.pos == self.buf.len() {
+            if self.eof {
+                return Ok(0);
+            }
+
+            match self
+                .remote
+                .handle
+                .block_on(self.remote.get_chunk(&self.path, self.chunk))
+                .map_err(io::Error::other)?
+            {
+                Some(data) => {
+                    self.buf = data;
+                    self.pos = 0;
+                    self.chunk += 1;
+                }
+                None if self.chunk == 0 => {
+                    return Err(io::Error::new(
+                        ErrorKind::NotFound,
+                        format!("{}{} not found", self.remote.prefix, self.path),
+                    ));
+                }
+                None => {
+                    self.eof = true;
+                }
+            }
+        }
+
+        let len = buf.len().min(self.buf.len() - self.pos);
+        buf[..len].copy_from_slice(&self.buf[self.pos..self.pos + len]);
+        self.pos += len;
+        Ok(len)
+    }
+}
+
+impl ChunkWriter {
+    fn put_chunk(&mut self, len: usize) -> io::Result<()> {
+        self.remote
+            .handle
+            .block_on(
+                self.remote
+                    .put_chunk(&self.path, self.chunk, &self.buf[..len]),
+            )
+            .map_err(io::Error::other)?;
+        self.buf.drain(..len);
+        self.chunk += 1;
+        Ok(())
+    }
+}
+
+impl Write for ChunkWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buf.extend_from_slice(buf);
+        while self.buf.len() >= CHUNK_SIZE {
+            self.put_chunk(CHUNK_SIZE)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.buf.is_empty() {
+            self.put_chunk(self.buf.len())?;
+        }
+        Ok(())
+    }
+}
+
+fn remote_path(path: &Path) -> String {
+    path.iter()
+        .map(|component| component.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
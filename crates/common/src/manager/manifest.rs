@@ -0,0 +1,392 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{location::BackupLocation, restore::KeyValueReader};
+use ahash::AHashSet;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::BTreeMap,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+};
+use store::{SUBSPACE_BLOBS, SUBSPACE_COUNTER, SUBSPACE_INDEXES, SUBSPACE_LOGS, SUBSPACE_PROPERTY};
+use types::blob_hash::BlobHash;
+use utils::HexEncode;
+
+pub const MANIFEST_FILE: &str = "manifest.json";
+pub const INDEX_FILE: &str = "index.json";
+pub(super) const BLOB_INDEX_FILE: &str = "blob_index";
+
+// Subspaces whose keys start with an account id. Incremental backups only
+// export these keys for accounts whose data changed since the parent backup.
+pub(super) const ACCOUNT_SUBSPACES: &[u8] = &[
+    SUBSPACE_PROPERTY,
+    SUBSPACE_INDEXES,
+    SUBSPACE_COUNTER,
+    SUBSPACE_LOGS,
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub id: u64,
+    pub sequence: u32,
+    pub parent: Option<BackupParent>,
+    pub schema_version: u32,
+    pub created_at: u64,
+    pub families: Vec<String>,
+    pub accounts: BTreeMap<u32, AccountState>,
+    pub files: Vec<BackupFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupParent {
+    pub id: u64,
+    pub path: PathBuf,
+}
+
+// Digest of the account data and its last changelog entry, followed by the
+// sequence of the backup holding the latest copy of the account data, and of
+// the first backup holding its changelog
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountState {
+    pub digest: u64,
+    pub last_log: u64,
+    pub data: u32,
+    pub logs_from: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupFile {
+    pub name: String,
+    pub subspace: u8,
+    #[serde(default)]
+    pub blob_index: bool,
+    pub keys: u64,
+    pub size: u64,
+    pub sha256: String,
+}
+
+// Backups written by the scheduler, oldest first
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupIndex {
+    pub backups: Vec<IndexedBackup>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexedBackup {
+    pub name: String,
+    pub id: u64,
+    pub sequence: u32,
+    pub parent: Option<String>,
+    pub created_at: u64,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct VerifyReport {
+    pub backups: usize,
+    pub files: usize,
+    pub keys: u64,
+    pub bytes: u64,
+}
+
+impl BackupManifest {
+    pub async fn read(location: &BackupLocation, path: &Path) -> Result<Self, String> {
+        let manifest_path = path.join(MANIFEST_FILE);
+        let bytes = location.read(&manifest_path).await?.ok_or_else(|| {
+            format!(
+                "Backup manifest {} not found",
+                location.display(&manifest_path)
+            )
+        })?;
+        serde_json::from_slice(&bytes)
+            .map_err(|err| format!("Failed to parse {manifest_path:?}: {err}"))
+    }
+
+    pub async fn write(&self, location: &BackupLocation, path: &Path) -> Result<(), String> {
+        let bytes = serde_json::to_vec_pretty(self)
+            .map_err(|err| format!("Failed to serialize manifest: {err}"))?;
+        location.write(&path.join(MANIFEST_FILE), &bytes).await
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.parent.is_none()
+    }
+
+    pub(super) fn parent_path(&self, path: &Path) -> Option<PathBuf> {
+        self.parent.as_ref().map(|parent| {
+            if parent.path.is_absolute() {
+                parent.path.clone()
+            } else {
+                path.parent()
+                    .unwrap_or_else(|| Path::new("."))
+                    .join(&parent.path)
+            }
+        })
+    }
+}
+
+impl BackupParent {
+    // Parents stored next to the backup are referenced by name so that
+    // backup sets can be moved as a whole
+    pub(super) fn new(location: &BackupLocation, id: u64, parent_path: &Path, dest: &Path) -> Self {
+        if location.is_remote() {
+            return BackupParent {
+                id,
+                path: parent_path.to_path_buf(),
+            };
+        }
+
+        let parent_path = parent_path
+            .canonicalize()
+            .unwrap_or_else(|_| parent_path.to_path_buf());
+        let dest_dir = dest
+            .canonicalize()
+            .ok()
+            .and_then(|dest| dest.parent().map(Path::to_path_buf));
+        let path = match (parent_path.parent(), parent_path.file_name()) {
+            (Some(dir), Some(name)) if dest_dir.as_deref() == Some(dir) => PathBuf::from(name),
+            _ => parent_path,
+        };
+
+        BackupParent { id, path }
+    }
+}
+
+// Loads the manifests from the full backup up to the backup at `path`
+pub async fn load_chain(
+    location: &BackupLocation,
+    path: &Path,
+) -> Result<Vec<(PathBuf, BackupManifest)>, String> {
+    let mut chain: Vec<(PathBuf, BackupManifest)> = Vec::new();
+    let mut path = path.to_path_buf();
+
+    loop {
+        let manifest = BackupManifest::read(location, &path).await?;
+        if let Some((_, child)) = chain.last() {
+            if child.parent.as_ref().map(|parent| parent.id) != Some(manifest.id) {
+                return Err(format!(
+                    "Backup {path:?} is not the parent of backup {}",
+                    child.id
+                ));
+            } else if child.sequence != manifest.sequence + 1 {
+                return Err(format!(
+                    "Backup {path:?} has sequence {}, expected {}",
+                    manifest.sequence,
+                    child.sequence.saturating_sub(1)
+                ));
+            } else if child.schema_version != manifest.schema_version {
+                return Err(format!(
+                    "Backup {path:?} has a different schema version than its children"
+                ));
+            }
+        }
+
+        match manifest.parent_path(&path) {
+            Some(parent_path) => {
+                chain.push((path, manifest));
+                path = parent_path;
+            }
+            None if manifest.sequence == 0 => {
+                chain.push((path, manifest));
+                break;
+            }
+            None => {
+                return Err(format!(
+                    "Backup {path:?} has sequence {} but no parent",
+                    manifest.sequence
+                ));
+            }
+        }
+    }
+
+    chain.reverse();
+    Ok(chain)
+}
+
+// Verifies the checksums and contents of a backup and all its parents
+pub async fn verify_backup(location: &BackupLocation, path: &Path) -> Result<VerifyReport, String> {
+    let chain = load_chain(location, path).await?;
+    let mut report = VerifyReport {
+        backups: chain.len(),
+        ..Default::default()
+    };
+
+    for (path, manifest) in chain {
+        for file in manifest.files {
+            let file_path = path.join(&file.name);
+            println!("Verifying {}.", location.display(&file_path));
+
+            let location = location.clone();
+            let (keys, size) =
+                tokio::task::spawn_blocking(move || verify_file(&location, &file_path, &file))
+                    .await
+                    .map_err(|err| format!("Verification task failed: {err}"))??;
+
+            report.files += 1;
+            report.keys += keys;
+            report.bytes += size;
+        }
+    }
+
+    Ok(report)
+}
+
+fn verify_file(
+    location: &BackupLocation,
+    file_path: &Path,
+    file: &BackupFile,
+) -> Result<(u64, u64), String> {
+    // Verify the checksum of the stored file
+    let mut reader = ChecksumReader::new(location.reader(file_path)?);
+    io::copy(&mut reader, &mut io::sink())
+        .map_err(|err| format!("Failed to read {file_path:?}: {err}"))?;
+    let (size, sha256) = reader.finalize();
+    if size != file.size || sha256 != file.sha256 {
+        return Err(format!("Checksum mismatch in {file_path:?}"));
+    }
+
+    // Verify the contents
+    let mut reader = KeyValueReader::try_new(location, file_path)?;
+    if reader.subspace != file.subspace {
+        return Err(format!("Unexpected subspace in {file_path:?}"));
+    }
+    let mut keys = 0;
+    while let Some((key, value)) = reader.try_next()? {
+        if file.subspace == SUBSPACE_BLOBS
+            && !file.blob_index
+            && BlobHash::generate(&value).as_slice() != key.as_slice()
+        {
+            return Err(format!("Blob hash mismatch in {file_path:?}"));
+        }
+        keys += 1;
+    }
+    if keys != file.keys {
+        return Err(format!(
+            "Expected {} keys in {file_path:?}, found {keys}",
+            file.keys
+        ));
+    }
+
+    Ok((keys, size))
+}
+
+impl BackupIndex {
+    pub async fn read(location: &BackupLocation) -> Result<Self, String> {
+        match location.read(Path::new(INDEX_FILE)).await? {
+            Some(bytes) => serde_json::from_slice(&bytes)
+                .map_err(|err| format!("Failed to parse backup index: {err}")),
+            None => Ok(BackupIndex::default()),
+        }
+    }
+
+    pub async fn write(&self, location: &BackupLocation) -> Result<(), String> {
+        let bytes = serde_json::to_vec_pretty(self)
+            .map_err(|err| format!("Failed to serialize backup index: {err}"))?;
+        location.write(Path::new(INDEX_FILE), &bytes).await
+    }
+
+    pub fn latest(&self) -> Option<&IndexedBackup> {
+        self.backups.last()
+    }
+
+    // Keeps the latest backup, the newest backup of each of the last `daily` days
+    // and `weekly` weeks, and every backup these depend on
+    pub fn retained(&self, now: u64, daily: u64, weekly: u64) -> AHashSet<String> {
+        const DAY: u64 = 86400;
+        const WEEK: u64 = 7 * DAY;
+
+        let mut retained = AHashSet::new();
+        let mut days = AHashSet::new();
+        let mut weeks = AHashSet::new();
+
+        for (pos, backup) in self.backups.iter().enumerate().rev() {
+            let day = backup.created_at / DAY;
+            let week = backup.created_at / WEEK;
+            let is_daily = day + daily > now / DAY && days.insert(day);
+            let is_weekly = week + weekly > now / WEEK && weeks.insert(week);
+
+            if is_daily || is_weekly || pos == self.backups.len() - 1 {
+                let mut name = Some(&backup.name);
+                while let Some(backup_name) = name {
+                    if !retained.insert(backup_name.clone()) {
+                        break;
+                    }
+                    name = self
+                        .backups
+                        .iter()
+                        .find(|backup| &backup.name == backup_name)
+                        .and_then(|backup| backup.parent.as_ref());
+                }
+            }
+        }
+
+        retained
+    }
+}
+
+pub(super) struct ChecksumWriter<W: Write> {
+    inner: W,
+    hasher: Sha256,
+    size: u64,
+}
+
+pub(super) struct ChecksumReader<R: Read> {
+    inner: R,
+    hasher: Sha256,
+    size: u64,
+}
+
+impl<W: Write> ChecksumWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            hasher: Sha256::new(),
+            size: 0,
+        }
+    }
+
+    pub fn finalize(mut self) -> io::Result<(u64, String)> {
+        self.inner.flush()?;
+        Ok((self.size, self.hasher.finalize().hex_encode()))
+    }
+}
+
+impl<W: Write> Write for ChecksumWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<R: Read> ChecksumReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            hasher: Sha256::new(),
+            size: 0,
+        }
+    }
+
+    pub fn finalize(self) -> (u64, String) {
+        (self.size, self.hasher.finalize().hex_encode())
+    }
+}
+
+impl<R: Read> Read for ChecksumReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.hasher.update(&buf[..read]);
+        self.size += read as u64;
+        Ok(read)
+    }
+}
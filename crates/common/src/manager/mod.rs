@@ -18,6 +18,8 @@ pub mod backup;
 pub mod boot;
 pub mod console;
 pub mod defaults;
+pub mod location;
+pub mod manifest;
 pub mod restore;
 
 pub const SPAM_TRAINER_KEY: &[u8] = "STALWART_SPAM_TRAIN_DATA.lz4".as_bytes();
@@ -4,27 +4,47 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use super::backup::MAGIC_MARKER;
+use super::{
+    backup::MAGIC_MARKER,
+    location::BackupLocation,
+    manifest::{ACCOUNT_SUBSPACES, BackupManifest, MANIFEST_FILE, load_chain},
+};
 use crate::{Core, DATABASE_SCHEMA_VERSION};
+use ahash::AHashMap;
 use lz4_flex::frame::FrameDecoder;
 use registry::schema::enums::CompressionAlgo;
 use std::{
-    fs::File,
-    io::{BufReader, ErrorKind, Read},
+    io::{ErrorKind, Read},
     path::{Path, PathBuf},
+    sync::Arc,
 };
 use store::{
-    BlobStore, IterateParams, SUBSPACE_BLOBS, SUBSPACE_COUNTER, SUBSPACE_INDEXES, SUBSPACE_QUOTA,
-    Store, U32_LEN,
+    BlobStore, IterateParams, SUBSPACE_BLOBS, SUBSPACE_COUNTER, SUBSPACE_INDEXES, SUBSPACE_LOGS,
+    SUBSPACE_QUOTA, Store, U32_LEN,
     write::{AnyClass, AnyKey, BatchBuilder, ValueClass, key::DeserializeBigEndian},
 };
+use tokio::sync::mpsc;
 use types::{collection::Collection, field::Field};
 use utils::{UnwrapFailure, failed};
 
+#[derive(Default)]
+pub struct RestoreParams {
+    src: PathBuf,
+    sequence: Option<u32>,
+    location: BackupLocation,
+}
+
+type KeyValueStream = mpsc::Receiver<Result<(Vec<u8>, Vec<u8>), String>>;
+
 impl Core {
-    pub async fn restore(&self, src: PathBuf) {
+    pub async fn restore(&self, params: RestoreParams) {
+        let src = params.src;
+
         // Backup the core
-        if src.is_dir() {
+        if params.location.is_remote() || src.join(MANIFEST_FILE).is_file() {
+            self.restore_chain(&params.location, &src, params.sequence)
+                .await;
+        } else if src.is_dir() {
             // Iterate directory and spawn a task for each file
             let mut tasks = Vec::new();
             for entry in std::fs::read_dir(&src).failed("Failed to read directory") {
@@ -46,6 +66,102 @@ impl Core {
             restore_file(self.storage.data.clone(), self.storage.blob.clone(), &src).await;
         }
     }
+
+    // Restores a backup by replaying its chain of parents, account data is
+    // restored from the last backup that exported it
+    async fn restore_chain(&self, location: &BackupLocation, src: &Path, sequence: Option<u32>) {
+        let mut chain = load_chain(location, src)
+            .await
+            .unwrap_or_else(|err| failed(&err));
+        if let Some(sequence) = sequence {
+            let pos = chain
+                .iter()
+                .position(|(_, manifest)| manifest.sequence == sequence)
+                .unwrap_or_else(|| {
+                    failed(&format!(
+                        "Backup sequence {sequence} not found in {}",
+                        location.display(src)
+                    ))
+                });
+            chain.truncate(pos + 1);
+        }
+        let target = Arc::new(chain.last().unwrap().1.clone());
+        println!(
+            "Restoring backup {} with sequence {}.",
+            target.id, target.sequence
+        );
+
+        let mut subspaces: AHashMap<u8, Vec<(PathBuf, u32)>> = AHashMap::new();
+        for (path, manifest) in &chain {
+            for file in &manifest.files {
+                if !file.blob_index {
+                    subspaces
+                        .entry(file.subspace)
+                        .or_default()
+                        .push((path.join(&file.name), manifest.sequence));
+                }
+            }
+        }
+
+        let mut tasks = Vec::new();
+        for (subspace, files) in subspaces {
+            let store = self.storage.data.clone();
+            let blob_store = self.storage.blob.clone();
+            let target = target.clone();
+            let location = location.clone();
+            tasks.push(tokio::spawn(async move {
+                assert_subspace_is_empty(&store, subspace, &location.display(&files[0].0)).await;
+
+                for (path, sequence) in files {
+                    if subspace == SUBSPACE_BLOBS {
+                        import_file(&store, &blob_store, &location, &path, |_| true).await;
+                    } else if ACCOUNT_SUBSPACES.contains(&subspace) {
+                        import_file(&store, &blob_store, &location, &path, |key| {
+                            restore_account_key(&target, subspace, sequence, key)
+                        })
+                        .await;
+                    } else if sequence == target.sequence {
+                        import_file(&store, &blob_store, &location, &path, |_| true).await;
+                    }
+                }
+            }));
+        }
+
+        for task in tasks {
+            task.await.failed("Failed to wait for task");
+        }
+    }
+}
+
+fn restore_account_key(target: &BackupManifest, subspace: u8, sequence: u32, key: &[u8]) -> bool {
+    match key
+        .deserialize_be_u32(0)
+        .ok()
+        .and_then(|account_id| target.accounts.get(&account_id))
+    {
+        Some(account) if subspace == SUBSPACE_LOGS => sequence >= account.logs_from,
+        Some(account) => sequence == account.data,
+        None => sequence == target.sequence,
+    }
+}
+
+impl RestoreParams {
+    pub fn new(src: PathBuf) -> Self {
+        Self {
+            src,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_sequence(mut self, sequence: u32) -> Self {
+        self.sequence = Some(sequence);
+        self
+    }
+
+    pub fn with_location(mut self, location: BackupLocation) -> Self {
+        self.location = location;
+        self
+    }
 }
 
 async fn subspace_has_data(store: &Store, subspace: u8) -> bool {
@@ -74,26 +190,49 @@ async fn subspace_has_data(store: &Store, subspace: u8) -> bool {
 }
 
 async fn restore_file(store: Store, blob_store: BlobStore, path: &Path) {
-    println!("Importing database dump from {}.", path.to_str().unwrap());
+    let location = BackupLocation::Local;
+    let subspace = KeyValueReader::try_new(&location, path)
+        .unwrap_or_else(|err| failed(&err))
+        .subspace;
+    assert_subspace_is_empty(&store, subspace, &location.display(path)).await;
 
-    let mut reader = KeyValueReader::new(path);
+    import_file(&store, &blob_store, &location, path, |_| true).await;
+}
 
-    if subspace_has_data(&store, reader.subspace).await {
+async fn assert_subspace_is_empty(store: &Store, subspace: u8, path: &str) {
+    if subspace_has_data(store, subspace).await {
         eprintln!(
             "Cannot import {}: the target database already contains data in the key range being \
              imported. This usually means Stalwart was started before the import ran, which can \
              create duplicate entries. Import into a fresh, empty database and do not start \
              Stalwart before importing.",
-            path.display()
+            path
         );
         std::process::exit(1);
     }
+}
+
+async fn import_file(
+    store: &Store,
+    blob_store: &BlobStore,
+    location: &BackupLocation,
+    path: &Path,
+    filter: impl Fn(&[u8]) -> bool,
+) {
+    println!("Importing database dump from {}.", location.display(path));
 
+    let (subspace, mut reader) = KeyValueReader::open(location, path)
+        .await
+        .unwrap_or_else(|err| failed(&err));
     let mut batch = BatchBuilder::new();
 
-    match reader.subspace {
+    match subspace {
         SUBSPACE_BLOBS => {
-            while let Some((key, value)) = reader.next() {
+            while let Some((key, value)) = next_key(&mut reader).await {
+                if !filter(&key) {
+                    continue;
+                }
+
                 blob_store
                     .put_blob(&key, &value, CompressionAlgo::Lz4)
                     .await
@@ -101,12 +240,13 @@ async fn restore_file(store: Store, blob_store: BlobStore, path: &Path) {
             }
         }
         SUBSPACE_COUNTER | SUBSPACE_QUOTA => {
-            while let Some((key, value)) = reader.next() {
+            while let Some((key, value)) = next_key(&mut reader).await {
+                if !filter(&key) {
+                    continue;
+                }
+
                 batch.add(
-                    ValueClass::Any(AnyClass {
-                        subspace: reader.subspace,
-                        key,
-                    }),
+                    ValueClass::Any(AnyClass { subspace, key }),
                     u64::from_le_bytes(
                         value
                             .try_into()
@@ -123,7 +263,11 @@ async fn restore_file(store: Store, blob_store: BlobStore, path: &Path) {
             }
         }
         SUBSPACE_INDEXES => {
-            while let Some((key, _)) = reader.next() {
+            while let Some((key, _)) = next_key(&mut reader).await {
+                if !filter(&key) {
+                    continue;
+                }
+
                 let account_id = key
                     .as_slice()
                     .deserialize_be_u32(0)
@@ -155,14 +299,12 @@ async fn restore_file(store: Store, blob_store: BlobStore, path: &Path) {
             }
         }
         _ => {
-            while let Some((key, value)) = reader.next() {
-                batch.set(
-                    ValueClass::Any(AnyClass {
-                        subspace: reader.subspace,
-                        key,
-                    }),
-                    value,
-                );
+            while let Some((key, value)) = next_key(&mut reader).await {
+                if !filter(&key) {
+                    continue;
+                }
+
+                batch.set(ValueClass::Any(AnyClass { subspace, key }), value);
                 if batch.is_large_batch() {
                     store
                         .write(batch.build_all())
@@ -182,85 +324,117 @@ async fn restore_file(store: Store, blob_store: BlobStore, path: &Path) {
     }
 }
 
-struct KeyValueReader {
-    subspace: u8,
-    file: FrameDecoder<BufReader<File>>,
+pub(super) struct KeyValueReader {
+    pub subspace: u8,
+    file: FrameDecoder<Box<dyn Read + Send>>,
+}
+
+async fn next_key(reader: &mut KeyValueStream) -> Option<(Vec<u8>, Vec<u8>)> {
+    reader
+        .recv()
+        .await
+        .map(|result| result.unwrap_or_else(|err| failed(&err)))
 }
 
 impl KeyValueReader {
-    fn new(path: &Path) -> Self {
-        let mut file = FrameDecoder::new(BufReader::new(
-            File::open(path).failed("Failed to open file"),
-        ));
+    // Reads the file from a blocking thread, returns its subspace and its records
+    async fn open(location: &BackupLocation, path: &Path) -> Result<(u8, KeyValueStream), String> {
+        let location = location.clone();
+        let path = path.to_path_buf();
+        let mut reader = tokio::task::spawn_blocking(move || Self::try_new(&location, &path))
+            .await
+            .map_err(|err| format!("Reader task failed: {err}"))??;
+        let subspace = reader.subspace;
+        let (tx, rx) = mpsc::channel(1024);
+
+        tokio::task::spawn_blocking(move || {
+            loop {
+                let result = reader.try_next().transpose();
+                let is_last = !matches!(result, Some(Ok(_)));
+                if let Some(result) = result
+                    && tx.blocking_send(result).is_err()
+                {
+                    break;
+                }
+                if is_last {
+                    break;
+                }
+            }
+        });
+
+        Ok((subspace, rx))
+    }
+
+    pub fn try_new(location: &BackupLocation, path: &Path) -> Result<Self, String> {
+        let mut file = FrameDecoder::new(location.reader(path)?);
         let mut buf = [0u8; 1];
         file.read_exact(&mut buf)
-            .failed(&format!("Failed to read magic marker from {path:?}"));
+            .map_err(|err| format!("Failed to read magic marker from {path:?}: {err}"))?;
 
         if buf[0] != MAGIC_MARKER {
-            failed(&format!("Invalid magic marker in {path:?}"));
+            return Err(format!("Invalid magic marker in {path:?}"));
         }
 
         file.read_exact(&mut buf)
-            .failed(&format!("Failed to read subspace from {path:?}"));
+            .map_err(|err| format!("Failed to read subspace from {path:?}: {err}"))?;
         let subspace = buf[0];
 
         let mut buf = [0u8; 4];
         file.read_exact(&mut buf)
-            .failed(&format!("Failed to read version from {path:?}"));
+            .map_err(|err| format!("Failed to read version from {path:?}: {err}"))?;
         let version = u32::from_le_bytes(buf);
 
         if version != DATABASE_SCHEMA_VERSION {
-            failed(&format!(
+            return Err(format!(
                 "Invalid database schema version in {path:?}: Expected {DATABASE_SCHEMA_VERSION}, found {version}"
             ));
         }
 
-        Self { file, subspace }
+        Ok(Self { file, subspace })
     }
 
-    fn next(&mut self) -> Option<(Vec<u8>, Vec<u8>)> {
-        let size = self.read_size()?;
+    pub fn try_next(&mut self) -> Result<Option<(Vec<u8>, Vec<u8>)>, String> {
+        let Some(size) = self.read_size()? else {
+            return Ok(None);
+        };
 
         let mut key = vec![0; size as usize];
         self.file
             .read_exact(&mut key)
-            .failed("Failed to read bytes");
-        let value = self.expect_sized_bytes();
+            .map_err(|err| format!("Failed to read bytes: {err}"))?;
+        let len = self
+            .read_size()?
+            .ok_or_else(|| "Missing leb128 value sequence".to_string())? as usize;
+        let mut value = vec![0; len];
+        self.file
+            .read_exact(&mut value)
+            .map_err(|err| format!("Failed to read bytes: {err}"))?;
 
-        Some((key, value))
+        Ok(Some((key, value)))
     }
 
-    fn read_size(&mut self) -> Option<u32> {
+    fn read_size(&mut self) -> Result<Option<u32>, String> {
         let mut result = 0;
         let mut buf = [0u8; 1];
 
         for shift in [0, 7, 14, 21, 28] {
             if let Err(err) = self.file.read_exact(&mut buf) {
-                if err.kind() == ErrorKind::UnexpectedEof {
-                    return None;
+                return if err.kind() == ErrorKind::UnexpectedEof {
+                    Ok(None)
                 } else {
-                    failed(&format!("Failed to read file: {err:?}"));
-                }
+                    Err(format!("Failed to read file: {err:?}"))
+                };
             }
 
             let byte = buf[0];
             if (byte & 0x80) == 0 {
                 result |= (byte as u32) << shift;
-                return Some(result);
+                return Ok(Some(result));
             } else {
                 result |= ((byte & 0x7F) as u32) << shift;
             }
         }
 
-        failed("Invalid leb128 sequence")
-    }
-
-    fn expect_sized_bytes(&mut self) -> Vec<u8> {
-        let len = self.read_size().failed("Missing leb128 value sequence") as usize;
-        let mut bytes = vec![0; len];
-        self.file
-            .read_exact(&mut bytes)
-            .failed("Failed to read bytes");
-        bytes
+        Err("Invalid leb128 sequence".to_string())
     }
 }
@@ -4,7 +4,15 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
+use image::{DynamicImage, ImageReader, Limits};
 use sieve::{Context, runtime::Variable};
+use std::io::Cursor;
+
+pub use image::ImageFormat;
+
+const MAX_DECODE_DIMENSION: u32 = 16384;
+const MAX_DECODE_PIXELS: u64 = 64 * 1024 * 1024;
+const MAX_DECODE_ALLOC: u64 = 512 * 1024 * 1024;
 
 pub fn fn_img_metadata<'x>(ctx: &'x Context<'x>, v: Vec<Variable>) -> Variable {
     ctx.message()
@@ -56,3 +64,51 @@ pub fn fn_img_metadata<'x>(ctx: &'x Context<'x>, v: Vec<Variable>) -> Variable {
         })
         .unwrap_or_default()
 }
+
+pub fn image_format(type_: &str) -> Option<ImageFormat> {
+    hashify::tiny_map_ignore_case!(type_.as_bytes(),
+        "image/png" => ImageFormat::Png,
+        "image/jpeg" => ImageFormat::Jpeg,
+        "image/jpg" => ImageFormat::Jpeg,
+        "image/gif" => ImageFormat::Gif,
+        "image/webp" => ImageFormat::WebP,
+    )
+}
+
+// Scales an image down to fit the given dimensions and encodes it in the requested
+// format. The dimensions are read from the image header before decoding, so that
+// decompression bombs are rejected without allocating the full bitmap.
+pub fn resize_image(bytes: &[u8], width: u32, height: u32, format: ImageFormat) -> Option<Vec<u8>> {
+    let size = imagesize::blob_size(bytes).ok()?;
+    if size.width as u64 > MAX_DECODE_DIMENSION as u64
+        || size.height as u64 > MAX_DECODE_DIMENSION as u64
+        || (size.width as u64).saturating_mul(size.height as u64) > MAX_DECODE_PIXELS
+    {
+        return None;
+    }
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_DECODE_DIMENSION);
+    limits.max_image_height = Some(MAX_DECODE_DIMENSION);
+    limits.max_alloc = Some(MAX_DECODE_ALLOC);
+    let mut reader = ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .ok()?;
+    reader.limits(limits);
+    let image = reader.decode().ok()?;
+    let image = if image.width() > width || image.height() > height {
+        image.thumbnail(width, height)
+    } else {
+        image
+    };
+    let image = if format == ImageFormat::Jpeg {
+        // JPEG has no alpha channel
+        DynamicImage::ImageRgb8(image.to_rgb8())
+    } else {
+        image
+    };
+
+    let mut output = Cursor::new(Vec::new());
+    image.write_to(&mut output, format).ok()?;
+    Some(output.into_inner())
+}
@@ -10,7 +10,7 @@ use mail_parser::{
     decoders::{base64::base64_decode, quoted_printable::quoted_printable_decode},
 };
 use store::{
-    U32_LEN, U64_LEN,
+    SerializeInfallible, U32_LEN, U64_LEN,
     dispatch::lookup::KeyValue,
     write::{BatchBuilder, BlobLink, BlobOp, now},
 };
@@ -117,7 +117,7 @@ impl Server {
 
             // Commit blob
             let mut batch = BatchBuilder::new();
-            batch.set(BlobOp::Commit { hash: hash.clone() }, Vec::new());
+            batch.set(BlobOp::Commit { hash: hash.clone() }, now().serialize());
             self.core
                 .storage
                 .data
@@ -180,7 +180,7 @@ impl Server {
 
             // Commit blob
             let mut batch = BatchBuilder::new();
-            batch.set(BlobOp::Commit { hash: hash.clone() }, Vec::new());
+            batch.set(BlobOp::Commit { hash: hash.clone() }, now().serialize());
             self.core
                 .storage
                 .data
@@ -0,0 +1,302 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    Server,
+    ipc::{BroadcastEvent, RegistryChange},
+};
+use ahash::AHashSet;
+use registry::{
+    schema::{
+        enums::{CompressionAlgo, CompressionContentClass},
+        prelude::ObjectType,
+        structs::CompressionDictionary,
+    },
+    types::{EnumImpl, datetime::UTCDateTime, id::ObjectId},
+};
+use std::{cmp::Reverse, time::Instant};
+use store::{
+    BlobStore, IterateParams, SerializeInfallible, ValueKey,
+    dispatch::compression::{CompressionDictionaries, ContentClassDetect, train_dictionary},
+    registry::{
+        bootstrap::Bootstrap,
+        write::{RegistryWrite, RegistryWriteResult},
+    },
+    write::{BatchBuilder, BlobLink, BlobOp, ValueClass, now},
+};
+use trc::{AddContext, StoreEvent};
+use types::{
+    blob::BlobId,
+    blob_hash::{BLOB_HASH_LEN, BlobHash},
+};
+
+const MAX_BLOBS_SCANNED: usize = 10_000;
+const MAX_SAMPLES_PER_CLASS: usize = 1_000;
+const MIN_SAMPLES_PER_CLASS: usize = 32;
+const MAX_SUPERSEDED_DICTIONARIES: usize = 4;
+const DICTIONARY_GRACE_PERIOD: i64 = 24 * 60 * 60;
+
+impl Server {
+    pub async fn train_compression_dictionaries(&self) -> trc::Result<()> {
+        // Obtain a sample of committed blobs
+        let mut hashes = Vec::new();
+        self.store()
+            .iterate(
+                IterateParams::new(
+                    ValueKey::from(ValueClass::Blob(BlobOp::Commit {
+                        hash: BlobHash::default(),
+                    })),
+                    ValueKey::from(ValueClass::Blob(BlobOp::Commit {
+                        hash: BlobHash::new_max(),
+                    })),
+                )
+                .ascending()
+                .no_values(),
+                |key, _| {
+                    if key.len() == BLOB_HASH_LEN {
+                        hashes.push(BlobHash::try_from_hash_slice(key).unwrap());
+                    }
+
+                    Ok(hashes.len() < MAX_BLOBS_SCANNED)
+                },
+            )
+            .await
+            .caused_by(trc::location!())?;
+
+        // Classify blobs by content
+        let mut samples: [Vec<Vec<u8>>; CompressionContentClass::COUNT] = Default::default();
+        for hash in hashes {
+            let Some(data) = self
+                .blob_store()
+                .get_blob(hash.as_ref(), 0..usize::MAX)
+                .await
+                .caused_by(trc::location!())?
+            else {
+                continue;
+            };
+
+            if let Some(class) = CompressionContentClass::detect(&data) {
+                let class_samples = &mut samples[class.to_id() as usize];
+                if class_samples.len() < MAX_SAMPLES_PER_CLASS
+                    && let Some(sample) = class.training_sample(&data)
+                {
+                    class_samples.push(sample);
+                }
+            }
+        }
+
+        // Train a dictionary for each content class
+        for (class_id, samples) in samples.into_iter().enumerate() {
+            if samples.len() < MIN_SAMPLES_PER_CLASS {
+                continue;
+            }
+
+            let class = CompressionContentClass::from_id(class_id as u16).unwrap();
+            let started = Instant::now();
+            let sample_count = samples.len();
+            let dictionary = tokio::task::spawn_blocking(move || train_dictionary(&samples))
+                .await
+                .map_err(|err| {
+                    trc::StoreEvent::UnexpectedError
+                        .caused_by(trc::location!())
+                        .reason(err)
+                        .details("Dictionary training task panicked")
+                })?
+                .map_err(|err| {
+                    trc::StoreEvent::UnexpectedError
+                        .caused_by(trc::location!())
+                        .reason(err)
+                        .details("Failed to train compression dictionary")
+                })?;
+
+            // Dictionaries are stored uncompressed
+            let hash = BlobHash::generate(&dictionary);
+            self.blob_store()
+                .put_blob(hash.as_ref(), &dictionary, CompressionAlgo::None)
+                .await
+                .caused_by(trc::location!())?;
+
+            // Link the dictionary blob before registering the dictionary
+            let item_id = self.registry().assign_id();
+            let object_id = ObjectId::new(ObjectType::CompressionDictionary, item_id.into());
+            let mut batch = BatchBuilder::new();
+            batch
+                .set(BlobOp::Commit { hash: hash.clone() }, now().serialize())
+                .set(
+                    BlobOp::Link {
+                        hash: hash.clone(),
+                        to: BlobLink::Id { id: item_id },
+                    },
+                    object_id.serialize(),
+                );
+            self.commit_batch(batch).await.caused_by(trc::location!())?;
+
+            let object = CompressionDictionary {
+                content_class: class,
+                blob_id: BlobId::new(hash, Default::default()),
+                sample_count: sample_count as u64,
+                created_at: UTCDateTime::now(),
+            };
+            match self
+                .registry()
+                .write(RegistryWrite::insert_with_id(
+                    item_id.into(),
+                    &object.into(),
+                ))
+                .await
+                .caused_by(trc::location!())?
+            {
+                RegistryWriteResult::Success(_) => {}
+                failure => {
+                    return Err(trc::StoreEvent::UnexpectedError
+                        .caused_by(trc::location!())
+                        .details("Failed to register compression dictionary")
+                        .reason(failure));
+                }
+            }
+
+            trc::event!(
+                Store(StoreEvent::CompressionDictionaryTrained),
+                Id = item_id,
+                Details = class.as_str(),
+                Total = sample_count,
+                Size = dictionary.len(),
+                Elapsed = started.elapsed()
+            );
+
+            // Make the dictionary available on all nodes
+            CompressionDictionaries::register(item_id, class, dictionary);
+            self.cluster_broadcast(BroadcastEvent::RegistryChange(RegistryChange::Insert(
+                object_id,
+            )))
+            .await;
+        }
+
+        self.prune_compression_dictionaries()
+            .await
+            .caused_by(trc::location!())
+    }
+
+    // Removes superseded dictionaries once a full scan shows that no blob references them
+    async fn prune_compression_dictionaries(&self) -> trc::Result<()> {
+        let mut dictionaries = self
+            .registry()
+            .list::<CompressionDictionary>()
+            .await
+            .caused_by(trc::location!())?;
+        dictionaries.sort_unstable_by_key(|dictionary| Reverse(dictionary.id.id().id()));
+
+        // Keep superseded dictionaries for a grace period so all nodes switch to the newer one
+        let grace_cutoff = now() as i64 - DICTIONARY_GRACE_PERIOD;
+        let mut superseded_at = [None; CompressionContentClass::COUNT];
+        dictionaries.retain(|dictionary| {
+            std::mem::replace(
+                &mut superseded_at[dictionary.object.content_class.to_id() as usize],
+                Some(dictionary.object.created_at.timestamp()),
+            )
+            .is_some_and(|superseded_at| superseded_at < grace_cutoff)
+        });
+        if dictionaries.len() <= MAX_SUPERSEDED_DICTIONARIES {
+            return Ok(());
+        }
+
+        // Rewrite blobs compressed with superseded dictionaries
+        for shard_index in 0..=u8::MAX {
+            self.store()
+                .recompress_blobs(
+                    self.blob_store().clone(),
+                    shard_index,
+                    self.core.email.compression,
+                )
+                .await
+                .caused_by(trc::location!())?;
+        }
+
+        // Verify that no blob, including reserved and temporary ones, still references them
+        let mut referenced = AHashSet::new();
+        for shard_index in 0..=u8::MAX {
+            for hash in self
+                .store()
+                .blob_hashes(shard_index)
+                .await
+                .caused_by(trc::location!())?
+            {
+                if let Some(dictionary_id) = self
+                    .blob_store()
+                    .blob_dictionary_id(hash.as_ref())
+                    .await
+                    .caused_by(trc::location!())?
+                {
+                    referenced.insert(dictionary_id);
+                }
+            }
+        }
+        dictionaries.retain(|dictionary| !referenced.contains(&dictionary.id.id().id()));
+
+        for dictionary in dictionaries {
+            let item_id = dictionary.id.id().id();
+            match self
+                .registry()
+                .write(RegistryWrite::delete(dictionary.id))
+                .await
+                .caused_by(trc::location!())?
+            {
+                RegistryWriteResult::Success(_) | RegistryWriteResult::NotFound { .. } => {}
+                failure => {
+                    return Err(trc::StoreEvent::UnexpectedError
+                        .caused_by(trc::location!())
+                        .details("Failed to delete compression dictionary")
+                        .reason(failure));
+                }
+            }
+
+            // Unlink the dictionary blob so it is purged
+            let mut batch = BatchBuilder::new();
+            batch.clear(BlobOp::Link {
+                hash: dictionary.object.blob_id.hash,
+                to: BlobLink::Id { id: item_id },
+            });
+            self.commit_batch(batch).await.caused_by(trc::location!())?;
+
+            CompressionDictionaries::unregister(item_id);
+            self.cluster_broadcast(BroadcastEvent::RegistryChange(RegistryChange::Delete(
+                dictionary.id,
+            )))
+            .await;
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) async fn load_compression_dictionaries(bp: &mut Bootstrap, blob_store: &BlobStore) {
+    CompressionDictionaries::set_registry(bp.registry.clone());
+
+    for dictionary in bp.list_infallible::<CompressionDictionary>().await {
+        let item_id = dictionary.id.id().id();
+        if CompressionDictionaries::is_registered(item_id) {
+            continue;
+        }
+
+        match blob_store
+            .get_blob(dictionary.object.blob_id.hash.as_ref(), 0..usize::MAX)
+            .await
+        {
+            Ok(Some(data)) => {
+                CompressionDictionaries::register(item_id, dictionary.object.content_class, data);
+            }
+            Ok(None) => {
+                bp.build_error(dictionary.id, "Compression dictionary blob not found");
+            }
+            Err(err) => {
+                bp.build_error(
+                    dictionary.id,
+                    format!("Failed to load compression dictionary: {err}"),
+                );
+            }
+        }
+    }
+}
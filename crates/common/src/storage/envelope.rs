@@ -0,0 +1,27 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use registry::schema::structs::BlobEncryptionKey;
+use store::{dispatch::encryption::BlobMasterKeys, registry::bootstrap::Bootstrap};
+
+pub(crate) async fn load_blob_encryption_keys(bp: &mut Bootstrap) {
+    let mut keys = Vec::new();
+    for key in bp.list_infallible::<BlobEncryptionKey>().await {
+        match key.object.secret.secret().await {
+            Ok(secret) => {
+                keys.push((key.id.id().id(), secret.into_owned()));
+            }
+            Err(err) => {
+                bp.build_error(
+                    key.id,
+                    format!("Failed to obtain blob encryption key: {err}"),
+                );
+            }
+        }
+    }
+
+    BlobMasterKeys::install(keys);
+}
@@ -4,7 +4,9 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use crate::{auth::AccountTenantIds, sharing::notification::ShareNotification};
+use crate::{
+    auth::AccountTenantIds, sharing::notification::ShareNotification, storage::usage::UsageBatch,
+};
 use registry::schema::{
     enums::IndexDocumentType,
     structs::{Task, TaskIndexDocument, TaskStatus},
@@ -47,9 +49,16 @@ pub enum IndexValue<'x> {
     Blob {
         value: BlobHash,
     },
+    Blobs {
+        values: Vec<BlobHash>,
+    },
     Quota {
         used: u32,
     },
+    MailboxUsage {
+        used: u32,
+        mailbox_ids: Vec<u32>,
+    },
     LogContainer {
         sync_collection: SyncCollection,
     },
@@ -465,6 +474,24 @@ fn build_index(
                 });
             }
         }
+        IndexValue::Blobs { values } => {
+            for hash in values {
+                if set {
+                    batch.set(
+                        BlobOp::Link {
+                            hash,
+                            to: BlobLink::Document,
+                        },
+                        vec![],
+                    );
+                } else {
+                    batch.clear(BlobOp::Link {
+                        hash,
+                        to: BlobLink::Document,
+                    });
+                }
+            }
+        }
         IndexValue::Acl { value } => {
             let object_account_id = batch.last_account_id().unwrap_or_default();
             let object_type = batch.last_collection().unwrap_or(Collection::None);
@@ -510,10 +537,19 @@ fn build_index(
 
             batch.add(ValueClass::Quota, value);
 
+            if let Some(collection) = batch.last_collection() {
+                batch.item_usage(collection, used, set);
+            }
+
             if let Some(tenant_id) = tenant_id {
                 batch.add(ValueClass::TenantQuota(tenant_id), value);
             }
         }
+        IndexValue::MailboxUsage { used, mailbox_ids } => {
+            for mailbox_id in mailbox_ids {
+                batch.mailbox_usage(mailbox_id, used, set);
+            }
+        }
         IndexValue::LogItem {
             sync_collection,
             prefix,
@@ -616,6 +652,27 @@ fn merge_index(
                 vec![],
             );
         }
+        (IndexValue::Blobs { values: old_hashes }, IndexValue::Blobs { values: new_hashes }) => {
+            for hash in &old_hashes {
+                if !new_hashes.contains(hash) {
+                    batch.clear(BlobOp::Link {
+                        hash: hash.clone(),
+                        to: BlobLink::Document,
+                    });
+                }
+            }
+            for hash in new_hashes {
+                if !old_hashes.contains(&hash) {
+                    batch.set(
+                        BlobOp::Link {
+                            hash,
+                            to: BlobLink::Document,
+                        },
+                        vec![],
+                    );
+                }
+            }
+        }
         (IndexValue::Acl { value: old_acl }, IndexValue::Acl { value: new_acl }) => {
             let has_old_acl = !old_acl.is_empty();
             let has_new_acl = !new_acl.is_empty();
@@ -731,10 +788,37 @@ fn merge_index(
             let value = new_used as i64 - old_used as i64;
             batch.add(ValueClass::Quota, value);
 
+            if let Some(collection) = batch.last_collection() {
+                batch
+                    .item_usage(collection, old_used, false)
+                    .item_usage(collection, new_used, true);
+            }
+
             if let Some(tenant_id) = tenant_id {
                 batch.add(ValueClass::TenantQuota(tenant_id), value);
             }
         }
+        (
+            IndexValue::MailboxUsage {
+                used: old_used,
+                mailbox_ids: old_ids,
+            },
+            IndexValue::MailboxUsage {
+                used: new_used,
+                mailbox_ids: new_ids,
+            },
+        ) => {
+            for mailbox_id in &old_ids {
+                if old_used != new_used || !new_ids.contains(mailbox_id) {
+                    batch.mailbox_usage(*mailbox_id, old_used, false);
+                }
+            }
+            for mailbox_id in new_ids {
+                if old_used != new_used || !old_ids.contains(&mailbox_id) {
+                    batch.mailbox_usage(mailbox_id, new_used, true);
+                }
+            }
+        }
         (
             IndexValue::LogItem {
                 sync_collection,
@@ -18,16 +18,19 @@ use store::{BlobStore, InMemoryStore, RegistryStore, SearchStore, Store};
 
 pub mod archive;
 pub mod blob;
+pub mod compression;
 pub mod dav;
 pub mod document;
 pub mod encryption;
+pub mod envelope;
 pub mod index;
 pub mod quota;
 pub mod state;
 pub mod transaction;
+pub mod usage;
 
 #[derive(Debug, Clone)]
-pub struct ObjectQuota([u32; StorageQuota::COUNT - 1]);
+pub struct ObjectQuota([u32; StorageQuota::COUNT]);
 
 #[derive(Debug, Clone)]
 pub struct TenantQuota([u32; TenantStorageQuota::COUNT - 1]);
@@ -0,0 +1,363 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::Server;
+use registry::{
+    schema::{
+        enums::{StorageSizeBucket, StorageUsageType},
+        prelude::ObjectType,
+    },
+    types::EnumImpl,
+};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use store::{
+    IterateParams, ValueKey,
+    ahash::AHashMap,
+    registry::RegistryQuery,
+    roaring::RoaringBitmap,
+    write::{BatchBuilder, UsageClass, ValueClass},
+};
+use trc::AddContext;
+use types::collection::Collection;
+use utils::map::vec_map::VecMap;
+
+// Upper bounds of the item size buckets, larger items fall in the last bucket
+const SIZE_BUCKETS: [u64; 4] = [100 * 1024, 1024 * 1024, 10 * 1024 * 1024, 100 * 1024 * 1024];
+const TOP_USAGE_TTL: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StorageUsage {
+    pub counters: AHashMap<UsageClass, i64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AccountUsageRank {
+    pub account_id: u32,
+    pub name: String,
+    pub used: i64,
+    pub by_type: VecMap<StorageUsageType, u64>,
+}
+
+#[derive(Default)]
+pub struct TopUsage {
+    updated: Option<Instant>,
+    limit: usize,
+    accounts: Arc<Vec<AccountUsageRank>>,
+}
+
+pub trait UsageBatch {
+    fn item_usage(&mut self, collection: Collection, size: u32, set: bool) -> &mut Self;
+    fn mailbox_usage(&mut self, mailbox_id: u32, size: u32, set: bool) -> &mut Self;
+}
+
+impl UsageBatch for BatchBuilder {
+    fn item_usage(&mut self, collection: Collection, size: u32, set: bool) -> &mut Self {
+        let (value, items) = if set {
+            (size as i64, 1)
+        } else {
+            (-(size as i64), -1)
+        };
+        let bucket = size_bucket(size as u64).to_id() as u8;
+
+        self.add(
+            ValueClass::Usage(UsageClass::Collection(collection.into())),
+            value,
+        )
+        .add(ValueClass::Usage(UsageClass::SizeBucket(bucket)), value)
+        .add(
+            ValueClass::Usage(UsageClass::SizeBucketItems(bucket)),
+            items,
+        )
+    }
+
+    fn mailbox_usage(&mut self, mailbox_id: u32, size: u32, set: bool) -> &mut Self {
+        self.add(
+            ValueClass::Usage(UsageClass::Mailbox(mailbox_id)),
+            if set { size as i64 } else { -(size as i64) },
+        )
+    }
+}
+
+impl StorageUsage {
+    pub fn add_item(&mut self, collection: Collection, size: u32) {
+        let bucket = size_bucket(size as u64).to_id() as u8;
+        for (class, value) in [
+            (UsageClass::Collection(collection.into()), size as i64),
+            (UsageClass::SizeBucket(bucket), size as i64),
+            (UsageClass::SizeBucketItems(bucket), 1),
+        ] {
+            *self.counters.entry(class).or_default() += value;
+        }
+    }
+
+    pub fn add_mailboxes(&mut self, mailbox_ids: impl IntoIterator<Item = u32>, size: u32) {
+        for mailbox_id in mailbox_ids {
+            *self
+                .counters
+                .entry(UsageClass::Mailbox(mailbox_id))
+                .or_default() += size as i64;
+        }
+    }
+
+    pub fn by_type(&self) -> VecMap<StorageUsageType, u64> {
+        let mut usage = VecMap::new();
+        for id in 0..StorageUsageType::COUNT as u16 {
+            let typ = StorageUsageType::from_id(id).unwrap();
+            let value = self
+                .counters
+                .iter()
+                .filter_map(|(class, value)| match class {
+                    UsageClass::Collection(collection)
+                        if usage_type(Collection::from(*collection)) == Some(typ) =>
+                    {
+                        Some((*value).max(0) as u64)
+                    }
+                    _ => None,
+                })
+                .sum::<u64>();
+            if value > 0 {
+                usage.append(typ, value);
+            }
+        }
+        usage
+    }
+
+    pub fn by_mailbox(&self) -> VecMap<u32, u64> {
+        let mut mailboxes = self
+            .counters
+            .iter()
+            .filter_map(|(class, value)| match class {
+                UsageClass::Mailbox(mailbox_id) if *value > 0 => Some((*mailbox_id, *value as u64)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        mailboxes.sort_unstable();
+        let mut usage = VecMap::with_capacity(mailboxes.len());
+        for (mailbox_id, value) in mailboxes {
+            usage.append(mailbox_id, value);
+        }
+        usage
+    }
+
+    pub fn by_size(&self) -> VecMap<StorageSizeBucket, u64> {
+        self.buckets(|class| match class {
+            UsageClass::SizeBucket(bucket) => Some(*bucket),
+            _ => None,
+        })
+    }
+
+    pub fn items_by_size(&self) -> VecMap<StorageSizeBucket, u64> {
+        self.buckets(|class| match class {
+            UsageClass::SizeBucketItems(bucket) => Some(*bucket),
+            _ => None,
+        })
+    }
+
+    fn buckets(&self, fnc: impl Fn(&UsageClass) -> Option<u8>) -> VecMap<StorageSizeBucket, u64> {
+        let mut usage = VecMap::with_capacity(StorageSizeBucket::COUNT);
+        for id in 0..StorageSizeBucket::COUNT as u16 {
+            let bucket = StorageSizeBucket::from_id(id).unwrap();
+            let value = self
+                .counters
+                .iter()
+                .find_map(|(class, value)| (fnc(class) == Some(id as u8)).then_some(*value))
+                .unwrap_or_default();
+            usage.append(bucket, value.max(0) as u64);
+        }
+        usage
+    }
+
+    pub fn write(&self, batch: &mut BatchBuilder) {
+        for (class, value) in &self.counters {
+            if *value != 0 {
+                batch.add(ValueClass::Usage(*class), *value);
+            }
+        }
+    }
+}
+
+impl Server {
+    pub async fn get_storage_usage(&self, account_id: u32) -> trc::Result<StorageUsage> {
+        let mut classes = Vec::new();
+        self.core
+            .storage
+            .data
+            .iterate(
+                IterateParams::new(
+                    usage_key(account_id, UsageClass::Collection(0)),
+                    usage_key(account_id, UsageClass::SizeBucketItems(u8::MAX)),
+                )
+                .no_values(),
+                |key, _| {
+                    classes.extend(UsageClass::deserialize(key));
+                    Ok(true)
+                },
+            )
+            .await
+            .add_context(|err| err.caused_by(trc::location!()).account_id(account_id))?;
+
+        // Counters are read individually as not all backends return their values when iterating
+        let mut usage = StorageUsage::default();
+        for class in classes {
+            let value = self
+                .core
+                .storage
+                .data
+                .get_counter(usage_key(account_id, class))
+                .await
+                .add_context(|err| err.caused_by(trc::location!()).account_id(account_id))?;
+            if value != 0 {
+                usage.counters.insert(class, value);
+            }
+        }
+
+        Ok(usage)
+    }
+
+    // SPDX-SnippetBegin
+    // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+    // SPDX-License-Identifier: LicenseRef-SEL
+    #[cfg(feature = "enterprise")]
+    pub async fn get_archived_usage(&self, account_id: u32) -> trc::Result<u64> {
+        use registry::schema::structs::ArchivedItem;
+        use store::write::{RegistryClass, now};
+        use types::id::Id;
+
+        let now = now() as i64;
+        let mut used = 0;
+        for id in self
+            .registry()
+            .query::<Vec<Id>>(RegistryQuery::new(ObjectType::ArchivedItem).with_account(account_id))
+            .await
+            .caused_by(trc::location!())?
+        {
+            if let Some(ArchivedItem::Email(item)) = self
+                .store()
+                .get_value::<ArchivedItem>(ValueKey::from(ValueClass::Registry(
+                    RegistryClass::Item {
+                        object_id: ObjectType::ArchivedItem.to_id(),
+                        item_id: id.id(),
+                    },
+                )))
+                .await
+                .caused_by(trc::location!())?
+                && item.archived_until.timestamp() > now
+            {
+                used += item.size;
+            }
+        }
+
+        Ok(used)
+    }
+    // SPDX-SnippetEnd
+
+    #[cfg(not(feature = "enterprise"))]
+    pub async fn get_archived_usage(&self, _account_id: u32) -> trc::Result<u64> {
+        Ok(0)
+    }
+
+    // Returns the accounts using the most disk space, the ranking is cached
+    // for a few minutes as it requires reading the quota of every account.
+    pub async fn top_storage_usage(&self, limit: usize) -> trc::Result<Arc<Vec<AccountUsageRank>>> {
+        {
+            let top = self.inner.data.top_usage.lock();
+            if top.limit == limit
+                && top
+                    .updated
+                    .is_some_and(|updated| updated.elapsed() < TOP_USAGE_TTL)
+            {
+                return Ok(top.accounts.clone());
+            }
+        }
+
+        let mut ranking = Vec::new();
+        for account_id in self
+            .registry()
+            .query::<RoaringBitmap>(RegistryQuery::new(ObjectType::Account))
+            .await
+            .caused_by(trc::location!())?
+        {
+            let used = self
+                .get_used_quota_account(account_id)
+                .await
+                .caused_by(trc::location!())?;
+            if used > 0 {
+                ranking.push((account_id, used));
+            }
+        }
+        ranking.sort_unstable_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ranking.truncate(limit);
+
+        let mut accounts = Vec::with_capacity(ranking.len());
+        for (account_id, used) in ranking {
+            let name = self
+                .account(account_id)
+                .await
+                .map(|account| account.name.to_string())
+                .unwrap_or_else(|_| account_id.to_string());
+            let mut by_type = self
+                .get_storage_usage(account_id)
+                .await
+                .caused_by(trc::location!())?
+                .by_type();
+            let archived = self
+                .get_archived_usage(account_id)
+                .await
+                .caused_by(trc::location!())?;
+            if archived > 0 {
+                by_type.set(StorageUsageType::Archived, archived);
+            }
+            accounts.push(AccountUsageRank {
+                account_id,
+                name,
+                used,
+                by_type,
+            });
+        }
+
+        let accounts = Arc::new(accounts);
+        *self.inner.data.top_usage.lock() = TopUsage {
+            updated: Some(Instant::now()),
+            limit,
+            accounts: accounts.clone(),
+        };
+
+        Ok(accounts)
+    }
+}
+
+pub fn size_bucket(size: u64) -> StorageSizeBucket {
+    let idx = SIZE_BUCKETS
+        .iter()
+        .position(|max| size < *max)
+        .unwrap_or(SIZE_BUCKETS.len());
+    StorageSizeBucket::from_id(idx as u16).unwrap_or_default()
+}
+
+pub fn usage_type(collection: Collection) -> Option<StorageUsageType> {
+    match collection {
+        Collection::Email => Some(StorageUsageType::Email),
+        Collection::Calendar
+        | Collection::CalendarEvent
+        | Collection::CalendarEventNotification => Some(StorageUsageType::Calendar),
+        Collection::AddressBook | Collection::ContactCard => Some(StorageUsageType::Contact),
+        Collection::FileNode => Some(StorageUsageType::File),
+        Collection::SieveScript => Some(StorageUsageType::Sieve),
+        _ => None,
+    }
+}
+
+fn usage_key(account_id: u32, class: UsageClass) -> ValueKey<ValueClass> {
+    ValueKey {
+        account_id,
+        collection: 0,
+        document_id: 0,
+        class: ValueClass::Usage(class),
+    }
+}
@@ -6,8 +6,9 @@
 
 use prometheus::{
     TextEncoder,
-    proto::{Bucket, Counter, Gauge, Histogram, Metric, MetricFamily, MetricType},
+    proto::{Bucket, Counter, Gauge, Histogram, LabelPair, Metric, MetricFamily, MetricType},
 };
+use registry::types::EnumImpl;
 use trc::{Collector, atomics::histogram::AtomicHistogram};
 
 use crate::Server;
@@ -56,6 +57,59 @@ impl Server {
             metrics.push(metric);
         }
 
+        // Add top accounts by disk usage
+        let top_accounts = self
+            .core
+            .metrics
+            .prometheus
+            .as_ref()
+            .map_or(0, |prometheus| prometheus.top_accounts);
+        if top_accounts > 0 {
+            match self.top_storage_usage(top_accounts).await {
+                Ok(accounts) => {
+                    let mut usage = Vec::with_capacity(accounts.len());
+                    let mut usage_by_type = Vec::new();
+                    for account in accounts.iter() {
+                        let mut metric = new_gauge(account.used.max(0) as u64);
+                        metric.set_label(vec![new_label("account", &account.name)]);
+                        usage.push(metric);
+
+                        for (typ, used) in account.by_type.iter() {
+                            let mut metric = new_gauge(*used);
+                            metric.set_label(vec![
+                                new_label("account", &account.name),
+                                new_label("type", typ.as_str()),
+                            ]);
+                            usage_by_type.push(metric);
+                        }
+                    }
+
+                    for (name, help, values) in [
+                        (
+                            "account_disk_usage",
+                            "Disk space used by the largest accounts",
+                            usage,
+                        ),
+                        (
+                            "account_disk_usage_by_type",
+                            "Disk space used by the largest accounts by data type",
+                            usage_by_type,
+                        ),
+                    ] {
+                        let mut metric = MetricFamily::default();
+                        metric.set_name(name.into());
+                        metric.set_help(help.into());
+                        metric.set_field_type(MetricType::GAUGE);
+                        metric.set_metric(values);
+                        metrics.push(metric);
+                    }
+                }
+                Err(err) => {
+                    trc::error!(err.details("Failed to obtain top accounts by disk usage"));
+                }
+            }
+        }
+
         TextEncoder::new().encode_to_string(&metrics).map_err(|e| {
             trc::EventType::Telemetry(trc::TelemetryEvent::OtelExporterError).reason(e)
         })
@@ -91,6 +145,13 @@ fn new_gauge(value: u64) -> Metric {
     m
 }
 
+fn new_label(name: &str, value: &str) -> LabelPair {
+    let mut label = LabelPair::default();
+    label.set_name(name.into());
+    label.set_value(value.into());
+    label
+}
+
 fn new_histogram(histogram: &AtomicHistogram<12>) -> Metric {
     let mut m = Metric::default();
     let mut h = Histogram::default();
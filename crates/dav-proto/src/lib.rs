@@ -36,9 +36,11 @@ pub fn xml_pretty_print(xml_string: &str) -> String {
 #[derive(Debug, Default, PartialEq, Eq)]
 pub struct RequestHeaders<'x> {
     pub uri: &'x str,
+    pub query: Option<&'x str>,
     pub depth: Depth,
     pub timeout: Timeout,
     pub content_type: Option<&'x str>,
+    pub filename: Option<&'x str>,
     pub destination: Option<&'x str>,
     pub lock_token: Option<&'x str>,
     pub vcard_version: Option<VCardVersion>,
@@ -51,6 +53,7 @@ pub struct RequestHeaders<'x> {
     pub if_: Vec<If<'x>>,
     pub range: Option<ByteRange>,
     pub if_range: Option<&'x str>,
+    pub total_length: Option<u64>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -152,6 +155,10 @@ impl From<&RequestHeaders<'_>> for Value {
                 .into(),
             );
         }
+        if let Some(total_length) = headers.total_length {
+            values.push(CompactString::const_new("OC-Total-Length").into());
+            values.push(Value::Int(total_length as i64));
+        }
         for (name, is_set) in [
             ("Overwrite", headers.overwrite_fail),
             ("No-Timezones", headers.no_timezones),
@@ -216,6 +223,7 @@ RFC8144 - Use of the Prefer Header Field in Web Distributed Authoring and Versio
 RFC4791 - Calendaring Extensions to WebDAV (CalDAV)
 RFC7809 - Calendaring Extensions to WebDAV (CalDAV) Time Zones by Reference
 RFC6638 - Scheduling Extensions to CalDAV
+RFC8607 - Calendaring Extensions to WebDAV (CalDAV) Managed Attachments
 RFC6352 - CardDAV vCard Extensions to Web Distributed Authoring and Versioning (WebDAV)
 RFC6764 - Locating Services for Calendaring Extensions to WebDAV (CalDAV) and vCard Extensions to WebDAV (CardDAV)
 
@@ -226,7 +234,6 @@ RFC4316 - Datatypes for Web Distributed Authoring and Versioning (WebDAV) Proper
 RFC4709 - Mounting Web Distributed Authoring and Versioning (WebDAV) Servers
 RFC3648 - Web Distributed Authoring and Versioning (WebDAV) Ordered Collections Protocol
 RFC4437 - Web Distributed Authoring and Versioning (WebDAV) Redirect Reference Resources
-RFC8607 - Calendaring Extensions to WebDAV (CalDAV) Managed Attachments
 RFC5995 - Using POST to Add Members to Web Distributed Authoring and Versioning (WebDAV) Collections
 RFC3253 - Versioning Extensions to WebDAV (Web Distributed Authoring and Versioning)
 RFC5323 - Web Distributed Authoring and Versioning (WebDAV) SEARCH
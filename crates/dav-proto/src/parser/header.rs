@@ -84,6 +84,18 @@ impl<'x> RequestHeaders<'x> {
                 }
                 return true;
             },
+            "Content-Disposition" => {
+                for param in value.split(';').skip(1) {
+                    if let Some((name, param_value)) = param.split_once('=')
+                        && name.trim().eq_ignore_ascii_case("filename") {
+                        let param_value = param_value.trim().trim_matches('"');
+                        if !param_value.is_empty() {
+                            self.filename = Some(param_value);
+                        }
+                    }
+                }
+                return true;
+            },
             "Accept" => {
                 let mut preferred: Option<(f32, VCardVersion)> = None;
 
@@ -137,6 +149,10 @@ impl<'x> RequestHeaders<'x> {
                 self.if_range = Some(value.trim());
                 return true;
             },
+            "OC-Total-Length" => {
+                self.total_length = value.trim().parse().ok();
+                return self.total_length.is_some();
+            },
             _ => {}
         );
 
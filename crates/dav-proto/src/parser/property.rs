@@ -310,7 +310,10 @@ impl Tokenizer<'_> {
                                     None => DavValue::Null,
                                 }
                             }
-                            DavProperty::CalDav(CalDavProperty::CalendarTimezone) => {
+                            DavProperty::CalDav(
+                                CalDavProperty::CalendarTimezone
+                                | CalDavProperty::CalendarAvailability,
+                            ) => {
                                 match self
                                     .collect_string_value()?
                                     .map(|v| ICalendar::parse(&v).map_err(|_| v))
@@ -565,6 +568,9 @@ impl DavProperty {
             (Namespace::CalDav, Element::ScheduleCalendarTransp) => {
                 Some(DavProperty::CalDav(CalDavProperty::ScheduleCalendarTransp))
             }
+            (Namespace::CalDav, Element::CalendarAvailability) => {
+                Some(DavProperty::CalDav(CalDavProperty::CalendarAvailability))
+            }
             (Namespace::CalDav, Element::CalendarHomeSet) => {
                 Some(DavProperty::Principal(PrincipalProperty::CalendarHomeSet))
             }
@@ -16,6 +16,7 @@ pub mod mkcol;
 pub mod propertyupdate;
 pub mod propfind;
 pub mod report;
+pub mod search;
 
 impl DavParser for DeadProperty {
     fn parse(stream: &mut Tokenizer<'_>) -> crate::parser::Result<Self> {
@@ -117,7 +118,7 @@ mod tests {
         parser::{DavParser, tokenizer::Tokenizer},
         schema::{
             property::{CardDavProperty, DavProperty},
-            request::{Acl, LockInfo, MkCol, PropFind, PropertyUpdate, Report},
+            request::{Acl, LockInfo, MkCol, PropFind, PropertyUpdate, Report, SearchRequest},
         },
     };
 
@@ -184,6 +185,10 @@ mod tests {
                     "acl" => {
                         serde_json::to_string_pretty(&Acl::parse(&mut tokenizer).unwrap()).unwrap()
                     }
+                    "search" => {
+                        serde_json::to_string_pretty(&SearchRequest::parse(&mut tokenizer).unwrap())
+                            .unwrap()
+                    }
                     _ => {
                         panic!("Unknown method: {}", filename);
                     }
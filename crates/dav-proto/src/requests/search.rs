@@ -0,0 +1,346 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    Depth,
+    parser::{DavParser, RawElement, Token, tokenizer::Tokenizer},
+    schema::{
+        Attribute, Element, NamedElement, Namespace,
+        property::DavProperty,
+        request::{
+            PropFind, SearchFilter, SearchOperator, SearchOrder, SearchRequest, SearchScope,
+        },
+    },
+};
+
+impl DavParser for SearchRequest {
+    fn parse(stream: &mut Tokenizer<'_>) -> crate::parser::Result<Self> {
+        stream.expect_named_element(NamedElement::dav(Element::Searchrequest))?;
+        stream.expect_named_element(NamedElement::dav(Element::Basicsearch))?;
+
+        let mut sr = SearchRequest {
+            properties: PropFind::AllProp(vec![]),
+            scope: vec![],
+            filters: vec![],
+            order_by: vec![],
+            limit: None,
+        };
+
+        loop {
+            match stream.token()? {
+                Token::ElementStart { name, .. } => match name {
+                    NamedElement {
+                        ns: Namespace::Dav,
+                        element: Element::Select,
+                    } => {
+                        sr.properties = match stream.unwrap_named_element()? {
+                            NamedElement {
+                                ns: Namespace::Dav,
+                                element: Element::Prop,
+                            } => PropFind::Prop(stream.collect_properties(Vec::new())?),
+                            NamedElement {
+                                ns: Namespace::Dav,
+                                element: Element::Allprop,
+                            } => {
+                                stream.expect_element_end()?;
+                                PropFind::AllProp(vec![])
+                            }
+                            NamedElement {
+                                ns: Namespace::Dav,
+                                element: Element::Propname,
+                            } => {
+                                stream.expect_element_end()?;
+                                PropFind::PropName
+                            }
+                            other => return Err(other.into_unexpected()),
+                        };
+                        stream.expect_element_end()?;
+                    }
+                    NamedElement {
+                        ns: Namespace::Dav,
+                        element: Element::From,
+                    } => {
+                        sr.scope = parse_scope(stream)?;
+                    }
+                    NamedElement {
+                        ns: Namespace::Dav,
+                        element: Element::Where,
+                    } => loop {
+                        match stream.token()? {
+                            Token::ElementStart { name, raw } => {
+                                let caseless = is_caseless(&raw)?;
+                                parse_expression(stream, name, caseless, &mut sr.filters)?;
+                            }
+                            Token::ElementEnd => break,
+                            Token::UnknownElement(_) => {
+                                stream.seek_element_end()?;
+                            }
+                            element => return Err(element.into_unexpected()),
+                        }
+                    },
+                    NamedElement {
+                        ns: Namespace::Dav,
+                        element: Element::Orderby,
+                    } => {
+                        sr.order_by = parse_order_by(stream)?;
+                    }
+                    NamedElement {
+                        ns: Namespace::Dav,
+                        element: Element::Limit,
+                    } => {
+                        stream.expect_named_element(NamedElement::dav(Element::Nresults))?;
+                        if let Some(Ok(limit)) = stream.parse_value::<u32>()? {
+                            sr.limit = limit.into();
+                        }
+                        stream.expect_element_end()?;
+                    }
+                    name => return Err(name.into_unexpected()),
+                },
+                Token::ElementEnd | Token::Eof => {
+                    break;
+                }
+                Token::UnknownElement(_) => {
+                    stream.seek_element_end()?;
+                }
+                element => return Err(element.into_unexpected()),
+            }
+        }
+
+        Ok(sr)
+    }
+}
+
+fn parse_scope(stream: &mut Tokenizer<'_>) -> crate::parser::Result<Vec<SearchScope>> {
+    let mut scopes = Vec::with_capacity(1);
+    let mut depth = 1;
+
+    loop {
+        match stream.token()? {
+            Token::ElementStart { name, .. } => match name {
+                NamedElement {
+                    ns: Namespace::Dav,
+                    element: Element::Scope,
+                } if depth == 1 => {
+                    scopes.push(SearchScope {
+                        href: String::new(),
+                        depth: Depth::Infinity,
+                    });
+                    depth += 1;
+                }
+                NamedElement {
+                    ns: Namespace::Dav,
+                    element: Element::Href,
+                } if depth == 2 => {
+                    if let (Some(scope), Some(href)) =
+                        (scopes.last_mut(), stream.collect_string_value()?)
+                    {
+                        scope.href = href;
+                    }
+                }
+                NamedElement {
+                    ns: Namespace::Dav,
+                    element: Element::Depth,
+                } if depth == 2 => {
+                    if let (Some(scope), Some(Ok(value))) =
+                        (scopes.last_mut(), stream.parse_value::<Depth>()?)
+                    {
+                        scope.depth = value;
+                    }
+                }
+                _ => {
+                    stream.seek_element_end()?;
+                }
+            },
+            Token::ElementEnd => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            Token::UnknownElement(_) => {
+                stream.seek_element_end()?;
+            }
+            element => return Err(element.into_unexpected()),
+        }
+    }
+
+    Ok(scopes)
+}
+
+fn parse_expression(
+    stream: &mut Tokenizer<'_>,
+    name: NamedElement,
+    caseless: bool,
+    filters: &mut Vec<SearchFilter>,
+) -> crate::parser::Result<()> {
+    if name.ns != Namespace::Dav {
+        return Err(name.into_unexpected());
+    }
+
+    let op = match name.element {
+        Element::And | Element::Or | Element::Not => {
+            filters.push(match name.element {
+                Element::And => SearchFilter::And,
+                Element::Or => SearchFilter::Or,
+                _ => SearchFilter::Not,
+            });
+            loop {
+                match stream.token()? {
+                    Token::ElementStart { name, raw } => {
+                        let caseless = is_caseless(&raw)?;
+                        parse_expression(stream, name, caseless, filters)?;
+                    }
+                    Token::ElementEnd => break,
+                    Token::UnknownElement(_) => {
+                        stream.seek_element_end()?;
+                    }
+                    element => return Err(element.into_unexpected()),
+                }
+            }
+            filters.push(SearchFilter::End);
+            return Ok(());
+        }
+        Element::IsCollection => {
+            stream.expect_element_end()?;
+            filters.push(SearchFilter::IsCollection);
+            return Ok(());
+        }
+        Element::IsDefined => {
+            let property = parse_operand(stream)?
+                .0
+                .ok_or_else(|| name.into_unexpected())?;
+            filters.push(SearchFilter::IsDefined(property));
+            return Ok(());
+        }
+        Element::Contains => {
+            filters.push(SearchFilter::Contains(
+                stream.collect_string_value()?.unwrap_or_default(),
+            ));
+            return Ok(());
+        }
+        Element::Eq => SearchOperator::Eq,
+        Element::Lt => SearchOperator::Lt,
+        Element::Lte => SearchOperator::Lte,
+        Element::Gt => SearchOperator::Gt,
+        Element::Gte => SearchOperator::Gte,
+        Element::Like => SearchOperator::Like,
+        _ => return Err(name.into_unexpected()),
+    };
+
+    match parse_operand(stream)? {
+        (Some(property), Some(value)) => {
+            filters.push(SearchFilter::Compare {
+                property,
+                op,
+                value,
+                caseless,
+            });
+            Ok(())
+        }
+        _ => Err(name.into_unexpected()),
+    }
+}
+
+fn parse_operand(
+    stream: &mut Tokenizer<'_>,
+) -> crate::parser::Result<(Option<DavProperty>, Option<String>)> {
+    let mut property = None;
+    let mut value = None;
+
+    loop {
+        match stream.token()? {
+            Token::ElementStart { name, .. } => match name {
+                NamedElement {
+                    ns: Namespace::Dav,
+                    element: Element::Prop,
+                } => {
+                    property = stream.collect_properties(Vec::new())?.into_iter().next();
+                }
+                NamedElement {
+                    ns: Namespace::Dav,
+                    element: Element::Literal | Element::TypedLiteral,
+                } => {
+                    value = Some(stream.collect_string_value()?.unwrap_or_default());
+                }
+                name => return Err(name.into_unexpected()),
+            },
+            Token::ElementEnd => break,
+            Token::UnknownElement(_) => {
+                stream.seek_element_end()?;
+            }
+            element => return Err(element.into_unexpected()),
+        }
+    }
+
+    Ok((property, value))
+}
+
+fn parse_order_by(stream: &mut Tokenizer<'_>) -> crate::parser::Result<Vec<SearchOrder>> {
+    let mut orders = Vec::with_capacity(2);
+    let mut depth = 1;
+
+    loop {
+        match stream.token()? {
+            Token::ElementStart { name, raw } => match name {
+                NamedElement {
+                    ns: Namespace::Dav,
+                    element: Element::Order,
+                } if depth == 1 => {
+                    let caseless = is_caseless(&raw)?;
+                    orders.push(SearchOrder {
+                        property: None,
+                        ascending: true,
+                        caseless,
+                    });
+                    depth += 1;
+                }
+                NamedElement {
+                    ns: Namespace::Dav,
+                    element: Element::Prop,
+                } if depth == 2 => {
+                    let property = stream.collect_properties(Vec::new())?.into_iter().next();
+                    if let Some(order) = orders.last_mut() {
+                        order.property = property;
+                    }
+                }
+                NamedElement {
+                    ns: Namespace::Dav,
+                    element: element @ (Element::Ascending | Element::Descending),
+                } if depth == 2 => {
+                    stream.expect_element_end()?;
+                    if let Some(order) = orders.last_mut() {
+                        order.ascending = element == Element::Ascending;
+                    }
+                }
+                _ => {
+                    stream.seek_element_end()?;
+                }
+            },
+            Token::ElementEnd => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            Token::UnknownElement(_) => {
+                stream.seek_element_end()?;
+            }
+            element => return Err(element.into_unexpected()),
+        }
+    }
+
+    Ok(orders)
+}
+
+fn is_caseless(raw: &RawElement<'_>) -> crate::parser::Result<bool> {
+    for attribute in raw.attributes::<String>() {
+        if let Attribute::Caseless(caseless) = attribute? {
+            return Ok(caseless);
+        }
+    }
+
+    Ok(false)
+}
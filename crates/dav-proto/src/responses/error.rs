@@ -136,6 +136,8 @@ impl Display for CalCondition {
             }
             CalCondition::ValidSchedulingMessage => write!(f, "<A:valid-scheduling-message/>"),
             CalCondition::ValidOrganizer => write!(f, "<A:valid-organizer/>"),
+            CalCondition::ValidManagedId => write!(f, "<A:valid-managed-id/>"),
+            CalCondition::ValidRid => write!(f, "<A:valid-rid/>"),
             CalCondition::SupportedCalendarComponent => {
                 write!(f, "<A:supported-calendar-component/>")
             }
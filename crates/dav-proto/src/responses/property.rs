@@ -202,6 +202,7 @@ impl DavProperty {
                     CalDavProperty::ScheduleDefaultCalendarURL => "A:schedule-default-calendar-URL",
                     CalDavProperty::ScheduleTag => "A:schedule-tag",
                     CalDavProperty::ScheduleCalendarTransp => "A:schedule-calendar-transp",
+                    CalDavProperty::CalendarAvailability => "A:calendar-availability",
                 },
                 DavProperty::Principal(prop) => match prop {
                     PrincipalProperty::AlternateURISet => "D:alternate-URI-set",
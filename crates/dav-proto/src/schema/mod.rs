@@ -379,6 +379,7 @@ pub enum Element {
     Scope,
     Score,
     Searchable,
+    Searchrequest,
     Segment,
     Select,
     Selectable,
@@ -759,6 +760,7 @@ impl Element {
             "scope" => Element::Scope,
             "score" => Element::Score,
             "searchable" => Element::Searchable,
+            "searchrequest" => Element::Searchrequest,
             "segment" => Element::Segment,
             "select" => Element::Select,
             "selectable" => Element::Selectable,
@@ -1144,6 +1146,7 @@ impl AsRef<str> for Element {
             Element::Scope => "scope",
             Element::Score => "score",
             Element::Searchable => "searchable",
+            Element::Searchrequest => "searchrequest",
             Element::Segment => "segment",
             Element::Select => "select",
             Element::Selectable => "selectable",
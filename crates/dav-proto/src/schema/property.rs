@@ -107,6 +107,7 @@ pub enum CalDavProperty {
     ScheduleDefaultCalendarURL,
     ScheduleTag,
     ScheduleCalendarTransp,
+    CalendarAvailability,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
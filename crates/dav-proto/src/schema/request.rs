@@ -228,6 +228,61 @@ pub struct PropertySearch {
     pub match_: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(test, derive(serde::Serialize, serde::Deserialize))]
+pub struct SearchRequest {
+    pub properties: PropFind,
+    pub scope: Vec<SearchScope>,
+    pub filters: Vec<SearchFilter>,
+    pub order_by: Vec<SearchOrder>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(test, derive(serde::Serialize, serde::Deserialize))]
+pub struct SearchScope {
+    pub href: String,
+    pub depth: Depth,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(test, derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(test, serde(tag = "type", content = "data"))]
+pub enum SearchFilter {
+    And,
+    Or,
+    Not,
+    End,
+    Compare {
+        property: DavProperty,
+        op: SearchOperator,
+        value: String,
+        caseless: bool,
+    },
+    IsDefined(DavProperty),
+    IsCollection,
+    Contains(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(test, derive(serde::Serialize, serde::Deserialize))]
+pub enum SearchOperator {
+    Eq,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+    Like,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(test, derive(serde::Serialize, serde::Deserialize))]
+pub struct SearchOrder {
+    pub property: Option<DavProperty>,
+    pub ascending: bool,
+    pub caseless: bool,
+}
+
 impl PropertyUpdate {
     pub fn has_changes(&self) -> bool {
         !self.set.is_empty() || !self.remove.is_empty()
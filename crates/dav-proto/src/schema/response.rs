@@ -256,6 +256,8 @@ pub enum CalCondition {
     ValidScheduleDefaultCalendarUrl,
     ValidSchedulingMessage,
     ValidOrganizer,
+    ValidManagedId,
+    ValidRid,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -365,6 +367,8 @@ impl CalCondition {
             CalCondition::ValidScheduleDefaultCalendarUrl => "ValidScheduleDefaultCalendarUrl",
             CalCondition::ValidSchedulingMessage => "ValidSchedulingMessage",
             CalCondition::ValidOrganizer => "ValidOrganizer",
+            CalCondition::ValidManagedId => "ValidManagedId",
+            CalCondition::ValidRid => "ValidRid",
             CalCondition::SupportedCalendarComponent => "SupportedCalendarComponent",
         }
     }
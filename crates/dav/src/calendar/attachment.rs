@@ -0,0 +1,396 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    DavError, DavErrorCondition, DavMethod,
+    common::{
+        ETag,
+        lock::{LockRequestHandler, ResourceState},
+        uri::DavUriResource,
+    },
+};
+use calcard::common::timezone::Tz;
+use common::{Server, auth::AccessToken};
+use dav_proto::{
+    RequestHeaders, Return,
+    schema::{property::Rfc1123DateTime, response::CalCondition},
+};
+use groupware::{
+    cache::GroupwareCache,
+    calendar::{
+        CalendarEvent, CalendarEventData,
+        attachment::{
+            AttachmentRid, ManagedAttachment, attachment_add, attachment_component_ids,
+            attachment_remove, attachment_update,
+        },
+        subscription::CalendarSubscriptionFetch,
+    },
+};
+use http_proto::HttpResponse;
+use hyper::StatusCode;
+use store::{
+    ValueKey,
+    write::{AlignedBytes, Archive, BatchBuilder},
+};
+use trc::AddContext;
+use types::{
+    acl::Acl,
+    blob_hash::BlobHash,
+    collection::{Collection, SyncCollection},
+};
+use utils::url_params::UrlParams;
+
+pub(crate) trait CalendarAttachmentRequestHandler: Sync + Send {
+    fn handle_calendar_attachment_request(
+        &self,
+        access_token: &AccessToken,
+        headers: &RequestHeaders<'_>,
+        params: UrlParams<'_>,
+        bytes: Vec<u8>,
+    ) -> impl Future<Output = crate::Result<HttpResponse>> + Send;
+
+    fn handle_calendar_attachment_get(
+        &self,
+        access_token: &AccessToken,
+        headers: &RequestHeaders<'_>,
+        managed_id: &str,
+        is_head: bool,
+    ) -> impl Future<Output = crate::Result<HttpResponse>> + Send;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AttachmentAction {
+    Add,
+    Update,
+    Remove,
+}
+
+impl CalendarAttachmentRequestHandler for Server {
+    async fn handle_calendar_attachment_request(
+        &self,
+        access_token: &AccessToken,
+        headers: &RequestHeaders<'_>,
+        params: UrlParams<'_>,
+        bytes: Vec<u8>,
+    ) -> crate::Result<HttpResponse> {
+        // Parse parameters
+        let action = match params.get("action") {
+            Some("attachment-add") => AttachmentAction::Add,
+            Some("attachment-update") => AttachmentAction::Update,
+            Some("attachment-remove") => AttachmentAction::Remove,
+            _ => return Err(DavError::Code(StatusCode::BAD_REQUEST)),
+        };
+        let managed_id = params.get("managed-id");
+        if action != AttachmentAction::Add && managed_id.is_none() {
+            return Err(DavError::Condition(
+                DavErrorCondition::new(StatusCode::BAD_REQUEST, CalCondition::ValidManagedId)
+                    .with_details("Missing managed-id parameter"),
+            ));
+        }
+        let rids = if let Some(rid) = params.get("rid") {
+            if action == AttachmentAction::Update {
+                return Err(DavError::Condition(
+                    DavErrorCondition::new(StatusCode::BAD_REQUEST, CalCondition::ValidRid)
+                        .with_details("The rid parameter is not allowed on attachment-update"),
+                ));
+            }
+            AttachmentRid::parse_list(rid)
+                .ok_or(DavError::Condition(DavErrorCondition::new(
+                    StatusCode::BAD_REQUEST,
+                    CalCondition::ValidRid,
+                )))?
+                .into()
+        } else {
+            None
+        };
+
+        // Validate URI
+        let resource_ = self
+            .validate_uri(access_token, headers.uri)
+            .await?
+            .into_owned_uri()?;
+        let account_id = resource_.account_id;
+        let resources = self
+            .fetch_dav_resources(
+                access_token.account_id(),
+                account_id,
+                SyncCollection::Calendar,
+            )
+            .await
+            .caused_by(trc::location!())?;
+        let resource = resources
+            .by_path(
+                resource_
+                    .resource
+                    .ok_or(DavError::Code(StatusCode::METHOD_NOT_ALLOWED))?,
+            )
+            .ok_or(DavError::Code(StatusCode::NOT_FOUND))?;
+        if resource.is_container() {
+            return Err(DavError::Code(StatusCode::METHOD_NOT_ALLOWED));
+        }
+        let document_id = resource.document_id();
+
+        // Validate ACL
+        if !access_token.is_member(account_id)
+            && !resources.has_access_to_container(
+                access_token,
+                resource.parent_id().unwrap(),
+                Acl::ModifyItems,
+            )
+        {
+            return Err(DavError::Code(StatusCode::FORBIDDEN));
+        }
+        if self
+            .is_subscribed_calendar(account_id, resource.parent_id().unwrap())
+            .await
+            .caused_by(trc::location!())?
+        {
+            return Err(DavError::Code(StatusCode::FORBIDDEN));
+        }
+
+        // Fetch event
+        let event_ = self
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                account_id,
+                Collection::CalendarEvent,
+                document_id,
+            ))
+            .await
+            .caused_by(trc::location!())?
+            .ok_or(DavError::Code(StatusCode::NOT_FOUND))?;
+        let event = event_
+            .to_unarchived::<CalendarEvent>()
+            .caused_by(trc::location!())?;
+
+        // Validate headers
+        self.validate_headers(
+            access_token,
+            headers,
+            vec![ResourceState {
+                account_id,
+                collection: Collection::CalendarEvent,
+                document_id: Some(document_id),
+                etag: event.etag().into(),
+                path: resource_.resource.unwrap(),
+                ..Default::default()
+            }],
+            Default::default(),
+            DavMethod::POST,
+        )
+        .await?;
+
+        let mut new_event = event
+            .deserialize::<CalendarEvent>()
+            .caused_by(trc::location!())?;
+        let component_ids = attachment_component_ids(&new_event.data.event, rids.as_deref())
+            .ok_or(DavError::Condition(DavErrorCondition::new(
+                StatusCode::BAD_REQUEST,
+                CalCondition::ValidRid,
+            )))?;
+
+        // Store attachment
+        let mut blob_hold = None;
+        let mut attachment = None;
+        if action != AttachmentAction::Remove {
+            if bytes.is_empty() {
+                return Err(DavError::Code(StatusCode::BAD_REQUEST));
+            } else if bytes.len() > self.core.groupware.max_file_size {
+                return Err(DavError::Code(StatusCode::PAYLOAD_TOO_LARGE));
+            }
+            self.has_available_quota(self.account(account_id).await?.as_ref(), bytes.len() as u64)
+                .await?;
+
+            let (blob_hash, blob_hold_) = self
+                .put_temporary_blob(account_id, &bytes, 60)
+                .await
+                .caused_by(trc::location!())?;
+            blob_hold = Some(blob_hold_);
+            attachment = Some(ManagedAttachment {
+                url: format!(
+                    "{}{}?managed-id={}",
+                    self.core.network.http.url_https,
+                    resources.format_resource(resource),
+                    blob_hash.to_hex()
+                ),
+                blob_hash,
+                size: bytes.len() as u64,
+                filename: headers.filename.map(|filename| filename.to_string()),
+                media_type: headers
+                    .content_type
+                    .map(|media_type| media_type.to_string()),
+            });
+        }
+
+        // Apply changes
+        let new_managed_id = attachment.as_ref().map(|a| a.managed_id());
+        let ical = &mut new_event.data.event;
+        match (action, attachment) {
+            (AttachmentAction::Add, Some(attachment)) => {
+                attachment_add(ical, &component_ids, attachment);
+            }
+            (AttachmentAction::Update, Some(attachment)) => {
+                if !attachment_update(ical, managed_id.unwrap(), attachment) {
+                    return Err(DavError::Condition(DavErrorCondition::new(
+                        StatusCode::BAD_REQUEST,
+                        CalCondition::ValidManagedId,
+                    )));
+                }
+            }
+            _ => {
+                if !attachment_remove(ical, &component_ids, managed_id.unwrap()) {
+                    return Err(DavError::Condition(DavErrorCondition::new(
+                        StatusCode::BAD_REQUEST,
+                        CalCondition::ValidManagedId,
+                    )));
+                }
+            }
+        }
+
+        // Build event
+        let mut next_email_alarm = None;
+        let ical = std::mem::take(&mut new_event.data.event);
+        let ical_text = ical.to_string();
+        new_event.size = ical_text.len() as u32;
+        new_event.data = CalendarEventData::new(
+            ical,
+            Tz::Floating,
+            self.core.groupware.max_ical_instances,
+            &mut next_email_alarm,
+        );
+        let schedule_tag = new_event.schedule_tag;
+        let modified = new_event.modified;
+
+        // Prepare write batch
+        let mut batch = BatchBuilder::new();
+        if let Some(blob_hold) = blob_hold {
+            batch
+                .with_account_id(account_id)
+                .with_collection(Collection::CalendarEvent)
+                .with_document(document_id)
+                .clear(blob_hold);
+        }
+        let etag = new_event
+            .update(
+                access_token.account_tenant_ids(),
+                event,
+                account_id,
+                document_id,
+                &mut batch,
+            )
+            .caused_by(trc::location!())?
+            .etag();
+        self.commit_batch(batch).await.caused_by(trc::location!())?;
+
+        let response = match action {
+            AttachmentAction::Add => HttpResponse::new(StatusCode::CREATED),
+            AttachmentAction::Update => HttpResponse::new(StatusCode::OK),
+            AttachmentAction::Remove => {
+                return Ok(HttpResponse::new(StatusCode::NO_CONTENT)
+                    .with_etag_opt(etag)
+                    .with_schedule_tag_opt(schedule_tag));
+            }
+        }
+        .with_etag_opt(etag)
+        .with_schedule_tag_opt(schedule_tag)
+        .with_header("Cal-Managed-ID", new_managed_id.unwrap_or_default());
+
+        if headers.ret != Return::Minimal {
+            Ok(response
+                .with_content_type("text/calendar; charset=utf-8")
+                .with_last_modified(Rfc1123DateTime::new(modified).to_string())
+                .with_binary_body(ical_text))
+        } else {
+            Ok(response.with_header("Preference-Applied", "return=minimal"))
+        }
+    }
+
+    async fn handle_calendar_attachment_get(
+        &self,
+        access_token: &AccessToken,
+        headers: &RequestHeaders<'_>,
+        managed_id: &str,
+        is_head: bool,
+    ) -> crate::Result<HttpResponse> {
+        // Validate URI
+        let resource_ = self
+            .validate_uri(access_token, headers.uri)
+            .await?
+            .into_owned_uri()?;
+        let account_id = resource_.account_id;
+        let resources = self
+            .fetch_dav_resources(
+                access_token.account_id(),
+                account_id,
+                SyncCollection::Calendar,
+            )
+            .await
+            .caused_by(trc::location!())?;
+        let resource = resources
+            .by_path(
+                resource_
+                    .resource
+                    .ok_or(DavError::Code(StatusCode::METHOD_NOT_ALLOWED))?,
+            )
+            .ok_or(DavError::Code(StatusCode::NOT_FOUND))?;
+        if resource.is_container() {
+            return Err(DavError::Code(StatusCode::METHOD_NOT_ALLOWED));
+        }
+
+        // Validate ACL
+        if !access_token.is_member(account_id)
+            && !resources.has_access_to_container(
+                access_token,
+                resource.parent_id().unwrap(),
+                Acl::ReadItems,
+            )
+        {
+            return Err(DavError::Code(StatusCode::FORBIDDEN));
+        }
+
+        // Fetch event
+        let event_ = self
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                account_id,
+                Collection::CalendarEvent,
+                resource.document_id(),
+            ))
+            .await
+            .caused_by(trc::location!())?
+            .ok_or(DavError::Code(StatusCode::NOT_FOUND))?;
+        let event = event_
+            .unarchive::<CalendarEvent>()
+            .caused_by(trc::location!())?;
+
+        // Make sure the attachment belongs to this event
+        let blob_hash = BlobHash::try_from_hex(managed_id)
+            .filter(|blob_hash| event.managed_attachments().contains_key(blob_hash))
+            .ok_or(DavError::Code(StatusCode::NOT_FOUND))?;
+        let media_type = event
+            .managed_attachment_media_type(managed_id)
+            .unwrap_or("application/octet-stream")
+            .to_string();
+
+        let contents = self
+            .blob_store()
+            .get_blob(blob_hash.as_slice(), 0..usize::MAX)
+            .await
+            .caused_by(trc::location!())?
+            .ok_or(DavError::Code(StatusCode::NOT_FOUND))?;
+
+        let response = HttpResponse::new(StatusCode::OK)
+            .with_content_type(media_type)
+            .with_etag(format!("\"{managed_id}\""))
+            .with_last_modified(Rfc1123DateTime::new(i64::from(event.modified)).to_string());
+
+        if !is_head {
+            Ok(response.with_binary_body(contents))
+        } else {
+            Ok(response.with_content_length(contents.len()))
+        }
+    }
+}
@@ -19,7 +19,10 @@ use dav_proto::{Depth, RequestHeaders};
 use groupware::{
     DestroyArchive,
     cache::GroupwareCache,
-    calendar::{Calendar, CalendarEvent, CalendarPreferences, Timezone},
+    calendar::{
+        Calendar, CalendarEvent, CalendarPreferences, Timezone,
+        subscription::CalendarSubscriptionFetch,
+    },
 };
 use http_proto::HttpResponse;
 use hyper::StatusCode;
@@ -213,6 +216,13 @@ impl CalendarCopyMoveRequestHandler for Server {
                     {
                         return Err(DavError::Code(StatusCode::FORBIDDEN));
                     }
+                    if self
+                        .is_subscribed_calendar(to_account_id, to_resource.document_id())
+                        .await
+                        .caused_by(trc::location!())?
+                    {
+                        return Err(DavError::Code(StatusCode::FORBIDDEN));
+                    }
 
                     // Overwrite container
                     copy_container(
@@ -255,6 +265,18 @@ impl CalendarCopyMoveRequestHandler for Server {
                     {
                         return Err(DavError::Code(StatusCode::FORBIDDEN));
                     }
+                    if self
+                        .is_subscribed_calendar(to_account_id, to_calendar_id)
+                        .await
+                        .caused_by(trc::location!())?
+                        || (is_move
+                            && self
+                                .is_subscribed_calendar(from_account_id, from_calendar_id)
+                                .await
+                                .caused_by(trc::location!())?)
+                    {
+                        return Err(DavError::Code(StatusCode::FORBIDDEN));
+                    }
 
                     if is_move {
                         move_event(
@@ -319,6 +341,18 @@ impl CalendarCopyMoveRequestHandler for Server {
                 {
                     return Err(DavError::Code(StatusCode::FORBIDDEN));
                 }
+                if self
+                    .is_subscribed_calendar(to_account_id, to_calendar_id)
+                    .await
+                    .caused_by(trc::location!())?
+                    || (is_move
+                        && self
+                            .is_subscribed_calendar(from_account_id, from_calendar_id)
+                            .await
+                            .caused_by(trc::location!())?)
+                {
+                    return Err(DavError::Code(StatusCode::FORBIDDEN));
+                }
 
                 // Copy/move event
                 if is_move {
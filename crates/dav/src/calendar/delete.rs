@@ -17,7 +17,7 @@ use dav_proto::RequestHeaders;
 use groupware::{
     DestroyArchive,
     cache::GroupwareCache,
-    calendar::{Calendar, CalendarEvent},
+    calendar::{Calendar, CalendarEvent, subscription::CalendarSubscriptionFetch},
 };
 use http_proto::HttpResponse;
 use hyper::StatusCode;
@@ -186,6 +186,13 @@ impl CalendarDeleteRequestHandler for Server {
             {
                 return Err(DavError::Code(StatusCode::FORBIDDEN));
             }
+            if self
+                .is_subscribed_calendar(account_id, calendar_id)
+                .await
+                .caused_by(trc::location!())?
+            {
+                return Err(DavError::Code(StatusCode::FORBIDDEN));
+            }
 
             let event_ = self
                 .store()
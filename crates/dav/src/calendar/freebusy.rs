@@ -18,7 +18,10 @@ use calcard::{
 };
 use common::{DavResourcePath, DavResources, PROD_ID, Server, auth::AccessToken};
 use dav_proto::{RequestHeaders, schema::request::FreeBusyQuery};
-use groupware::{cache::GroupwareCache, calendar::CalendarEvent};
+use groupware::{
+    cache::GroupwareCache,
+    calendar::{CalendarEvent, availability::CalendarAvailability},
+};
 use http_proto::HttpResponse;
 use hyper::StatusCode;
 use std::str::FromStr;
@@ -279,6 +282,18 @@ impl CalendarFreebusyRequestHandler for Server {
                 }
             }
 
+            // Time outside the owner's working hours is reported as unavailable
+            let unavailable = self
+                .calendar_unavailable_periods(account_id, range, default_tz)
+                .await
+                .caused_by(trc::location!())?;
+            if !unavailable.is_empty() {
+                fb_entries
+                    .entry(ICalendarFreeBusyType::BusyUnavailable)
+                    .or_default()
+                    .extend(unavailable);
+            }
+
             for (fbtype, events_in_range) in fb_entries {
                 entries.push(ICalendarEntry {
                     name: ICalendarProperty::Freebusy,
@@ -4,6 +4,7 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
+pub mod attachment;
 pub mod copy_move;
 pub mod delete;
 pub mod freebusy;
@@ -5,7 +5,7 @@
  */
 
 use crate::{
-    DavError, DavErrorCondition, DavMethod,
+    DavError, DavErrorCondition, DavMethod, PropStatBuilder,
     calendar::freebusy::CalendarFreebusyRequestHandler,
     common::{
         ETag,
@@ -16,17 +16,20 @@ use crate::{
 use calcard::{
     Entry, Parser,
     icalendar::{
-        ICalendarComponentType, ICalendarEntry, ICalendarMethod, ICalendarProperty, ICalendarValue,
-        Uri,
+        ICalendar, ICalendarComponentType, ICalendarEntry, ICalendarMethod, ICalendarProperty,
+        ICalendarValue, Uri,
     },
 };
 use common::{Server, auth::AccessToken};
 use dav_proto::{
-    RequestHeaders,
+    RequestHeaders, Return,
     schema::{
-        property::Rfc1123DateTime,
-        request::FreeBusyQuery,
-        response::{CalCondition, Href, ScheduleResponse, ScheduleResponseItem},
+        Namespace,
+        property::{CalDavProperty, DavProperty, DavValue, Rfc1123DateTime},
+        request::{FreeBusyQuery, PropertyUpdate},
+        response::{
+            CalCondition, Href, MultiStatus, Response, ScheduleResponse, ScheduleResponseItem,
+        },
     },
 };
 use groupware::{
@@ -35,12 +38,15 @@ use groupware::{
 use http_proto::HttpResponse;
 use hyper::StatusCode;
 use store::{
-    ValueKey,
-    write::{AlignedBytes, Archive},
+    Serialize, ValueKey,
+    write::{AlignedBytes, Archive, Archiver},
 };
 use store::{ahash::AHashMap, write::BatchBuilder};
 use trc::AddContext;
-use types::collection::{Collection, SyncCollection};
+use types::{
+    collection::{Collection, SyncCollection},
+    field::PrincipalField,
+};
 use utils::sanitize_email;
 
 pub(crate) trait CalendarEventNotificationHandler: Sync + Send {
@@ -63,6 +69,13 @@ pub(crate) trait CalendarEventNotificationHandler: Sync + Send {
         headers: &RequestHeaders<'_>,
         bytes: Vec<u8>,
     ) -> impl Future<Output = crate::Result<HttpResponse>> + Send;
+
+    fn handle_scheduling_proppatch_request(
+        &self,
+        access_token: &AccessToken,
+        headers: &RequestHeaders<'_>,
+        request: PropertyUpdate,
+    ) -> impl Future<Output = crate::Result<HttpResponse>> + Send;
 }
 
 impl CalendarEventNotificationHandler for Server {
@@ -435,4 +448,158 @@ impl CalendarEventNotificationHandler for Server {
 
         Ok(HttpResponse::new(StatusCode::OK).with_xml_body(response.to_string()))
     }
+
+    async fn handle_scheduling_proppatch_request(
+        &self,
+        access_token: &AccessToken,
+        headers: &RequestHeaders<'_>,
+        mut request: PropertyUpdate,
+    ) -> crate::Result<HttpResponse> {
+        // Validate URI
+        let resource = self
+            .validate_uri(access_token, headers.uri)
+            .await?
+            .into_owned_uri()?;
+        let account_id = resource.account_id;
+        if resource.resource.is_none_or(|r| r != "inbox") || !access_token.is_member(account_id) {
+            return Err(DavError::Code(StatusCode::FORBIDDEN));
+        }
+
+        if !request.has_changes() {
+            return Ok(HttpResponse::new(StatusCode::NO_CONTENT));
+        }
+
+        let mut items = PropStatBuilder::default();
+        let mut availability = None;
+
+        // Remove properties
+        if !request.set_first && !request.remove.is_empty() {
+            remove_inbox_properties(
+                std::mem::take(&mut request.remove),
+                &mut availability,
+                &mut items,
+            );
+        }
+
+        // Set properties
+        let mut is_success = true;
+        for property in request.set {
+            match (&property.property, property.value) {
+                (
+                    DavProperty::CalDav(CalDavProperty::CalendarAvailability),
+                    DavValue::ICalendar(ical),
+                ) => {
+                    if ical.size() > self.core.groupware.max_ical_size {
+                        items.insert_error_with_description(
+                            property.property,
+                            StatusCode::INSUFFICIENT_STORAGE,
+                            "Property value is too long",
+                        );
+                        is_success = false;
+                    } else if !is_availability(&ical) {
+                        items.insert_precondition_failed_with_description(
+                            property.property,
+                            StatusCode::PRECONDITION_FAILED,
+                            CalCondition::ValidCalendarData,
+                            "Invalid calendar availability",
+                        );
+                        is_success = false;
+                    } else {
+                        availability = Some(Some(ical));
+                        items.insert_ok(property.property);
+                    }
+                }
+                (DavProperty::CalDav(CalDavProperty::CalendarAvailability), _) => {
+                    items.insert_precondition_failed_with_description(
+                        property.property,
+                        StatusCode::PRECONDITION_FAILED,
+                        CalCondition::ValidCalendarData,
+                        "Invalid calendar availability",
+                    );
+                    is_success = false;
+                }
+                _ => {
+                    items.insert_error_with_description(
+                        property.property,
+                        StatusCode::CONFLICT,
+                        "Property cannot be modified",
+                    );
+                    is_success = false;
+                }
+            }
+        }
+
+        // Remove properties
+        if is_success && !request.remove.is_empty() {
+            remove_inbox_properties(request.remove, &mut availability, &mut items);
+        }
+
+        if is_success && let Some(availability) = availability {
+            let mut batch = BatchBuilder::new();
+            batch
+                .with_account_id(account_id)
+                .with_collection(Collection::Principal)
+                .with_document(0);
+            if let Some(availability) = availability {
+                batch.set(
+                    PrincipalField::CalendarAvailability,
+                    Archiver::new(availability)
+                        .serialize()
+                        .caused_by(trc::location!())?,
+                );
+            } else {
+                batch.clear(PrincipalField::CalendarAvailability);
+            }
+            self.commit_batch(batch).await.caused_by(trc::location!())?;
+        }
+
+        if headers.ret != Return::Minimal || !is_success {
+            Ok(HttpResponse::new(StatusCode::MULTI_STATUS).with_xml_body(
+                MultiStatus::new(vec![Response::new_propstat(headers.uri, items.build())])
+                    .with_namespace(Namespace::CalDav)
+                    .to_string(),
+            ))
+        } else {
+            Ok(HttpResponse::new(StatusCode::NO_CONTENT))
+        }
+    }
+}
+
+fn remove_inbox_properties(
+    properties: Vec<DavProperty>,
+    availability: &mut Option<Option<ICalendar>>,
+    items: &mut PropStatBuilder,
+) {
+    for property in properties {
+        if matches!(
+            property,
+            DavProperty::CalDav(CalDavProperty::CalendarAvailability)
+        ) {
+            *availability = Some(None);
+            items.insert_with_status(property, StatusCode::NO_CONTENT);
+        } else {
+            items.insert_error_with_description(
+                property,
+                StatusCode::CONFLICT,
+                "Property cannot be deleted",
+            );
+        }
+    }
+}
+
+fn is_availability(ical: &ICalendar) -> bool {
+    ical.components
+        .iter()
+        .any(|comp| comp.component_type == ICalendarComponentType::VAvailability)
+        && ical.components.iter().all(|comp| {
+            matches!(
+                comp.component_type,
+                ICalendarComponentType::VCalendar
+                    | ICalendarComponentType::VTimezone
+                    | ICalendarComponentType::Standard
+                    | ICalendarComponentType::Daylight
+                    | ICalendarComponentType::VAvailability
+                    | ICalendarComponentType::Available
+            )
+        })
 }
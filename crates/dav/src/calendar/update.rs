@@ -28,7 +28,10 @@ use dav_proto::{
 };
 use groupware::{
     cache::GroupwareCache,
-    calendar::{CalendarEvent, CalendarEventData},
+    calendar::{
+        CalendarEvent, CalendarEventData, attachment::sanitize_managed_attachments,
+        subscription::CalendarSubscriptionFetch,
+    },
     scheduling::{ItipMessages, event_create::itip_create, event_update::itip_update},
 };
 use http_proto::HttpResponse;
@@ -100,7 +103,7 @@ impl CalendarUpdateRequestHandler for Server {
             )
         })?;
 
-        let ical = match Parser::new(ical_raw).entry() {
+        let mut ical = match Parser::new(ical_raw).entry() {
             Entry::ICalendar(ical) => ical,
             _ => {
                 return Err(DavError::Condition(
@@ -131,6 +134,13 @@ impl CalendarUpdateRequestHandler for Server {
             {
                 return Err(DavError::Code(StatusCode::FORBIDDEN));
             }
+            if self
+                .is_subscribed_calendar(account_id, parent_id)
+                .await
+                .caused_by(trc::location!())?
+            {
+                return Err(DavError::Code(StatusCode::FORBIDDEN));
+            }
 
             // Update
             let event_ = self
@@ -181,6 +191,7 @@ impl CalendarUpdateRequestHandler for Server {
                 Err(e) => return Err(e),
             }
 
+            sanitize_managed_attachments(&mut ical, &event.inner.managed_attachments());
             if ical == event.inner.data.event {
                 // No changes, return existing event
                 return Ok(HttpResponse::new(StatusCode::NO_CONTENT));
@@ -339,6 +350,13 @@ impl CalendarUpdateRequestHandler for Server {
             {
                 return Err(DavError::Code(StatusCode::FORBIDDEN));
             }
+            if self
+                .is_subscribed_calendar(account_id, parent.document_id())
+                .await
+                .caused_by(trc::location!())?
+            {
+                return Err(DavError::Code(StatusCode::FORBIDDEN));
+            }
 
             // Validate headers
             self.validate_headers(
@@ -357,6 +375,7 @@ impl CalendarUpdateRequestHandler for Server {
             .await?;
 
             // Validate ical object
+            sanitize_managed_attachments(&mut ical, &Default::default());
             assert_is_unique_uid(
                 self,
                 &resources,
@@ -16,7 +16,7 @@ use dav_proto::{
         property::{DavProperty, ReportSet, ResourceType},
         request::{
             AddressbookQuery, CalendarQuery, ExpandProperty, Filter, MultiGet, PropFind,
-            SyncCollection, Timezone, VCardPropertyWithGroup,
+            SearchRequest, SyncCollection, Timezone, VCardPropertyWithGroup,
         },
     },
 };
@@ -75,7 +75,7 @@ pub(crate) enum DavQueryResource<'x> {
         hrefs: Vec<String>,
     },
     Query {
-        filter: DavQueryFilter,
+        filter: Option<DavQueryFilter>,
         parent_collection: Collection,
         items: Vec<PropFindItem>,
     },
@@ -206,7 +206,7 @@ impl<'x> DavQuery<'x> {
     ) -> Self {
         Self {
             resource: DavQueryResource::Query {
-                filter: DavQueryFilter::Addressbook(query.filters),
+                filter: Some(DavQueryFilter::Addressbook(query.filters)),
                 parent_collection: Collection::AddressBook,
                 items,
             },
@@ -230,11 +230,11 @@ impl<'x> DavQuery<'x> {
     ) -> Self {
         Self {
             resource: DavQueryResource::Query {
-                filter: DavQueryFilter::Calendar {
+                filter: Some(DavQueryFilter::Calendar {
                     filter: query.filters,
                     timezone: query.timezone,
                     max_time_range,
-                },
+                }),
                 parent_collection: Collection::Calendar,
                 items,
             },
@@ -250,6 +250,29 @@ impl<'x> DavQuery<'x> {
         }
     }
 
+    pub fn file_search(
+        request: SearchRequest,
+        items: Vec<PropFindItem>,
+        headers: &RequestHeaders<'x>,
+    ) -> Self {
+        Self {
+            resource: DavQueryResource::Query {
+                filter: None,
+                parent_collection: Collection::FileNode,
+                items,
+            },
+            propfind: request.properties,
+            limit: request.limit,
+            ret: headers.ret,
+            depth_no_root: headers.depth_no_root,
+            uri: headers.uri,
+            sync_type: Default::default(),
+            depth: Default::default(),
+            vcard_version: Default::default(),
+            expand: Default::default(),
+        }
+    }
+
     pub fn changes(
         resource: OwnedUri<'x>,
         changes: SyncCollection,
@@ -50,7 +50,9 @@ use dav_proto::{
         },
     },
 };
-use groupware::calendar::{SCHEDULE_INBOX_ID, SupportedComponent};
+use groupware::calendar::{
+    SCHEDULE_INBOX_ID, SupportedComponent, availability::CalendarAvailability,
+};
 use groupware::{
     DavCalendarResource, DavResourceName, cache::GroupwareCache, calendar::ArchivedTimezone,
 };
@@ -349,7 +351,7 @@ impl PropFindRequestHandler for Server {
                 parent_collection,
                 items,
             } => {
-                query_filter = Some(filter);
+                query_filter = filter;
                 collection_container = parent_collection;
                 collection_children = collection_container.child_collection().unwrap();
                 sync_collection = SyncCollection::from(collection_container);
@@ -1055,6 +1057,23 @@ impl PropFindRequestHandler for Server {
                                 fields_not_found.push(DavPropertyValue::empty(property.clone()));
                             }
                         }
+                        (
+                            CalDavProperty::CalendarAvailability,
+                            ArchivedResource::CalendarEventNotificationCollection(true),
+                        ) => {
+                            if let Some(availability) = self
+                                .calendar_availability(account_id)
+                                .await
+                                .caused_by(trc::location!())?
+                            {
+                                fields.push(DavPropertyValue::new(
+                                    property.clone(),
+                                    DavValue::CData(availability.to_string()),
+                                ));
+                            } else {
+                                fields_not_found.push(DavPropertyValue::empty(property.clone()));
+                            }
+                        }
 
                         _ => {
                             if !skip_not_found {
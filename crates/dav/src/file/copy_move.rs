@@ -627,12 +627,14 @@ async fn overwrite_and_delete_item(
         .etag();
     DestroyArchive(source_node_)
         .delete(
+            server,
             access_token.account_tenant_ids(),
             from_account_id,
             from_document_id,
             &mut batch,
             from_resource_path,
         )
+        .await
         .caused_by(trc::location!())?;
     server
         .commit_batch(batch)
@@ -776,12 +778,14 @@ async fn move_item(
             .etag();
         DestroyArchive(node)
             .delete(
+                server,
                 access_token.account_tenant_ids(),
                 from_account_id,
                 from_document_id,
                 &mut batch,
                 from_resource_path,
             )
+            .await
             .caused_by(trc::location!())?;
         etag
     };
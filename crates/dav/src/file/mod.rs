@@ -17,7 +17,10 @@ pub mod delete;
 pub mod get;
 pub mod mkcol;
 pub mod proppatch;
+pub mod search;
 pub mod update;
+pub mod upload;
+pub mod version;
 
 pub(crate) static FILE_CONTAINER_PROPS: [DavProperty; 19] = [
     DavProperty::WebDav(WebDavProperty::CreationDate),
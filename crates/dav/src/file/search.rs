@@ -0,0 +1,500 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    DavError,
+    common::{
+        DavQuery,
+        propfind::{PropFindItem, PropFindRequestHandler},
+        uri::DavUriResource,
+    },
+};
+use common::{Server, auth::AccessToken};
+use dav_proto::{
+    Depth, RequestHeaders,
+    schema::{
+        property::{DavProperty, WebDavProperty},
+        request::{SearchFilter, SearchOperator, SearchRequest, SearchScope},
+        response::MultiStatus,
+    },
+};
+use groupware::{cache::GroupwareCache, file::FileNode};
+use http_proto::HttpResponse;
+use hyper::StatusCode;
+use std::{borrow::Cow, cmp::Ordering};
+use store::{
+    ValueKey,
+    ahash::{AHashMap, AHashSet},
+    roaring::RoaringBitmap,
+    search::{self, FileSearchField, SearchQuery},
+    write::{AlignedBytes, Archive, SearchIndex},
+};
+use trc::AddContext;
+use types::{
+    acl::Acl,
+    collection::{Collection, SyncCollection},
+};
+
+pub(crate) trait FileSearchRequestHandler: Sync + Send {
+    fn handle_file_search_request(
+        &self,
+        access_token: &AccessToken,
+        headers: &RequestHeaders<'_>,
+        request: SearchRequest,
+    ) -> impl Future<Output = crate::Result<HttpResponse>> + Send;
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum FileValue<'x> {
+    Int(i64),
+    Text(Cow<'x, str>),
+}
+
+enum FilterState {
+    And(RoaringBitmap),
+    Or(RoaringBitmap),
+    Not(RoaringBitmap),
+}
+
+struct SearchResult {
+    item: PropFindItem,
+    node: Option<FileNode>,
+}
+
+impl FileSearchRequestHandler for Server {
+    async fn handle_file_search_request(
+        &self,
+        access_token: &AccessToken,
+        headers: &RequestHeaders<'_>,
+        request: SearchRequest,
+    ) -> crate::Result<HttpResponse> {
+        // Node metadata is only fetched when a filter or sort order requires it
+        let needs_nodes = request.filters.iter().any(|filter| {
+            matches!(
+                filter,
+                SearchFilter::Compare { .. } | SearchFilter::IsDefined(_)
+            )
+        }) || request
+            .order_by
+            .iter()
+            .any(|order| order.property.is_some());
+
+        let scopes = if !request.scope.is_empty() {
+            request
+                .scope
+                .iter()
+                .map(|scope| SearchScope {
+                    href: if scope.href.is_empty() {
+                        headers.uri.to_string()
+                    } else if !scope.href.contains("/dav/") {
+                        format!(
+                            "{}/{}",
+                            headers.uri.trim_end_matches('/'),
+                            scope.href.trim_start_matches('/')
+                        )
+                    } else {
+                        scope.href.clone()
+                    },
+                    depth: scope.depth,
+                })
+                .collect::<Vec<_>>()
+        } else {
+            vec![SearchScope {
+                href: headers.uri.to_string(),
+                depth: Depth::Infinity,
+            }]
+        };
+
+        let mut results = Vec::with_capacity(16);
+        let mut seen_ids = AHashSet::with_capacity(16);
+        for scope in &scopes {
+            // Validate scope
+            let resource = self
+                .validate_uri(access_token, &scope.href)
+                .await?
+                .into_owned_uri()?;
+            if resource.collection != Collection::FileNode {
+                return Err(DavError::Code(StatusCode::BAD_REQUEST));
+            }
+            let account_id = resource.account_id;
+            let resources = self
+                .fetch_dav_resources(
+                    access_token.account_id(),
+                    account_id,
+                    SyncCollection::FileNode,
+                )
+                .await
+                .caused_by(trc::location!())?;
+
+            // Obtain shared ids
+            let shared_ids = if !access_token.is_member(account_id) {
+                resources
+                    .shared_containers(access_token, [Acl::Read], true)
+                    .into()
+            } else {
+                None
+            };
+
+            // Obtain resources in scope
+            let depth = match scope.depth {
+                Depth::Zero => 0,
+                Depth::One => 1,
+                Depth::Infinity | Depth::None => usize::MAX,
+            };
+            let mut candidates = AHashMap::with_capacity(16);
+            if let Some(path) = resource.resource {
+                if resources.by_path(path).is_none() {
+                    return Ok(HttpResponse::new(StatusCode::MULTI_STATUS)
+                        .with_xml_body(MultiStatus::not_found(scope.href.as_str()).to_string()));
+                }
+                for item in resources.subtree_with_depth(path, depth) {
+                    candidates.insert(
+                        item.document_id(),
+                        PropFindItem::new(resources.format_resource(item), account_id, item),
+                    );
+                }
+            } else if depth > 0 {
+                for item in resources.tree_with_depth(depth - 1) {
+                    candidates.insert(
+                        item.document_id(),
+                        PropFindItem::new(resources.format_resource(item), account_id, item),
+                    );
+                }
+            }
+            if let Some(shared_ids) = &shared_ids {
+                candidates.retain(|document_id, _| shared_ids.contains(*document_id));
+            }
+            if candidates.is_empty() {
+                continue;
+            }
+            let candidate_ids = RoaringBitmap::from_iter(candidates.keys().copied());
+
+            // Fetch node metadata
+            let mut nodes = AHashMap::with_capacity(if needs_nodes { candidates.len() } else { 0 });
+            if needs_nodes {
+                for document_id in candidate_ids.iter() {
+                    if let Some(node) = self
+                        .store()
+                        .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                            account_id,
+                            Collection::FileNode,
+                            document_id,
+                        ))
+                        .await
+                        .caused_by(trc::location!())?
+                    {
+                        nodes.insert(
+                            document_id,
+                            node.deserialize::<FileNode>().caused_by(trc::location!())?,
+                        );
+                    }
+                }
+            }
+
+            // Run full-text searches
+            let mut text_matches = AHashMap::new();
+            for (pos, filter) in request.filters.iter().enumerate() {
+                if let SearchFilter::Contains(text) = filter {
+                    let document_ids = self
+                        .search_store()
+                        .query_account(
+                            SearchQuery::new(SearchIndex::File)
+                                .with_filters(vec![
+                                    search::SearchFilter::Or,
+                                    search::SearchFilter::has_keyword(
+                                        FileSearchField::Name,
+                                        text.clone(),
+                                    ),
+                                    search::SearchFilter::has_text_detect(
+                                        FileSearchField::Content,
+                                        text.clone(),
+                                        self.core.email.default_language,
+                                    ),
+                                    search::SearchFilter::End,
+                                ])
+                                .with_account_id(account_id)
+                                .with_mask(candidate_ids.clone()),
+                        )
+                        .await
+                        .caused_by(trc::location!())?;
+                    text_matches.insert(pos, RoaringBitmap::from_iter(document_ids));
+                }
+            }
+
+            // Apply filters
+            let container_ids = RoaringBitmap::from_iter(
+                candidates
+                    .values()
+                    .filter(|item| item.is_container)
+                    .map(|item| item.document_id),
+            );
+            let matches = evaluate_filters(
+                &request.filters,
+                &candidate_ids,
+                &container_ids,
+                &nodes,
+                &mut text_matches,
+            );
+
+            for document_id in matches.iter() {
+                if seen_ids.insert((account_id, document_id))
+                    && let Some(item) = candidates.remove(&document_id)
+                {
+                    results.push(SearchResult {
+                        item,
+                        node: nodes.remove(&document_id),
+                    });
+                }
+            }
+        }
+
+        // Sort results
+        if request
+            .order_by
+            .iter()
+            .any(|order| order.property.is_some())
+        {
+            results.sort_by(|a, b| {
+                for order in &request.order_by {
+                    let Some(property) = &order.property else {
+                        continue;
+                    };
+                    let a = a
+                        .node
+                        .as_ref()
+                        .and_then(|node| file_value(node, property, order.caseless));
+                    let b = b
+                        .node
+                        .as_ref()
+                        .and_then(|node| file_value(node, property, order.caseless));
+                    let ordering = if order.ascending {
+                        a.cmp(&b)
+                    } else {
+                        b.cmp(&a)
+                    };
+                    if ordering != Ordering::Equal {
+                        return ordering;
+                    }
+                }
+                Ordering::Equal
+            });
+        }
+
+        self.handle_dav_query(
+            access_token,
+            DavQuery::file_search(
+                request,
+                results.into_iter().map(|result| result.item).collect(),
+                headers,
+            ),
+        )
+        .await
+    }
+}
+
+fn evaluate_filters(
+    filters: &[SearchFilter],
+    candidate_ids: &RoaringBitmap,
+    container_ids: &RoaringBitmap,
+    nodes: &AHashMap<u32, FileNode>,
+    text_matches: &mut AHashMap<usize, RoaringBitmap>,
+) -> RoaringBitmap {
+    let mut stack = vec![FilterState::And(candidate_ids.clone())];
+
+    for (pos, filter) in filters.iter().enumerate() {
+        let result = match filter {
+            SearchFilter::And => {
+                stack.push(FilterState::And(candidate_ids.clone()));
+                continue;
+            }
+            SearchFilter::Or => {
+                stack.push(FilterState::Or(RoaringBitmap::new()));
+                continue;
+            }
+            SearchFilter::Not => {
+                stack.push(FilterState::Not(candidate_ids.clone()));
+                continue;
+            }
+            SearchFilter::End => {
+                if stack.len() == 1 {
+                    continue;
+                }
+                match stack.pop().unwrap() {
+                    FilterState::And(result) | FilterState::Or(result) => result,
+                    FilterState::Not(result) => candidate_ids - &result,
+                }
+            }
+            SearchFilter::Contains(_) => text_matches.remove(&pos).unwrap_or_default(),
+            SearchFilter::IsCollection => container_ids.clone(),
+            SearchFilter::IsDefined(property) => {
+                RoaringBitmap::from_iter(candidate_ids.iter().filter(|document_id| {
+                    nodes
+                        .get(document_id)
+                        .and_then(|node| file_value(node, property, false))
+                        .is_some()
+                }))
+            }
+            SearchFilter::Compare {
+                property,
+                op,
+                value,
+                caseless,
+            } => match parse_literal(property, value, *caseless) {
+                Some(literal) => {
+                    RoaringBitmap::from_iter(candidate_ids.iter().filter(|document_id| {
+                        nodes
+                            .get(document_id)
+                            .and_then(|node| file_value(node, property, *caseless))
+                            .is_some_and(|value| compare(&value, *op, &literal))
+                    }))
+                }
+                None => RoaringBitmap::new(),
+            },
+        };
+
+        match stack.last_mut().unwrap() {
+            FilterState::Or(matches) => *matches |= result,
+            FilterState::And(matches) | FilterState::Not(matches) => *matches &= result,
+        }
+    }
+
+    // Close any unterminated operators
+    while stack.len() > 1 {
+        let result = match stack.pop().unwrap() {
+            FilterState::And(result) | FilterState::Or(result) => result,
+            FilterState::Not(result) => candidate_ids - &result,
+        };
+        match stack.last_mut().unwrap() {
+            FilterState::Or(matches) => *matches |= result,
+            FilterState::And(matches) | FilterState::Not(matches) => *matches &= result,
+        }
+    }
+
+    match stack.pop().unwrap() {
+        FilterState::And(result) | FilterState::Or(result) | FilterState::Not(result) => result,
+    }
+}
+
+fn file_value<'x>(
+    node: &'x FileNode,
+    property: &DavProperty,
+    caseless: bool,
+) -> Option<FileValue<'x>> {
+    let text = match property {
+        DavProperty::WebDav(WebDavProperty::DisplayName) => {
+            node.display_name.as_deref().unwrap_or(node.name.as_str())
+        }
+        DavProperty::WebDav(WebDavProperty::GetContentType) => {
+            node.file.as_ref()?.media_type.as_deref()?
+        }
+        DavProperty::WebDav(WebDavProperty::GetContentLength) => {
+            return node.file.as_ref().map(|f| FileValue::Int(f.size as i64));
+        }
+        DavProperty::WebDav(WebDavProperty::GetLastModified) => {
+            return Some(FileValue::Int(node.modified));
+        }
+        DavProperty::WebDav(WebDavProperty::CreationDate) => {
+            return Some(FileValue::Int(node.created));
+        }
+        _ => return None,
+    };
+
+    Some(FileValue::Text(if caseless {
+        text.to_lowercase().into()
+    } else {
+        text.into()
+    }))
+}
+
+fn parse_literal<'x>(
+    property: &DavProperty,
+    value: &'x str,
+    caseless: bool,
+) -> Option<FileValue<'x>> {
+    let value = value.trim();
+
+    match property {
+        DavProperty::WebDav(WebDavProperty::DisplayName | WebDavProperty::GetContentType) => {
+            Some(FileValue::Text(if caseless {
+                value.to_lowercase().into()
+            } else {
+                value.into()
+            }))
+        }
+        DavProperty::WebDav(WebDavProperty::GetContentLength) => {
+            value.parse::<i64>().ok().map(FileValue::Int)
+        }
+        DavProperty::WebDav(WebDavProperty::GetLastModified | WebDavProperty::CreationDate) => {
+            value
+                .parse::<i64>()
+                .ok()
+                .or_else(|| {
+                    chrono::DateTime::parse_from_rfc2822(value)
+                        .or_else(|_| chrono::DateTime::parse_from_rfc3339(value))
+                        .ok()
+                        .map(|dt| dt.timestamp())
+                })
+                .map(FileValue::Int)
+        }
+        _ => None,
+    }
+}
+
+fn compare(value: &FileValue<'_>, op: SearchOperator, literal: &FileValue<'_>) -> bool {
+    match op {
+        SearchOperator::Eq => value == literal,
+        SearchOperator::Lt => value < literal,
+        SearchOperator::Lte => value <= literal,
+        SearchOperator::Gt => value > literal,
+        SearchOperator::Gte => value >= literal,
+        SearchOperator::Like => match (value, literal) {
+            (FileValue::Text(value), FileValue::Text(pattern)) => like_matches(value, pattern),
+            _ => false,
+        },
+    }
+}
+
+fn like_matches(value: &str, pattern: &str) -> bool {
+    let value = value.chars().collect::<Vec<_>>();
+    let mut tokens = Vec::with_capacity(pattern.len());
+    let mut chars = pattern.chars();
+    while let Some(ch) = chars.next() {
+        tokens.push(match ch {
+            '%' => None,
+            '_' => Some(None),
+            '\\' => Some(Some(chars.next().unwrap_or('\\'))),
+            ch => Some(Some(ch)),
+        });
+    }
+
+    let mut v_pos = 0;
+    let mut t_pos = 0;
+    let mut backtrack = None;
+
+    while v_pos < value.len() {
+        match tokens.get(t_pos) {
+            Some(Some(token)) if token.is_none_or(|ch| ch == value[v_pos]) => {
+                v_pos += 1;
+                t_pos += 1;
+            }
+            Some(None) => {
+                backtrack = Some((t_pos, v_pos));
+                t_pos += 1;
+            }
+            _ => {
+                if let Some((b_t_pos, b_v_pos)) = backtrack {
+                    t_pos = b_t_pos + 1;
+                    v_pos = b_v_pos + 1;
+                    backtrack = Some((b_t_pos, v_pos));
+                } else {
+                    return false;
+                }
+            }
+        }
+    }
+
+    tokens[t_pos..].iter().all(|token| token.is_none())
+}
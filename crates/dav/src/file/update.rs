@@ -20,7 +20,10 @@ use common::{
 use dav_proto::{RequestHeaders, Return, schema::property::Rfc1123DateTime};
 use groupware::{
     cache::GroupwareCache,
-    file::{FileNode, FileProperties},
+    file::{
+        FileNode, FileProperties,
+        version::{FileVersion, FileVersioning},
+    },
 };
 use http_proto::HttpResponse;
 use hyper::StatusCode;
@@ -162,9 +165,13 @@ impl FileUpdateRequestHandler for Server {
                 return Err(DavError::Code(StatusCode::METHOD_NOT_ALLOWED));
             }
 
-            // Validate quota
-            let extra_bytes = (bytes.len() as u64)
-                .saturating_sub(u32::from(node.inner.file.as_ref().unwrap().size) as u64);
+            // Validate quota, the previous contents are kept when versioning is enabled
+            let extra_bytes = if self.core.groupware.max_file_versions > 0 {
+                bytes.len() as u64
+            } else {
+                (bytes.len() as u64)
+                    .saturating_sub(u32::from(node.inner.file.as_ref().unwrap().size) as u64)
+            };
             if extra_bytes > 0 {
                 self.has_available_quota(self.account(account_id).await?.as_ref(), extra_bytes)
                     .await?;
@@ -177,9 +184,10 @@ impl FileUpdateRequestHandler for Server {
                 .caused_by(trc::location!())?;
 
             // Build node
+            let previous = FileVersion::from_node(node.inner).unwrap();
             let mut new_node = node.deserialize::<FileNode>().caused_by(trc::location!())?;
             let new_file = new_node.file.as_mut().unwrap();
-            new_file.blob_hash = blob_hash;
+            new_file.blob_hash = blob_hash.clone();
             new_file.media_type = headers
                 .content_type
                 .filter(|ct| !ct.is_empty() && *ct != "application/octet-stream")
@@ -201,6 +209,16 @@ impl FileUpdateRequestHandler for Server {
                         .with_changed_by(access_token.account_tenant_ids()),
                 )
                 .caused_by(trc::location!())?;
+            self.archive_file_version(
+                account_id,
+                document_id,
+                previous,
+                &blob_hash,
+                access_token.account_tenant_ids(),
+                &mut batch,
+            )
+            .await
+            .caused_by(trc::location!())?;
             let etag = batch.etag();
             self.commit_batch(batch).await.caused_by(trc::location!())?;
 
@@ -0,0 +1,420 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    DavError, DavMethod,
+    common::uri::DavUriResource,
+    file::{update::FileUpdateRequestHandler, version::prop_stat},
+};
+use common::{KV_UPLOAD_DAV, Server, auth::AccessToken};
+use dav_proto::{
+    Depth, RequestHeaders, Return,
+    parser::{DavParser, tokenizer::Tokenizer},
+    schema::{
+        property::{DavProperty, DavPropertyValue, DavValue, ResourceType, WebDavProperty},
+        request::PropFind,
+        response::{MultiStatus, Response},
+    },
+};
+use http_proto::{HttpResponse, request::decode_path_element};
+use hyper::StatusCode;
+use store::{
+    Serialize, U32_LEN,
+    dispatch::lookup::KeyValue,
+    write::{AlignedBytes, Archive, Archiver, now},
+};
+use trc::AddContext;
+use types::{blob_hash::BlobHash, collection::Collection};
+
+pub(crate) const UPLOADS_PATH: &str = "/dav/uploads/";
+
+const MAX_CHUNKS: u32 = 10000;
+const MAX_TRANSFER_ID_LEN: usize = 64;
+const ASSEMBLE_RESOURCE: &str = ".file";
+
+static UPLOAD_PROPS: [DavProperty; 2] = [
+    DavProperty::WebDav(WebDavProperty::ResourceType),
+    DavProperty::WebDav(WebDavProperty::GetContentLength),
+];
+
+#[derive(Debug, rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+struct UploadSession {
+    destination: String,
+    total_length: u64,
+    expires: u64,
+}
+
+#[derive(Debug, rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+struct UploadChunk {
+    hash: BlobHash,
+    size: u64,
+}
+
+pub(crate) trait FileUploadRequestHandler: Sync + Send {
+    fn handle_file_upload_request(
+        &self,
+        access_token: &AccessToken,
+        headers: &RequestHeaders<'_>,
+        method: DavMethod,
+        body: Vec<u8>,
+    ) -> impl Future<Output = crate::Result<HttpResponse>> + Send;
+}
+
+impl FileUploadRequestHandler for Server {
+    async fn handle_file_upload_request(
+        &self,
+        access_token: &AccessToken,
+        headers: &RequestHeaders<'_>,
+        method: DavMethod,
+        body: Vec<u8>,
+    ) -> crate::Result<HttpResponse> {
+        // Parse upload path
+        let mut uri_parts = headers
+            .uri
+            .strip_prefix(UPLOADS_PATH)
+            .unwrap_or_default()
+            .trim_end_matches('/')
+            .splitn(3, '/')
+            .filter(|x| !x.is_empty());
+        let account = uri_parts
+            .next()
+            .ok_or(DavError::Code(StatusCode::NOT_FOUND))?;
+        let account_id = if let Some(account_id) = account.strip_prefix('_') {
+            account_id
+                .parse::<u32>()
+                .map_err(|_| DavError::Code(StatusCode::NOT_FOUND))?
+        } else {
+            self.account_id_from_email(&decode_path_element(account), false)
+                .await
+                .caused_by(trc::location!())?
+                .ok_or(DavError::Code(StatusCode::NOT_FOUND))?
+        };
+
+        // Uploads are staged in the owner's home only
+        if account_id != access_token.account_id() {
+            return Err(DavError::Code(StatusCode::FORBIDDEN));
+        }
+
+        let Some(transfer_id) = uri_parts.next() else {
+            return match method {
+                DavMethod::PROPFIND => upload_propfind(headers, &body, Vec::new()),
+                _ => Err(DavError::Code(StatusCode::METHOD_NOT_ALLOWED)),
+            };
+        };
+        if transfer_id.len() > MAX_TRANSFER_ID_LEN
+            || !transfer_id
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_'))
+        {
+            return Err(DavError::Code(StatusCode::BAD_REQUEST));
+        }
+
+        match (method, uri_parts.next()) {
+            (DavMethod::MKCOL, None) => {
+                // Validate destination and declared length
+                let destination = headers
+                    .destination
+                    .ok_or(DavError::Code(StatusCode::BAD_REQUEST))?;
+                let total_length = headers
+                    .total_length
+                    .ok_or(DavError::Code(StatusCode::BAD_REQUEST))?;
+                let destination_account_id =
+                    validate_destination(self, access_token, destination).await?;
+                if total_length > self.core.groupware.max_file_size as u64 {
+                    return Err(DavError::Code(StatusCode::PAYLOAD_TOO_LARGE));
+                }
+                if total_length > 0 {
+                    self.has_available_quota(
+                        self.account(destination_account_id).await?.as_ref(),
+                        total_length,
+                    )
+                    .await?;
+                }
+
+                // Create upload session
+                let session_key = upload_key(account_id, 0, transfer_id);
+                if self
+                    .in_memory_store()
+                    .key_get::<Archive<AlignedBytes>>(session_key.as_slice())
+                    .await
+                    .caused_by(trc::location!())?
+                    .is_some()
+                {
+                    return Err(DavError::Code(StatusCode::METHOD_NOT_ALLOWED));
+                }
+                let ttl = self.core.jmap.upload_tmp_ttl;
+                self.in_memory_store()
+                    .key_set(
+                        KeyValue::new(
+                            session_key,
+                            Archiver::new(UploadSession {
+                                destination: destination.to_string(),
+                                total_length,
+                                expires: now() + ttl,
+                            })
+                            .untrusted()
+                            .serialize()
+                            .caused_by(trc::location!())?,
+                        )
+                        .expires(ttl),
+                    )
+                    .await
+                    .caused_by(trc::location!())?;
+
+                Ok(HttpResponse::new(StatusCode::CREATED))
+            }
+            (DavMethod::PUT, Some(chunk)) => {
+                let chunk_id = chunk
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|id| (1..=MAX_CHUNKS).contains(id))
+                    .ok_or(DavError::Code(StatusCode::BAD_REQUEST))?;
+                let session_ = fetch_session(self, account_id, transfer_id).await?;
+                let session = session_
+                    .unarchive::<UploadSession>()
+                    .caused_by(trc::location!())?;
+                if body.len() as u64 > u64::from(session.total_length)
+                    || body.len() > self.core.groupware.max_file_size
+                {
+                    return Err(DavError::Code(StatusCode::PAYLOAD_TOO_LARGE));
+                }
+
+                // Stage chunk as a temporary blob, expired by the blob cleanup task
+                // unless the upload is assembled before the session expires
+                let expires_in = u64::from(session.expires).saturating_sub(now());
+                if expires_in == 0 {
+                    return Err(DavError::Code(StatusCode::NOT_FOUND));
+                }
+                let (hash, _) = self
+                    .put_temporary_blob(account_id, &body, expires_in)
+                    .await
+                    .caused_by(trc::location!())?;
+                self.in_memory_store()
+                    .key_set(
+                        KeyValue::new(
+                            upload_key(account_id, chunk_id, transfer_id),
+                            Archiver::new(UploadChunk {
+                                hash,
+                                size: body.len() as u64,
+                            })
+                            .untrusted()
+                            .serialize()
+                            .caused_by(trc::location!())?,
+                        )
+                        .expires(expires_in),
+                    )
+                    .await
+                    .caused_by(trc::location!())?;
+
+                Ok(HttpResponse::new(StatusCode::CREATED))
+            }
+            (DavMethod::PROPFIND, None) => {
+                fetch_session(self, account_id, transfer_id).await?;
+                let mut chunks = Vec::new();
+                if headers.depth != Depth::Zero {
+                    for chunk_id in 1..=MAX_CHUNKS {
+                        let Some(chunk) =
+                            fetch_chunk(self, account_id, chunk_id, transfer_id).await?
+                        else {
+                            break;
+                        };
+                        chunks.push((chunk_id, chunk.1));
+                    }
+                }
+
+                upload_propfind(headers, &body, chunks)
+            }
+            (DavMethod::MOVE, Some(ASSEMBLE_RESOURCE)) => {
+                let session_ = fetch_session(self, account_id, transfer_id).await?;
+                let session = session_
+                    .unarchive::<UploadSession>()
+                    .caused_by(trc::location!())?;
+                let destination = headers
+                    .destination
+                    .unwrap_or_else(|| session.destination.as_str());
+                validate_destination(self, access_token, destination).await?;
+
+                // Assemble chunks in order
+                let total_length = u64::from(session.total_length);
+                let mut bytes = Vec::with_capacity(total_length as usize);
+                let mut num_chunks = 0;
+                while (bytes.len() as u64) < total_length {
+                    num_chunks += 1;
+                    let (hash, size) = fetch_chunk(self, account_id, num_chunks, transfer_id)
+                        .await?
+                        .ok_or(DavError::Code(StatusCode::BAD_REQUEST))?;
+                    let contents = self
+                        .blob_store()
+                        .get_blob(hash.as_slice(), 0..usize::MAX)
+                        .await
+                        .caused_by(trc::location!())?
+                        .filter(|contents| contents.len() as u64 == size)
+                        .ok_or(DavError::Code(StatusCode::BAD_REQUEST))?;
+                    bytes.extend_from_slice(&contents);
+                }
+                if bytes.len() as u64 != total_length {
+                    return Err(DavError::Code(StatusCode::BAD_REQUEST));
+                }
+
+                // Write the destination file
+                let mut put_headers = RequestHeaders::new(destination);
+                put_headers.content_type = headers.content_type;
+                put_headers.if_ = headers.if_.clone();
+                put_headers.ret = headers.ret;
+                let response = self
+                    .handle_file_update_request(access_token, &put_headers, bytes, false)
+                    .await?;
+                delete_upload(self, account_id, transfer_id).await?;
+
+                Ok(response)
+            }
+            (DavMethod::DELETE, None) => {
+                fetch_session(self, account_id, transfer_id).await?;
+                delete_upload(self, account_id, transfer_id).await?;
+
+                Ok(HttpResponse::new(StatusCode::NO_CONTENT))
+            }
+            _ => Err(DavError::Code(StatusCode::METHOD_NOT_ALLOWED)),
+        }
+    }
+}
+
+async fn validate_destination(
+    server: &Server,
+    access_token: &AccessToken,
+    destination: &str,
+) -> crate::Result<u32> {
+    let destination = server
+        .validate_uri_with_status(access_token, destination, StatusCode::BAD_GATEWAY)
+        .await?;
+    if destination.collection != Collection::FileNode || destination.resource.is_none() {
+        return Err(DavError::Code(StatusCode::BAD_GATEWAY));
+    }
+
+    destination
+        .account_id
+        .ok_or(DavError::Code(StatusCode::BAD_GATEWAY))
+}
+
+async fn fetch_session(
+    server: &Server,
+    account_id: u32,
+    transfer_id: &str,
+) -> crate::Result<Archive<AlignedBytes>> {
+    server
+        .in_memory_store()
+        .key_get::<Archive<AlignedBytes>>(upload_key(account_id, 0, transfer_id).as_slice())
+        .await
+        .caused_by(trc::location!())?
+        .ok_or(DavError::Code(StatusCode::NOT_FOUND))
+}
+
+async fn fetch_chunk(
+    server: &Server,
+    account_id: u32,
+    chunk_id: u32,
+    transfer_id: &str,
+) -> crate::Result<Option<(BlobHash, u64)>> {
+    if let Some(chunk) = server
+        .in_memory_store()
+        .key_get::<Archive<AlignedBytes>>(upload_key(account_id, chunk_id, transfer_id).as_slice())
+        .await
+        .caused_by(trc::location!())?
+    {
+        let chunk = chunk
+            .unarchive::<UploadChunk>()
+            .caused_by(trc::location!())?;
+        Ok(Some((BlobHash::from(&chunk.hash), u64::from(chunk.size))))
+    } else {
+        Ok(None)
+    }
+}
+
+async fn delete_upload(server: &Server, account_id: u32, transfer_id: &str) -> trc::Result<()> {
+    // Staged blobs are not released here, their temporary links expire on their own
+    for chunk_id in 0..=MAX_CHUNKS {
+        let key = upload_key(account_id, chunk_id, transfer_id);
+        if chunk_id > 0
+            && server
+                .in_memory_store()
+                .key_get::<Archive<AlignedBytes>>(key.as_slice())
+                .await
+                .caused_by(trc::location!())?
+                .is_none()
+        {
+            break;
+        }
+        server
+            .in_memory_store()
+            .key_delete(key)
+            .await
+            .caused_by(trc::location!())?;
+    }
+
+    Ok(())
+}
+
+fn upload_propfind(
+    headers: &RequestHeaders<'_>,
+    body: &[u8],
+    chunks: Vec<(u32, u64)>,
+) -> crate::Result<HttpResponse> {
+    let request = PropFind::parse(&mut Tokenizer::new(body))?;
+    let (properties, is_propname) = match &request {
+        PropFind::PropName => (UPLOAD_PROPS.to_vec(), true),
+        PropFind::AllProp(_) => (UPLOAD_PROPS.to_vec(), false),
+        PropFind::Prop(items) => (items.clone(), false),
+    };
+    let skip_not_found = !matches!(request, PropFind::Prop(_)) || headers.ret == Return::Minimal;
+    let base_href = headers.uri.trim_end_matches('/');
+    let mut response = MultiStatus::new(Vec::with_capacity(chunks.len() + 1));
+
+    for (name, size) in [(None, None)].into_iter().chain(
+        chunks
+            .into_iter()
+            .map(|(chunk_id, size)| (Some(chunk_id), Some(size))),
+    ) {
+        let mut fields = Vec::with_capacity(properties.len());
+        let mut fields_not_found = Vec::new();
+        for property in &properties {
+            let value = match property {
+                _ if is_propname => None,
+                DavProperty::WebDav(WebDavProperty::ResourceType) => size
+                    .is_none()
+                    .then(|| DavValue::from(vec![ResourceType::Collection])),
+                DavProperty::WebDav(WebDavProperty::GetContentLength) if size.is_some() => {
+                    size.map(DavValue::Uint64)
+                }
+                _ => {
+                    fields_not_found.push(DavPropertyValue::empty(property.clone()));
+                    continue;
+                }
+            };
+            fields.push(match value {
+                Some(value) => DavPropertyValue::new(property.clone(), value),
+                None => DavPropertyValue::empty(property.clone()),
+            });
+        }
+        response.add_response(Response::new_propstat(
+            match name {
+                Some(chunk_id) => format!("{base_href}/{chunk_id}"),
+                None => format!("{base_href}/"),
+            },
+            prop_stat(fields, fields_not_found, skip_not_found),
+        ));
+    }
+
+    Ok(HttpResponse::new(StatusCode::MULTI_STATUS).with_xml_body(response.to_string()))
+}
+
+fn upload_key(account_id: u32, chunk_id: u32, transfer_id: &str) -> Vec<u8> {
+    let mut key = Vec::with_capacity(1 + U32_LEN * 2 + transfer_id.len());
+    key.push(KV_UPLOAD_DAV);
+    key.extend_from_slice(account_id.to_be_bytes().as_slice());
+    key.extend_from_slice(chunk_id.to_be_bytes().as_slice());
+    key.extend_from_slice(transfer_id.as_bytes());
+    key
+}
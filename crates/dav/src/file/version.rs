@@ -0,0 +1,419 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    DavError, DavMethod,
+    common::{
+        ETag, ExtractETag,
+        lock::{LockRequestHandler, ResourceState},
+        uri::DavUriResource,
+    },
+    file::DavFileResource,
+};
+use common::{
+    Server, auth::AccessToken, sharing::EffectiveAcl, storage::index::ObjectIndexBuilder,
+};
+use dav_proto::{
+    Depth, RequestHeaders, Return,
+    schema::{
+        property::{
+            DavProperty, DavPropertyValue, DavValue, ResourceType, Rfc1123DateTime, WebDavProperty,
+        },
+        request::PropFind,
+        response::{MultiStatus, PropStat, Response},
+    },
+};
+use groupware::{
+    cache::GroupwareCache,
+    file::{
+        FileNode,
+        version::{FileVersion, FileVersioning, FileVersions},
+    },
+};
+use http_proto::HttpResponse;
+use hyper::StatusCode;
+use store::{
+    ValueKey,
+    write::{AlignedBytes, Archive, BatchBuilder, now},
+};
+use trc::AddContext;
+use types::{
+    acl::Acl,
+    blob_hash::BlobHash,
+    collection::{Collection, SyncCollection},
+};
+
+static FILE_VERSION_PROPS: [DavProperty; 6] = [
+    DavProperty::WebDav(WebDavProperty::CreationDate),
+    DavProperty::WebDav(WebDavProperty::GetETag),
+    DavProperty::WebDav(WebDavProperty::GetLastModified),
+    DavProperty::WebDav(WebDavProperty::ResourceType),
+    DavProperty::WebDav(WebDavProperty::GetContentLength),
+    DavProperty::WebDav(WebDavProperty::GetContentType),
+];
+
+pub(crate) trait FileVersionRequestHandler: Sync + Send {
+    fn handle_file_versions_propfind(
+        &self,
+        access_token: &AccessToken,
+        headers: &RequestHeaders<'_>,
+        request: PropFind,
+    ) -> impl Future<Output = crate::Result<HttpResponse>> + Send;
+
+    fn handle_file_version_get(
+        &self,
+        access_token: &AccessToken,
+        headers: &RequestHeaders<'_>,
+        version_id: &str,
+        is_head: bool,
+    ) -> impl Future<Output = crate::Result<HttpResponse>> + Send;
+
+    fn handle_file_version_restore(
+        &self,
+        access_token: &AccessToken,
+        headers: &RequestHeaders<'_>,
+        version_id: &str,
+    ) -> impl Future<Output = crate::Result<HttpResponse>> + Send;
+}
+
+struct VersionedFile {
+    account_id: u32,
+    document_id: u32,
+    path: String,
+    node: Archive<AlignedBytes>,
+    versions: Option<Archive<AlignedBytes>>,
+}
+
+impl FileVersionRequestHandler for Server {
+    async fn handle_file_versions_propfind(
+        &self,
+        access_token: &AccessToken,
+        headers: &RequestHeaders<'_>,
+        request: PropFind,
+    ) -> crate::Result<HttpResponse> {
+        let file = fetch_versioned_file(self, access_token, headers, Acl::Read).await?;
+        let node = file
+            .node
+            .unarchive::<FileNode>()
+            .caused_by(trc::location!())?;
+        let versions = file
+            .versions
+            .as_ref()
+            .map(|v| v.unarchive::<FileVersions>())
+            .transpose()
+            .caused_by(trc::location!())?;
+
+        let (properties, is_propname) = match &request {
+            PropFind::PropName => (FILE_VERSION_PROPS.to_vec(), true),
+            PropFind::AllProp(_) => (FILE_VERSION_PROPS.to_vec(), false),
+            PropFind::Prop(items) => (items.clone(), false),
+        };
+        let skip_not_found =
+            !matches!(request, PropFind::Prop(_)) || headers.ret == Return::Minimal;
+        let base_href = format!("{}?versions", headers.uri);
+        let mut response = MultiStatus::new(Vec::with_capacity(4));
+
+        // Add versions collection
+        let mut fields = Vec::with_capacity(properties.len());
+        let mut fields_not_found = Vec::new();
+        for property in &properties {
+            let value = match property {
+                _ if is_propname => None,
+                DavProperty::WebDav(WebDavProperty::ResourceType) => {
+                    Some(DavValue::from(vec![ResourceType::Collection]))
+                }
+                DavProperty::WebDav(WebDavProperty::GetLastModified) => Some(
+                    DavValue::Rfc1123Date(Rfc1123DateTime::new(i64::from(node.modified))),
+                ),
+                DavProperty::WebDav(WebDavProperty::GetETag) => {
+                    Some(DavValue::String(file.node.etag()))
+                }
+                _ => {
+                    fields_not_found.push(DavPropertyValue::empty(property.clone()));
+                    continue;
+                }
+            };
+            fields.push(match value {
+                Some(value) => DavPropertyValue::new(property.clone(), value),
+                None => DavPropertyValue::empty(property.clone()),
+            });
+        }
+        response.add_response(Response::new_propstat(
+            base_href.clone(),
+            prop_stat(fields, fields_not_found, skip_not_found),
+        ));
+
+        // Add versions
+        if headers.depth != Depth::Zero
+            && let Some(versions) = versions
+        {
+            for version in versions.versions.iter().rev() {
+                let mut fields = Vec::with_capacity(properties.len());
+                let mut fields_not_found = Vec::new();
+                for property in &properties {
+                    let value = match property {
+                        _ if is_propname => None,
+                        DavProperty::WebDav(WebDavProperty::ResourceType) => None,
+                        DavProperty::WebDav(WebDavProperty::CreationDate) => {
+                            Some(DavValue::Timestamp(i64::from(version.replaced)))
+                        }
+                        DavProperty::WebDav(WebDavProperty::GetLastModified) => {
+                            Some(DavValue::Rfc1123Date(Rfc1123DateTime::new(i64::from(
+                                version.modified,
+                            ))))
+                        }
+                        DavProperty::WebDav(WebDavProperty::GetETag) => Some(DavValue::String(
+                            version_etag(&BlobHash::from(&version.blob_hash)),
+                        )),
+                        DavProperty::WebDav(WebDavProperty::GetContentLength) => {
+                            Some(DavValue::Uint64(u32::from(version.size) as u64))
+                        }
+                        DavProperty::WebDav(WebDavProperty::GetContentType) => {
+                            Some(DavValue::String(
+                                version
+                                    .media_type
+                                    .as_ref()
+                                    .map(|t| t.as_str())
+                                    .unwrap_or("application/octet-stream")
+                                    .to_string(),
+                            ))
+                        }
+                        _ => {
+                            fields_not_found.push(DavPropertyValue::empty(property.clone()));
+                            continue;
+                        }
+                    };
+                    fields.push(match value {
+                        Some(value) => DavPropertyValue::new(property.clone(), value),
+                        None => DavPropertyValue::empty(property.clone()),
+                    });
+                }
+                response.add_response(Response::new_propstat(
+                    format!("{}?version={}", headers.uri, version.id),
+                    prop_stat(fields, fields_not_found, skip_not_found),
+                ));
+            }
+        }
+
+        Ok(HttpResponse::new(StatusCode::MULTI_STATUS).with_xml_body(response.to_string()))
+    }
+
+    async fn handle_file_version_get(
+        &self,
+        access_token: &AccessToken,
+        headers: &RequestHeaders<'_>,
+        version_id: &str,
+        is_head: bool,
+    ) -> crate::Result<HttpResponse> {
+        let file = fetch_versioned_file(self, access_token, headers, Acl::Read).await?;
+        let versions = file
+            .versions
+            .as_ref()
+            .ok_or(DavError::Code(StatusCode::NOT_FOUND))?
+            .unarchive::<FileVersions>()
+            .caused_by(trc::location!())?;
+        let version = version_id
+            .parse::<u32>()
+            .ok()
+            .and_then(|id| versions.by_id(id))
+            .ok_or(DavError::Code(StatusCode::NOT_FOUND))?;
+        let blob_hash = BlobHash::from(&version.blob_hash);
+
+        let response = HttpResponse::new(StatusCode::OK)
+            .with_content_type(
+                version
+                    .media_type
+                    .as_ref()
+                    .map(|t| t.as_str())
+                    .unwrap_or("application/octet-stream"),
+            )
+            .with_etag(version_etag(&blob_hash))
+            .with_last_modified(Rfc1123DateTime::new(i64::from(version.modified)).to_string());
+
+        if is_head {
+            return Ok(response.with_content_length(u32::from(version.size) as usize));
+        }
+
+        let contents = self
+            .blob_store()
+            .get_blob(blob_hash.as_slice(), 0..usize::MAX)
+            .await
+            .caused_by(trc::location!())?
+            .ok_or(DavError::Code(StatusCode::NOT_FOUND))?;
+
+        Ok(response.with_binary_body(contents))
+    }
+
+    async fn handle_file_version_restore(
+        &self,
+        access_token: &AccessToken,
+        headers: &RequestHeaders<'_>,
+        version_id: &str,
+    ) -> crate::Result<HttpResponse> {
+        let file = fetch_versioned_file(self, access_token, headers, Acl::Modify).await?;
+        let node = file
+            .node
+            .to_unarchived::<FileNode>()
+            .caused_by(trc::location!())?;
+
+        // Validate headers
+        self.validate_headers(
+            access_token,
+            headers,
+            vec![ResourceState {
+                account_id: file.account_id,
+                collection: Collection::FileNode,
+                document_id: Some(file.document_id),
+                etag: file.node.etag().into(),
+                path: file.path.as_str(),
+                ..Default::default()
+            }],
+            Default::default(),
+            DavMethod::POST,
+        )
+        .await?;
+
+        let versions = file
+            .versions
+            .as_ref()
+            .ok_or(DavError::Code(StatusCode::NOT_FOUND))?
+            .unarchive::<FileVersions>()
+            .caused_by(trc::location!())?;
+        let version = version_id
+            .parse::<u32>()
+            .ok()
+            .and_then(|id| versions.by_id(id))
+            .ok_or(DavError::Code(StatusCode::NOT_FOUND))?;
+        let blob_hash = BlobHash::from(&version.blob_hash);
+
+        // Validate quota, restoring adds the current contents to the version history
+        if self.core.groupware.max_file_versions > 0 {
+            self.has_available_quota(
+                self.account(file.account_id).await?.as_ref(),
+                u32::from(version.size) as u64,
+            )
+            .await?;
+        }
+
+        // Build node
+        let previous = FileVersion::from_node(node.inner).unwrap();
+        let mut new_node = node.deserialize::<FileNode>().caused_by(trc::location!())?;
+        let new_file = new_node.file.as_mut().unwrap();
+        new_file.blob_hash = blob_hash.clone();
+        new_file.media_type = version.media_type.as_ref().map(|t| t.to_string());
+        new_file.size = u32::from(version.size);
+        new_node.modified = now() as i64;
+
+        // Prepare write batch
+        let mut batch = BatchBuilder::new();
+        batch
+            .with_account_id(file.account_id)
+            .with_collection(Collection::FileNode)
+            .with_document(file.document_id)
+            .custom(
+                ObjectIndexBuilder::new()
+                    .with_current(node)
+                    .with_changes(new_node)
+                    .with_changed_by(access_token.account_tenant_ids()),
+            )
+            .caused_by(trc::location!())?;
+        self.archive_file_version(
+            file.account_id,
+            file.document_id,
+            previous,
+            &blob_hash,
+            access_token.account_tenant_ids(),
+            &mut batch,
+        )
+        .await
+        .caused_by(trc::location!())?;
+        let etag = batch.etag();
+        self.commit_batch(batch).await.caused_by(trc::location!())?;
+
+        Ok(HttpResponse::new(StatusCode::NO_CONTENT).with_etag_opt(etag))
+    }
+}
+
+async fn fetch_versioned_file(
+    server: &Server,
+    access_token: &AccessToken,
+    headers: &RequestHeaders<'_>,
+    acl: Acl,
+) -> crate::Result<VersionedFile> {
+    // Validate URI
+    let resource_ = server
+        .validate_uri(access_token, headers.uri)
+        .await?
+        .into_owned_uri()?;
+    let account_id = resource_.account_id;
+    let files = server
+        .fetch_dav_resources(
+            access_token.account_id(),
+            account_id,
+            SyncCollection::FileNode,
+        )
+        .await
+        .caused_by(trc::location!())?;
+    let resource = files.map_resource::<u32>(&resource_)?;
+    let document_id = resource.resource;
+
+    // Fetch node
+    let node = server
+        .store()
+        .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+            account_id,
+            Collection::FileNode,
+            document_id,
+        ))
+        .await
+        .caused_by(trc::location!())?
+        .ok_or(DavError::Code(StatusCode::NOT_FOUND))?;
+    let node_ = node.unarchive::<FileNode>().caused_by(trc::location!())?;
+
+    // Validate ACL
+    if !access_token.is_member(account_id) && !node_.acls.effective_acl(access_token).contains(acl)
+    {
+        return Err(DavError::Code(StatusCode::FORBIDDEN));
+    }
+
+    // Only files have versions
+    if node_.file.is_none() {
+        return Err(DavError::Code(StatusCode::METHOD_NOT_ALLOWED));
+    }
+
+    Ok(VersionedFile {
+        account_id,
+        document_id,
+        path: resource_.resource.unwrap_or_default().to_string(),
+        versions: server
+            .file_versions(account_id, document_id)
+            .await
+            .caused_by(trc::location!())?,
+        node,
+    })
+}
+
+pub(crate) fn prop_stat(
+    fields: Vec<DavPropertyValue>,
+    fields_not_found: Vec<DavPropertyValue>,
+    skip_not_found: bool,
+) -> Vec<PropStat> {
+    let mut prop_stat = Vec::with_capacity(2);
+    if !fields.is_empty() {
+        prop_stat.push(PropStat::new_list(fields));
+    }
+    if !fields_not_found.is_empty() && !skip_not_found {
+        prop_stat.push(PropStat::new_list(fields_not_found).with_status(StatusCode::NOT_FOUND));
+    }
+    if prop_stat.is_empty() {
+        prop_stat.push(PropStat::new_list(vec![]));
+    }
+    prop_stat
+}
+
+fn version_etag(blob_hash: &BlobHash) -> String {
+    format!("\"{}\"", blob_hash.to_hex())
+}
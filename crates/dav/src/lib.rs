@@ -41,6 +41,7 @@ pub enum DavMethod {
     UNLOCK,
     OPTIONS,
     ACL,
+    SEARCH,
 }
 
 impl From<DavMethod> for trc::WebDavEvent {
@@ -63,6 +64,7 @@ impl From<DavMethod> for trc::WebDavEvent {
             DavMethod::UNLOCK => trc::WebDavEvent::Unlock,
             DavMethod::OPTIONS => trc::WebDavEvent::Options,
             DavMethod::ACL => trc::WebDavEvent::Acl,
+            DavMethod::SEARCH => trc::WebDavEvent::Search,
         }
     }
 }
@@ -132,7 +134,8 @@ impl DavMethod {
                     "MOVE" => DavMethod::MOVE,
                     "LOCK" => DavMethod::LOCK,
                     "UNLOCK" => DavMethod::UNLOCK,
-                    "ACL" => DavMethod::ACL
+                    "ACL" => DavMethod::ACL,
+                    "SEARCH" => DavMethod::SEARCH
                 )
             }
         }
@@ -151,6 +154,7 @@ impl DavMethod {
                 | DavMethod::LOCK
                 | DavMethod::ACL
                 | DavMethod::MKCALENDAR
+                | DavMethod::SEARCH
         )
     }
 }
@@ -25,6 +25,7 @@ use dav_proto::schema::{
 use groupware::RFC_3986;
 use groupware::cache::GroupwareCache;
 use hyper::StatusCode;
+use registry::schema::enums::ResourceType;
 use std::borrow::Cow;
 use trc::AddContext;
 use types::collection::Collection;
@@ -294,11 +295,15 @@ impl PrincipalPropFind for Server {
                         PrincipalProperty::CalendarUserType => {
                             fields.push(DavPropertyValue::new(
                                 property.clone(),
-                                if account.is_user_account() {
-                                    DavValue::String("INDIVIDUAL".to_string())
-                                } else {
-                                    DavValue::String("GROUP".to_string())
-                                },
+                                DavValue::String(
+                                    match account.resource_type() {
+                                        Some(ResourceType::Room) => "ROOM",
+                                        Some(ResourceType::Equipment) => "RESOURCE",
+                                        None if account.is_user_account() => "INDIVIDUAL",
+                                        None => "GROUP",
+                                    }
+                                    .to_string(),
+                                ),
                             ));
                             response.set_namespace(Namespace::CalDav);
                         }
@@ -7,13 +7,16 @@
 use super::propfind::PrincipalPropFind;
 use common::{Server, auth::AccessToken};
 use dav_proto::schema::{
-    property::{DavProperty, WebDavProperty},
+    property::{DavProperty, PrincipalProperty, WebDavProperty},
     request::{PrincipalPropertySearch, PropFind},
     response::MultiStatus,
 };
 use http_proto::HttpResponse;
 use hyper::StatusCode;
-use registry::schema::prelude::{ObjectType, Property};
+use registry::{
+    schema::prelude::{AccountType, ObjectType, Property, ResourceType},
+    types::EnumImpl,
+};
 use store::{registry::RegistryQuery, roaring::RoaringBitmap};
 use trc::AddContext;
 use types::collection::Collection;
@@ -33,29 +36,64 @@ impl PrincipalPropSearch for Server {
         mut request: PrincipalPropertySearch,
     ) -> crate::Result<HttpResponse> {
         let mut search_for = None;
+        let mut user_type = None;
 
         for prop_search in request.property_search {
-            if matches!(
-                prop_search.property,
+            match prop_search.property {
                 DavProperty::WebDav(WebDavProperty::DisplayName)
-            ) && !prop_search.match_.is_empty()
-            {
-                search_for = Some(prop_search.match_);
+                    if !prop_search.match_.is_empty() =>
+                {
+                    search_for = Some(prop_search.match_);
+                }
+                DavProperty::Principal(PrincipalProperty::CalendarUserType) => {
+                    user_type = Some(
+                        match prop_search.match_.trim().to_ascii_uppercase().as_str() {
+                            "INDIVIDUAL" => (AccountType::User, None),
+                            "GROUP" => (AccountType::Group, None),
+                            "ROOM" => (AccountType::Resource, Some(ResourceType::Room)),
+                            "RESOURCE" => (AccountType::Resource, Some(ResourceType::Equipment)),
+                            _ => {
+                                return Ok(HttpResponse::new(StatusCode::MULTI_STATUS)
+                                    .with_xml_body(MultiStatus::new(vec![]).to_string()));
+                            }
+                        },
+                    );
+                }
+                _ => {}
             }
         }
 
         let mut response = MultiStatus::new(Vec::with_capacity(16));
-        if let Some(search_for) = search_for {
-            let ids = self
+        if search_for.is_some() || user_type.is_some() {
+            let mut query =
+                RegistryQuery::new(ObjectType::Account).with_tenant(access_token.tenant_id());
+            if let Some(search_for) = search_for {
+                query = query.text(Property::Text, search_for);
+            }
+            if let Some((account_type, _)) = user_type {
+                query = query.equal(Property::Type, account_type.to_id());
+            }
+            let mut ids = self
                 .registry()
-                .query::<RoaringBitmap>(
-                    RegistryQuery::new(ObjectType::Account)
-                        .with_tenant(access_token.tenant_id())
-                        .text(Property::Text, search_for),
-                )
+                .query::<RoaringBitmap>(query)
                 .await
                 .caused_by(trc::location!())?;
 
+            // Rooms and equipment share the same account type
+            if let Some((_, Some(resource_type))) = user_type {
+                for account_id in ids.clone() {
+                    if self
+                        .account(account_id)
+                        .await
+                        .caused_by(trc::location!())?
+                        .resource_type()
+                        != Some(resource_type)
+                    {
+                        ids.remove(account_id);
+                    }
+                }
+            }
+
             if !ids.is_empty() {
                 if request.properties.is_empty() {
                     request
@@ -7,11 +7,11 @@
 use crate::{
     DavError, DavErrorCondition, DavMethod, DavResourceName,
     calendar::{
-        copy_move::CalendarCopyMoveRequestHandler, delete::CalendarDeleteRequestHandler,
-        freebusy::CalendarFreebusyRequestHandler, get::CalendarGetRequestHandler,
-        mkcol::CalendarMkColRequestHandler, proppatch::CalendarPropPatchRequestHandler,
-        query::CalendarQueryRequestHandler, scheduling::CalendarEventNotificationHandler,
-        update::CalendarUpdateRequestHandler,
+        attachment::CalendarAttachmentRequestHandler, copy_move::CalendarCopyMoveRequestHandler,
+        delete::CalendarDeleteRequestHandler, freebusy::CalendarFreebusyRequestHandler,
+        get::CalendarGetRequestHandler, mkcol::CalendarMkColRequestHandler,
+        proppatch::CalendarPropPatchRequestHandler, query::CalendarQueryRequestHandler,
+        scheduling::CalendarEventNotificationHandler, update::CalendarUpdateRequestHandler,
     },
     card::{
         copy_move::CardCopyMoveRequestHandler, delete::CardDeleteRequestHandler,
@@ -27,9 +27,15 @@ use crate::{
         uri::DavUriResource,
     },
     file::{
-        copy_move::FileCopyMoveRequestHandler, delete::FileDeleteRequestHandler,
-        get::FileGetRequestHandler, mkcol::FileMkColRequestHandler,
-        proppatch::FilePropPatchRequestHandler, update::FileUpdateRequestHandler,
+        copy_move::FileCopyMoveRequestHandler,
+        delete::FileDeleteRequestHandler,
+        get::FileGetRequestHandler,
+        mkcol::FileMkColRequestHandler,
+        proppatch::FilePropPatchRequestHandler,
+        search::FileSearchRequestHandler,
+        update::FileUpdateRequestHandler,
+        upload::{FileUploadRequestHandler, UPLOADS_PATH},
+        version::FileVersionRequestHandler,
     },
     principal::{matching::PrincipalMatching, propsearch::PrincipalPropSearch},
 };
@@ -41,7 +47,7 @@ use dav_proto::{
     schema::{
         Namespace,
         property::WebDavProperty,
-        request::{Acl, LockInfo, MkCol, PropFind, PropertyUpdate, Report},
+        request::{Acl, LockInfo, MkCol, PropFind, PropertyUpdate, Report, SearchRequest},
         response::{
             BaseCondition, ErrorResponse, List, PrincipalSearchProperty, PrincipalSearchPropertySet,
         },
@@ -53,6 +59,7 @@ use registry::schema::enums::Permission;
 use std::time::Instant;
 use trc::{EventType, LimitEvent, StoreEvent, WebDavEvent};
 use types::collection::Collection;
+use utils::url_params::UrlParams;
 
 pub trait DavRequestHandler: Sync + Send {
     fn handle_dav_request(
@@ -85,13 +92,32 @@ impl DavRequestDispatcher for Server {
         method: DavMethod,
         body: Vec<u8>,
     ) -> crate::Result<HttpResponse> {
+        // Chunked uploads
+        if resource == DavResourceName::File && headers.uri.starts_with(UPLOADS_PATH) {
+            // Validate permissions
+            let access_token = access_token.assert_has_permission(Permission::DavFilePut)?;
+
+            return self
+                .handle_file_upload_request(&access_token, headers, method, body)
+                .await;
+        }
+
         // Dispatch
         match method {
             DavMethod::PROPFIND => {
                 let request = PropFind::parse(&mut Tokenizer::new(&body))?;
 
-                self.handle_propfind_request(&access_token, headers, request)
-                    .await
+                if resource == DavResourceName::File && headers.query == Some("versions") {
+                    // Validate permissions
+                    let access_token =
+                        access_token.assert_has_permission(Permission::DavFilePropFind)?;
+
+                    self.handle_file_versions_propfind(&access_token, headers, request)
+                        .await
+                } else {
+                    self.handle_propfind_request(&access_token, headers, request)
+                        .await
+                }
             }
             DavMethod::GET | DavMethod::HEAD => match resource {
                 DavResourceName::Card => {
@@ -110,12 +136,22 @@ impl DavRequestDispatcher for Server {
                     // Validate permissions
                     let access_token = access_token.assert_has_permission(Permission::DavCalGet)?;
 
-                    self.handle_calendar_get_request(
-                        &access_token,
-                        headers,
-                        matches!(method, DavMethod::HEAD),
-                    )
-                    .await
+                    if let Some(managed_id) = UrlParams::new(headers.query).get("managed-id") {
+                        self.handle_calendar_attachment_get(
+                            &access_token,
+                            headers,
+                            managed_id,
+                            matches!(method, DavMethod::HEAD),
+                        )
+                        .await
+                    } else {
+                        self.handle_calendar_get_request(
+                            &access_token,
+                            headers,
+                            matches!(method, DavMethod::HEAD),
+                        )
+                        .await
+                    }
                 }
                 DavResourceName::File => {
                     // Validate permissions
@@ -130,12 +166,22 @@ impl DavRequestDispatcher for Server {
                             && !request.headers().contains_key("x-litmus"),
                     )
                     .await*/
-                    self.handle_file_get_request(
-                        &access_token,
-                        headers,
-                        matches!(method, DavMethod::HEAD),
-                    )
-                    .await
+                    if let Some(version_id) = UrlParams::new(headers.query).get("version") {
+                        self.handle_file_version_get(
+                            &access_token,
+                            headers,
+                            version_id,
+                            matches!(method, DavMethod::HEAD),
+                        )
+                        .await
+                    } else {
+                        self.handle_file_get_request(
+                            &access_token,
+                            headers,
+                            matches!(method, DavMethod::HEAD),
+                        )
+                        .await
+                    }
                 }
                 DavResourceName::Scheduling => {
                     // Validate permissions
@@ -352,7 +398,15 @@ impl DavRequestDispatcher for Server {
                         self.handle_file_proppatch_request(&access_token, headers, request)
                             .await
                     }
-                    DavResourceName::Principal | DavResourceName::Scheduling => {
+                    DavResourceName::Scheduling => {
+                        // Validate permissions
+                        let access_token =
+                            access_token.assert_has_permission(Permission::DavCalPropPatch)?;
+
+                        self.handle_scheduling_proppatch_request(&access_token, headers, request)
+                            .await
+                    }
+                    DavResourceName::Principal => {
                         Err(DavError::Code(StatusCode::METHOD_NOT_ALLOWED))
                     }
                 }
@@ -447,26 +501,53 @@ impl DavRequestDispatcher for Server {
                     // Validate permissions
                     let access_token = access_token.assert_has_permission(Permission::DavCalPut)?;
 
-                    self.handle_calendar_update_request(
-                        &access_token,
-                        headers,
-                        body,
-                        matches!(method, DavMethod::PATCH),
-                    )
-                    .await
+                    let params = UrlParams::new(headers.query);
+                    if matches!(method, DavMethod::POST)
+                        && params
+                            .get("action")
+                            .is_some_and(|action| action.starts_with("attachment-"))
+                    {
+                        self.handle_calendar_attachment_request(
+                            &access_token,
+                            headers,
+                            params,
+                            body,
+                        )
+                        .await
+                    } else {
+                        self.handle_calendar_update_request(
+                            &access_token,
+                            headers,
+                            body,
+                            matches!(method, DavMethod::PATCH),
+                        )
+                        .await
+                    }
                 }
                 DavResourceName::File => {
                     // Validate permissions
                     let access_token =
                         access_token.assert_has_permission(Permission::DavFilePut)?;
 
-                    self.handle_file_update_request(
-                        &access_token,
-                        headers,
-                        body,
-                        matches!(method, DavMethod::PATCH),
-                    )
-                    .await
+                    let params = UrlParams::new(headers.query);
+                    if matches!(method, DavMethod::POST)
+                        && params.get("action") == Some("restore-version")
+                    {
+                        self.handle_file_version_restore(
+                            &access_token,
+                            headers,
+                            params.get("version").unwrap_or_default(),
+                        )
+                        .await
+                    } else {
+                        self.handle_file_update_request(
+                            &access_token,
+                            headers,
+                            body,
+                            matches!(method, DavMethod::PATCH),
+                        )
+                        .await
+                    }
                 }
                 DavResourceName::Scheduling => {
                     // Validate permissions
@@ -581,6 +662,21 @@ impl DavRequestDispatcher for Server {
                 )
                 .await
             }
+            DavMethod::SEARCH => match resource {
+                DavResourceName::File => {
+                    // Validate permissions
+                    let access_token =
+                        access_token.assert_has_permission(Permission::DavFilePropFind)?;
+
+                    self.handle_file_search_request(
+                        &access_token,
+                        headers,
+                        SearchRequest::parse(&mut Tokenizer::new(&body))?,
+                    )
+                    .await
+                }
+                _ => Err(DavError::Code(StatusCode::METHOD_NOT_ALLOWED)),
+            },
             DavMethod::OPTIONS => unreachable!(),
         }
     }
@@ -630,6 +726,7 @@ impl DavRequestHandler for Server {
 
         // Parse headers
         let mut headers = RequestHeaders::new(request.uri().path());
+        headers.query = request.uri().query();
         for (key, value) in request.headers() {
             headers.parse(key.as_str(), value.to_str().unwrap_or_default());
         }
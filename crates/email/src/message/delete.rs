@@ -7,7 +7,7 @@
 use super::metadata::MessageData;
 use crate::cache::{MessageCacheFetch, email::MessageCacheAccess};
 use common::{Server, storage::index::ObjectIndexBuilder};
-use groupware::calendar::storage::ItipAutoExpunge;
+use groupware::{calendar::storage::ItipAutoExpunge, file::version::FileVersioning};
 use registry::schema::enums::IndexDocumentType;
 use registry::schema::structs::{Task, TaskIndexDocument, TaskStatus};
 use std::future::Future;
@@ -91,6 +91,7 @@ impl EmailDeletion for Server {
                             .with_current(metadata),
                     )
                     .caused_by(trc::location!())?
+                    .clear(EmailField::Snoozed)
                     .schedule_task(Task::UnindexDocument(TaskIndexDocument {
                         account_id: account_id.into(),
                         document_id: document_id.into(),
@@ -163,6 +164,11 @@ impl EmailDeletion for Server {
                 .caused_by(trc::location!())?;
         }
 
+        // Prune file versions
+        self.prune_file_versions(account_id)
+            .await
+            .caused_by(trc::location!())?;
+
         // Delete old e-mail submissions
         if let Some(hold_period) = self.core.email.email_submission_autoexpunge_after {
             self.purge_email_submissions(account_id, hold_period)
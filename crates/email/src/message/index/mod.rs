@@ -39,6 +39,10 @@ impl IndexableObject for MessageData {
                 },
             },
             IndexValue::Quota { used: self.size },
+            IndexValue::MailboxUsage {
+                used: self.size,
+                mailbox_ids: mailboxes.clone(),
+            },
             IndexValue::LogItem {
                 sync_collection: SyncCollection::Email,
                 prefix: self.thread_id.into(),
@@ -79,6 +83,10 @@ impl IndexableObject for &ArchivedMessageData {
             IndexValue::Quota {
                 used: self.size.to_native(),
             },
+            IndexValue::MailboxUsage {
+                used: self.size.to_native(),
+                mailbox_ids: mailboxes.clone(),
+            },
             IndexValue::LogItem {
                 sync_collection: SyncCollection::Email,
                 prefix: self.thread_id.to_native().into(),
@@ -399,10 +399,8 @@ impl EmailIngest for Server {
                                         )
                                         .await
                                     {
-                                        Ok(message) => {
-                                            if let Some(message) = message {
-                                                itip_messages.push(message);
-                                            }
+                                        Ok(messages) => {
+                                            itip_messages.extend(messages);
                                             trc::event!(
                                                 Calendar(trc::CalendarEvent::ItipMessageReceived),
                                                 SpanId = params.session_id,
@@ -0,0 +1,98 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use common::Server;
+use registry::schema::structs::{Task, TaskEmailSnooze, TaskStatus};
+use std::future::Future;
+use store::{
+    Serialize, ValueKey,
+    write::{AlignedBytes, Archive, Archiver, BatchBuilder},
+};
+use trc::AddContext;
+use types::{collection::Collection, field::EmailField, keyword::Keyword};
+
+#[derive(rkyv::Serialize, rkyv::Deserialize, rkyv::Archive, Debug, Clone, PartialEq, Eq)]
+pub struct EmailSnooze {
+    pub until: u64,
+    pub move_to_mailbox_id: Option<u32>,
+    pub add_keywords: Box<[Keyword]>,
+    pub remove_keywords: Box<[Keyword]>,
+}
+
+pub trait EmailSnoozeFetch: Sync + Send {
+    fn email_snooze(
+        &self,
+        account_id: u32,
+        document_id: u32,
+    ) -> impl Future<Output = trc::Result<Option<EmailSnooze>>> + Send;
+}
+
+impl EmailSnoozeFetch for Server {
+    async fn email_snooze(
+        &self,
+        account_id: u32,
+        document_id: u32,
+    ) -> trc::Result<Option<EmailSnooze>> {
+        if let Some(snooze) = self
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                account_id,
+                Collection::Email,
+                document_id,
+                EmailField::Snoozed,
+            ))
+            .await
+            .caused_by(trc::location!())?
+        {
+            snooze
+                .deserialize::<EmailSnooze>()
+                .caused_by(trc::location!())
+                .map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl EmailSnooze {
+    pub fn new(until: u64) -> Self {
+        EmailSnooze {
+            until,
+            move_to_mailbox_id: None,
+            add_keywords: Box::new([]),
+            remove_keywords: Box::new([]),
+        }
+    }
+
+    // Writes the snooze property and schedules the wake-up task,
+    // the batch must point to the email document.
+    pub fn write(
+        self,
+        batch: &mut BatchBuilder,
+        account_id: u32,
+        document_id: u32,
+    ) -> trc::Result<()> {
+        let until = self.until;
+        batch
+            .set(
+                EmailField::Snoozed,
+                Archiver::new(self)
+                    .serialize()
+                    .caused_by(trc::location!())?,
+            )
+            .schedule_task(Task::EmailSnooze(TaskEmailSnooze {
+                account_id: account_id.into(),
+                document_id: document_id.into(),
+                status: TaskStatus::at(until as i64),
+            }));
+
+        Ok(())
+    }
+
+    pub fn has_keyword_changes(&self) -> bool {
+        !self.add_keywords.is_empty() || !self.remove_keywords.is_empty()
+    }
+}
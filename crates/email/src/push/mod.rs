@@ -38,6 +38,24 @@ pub struct PushSubscriptions {
     pub subscriptions: Vec<PushSubscription>,
 }
 
+#[derive(
+    rkyv::Archive, rkyv::Deserialize, rkyv::Serialize, Default, Debug, Clone, PartialEq, Eq,
+)]
+pub struct Webhook {
+    pub id: u32,
+    pub url: String,
+    pub secret: String,
+    pub types: Bitmap<DataType>,
+    pub enabled: bool,
+}
+
+#[derive(
+    rkyv::Archive, rkyv::Deserialize, rkyv::Serialize, Default, Debug, Clone, PartialEq, Eq,
+)]
+pub struct Webhooks {
+    pub webhooks: Vec<Webhook>,
+}
+
 #[derive(
     rkyv::Archive, rkyv::Deserialize, rkyv::Serialize, Default, Debug, Clone, PartialEq, Eq,
 )]
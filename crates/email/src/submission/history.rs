@@ -0,0 +1,141 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use common::Server;
+use std::future::Future;
+use store::{
+    Serialize, ValueKey,
+    write::{AlignedBytes, Archive, Archiver, BatchBuilder, now},
+};
+use trc::AddContext;
+use types::{collection::Collection, field::PrincipalField};
+
+const MAX_SENT_RECIPIENTS: usize = 1000;
+const SCORE_HALF_LIFE: u64 = 30 * 86400;
+
+#[derive(
+    rkyv::Archive, rkyv::Deserialize, rkyv::Serialize, Debug, Default, Clone, PartialEq, Eq,
+)]
+pub struct SentRecipients {
+    pub recipients: Vec<SentRecipient>,
+}
+
+#[derive(
+    rkyv::Archive, rkyv::Deserialize, rkyv::Serialize, Debug, Default, Clone, PartialEq, Eq,
+)]
+pub struct SentRecipient {
+    pub address: String,
+    pub count: u32,
+    pub last_sent: u64,
+}
+
+pub trait SentRecipientHistory: Sync + Send {
+    fn sent_recipients(
+        &self,
+        account_id: u32,
+    ) -> impl Future<Output = trc::Result<SentRecipients>> + Send;
+
+    fn record_sent_recipients(
+        &self,
+        account_id: u32,
+        addresses: impl IntoIterator<Item = String> + Send,
+    ) -> impl Future<Output = trc::Result<()>> + Send;
+}
+
+impl SentRecipientHistory for Server {
+    async fn sent_recipients(&self, account_id: u32) -> trc::Result<SentRecipients> {
+        if let Some(archive) = self
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                account_id,
+                Collection::Principal,
+                0,
+                PrincipalField::SentRecipients,
+            ))
+            .await
+            .caused_by(trc::location!())?
+        {
+            archive
+                .deserialize::<SentRecipients>()
+                .caused_by(trc::location!())
+        } else {
+            Ok(SentRecipients::default())
+        }
+    }
+
+    async fn record_sent_recipients(
+        &self,
+        account_id: u32,
+        addresses: impl IntoIterator<Item = String> + Send,
+    ) -> trc::Result<()> {
+        let mut history = self.sent_recipients(account_id).await?;
+        let now = now();
+        let mut has_changes = false;
+
+        for address in addresses {
+            let address = address.to_lowercase();
+            if let Some(recipient) = history.recipients.iter_mut().find(|r| r.address == address) {
+                recipient.count = recipient.count.saturating_add(1);
+                recipient.last_sent = now;
+            } else {
+                history.recipients.push(SentRecipient {
+                    address,
+                    count: 1,
+                    last_sent: now,
+                });
+            }
+            has_changes = true;
+        }
+
+        if !has_changes {
+            return Ok(());
+        }
+
+        // Evict the lowest ranked recipients
+        if history.recipients.len() > MAX_SENT_RECIPIENTS {
+            history
+                .recipients
+                .sort_unstable_by(|a, b| b.score(now).total_cmp(&a.score(now)));
+            history.recipients.truncate(MAX_SENT_RECIPIENTS);
+        }
+
+        // Concurrent submissions may overwrite each other's counters,
+        // which is acceptable for ranking purposes.
+        let mut batch = BatchBuilder::new();
+        batch
+            .with_account_id(account_id)
+            .with_collection(Collection::Principal)
+            .with_document(0)
+            .set(
+                PrincipalField::SentRecipients,
+                Archiver::new(history)
+                    .serialize()
+                    .caused_by(trc::location!())?,
+            );
+        self.store()
+            .write(batch.build_all())
+            .await
+            .caused_by(trc::location!())
+            .map(|_| ())
+    }
+}
+
+impl SentRecipients {
+    pub fn score(&self, address: &str, now: u64) -> Option<f64> {
+        self.recipients
+            .iter()
+            .find(|r| r.address == address)
+            .map(|r| r.score(now))
+    }
+}
+
+impl SentRecipient {
+    // Frequency weighted by recency, halving every SCORE_HALF_LIFE seconds
+    pub fn score(&self, now: u64) -> f64 {
+        let age = now.saturating_sub(self.last_sent) as f64;
+        self.count as f64 * 0.5f64.powf(age / SCORE_HALF_LIFE as f64)
+    }
+}
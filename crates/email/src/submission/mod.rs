@@ -6,6 +6,7 @@
 
 use utils::map::vec_map::VecMap;
 
+pub mod history;
 pub mod index;
 
 #[derive(
@@ -0,0 +1,354 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{ArchivedCalendarEvent, CalendarEvent};
+use ahash::AHashMap;
+use calcard::icalendar::{
+    ArchivedICalendarParameterName, ArchivedICalendarParameterValue, ArchivedICalendarProperty,
+    ICalendar, ICalendarEntry, ICalendarParameter, ICalendarParameterName, ICalendarParameterValue,
+    ICalendarProperty, ICalendarValue, Uri,
+};
+use chrono::{NaiveDate, NaiveDateTime};
+use types::blob_hash::BlobHash;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttachmentRid {
+    Master,
+    Instance(i64),
+}
+
+pub struct ManagedAttachment {
+    pub url: String,
+    pub blob_hash: BlobHash,
+    pub size: u64,
+    pub filename: Option<String>,
+    pub media_type: Option<String>,
+}
+
+impl CalendarEvent {
+    // Managed attachments are shared across recurrence instances, so each blob
+    // is only accounted for once.
+    pub fn managed_attachments(&self) -> AHashMap<BlobHash, u64> {
+        let mut attachments = AHashMap::new();
+
+        for entry in self
+            .data
+            .event
+            .components
+            .iter()
+            .filter(|c| c.component_type.is_scheduling_object())
+            .flat_map(|c| c.entries.iter())
+            .filter(|e| e.name == ICalendarProperty::Attach)
+        {
+            let mut blob_hash = None;
+            let mut size = 0;
+
+            for param in &entry.params {
+                match (&param.name, &param.value) {
+                    (ICalendarParameterName::ManagedId, ICalendarParameterValue::Text(id)) => {
+                        blob_hash = BlobHash::try_from_hex(id);
+                    }
+                    (ICalendarParameterName::Size, ICalendarParameterValue::Integer(value)) => {
+                        size = *value;
+                    }
+                    _ => {}
+                }
+            }
+
+            if let Some(blob_hash) = blob_hash {
+                attachments.insert(blob_hash, size);
+            }
+        }
+
+        attachments
+    }
+}
+
+impl ArchivedCalendarEvent {
+    pub fn managed_attachments(&self) -> AHashMap<BlobHash, u64> {
+        let mut attachments = AHashMap::new();
+
+        for entry in self
+            .data
+            .event
+            .components
+            .iter()
+            .filter(|c| c.component_type.is_scheduling_object())
+            .flat_map(|c| c.entries.iter())
+            .filter(|e| e.name == ArchivedICalendarProperty::Attach)
+        {
+            let mut blob_hash = None;
+            let mut size = 0;
+
+            for param in entry.params.iter() {
+                match (&param.name, &param.value) {
+                    (
+                        ArchivedICalendarParameterName::ManagedId,
+                        ArchivedICalendarParameterValue::Text(id),
+                    ) => {
+                        blob_hash = BlobHash::try_from_hex(id.as_str());
+                    }
+                    (
+                        ArchivedICalendarParameterName::Size,
+                        ArchivedICalendarParameterValue::Integer(value),
+                    ) => {
+                        size = value.to_native();
+                    }
+                    _ => {}
+                }
+            }
+
+            if let Some(blob_hash) = blob_hash {
+                attachments.insert(blob_hash, size);
+            }
+        }
+
+        attachments
+    }
+
+    pub fn managed_attachment_media_type(&self, managed_id: &str) -> Option<&str> {
+        self.data
+            .event
+            .components
+            .iter()
+            .flat_map(|c| c.entries.iter())
+            .filter(|e| {
+                e.name == ArchivedICalendarProperty::Attach
+                    && e.params.iter().any(|param| {
+                        matches!(
+                            (&param.name, &param.value),
+                            (
+                                ArchivedICalendarParameterName::ManagedId,
+                                ArchivedICalendarParameterValue::Text(id),
+                            ) if id == managed_id
+                        )
+                    })
+            })
+            .flat_map(|e| e.params.iter())
+            .find_map(|param| match (&param.name, &param.value) {
+                (
+                    ArchivedICalendarParameterName::Fmttype,
+                    ArchivedICalendarParameterValue::Text(media_type),
+                ) => Some(media_type.as_str()),
+                _ => None,
+            })
+    }
+}
+
+impl ManagedAttachment {
+    pub fn managed_id(&self) -> String {
+        self.blob_hash.to_hex()
+    }
+
+    pub fn into_entry(self) -> ICalendarEntry {
+        let mut params = vec![
+            ICalendarParameter::managed_id(self.blob_hash.to_hex()),
+            ICalendarParameter::size(self.size),
+        ];
+        if let Some(filename) = self.filename {
+            params.push(ICalendarParameter::filename(filename));
+        }
+        if let Some(media_type) = self.media_type {
+            params.push(ICalendarParameter::fmttype(media_type));
+        }
+
+        ICalendarEntry {
+            name: ICalendarProperty::Attach,
+            params,
+            values: vec![ICalendarValue::Uri(Uri::Location(self.url))],
+        }
+    }
+}
+
+impl AttachmentRid {
+    pub fn parse_list(value: &str) -> Option<Vec<AttachmentRid>> {
+        value
+            .split(',')
+            .map(|rid| {
+                let rid = rid.trim();
+                if rid == "M" {
+                    Some(AttachmentRid::Master)
+                } else if let Ok(dt) = NaiveDateTime::parse_from_str(rid, "%Y%m%dT%H%M%SZ") {
+                    Some(AttachmentRid::Instance(dt.and_utc().timestamp()))
+                } else {
+                    NaiveDate::parse_from_str(rid, "%Y%m%d")
+                        .ok()
+                        .and_then(|date| date.and_hms_opt(0, 0, 0))
+                        .map(|dt| AttachmentRid::Instance(dt.and_utc().timestamp()))
+                }
+            })
+            .collect()
+    }
+}
+
+// Returns the ids of the components matching the requested recurrence ids, or all
+// scheduling components if none were requested. Returns None if a recurrence id
+// does not match any overridden instance.
+pub fn attachment_component_ids(
+    ical: &ICalendar,
+    rids: Option<&[AttachmentRid]>,
+) -> Option<Vec<u16>> {
+    let mut tz_resolver = None;
+    let mut component_ids = Vec::new();
+    let mut matched = vec![false; rids.map_or(0, |rids| rids.len())];
+
+    for (component_id, component) in ical.components.iter().enumerate() {
+        if !component.component_type.is_scheduling_object() {
+            continue;
+        }
+
+        let Some(rids) = rids else {
+            component_ids.push(component_id as u16);
+            continue;
+        };
+
+        let rid = component
+            .entries
+            .iter()
+            .find(|entry| entry.name == ICalendarProperty::RecurrenceId)
+            .map(|entry| {
+                let tz_id = entry.params.iter().find_map(|param| {
+                    if let (ICalendarParameterName::Tzid, ICalendarParameterValue::Text(id)) =
+                        (&param.name, &param.value)
+                    {
+                        Some(id.as_str())
+                    } else {
+                        None
+                    }
+                });
+
+                entry
+                    .values
+                    .first()
+                    .and_then(|v| v.as_partial_date_time())
+                    .map(|date| {
+                        AttachmentRid::Instance(
+                            date.to_date_time_with_tz(
+                                tz_resolver
+                                    .get_or_insert_with(|| ical.build_tz_resolver())
+                                    .resolve_or_default(tz_id),
+                            )
+                            .map(|dt| dt.timestamp())
+                            .unwrap_or_else(|| date.to_timestamp().unwrap_or_default()),
+                        )
+                    })
+                    .unwrap_or(AttachmentRid::Master)
+            })
+            .unwrap_or(AttachmentRid::Master);
+
+        if let Some(pos) = rids.iter().position(|r| *r == rid) {
+            matched[pos] = true;
+            component_ids.push(component_id as u16);
+        }
+    }
+
+    if matched.iter().all(|m| *m) && !component_ids.is_empty() {
+        Some(component_ids)
+    } else {
+        None
+    }
+}
+
+pub fn attachment_add(ical: &mut ICalendar, component_ids: &[u16], attachment: ManagedAttachment) {
+    let managed_id = attachment.managed_id();
+    let entry = attachment.into_entry();
+
+    for component_id in component_ids {
+        let component = &mut ical.components[*component_id as usize];
+        if !component
+            .entries
+            .iter()
+            .any(|e| entry_managed_id(e) == Some(managed_id.as_str()))
+        {
+            component.entries.push(entry.clone());
+        }
+    }
+}
+
+pub fn attachment_update(
+    ical: &mut ICalendar,
+    managed_id: &str,
+    attachment: ManagedAttachment,
+) -> bool {
+    let entry = attachment.into_entry();
+    let mut did_update = false;
+
+    for component in &mut ical.components {
+        if component.component_type.is_scheduling_object() {
+            for existing in &mut component.entries {
+                if entry_managed_id(existing) == Some(managed_id) {
+                    *existing = entry.clone();
+                    did_update = true;
+                }
+            }
+        }
+    }
+
+    did_update
+}
+
+pub fn attachment_remove(ical: &mut ICalendar, component_ids: &[u16], managed_id: &str) -> bool {
+    let mut did_remove = false;
+
+    for component_id in component_ids {
+        let component = &mut ical.components[*component_id as usize];
+        let len = component.entries.len();
+        component
+            .entries
+            .retain(|e| entry_managed_id(e) != Some(managed_id));
+        did_remove |= component.entries.len() != len;
+    }
+
+    did_remove
+}
+
+// Clients may only reference attachments that were previously uploaded to this
+// event. Unknown MANAGED-ID parameters are dropped and the SIZE of known ones is
+// restored so it cannot be used to bypass quotas.
+pub fn sanitize_managed_attachments(ical: &mut ICalendar, known: &AHashMap<BlobHash, u64>) {
+    for component in &mut ical.components {
+        if !component.component_type.is_scheduling_object() {
+            continue;
+        }
+
+        for entry in &mut component.entries {
+            if entry.name != ICalendarProperty::Attach {
+                continue;
+            }
+            let Some(managed_id) = entry_managed_id(entry) else {
+                continue;
+            };
+
+            let known_size = BlobHash::try_from_hex(managed_id)
+                .and_then(|blob_hash| known.get(&blob_hash).copied());
+            entry.params.retain(|param| {
+                !matches!(
+                    param.name,
+                    ICalendarParameterName::ManagedId | ICalendarParameterName::Size
+                ) || (known_size.is_some() && param.name == ICalendarParameterName::ManagedId)
+            });
+            if let Some(size) = known_size {
+                entry.params.push(ICalendarParameter::size(size));
+            }
+        }
+    }
+}
+
+fn entry_managed_id(entry: &ICalendarEntry) -> Option<&str> {
+    if entry.name == ICalendarProperty::Attach {
+        entry.params.iter().find_map(|param| {
+            if let (ICalendarParameterName::ManagedId, ICalendarParameterValue::Text(id)) =
+                (&param.name, &param.value)
+            {
+                Some(id.as_str())
+            } else {
+                None
+            }
+        })
+    } else {
+        None
+    }
+}
@@ -0,0 +1,245 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use ahash::AHashMap;
+use calcard::{
+    common::timezone::Tz,
+    icalendar::{
+        ICalendar, ICalendarComponentType, ICalendarProperty, ICalendarValue, dates::TimeOrDelta,
+    },
+};
+use common::Server;
+use std::future::Future;
+use store::{
+    ValueKey,
+    write::{AlignedBytes, Archive},
+};
+use trc::AddContext;
+use types::{TimeRange, collection::Collection, field::PrincipalField};
+
+pub trait CalendarAvailability: Sync + Send {
+    fn calendar_availability(
+        &self,
+        account_id: u32,
+    ) -> impl Future<Output = trc::Result<Option<ICalendar>>> + Send;
+
+    fn calendar_unavailable_periods(
+        &self,
+        account_id: u32,
+        range: TimeRange,
+        default_tz: Tz,
+    ) -> impl Future<Output = trc::Result<Vec<(i64, i64)>>> + Send;
+}
+
+impl CalendarAvailability for Server {
+    async fn calendar_availability(&self, account_id: u32) -> trc::Result<Option<ICalendar>> {
+        if let Some(archive) = self
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                account_id,
+                Collection::Principal,
+                0,
+                PrincipalField::CalendarAvailability,
+            ))
+            .await
+            .caused_by(trc::location!())?
+        {
+            archive
+                .deserialize::<ICalendar>()
+                .caused_by(trc::location!())
+                .map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn calendar_unavailable_periods(
+        &self,
+        account_id: u32,
+        range: TimeRange,
+        default_tz: Tz,
+    ) -> trc::Result<Vec<(i64, i64)>> {
+        Ok(self
+            .calendar_availability(account_id)
+            .await?
+            .map(|ical| {
+                unavailable_periods(
+                    &ical,
+                    range,
+                    default_tz,
+                    self.core.groupware.max_ical_instances,
+                )
+            })
+            .unwrap_or_default())
+    }
+}
+
+struct AvailabilityWindow {
+    start: i64,
+    end: i64,
+    priority: i64,
+    available: Vec<(i64, i64)>,
+}
+
+/*
+   Returns the periods within the range that are outside the available time
+   defined by the VAVAILABILITY components (RFC 7953, Section 4). Components
+   with a higher priority (1 being the highest and 0 undefined) override the
+   time covered by lower priority ones.
+*/
+pub fn unavailable_periods(
+    ical: &ICalendar,
+    range: TimeRange,
+    default_tz: Tz,
+    max_instances: usize,
+) -> Vec<(i64, i64)> {
+    let tz_resolver = ical.build_tz_resolver();
+    let mut windows = Vec::new();
+    let mut available_ids = AHashMap::new();
+
+    for component in &ical.components {
+        if component.component_type != ICalendarComponentType::VAvailability {
+            continue;
+        }
+
+        let mut start = None;
+        let mut end = None;
+        let mut duration = None;
+        let mut priority = 0;
+
+        for entry in &component.entries {
+            match &entry.name {
+                ICalendarProperty::Dtstart | ICalendarProperty::Dtend => {
+                    let value = entry
+                        .values
+                        .first()
+                        .and_then(|v| v.as_partial_date_time())
+                        .and_then(|dt| {
+                            let tz = tz_resolver.resolve_or_default(entry.tz_id());
+                            dt.to_date_time_with_tz(if tz.is_floating() { default_tz } else { tz })
+                        })
+                        .map(|dt| dt.timestamp());
+                    if entry.name == ICalendarProperty::Dtstart {
+                        start = value;
+                    } else {
+                        end = value;
+                    }
+                }
+                ICalendarProperty::Duration => {
+                    if let Some(ICalendarValue::Duration(value)) = entry.values.first() {
+                        duration = Some(value.as_seconds());
+                    }
+                }
+                ICalendarProperty::Priority => {
+                    priority = entry
+                        .values
+                        .first()
+                        .and_then(|v| v.as_integer())
+                        .unwrap_or_default();
+                }
+                _ => {}
+            }
+        }
+
+        // A missing DTSTART or DTEND means the component is unbounded in that direction
+        let end = end
+            .or_else(|| start?.checked_add(duration?))
+            .unwrap_or(i64::MAX)
+            .min(range.end);
+        let start = start.unwrap_or(i64::MIN).max(range.start);
+        if start >= end {
+            continue;
+        }
+
+        for component_id in &component.component_ids {
+            if ical
+                .components
+                .get(*component_id as usize)
+                .is_some_and(|c| c.component_type == ICalendarComponentType::Available)
+            {
+                available_ids.insert(*component_id, windows.len());
+            }
+        }
+
+        windows.push(AvailabilityWindow {
+            start,
+            end,
+            priority: if (1..=9).contains(&priority) {
+                priority
+            } else {
+                10
+            },
+            available: Vec::new(),
+        });
+    }
+
+    if windows.is_empty() {
+        return Vec::new();
+    }
+
+    // Expand AVAILABLE components as if they were events
+    if !available_ids.is_empty() {
+        let mut available = ical.clone();
+        for component_id in available_ids.keys() {
+            available.components[*component_id as usize].component_type =
+                ICalendarComponentType::VEvent;
+        }
+        for instance in available.expand_dates(default_tz, max_instances).events {
+            let Some(window) = available_ids
+                .get(&instance.comp_id)
+                .and_then(|idx| windows.get_mut(*idx))
+            else {
+                continue;
+            };
+            let start = instance.start.timestamp();
+            let end = match instance.end {
+                TimeOrDelta::Time(time) => time.timestamp(),
+                TimeOrDelta::Delta(delta) => start + delta.num_seconds(),
+            };
+            if start < window.end && end > window.start {
+                window
+                    .available
+                    .push((start.max(window.start), end.min(window.end)));
+            }
+        }
+    }
+
+    // Apply lower priority components first, so higher priority ones replace them
+    windows.sort_by(|a, b| b.priority.cmp(&a.priority));
+    let mut busy: Vec<(i64, i64)> = Vec::new();
+
+    for mut window in windows {
+        let mut periods = Vec::with_capacity(busy.len() + window.available.len() + 1);
+
+        // Remove the time covered by this component
+        for (start, end) in busy {
+            if start < window.start {
+                periods.push((start, end.min(window.start)));
+            }
+            if end > window.end {
+                periods.push((start.max(window.end), end));
+            }
+        }
+
+        // Add the time not covered by the available periods
+        window.available.sort_unstable();
+        let mut next_start = window.start;
+        for (start, end) in window.available {
+            if start > next_start {
+                periods.push((next_start, start));
+            }
+            next_start = next_start.max(end);
+        }
+        if next_start < window.end {
+            periods.push((next_start, window.end));
+        }
+
+        busy = periods;
+    }
+
+    busy.sort_unstable();
+    busy
+}
@@ -99,6 +99,9 @@ impl IndexableObject for CalendarEvent {
             IndexValue::Quota {
                 used: self.size() as u32,
             },
+            IndexValue::Blobs {
+                values: self.managed_attachments().into_keys().collect(),
+            },
             IndexValue::LogItem {
                 sync_collection: SyncCollection::Calendar,
                 prefix: None,
@@ -125,6 +128,9 @@ impl IndexableObject for &ArchivedCalendarEvent {
             IndexValue::Quota {
                 used: self.size() as u32,
             },
+            IndexValue::Blobs {
+                values: self.managed_attachments().into_keys().collect(),
+            },
             IndexValue::LogItem {
                 sync_collection: SyncCollection::Calendar,
                 prefix: None,
@@ -220,6 +226,7 @@ impl CalendarEvent {
             + self.names.iter().map(|n| n.name.len()).sum::<usize>()
             + self.preferences.iter().map(|p| p.size()).sum::<usize>()
             + self.size as usize
+            + self.managed_attachments().values().sum::<u64>() as usize
             + std::mem::size_of::<CalendarEvent>()
     }
 }
@@ -231,6 +238,7 @@ impl ArchivedCalendarEvent {
             + self.names.iter().map(|n| n.name.len()).sum::<usize>()
             + self.preferences.iter().map(|p| p.size()).sum::<usize>()
             + self.size.to_native() as usize
+            + self.managed_attachments().values().sum::<u64>() as usize
             + std::mem::size_of::<CalendarEvent>()
     }
 }
@@ -13,36 +13,47 @@ use crate::{
     },
     scheduling::{
         ItipError, ItipMessage,
+        event_update::itip_update,
         inbound::{
-            MergeResult, itip_import_message, itip_merge_changes, itip_method, itip_process_message,
+            MergeResult, ResourceBooking, itip_import_message, itip_merge_changes, itip_method,
+            itip_process_message, itip_resource_booking,
         },
         snapshot::itip_snapshot,
     },
+    strip_mailto_scheme,
 };
+use ahash::AHashSet;
 use calcard::{
     common::{IanaString, timezone::Tz},
     icalendar::{
-        ICalendar, ICalendarComponentType, ICalendarEntry, ICalendarMethod, ICalendarParameter,
-        ICalendarParameterName, ICalendarParameterValue, ICalendarParticipationStatus,
-        ICalendarProperty, ICalendarValue,
+        ArchivedICalendarParameterValue, ArchivedICalendarParticipationStatus,
+        ArchivedICalendarProperty, ArchivedICalendarStatus, ArchivedICalendarTransparency,
+        ArchivedICalendarValue, ICalendar, ICalendarComponentType, ICalendarEntry, ICalendarMethod,
+        ICalendarParameter, ICalendarParameterName, ICalendarParameterValue,
+        ICalendarParticipationStatus, ICalendarProperty, ICalendarUserTypes, ICalendarValue,
+        dates::TimeOrDelta,
     },
 };
+use chrono::{Datelike, Timelike, Weekday};
 use common::{
     DavName, Server,
     auth::{AccountInfo, oauth::GrantType},
     config::groupware::CalendarTemplateVariable,
     i18n,
 };
+use registry::{schema::structs::Account, types::EnumImpl};
+use std::str::FromStr;
 use store::{
     ValueKey, rand,
     write::{AlignedBytes, Archive, BatchBuilder, now},
 };
 use trc::AddContext;
 use types::{
-    collection::Collection,
+    TimeRange,
+    collection::{Collection, SyncCollection},
     field::{CalendarEventField, ContactField},
 };
-use utils::{template::Variables, url_params::UrlParams};
+use utils::{sanitize_email, template::Variables, url_params::UrlParams};
 
 pub enum ItipIngestError {
     Message(ItipError),
@@ -59,7 +70,7 @@ pub trait ItipIngest: Sync + Send {
         sender: &str,
         recipient: &str,
         itip_message: &str,
-    ) -> impl Future<Output = Result<Option<ItipMessage<ICalendar>>, ItipIngestError>> + Send;
+    ) -> impl Future<Output = Result<Vec<ItipMessage<ICalendar>>, ItipIngestError>> + Send;
 
     fn http_rsvp_url(
         &self,
@@ -83,7 +94,7 @@ impl ItipIngest for Server {
         sender: &str,
         recipient: &str,
         itip_message: &str,
-    ) -> Result<Option<ItipMessage<ICalendar>>, ItipIngestError> {
+    ) -> Result<Vec<ItipMessage<ICalendar>>, ItipIngestError> {
         // Parse and validate the iTIP message
         let mut itip = ICalendar::parse(itip_message)
             .map_err(|_| ItipIngestError::Message(ItipError::ICalendarParseError))
@@ -134,6 +145,8 @@ impl ItipIngest for Server {
         }
 
         let itip_snapshots = itip_snapshot(&itip, account_info.addresses(), false)?;
+        let is_resource_request = account_info.resource_type().is_some()
+            && itip_method(&itip).is_ok_and(|method| method == &ICalendarMethod::Request);
         if !itip_snapshots.sender_is_organizer_or_attendee(sender) {
             return Err(ItipIngestError::Message(
                 ItipError::SenderIsNotOrganizerNorAttendee,
@@ -193,6 +206,24 @@ impl ItipIngest for Server {
                         // Merge changes
                         itip_merge_changes(&mut event.data.event, changes);
 
+                        // Apply the booking policy of resource accounts
+                        let mut messages = Vec::new();
+                        if is_resource_request
+                            && let Some(booking) = resource_booking(
+                                self,
+                                account_info,
+                                &event.data.event,
+                                Some(document_id),
+                            )
+                            .await?
+                        {
+                            messages = resource_reply(
+                                &mut event.data.event,
+                                account_info.addresses(),
+                                &booking,
+                            );
+                        }
+
                         // Calculate the new ical size
                         event.size = event.data.event.to_string().len() as u32;
                         if event.size > self.core.groupware.max_ical_size as u32 {
@@ -280,10 +311,10 @@ impl ItipIngest for Server {
                             .caused_by(trc::location!())?;
                         self.commit_batch(batch).await.caused_by(trc::location!())?;
 
-                        Ok(None)
+                        Ok(messages)
                     }
-                    MergeResult::Message(itip_message) => Ok(Some(itip_message)),
-                    MergeResult::None => Ok(None),
+                    MergeResult::Message(itip_message) => Ok(vec![itip_message]),
+                    MergeResult::None => Ok(vec![]),
                 }
             } else {
                 Err(ItipIngestError::Message(ItipError::EventNotFound))
@@ -311,6 +342,14 @@ impl ItipIngest for Server {
             let mut ical = itip.clone();
             itip_import_message(&mut ical)?;
 
+            // Apply the booking policy of resource accounts
+            let mut messages = Vec::new();
+            if is_resource_request
+                && let Some(booking) = resource_booking(self, account_info, &ical, None).await?
+            {
+                messages = resource_reply(&mut ical, account_info.addresses(), &booking);
+            }
+
             // Validate quota
             if self
                 .has_available_quota(
@@ -391,7 +430,7 @@ impl ItipIngest for Server {
                 .caused_by(trc::location!())?;
             self.commit_batch(batch).await.caused_by(trc::location!())?;
 
-            Ok(None)
+            Ok(messages)
         }
     }
 
@@ -541,6 +580,259 @@ impl ItipIngest for Server {
     }
 }
 
+async fn resource_booking(
+    server: &Server,
+    account_info: &AccountInfo,
+    ical: &ICalendar,
+    document_id: Option<u32>,
+) -> trc::Result<Option<ResourceBooking>> {
+    let account_id = account_info.account_id();
+    let Some(resource) = server
+        .registry()
+        .object::<Account>(account_id.into())
+        .await?
+        .and_then(Account::into_resource)
+    else {
+        return Ok(None);
+    };
+    let Ok(snapshots) = itip_snapshot(ical, account_info.addresses(), false) else {
+        return Ok(None);
+    };
+
+    // Only evaluate invitations that have not been answered yet
+    if snapshots.organizer.email.is_local
+        || !snapshots.components.values().any(|instance| {
+            instance.local_attendee().is_some_and(|attendee| {
+                attendee
+                    .part_stat
+                    .is_none_or(|part_stat| part_stat == &ICalendarParticipationStatus::NeedsAction)
+            })
+        })
+    {
+        return Ok(None);
+    }
+
+    // Verify that the organizer is allowed to book this resource
+    let policy = &resource.booking_policy;
+    let is_authorized = policy.allowed_group_ids.is_empty()
+        || match server
+            .account_id_from_email(&snapshots.organizer.email.email, false)
+            .await?
+        {
+            Some(organizer_id) => {
+                server
+                    .account(organizer_id)
+                    .await?
+                    .id_member_of
+                    .iter()
+                    .any(|group_id| {
+                        policy
+                            .allowed_group_ids
+                            .iter()
+                            .any(|allowed_id| allowed_id.document_id() == *group_id)
+                    })
+            }
+            None => false,
+        };
+
+    if !is_authorized {
+        return Ok(Some(ResourceBooking::Decline));
+    } else if !policy.auto_accept {
+        // Forward the request to the delegates using their primary address
+        let mut delegates = Vec::with_capacity(resource.delegate_ids.len());
+        for delegate_id in resource.delegate_ids.iter() {
+            if let Some(address) = server
+                .account_info(delegate_id.document_id())
+                .await?
+                .addresses()
+                .first()
+            {
+                delegates.push(address.clone());
+            }
+        }
+        return Ok((!delegates.is_empty()).then_some(ResourceBooking::Delegate(delegates)));
+    }
+
+    // Validate capacity
+    if let Some(capacity) = resource.capacity
+        && snapshots.components.values().any(|instance| {
+            instance
+                .attendees
+                .iter()
+                .filter(|attendee| {
+                    !matches!(
+                        attendee.cu_type,
+                        Some(ICalendarUserTypes::Room | ICalendarUserTypes::Resource)
+                    )
+                })
+                .count() as u64
+                > capacity
+        })
+    {
+        return Ok(Some(ResourceBooking::Decline));
+    }
+
+    // Validate duration and working hours
+    let tz = resource
+        .time_zone
+        .and_then(|tz| Tz::from_str(tz.as_str()).ok())
+        .unwrap_or(Tz::UTC);
+    let mut instances = Vec::new();
+    for instance in ical
+        .expand_dates(tz, server.core.groupware.max_ical_instances)
+        .events
+    {
+        let start = instance.start.timestamp();
+        let end = match instance.end {
+            TimeOrDelta::Time(time) => time.timestamp(),
+            TimeOrDelta::Delta(delta) => start + delta.num_seconds(),
+        };
+        let duration = end.saturating_sub(start).max(0) as u64;
+        if policy
+            .max_duration
+            .as_ref()
+            .is_some_and(|max_duration| duration > max_duration.0.as_secs())
+        {
+            return Ok(Some(ResourceBooking::Decline));
+        }
+
+        let local_start = instance.start.with_timezone(&tz);
+        let start_minute = (local_start.hour() * 60 + local_start.minute()) as u64;
+        if (policy.weekdays_only && matches!(local_start.weekday(), Weekday::Sat | Weekday::Sun))
+            || policy
+                .working_hours_start
+                .is_some_and(|hours_start| start_minute < hours_start)
+            || policy
+                .working_hours_end
+                .is_some_and(|hours_end| start_minute + duration.div_ceil(60) > hours_end)
+        {
+            return Ok(Some(ResourceBooking::Decline));
+        }
+
+        instances.push((start, end));
+    }
+
+    // Check for conflicts with existing bookings
+    if policy.decline_conflicts && !instances.is_empty() {
+        let range = TimeRange {
+            start: instances.iter().map(|(start, _)| *start).min().unwrap(),
+            end: instances.iter().map(|(_, end)| *end).max().unwrap(),
+        };
+        let resources = server
+            .fetch_dav_resources(account_id, account_id, SyncCollection::Calendar)
+            .await
+            .caused_by(trc::location!())?;
+
+        for resource in resources.resources.iter().filter(|r| {
+            Some(r.document_id) != document_id
+                && r.event_time_range()
+                    .is_some_and(|(start, end)| range.is_in_range(false, start, end))
+        }) {
+            let Some(archive) = server
+                .store()
+                .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                    account_id,
+                    Collection::CalendarEvent,
+                    resource.document_id,
+                ))
+                .await
+                .caused_by(trc::location!())?
+            else {
+                continue;
+            };
+            let event = archive
+                .unarchive::<CalendarEvent>()
+                .caused_by(trc::location!())?;
+
+            // Cancelled, transparent or declined components do not block the resource
+            let busy_ids = event
+                .data
+                .event
+                .components
+                .iter()
+                .enumerate()
+                .filter(|(_, component)| {
+                    component.component_type.is_event_or_todo()
+                        && !component.entries.iter().any(|entry| {
+                            match (&entry.name, entry.values.first()) {
+                                (
+                                    ArchivedICalendarProperty::Status,
+                                    Some(ArchivedICalendarValue::Status(
+                                        ArchivedICalendarStatus::Cancelled,
+                                    )),
+                                )
+                                | (
+                                    ArchivedICalendarProperty::Transp,
+                                    Some(ArchivedICalendarValue::Transparency(
+                                        ArchivedICalendarTransparency::Transparent,
+                                    )),
+                                ) => true,
+                                (ArchivedICalendarProperty::Attendee, Some(value)) => {
+                                    value
+                                        .as_text()
+                                        .and_then(|attendee| {
+                                            sanitize_email(strip_mailto_scheme(attendee))
+                                        })
+                                        .is_some_and(|attendee| {
+                                            account_info.addresses().contains(&attendee)
+                                        })
+                                        && entry.parameters(&ICalendarParameterName::Partstat).any(
+                                            |value| {
+                                                matches!(
+                                                    value,
+                                                    ArchivedICalendarParameterValue::Partstat(
+                                                ArchivedICalendarParticipationStatus::Declined
+                                            )
+                                                )
+                                            },
+                                        )
+                                }
+                                _ => false,
+                            }
+                        })
+                })
+                .map(|(component_id, _)| component_id as u32)
+                .collect::<AHashSet<_>>();
+
+            if event
+                .data
+                .expand(tz, range)
+                .unwrap_or_default()
+                .iter()
+                .filter(|expansion| busy_ids.contains(&expansion.comp_id))
+                .any(|expansion| {
+                    instances
+                        .iter()
+                        .any(|(start, end)| expansion.start < *end && expansion.end > *start)
+                })
+            {
+                return Ok(Some(ResourceBooking::Decline));
+            }
+        }
+    }
+
+    Ok(Some(ResourceBooking::Accept))
+}
+
+fn resource_reply(
+    ical: &mut ICalendar,
+    account_emails: &[String],
+    booking: &ResourceBooking,
+) -> Vec<ItipMessage<ICalendar>> {
+    let old_ical = ical.clone();
+    if itip_resource_booking(ical, account_emails, booking) {
+        match itip_update(ical, &old_ical, account_emails) {
+            Ok(messages) => messages,
+            Err(_) => {
+                *ical = old_ical;
+                vec![]
+            }
+        }
+    } else {
+        vec![]
+    }
+}
+
 struct RsvpResponse {
     account_id: u32,
     document_id: u32,
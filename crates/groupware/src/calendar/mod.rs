@@ -5,11 +5,15 @@
  */
 
 pub mod alarm;
+pub mod attachment;
+pub mod availability;
 pub mod dates;
 pub mod expand;
 pub mod index;
 pub mod itip;
+pub mod publish;
 pub mod storage;
+pub mod subscription;
 
 use calcard::icalendar::{
     ICalendar, ICalendarComponent, ICalendarComponentType, ICalendarDuration, ICalendarEntry,
@@ -0,0 +1,567 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{ArchivedCalendar, Calendar, CalendarEvent};
+use crate::cache::GroupwareCache;
+use ahash::{AHashMap, AHashSet};
+use calcard::{
+    common::{PartialDateTime, timezone::Tz},
+    icalendar::{
+        ArchivedICalendarComponentType, ArchivedICalendarProperty, ArchivedICalendarStatus,
+        ArchivedICalendarTransparency, ArchivedICalendarValue, ICalendar, ICalendarClassification,
+        ICalendarComponent, ICalendarComponentType, ICalendarEntry, ICalendarFreeBusyType,
+        ICalendarParameter, ICalendarPeriod, ICalendarProperty, ICalendarValue,
+    },
+};
+use common::{PROD_ID, PublishedCalendar, Server};
+use std::{future::Future, sync::Arc};
+use store::{
+    Serialize, ValueKey,
+    rand::{RngExt, distr::Alphanumeric, rng},
+    write::{AlignedBytes, Archive, Archiver, BatchBuilder, now},
+};
+use trc::AddContext;
+use types::{
+    TimeRange,
+    collection::{Collection, SyncCollection},
+    field::CalendarField,
+    id::Id,
+};
+use utils::constant_time_eq;
+
+const SECRET_LEN: usize = 32;
+const FREEBUSY_PAST: i64 = 30 * 24 * 60 * 60;
+const FREEBUSY_FUTURE: i64 = 366 * 24 * 60 * 60;
+const FREEBUSY_CACHE_TTL: u64 = 60 * 60;
+
+#[derive(rkyv::Serialize, rkyv::Deserialize, rkyv::Archive, Debug, Clone, PartialEq, Eq)]
+pub struct CalendarPublication {
+    pub secret: String,
+    pub mode: CalendarPublishMode,
+}
+
+#[derive(rkyv::Serialize, rkyv::Deserialize, rkyv::Archive, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalendarPublishMode {
+    Full,
+    FreeBusy,
+}
+
+pub trait CalendarPublish: Sync + Send {
+    fn calendar_publication(
+        &self,
+        account_id: u32,
+        calendar_id: u32,
+    ) -> impl Future<Output = trc::Result<Option<CalendarPublication>>> + Send;
+
+    fn http_publish_handle(
+        &self,
+        id: &str,
+        secret: &str,
+    ) -> impl Future<Output = trc::Result<Option<String>>> + Send;
+}
+
+impl CalendarPublish for Server {
+    async fn calendar_publication(
+        &self,
+        account_id: u32,
+        calendar_id: u32,
+    ) -> trc::Result<Option<CalendarPublication>> {
+        if let Some(publication) = self
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                account_id,
+                Collection::Calendar,
+                calendar_id,
+                CalendarField::Publication,
+            ))
+            .await
+            .caused_by(trc::location!())?
+        {
+            publication
+                .deserialize::<CalendarPublication>()
+                .caused_by(trc::location!())
+                .map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn http_publish_handle(&self, id: &str, secret: &str) -> trc::Result<Option<String>> {
+        // Validate the secret
+        let Ok(id) = id.parse::<Id>() else {
+            return Ok(None);
+        };
+        let account_id = id.prefix_id();
+        let calendar_id = id.document_id();
+        let Some(publication) = self
+            .calendar_publication(account_id, calendar_id)
+            .await
+            .caused_by(trc::location!())?
+            .filter(|publication| publication.matches_secret(secret))
+        else {
+            return Ok(None);
+        };
+
+        // Obtain the events in the calendar
+        let resources = self
+            .fetch_dav_resources(account_id, account_id, SyncCollection::Calendar)
+            .await
+            .caused_by(trc::location!())?;
+        let is_free_busy = publication.mode == CalendarPublishMode::FreeBusy;
+        let cache_key = id.id();
+        if let Some(cached) = self
+            .inner
+            .cache
+            .publications
+            .get(&cache_key)
+            .filter(|cached| {
+                cached.change_id == resources.highest_change_id
+                    && cached.is_free_busy == is_free_busy
+                    && cached.expires > now()
+            })
+        {
+            return Ok(Some(cached.ical.clone()));
+        }
+
+        let Some(calendar_) = self
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                account_id,
+                Collection::Calendar,
+                calendar_id,
+            ))
+            .await
+            .caused_by(trc::location!())?
+        else {
+            return Ok(None);
+        };
+        let calendar = calendar_
+            .unarchive::<Calendar>()
+            .caused_by(trc::location!())?;
+        let default_tz = resources
+            .container_resource_by_id(calendar_id)
+            .and_then(|resource| resource.calendar_preferences(account_id))
+            .map(|preferences| preferences.tz)
+            .unwrap_or(Tz::UTC);
+        let range = TimeRange {
+            start: now() as i64 - FREEBUSY_PAST,
+            end: now() as i64 + FREEBUSY_FUTURE,
+        };
+        let document_ids = resources
+            .children(calendar_id)
+            .filter(|resource| {
+                publication.mode == CalendarPublishMode::Full
+                    || resource
+                        .resource
+                        .event_time_range()
+                        .is_some_and(|(start, end)| range.is_in_range(false, start, end))
+            })
+            .map(|resource| resource.document_id())
+            .collect::<Vec<_>>();
+
+        let mut builder = PublishBuilder::new(publication.mode, calendar, account_id);
+        let max_instances = self.core.groupware.max_ical_instances;
+        let max_size = self.core.groupware.max_ical_size;
+        let mut size = 0;
+        'outer: for document_id in document_ids {
+            let Some(archive) = self
+                .store()
+                .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                    account_id,
+                    Collection::CalendarEvent,
+                    document_id,
+                ))
+                .await
+                .caused_by(trc::location!())?
+            else {
+                continue;
+            };
+
+            match publication.mode {
+                CalendarPublishMode::Full => {
+                    let event = archive
+                        .deserialize::<CalendarEvent>()
+                        .caused_by(trc::location!())?;
+
+                    // Truncate the feed once it reaches the configured limits
+                    size += event.size as usize;
+                    if size > max_size || builder.num_instances() >= max_instances {
+                        break;
+                    }
+                    builder.add_event(event.data.event);
+                }
+                CalendarPublishMode::FreeBusy => {
+                    let event = archive
+                        .unarchive::<CalendarEvent>()
+                        .caused_by(trc::location!())?;
+
+                    // Only opaque events that were not cancelled block time
+                    let busy_ids = event
+                        .data
+                        .event
+                        .components
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(component_id, component)| {
+                            if !matches!(
+                                component.component_type,
+                                ArchivedICalendarComponentType::VEvent
+                            ) {
+                                return None;
+                            }
+                            let mut fbtype = ICalendarFreeBusyType::Busy;
+                            for entry in component.entries.iter() {
+                                match (&entry.name, entry.values.first()) {
+                                    (
+                                        ArchivedICalendarProperty::Status,
+                                        Some(ArchivedICalendarValue::Status(
+                                            ArchivedICalendarStatus::Cancelled,
+                                        )),
+                                    )
+                                    | (
+                                        ArchivedICalendarProperty::Transp,
+                                        Some(ArchivedICalendarValue::Transparency(
+                                            ArchivedICalendarTransparency::Transparent,
+                                        )),
+                                    ) => return None,
+                                    (
+                                        ArchivedICalendarProperty::Status,
+                                        Some(ArchivedICalendarValue::Status(
+                                            ArchivedICalendarStatus::Tentative,
+                                        )),
+                                    ) => {
+                                        fbtype = ICalendarFreeBusyType::BusyTentative;
+                                    }
+                                    _ => {}
+                                }
+                            }
+                            Some((component_id as u32, fbtype))
+                        })
+                        .collect::<AHashMap<_, _>>();
+                    if busy_ids.is_empty() {
+                        continue;
+                    }
+
+                    for expansion in event.data.expand(default_tz, range).unwrap_or_default() {
+                        if let Some(fbtype) = busy_ids.get(&expansion.comp_id) {
+                            if builder.num_instances() >= max_instances {
+                                break 'outer;
+                            }
+                            builder.add_period(fbtype.clone(), expansion.start, expansion.end);
+                        }
+                    }
+                }
+            }
+        }
+
+        let ical = builder.build(range).to_string();
+        self.inner.cache.publications.insert(
+            cache_key,
+            Arc::new(PublishedCalendar {
+                change_id: resources.highest_change_id,
+                is_free_busy,
+                expires: if is_free_busy {
+                    now() + FREEBUSY_CACHE_TTL
+                } else {
+                    u64::MAX
+                },
+                ical: ical.clone(),
+            }),
+        );
+
+        Ok(Some(ical))
+    }
+}
+
+impl CalendarPublication {
+    pub fn new(mode: CalendarPublishMode) -> Self {
+        CalendarPublication {
+            secret: rng()
+                .sample_iter(Alphanumeric)
+                .take(SECRET_LEN)
+                .map(char::from)
+                .collect(),
+            mode,
+        }
+    }
+
+    pub fn write(self, batch: &mut BatchBuilder) -> trc::Result<()> {
+        batch.set(
+            CalendarField::Publication,
+            Archiver::new(self)
+                .serialize()
+                .caused_by(trc::location!())?,
+        );
+        Ok(())
+    }
+
+    pub fn url(&self, base_url: &str, account_id: u32, calendar_id: u32) -> String {
+        format!(
+            "{}/calendar/publish/{}/{}.ics",
+            base_url.trim_end_matches('/'),
+            Id::from_parts(account_id, calendar_id),
+            self.secret
+        )
+    }
+
+    fn matches_secret(&self, secret: &str) -> bool {
+        constant_time_eq(self.secret.as_bytes(), secret.as_bytes())
+    }
+}
+
+impl CalendarPublishMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map!(value.as_bytes(),
+            "full" => CalendarPublishMode::Full,
+            "freeBusy" => CalendarPublishMode::FreeBusy,
+        )
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CalendarPublishMode::Full => "full",
+            CalendarPublishMode::FreeBusy => "freeBusy",
+        }
+    }
+}
+
+struct PublishBuilder {
+    mode: CalendarPublishMode,
+    name: String,
+    ical: ICalendar,
+    timezones: AHashSet<String>,
+    periods: AHashMap<ICalendarFreeBusyType, Vec<(i64, i64)>>,
+    num_instances: usize,
+}
+
+impl PublishBuilder {
+    fn new(mode: CalendarPublishMode, calendar: &ArchivedCalendar, account_id: u32) -> Self {
+        let name = calendar
+            .preferences
+            .iter()
+            .find(|preferences| preferences.account_id == account_id)
+            .or_else(|| calendar.preferences.first())
+            .map(|preferences| preferences.name.as_str().to_string())
+            .unwrap_or_else(|| calendar.name.as_str().to_string());
+
+        PublishBuilder {
+            mode,
+            ical: ICalendar {
+                components: vec![ICalendarComponent {
+                    component_type: ICalendarComponentType::VCalendar,
+                    entries: vec![
+                        ICalendarEntry {
+                            name: ICalendarProperty::Version,
+                            params: vec![],
+                            values: vec![ICalendarValue::Text("2.0".to_string())],
+                        },
+                        ICalendarEntry {
+                            name: ICalendarProperty::Prodid,
+                            params: vec![],
+                            values: vec![ICalendarValue::Text(PROD_ID.to_string())],
+                        },
+                        ICalendarEntry {
+                            name: ICalendarProperty::Other("X-WR-CALNAME".to_string()),
+                            params: vec![],
+                            values: vec![ICalendarValue::Text(name.clone())],
+                        },
+                    ],
+                    component_ids: vec![],
+                }],
+            },
+            name,
+            timezones: AHashSet::new(),
+            periods: AHashMap::new(),
+            num_instances: 0,
+        }
+    }
+
+    fn add_event(&mut self, event: ICalendar) {
+        let Some(root) = event
+            .components
+            .first()
+            .filter(|c| c.component_type == ICalendarComponentType::VCalendar)
+        else {
+            return;
+        };
+
+        for component_id in &root.component_ids {
+            let component = &event.components[*component_id as usize];
+            match &component.component_type {
+                ICalendarComponentType::VTimezone => {
+                    // Timezones are shared across events
+                    let Some(tz_id) = component
+                        .property(&ICalendarProperty::Tzid)
+                        .and_then(|entry| entry.values.first())
+                        .and_then(|value| value.as_text())
+                    else {
+                        continue;
+                    };
+                    if !self.timezones.insert(tz_id.to_string()) {
+                        continue;
+                    }
+                }
+                component_type if component_type.is_scheduling_object() => {
+                    self.num_instances += 1;
+                }
+                _ => continue,
+            }
+
+            let new_id = self.copy_component(&event, *component_id);
+            self.ical.components[0].component_ids.push(new_id);
+        }
+    }
+
+    fn copy_component(&mut self, event: &ICalendar, component_id: u32) -> u32 {
+        let component = &event.components[component_id as usize];
+        let new_id = self.ical.components.len() as u32;
+        let is_private = component.entries.iter().any(|entry| {
+            entry.name == ICalendarProperty::Class
+                && matches!(
+                    entry.values.first(),
+                    Some(ICalendarValue::Classification(
+                        ICalendarClassification::Private | ICalendarClassification::Confidential
+                    ))
+                )
+        });
+        let entries = if is_private {
+            // Private events only disclose their time
+            component
+                .entries
+                .iter()
+                .filter(|entry| {
+                    matches!(
+                        entry.name,
+                        ICalendarProperty::Uid
+                            | ICalendarProperty::Dtstamp
+                            | ICalendarProperty::Dtstart
+                            | ICalendarProperty::Dtend
+                            | ICalendarProperty::Due
+                            | ICalendarProperty::Duration
+                            | ICalendarProperty::Rrule
+                            | ICalendarProperty::Rdate
+                            | ICalendarProperty::Exdate
+                            | ICalendarProperty::RecurrenceId
+                            | ICalendarProperty::Status
+                            | ICalendarProperty::Transp
+                            | ICalendarProperty::Class
+                    )
+                })
+                .cloned()
+                .chain([ICalendarEntry {
+                    name: ICalendarProperty::Summary,
+                    params: vec![],
+                    values: vec![ICalendarValue::Text("Busy".to_string())],
+                }])
+                .collect()
+        } else {
+            component.entries.clone()
+        };
+        self.ical.components.push(ICalendarComponent {
+            component_type: component.component_type.clone(),
+            entries,
+            component_ids: vec![],
+        });
+
+        // Alarms belong to the owner and private events have no sub-components
+        if !is_private {
+            for sub_component_id in &component.component_ids {
+                if event.components[*sub_component_id as usize].component_type
+                    != ICalendarComponentType::VAlarm
+                {
+                    let sub_id = self.copy_component(event, *sub_component_id);
+                    self.ical.components[new_id as usize]
+                        .component_ids
+                        .push(sub_id);
+                }
+            }
+        }
+
+        new_id
+    }
+
+    fn add_period(&mut self, fbtype: ICalendarFreeBusyType, start: i64, end: i64) {
+        self.periods.entry(fbtype).or_default().push((start, end));
+        self.num_instances += 1;
+    }
+
+    fn num_instances(&self) -> usize {
+        self.num_instances
+    }
+
+    fn build(mut self, range: TimeRange) -> ICalendar {
+        if self.mode == CalendarPublishMode::FreeBusy {
+            let mut entries = vec![
+                ICalendarEntry {
+                    name: ICalendarProperty::Dtstamp,
+                    params: vec![],
+                    values: vec![ICalendarValue::PartialDateTime(Box::new(
+                        PartialDateTime::from_utc_timestamp(now() as i64),
+                    ))],
+                },
+                ICalendarEntry {
+                    name: ICalendarProperty::Dtstart,
+                    params: vec![],
+                    values: vec![ICalendarValue::PartialDateTime(Box::new(
+                        PartialDateTime::from_utc_timestamp(range.start),
+                    ))],
+                },
+                ICalendarEntry {
+                    name: ICalendarProperty::Dtend,
+                    params: vec![],
+                    values: vec![ICalendarValue::PartialDateTime(Box::new(
+                        PartialDateTime::from_utc_timestamp(range.end),
+                    ))],
+                },
+                ICalendarEntry {
+                    name: ICalendarProperty::Summary,
+                    params: vec![],
+                    values: vec![ICalendarValue::Text(self.name)],
+                },
+            ];
+            for (fbtype, periods) in self.periods {
+                entries.push(ICalendarEntry {
+                    name: ICalendarProperty::Freebusy,
+                    params: vec![ICalendarParameter::fbtype(fbtype)],
+                    values: merge_periods(periods),
+                });
+            }
+
+            let component_id = self.ical.components.len() as u32;
+            self.ical.components.push(ICalendarComponent {
+                component_type: ICalendarComponentType::VFreebusy,
+                entries,
+                component_ids: vec![],
+            });
+            self.ical.components[0].component_ids.push(component_id);
+        }
+
+        self.ical
+    }
+}
+
+fn merge_periods(mut periods: Vec<(i64, i64)>) -> Vec<ICalendarValue> {
+    periods.sort_unstable_by_key(|period| period.0);
+    let mut merged: Vec<(i64, i64)> = Vec::with_capacity(periods.len());
+    for (start, end) in periods {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => {
+                last.1 = last.1.max(end);
+            }
+            _ => merged.push((start, end)),
+        }
+    }
+
+    merged
+        .into_iter()
+        .map(|(start, end)| {
+            ICalendarValue::Period(ICalendarPeriod::Range {
+                start: PartialDateTime::from_utc_timestamp(start),
+                end: PartialDateTime::from_utc_timestamp(end),
+            })
+        })
+        .collect()
+}
@@ -36,7 +36,7 @@ use store::{
 use trc::AddContext;
 use types::{
     collection::{Collection, VanishedCollection},
-    field::CalendarNotificationField,
+    field::{CalendarField, CalendarNotificationField},
     id::Id,
 };
 
@@ -391,7 +391,9 @@ impl DestroyArchive<Archive<&ArchivedCalendar>> {
                     .with_changed_by(changed_by)
                     .with_current(calendar),
             )
-            .caused_by(trc::location!())?;
+            .caused_by(trc::location!())?
+            .clear(CalendarField::Subscription)
+            .clear(CalendarField::Publication);
         if let Some(delete_path) = delete_path {
             batch.log_vanished_item(VanishedCollection::Calendar, delete_path);
         }
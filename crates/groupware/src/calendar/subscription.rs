@@ -0,0 +1,248 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use ahash::AHashMap;
+use calcard::icalendar::{
+    ICalendar, ICalendarComponent, ICalendarComponentType, ICalendarProperty,
+};
+use common::Server;
+use registry::schema::structs::{Task, TaskCalendarSubscription, TaskStatus};
+use std::future::Future;
+use store::{
+    Serialize, ValueKey,
+    roaring::RoaringBitmap,
+    write::{AlignedBytes, Archive, Archiver, BatchBuilder},
+};
+use trc::AddContext;
+use types::{acl::Acl, collection::Collection, field::CalendarField};
+
+pub const MIN_REFRESH_INTERVAL: u64 = 15 * 60;
+pub const DEFAULT_REFRESH_INTERVAL: u64 = 24 * 60 * 60;
+
+// Events in subscribed calendars are only written by the refresh task
+pub const SUBSCRIPTION_READ_ONLY_ACLS: [Acl; 4] = [
+    Acl::AddItems,
+    Acl::ModifyItems,
+    Acl::ModifyItemsOwn,
+    Acl::ModifyRSVP,
+];
+
+#[derive(rkyv::Serialize, rkyv::Deserialize, rkyv::Archive, Debug, Clone, PartialEq, Eq)]
+pub struct CalendarSubscription {
+    pub url: String,
+    pub refresh_interval: u64,
+    pub last_refresh: u64,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub last_error: Option<String>,
+}
+
+pub trait CalendarSubscriptionFetch: Sync + Send {
+    fn calendar_subscription(
+        &self,
+        account_id: u32,
+        calendar_id: u32,
+    ) -> impl Future<Output = trc::Result<Option<CalendarSubscription>>> + Send;
+
+    fn is_subscribed_calendar(
+        &self,
+        account_id: u32,
+        calendar_id: u32,
+    ) -> impl Future<Output = trc::Result<bool>> + Send;
+
+    fn subscribed_calendar_ids(
+        &self,
+        account_id: u32,
+        calendar_ids: impl Iterator<Item = u32> + Send,
+    ) -> impl Future<Output = trc::Result<RoaringBitmap>> + Send;
+}
+
+impl CalendarSubscriptionFetch for Server {
+    async fn calendar_subscription(
+        &self,
+        account_id: u32,
+        calendar_id: u32,
+    ) -> trc::Result<Option<CalendarSubscription>> {
+        if let Some(subscription) = self
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                account_id,
+                Collection::Calendar,
+                calendar_id,
+                CalendarField::Subscription,
+            ))
+            .await
+            .caused_by(trc::location!())?
+        {
+            subscription
+                .deserialize::<CalendarSubscription>()
+                .caused_by(trc::location!())
+                .map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn is_subscribed_calendar(&self, account_id: u32, calendar_id: u32) -> trc::Result<bool> {
+        self.store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                account_id,
+                Collection::Calendar,
+                calendar_id,
+                CalendarField::Subscription,
+            ))
+            .await
+            .caused_by(trc::location!())
+            .map(|subscription| subscription.is_some())
+    }
+
+    async fn subscribed_calendar_ids(
+        &self,
+        account_id: u32,
+        calendar_ids: impl Iterator<Item = u32> + Send,
+    ) -> trc::Result<RoaringBitmap> {
+        let mut subscribed_ids = RoaringBitmap::new();
+        for calendar_id in calendar_ids {
+            if self.is_subscribed_calendar(account_id, calendar_id).await? {
+                subscribed_ids.insert(calendar_id);
+            }
+        }
+
+        Ok(subscribed_ids)
+    }
+}
+
+impl CalendarSubscription {
+    pub fn new(url: String, refresh_interval: u64) -> Self {
+        CalendarSubscription {
+            url,
+            refresh_interval: refresh_interval.max(MIN_REFRESH_INTERVAL),
+            last_refresh: 0,
+            etag: None,
+            last_modified: None,
+            last_error: None,
+        }
+    }
+
+    // Writes the subscription property and schedules the next refresh,
+    // the batch must point to the calendar document.
+    pub fn write(
+        self,
+        batch: &mut BatchBuilder,
+        account_id: u32,
+        calendar_id: u32,
+        refresh_at: u64,
+    ) -> trc::Result<()> {
+        batch
+            .set(
+                CalendarField::Subscription,
+                Archiver::new(self)
+                    .serialize()
+                    .caused_by(trc::location!())?,
+            )
+            .schedule_task(Task::CalendarSubscription(TaskCalendarSubscription {
+                account_id: account_id.into(),
+                document_id: calendar_id.into(),
+                status: TaskStatus::at(refresh_at as i64),
+            }));
+
+        Ok(())
+    }
+
+    pub fn next_refresh(&self) -> u64 {
+        self.last_refresh + self.refresh_interval
+    }
+}
+
+// Splits a calendar feed into one iCalendar object per UID, each one carrying
+// the timezones defined in the feed. Alarms are dropped as they are set by the
+// publisher and would otherwise fire for every subscriber.
+pub fn split_calendar_feed(feed: &ICalendar) -> Vec<(String, ICalendar)> {
+    let Some(root) = feed
+        .components
+        .first()
+        .filter(|c| c.component_type == ICalendarComponentType::VCalendar)
+    else {
+        return vec![];
+    };
+    let envelope = ICalendarComponent {
+        component_type: ICalendarComponentType::VCalendar,
+        entries: root
+            .entries
+            .iter()
+            .filter(|e| {
+                matches!(
+                    e.name,
+                    ICalendarProperty::Version
+                        | ICalendarProperty::Prodid
+                        | ICalendarProperty::Calscale
+                )
+            })
+            .cloned()
+            .collect(),
+        component_ids: vec![],
+    };
+    let timezone_ids = root
+        .component_ids
+        .iter()
+        .filter(|id| {
+            feed.components[**id as usize].component_type == ICalendarComponentType::VTimezone
+        })
+        .copied()
+        .collect::<Vec<_>>();
+
+    let mut calendars: Vec<(String, ICalendar)> = Vec::new();
+    let mut uid_map: AHashMap<&str, usize> = AHashMap::new();
+
+    for component_id in &root.component_ids {
+        let component = &feed.components[*component_id as usize];
+        if !component.component_type.is_scheduling_object() {
+            continue;
+        }
+        let Some(uid) = component.uid() else {
+            continue;
+        };
+
+        let idx = *uid_map.entry(uid).or_insert_with(|| {
+            let mut ical = ICalendar {
+                components: vec![envelope.clone()],
+            };
+            for timezone_id in &timezone_ids {
+                let new_id = copy_component(feed, *timezone_id, &mut ical);
+                ical.components[0].component_ids.push(new_id);
+            }
+            calendars.push((uid.to_string(), ical));
+            calendars.len() - 1
+        });
+
+        let ical = &mut calendars[idx].1;
+        let new_id = copy_component(feed, *component_id, ical);
+        ical.components[0].component_ids.push(new_id);
+    }
+
+    calendars
+}
+
+fn copy_component(feed: &ICalendar, component_id: u32, ical: &mut ICalendar) -> u32 {
+    let component = &feed.components[component_id as usize];
+    let new_id = ical.components.len() as u32;
+    ical.components.push(ICalendarComponent {
+        component_type: component.component_type.clone(),
+        entries: component.entries.clone(),
+        component_ids: vec![],
+    });
+
+    for sub_component_id in &component.component_ids {
+        if feed.components[*sub_component_id as usize].component_type
+            != ICalendarComponentType::VAlarm
+        {
+            let sub_id = copy_component(feed, *sub_component_id, ical);
+            ical.components[new_id as usize].component_ids.push(sub_id);
+        }
+    }
+
+    new_id
+}
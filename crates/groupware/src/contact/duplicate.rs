@@ -0,0 +1,299 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{ArchivedContactCard, ContactCard};
+use ahash::{AHashMap, AHashSet};
+use calcard::vcard::{ArchivedVCardValue, VCardEntry, VCardProperty, VCardValue};
+use std::collections::hash_map::Entry;
+use store::search::ContactSearchField;
+use utils::sanitize_email;
+
+const MIN_PHONE_DIGITS: usize = 7;
+const MAX_PHONE_DIGITS: usize = 10;
+
+#[derive(Debug, Default)]
+pub struct DuplicateFinder {
+    keys: AHashMap<(ContactSearchField, String), usize>,
+    document_ids: Vec<u32>,
+    parents: Vec<usize>,
+    matches: Vec<(usize, ContactSearchField)>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DuplicateCluster {
+    pub document_ids: Vec<u32>,
+    pub matched_on: Vec<ContactSearchField>,
+}
+
+impl DuplicateFinder {
+    pub fn add(&mut self, document_id: u32, card: &ArchivedContactCard) {
+        if card.is_group() {
+            return;
+        }
+
+        let idx = self.document_ids.len();
+        self.document_ids.push(document_id);
+        self.parents.push(idx);
+
+        for key in card.duplicate_keys() {
+            match self.keys.entry(key) {
+                Entry::Occupied(entry) => {
+                    let field = entry.key().0;
+                    let other_idx = *entry.get();
+                    self.union(idx, other_idx);
+                    self.matches.push((idx, field));
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(idx);
+                }
+            }
+        }
+    }
+
+    pub fn clusters(mut self) -> Vec<DuplicateCluster> {
+        let mut clusters: AHashMap<usize, DuplicateCluster> = AHashMap::new();
+        for (idx, field) in std::mem::take(&mut self.matches) {
+            let cluster = clusters.entry(self.find(idx)).or_default();
+            if !cluster.matched_on.contains(&field) {
+                cluster.matched_on.push(field);
+            }
+        }
+        for idx in 0..self.document_ids.len() {
+            if let Some(cluster) = clusters.get_mut(&self.find(idx)) {
+                cluster.document_ids.push(self.document_ids[idx]);
+            }
+        }
+
+        let mut clusters = clusters.into_values().collect::<Vec<_>>();
+        for cluster in &mut clusters {
+            cluster.document_ids.sort_unstable();
+            cluster
+                .matched_on
+                .sort_unstable_by_key(|field| match field {
+                    ContactSearchField::Email => 0,
+                    ContactSearchField::Phone => 1,
+                    _ => 2,
+                });
+        }
+        clusters.sort_unstable_by_key(|cluster| cluster.document_ids[0]);
+        clusters
+    }
+
+    fn find(&mut self, mut idx: usize) -> usize {
+        while self.parents[idx] != idx {
+            self.parents[idx] = self.parents[self.parents[idx]];
+            idx = self.parents[idx];
+        }
+        idx
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a != b {
+            self.parents[a.max(b)] = a.min(b);
+        }
+    }
+}
+
+impl ContactCard {
+    pub fn merge(&mut self, other: ContactCard) {
+        // Combine multi-valued properties, fill in missing ones
+        let mut keys = self
+            .card
+            .entries
+            .iter()
+            .filter_map(merge_key)
+            .collect::<AHashSet<_>>();
+        for entry in other.card.entries {
+            if is_combined_property(&entry.name) {
+                if merge_key(&entry).is_some_and(|key| keys.insert(key)) {
+                    self.card.entries.push(entry);
+                }
+            } else if is_single_property(&entry.name)
+                && self.card.properties(&entry.name).next().is_none()
+            {
+                self.card.entries.push(entry);
+            }
+        }
+
+        // Keep the card in all address books
+        for name in other.names {
+            if self.names.iter().all(|n| n.parent_id != name.parent_id) {
+                self.names.push(name);
+            }
+        }
+
+        if self.display_name.is_none() {
+            self.display_name = other.display_name;
+        }
+        self.size = self.card.size() as u32;
+    }
+
+    pub fn replace_members(&mut self, old_uids: &[&str], new_uid: &str) -> bool {
+        let mut has_new_uid = self
+            .card
+            .properties(&VCardProperty::Member)
+            .flat_map(|entry| entry.values.iter().filter_map(|v| v.as_text()))
+            .any(|member| is_same_uid(member, new_uid));
+        let mut has_changes = false;
+
+        self.card.entries.retain_mut(|entry| {
+            if !matches!(entry.name, VCardProperty::Member) {
+                return true;
+            }
+            let Some(member) = entry
+                .values
+                .iter()
+                .filter_map(|v| v.as_text())
+                .find(|member| old_uids.iter().any(|uid| is_same_uid(member, uid)))
+            else {
+                return true;
+            };
+
+            has_changes = true;
+            if has_new_uid {
+                false
+            } else {
+                let new_member = if member.starts_with("urn:uuid:") && !new_uid.contains(':') {
+                    format!("urn:uuid:{new_uid}")
+                } else {
+                    new_uid.to_string()
+                };
+                entry.values = vec![VCardValue::Text(new_member)];
+                has_new_uid = true;
+                true
+            }
+        });
+
+        if has_changes {
+            self.size = self.card.size() as u32;
+        }
+
+        has_changes
+    }
+}
+
+impl ArchivedContactCard {
+    pub fn is_group(&self) -> bool {
+        self.card
+            .properties(&VCardProperty::Kind)
+            .flat_map(|entry| entry.values.iter())
+            .any(|value| match value {
+                ArchivedVCardValue::Kind(kind) => kind.as_str().eq_ignore_ascii_case("group"),
+                ArchivedVCardValue::Text(kind) => kind.as_str().eq_ignore_ascii_case("group"),
+                _ => false,
+            })
+    }
+
+    pub fn duplicate_keys(&self) -> AHashSet<(ContactSearchField, String)> {
+        let mut keys = AHashSet::new();
+
+        for email in self.emails() {
+            keys.insert((ContactSearchField::Email, email.to_lowercase()));
+        }
+        for phone in self
+            .card
+            .properties(&VCardProperty::Tel)
+            .flat_map(|entry| entry.values.iter().filter_map(|v| v.as_text()))
+            .filter_map(normalize_phone)
+        {
+            keys.insert((ContactSearchField::Phone, phone));
+        }
+        if let Some(name) = self.full_name().and_then(normalize_name) {
+            keys.insert((ContactSearchField::Name, name));
+        }
+
+        keys
+    }
+}
+
+fn merge_key(entry: &VCardEntry) -> Option<(ContactSearchField, String)> {
+    let text = || entry.values.iter().find_map(|v| v.as_text());
+
+    match entry.name {
+        VCardProperty::Email => text()
+            .and_then(sanitize_email)
+            .map(|email| (ContactSearchField::Email, email.to_lowercase())),
+        VCardProperty::Tel => text().map(|phone| {
+            (
+                ContactSearchField::Phone,
+                normalize_phone(phone).unwrap_or_else(|| phone.trim().to_lowercase()),
+            )
+        }),
+        VCardProperty::Adr => {
+            let address = entry
+                .values
+                .iter()
+                .flat_map(|value| match value {
+                    VCardValue::Component(parts) => parts.clone(),
+                    VCardValue::Text(text) => vec![text.clone()],
+                    _ => vec![],
+                })
+                .map(|part| part.trim().to_lowercase())
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>();
+            (!address.is_empty()).then(|| (ContactSearchField::Address, address.join(";")))
+        }
+        VCardProperty::Member => text().map(|member| {
+            (
+                ContactSearchField::Member,
+                member
+                    .strip_prefix("urn:uuid:")
+                    .unwrap_or(member)
+                    .to_lowercase(),
+            )
+        }),
+        _ => None,
+    }
+}
+
+fn is_combined_property(property: &VCardProperty) -> bool {
+    matches!(
+        property,
+        VCardProperty::Email | VCardProperty::Tel | VCardProperty::Adr | VCardProperty::Member
+    )
+}
+
+fn is_single_property(property: &VCardProperty) -> bool {
+    matches!(
+        property,
+        VCardProperty::Fn
+            | VCardProperty::N
+            | VCardProperty::Nickname
+            | VCardProperty::Org
+            | VCardProperty::Title
+            | VCardProperty::Note
+            | VCardProperty::Uid
+    )
+}
+
+fn is_same_uid(member: &str, uid: &str) -> bool {
+    member
+        .strip_prefix("urn:uuid:")
+        .unwrap_or(member)
+        .eq_ignore_ascii_case(uid.strip_prefix("urn:uuid:").unwrap_or(uid))
+}
+
+fn normalize_phone(phone: &str) -> Option<String> {
+    let digits = phone
+        .chars()
+        .filter(|ch| ch.is_ascii_digit())
+        .collect::<String>();
+
+    // Compare national numbers so that country prefixes do not matter
+    (digits.len() >= MIN_PHONE_DIGITS)
+        .then(|| digits[digits.len().saturating_sub(MAX_PHONE_DIGITS)..].to_string())
+}
+
+fn normalize_name(name: &str) -> Option<String> {
+    let name = name
+        .split_whitespace()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    (!name.is_empty()).then_some(name)
+}
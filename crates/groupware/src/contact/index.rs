@@ -257,6 +257,13 @@ impl ArchivedContactCard {
         })
     }
 
+    pub fn full_name(&self) -> Option<&str> {
+        self.card
+            .properties(&VCardProperty::Fn)
+            .flat_map(|e| e.values.iter().filter_map(|v| v.as_text()))
+            .find(|name| !name.trim().is_empty())
+    }
+
     pub fn index_document(
         &self,
         account_id: u32,
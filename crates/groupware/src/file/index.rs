@@ -5,7 +5,17 @@
  */
 
 use super::{ArchivedFileNode, FileNode};
+use ahash::AHashSet;
 use common::storage::index::{IndexValue, IndexableAndSerializableObject, IndexableObject};
+use nlp::language::{
+    Language,
+    detect::{LanguageDetector, MIN_LANGUAGE_SCORE},
+};
+use store::{
+    search::{FileSearchField, IndexDocument, SearchField},
+    write::SearchIndex,
+    xxhash_rust::xxh3,
+};
 use types::{acl::AclGrant, collection::SyncCollection};
 
 impl IndexableObject for FileNode {
@@ -23,6 +33,10 @@ impl IndexableObject for FileNode {
             IndexValue::Quota {
                 used: self.size() as u32,
             },
+            IndexValue::SearchIndex {
+                index: SearchIndex::File,
+                hash: self.hashes().fold(0, |acc, hash| acc ^ hash),
+            },
         ]);
 
         if let Some(file) = &self.file {
@@ -55,6 +69,10 @@ impl IndexableObject for &ArchivedFileNode {
             IndexValue::Quota {
                 used: self.size() as u32,
             },
+            IndexValue::SearchIndex {
+                index: SearchIndex::File,
+                hash: self.hashes().fold(0, |acc, hash| acc ^ hash),
+            },
         ]);
 
         if let Some(file) = self.file.as_ref() {
@@ -81,6 +99,17 @@ impl FileNode {
             + self.file.as_ref().map_or(0, |f| f.size as usize)
             + std::mem::size_of::<FileNode>()
     }
+
+    pub fn hashes(&self) -> impl Iterator<Item = u64> {
+        [
+            Some(xxh3::xxh3_64(self.name.as_bytes())),
+            self.file
+                .as_ref()
+                .map(|f| xxh3::xxh3_64(f.blob_hash.as_slice())),
+        ]
+        .into_iter()
+        .flatten()
+    }
 }
 
 impl ArchivedFileNode {
@@ -94,4 +123,56 @@ impl ArchivedFileNode {
                 .map_or(0, |f| f.size.to_native() as usize)
             + std::mem::size_of::<FileNode>()
     }
+
+    pub fn hashes(&self) -> impl Iterator<Item = u64> {
+        [
+            Some(xxh3::xxh3_64(self.name.as_bytes())),
+            self.file
+                .as_ref()
+                .map(|f| xxh3::xxh3_64(f.blob_hash.0.as_slice())),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    pub fn index_document(
+        &self,
+        account_id: u32,
+        document_id: u32,
+        contents: Option<&str>,
+        index_fields: &AHashSet<SearchField>,
+        default_language: Language,
+    ) -> IndexDocument {
+        let mut document = IndexDocument::new(SearchIndex::File)
+            .with_account_id(account_id)
+            .with_document_id(document_id);
+
+        let field = SearchField::File(FileSearchField::Name);
+        if index_fields.is_empty() || index_fields.contains(&field) {
+            document.index_text(field, self.name.as_str(), Language::None);
+            if let Some(display_name) = self.display_name.as_ref() {
+                document.index_text(
+                    SearchField::File(FileSearchField::Name),
+                    display_name.as_str(),
+                    Language::None,
+                );
+            }
+        }
+
+        let field = SearchField::File(FileSearchField::Content);
+        if let Some(contents) = contents.filter(|c| !c.trim().is_empty())
+            && (index_fields.is_empty() || index_fields.contains(&field))
+        {
+            let mut detector = LanguageDetector::new();
+            detector.detect(contents, MIN_LANGUAGE_SCORE);
+            document.index_text(field, contents, Language::Unknown);
+            document.set_unknown_language(
+                detector
+                    .most_frequent_language()
+                    .unwrap_or(default_language),
+            );
+        }
+
+        document
+    }
 }
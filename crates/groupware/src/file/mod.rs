@@ -5,10 +5,19 @@
  */
 
 pub mod index;
+pub mod share;
 pub mod storage;
+pub mod version;
 
 use types::{acl::AclGrant, blob_hash::BlobHash, dead_property::DeadProperty};
 
+pub const FORBIDDEN_NAME_CHARS: &str = "/<>:\"\\|?*";
+pub const FORBIDDEN_NODE_NAMES: &[&str] = &[
+    ".", "..", "CON", "PRN", "AUX", "NUL", "COM0", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6",
+    "COM7", "COM8", "COM9", "LPT0", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8",
+    "LPT9",
+];
+
 #[derive(
     rkyv::Archive, rkyv::Deserialize, rkyv::Serialize, Debug, Default, Clone, PartialEq, Eq,
 )]
@@ -34,3 +43,19 @@ pub struct FileProperties {
     pub media_type: Option<String>,
     pub executable: bool,
 }
+
+// Validates a decoded file node name, returns the reason it was rejected.
+pub fn validate_file_name(name: &str) -> Result<(), &'static str> {
+    if !(1..=255).contains(&name.len()) {
+        Err("Name must be between 1 and 255 octets.")
+    } else if name.contains(|c: char| c.is_control() || FORBIDDEN_NAME_CHARS.contains(c)) {
+        Err("Name contains a forbidden character.")
+    } else if FORBIDDEN_NODE_NAMES
+        .iter()
+        .any(|n| n.eq_ignore_ascii_case(name))
+    {
+        Err("Name is reserved and cannot be used.")
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,224 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use common::Server;
+use std::future::Future;
+use store::{
+    Serialize, ValueKey,
+    rand::{RngExt, distr::Alphanumeric, rng},
+    write::{AlignedBytes, Archive, Archiver, BatchBuilder, ValueClass, now},
+};
+use trc::AddContext;
+use types::{collection::Collection, field::FileNodeField, id::Id};
+use utils::constant_time_eq;
+
+const TOKEN_LEN: usize = 32;
+const MAX_COMMIT_ATTEMPTS: usize = 3;
+
+#[derive(
+    rkyv::Archive, rkyv::Deserialize, rkyv::Serialize, Debug, Default, Clone, PartialEq, Eq,
+)]
+#[rkyv(derive(Debug))]
+pub struct FileShareLinks {
+    pub links: Vec<FileShareLink>,
+}
+
+#[derive(
+    rkyv::Archive, rkyv::Deserialize, rkyv::Serialize, Debug, Default, Clone, PartialEq, Eq,
+)]
+#[rkyv(derive(Debug))]
+pub struct FileShareLink {
+    pub token: String,
+    pub password_hash: Option<String>,
+    pub expires: Option<i64>,
+    pub max_downloads: Option<u32>,
+    pub downloads: u32,
+    pub file_drop: bool,
+    pub created: i64,
+}
+
+pub trait FileShare: Sync + Send {
+    fn file_share_links(
+        &self,
+        account_id: u32,
+        document_id: u32,
+    ) -> impl Future<Output = trc::Result<Option<Archive<AlignedBytes>>>> + Send;
+
+    fn file_share_link(
+        &self,
+        id: &str,
+        token: &str,
+    ) -> impl Future<Output = trc::Result<Option<(u32, u32, FileShareLink)>>> + Send;
+
+    fn record_file_share_download(
+        &self,
+        account_id: u32,
+        document_id: u32,
+        token: &str,
+    ) -> impl Future<Output = trc::Result<bool>> + Send;
+}
+
+impl FileShare for Server {
+    async fn file_share_links(
+        &self,
+        account_id: u32,
+        document_id: u32,
+    ) -> trc::Result<Option<Archive<AlignedBytes>>> {
+        self.store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                account_id,
+                Collection::FileNode,
+                document_id,
+                FileNodeField::ShareLinks,
+            ))
+            .await
+            .caused_by(trc::location!())
+    }
+
+    // Returns the account, file node and link matching an anonymous
+    // request, provided the link has not expired or run out of downloads.
+    async fn file_share_link(
+        &self,
+        id: &str,
+        token: &str,
+    ) -> trc::Result<Option<(u32, u32, FileShareLink)>> {
+        let Ok(id) = id.parse::<Id>() else {
+            return Ok(None);
+        };
+        let account_id = id.prefix_id();
+        let document_id = id.document_id();
+        let Some(links_) = self.file_share_links(account_id, document_id).await? else {
+            return Ok(None);
+        };
+        let now = now() as i64;
+
+        Ok(links_
+            .deserialize::<FileShareLinks>()
+            .caused_by(trc::location!())?
+            .links
+            .into_iter()
+            .find(|link| link.matches_token(token))
+            .filter(|link| link.is_active(now))
+            .map(|link| (account_id, document_id, link)))
+    }
+
+    // Increments the download counter of a link, returns false if the
+    // link no longer exists or its download limit has been reached.
+    async fn record_file_share_download(
+        &self,
+        account_id: u32,
+        document_id: u32,
+        token: &str,
+    ) -> trc::Result<bool> {
+        let mut attempt = 0;
+
+        loop {
+            let Some(links_) = self.file_share_links(account_id, document_id).await? else {
+                return Ok(false);
+            };
+            let links = links_
+                .to_unarchived::<FileShareLinks>()
+                .caused_by(trc::location!())?;
+            let mut new_links = links
+                .deserialize::<FileShareLinks>()
+                .caused_by(trc::location!())?;
+            let now = now() as i64;
+            let Some(link) = new_links
+                .links
+                .iter_mut()
+                .find(|link| link.matches_token(token))
+                .filter(|link| link.is_active(now))
+            else {
+                return Ok(false);
+            };
+            link.downloads += 1;
+
+            let mut batch = BatchBuilder::new();
+            batch
+                .with_account_id(account_id)
+                .with_collection(Collection::FileNode)
+                .with_document(document_id);
+            new_links.write(Some(links), &mut batch)?;
+            match self.commit_batch(batch).await {
+                Ok(_) => return Ok(true),
+                Err(err)
+                    if err.matches(trc::EventType::Store(trc::StoreEvent::AssertValueFailed))
+                        && attempt < MAX_COMMIT_ATTEMPTS =>
+                {
+                    attempt += 1;
+                }
+                Err(err) => return Err(err.caused_by(trc::location!())),
+            }
+        }
+    }
+}
+
+impl FileShareLinks {
+    // Writes the share links of a file node, the batch must point to the file node document.
+    pub fn write(
+        self,
+        current: Option<Archive<&ArchivedFileShareLinks>>,
+        batch: &mut BatchBuilder,
+    ) -> trc::Result<()> {
+        if let Some(current) = &current {
+            batch.assert_value(
+                ValueClass::Property(FileNodeField::ShareLinks.into()),
+                current,
+            );
+        }
+
+        if !self.links.is_empty() {
+            batch.set(
+                FileNodeField::ShareLinks,
+                Archiver::new(self)
+                    .serialize()
+                    .caused_by(trc::location!())?,
+            );
+        } else if current.is_some() {
+            batch.clear(FileNodeField::ShareLinks);
+        }
+
+        Ok(())
+    }
+
+    pub fn by_token(&self, token: &str) -> Option<&FileShareLink> {
+        self.links.iter().find(|link| link.token == token)
+    }
+}
+
+impl FileShareLink {
+    pub fn new() -> Self {
+        FileShareLink {
+            token: rng()
+                .sample_iter(Alphanumeric)
+                .take(TOKEN_LEN)
+                .map(char::from)
+                .collect(),
+            created: now() as i64,
+            ..Default::default()
+        }
+    }
+
+    pub fn url(&self, base_url: &str, account_id: u32, document_id: u32) -> String {
+        format!(
+            "{}/file/share/{}/{}",
+            base_url.trim_end_matches('/'),
+            Id::from_parts(account_id, document_id),
+            self.token
+        )
+    }
+
+    pub fn is_active(&self, now: i64) -> bool {
+        self.expires.is_none_or(|expires| expires > now)
+            && self
+                .max_downloads
+                .is_none_or(|max_downloads| self.downloads < max_downloads)
+    }
+
+    fn matches_token(&self, token: &str) -> bool {
+        constant_time_eq(self.token.as_bytes(), token.as_bytes())
+    }
+}
@@ -4,7 +4,7 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use super::{ArchivedFileNode, FileNode};
+use super::{ArchivedFileNode, FileNode, version::FileVersioning};
 use crate::DestroyArchive;
 use common::{Server, auth::AccountTenantIds, storage::index::ObjectIndexBuilder};
 use store::{
@@ -12,7 +12,10 @@ use store::{
     write::{AlignedBytes, Archive, BatchBuilder, now},
 };
 use trc::AddContext;
-use types::collection::{Collection, VanishedCollection};
+use types::{
+    collection::{Collection, VanishedCollection},
+    field::FileNodeField,
+};
 
 impl FileNode {
     pub fn insert(
@@ -74,8 +77,9 @@ impl FileNode {
 }
 
 impl DestroyArchive<Archive<&ArchivedFileNode>> {
-    pub fn delete(
+    pub async fn delete(
         self,
+        server: &Server,
         changed_by: AccountTenantIds,
         account_id: u32,
         document_id: u32,
@@ -92,8 +96,12 @@ impl DestroyArchive<Archive<&ArchivedFileNode>> {
                     .with_current(self.0)
                     .with_changed_by(changed_by),
             )?
-            .log_vanished_item(VanishedCollection::FileNode, path)
-            .commit_point();
+            .clear(FileNodeField::ShareLinks)
+            .log_vanished_item(VanishedCollection::FileNode, path);
+        server
+            .delete_file_versions(account_id, document_id, changed_by, batch)
+            .await?;
+        batch.commit_point();
         Ok(())
     }
 }
@@ -155,7 +163,11 @@ impl DestroyArchive<Vec<u32>> {
                             ),
                     )
                     .caused_by(trc::location!())?
-                    .commit_point();
+                    .clear(FileNodeField::ShareLinks);
+                server
+                    .delete_file_versions(account_id, document_id, changed_by, batch)
+                    .await?;
+                batch.commit_point();
             }
         }
 
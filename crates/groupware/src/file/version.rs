@@ -0,0 +1,375 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{ArchivedFileNode, FileNode};
+use common::{Server, auth::AccountTenantIds, storage::usage::UsageBatch};
+use std::future::Future;
+use store::{
+    Serialize, ValueKey,
+    write::{AlignedBytes, Archive, Archiver, BatchBuilder, BlobLink, BlobOp, ValueClass, now},
+};
+use trc::AddContext;
+use types::{blob_hash::BlobHash, collection::Collection, field::FileNodeField};
+
+#[derive(
+    rkyv::Archive, rkyv::Deserialize, rkyv::Serialize, Debug, Default, Clone, PartialEq, Eq,
+)]
+#[rkyv(derive(Debug))]
+pub struct FileVersions {
+    pub next_id: u32,
+    pub versions: Vec<FileVersion>,
+}
+
+#[derive(
+    rkyv::Archive, rkyv::Deserialize, rkyv::Serialize, Debug, Default, Clone, PartialEq, Eq,
+)]
+#[rkyv(derive(Debug))]
+pub struct FileVersion {
+    pub id: u32,
+    pub blob_hash: BlobHash,
+    pub size: u32,
+    pub media_type: Option<String>,
+    pub modified: i64,
+    pub replaced: i64,
+}
+
+pub trait FileVersioning: Sync + Send {
+    fn file_versions(
+        &self,
+        account_id: u32,
+        document_id: u32,
+    ) -> impl Future<Output = trc::Result<Option<Archive<AlignedBytes>>>> + Send;
+
+    fn archive_file_version(
+        &self,
+        account_id: u32,
+        document_id: u32,
+        previous: FileVersion,
+        current_hash: &BlobHash,
+        changed_by: AccountTenantIds,
+        batch: &mut BatchBuilder,
+    ) -> impl Future<Output = trc::Result<()>> + Send;
+
+    fn delete_file_versions(
+        &self,
+        account_id: u32,
+        document_id: u32,
+        changed_by: AccountTenantIds,
+        batch: &mut BatchBuilder,
+    ) -> impl Future<Output = trc::Result<()>> + Send;
+
+    fn prune_file_versions(&self, account_id: u32) -> impl Future<Output = trc::Result<()>> + Send;
+}
+
+impl FileVersioning for Server {
+    async fn file_versions(
+        &self,
+        account_id: u32,
+        document_id: u32,
+    ) -> trc::Result<Option<Archive<AlignedBytes>>> {
+        self.store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                account_id,
+                Collection::FileNode,
+                document_id,
+                FileNodeField::Versions,
+            ))
+            .await
+            .caused_by(trc::location!())
+    }
+
+    // Keeps the previous contents of a file that is being overwritten, this
+    // must be called after the file node update has been added to the batch.
+    async fn archive_file_version(
+        &self,
+        account_id: u32,
+        document_id: u32,
+        previous: FileVersion,
+        current_hash: &BlobHash,
+        changed_by: AccountTenantIds,
+        batch: &mut BatchBuilder,
+    ) -> trc::Result<()> {
+        let max_versions = self.core.groupware.max_file_versions;
+        let versions_ = self.file_versions(account_id, document_id).await?;
+        if max_versions == 0 && versions_.is_none() {
+            return Ok(());
+        }
+        let versions = versions_
+            .as_ref()
+            .map(|v| v.to_unarchived::<FileVersions>())
+            .transpose()
+            .caused_by(trc::location!())?;
+        let mut new_versions = versions
+            .as_ref()
+            .map(|v| v.deserialize::<FileVersions>())
+            .transpose()
+            .caused_by(trc::location!())?
+            .unwrap_or_default();
+
+        if max_versions > 0 && previous.blob_hash != *current_hash {
+            new_versions.push(previous);
+        }
+        new_versions
+            .versions
+            .retain(|v| v.blob_hash != *current_hash);
+        new_versions.prune(
+            max_versions,
+            self.core.groupware.file_version_retention,
+            now() as i64,
+        );
+
+        batch
+            .with_account_id(account_id)
+            .with_collection(Collection::FileNode)
+            .with_document(document_id);
+        new_versions.write(versions, Some(current_hash), changed_by, batch)
+    }
+
+    async fn delete_file_versions(
+        &self,
+        account_id: u32,
+        document_id: u32,
+        changed_by: AccountTenantIds,
+        batch: &mut BatchBuilder,
+    ) -> trc::Result<()> {
+        if let Some(versions_) = self.file_versions(account_id, document_id).await? {
+            let versions = versions_
+                .to_unarchived::<FileVersions>()
+                .caused_by(trc::location!())?;
+            batch
+                .with_account_id(account_id)
+                .with_collection(Collection::FileNode)
+                .with_document(document_id);
+            FileVersions::default().write(Some(versions), None, changed_by, batch)?;
+        }
+
+        Ok(())
+    }
+
+    async fn prune_file_versions(&self, account_id: u32) -> trc::Result<()> {
+        let max_versions = self.core.groupware.max_file_versions;
+        let retention = self.core.groupware.file_version_retention;
+        let now = now() as i64;
+
+        // Find files with versions exceeding the configured limits
+        let mut document_ids = Vec::new();
+        self.all_archives(
+            account_id,
+            Collection::FileNode,
+            FileNodeField::Versions.into(),
+            |document_id, archive| {
+                let mut versions = archive.deserialize::<FileVersions>()?;
+                if versions.prune(max_versions, retention, now) {
+                    document_ids.push(document_id);
+                }
+                Ok(())
+            },
+        )
+        .await
+        .caused_by(trc::location!())?;
+
+        if document_ids.is_empty() {
+            return Ok(());
+        }
+
+        trc::event!(
+            Store(trc::StoreEvent::AutoExpunge),
+            AccountId = account_id,
+            Collection = Collection::FileNode.as_str(),
+            Total = document_ids.len(),
+        );
+
+        let changed_by = self
+            .account(account_id)
+            .await
+            .caused_by(trc::location!())?
+            .account_tenant_ids();
+        let mut batch = BatchBuilder::new();
+        for document_id in document_ids {
+            let (Some(versions_), Some(node_)) = (
+                self.file_versions(account_id, document_id).await?,
+                self.store()
+                    .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                        account_id,
+                        Collection::FileNode,
+                        document_id,
+                    ))
+                    .await
+                    .caused_by(trc::location!())?,
+            ) else {
+                continue;
+            };
+            let versions = versions_
+                .to_unarchived::<FileVersions>()
+                .caused_by(trc::location!())?;
+            let mut new_versions = versions
+                .deserialize::<FileVersions>()
+                .caused_by(trc::location!())?;
+            new_versions.prune(max_versions, retention, now);
+            let current_hash = node_
+                .unarchive::<FileNode>()
+                .caused_by(trc::location!())?
+                .file
+                .as_ref()
+                .map(|f| BlobHash::from(&f.blob_hash));
+
+            batch
+                .with_account_id(account_id)
+                .with_collection(Collection::FileNode)
+                .with_document(document_id);
+            new_versions.write(
+                Some(versions),
+                current_hash.as_ref(),
+                changed_by,
+                &mut batch,
+            )?;
+            batch.commit_point();
+        }
+
+        if !batch.is_empty() {
+            self.commit_batch(batch).await.caused_by(trc::location!())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FileVersions {
+    pub fn push(&mut self, version: FileVersion) {
+        self.versions.push(FileVersion {
+            id: self.next_id,
+            ..version
+        });
+        self.next_id += 1;
+    }
+
+    // Removes the oldest versions exceeding the count limit as well as
+    // the ones that were replaced before the retention period.
+    pub fn prune(&mut self, max_versions: usize, retention: Option<u64>, now: i64) -> bool {
+        let total = self.versions.len();
+        if let Some(retention) = retention {
+            let cutoff = now.saturating_sub(retention as i64);
+            self.versions.retain(|v| v.replaced > cutoff);
+        }
+        if self.versions.len() > max_versions {
+            self.versions.sort_unstable_by_key(|v| v.id);
+            self.versions.drain(..self.versions.len() - max_versions);
+        }
+        self.versions.len() != total
+    }
+
+    pub fn size(&self) -> u64 {
+        self.versions.iter().map(|v| v.size as u64).sum()
+    }
+
+    // Writes the version history of a file, linking the version blobs to the
+    // file node and accounting for their size. The batch must point to the file
+    // node document and any change to the file node itself must precede this call.
+    pub fn write(
+        self,
+        current: Option<Archive<&ArchivedFileVersions>>,
+        current_hash: Option<&BlobHash>,
+        changed_by: AccountTenantIds,
+        batch: &mut BatchBuilder,
+    ) -> trc::Result<()> {
+        let mut quota = self.size() as i64;
+
+        if let Some(current) = &current {
+            batch.assert_value(
+                ValueClass::Property(FileNodeField::Versions.into()),
+                current,
+            );
+            for version in current.inner.versions.iter() {
+                quota -= version.size.to_native() as i64;
+                if !self.versions.iter().any(|v| v.id == version.id.to_native()) {
+                    batch.item_usage(Collection::FileNode, version.size.to_native(), false);
+                }
+                let hash = BlobHash::from(&version.blob_hash);
+
+                // Versions might share their blob with the file or other versions
+                if current_hash != Some(&hash) && !self.versions.iter().any(|v| v.blob_hash == hash)
+                {
+                    batch.clear(BlobOp::Link {
+                        hash,
+                        to: BlobLink::Document,
+                    });
+                }
+            }
+        } else {
+            batch.assert_value(ValueClass::Property(FileNodeField::Versions.into()), ());
+        }
+
+        // Links are set again as the file node update may have removed them
+        for version in &self.versions {
+            if !current.as_ref().is_some_and(|current| {
+                current
+                    .inner
+                    .versions
+                    .iter()
+                    .any(|v| v.id.to_native() == version.id)
+            }) {
+                batch.item_usage(Collection::FileNode, version.size, true);
+            }
+            batch.set(
+                BlobOp::Link {
+                    hash: version.blob_hash.clone(),
+                    to: BlobLink::Document,
+                },
+                vec![],
+            );
+        }
+
+        if quota != 0 {
+            batch.add(ValueClass::Quota, quota);
+            if let Some(tenant_id) = changed_by.tenant_id {
+                batch.add(ValueClass::TenantQuota(tenant_id), quota);
+            }
+        }
+
+        if !self.versions.is_empty() {
+            batch.set(
+                FileNodeField::Versions,
+                Archiver::new(self)
+                    .serialize()
+                    .caused_by(trc::location!())?,
+            );
+        } else if current.is_some() {
+            batch.clear(FileNodeField::Versions);
+        }
+
+        Ok(())
+    }
+
+    pub fn by_id(&self, id: u32) -> Option<&FileVersion> {
+        self.versions.iter().find(|v| v.id == id)
+    }
+}
+
+impl ArchivedFileVersions {
+    pub fn by_id(&self, id: u32) -> Option<&ArchivedFileVersion> {
+        self.versions.iter().find(|v| v.id == id)
+    }
+
+    pub fn size(&self) -> u64 {
+        self.versions
+            .iter()
+            .map(|v| v.size.to_native() as u64)
+            .sum()
+    }
+}
+
+impl FileVersion {
+    pub fn from_node(node: &ArchivedFileNode) -> Option<Self> {
+        node.file.as_ref().map(|file| FileVersion {
+            id: 0,
+            blob_hash: BlobHash::from(&file.blob_hash),
+            size: file.size.to_native(),
+            media_type: file.media_type.as_ref().map(|t| t.to_string()),
+            modified: node.modified.to_native(),
+            replaced: now() as i64,
+        })
+    }
+}
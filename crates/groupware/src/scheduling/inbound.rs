@@ -10,8 +10,8 @@ use crate::scheduling::{
 use ahash::AHashSet;
 use calcard::icalendar::{
     ICalendar, ICalendarComponent, ICalendarComponentType, ICalendarEntry, ICalendarMethod,
-    ICalendarParameter, ICalendarParameterName, ICalendarProperty, ICalendarStatus, ICalendarValue,
-    Uri,
+    ICalendarParameter, ICalendarParameterName, ICalendarParticipationStatus, ICalendarProperty,
+    ICalendarStatus, ICalendarValue, Uri,
 };
 
 #[derive(Debug)]
@@ -42,6 +42,13 @@ pub enum MergeAction {
     },
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceBooking {
+    Accept,
+    Decline,
+    Delegate(Vec<String>),
+}
+
 pub enum MergeResult {
     Actions(Vec<MergeAction>),
     Message(ItipMessage<ICalendar>),
@@ -599,6 +606,95 @@ pub fn itip_merge_changes(ical: &mut ICalendar, changes: Vec<MergeAction>) {
     }
 }
 
+// Sets the participation status of a resource attendee according to the outcome
+// of its booking policy. Delegates are added as new attendees so that the regular
+// attendee update flow sends them an invitation.
+pub fn itip_resource_booking(
+    ical: &mut ICalendar,
+    account_emails: &[String],
+    booking: &ResourceBooking,
+) -> bool {
+    let (part_stat, delegates) = match booking {
+        ResourceBooking::Accept => (ICalendarParticipationStatus::Accepted, &[][..]),
+        ResourceBooking::Decline => (ICalendarParticipationStatus::Declined, &[][..]),
+        ResourceBooking::Delegate(delegates) => (
+            ICalendarParticipationStatus::Delegated,
+            delegates.as_slice(),
+        ),
+    };
+    let mut did_change = false;
+
+    for component in &mut ical.components {
+        if !component.component_type.is_scheduling_object() {
+            continue;
+        }
+
+        let attendees = component
+            .entries
+            .iter()
+            .filter(|entry| entry.name == ICalendarProperty::Attendee)
+            .filter_map(|entry| entry.calendar_address())
+            .map(|address| address.to_lowercase())
+            .collect::<AHashSet<_>>();
+        let mut delegate_entries = Vec::new();
+
+        for entry in &mut component.entries {
+            if entry.name != ICalendarProperty::Attendee {
+                continue;
+            }
+            let Some(resource) = entry
+                .calendar_address()
+                .filter(|address| {
+                    account_emails
+                        .iter()
+                        .any(|email| email.eq_ignore_ascii_case(address))
+                })
+                .map(|address| address.to_string())
+            else {
+                continue;
+            };
+
+            entry.params.retain(|param| {
+                !matches!(
+                    param.name,
+                    ICalendarParameterName::Partstat | ICalendarParameterName::DelegatedTo
+                )
+            });
+            entry
+                .params
+                .push(ICalendarParameter::partstat(part_stat.clone()));
+
+            for delegate in delegates {
+                entry
+                    .params
+                    .push(ICalendarParameter::delegated_to(Uri::Location(format!(
+                        "mailto:{delegate}"
+                    ))));
+
+                if !attendees.contains(&delegate.to_lowercase()) {
+                    delegate_entries.push(ICalendarEntry {
+                        name: ICalendarProperty::Attendee,
+                        params: vec![
+                            ICalendarParameter::delegated_from(Uri::Location(format!(
+                                "mailto:{resource}"
+                            ))),
+                            ICalendarParameter::partstat(ICalendarParticipationStatus::NeedsAction),
+                            ICalendarParameter::rsvp(true),
+                        ],
+                        values: vec![ICalendarValue::Text(format!("mailto:{delegate}"))],
+                    });
+                }
+            }
+
+            did_change = true;
+        }
+
+        component.entries.extend(delegate_entries);
+    }
+
+    did_change
+}
+
 pub fn itip_method(ical: &ICalendar) -> Result<&ICalendarMethod, ItipError> {
     ical.components
         .first()
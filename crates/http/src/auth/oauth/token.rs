@@ -29,6 +29,7 @@ use store::{
     write::{AlignedBytes, Archive},
 };
 use trc::AddContext;
+use utils::constant_time_eq;
 
 pub trait TokenHandler: Sync + Send {
     fn handle_token_request(
@@ -409,17 +410,6 @@ fn verify_pkce(stored: &ArchivedPkceCodeChallenge, verifier: Option<&str>) -> bo
                 .bytes()
                 .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~'))
     };
-    let constant_time_eq = |a: &[u8], b: &[u8]| {
-        if a.len() != b.len() {
-            return false;
-        }
-        let mut diff: u8 = 0;
-        for (x, y) in a.iter().zip(b.iter()) {
-            diff |= x ^ y;
-        }
-        diff == 0
-    };
-
     match (stored, verifier) {
         (ArchivedPkceCodeChallenge::None, None) => true,
         (ArchivedPkceCodeChallenge::Plain(expected), Some(verifier))
@@ -15,6 +15,7 @@ use crate::{
         },
     },
     form::FormHandler,
+    share::FileShareHandler,
 };
 use common::{
     BuildServer, Inner, KV_ACME, Server,
@@ -23,7 +24,10 @@ use common::{
     network::{SessionData, SessionManager, SessionStream},
 };
 use dav::{DavMethod, request::DavRequestHandler};
-use groupware::{DavResourceName, calendar::itip::ItipIngest};
+use groupware::{
+    DavResourceName,
+    calendar::{itip::ItipIngest, publish::CalendarPublish},
+};
 use http_proto::{
     DownloadResponse, HtmlResponse, HttpContext, HttpRequest, HttpResponse, HttpResponseBody,
     HttpSessionData, JsonProblemResponse, ToHttpResponse, form_urlencoded, request::fetch_body,
@@ -237,7 +241,14 @@ impl ParseHttp for Server {
             }
             "dav" => {
                 let response = match (
-                    path.next().and_then(DavResourceName::parse),
+                    path.next().and_then(|name| {
+                        // Chunked uploads are handled by the file store
+                        if name == "uploads" {
+                            Some(DavResourceName::File)
+                        } else {
+                            DavResourceName::parse(name)
+                        }
+                    }),
                     DavMethod::parse(req.method()),
                 ) {
                     (Some(_), Some(DavMethod::OPTIONS)) => HttpResponse::new(StatusCode::OK)
@@ -245,16 +256,18 @@ impl ParseHttp for Server {
                             "DAV",
                             concat!(
                                 "1, 2, 3, access-control, extended-mkcol, calendar-access, ",
-                                "calendar-auto-schedule, calendar-no-timezone, addressbook"
+                                "calendar-auto-schedule, calendar-no-timezone, ",
+                                "calendar-managed-attachments, addressbook"
                             ),
                         )
                         .with_header(
                             "Allow",
                             concat!(
                                 "OPTIONS, GET, HEAD, POST, PUT, DELETE, COPY, MOVE, MKCALENDAR, ",
-                                "MKCOL, PROPFIND, PROPPATCH, LOCK, UNLOCK, REPORT, ACL"
+                                "MKCOL, PROPFIND, PROPPATCH, LOCK, UNLOCK, REPORT, ACL, SEARCH"
                             ),
-                        ),
+                        )
+                        .with_header("DASL", "<DAV:basicsearch>"),
                     (Some(resource), Some(method)) => {
                         // Authenticate request
                         let (_in_flight, access_token) =
@@ -453,28 +466,46 @@ impl ParseHttp for Server {
                 self.is_http_anonymous_request_allowed(session.remote_ip)
                     .await?;
 
-                if self.core.groupware.itip_http_rsvp_url.is_some()
-                    && req.method() == Method::GET
-                    && path.next().unwrap_or_default() == "rsvp"
-                {
-                    return self
-                        .http_rsvp_handle(
-                            req.uri().query().unwrap_or_default(),
-                            req.headers()
-                                .get(header::ACCEPT_LANGUAGE)
-                                .and_then(|v| v.to_str().ok())
-                                .map(|lang| {
-                                    let lang = lang.split_once(',').map_or(lang, |(l, _)| l);
-                                    lang.split_once(';').map_or(lang, |(l, _)| l)
-                                })
-                                .unwrap_or("en"),
-                        )
-                        .await
-                        .map(|response| {
-                            HtmlResponse::new(response)
-                                .into_http_response()
-                                .with_no_store()
-                        });
+                match (path.next().unwrap_or_default(), req.method()) {
+                    ("rsvp", &Method::GET) if self.core.groupware.itip_http_rsvp_url.is_some() => {
+                        return self
+                            .http_rsvp_handle(
+                                req.uri().query().unwrap_or_default(),
+                                req.headers()
+                                    .get(header::ACCEPT_LANGUAGE)
+                                    .and_then(|v| v.to_str().ok())
+                                    .map(|lang| {
+                                        let lang = lang.split_once(',').map_or(lang, |(l, _)| l);
+                                        lang.split_once(';').map_or(lang, |(l, _)| l)
+                                    })
+                                    .unwrap_or("en"),
+                            )
+                            .await
+                            .map(|response| {
+                                HtmlResponse::new(response)
+                                    .into_http_response()
+                                    .with_no_store()
+                            });
+                    }
+                    ("publish", &Method::GET) => {
+                        if let (Some(id), Some(secret), None) = (
+                            path.next(),
+                            path.next().and_then(|name| name.strip_suffix(".ics")),
+                            path.next(),
+                        ) {
+                            return self.http_publish_handle(id, secret).await.map(|ical| {
+                                if let Some(ical) = ical {
+                                    HttpResponse::new(StatusCode::OK)
+                                        .with_content_type("text/calendar; charset=utf-8")
+                                        .with_text_body(ical)
+                                        .with_no_cache()
+                                } else {
+                                    HttpResponse::new(StatusCode::NOT_FOUND)
+                                }
+                            });
+                        }
+                    }
+                    _ => {}
                 }
             }
             "autodiscover" | "Autodiscover" | "AutoDiscover" => {
@@ -606,6 +637,27 @@ impl ParseHttp for Server {
                 }
             }
             // SPDX-SnippetEnd
+            "file" => {
+                if path.next() == Some("share")
+                    && let (Some(id), Some(token)) = (path.next(), path.next())
+                {
+                    // Limit anonymous requests
+                    self.is_http_anonymous_request_allowed(session.remote_ip)
+                        .await?;
+
+                    let (id, token) = (id.to_string(), token.to_string());
+                    let path = path.collect::<Vec<_>>().join("/");
+                    return self
+                        .handle_file_share_request(
+                            &mut req,
+                            &session,
+                            &id,
+                            &token,
+                            path.trim_end_matches('/'),
+                        )
+                        .await;
+                }
+            }
             "form" => {
                 if let Some(form) = &self.core.network.contact_form {
                     match *req.method() {
@@ -0,0 +1,323 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::auth::authenticate::HttpHeaders;
+use common::{Server, storage::index::ObjectIndexBuilder};
+use directory::core::secret::verify_secret_hash;
+use groupware::{
+    cache::GroupwareCache,
+    file::{
+        FileNode, FileProperties,
+        share::{FileShare, FileShareLink},
+        validate_file_name,
+    },
+};
+use http_proto::{
+    HttpRequest, HttpResponse, HttpSessionData, JsonResponse, ToHttpResponse, request::fetch_body,
+};
+use hyper::{Method, StatusCode};
+use mail_parser::decoders::base64::base64_decode;
+use percent_encoding::percent_decode_str;
+use serde_json::json;
+use std::future::Future;
+use store::{
+    ValueKey,
+    write::{AlignedBytes, Archive, BatchBuilder, now},
+};
+use trc::AddContext;
+use types::collection::{Collection, SyncCollection};
+
+const MAX_RENAME_ATTEMPTS: u32 = 100;
+
+pub trait FileShareHandler: Sync + Send {
+    fn handle_file_share_request(
+        &self,
+        req: &mut HttpRequest,
+        session: &HttpSessionData,
+        id: &str,
+        token: &str,
+        path: &str,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+impl FileShareHandler for Server {
+    async fn handle_file_share_request(
+        &self,
+        req: &mut HttpRequest,
+        session: &HttpSessionData,
+        id: &str,
+        token: &str,
+        path: &str,
+    ) -> trc::Result<HttpResponse> {
+        let Some((account_id, document_id, link)) = self.file_share_link(id, token).await? else {
+            return Ok(HttpResponse::new(StatusCode::NOT_FOUND));
+        };
+
+        // Validate password
+        if let Some(password_hash) = &link.password_hash {
+            let password = req
+                .authorization_basic()
+                .and_then(|token| base64_decode(token.as_bytes()))
+                .and_then(|token| String::from_utf8(token).ok())
+                .and_then(|token| token.split_once(':').map(|(_, secret)| secret.to_string()));
+
+            match password {
+                Some(password)
+                    if verify_secret_hash(password_hash, password.as_bytes())
+                        .await
+                        .caused_by(trc::location!())? => {}
+                Some(_)
+                    if self.has_auth_fail2ban()
+                        && self
+                            .is_auth_fail2banned(session.remote_ip, Some(token))
+                            .await? =>
+                {
+                    return Err(trc::SecurityEvent::AuthenticationBan
+                        .into_err()
+                        .ctx(trc::Key::RemoteIp, session.remote_ip));
+                }
+                _ => {
+                    return Ok(HttpResponse::new(StatusCode::UNAUTHORIZED)
+                        .with_header("WWW-Authenticate", "Basic realm=\"Shared link\"")
+                        .with_no_store());
+                }
+            }
+        }
+
+        // Fetch shared node
+        let resources = self
+            .fetch_dav_resources(account_id, account_id, SyncCollection::FileNode)
+            .await
+            .caused_by(trc::location!())?;
+        let Some(root) = resources.any_resource_path_by_id(document_id) else {
+            return Ok(HttpResponse::new(StatusCode::NOT_FOUND));
+        };
+        let method = req.method().clone();
+        let is_head = method == Method::HEAD;
+
+        match (&method, root.is_container(), link.file_drop) {
+            (&Method::GET | &Method::HEAD, false, _) if path.is_empty() => {
+                self.download_shared_file(account_id, document_id, document_id, &link, is_head)
+                    .await
+            }
+            (&Method::GET | &Method::HEAD, true, false) if path.is_empty() => {
+                // List folder contents
+                let prefix = format!("{}/", root.path());
+                let listing = resources
+                    .subtree(root.path())
+                    .filter_map(|item| {
+                        let name = item.path().strip_prefix(&prefix)?;
+                        Some(if item.is_container() {
+                            json!({"name": name, "type": "directory"})
+                        } else {
+                            json!({"name": name, "type": "file", "size": item.size()})
+                        })
+                    })
+                    .collect::<Vec<_>>();
+
+                Ok(JsonResponse::new(listing)
+                    .into_http_response()
+                    .with_no_store())
+            }
+            (&Method::GET | &Method::HEAD, true, false) => {
+                match resources.by_path(&format!("{}/{}", root.path(), path)) {
+                    Some(item) if !item.is_container() => {
+                        self.download_shared_file(
+                            account_id,
+                            document_id,
+                            item.document_id(),
+                            &link,
+                            is_head,
+                        )
+                        .await
+                    }
+                    _ => Ok(HttpResponse::new(StatusCode::NOT_FOUND)),
+                }
+            }
+            (&Method::PUT | &Method::POST, true, true)
+                if !path.is_empty() && !path.contains('/') =>
+            {
+                // Names are stored as URI path segments, as in WebDAV PUT
+                if percent_decode_str(path)
+                    .decode_utf8()
+                    .ok()
+                    .is_none_or(|name| validate_file_name(&name).is_err())
+                {
+                    return Ok(HttpResponse::new(StatusCode::BAD_REQUEST));
+                }
+
+                // Drop a file in the shared folder
+                let Some(bytes) =
+                    fetch_body(req, self.core.groupware.max_file_size, session.session_id).await
+                else {
+                    return Ok(HttpResponse::new(StatusCode::PAYLOAD_TOO_LARGE));
+                };
+                let account = self.account(account_id).await?;
+                if !bytes.is_empty() {
+                    self.has_available_quota(account.as_ref(), bytes.len() as u64)
+                        .await?;
+                }
+
+                // Avoid overwriting existing files
+                let mut name = path.to_string();
+                let (stem, extension) = match path.rsplit_once('.') {
+                    Some((stem, extension)) if !stem.is_empty() => (stem, Some(extension)),
+                    _ => (path, None),
+                };
+                let mut attempt = 1;
+                while resources
+                    .by_path(&format!("{}/{}", root.path(), name))
+                    .is_some()
+                {
+                    if attempt > MAX_RENAME_ATTEMPTS {
+                        return Ok(HttpResponse::new(StatusCode::CONFLICT));
+                    }
+                    name = if let Some(extension) = extension {
+                        format!("{stem}-{attempt}.{extension}")
+                    } else {
+                        format!("{stem}-{attempt}")
+                    };
+                    attempt += 1;
+                }
+
+                // Write blob
+                let (blob_hash, blob_hold) = self
+                    .put_temporary_blob(account_id, &bytes, 60)
+                    .await
+                    .caused_by(trc::location!())?;
+
+                // Build node
+                let now = now();
+                let node = FileNode {
+                    parent_id: document_id + 1,
+                    name,
+                    display_name: None,
+                    file: Some(FileProperties {
+                        blob_hash,
+                        size: bytes.len() as u32,
+                        media_type: req
+                            .headers()
+                            .get(hyper::header::CONTENT_TYPE)
+                            .and_then(|v| v.to_str().ok())
+                            .filter(|ct| !ct.is_empty() && *ct != "application/octet-stream")
+                            .map(|v| v.to_string()),
+                        executable: false,
+                    }),
+                    created: now as i64,
+                    modified: now as i64,
+                    dead_properties: Default::default(),
+                    acls: root
+                        .resource
+                        .acls()
+                        .map(|acls| acls.to_vec())
+                        .unwrap_or_default(),
+                };
+
+                // Prepare write batch
+                let mut batch = BatchBuilder::new();
+                let document_id = self
+                    .store()
+                    .assign_document_ids(account_id, Collection::FileNode, 1)
+                    .await
+                    .caused_by(trc::location!())?;
+                batch
+                    .with_account_id(account_id)
+                    .with_collection(Collection::FileNode)
+                    .with_document(document_id)
+                    .clear(blob_hold)
+                    .custom(
+                        ObjectIndexBuilder::<(), _>::new()
+                            .with_changes(node)
+                            .with_changed_by(account.account_tenant_ids()),
+                    )
+                    .caused_by(trc::location!())?;
+                self.commit_batch(batch).await.caused_by(trc::location!())?;
+
+                Ok(HttpResponse::new(StatusCode::CREATED))
+            }
+            (&Method::OPTIONS, _, _) => Ok(HttpResponse::new(StatusCode::NO_CONTENT)),
+            (&Method::GET | &Method::HEAD, true, true) => {
+                Ok(HttpResponse::new(StatusCode::FORBIDDEN))
+            }
+            _ => Ok(HttpResponse::new(StatusCode::METHOD_NOT_ALLOWED)),
+        }
+    }
+}
+
+trait SharedFileDownload: Sync + Send {
+    fn download_shared_file(
+        &self,
+        account_id: u32,
+        link_document_id: u32,
+        document_id: u32,
+        link: &FileShareLink,
+        is_head: bool,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+impl SharedFileDownload for Server {
+    async fn download_shared_file(
+        &self,
+        account_id: u32,
+        link_document_id: u32,
+        document_id: u32,
+        link: &FileShareLink,
+        is_head: bool,
+    ) -> trc::Result<HttpResponse> {
+        let Some(node_) = self
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                account_id,
+                Collection::FileNode,
+                document_id,
+            ))
+            .await
+            .caused_by(trc::location!())?
+        else {
+            return Ok(HttpResponse::new(StatusCode::NOT_FOUND));
+        };
+        let node = node_.unarchive::<FileNode>().caused_by(trc::location!())?;
+        let Some(file) = node.file.as_ref() else {
+            return Ok(HttpResponse::new(StatusCode::NOT_FOUND));
+        };
+        let name = percent_decode_str(node.name.as_str()).decode_utf8_lossy();
+        let response = HttpResponse::new(StatusCode::OK)
+            .with_content_type(
+                file.media_type
+                    .as_ref()
+                    .map(|v| v.as_str())
+                    .unwrap_or("application/octet-stream"),
+            )
+            .with_content_disposition(format!(
+                "attachment; filename=\"{}\"",
+                name.replace('"', "\\\"")
+            ))
+            .with_no_store();
+
+        if is_head {
+            return Ok(response.with_content_length(u32::from(file.size) as usize));
+        }
+
+        // Downloads are counted against the link that was used
+        if !self
+            .record_file_share_download(account_id, link_document_id, &link.token)
+            .await?
+        {
+            return Ok(HttpResponse::new(StatusCode::NOT_FOUND));
+        }
+
+        let contents = self
+            .blob_store()
+            .get_blob(file.blob_hash.0.as_slice(), 0..usize::MAX)
+            .await
+            .caused_by(trc::location!())?;
+
+        Ok(match contents {
+            Some(contents) => response.with_binary_body(contents),
+            None => HttpResponse::new(StatusCode::NOT_FOUND),
+        })
+    }
+}
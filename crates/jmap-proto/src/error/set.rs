@@ -113,6 +113,8 @@ pub enum SetErrorType {
     CalendarHasEvent,
     #[serde(rename = "noSupportedScheduleMethods")]
     NoSupportedScheduleMethods,
+    #[serde(rename = "unsupportedType")]
+    UnsupportedType,
     // Stalwart registry errors
     #[serde(rename = "objectIsLinked")]
     ObjectIsLinked,
@@ -156,6 +158,7 @@ impl SetErrorType {
             SetErrorType::NodeHasChildren => "nodeHasChildren",
             SetErrorType::CalendarHasEvent => "calendarHasEvent",
             SetErrorType::NoSupportedScheduleMethods => "noSupportedScheduleMethods",
+            SetErrorType::UnsupportedType => "unsupportedType",
             SetErrorType::ObjectIsLinked => "objectIsLinked",
             SetErrorType::InvalidForeignKey => "invalidForeignKey",
             SetErrorType::PrimaryKeyViolation => "primaryKeyViolation",
@@ -0,0 +1,200 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::request::{
+    deserialize::{DeserializeArguments, deserialize_request},
+    reference::MaybeIdReference,
+};
+use serde::{Deserialize, Deserializer};
+use types::{blob::BlobId, id::Id};
+use utils::map::vec_map::VecMap;
+
+#[derive(Debug, Clone, Default)]
+pub struct BlobConvertRequest {
+    pub account_id: Id,
+    pub create: VecMap<String, ConvertObject>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ConvertObject {
+    pub image_convert: Option<ImageConvert>,
+    pub text_extract: Option<TextExtract>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImageConvert {
+    pub blob_id: MaybeIdReference<BlobId>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub type_: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TextExtract {
+    pub blob_id: MaybeIdReference<BlobId>,
+    pub type_: Option<String>,
+    pub max_bytes: Option<usize>,
+}
+
+impl Default for ImageConvert {
+    fn default() -> Self {
+        ImageConvert {
+            blob_id: MaybeIdReference::Invalid(String::new()),
+            width: None,
+            height: None,
+            type_: None,
+        }
+    }
+}
+
+impl Default for TextExtract {
+    fn default() -> Self {
+        TextExtract {
+            blob_id: MaybeIdReference::Invalid(String::new()),
+            type_: None,
+            max_bytes: None,
+        }
+    }
+}
+
+impl ConvertObject {
+    pub fn blob_id_mut(&mut self) -> Option<&mut MaybeIdReference<BlobId>> {
+        match (&mut self.image_convert, &mut self.text_extract) {
+            (Some(image), None) => Some(&mut image.blob_id),
+            (None, Some(text)) => Some(&mut text.blob_id),
+            _ => None,
+        }
+    }
+}
+
+impl<'de> DeserializeArguments<'de> for BlobConvertRequest {
+    fn deserialize_argument<A>(&mut self, key: &str, map: &mut A) -> Result<(), A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        hashify::fnc_map!(key.as_bytes(),
+            b"accountId" => {
+                self.account_id = crate::request::deserialize_account_id(map)?;
+            },
+            b"create" => {
+                self.create = map.next_value()?;
+            }
+            _ => {
+                let _ = map.next_value::<serde::de::IgnoredAny>()?;
+            }
+        );
+
+        Ok(())
+    }
+}
+
+impl<'de> DeserializeArguments<'de> for ConvertObject {
+    fn deserialize_argument<A>(&mut self, key: &str, map: &mut A) -> Result<(), A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        hashify::fnc_map!(key.as_bytes(),
+            b"imageConvert" => {
+                self.image_convert = map.next_value()?;
+            },
+            b"textExtract" => {
+                self.text_extract = map.next_value()?;
+            },
+            _ => {
+                let _ = map.next_value::<serde::de::IgnoredAny>()?;
+            }
+        );
+
+        Ok(())
+    }
+}
+
+impl<'de> DeserializeArguments<'de> for ImageConvert {
+    fn deserialize_argument<A>(&mut self, key: &str, map: &mut A) -> Result<(), A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        hashify::fnc_map!(key.as_bytes(),
+            b"blobId" => {
+                self.blob_id = map.next_value()?;
+            },
+            b"width" => {
+                self.width = map.next_value()?;
+            },
+            b"height" => {
+                self.height = map.next_value()?;
+            },
+            b"type" => {
+                self.type_ = map.next_value()?;
+            },
+            _ => {
+                let _ = map.next_value::<serde::de::IgnoredAny>()?;
+            }
+        );
+
+        Ok(())
+    }
+}
+
+impl<'de> DeserializeArguments<'de> for TextExtract {
+    fn deserialize_argument<A>(&mut self, key: &str, map: &mut A) -> Result<(), A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        hashify::fnc_map!(key.as_bytes(),
+            b"blobId" => {
+                self.blob_id = map.next_value()?;
+            },
+            b"type" => {
+                self.type_ = map.next_value()?;
+            },
+            b"maxBytes" => {
+                self.max_bytes = map.next_value()?;
+            },
+            _ => {
+                let _ = map.next_value::<serde::de::IgnoredAny>()?;
+            }
+        );
+
+        Ok(())
+    }
+}
+
+impl<'de> Deserialize<'de> for BlobConvertRequest {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_request(deserializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ConvertObject {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_request(deserializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ImageConvert {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_request(deserializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TextExtract {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_request(deserializer)
+    }
+}
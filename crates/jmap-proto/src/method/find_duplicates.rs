@@ -0,0 +1,76 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::request::{
+    MaybeInvalid,
+    deserialize::{DeserializeArguments, deserialize_request},
+};
+use serde::{Deserialize, Deserializer, Serialize};
+use types::id::Id;
+
+#[derive(Debug, Clone, Default)]
+pub struct FindDuplicatesRequest {
+    pub account_id: Id,
+    pub address_book_id: Option<MaybeInvalid<Id>>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FindDuplicatesResponse {
+    #[serde(rename = "accountId")]
+    pub account_id: Id,
+    pub clusters: Vec<DuplicateCluster>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DuplicateCluster {
+    pub ids: Vec<Id>,
+    #[serde(rename = "matchedOn")]
+    pub matched_on: Vec<DuplicateMatch>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+pub enum DuplicateMatch {
+    #[serde(rename = "email")]
+    Email,
+    #[serde(rename = "phone")]
+    Phone,
+    #[serde(rename = "name")]
+    Name,
+}
+
+impl<'de> DeserializeArguments<'de> for FindDuplicatesRequest {
+    fn deserialize_argument<A>(&mut self, key: &str, map: &mut A) -> Result<(), A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        hashify::fnc_map!(key.as_bytes(),
+            b"accountId" => {
+                self.account_id = crate::request::deserialize_account_id(map)?;
+            },
+            b"addressBookId" => {
+                self.address_book_id = map.next_value()?;
+            },
+            b"limit" => {
+                self.limit = map.next_value()?;
+            },
+            _ => {
+                let _ = map.next_value::<serde::de::IgnoredAny>()?;
+            }
+        );
+
+        Ok(())
+    }
+}
+
+impl<'de> Deserialize<'de> for FindDuplicatesRequest {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_request(deserializer)
+    }
+}
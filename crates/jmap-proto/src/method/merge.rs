@@ -0,0 +1,71 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    request::{
+        MaybeInvalid,
+        deserialize::{DeserializeArguments, deserialize_request},
+    },
+    types::state::State,
+};
+use serde::{Deserialize, Deserializer, Serialize};
+use types::id::Id;
+
+#[derive(Debug, Clone, Default)]
+pub struct MergeContactCardsRequest {
+    pub account_id: Id,
+    pub if_in_state: Option<State>,
+    pub target_id: MaybeInvalid<Id>,
+    pub source_ids: Vec<MaybeInvalid<Id>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MergeContactCardsResponse {
+    #[serde(rename = "accountId")]
+    pub account_id: Id,
+    #[serde(rename = "oldState")]
+    pub old_state: State,
+    #[serde(rename = "newState")]
+    pub new_state: State,
+    pub updated: Vec<Id>,
+    pub destroyed: Vec<Id>,
+}
+
+impl<'de> DeserializeArguments<'de> for MergeContactCardsRequest {
+    fn deserialize_argument<A>(&mut self, key: &str, map: &mut A) -> Result<(), A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        hashify::fnc_map!(key.as_bytes(),
+            b"accountId" => {
+                self.account_id = crate::request::deserialize_account_id(map)?;
+            },
+            b"ifInState" => {
+                self.if_in_state = map.next_value()?;
+            },
+            b"targetId" => {
+                self.target_id = map.next_value()?;
+            },
+            b"sourceIds" => {
+                self.source_ids = map.next_value()?;
+            },
+            _ => {
+                let _ = map.next_value::<serde::de::IgnoredAny>()?;
+            }
+        );
+
+        Ok(())
+    }
+}
+
+impl<'de> Deserialize<'de> for MergeContactCardsRequest {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_request(deserializer)
+    }
+}
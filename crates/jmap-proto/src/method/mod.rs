@@ -14,15 +14,20 @@ use std::{borrow::Cow, fmt, str::FromStr};
 
 pub mod availability;
 pub mod changes;
+pub mod convert;
 pub mod copy;
+pub mod find_duplicates;
 pub mod get;
 pub mod import;
 pub mod lookup;
+pub mod merge;
 pub mod parse;
 pub mod query;
 pub mod query_changes;
 pub mod search_snippet;
 pub mod set;
+pub mod suggest;
+pub mod test;
 pub mod upload;
 pub mod validate;
 
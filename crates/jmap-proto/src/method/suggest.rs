@@ -0,0 +1,82 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::request::deserialize::{DeserializeArguments, deserialize_request};
+use serde::{Deserialize, Deserializer, Serialize};
+use types::id::Id;
+
+#[derive(Debug, Clone, Default)]
+pub struct SuggestRecipientsRequest {
+    pub account_id: Id,
+    pub text: String,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SuggestRecipientsResponse {
+    #[serde(rename = "accountId")]
+    pub account_id: Id,
+    pub suggestions: Vec<RecipientSuggestion>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecipientSuggestion {
+    pub email: String,
+    pub name: Option<String>,
+    pub source: RecipientSource,
+    pub score: f64,
+    #[serde(rename = "contactCardId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_card_id: Option<Id>,
+    #[serde(rename = "principalId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub principal_id: Option<Id>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+pub enum RecipientSource {
+    #[serde(rename = "personal")]
+    Personal,
+    #[serde(rename = "sent")]
+    Sent,
+    #[serde(rename = "shared")]
+    Shared,
+    #[serde(rename = "directory")]
+    Directory,
+}
+
+impl<'de> DeserializeArguments<'de> for SuggestRecipientsRequest {
+    fn deserialize_argument<A>(&mut self, key: &str, map: &mut A) -> Result<(), A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        hashify::fnc_map!(key.as_bytes(),
+            b"accountId" => {
+                self.account_id = crate::request::deserialize_account_id(map)?;
+            },
+            b"text" => {
+                self.text = map.next_value()?;
+            },
+            b"limit" => {
+                self.limit = map.next_value()?;
+            },
+            _ => {
+                let _ = map.next_value::<serde::de::IgnoredAny>()?;
+            }
+        );
+
+        Ok(())
+    }
+}
+
+impl<'de> Deserialize<'de> for SuggestRecipientsRequest {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_request(deserializer)
+    }
+}
@@ -0,0 +1,101 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    error::set::SetError,
+    object::sieve::SieveProperty,
+    request::{
+        MaybeInvalid,
+        deserialize::{DeserializeArguments, deserialize_request},
+    },
+};
+use serde::{Deserialize, Deserializer, Serialize};
+use types::{blob::BlobId, id::Id};
+
+#[derive(Debug, Clone, Default)]
+pub struct TestSieveScriptRequest {
+    pub account_id: Id,
+    pub script_blob_id: Option<MaybeInvalid<BlobId>>,
+    pub sieve_script_id: Option<MaybeInvalid<Id>>,
+    pub email_blob_id: MaybeInvalid<BlobId>,
+    pub envelope_from: Option<String>,
+    pub envelope_to: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TestSieveScriptResponse {
+    #[serde(rename = "accountId")]
+    pub account_id: Id,
+    pub actions: Vec<SieveTestAction>,
+    #[serde(rename = "runtimeErrors")]
+    pub runtime_errors: Vec<String>,
+    pub error: Option<SetError<SieveProperty>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type")]
+pub enum SieveTestAction {
+    #[serde(rename = "keep")]
+    Keep { flags: Vec<String> },
+    #[serde(rename = "fileinto")]
+    FileInto {
+        mailbox: String,
+        #[serde(rename = "mailboxId")]
+        mailbox_id: Option<Id>,
+        flags: Vec<String>,
+        create: bool,
+    },
+    #[serde(rename = "redirect")]
+    Redirect { recipients: Vec<String> },
+    #[serde(rename = "reject")]
+    Reject { reason: String },
+    #[serde(rename = "discard")]
+    Discard,
+    #[serde(rename = "vacation")]
+    Vacation { recipients: Vec<String> },
+}
+
+impl<'de> DeserializeArguments<'de> for TestSieveScriptRequest {
+    fn deserialize_argument<A>(&mut self, key: &str, map: &mut A) -> Result<(), A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        hashify::fnc_map!(key.as_bytes(),
+            b"accountId" => {
+                self.account_id = crate::request::deserialize_account_id(map)?;
+            },
+            b"scriptBlobId" => {
+                self.script_blob_id = map.next_value()?;
+            },
+            b"sieveScriptId" => {
+                self.sieve_script_id = map.next_value()?;
+            },
+            b"emailBlobId" => {
+                self.email_blob_id = map.next_value()?;
+            },
+            b"envelopeFrom" => {
+                self.envelope_from = map.next_value()?;
+            },
+            b"envelopeTo" => {
+                self.envelope_to = map.next_value()?;
+            },
+            _ => {
+                let _ = map.next_value::<serde::de::IgnoredAny>()?;
+            }
+        );
+
+        Ok(())
+    }
+}
+
+impl<'de> Deserialize<'de> for TestSieveScriptRequest {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_request(deserializer)
+    }
+}
@@ -39,6 +39,10 @@ pub enum CalendarProperty {
     TimeZone,
     ShareWith,
     MyRights,
+    SourceUrl,
+    RefreshInterval,
+    PublishMode,
+    PublishUrl,
 
     // Alert object properties
     When,
@@ -118,6 +122,10 @@ impl Property for CalendarProperty {
             CalendarProperty::TimeZone => "timeZone",
             CalendarProperty::ShareWith => "shareWith",
             CalendarProperty::MyRights => "myRights",
+            CalendarProperty::SourceUrl => "sourceUrl",
+            CalendarProperty::RefreshInterval => "refreshInterval",
+            CalendarProperty::PublishMode => "publishMode",
+            CalendarProperty::PublishUrl => "publishUrl",
             CalendarProperty::When => "when",
             CalendarProperty::Trigger => "trigger",
             CalendarProperty::Offset => "offset",
@@ -229,6 +237,10 @@ impl CalendarProperty {
             b"timeZone" => CalendarProperty::TimeZone,
             b"shareWith" => CalendarProperty::ShareWith,
             b"myRights" => CalendarProperty::MyRights,
+            b"sourceUrl" => CalendarProperty::SourceUrl,
+            b"refreshInterval" => CalendarProperty::RefreshInterval,
+            b"publishMode" => CalendarProperty::PublishMode,
+            b"publishUrl" => CalendarProperty::PublishUrl,
             b"mayReadFreeBusy" => CalendarProperty::Rights(CalendarRight::MayReadFreeBusy),
             b"mayReadItems" => CalendarProperty::Rights(CalendarRight::MayReadItems),
             b"mayWriteAll" => CalendarProperty::Rights(CalendarRight::MayWriteAll),
@@ -74,6 +74,12 @@ pub enum EmailProperty {
     HasAttachment,
     Preview,
 
+    // Snooze
+    Snoozed,
+    Until,
+    MoveToMailboxId,
+    SetKeywords,
+
     // Other
     Keyword(Keyword),
     IdValue(Id),
@@ -112,7 +118,9 @@ impl Property for EmailProperty {
         let allow_patch = key.is_none();
         if let Some(Key::Property(key)) = key {
             match key.patch_or_prop() {
-                EmailProperty::Keywords => EmailProperty::Keyword(Keyword::parse(value)).into(),
+                EmailProperty::Keywords | EmailProperty::SetKeywords => {
+                    EmailProperty::Keyword(Keyword::parse(value)).into()
+                }
                 EmailProperty::MailboxIds => match parse_ref(value) {
                     MaybeReference::Value(v) => Some(EmailProperty::IdValue(v)),
                     MaybeReference::Reference(v) => Some(EmailProperty::IdReference(v)),
@@ -167,6 +175,10 @@ impl Property for EmailProperty {
             EmailProperty::Value => "value",
             EmailProperty::IsEncodingProblem => "isEncodingProblem",
             EmailProperty::IsTruncated => "isTruncated",
+            EmailProperty::Snoozed => "snoozed",
+            EmailProperty::Until => "until",
+            EmailProperty::MoveToMailboxId => "moveToMailboxId",
+            EmailProperty::SetKeywords => "setKeywords",
             EmailProperty::Header(header) => return header.to_string().into(),
             EmailProperty::Keyword(keyword) => return keyword.to_string().into(),
             EmailProperty::IdValue(id) => return id.to_string().into(),
@@ -183,7 +195,10 @@ impl Element for EmailValue {
     fn try_parse<P>(key: &Key<'_, Self::Property>, value: &str) -> Option<Self> {
         if let Key::Property(prop) = key {
             match prop.patch_or_prop() {
-                EmailProperty::Id | EmailProperty::ThreadId | EmailProperty::MailboxIds => {
+                EmailProperty::Id
+                | EmailProperty::ThreadId
+                | EmailProperty::MailboxIds
+                | EmailProperty::MoveToMailboxId => {
                     match parse_ref(value) {
                         MaybeReference::Value(v) => Some(EmailValue::Id(v)),
                         MaybeReference::Reference(v) => Some(EmailValue::IdReference(v)),
@@ -200,7 +215,8 @@ impl Element for EmailValue {
                     ..
                 })
                 | EmailProperty::ReceivedAt
-                | EmailProperty::SentAt => UTCDate::from_str(value).ok().map(EmailValue::Date),
+                | EmailProperty::SentAt
+                | EmailProperty::Until => UTCDate::from_str(value).ok().map(EmailValue::Date),
                 _ => None,
             }
         } else {
@@ -260,7 +276,11 @@ impl EmailProperty {
                 "isEncodingProblem" => EmailProperty::IsEncodingProblem,
                 "isTruncated" => EmailProperty::IsTruncated,
                 "hasAttachment" => EmailProperty::HasAttachment,
-                "preview" => EmailProperty::Preview
+                "preview" => EmailProperty::Preview,
+                "snoozed" => EmailProperty::Snoozed,
+                "until" => EmailProperty::Until,
+                "moveToMailboxId" => EmailProperty::MoveToMailboxId,
+                "setKeywords" => EmailProperty::SetKeywords
         )
         .or_else(|| {
             if let Some(header) = value.strip_prefix("header:") {
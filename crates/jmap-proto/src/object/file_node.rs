@@ -38,6 +38,9 @@ pub enum FileNodeProperty {
     MyRights,
     ShareWith,
     IsSubscribed,
+    Versions,
+    RestoreVersion,
+    ShareLinks,
 
     IdValue(Id),
     Rights(FileNodeRight),
@@ -116,6 +119,9 @@ impl Property for FileNodeProperty {
             FileNodeProperty::MyRights => "myRights",
             FileNodeProperty::ShareWith => "shareWith",
             FileNodeProperty::IsSubscribed => "isSubscribed",
+            FileNodeProperty::Versions => "versions",
+            FileNodeProperty::RestoreVersion => "restoreVersion",
+            FileNodeProperty::ShareLinks => "shareLinks",
             FileNodeProperty::Rights(file_right) => file_right.as_str(),
             FileNodeProperty::Pointer(json_pointer) => return json_pointer.to_string().into(),
             FileNodeProperty::IdValue(id) => return id.to_string().into(),
@@ -272,6 +278,9 @@ impl FileNodeProperty {
             b"myRights" => FileNodeProperty::MyRights,
             b"shareWith" => FileNodeProperty::ShareWith,
             b"isSubscribed" => FileNodeProperty::IsSubscribed,
+            b"versions" => FileNodeProperty::Versions,
+            b"restoreVersion" => FileNodeProperty::RestoreVersion,
+            b"shareLinks" => FileNodeProperty::ShareLinks,
             b"mayRead" => FileNodeProperty::Rights(FileNodeRight::MayRead),
             b"mayAddChildren" => FileNodeProperty::Rights(FileNodeRight::MayAddChildren),
             b"mayRename" => FileNodeProperty::Rights(FileNodeRight::MayRename),
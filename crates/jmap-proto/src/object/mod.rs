@@ -31,6 +31,7 @@ pub mod share_notification;
 pub mod sieve;
 pub mod thread;
 pub mod vacation_response;
+pub mod webhook;
 
 pub trait JmapObject: std::fmt::Debug {
     type Property: Property + JmapObjectId + FromStr + Debug + Sync + Send;
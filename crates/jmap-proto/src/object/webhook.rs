@@ -0,0 +1,163 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::object::{AnyId, JmapObject, JmapObjectId};
+use jmap_tools::{Element, Key, Property};
+use std::{borrow::Cow, str::FromStr};
+use types::{id::Id, type_state::DataType};
+
+#[derive(Debug, Clone, Default)]
+pub struct Webhook;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WebhookProperty {
+    Id,
+    Url,
+    Secret,
+    Types,
+    IsEnabled,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WebhookValue {
+    Id(Id),
+    Types(DataType),
+}
+
+impl Property for WebhookProperty {
+    fn try_parse(_: Option<&Key<'_, Self>>, value: &str) -> Option<Self> {
+        WebhookProperty::parse(value)
+    }
+
+    fn to_cow(&self) -> Cow<'static, str> {
+        match self {
+            WebhookProperty::Id => "id",
+            WebhookProperty::Url => "url",
+            WebhookProperty::Secret => "secret",
+            WebhookProperty::Types => "types",
+            WebhookProperty::IsEnabled => "isEnabled",
+        }
+        .into()
+    }
+}
+
+impl WebhookProperty {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map!(value.as_bytes(),
+            b"id" => WebhookProperty::Id,
+            b"url" => WebhookProperty::Url,
+            b"secret" => WebhookProperty::Secret,
+            b"types" => WebhookProperty::Types,
+            b"isEnabled" => WebhookProperty::IsEnabled,
+        )
+    }
+}
+
+impl Element for WebhookValue {
+    type Property = WebhookProperty;
+
+    fn try_parse<P>(key: &Key<'_, Self::Property>, value: &str) -> Option<Self> {
+        match key {
+            Key::Property(WebhookProperty::Id) => Id::from_str(value).ok().map(WebhookValue::Id),
+            Key::Property(WebhookProperty::Types) => {
+                DataType::parse(value).map(WebhookValue::Types)
+            }
+            _ => None,
+        }
+    }
+
+    fn to_cow(&self) -> Cow<'static, str> {
+        match self {
+            WebhookValue::Id(id) => id.to_string().into(),
+            WebhookValue::Types(data_type) => data_type.as_str().into(),
+        }
+    }
+}
+
+impl FromStr for WebhookProperty {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        WebhookProperty::parse(s).ok_or(())
+    }
+}
+
+impl JmapObject for Webhook {
+    type Property = WebhookProperty;
+
+    type Element = WebhookValue;
+
+    type Id = Id;
+
+    type Filter = ();
+
+    type Comparator = ();
+
+    type GetArguments = ();
+
+    type SetArguments<'de> = ();
+
+    type QueryArguments = ();
+
+    type CopyArguments = ();
+
+    type ParseArguments = ();
+
+    const ID_PROPERTY: Self::Property = WebhookProperty::Id;
+}
+
+impl From<Id> for WebhookValue {
+    fn from(id: Id) -> Self {
+        WebhookValue::Id(id)
+    }
+}
+
+impl JmapObjectId for WebhookValue {
+    fn as_id(&self) -> Option<Id> {
+        match self {
+            WebhookValue::Id(id) => Some(*id),
+            _ => None,
+        }
+    }
+
+    fn as_any_id(&self) -> Option<AnyId> {
+        match self {
+            WebhookValue::Id(id) => Some(AnyId::Id(*id)),
+            _ => None,
+        }
+    }
+
+    fn as_id_ref(&self) -> Option<&str> {
+        None
+    }
+
+    fn try_set_id(&mut self, new_id: AnyId) -> bool {
+        if let AnyId::Id(id) = new_id {
+            *self = WebhookValue::Id(id);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl JmapObjectId for WebhookProperty {
+    fn as_id(&self) -> Option<Id> {
+        None
+    }
+
+    fn as_any_id(&self) -> Option<AnyId> {
+        None
+    }
+
+    fn as_id_ref(&self) -> Option<&str> {
+        None
+    }
+
+    fn try_set_id(&mut self, _: AnyId) -> bool {
+        false
+    }
+}
@@ -44,6 +44,9 @@ impl Response<'_> {
                         GetResponseMethod::PushSubscription(response) => {
                             response.eval_jptr(path, &mut results)
                         }
+                        GetResponseMethod::Webhook(response) => {
+                            response.eval_jptr(path, &mut results)
+                        }
                         GetResponseMethod::Sieve(response) => {
                             response.eval_jptr(path, &mut results)
                         }
@@ -128,6 +131,9 @@ impl Response<'_> {
                         ChangesResponseMethod::ShareNotification(response) => {
                             response.eval_jptr(path, &mut results)
                         }
+                        ChangesResponseMethod::Sieve(response) => {
+                            response.eval_jptr(path, &mut results)
+                        }
                     },
                     ResponseMethod::Query(response) => response.eval_jptr(path, &mut results),
                     ResponseMethod::QueryChanges(response) => {
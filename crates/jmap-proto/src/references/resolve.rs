@@ -7,6 +7,7 @@
 use crate::{
     error::set::SetError,
     method::{
+        convert::BlobConvertRequest,
         copy::CopyRequest,
         get::GetRequest,
         import::ImportEmailRequest,
@@ -39,6 +40,7 @@ impl Response<'_> {
                 GetRequestMethod::Identity(request) => request.resolve_references(self)?,
                 GetRequestMethod::EmailSubmission(request) => request.resolve_references(self)?,
                 GetRequestMethod::PushSubscription(request) => request.resolve_references(self)?,
+                GetRequestMethod::Webhook(request) => request.resolve_references(self)?,
                 GetRequestMethod::Sieve(request) => request.resolve_references(self)?,
                 GetRequestMethod::VacationResponse(request) => request.resolve_references(self)?,
                 GetRequestMethod::Principal(request) => request.resolve_references(self)?,
@@ -71,6 +73,7 @@ impl Response<'_> {
                 SetRequestMethod::PushSubscription(request) => {
                     request.resolve_references(self, 1, false)?
                 }
+                SetRequestMethod::Webhook(request) => request.resolve_references(self, 1, false)?,
                 SetRequestMethod::Sieve(request) => request.resolve_references(self, 1, false)?,
                 SetRequestMethod::VacationResponse(request) => {
                     request.resolve_references(self, 1, false)?
@@ -117,6 +120,7 @@ impl Response<'_> {
             RequestMethod::ImportEmail(request) => request.resolve_references(self)?,
             RequestMethod::SearchSnippet(request) => request.resolve_references(self)?,
             RequestMethod::UploadBlob(request) => request.resolve_references(self)?,
+            RequestMethod::ConvertBlob(request) => request.resolve_references(self)?,
             RequestMethod::Parse(request) => match request {
                 ParseRequestMethod::Email(request) => request.resolve_references(self)?,
                 ParseRequestMethod::ContactCard(request) => request.resolve_references(self)?,
@@ -418,6 +422,41 @@ impl ResolveReference for BlobUploadRequest {
     }
 }
 
+impl ResolveReference for BlobConvertRequest {
+    fn resolve_references(&mut self, response: &Response<'_>) -> trc::Result<()> {
+        let mut graph = HashMap::with_capacity(self.create.len());
+        for (create_id, object) in self.create.iter_mut() {
+            if let Some(id) = object.blob_id_mut()
+                && let MaybeIdReference::Reference(parent_id) = id
+            {
+                match response.created_ids.get(parent_id) {
+                    Some(AnyId::BlobId(blob_id)) => {
+                        *id = MaybeIdReference::Id(blob_id.clone());
+                    }
+                    Some(_) => {
+                        return Err(trc::JmapEvent::InvalidResultReference.into_err().details(
+                            format_compact!("Id reference {parent_id:?} points to invalid type."),
+                        ));
+                    }
+                    None => {
+                        graph
+                            .entry(create_id.to_string())
+                            .or_insert_with(Vec::new)
+                            .push(parent_id.to_string());
+                    }
+                }
+            }
+        }
+
+        // Perform topological sort
+        if !graph.is_empty() {
+            self.create = topological_sort(&mut self.create, graph)?;
+        }
+
+        Ok(())
+    }
+}
+
 impl<T> ResolveCreatedReference<T::Property, T::Element> for SetResponse<T>
 where
     T: JmapObject,
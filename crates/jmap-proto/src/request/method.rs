@@ -40,6 +40,8 @@ pub enum MethodObject {
     FileNode,
     ParticipantIdentity,
     ShareNotification,
+    Webhook,
+    Recipient,
     Registry(ObjectType),
 }
 
@@ -49,8 +51,11 @@ impl MethodObject {
             MethodObject::Email
             | MethodObject::Mailbox
             | MethodObject::Thread
-            | MethodObject::SearchSnippet => Capability::Mail,
-            MethodObject::Core | MethodObject::PushSubscription => Capability::Core,
+            | MethodObject::SearchSnippet
+            | MethodObject::Recipient => Capability::Mail,
+            MethodObject::Core | MethodObject::PushSubscription | MethodObject::Webhook => {
+                Capability::Core
+            }
             MethodObject::Blob => Capability::Blob,
             MethodObject::Identity | MethodObject::EmailSubmission => Capability::Submission,
             MethodObject::VacationResponse => Capability::VacationResponse,
@@ -79,10 +84,15 @@ pub enum MethodFunction {
     Import,
     Parse,
     Validate,
+    Test,
+    Suggest,
     Lookup,
     Upload,
+    Convert,
     Echo,
     GetAvailability,
+    FindDuplicates,
+    Merge,
 }
 
 impl Display for MethodName {
@@ -107,6 +117,8 @@ impl MethodName {
         match (self.fnc, self.obj) {
             (MethodFunction::Get, MethodObject::PushSubscription) => "PushSubscription/get",
             (MethodFunction::Set, MethodObject::PushSubscription) => "PushSubscription/set",
+            (MethodFunction::Get, MethodObject::Webhook) => "Webhook/get",
+            (MethodFunction::Set, MethodObject::Webhook) => "Webhook/set",
 
             (MethodFunction::Get, MethodObject::Mailbox) => "Mailbox/get",
             (MethodFunction::Changes, MethodObject::Mailbox) => "Mailbox/changes",
@@ -127,6 +139,7 @@ impl MethodName {
             (MethodFunction::Parse, MethodObject::Email) => "Email/parse",
 
             (MethodFunction::Get, MethodObject::SearchSnippet) => "SearchSnippet/get",
+            (MethodFunction::Suggest, MethodObject::Recipient) => "Recipient/suggest",
 
             (MethodFunction::Get, MethodObject::Identity) => "Identity/get",
             (MethodFunction::Changes, MethodObject::Identity) => "Identity/changes",
@@ -147,6 +160,8 @@ impl MethodName {
             (MethodFunction::Set, MethodObject::SieveScript) => "SieveScript/set",
             (MethodFunction::Query, MethodObject::SieveScript) => "SieveScript/query",
             (MethodFunction::Validate, MethodObject::SieveScript) => "SieveScript/validate",
+            (MethodFunction::Changes, MethodObject::SieveScript) => "SieveScript/changes",
+            (MethodFunction::Test, MethodObject::SieveScript) => "SieveScript/test",
 
             (MethodFunction::Get, MethodObject::Principal) => "Principal/get",
             (MethodFunction::Set, MethodObject::Principal) => "Principal/set",
@@ -166,6 +181,7 @@ impl MethodName {
             (MethodFunction::Copy, MethodObject::Blob) => "Blob/copy",
             (MethodFunction::Lookup, MethodObject::Blob) => "Blob/lookup",
             (MethodFunction::Upload, MethodObject::Blob) => "Blob/upload",
+            (MethodFunction::Convert, MethodObject::Blob) => "Blob/convert",
 
             (MethodFunction::Get, MethodObject::AddressBook) => "AddressBook/get",
             (MethodFunction::Changes, MethodObject::AddressBook) => "AddressBook/changes",
@@ -179,6 +195,10 @@ impl MethodName {
             (MethodFunction::Set, MethodObject::ContactCard) => "ContactCard/set",
             (MethodFunction::Copy, MethodObject::ContactCard) => "ContactCard/copy",
             (MethodFunction::Parse, MethodObject::ContactCard) => "ContactCard/parse",
+            (MethodFunction::FindDuplicates, MethodObject::ContactCard) => {
+                "ContactCard/findDuplicates"
+            }
+            (MethodFunction::Merge, MethodObject::ContactCard) => "ContactCard/merge",
 
             (MethodFunction::Get, MethodObject::FileNode) => "FileNode/get",
             (MethodFunction::Changes, MethodObject::FileNode) => "FileNode/changes",
@@ -247,6 +267,8 @@ impl MethodName {
         hashify::tiny_map!(s.as_bytes(),
             "PushSubscription/get" => (MethodObject::PushSubscription, MethodFunction::Get),
             "PushSubscription/set" => (MethodObject::PushSubscription, MethodFunction::Set),
+            "Webhook/get" => (MethodObject::Webhook, MethodFunction::Get),
+            "Webhook/set" => (MethodObject::Webhook, MethodFunction::Set),
 
             "Mailbox/get" => (MethodObject::Mailbox, MethodFunction::Get),
             "Mailbox/changes" => (MethodObject::Mailbox, MethodFunction::Changes),
@@ -267,6 +289,7 @@ impl MethodName {
             "Email/parse" => (MethodObject::Email, MethodFunction::Parse),
 
             "SearchSnippet/get" => (MethodObject::SearchSnippet, MethodFunction::Get),
+            "Recipient/suggest" => (MethodObject::Recipient, MethodFunction::Suggest),
 
             "Identity/get" => (MethodObject::Identity, MethodFunction::Get),
             "Identity/changes" => (MethodObject::Identity, MethodFunction::Changes),
@@ -285,6 +308,8 @@ impl MethodName {
             "SieveScript/set" => (MethodObject::SieveScript, MethodFunction::Set),
             "SieveScript/query" => (MethodObject::SieveScript, MethodFunction::Query),
             "SieveScript/validate" => (MethodObject::SieveScript, MethodFunction::Validate),
+            "SieveScript/changes" => (MethodObject::SieveScript, MethodFunction::Changes),
+            "SieveScript/test" => (MethodObject::SieveScript, MethodFunction::Test),
 
             "Principal/get" => (MethodObject::Principal, MethodFunction::Get),
             "Principal/set" => (MethodObject::Principal, MethodFunction::Set),
@@ -302,6 +327,7 @@ impl MethodName {
             "Blob/copy" => (MethodObject::Blob, MethodFunction::Copy),
             "Blob/lookup" => (MethodObject::Blob, MethodFunction::Lookup),
             "Blob/upload" => (MethodObject::Blob, MethodFunction::Upload),
+            "Blob/convert" => (MethodObject::Blob, MethodFunction::Convert),
 
             "AddressBook/get" => (MethodObject::AddressBook, MethodFunction::Get),
             "AddressBook/changes" => (MethodObject::AddressBook, MethodFunction::Changes),
@@ -315,6 +341,8 @@ impl MethodName {
             "ContactCard/set" => (MethodObject::ContactCard, MethodFunction::Set),
             "ContactCard/copy" => (MethodObject::ContactCard, MethodFunction::Copy),
             "ContactCard/parse" => (MethodObject::ContactCard, MethodFunction::Parse),
+            "ContactCard/findDuplicates" => (MethodObject::ContactCard, MethodFunction::FindDuplicates),
+            "ContactCard/merge" => (MethodObject::ContactCard, MethodFunction::Merge),
 
             "FileNode/get" => (MethodObject::FileNode, MethodFunction::Get),
             "FileNode/changes" => (MethodObject::FileNode, MethodFunction::Changes),
@@ -381,6 +409,8 @@ impl Display for MethodObject {
             MethodObject::Identity => "Identity",
             MethodObject::VacationResponse => "VacationResponse",
             MethodObject::PushSubscription => "PushSubscription",
+            MethodObject::Webhook => "Webhook",
+            MethodObject::Recipient => "Recipient",
             MethodObject::SieveScript => "SieveScript",
             MethodObject::Principal => "Principal",
             MethodObject::Core => "Core",
@@ -416,10 +446,15 @@ impl MethodFunction {
             MethodFunction::Import => "import",
             MethodFunction::Parse => "parse",
             MethodFunction::Validate => "validate",
+            MethodFunction::Test => "test",
+            MethodFunction::Suggest => "suggest",
             MethodFunction::Lookup => "lookup",
             MethodFunction::Upload => "upload",
+            MethodFunction::Convert => "convert",
             MethodFunction::Echo => "echo",
             MethodFunction::GetAvailability => "getAvailability",
+            MethodFunction::FindDuplicates => "findDuplicates",
+            MethodFunction::Merge => "merge",
         }
     }
 }
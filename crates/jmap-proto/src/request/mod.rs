@@ -16,15 +16,20 @@ use crate::{
     method::{
         availability::GetAvailabilityRequest,
         changes::ChangesRequest,
+        convert::BlobConvertRequest,
         copy::{CopyBlobRequest, CopyRequest},
+        find_duplicates::FindDuplicatesRequest,
         get::GetRequest,
         import::ImportEmailRequest,
         lookup::BlobLookupRequest,
+        merge::MergeContactCardsRequest,
         parse::ParseRequest,
         query::QueryRequest,
         query_changes::QueryChangesRequest,
         search_snippet::GetSearchSnippetRequest,
         set::SetRequest,
+        suggest::SuggestRecipientsRequest,
+        test::TestSieveScriptRequest,
         upload::BlobUploadRequest,
         validate::ValidateSieveScriptRequest,
     },
@@ -35,7 +40,7 @@ use crate::{
         identity::Identity, mailbox::Mailbox, participant_identity::ParticipantIdentity,
         principal::Principal, push_subscription::PushSubscription, quota::Quota,
         registry::Registry, share_notification::ShareNotification, sieve::Sieve, thread::Thread,
-        vacation_response::VacationResponse,
+        vacation_response::VacationResponse, webhook::Webhook,
     },
     request::{capability::CapabilityIds, reference::MaybeIdReference},
 };
@@ -82,8 +87,13 @@ pub enum RequestMethod<'x> {
     QueryChanges(QueryChangesRequestMethod),
     SearchSnippet(Box<GetSearchSnippetRequest>),
     ValidateScript(Box<ValidateSieveScriptRequest>),
+    TestScript(Box<TestSieveScriptRequest>),
+    SuggestRecipients(Box<SuggestRecipientsRequest>),
+    FindDuplicates(Box<FindDuplicatesRequest>),
+    MergeContactCards(Box<MergeContactCardsRequest>),
     LookupBlob(Box<BlobLookupRequest>),
     UploadBlob(Box<BlobUploadRequest>),
+    ConvertBlob(Box<BlobConvertRequest>),
     Echo(Value<'x, Null, Null>),
     Error(trc::Error),
 }
@@ -96,6 +106,7 @@ pub enum GetRequestMethod {
     Identity(Box<GetRequest<Identity>>),
     EmailSubmission(Box<GetRequest<EmailSubmission>>),
     PushSubscription(Box<GetRequest<PushSubscription>>),
+    Webhook(Box<GetRequest<Webhook>>),
     Sieve(Box<GetRequest<Sieve>>),
     VacationResponse(Box<GetRequest<VacationResponse>>),
     Principal(Box<GetRequest<Principal>>),
@@ -120,6 +131,7 @@ pub enum SetRequestMethod<'x> {
     Identity(Box<SetRequest<'x, Identity>>),
     EmailSubmission(Box<SetRequest<'x, EmailSubmission>>),
     PushSubscription(Box<SetRequest<'x, PushSubscription>>),
+    Webhook(Box<SetRequest<'x, Webhook>>),
     Sieve(Box<SetRequest<'x, Sieve>>),
     VacationResponse(Box<SetRequest<'x, VacationResponse>>),
     AddressBook(Box<SetRequest<'x, AddressBook>>),
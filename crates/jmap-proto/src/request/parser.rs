@@ -146,6 +146,13 @@ impl<'de> Visitor<'de> for CallVisitor {
                     return Err(de::Error::invalid_length(1, &self));
                 }
             },
+            (MethodFunction::Get, MethodObject::Webhook) => match seq.next_element() {
+                Ok(Some(value)) => RequestMethod::Get(GetRequestMethod::Webhook(value)),
+                Err(err) => RequestMethod::invalid(err),
+                Ok(None) => {
+                    return Err(de::Error::invalid_length(1, &self));
+                }
+            },
             (MethodFunction::Get, MethodObject::VacationResponse) => match seq.next_element() {
                 Ok(Some(value)) => RequestMethod::Get(GetRequestMethod::VacationResponse(value)),
                 Err(err) => RequestMethod::invalid(err),
@@ -290,6 +297,13 @@ impl<'de> Visitor<'de> for CallVisitor {
                     return Err(de::Error::invalid_length(1, &self));
                 }
             },
+            (MethodFunction::Set, MethodObject::Webhook) => match seq.next_element() {
+                Ok(Some(value)) => RequestMethod::Set(SetRequestMethod::Webhook(value)),
+                Err(err) => RequestMethod::invalid(err),
+                Ok(None) => {
+                    return Err(de::Error::invalid_length(1, &self));
+                }
+            },
             (MethodFunction::Set, MethodObject::VacationResponse) => match seq.next_element() {
                 Ok(Some(value)) => RequestMethod::Set(SetRequestMethod::VacationResponse(value)),
                 Err(err) => RequestMethod::invalid(err),
@@ -628,6 +642,13 @@ impl<'de> Visitor<'de> for CallVisitor {
                     return Err(de::Error::invalid_length(1, &self));
                 }
             },
+            (MethodFunction::Convert, MethodObject::Blob) => match seq.next_element() {
+                Ok(Some(value)) => RequestMethod::ConvertBlob(value),
+                Err(err) => RequestMethod::invalid(err),
+                Ok(None) => {
+                    return Err(de::Error::invalid_length(1, &self));
+                }
+            },
             (MethodFunction::Import, MethodObject::Email) => match seq.next_element() {
                 Ok(Some(value)) => RequestMethod::ImportEmail(value),
                 Err(err) => RequestMethod::invalid(err),
@@ -656,6 +677,22 @@ impl<'de> Visitor<'de> for CallVisitor {
                     return Err(de::Error::invalid_length(1, &self));
                 }
             },
+            (MethodFunction::FindDuplicates, MethodObject::ContactCard) => {
+                match seq.next_element() {
+                    Ok(Some(value)) => RequestMethod::FindDuplicates(value),
+                    Err(err) => RequestMethod::invalid(err),
+                    Ok(None) => {
+                        return Err(de::Error::invalid_length(1, &self));
+                    }
+                }
+            }
+            (MethodFunction::Merge, MethodObject::ContactCard) => match seq.next_element() {
+                Ok(Some(value)) => RequestMethod::MergeContactCards(value),
+                Err(err) => RequestMethod::invalid(err),
+                Ok(None) => {
+                    return Err(de::Error::invalid_length(1, &self));
+                }
+            },
             (MethodFunction::GetAvailability, MethodObject::Principal) => {
                 match seq.next_element() {
                     Ok(Some(value)) => {
@@ -674,6 +711,20 @@ impl<'de> Visitor<'de> for CallVisitor {
                     return Err(de::Error::invalid_length(1, &self));
                 }
             },
+            (MethodFunction::Test, MethodObject::SieveScript) => match seq.next_element() {
+                Ok(Some(value)) => RequestMethod::TestScript(value),
+                Err(err) => RequestMethod::invalid(err),
+                Ok(None) => {
+                    return Err(de::Error::invalid_length(1, &self));
+                }
+            },
+            (MethodFunction::Suggest, MethodObject::Recipient) => match seq.next_element() {
+                Ok(Some(value)) => RequestMethod::SuggestRecipients(value),
+                Err(err) => RequestMethod::invalid(err),
+                Ok(None) => {
+                    return Err(de::Error::invalid_length(1, &self));
+                }
+            },
             (MethodFunction::Echo, MethodObject::Core) => match seq.next_element() {
                 Ok(Some(value)) => RequestMethod::Echo(value),
                 Err(err) => RequestMethod::invalid(err),
@@ -14,14 +14,18 @@ use crate::{
         availability::GetAvailabilityResponse,
         changes::ChangesResponse,
         copy::{CopyBlobResponse, CopyResponse},
+        find_duplicates::FindDuplicatesResponse,
         get::GetResponse,
         import::ImportEmailResponse,
         lookup::BlobLookupResponse,
+        merge::MergeContactCardsResponse,
         parse::ParseResponse,
         query::QueryResponse,
         query_changes::QueryChangesResponse,
         search_snippet::GetSearchSnippetResponse,
         set::SetResponse,
+        suggest::SuggestRecipientsResponse,
+        test::TestSieveScriptResponse,
         upload::BlobUploadResponse,
         validate::ValidateSieveScriptResponse,
     },
@@ -49,6 +53,7 @@ use crate::{
         sieve::Sieve,
         thread::Thread,
         vacation_response::VacationResponse,
+        webhook::Webhook,
     },
     request::{Call, method::MethodName},
 };
@@ -68,6 +73,10 @@ pub enum ResponseMethod<'x> {
     Query(QueryResponse),
     SearchSnippet(GetSearchSnippetResponse),
     ValidateScript(ValidateSieveScriptResponse),
+    TestScript(TestSieveScriptResponse),
+    SuggestRecipients(SuggestRecipientsResponse),
+    FindDuplicates(FindDuplicatesResponse),
+    MergeContactCards(MergeContactCardsResponse),
     LookupBlob(BlobLookupResponse),
     UploadBlob(BlobUploadResponse),
     Echo(Value<'x, Null, Null>),
@@ -83,6 +92,7 @@ pub enum GetResponseMethod {
     Identity(GetResponse<Identity>),
     EmailSubmission(GetResponse<EmailSubmission>),
     PushSubscription(GetResponse<PushSubscription>),
+    Webhook(GetResponse<Webhook>),
     Sieve(GetResponse<Sieve>),
     VacationResponse(GetResponse<VacationResponse>),
     Principal(GetResponse<Principal>),
@@ -108,6 +118,7 @@ pub enum SetResponseMethod {
     Identity(Box<SetResponse<Identity>>),
     EmailSubmission(Box<SetResponse<EmailSubmission>>),
     PushSubscription(Box<SetResponse<PushSubscription>>),
+    Webhook(Box<SetResponse<Webhook>>),
     Sieve(Box<SetResponse<Sieve>>),
     VacationResponse(Box<SetResponse<VacationResponse>>),
     AddressBook(Box<SetResponse<AddressBook>>),
@@ -137,6 +148,7 @@ pub enum ChangesResponseMethod {
     CalendarEvent(Box<ChangesResponse<CalendarEvent>>),
     CalendarEventNotification(Box<ChangesResponse<CalendarEventNotification>>),
     ShareNotification(Box<ChangesResponse<ShareNotification>>),
+    Sieve(Box<ChangesResponse<Sieve>>),
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -257,6 +269,12 @@ impl<'x> From<GetResponse<PushSubscription>> for ResponseMethod<'x> {
     }
 }
 
+impl<'x> From<GetResponse<Webhook>> for ResponseMethod<'x> {
+    fn from(value: GetResponse<Webhook>) -> Self {
+        ResponseMethod::Get(GetResponseMethod::Webhook(value))
+    }
+}
+
 impl<'x> From<GetResponse<Sieve>> for ResponseMethod<'x> {
     fn from(value: GetResponse<Sieve>) -> Self {
         ResponseMethod::Get(GetResponseMethod::Sieve(value))
@@ -335,6 +353,12 @@ impl<'x> From<SetResponse<PushSubscription>> for ResponseMethod<'x> {
     }
 }
 
+impl<'x> From<SetResponse<Webhook>> for ResponseMethod<'x> {
+    fn from(value: SetResponse<Webhook>) -> Self {
+        ResponseMethod::Set(SetResponseMethod::Webhook(Box::new(value)))
+    }
+}
+
 impl<'x> From<SetResponse<Sieve>> for ResponseMethod<'x> {
     fn from(value: SetResponse<Sieve>) -> Self {
         ResponseMethod::Set(SetResponseMethod::Sieve(Box::new(value)))
@@ -467,6 +491,30 @@ impl<'x> From<ValidateSieveScriptResponse> for ResponseMethod<'x> {
     }
 }
 
+impl<'x> From<TestSieveScriptResponse> for ResponseMethod<'x> {
+    fn from(value: TestSieveScriptResponse) -> Self {
+        ResponseMethod::TestScript(value)
+    }
+}
+
+impl<'x> From<SuggestRecipientsResponse> for ResponseMethod<'x> {
+    fn from(value: SuggestRecipientsResponse) -> Self {
+        ResponseMethod::SuggestRecipients(value)
+    }
+}
+
+impl<'x> From<FindDuplicatesResponse> for ResponseMethod<'x> {
+    fn from(value: FindDuplicatesResponse) -> Self {
+        ResponseMethod::FindDuplicates(value)
+    }
+}
+
+impl<'x> From<MergeContactCardsResponse> for ResponseMethod<'x> {
+    fn from(value: MergeContactCardsResponse) -> Self {
+        ResponseMethod::MergeContactCards(value)
+    }
+}
+
 impl<'x> From<BlobLookupResponse> for ResponseMethod<'x> {
     fn from(value: BlobLookupResponse) -> Self {
         ResponseMethod::LookupBlob(value)
@@ -565,6 +613,12 @@ impl From<ChangesResponse<ShareNotification>> for ResponseMethod<'_> {
     }
 }
 
+impl From<ChangesResponse<Sieve>> for ResponseMethod<'_> {
+    fn from(response: ChangesResponse<Sieve>) -> Self {
+        ResponseMethod::Changes(ChangesResponseMethod::Sieve(Box::new(response)))
+    }
+}
+
 impl From<SetResponse<ShareNotification>> for ResponseMethod<'_> {
     fn from(response: SetResponse<ShareNotification>) -> Self {
         ResponseMethod::Set(SetResponseMethod::ShareNotification(Box::new(response)))
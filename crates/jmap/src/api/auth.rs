@@ -67,6 +67,7 @@ impl JmapAuthorization for AccessToken {
                 GetRequestMethod::Identity(_) => Permission::JmapIdentityGet,
                 GetRequestMethod::EmailSubmission(_) => Permission::JmapEmailSubmissionGet,
                 GetRequestMethod::PushSubscription(_) => Permission::JmapPushSubscriptionGet,
+                GetRequestMethod::Webhook(_) => Permission::JmapWebhookGet,
                 GetRequestMethod::Sieve(_) => Permission::JmapSieveScriptGet,
                 GetRequestMethod::VacationResponse(_) => Permission::JmapVacationResponseGet,
                 GetRequestMethod::Principal(_) => Permission::JmapPrincipalGet,
@@ -129,6 +130,13 @@ impl JmapAuthorization for AccessToken {
                         Permission::JmapPushSubscriptionUpdate,
                         Permission::JmapPushSubscriptionDestroy,
                     ),
+                    SetRequestMethod::Webhook(s) => validate_set(
+                        s,
+                        self,
+                        Permission::JmapWebhookCreate,
+                        Permission::JmapWebhookUpdate,
+                        Permission::JmapWebhookDestroy,
+                    ),
                     SetRequestMethod::Sieve(s) => validate_set(
                         s,
                         self,
@@ -232,12 +240,14 @@ impl JmapAuthorization for AccessToken {
                 MethodObject::ShareNotification => Permission::JmapShareNotificationChanges,
                 MethodObject::Principal => Permission::JmapPrincipalChanges,
                 MethodObject::AddressBook => Permission::JmapAddressBookChanges,
+                MethodObject::SieveScript => Permission::JmapSieveScriptChanges,
                 MethodObject::Core
                 | MethodObject::Blob
                 | MethodObject::PushSubscription
+                | MethodObject::Webhook
+                | MethodObject::Recipient
                 | MethodObject::SearchSnippet
                 | MethodObject::VacationResponse
-                | MethodObject::SieveScript
                 | MethodObject::Registry(_) => Permission::JmapEmailChanges,
             },
             RequestMethod::Copy(m) => match &m {
@@ -300,8 +310,13 @@ impl JmapAuthorization for AccessToken {
             },
             RequestMethod::SearchSnippet(_) => Permission::JmapSearchSnippetGet,
             RequestMethod::ValidateScript(_) => Permission::JmapSieveScriptValidate,
+            RequestMethod::TestScript(_) => Permission::JmapSieveScriptTest,
+            RequestMethod::SuggestRecipients(_) => Permission::JmapRecipientSuggest,
+            RequestMethod::FindDuplicates(_) => Permission::JmapContactCardFindDuplicates,
+            RequestMethod::MergeContactCards(_) => Permission::JmapContactCardMerge,
             RequestMethod::LookupBlob(_) => Permission::JmapBlobLookup,
             RequestMethod::UploadBlob(_) => Permission::JmapBlobUpload,
+            RequestMethod::ConvertBlob(_) => Permission::JmapBlobConvert,
             RequestMethod::Echo(_) => Permission::JmapCoreEcho,
             RequestMethod::Error(_) => return Ok(()),
         };
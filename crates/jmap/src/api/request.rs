@@ -7,7 +7,7 @@
 use crate::{
     addressbook::{get::AddressBookGet, set::AddressBookSet},
     api::auth::JmapAuthorization,
-    blob::{copy::BlobCopy, get::BlobOperations, upload::BlobUpload},
+    blob::{convert::BlobConvert, copy::BlobCopy, get::BlobOperations, upload::BlobUpload},
     calendar::{get::CalendarGet, set::CalendarSet},
     calendar_event::{
         copy::JmapCalendarEventCopy, get::CalendarEventGet, parse::CalendarEventParse,
@@ -19,8 +19,9 @@ use crate::{
     },
     changes::{get::ChangesLookup, query::QueryChanges},
     contact::{
-        copy::JmapContactCardCopy, get::ContactCardGet, parse::ContactCardParse,
-        query::ContactCardQuery, set::ContactCardSet,
+        copy::JmapContactCardCopy, duplicates::ContactCardFindDuplicates, get::ContactCardGet,
+        merge::ContactCardMerge, parse::ContactCardParse, query::ContactCardQuery,
+        set::ContactCardSet,
     },
     email::{
         copy::JmapEmailCopy, get::EmailGet, import::EmailImport, parse::EmailParse,
@@ -33,17 +34,19 @@ use crate::{
     principal::{availability::PrincipalGetAvailability, get::PrincipalGet, query::PrincipalQuery},
     push::{get::PushSubscriptionFetch, set::PushSubscriptionSet},
     quota::{get::QuotaGet, query::QuotaQuery},
+    recipient::suggest::RecipientSuggest,
     registry::{get::RegistryGet, query::RegistryQuery, set::RegistrySet},
     share_notification::{
         get::ShareNotificationGet, query::ShareNotificationQuery, set::ShareNotificationSet,
     },
     sieve::{
-        get::SieveScriptGet, query::SieveScriptQuery, set::SieveScriptSet,
+        get::SieveScriptGet, query::SieveScriptQuery, set::SieveScriptSet, test::SieveScriptTest,
         validate::SieveScriptValidate,
     },
     submission::{get::EmailSubmissionGet, query::EmailSubmissionQuery, set::EmailSubmissionSet},
     thread::get::ThreadGet,
     vacation::{get::VacationResponseGet, set::VacationResponseSet},
+    webhook::{get::WebhookFetch, set::WebhookSet},
 };
 use common::{Server, auth::AccessToken};
 use http_proto::HttpSessionData;
@@ -156,6 +159,9 @@ impl RequestHandler for Server {
                                     SetResponseMethod::PushSubscription(set_response) => {
                                         set_response.update_created_ids(&mut response);
                                     }
+                                    SetResponseMethod::Webhook(set_response) => {
+                                        set_response.update_created_ids(&mut response);
+                                    }
                                     SetResponseMethod::Sieve(set_response) => {
                                         set_response.update_created_ids(&mut response);
                                     }
@@ -284,6 +290,10 @@ impl RequestHandler for Server {
                     resolve_account_id(&mut req.account_id, method_name.obj, access_token)?;
                     self.push_subscription_get(*req, access_token).await?.into()
                 }
+                GetRequestMethod::Webhook(mut req) => {
+                    resolve_account_id(&mut req.account_id, method_name.obj, access_token)?;
+                    self.webhook_get(*req, access_token).await?.into()
+                }
                 GetRequestMethod::Sieve(mut req) => {
                     resolve_account_id(&mut req.account_id, method_name.obj, access_token)?;
                     access_token.assert_is_member(req.account_id)?;
@@ -500,6 +510,10 @@ impl RequestHandler for Server {
                     resolve_account_id(&mut req.account_id, method_name.obj, access_token)?;
                     self.push_subscription_set(*req, access_token).await?.into()
                 }
+                SetRequestMethod::Webhook(mut req) => {
+                    resolve_account_id(&mut req.account_id, method_name.obj, access_token)?;
+                    self.webhook_set(*req, access_token).await?.into()
+                }
                 SetRequestMethod::Sieve(mut req) => {
                     resolve_account_id(&mut req.account_id, method_name.obj, access_token)?;
                     access_token.assert_is_member(req.account_id)?;
@@ -688,6 +702,32 @@ impl RequestHandler for Server {
 
                 self.sieve_script_validate(*req, access_token).await?.into()
             }
+            RequestMethod::TestScript(mut req) => {
+                resolve_account_id(&mut req.account_id, method_name.obj, access_token)?;
+                access_token.assert_is_member(req.account_id)?;
+
+                self.sieve_script_test(*req, access_token).await?.into()
+            }
+            RequestMethod::SuggestRecipients(mut req) => {
+                resolve_account_id(&mut req.account_id, method_name.obj, access_token)?;
+                access_token.assert_is_member(req.account_id)?;
+
+                self.recipient_suggest(*req, access_token).await?.into()
+            }
+            RequestMethod::FindDuplicates(mut req) => {
+                resolve_account_id(&mut req.account_id, method_name.obj, access_token)?;
+                access_token.assert_has_access(req.account_id, Collection::ContactCard)?;
+
+                self.contact_card_find_duplicates(*req, access_token)
+                    .await?
+                    .into()
+            }
+            RequestMethod::MergeContactCards(mut req) => {
+                resolve_account_id(&mut req.account_id, method_name.obj, access_token)?;
+                access_token.assert_has_access(req.account_id, Collection::ContactCard)?;
+
+                self.contact_card_merge(*req, access_token).await?.into()
+            }
             RequestMethod::LookupBlob(mut req) => {
                 resolve_account_id(&mut req.account_id, method_name.obj, access_token)?;
                 access_token.assert_is_member(req.account_id)?;
@@ -700,6 +740,12 @@ impl RequestHandler for Server {
 
                 self.blob_upload_many(*req, access_token).await?.into()
             }
+            RequestMethod::ConvertBlob(mut req) => {
+                resolve_account_id(&mut req.account_id, method_name.obj, access_token)?;
+                access_token.assert_is_member(req.account_id)?;
+
+                self.blob_convert(*req, access_token).await?.into()
+            }
             RequestMethod::Echo(req) => req.into(),
             RequestMethod::Error(error) => return Err(error),
         };
@@ -725,7 +771,10 @@ pub(crate) fn resolve_account_id(
         Ok(())
     } else if matches!(
         obj,
-        MethodObject::Core | MethodObject::PushSubscription | MethodObject::Registry(_)
+        MethodObject::Core
+            | MethodObject::PushSubscription
+            | MethodObject::Webhook
+            | MethodObject::Registry(_)
     ) {
         *account_id = Id::from(access_token.account_id());
         Ok(())
@@ -0,0 +1,405 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::download::BlobDownload;
+use common::{
+    KV_BLOB_CONVERT, Server,
+    auth::AccessToken,
+    scripts::functions::image::{ImageFormat, image_format, resize_image},
+};
+use jmap_proto::{
+    error::set::{SetError, SetErrorType},
+    method::{
+        convert::BlobConvertRequest,
+        upload::{BlobUploadResponse, BlobUploadResponseObject},
+    },
+    request::reference::MaybeIdReference,
+};
+use mail_parser::{MessageParser, decoders::html::html_to_text};
+use registry::schema::enums::Permission;
+use std::future::Future;
+use store::{
+    Serialize,
+    dispatch::lookup::KeyValue,
+    write::{AlignedBytes, Archive, Archiver},
+};
+use trc::AddContext;
+use types::{blob::BlobId, blob_hash::BlobHash};
+
+const MAX_IMAGE_DIMENSION: u32 = 4096;
+
+pub trait BlobConvert: Sync + Send {
+    fn blob_convert(
+        &self,
+        request: BlobConvertRequest,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<BlobUploadResponse>> + Send;
+}
+
+enum Conversion {
+    Image {
+        width: u32,
+        height: u32,
+        format: ImageFormat,
+    },
+    Text {
+        type_: Option<String>,
+        max_bytes: usize,
+    },
+}
+
+impl BlobConvert for Server {
+    async fn blob_convert(
+        &self,
+        request: BlobConvertRequest,
+        access_token: &AccessToken,
+    ) -> trc::Result<BlobUploadResponse> {
+        let mut response = BlobUploadResponse {
+            account_id: request.account_id,
+            created: Default::default(),
+            not_created: Default::default(),
+        };
+        let account_id = request.account_id.document_id();
+
+        if request.create.len() > self.core.jmap.set_max_objects {
+            return Err(trc::JmapEvent::RequestTooLarge.into_err());
+        }
+
+        for (create_id, convert_object) in request.create {
+            // Validate conversion
+            let (blob_id, conversion) =
+                match (convert_object.image_convert, convert_object.text_extract) {
+                    (Some(image), None) => {
+                        let width = image.width.unwrap_or(MAX_IMAGE_DIMENSION);
+                        let height = image.height.unwrap_or(MAX_IMAGE_DIMENSION);
+                        if !(1..=MAX_IMAGE_DIMENSION).contains(&width)
+                            || !(1..=MAX_IMAGE_DIMENSION).contains(&height)
+                        {
+                            response.not_created.append(
+                                create_id,
+                                SetError::invalid_properties().with_description(format!(
+                                    "Image dimensions must be between 1 and {} pixels.",
+                                    MAX_IMAGE_DIMENSION
+                                )),
+                            );
+                            continue;
+                        }
+                        let format = match image.type_.as_deref() {
+                            Some(type_) => {
+                                if let Some(format) = image_format(type_) {
+                                    format
+                                } else {
+                                    response.not_created.append(
+                                        create_id,
+                                        SetError::invalid_properties().with_description(format!(
+                                            "Unsupported image type {type_:?}."
+                                        )),
+                                    );
+                                    continue;
+                                }
+                            }
+                            None => ImageFormat::Png,
+                        };
+
+                        (
+                            image.blob_id,
+                            Conversion::Image {
+                                width,
+                                height,
+                                format,
+                            },
+                        )
+                    }
+                    (None, Some(text)) => {
+                        let max_bytes = text
+                            .max_bytes
+                            .filter(|max_bytes| *max_bytes > 0)
+                            .map_or(self.core.jmap.upload_max_size, |max_bytes| {
+                                std::cmp::min(max_bytes, self.core.jmap.upload_max_size)
+                            });
+
+                        (
+                            text.blob_id,
+                            Conversion::Text {
+                                type_: text.type_,
+                                max_bytes,
+                            },
+                        )
+                    }
+                    _ => {
+                        response.not_created.append(
+                            create_id,
+                            SetError::invalid_properties().with_description(
+                                "Exactly one of imageConvert or textExtract must be specified.",
+                            ),
+                        );
+                        continue;
+                    }
+                };
+
+            // Obtain blob id
+            let blob_id = match blob_id {
+                MaybeIdReference::Id(id) => id,
+                MaybeIdReference::Reference(reference) => {
+                    if let Some(obj) = response.created.get(&reference) {
+                        obj.id.clone()
+                    } else {
+                        response.not_created.append(
+                            create_id,
+                            SetError::not_found()
+                                .with_description(format!("Id reference {reference:?} not found.")),
+                        );
+                        continue;
+                    }
+                }
+                MaybeIdReference::Invalid(id) => {
+                    response.not_created.append(
+                        create_id,
+                        SetError::invalid_properties()
+                            .with_description(format!("Invalid blobId {id:?}.")),
+                    );
+                    continue;
+                }
+            };
+
+            if !self.has_access_blob(&blob_id, access_token).await? {
+                response.not_created.append(
+                    create_id,
+                    SetError::forbidden()
+                        .with_description(format!("You do not have access to blobId {blob_id}.")),
+                );
+                continue;
+            }
+
+            // Converted blobs are cached by source and conversion parameters
+            let cache_key = conversion.cache_key(&blob_id);
+            let cached = if let Some(hash) = self
+                .in_memory_store()
+                .key_get::<Archive<AlignedBytes>>(KeyValue::<()>::build_key(
+                    KV_BLOB_CONVERT,
+                    cache_key.as_slice(),
+                ))
+                .await
+                .caused_by(trc::location!())?
+            {
+                let hash = hash.deserialize::<BlobHash>().caused_by(trc::location!())?;
+                self.blob_store()
+                    .get_blob(hash.as_slice(), 0..usize::MAX)
+                    .await
+                    .caused_by(trc::location!())?
+            } else {
+                None
+            };
+            let is_cached = cached.is_some();
+
+            let data = if let Some(data) = cached {
+                data
+            } else {
+                // Fetch source blob
+                let source = if let Some(section) = &blob_id.section {
+                    self.get_blob_section(&blob_id.hash, section).await?
+                } else {
+                    self.blob_store()
+                        .get_blob(blob_id.hash.as_slice(), 0..usize::MAX)
+                        .await?
+                };
+                let Some(source) = source else {
+                    response.not_created.append(
+                        create_id,
+                        SetError::blob_not_found()
+                            .with_description(format!("BlobId {blob_id} not found.")),
+                    );
+                    continue;
+                };
+                if source.len() > self.core.jmap.upload_max_size {
+                    response.not_created.append(
+                        create_id,
+                        SetError::too_large().with_description(format!(
+                            "Blob size exceeds maximum of {} bytes.",
+                            self.core.jmap.upload_max_size
+                        )),
+                    );
+                    continue;
+                }
+
+                match conversion {
+                    Conversion::Image {
+                        width,
+                        height,
+                        format,
+                    } => {
+                        match tokio::task::spawn_blocking(move || {
+                            resize_image(&source, width, height, format)
+                        })
+                        .await
+                        {
+                            Ok(Some(data)) => data,
+                            Ok(None) => {
+                                response.not_created.append(
+                                    create_id,
+                                    SetError::invalid_properties().with_description(format!(
+                                        "BlobId {blob_id} is not a supported image."
+                                    )),
+                                );
+                                continue;
+                            }
+                            Err(err) => {
+                                return Err(trc::EventType::Server(trc::ServerEvent::ThreadError)
+                                    .reason(err)
+                                    .caused_by(trc::location!()));
+                            }
+                        }
+                    }
+                    Conversion::Text {
+                        ref type_,
+                        max_bytes,
+                    } => {
+                        if let Some(text) = extract_text(&source, type_.as_deref(), max_bytes) {
+                            text.into_bytes()
+                        } else {
+                            response.not_created.append(
+                                create_id,
+                                SetError::new(SetErrorType::UnsupportedType).with_description(
+                                    format!("Text cannot be extracted from blobId {blob_id}."),
+                                ),
+                            );
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            // Enforce quota
+            if !access_token.has_permission(Permission::UnlimitedUploads)
+                && !self
+                    .blob_has_quota(account_id, data.len())
+                    .await
+                    .caused_by(trc::location!())?
+                    .allowed
+            {
+                response.not_created.append(
+                    create_id,
+                    SetError::over_quota().with_description(format!(
+                        "You have exceeded the blob upload quota of {} files or {} bytes.",
+                        self.core.jmap.upload_tmp_quota_amount,
+                        self.core.jmap.upload_tmp_quota_size
+                    )),
+                );
+                continue;
+            }
+
+            // Write blob
+            let id = self
+                .put_jmap_blob(account_id, &data)
+                .await
+                .caused_by(trc::location!())?;
+            if !is_cached {
+                self.in_memory_store()
+                    .key_set(
+                        KeyValue::with_prefix(
+                            KV_BLOB_CONVERT,
+                            cache_key.as_slice(),
+                            Archiver::new(id.hash.clone())
+                                .serialize()
+                                .caused_by(trc::location!())?,
+                        )
+                        .expires(self.core.jmap.upload_tmp_ttl),
+                    )
+                    .await
+                    .caused_by(trc::location!())?;
+            }
+            response.created.insert(
+                create_id,
+                BlobUploadResponseObject {
+                    id,
+                    type_: Some(conversion.content_type().to_string()),
+                    size: data.len(),
+                },
+            );
+        }
+
+        Ok(response)
+    }
+}
+
+impl Conversion {
+    fn cache_key(&self, blob_id: &BlobId) -> BlobHash {
+        let mut key = Vec::with_capacity(64);
+        key.extend_from_slice(blob_id.hash.as_slice());
+        if let Some(section) = &blob_id.section {
+            key.extend_from_slice(&(section.offset_start as u64).to_be_bytes());
+            key.extend_from_slice(&(section.size as u64).to_be_bytes());
+            key.push(section.encoding);
+        }
+        match self {
+            Conversion::Image {
+                width,
+                height,
+                format,
+            } => {
+                key.extend_from_slice(b"image");
+                key.extend_from_slice(&width.to_be_bytes());
+                key.extend_from_slice(&height.to_be_bytes());
+                key.extend_from_slice(format.to_mime_type().as_bytes());
+            }
+            Conversion::Text { type_, max_bytes } => {
+                key.extend_from_slice(b"text");
+                key.extend_from_slice(&(*max_bytes as u64).to_be_bytes());
+                key.extend_from_slice(type_.as_deref().unwrap_or_default().as_bytes());
+            }
+        }
+        BlobHash::generate(key)
+    }
+
+    fn content_type(&self) -> &'static str {
+        match self {
+            Conversion::Image { format, .. } => format.to_mime_type(),
+            Conversion::Text { .. } => "text/plain; charset=utf-8",
+        }
+    }
+}
+
+// Extracts text from HTML, messages and plain text, other types are not supported
+fn extract_text(bytes: &[u8], type_: Option<&str>, max_bytes: usize) -> Option<String> {
+    let type_ = type_.map(|t| t.split(';').next().unwrap_or_default().trim());
+    let mut text = match type_ {
+        Some(t) if t.eq_ignore_ascii_case("text/html") => {
+            html_to_text(&String::from_utf8_lossy(bytes))
+        }
+        Some(t) if t.eq_ignore_ascii_case("message/rfc822") => {
+            if let Some(message) = MessageParser::new().parse(bytes) {
+                let mut text = String::new();
+                for body in (0..).map_while(|idx| message.body_text(idx)) {
+                    if !text.is_empty() {
+                        text.push('\n');
+                    }
+                    text.push_str(&body);
+                    if text.len() >= max_bytes {
+                        break;
+                    }
+                }
+                text
+            } else {
+                return None;
+            }
+        }
+        Some(t) if t.get(..5).is_some_and(|t| t.eq_ignore_ascii_case("text/")) => {
+            String::from_utf8_lossy(bytes).into_owned()
+        }
+        Some(_) => return None,
+        None => std::str::from_utf8(bytes).ok()?.to_string(),
+    };
+
+    if text.len() > max_bytes {
+        let mut end = max_bytes;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text.truncate(end);
+    }
+
+    Some(text)
+}
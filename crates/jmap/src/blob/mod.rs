@@ -6,6 +6,7 @@
 
 use types::{blob::BlobId, id::Id};
 
+pub mod convert;
 pub mod copy;
 pub mod download;
 pub mod get;
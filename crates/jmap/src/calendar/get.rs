@@ -12,6 +12,8 @@ use groupware::{
     calendar::{
         ALERT_EMAIL, ALERT_RELATIVE_TO_END, ArchivedDefaultAlert, CALENDAR_INVISIBLE,
         CALENDAR_SUBSCRIBED, Calendar,
+        publish::CalendarPublish,
+        subscription::{CalendarSubscriptionFetch, SUBSCRIPTION_READ_ONLY_ACLS},
     },
 };
 use jmap_proto::{
@@ -30,6 +32,7 @@ use types::{
     collection::{Collection, SyncCollection},
     field::PrincipalField,
 };
+use utils::map::bitmap::Bitmap;
 
 pub trait CalendarGet: Sync + Send {
     fn calendar_get(
@@ -66,6 +69,21 @@ impl CalendarGet for Server {
             )
             .await?;
         let is_owner = access_token.is_member(account_id);
+        let fetch_subscription = properties.iter().any(|property| {
+            matches!(
+                property,
+                CalendarProperty::SourceUrl
+                    | CalendarProperty::RefreshInterval
+                    | CalendarProperty::MyRights
+            )
+        });
+        let fetch_publication = is_owner
+            && properties.iter().any(|property| {
+                matches!(
+                    property,
+                    CalendarProperty::PublishMode | CalendarProperty::PublishUrl
+                )
+            });
         let calendar_ids = if is_owner {
             cache.document_ids(true).collect::<RoaringBitmap>()
         } else {
@@ -129,6 +147,20 @@ impl CalendarGet for Server {
             let calendar = _calendar
                 .unarchive::<Calendar>()
                 .caused_by(trc::location!())?;
+            let subscription = if fetch_subscription {
+                self.calendar_subscription(account_id, document_id)
+                    .await
+                    .caused_by(trc::location!())?
+            } else {
+                None
+            };
+            let publication = if fetch_publication {
+                self.calendar_publication(account_id, document_id)
+                    .await
+                    .caused_by(trc::location!())?
+            } else {
+                None
+            };
             let mut result = Map::with_capacity(properties.len());
             for property in &properties {
                 match property {
@@ -245,10 +277,47 @@ impl CalendarGet for Server {
                             ),
                         );
                     }
+                    CalendarProperty::SourceUrl => {
+                        result.insert_unchecked(
+                            CalendarProperty::SourceUrl,
+                            subscription.as_ref().map(|s| s.url.clone()),
+                        );
+                    }
+                    CalendarProperty::RefreshInterval => {
+                        result.insert_unchecked(
+                            CalendarProperty::RefreshInterval,
+                            subscription
+                                .as_ref()
+                                .map(|s| Value::Number(s.refresh_interval.into()))
+                                .unwrap_or(Value::Null),
+                        );
+                    }
+                    CalendarProperty::PublishMode => {
+                        result.insert_unchecked(
+                            CalendarProperty::PublishMode,
+                            publication.as_ref().map(|p| p.mode.as_str().to_string()),
+                        );
+                    }
+                    CalendarProperty::PublishUrl => {
+                        result.insert_unchecked(
+                            CalendarProperty::PublishUrl,
+                            publication.as_ref().map(|p| {
+                                p.url(&self.core.network.http.url_https, account_id, document_id)
+                            }),
+                        );
+                    }
                     CalendarProperty::MyRights => {
                         result.insert_unchecked(
                             CalendarProperty::MyRights,
-                            if access_token.is_shared(account_id) {
+                            if subscription.is_some() {
+                                let mut acls = if access_token.is_shared(account_id) {
+                                    calendar.acls.effective_acl(access_token)
+                                } else {
+                                    Bitmap::all()
+                                };
+                                acls.remove_many(SUBSCRIPTION_READ_ONLY_ACLS);
+                                JmapRights::rights::<calendar::Calendar>(acls)
+                            } else if access_token.is_shared(account_id) {
                                 JmapRights::rights::<calendar::Calendar>(
                                     calendar.acls.effective_acl(access_token),
                                 )
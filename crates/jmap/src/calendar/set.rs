@@ -7,7 +7,7 @@
 use crate::api::acl::{JmapAcl, JmapRights};
 use crate::changes::state::JmapCacheState;
 use calcard::jscalendar::{JSCalendarAlertAction, JSCalendarRelativeTo, JSCalendarType};
-use common::{Server, auth::AccessToken, sharing::EffectiveAcl};
+use common::{Server, auth::AccessToken, network::is_global_ip, sharing::EffectiveAcl};
 use groupware::{
     DestroyArchive,
     cache::GroupwareCache,
@@ -15,6 +15,11 @@ use groupware::{
         ALERT_EMAIL, ALERT_RELATIVE_TO_END, ALERT_WITH_TIME, CALENDAR_AVAILABILITY_ALL,
         CALENDAR_AVAILABILITY_ATTENDING, CALENDAR_AVAILABILITY_NONE, CALENDAR_INVISIBLE,
         CALENDAR_SUBSCRIBED, Calendar, CalendarEvent, CalendarPreferences, DefaultAlert, Timezone,
+        publish::{CalendarPublication, CalendarPublish, CalendarPublishMode},
+        subscription::{
+            CalendarSubscription, CalendarSubscriptionFetch, DEFAULT_REFRESH_INTERVAL,
+            MIN_REFRESH_INTERVAL,
+        },
     },
 };
 use http_proto::HttpSessionData;
@@ -27,16 +32,18 @@ use jmap_proto::{
 };
 use jmap_tools::{JsonPointerItem, Key, Map, Value};
 use rand::{RngExt, distr::Alphanumeric};
+use reqwest::Url;
+use std::net::IpAddr;
 use store::{
     SerializeInfallible, ValueKey,
     ahash::AHashSet,
-    write::{AlignedBytes, Archive, BatchBuilder, ValueClass},
+    write::{AlignedBytes, Archive, BatchBuilder, ValueClass, now},
 };
 use trc::AddContext;
 use types::{
     acl::Acl,
     collection::{Collection, SyncCollection},
-    field::PrincipalField,
+    field::{CalendarField, PrincipalField},
     id::Id,
 };
 
@@ -97,7 +104,16 @@ impl CalendarSet for Server {
             };
 
             // Process changes
-            if let Err(err) = update_calendar(None, object, &mut calendar, access_token) {
+            let mut subscription = None;
+            let mut publication = None;
+            if let Err(err) = update_calendar(
+                None,
+                object,
+                &mut calendar,
+                &mut subscription,
+                &mut publication,
+                access_token,
+            ) {
                 response.not_created.append(id, err);
                 continue 'create;
             }
@@ -128,6 +144,16 @@ impl CalendarSet for Server {
                     &mut batch,
                 )
                 .caused_by(trc::location!())?;
+            if let Some(subscription) = subscription {
+                batch.with_document(document_id);
+                subscription
+                    .write(&mut batch, account_id, document_id, now())
+                    .caused_by(trc::location!())?;
+            }
+            if let Some(publication) = publication {
+                batch.with_document(document_id);
+                publication.write(&mut batch).caused_by(trc::location!())?;
+            }
 
             if let Some(MaybeIdReference::Reference(id_ref)) =
                 &request.arguments.on_success_set_is_default
@@ -176,16 +202,34 @@ impl CalendarSet for Server {
             let mut new_calendar = calendar
                 .deserialize::<Calendar>()
                 .caused_by(trc::location!())?;
+            let current_subscription = self
+                .calendar_subscription(account_id, document_id)
+                .await
+                .caused_by(trc::location!())?;
+            let mut subscription = current_subscription.clone();
+            let current_publication = self
+                .calendar_publication(account_id, document_id)
+                .await
+                .caused_by(trc::location!())?;
+            let mut publication = current_publication.clone();
 
             // Apply changes
-            let has_acl_changes =
-                match update_calendar(Some(id), object, &mut new_calendar, access_token) {
-                    Ok(has_acl_changes_) => has_acl_changes_,
-                    Err(err) => {
-                        response.not_updated.append(id, err);
-                        continue 'update;
-                    }
-                };
+            let has_acl_changes = match update_calendar(
+                Some(id),
+                object,
+                &mut new_calendar,
+                &mut subscription,
+                &mut publication,
+                access_token,
+            ) {
+                Ok(has_acl_changes_) => has_acl_changes_,
+                Err(err) => {
+                    response.not_updated.append(id, err);
+                    continue 'update;
+                }
+            };
+            let has_subscription_changes = subscription != current_subscription;
+            let has_publication_changes = publication != current_publication;
 
             // Validate ACL
             if is_shared {
@@ -197,6 +241,21 @@ impl CalendarSet for Server {
                             .with_description("You are not allowed to modify this calendar."),
                     );
                     continue 'update;
+                } else if has_subscription_changes {
+                    response.not_updated.append(
+                        id,
+                        SetError::forbidden().with_description(
+                            "You are not allowed to change the subscription of this calendar.",
+                        ),
+                    );
+                    continue 'update;
+                } else if has_publication_changes {
+                    response.not_updated.append(
+                        id,
+                        SetError::forbidden()
+                            .with_description("You are not allowed to publish this calendar."),
+                    );
+                    continue 'update;
                 }
             }
             if has_acl_changes {
@@ -219,6 +278,30 @@ impl CalendarSet for Server {
                     &mut batch,
                 )
                 .caused_by(trc::location!())?;
+            if has_subscription_changes {
+                batch.with_document(document_id);
+                if let Some(subscription) = subscription {
+                    // Refresh right away when the source URL changed
+                    let refresh_at = if subscription.last_refresh != 0 {
+                        subscription.next_refresh()
+                    } else {
+                        now()
+                    };
+                    subscription
+                        .write(&mut batch, account_id, document_id, refresh_at)
+                        .caused_by(trc::location!())?;
+                } else {
+                    batch.clear(CalendarField::Subscription);
+                }
+            }
+            if has_publication_changes {
+                batch.with_document(document_id);
+                if let Some(publication) = publication {
+                    publication.write(&mut batch).caused_by(trc::location!())?;
+                } else {
+                    batch.clear(CalendarField::Publication);
+                }
+            }
             response.updated.append(id, None);
         }
 
@@ -414,9 +497,12 @@ fn update_calendar(
     expected_id: Option<Id>,
     updates: Value<'_, CalendarProperty, CalendarValue>,
     calendar: &mut Calendar,
+    subscription: &mut Option<CalendarSubscription>,
+    publication: &mut Option<CalendarPublication>,
     access_token: &AccessToken,
 ) -> Result<bool, SetError<CalendarProperty>> {
     let mut has_acl_changes = false;
+    let mut refresh_interval = None;
 
     for (property, value) in updates.into_expanded_object() {
         let Key::Property(property) = property else {
@@ -505,6 +591,45 @@ fn update_calendar(
                     }
                 }
             }
+            (CalendarProperty::SourceUrl, Value::Str(value)) => {
+                let url = validate_subscription_url(value.as_ref()).map_err(|description| {
+                    SetError::invalid_properties()
+                        .with_property(CalendarProperty::SourceUrl)
+                        .with_description(description)
+                })?;
+                match subscription {
+                    Some(current) if current.url == url => {}
+                    Some(current) => {
+                        *current = CalendarSubscription::new(url, current.refresh_interval);
+                    }
+                    None => {
+                        *subscription =
+                            Some(CalendarSubscription::new(url, DEFAULT_REFRESH_INTERVAL));
+                    }
+                }
+            }
+            (CalendarProperty::SourceUrl, Value::Null) => {
+                *subscription = None;
+            }
+            (CalendarProperty::PublishMode, Value::Str(value)) => {
+                let mode = CalendarPublishMode::parse(value.as_ref()).ok_or_else(|| {
+                    SetError::invalid_properties()
+                        .with_property(CalendarProperty::PublishMode)
+                        .with_description("Invalid publish mode.")
+                })?;
+                // Changing the mode keeps the existing URL
+                if let Some(publication) = publication {
+                    publication.mode = mode;
+                } else {
+                    *publication = Some(CalendarPublication::new(mode));
+                }
+            }
+            (CalendarProperty::PublishMode, Value::Null) => {
+                *publication = None;
+            }
+            (CalendarProperty::RefreshInterval, Value::Number(value)) => {
+                refresh_interval = Some(value.cast_to_u64());
+            }
             (CalendarProperty::ShareWith, value) => {
                 calendar.acls = JmapRights::acl_set::<calendar::Calendar>(value)?;
                 has_acl_changes = true;
@@ -573,6 +698,17 @@ fn update_calendar(
         }
     }
 
+    // Validate refresh interval
+    if let Some(refresh_interval) = refresh_interval {
+        if let Some(subscription) = subscription {
+            subscription.refresh_interval = refresh_interval.max(MIN_REFRESH_INTERVAL);
+        } else {
+            return Err(SetError::invalid_properties()
+                .with_property(CalendarProperty::RefreshInterval)
+                .with_description("A refresh interval requires a source URL."));
+        }
+    }
+
     // Validate name
     if calendar.preferences(access_token).name.is_empty() {
         return Err(SetError::invalid_properties()
@@ -583,6 +719,47 @@ fn update_calendar(
     Ok(has_acl_changes)
 }
 
+fn validate_subscription_url(url: &str) -> Result<String, &'static str> {
+    // webcal:// links are fetched over https
+    let url = Url::parse(
+        &url.strip_prefix("webcal://")
+            .or_else(|| url.strip_prefix("webcals://"))
+            .map(|url| format!("https://{url}"))
+            .unwrap_or_else(|| url.to_string()),
+    )
+    .map_err(|_| "Invalid subscription URL.")?;
+
+    if url.scheme() != "https" {
+        return Err("Subscription URLs must use the https or webcal scheme.");
+    }
+
+    if !url.username().is_empty() || url.password().is_some() {
+        return Err("Subscription URLs must not contain credentials.");
+    }
+
+    let Some(host) = url.host_str() else {
+        return Err("Subscription URLs must contain a host.");
+    };
+
+    let host = host
+        .strip_prefix('[')
+        .and_then(|host| host.strip_suffix(']'))
+        .unwrap_or(host);
+
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        #[cfg(feature = "test_mode")]
+        if ip.is_loopback() {
+            return Ok(url.to_string());
+        }
+
+        if !is_global_ip(&ip) {
+            return Err("Subscription URLs must not point to a local or reserved IP address.");
+        }
+    }
+
+    Ok(url.to_string())
+}
+
 fn value_to_default_alert(
     id: String,
     value: Map<'_, CalendarProperty, CalendarValue>,
@@ -5,7 +5,7 @@
  */
 
 use crate::{
-    calendar_event::{CalendarSyntheticId, set::CalendarEventSet},
+    calendar_event::{CalendarSyntheticId, exclude_subscribed_calendars, set::CalendarEventSet},
     changes::state::JmapCacheState,
 };
 use calcard::jscalendar::JSCalendarProperty;
@@ -103,6 +103,9 @@ impl JmapCalendarEventCopy for Server {
         } else {
             None
         };
+        let [can_add_calendars, _, _] =
+            exclude_subscribed_calendars(self, account_id, &cache, [can_add_calendars, None, None])
+                .await?;
         let on_success_delete = request.on_success_destroy_original.unwrap_or(false);
         let mut destroy_ids = Vec::new();
 
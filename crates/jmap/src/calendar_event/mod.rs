@@ -5,8 +5,10 @@
  */
 
 use calcard::jscalendar::JSCalendarProperty;
-use common::Server;
+use common::{DavResources, Server};
+use groupware::calendar::subscription::CalendarSubscriptionFetch;
 use jmap_proto::error::set::SetError;
+use store::roaring::RoaringBitmap;
 use trc::AddContext;
 use types::{collection::Collection, field::CalendarEventField, id::Id};
 
@@ -82,3 +84,28 @@ pub(super) async fn assert_is_unique_uid(
         Ok(Ok(()))
     }
 }
+
+// Events in subscribed calendars are only written by the refresh task, returns
+// the writable calendars or None if all calendars are writable.
+pub(super) async fn exclude_subscribed_calendars(
+    server: &Server,
+    account_id: u32,
+    cache: &DavResources,
+    calendar_ids: [Option<RoaringBitmap>; 3],
+) -> trc::Result<[Option<RoaringBitmap>; 3]> {
+    let subscribed_ids = server
+        .subscribed_calendar_ids(account_id, cache.document_ids(true))
+        .await
+        .caused_by(trc::location!())?;
+
+    if !subscribed_ids.is_empty() {
+        Ok(calendar_ids.map(|calendar_ids| {
+            Some(
+                calendar_ids.unwrap_or_else(|| cache.document_ids(true).collect())
+                    - &subscribed_ids,
+            )
+        }))
+    } else {
+        Ok(calendar_ids)
+    }
+}
@@ -4,7 +4,9 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use crate::calendar_event::{CalendarSyntheticId, assert_is_unique_uid};
+use crate::calendar_event::{
+    CalendarSyntheticId, assert_is_unique_uid, exclude_subscribed_calendars,
+};
 use crate::changes::state::JmapCacheState;
 use calcard::{
     common::{PartialDateTime, timezone::Tz},
@@ -123,6 +125,21 @@ impl CalendarEventSet for Server {
             } else {
                 (None, None, None)
             };
+        let [
+            can_add_calendars,
+            can_delete_calendars,
+            can_modify_calendars,
+        ] = exclude_subscribed_calendars(
+            self,
+            account_id,
+            &cache,
+            [
+                can_add_calendars,
+                can_delete_calendars,
+                can_modify_calendars,
+            ],
+        )
+        .await?;
 
         // Process creates
         let mut batch = BatchBuilder::new();
@@ -109,6 +109,11 @@ impl ChangesLookup for Server {
 
                 (SyncCollection::ShareNotification, false)
             }
+            MethodObject::SieveScript => {
+                access_token.assert_is_member(request.account_id)?;
+
+                (SyncCollection::SieveScript, false)
+            }
             _ => {
                 return Err(trc::JmapEvent::CannotCalculateChanges.into_err());
             }
@@ -404,13 +409,17 @@ impl IntermediateChangesResponse {
             MethodObject::ShareNotification => {
                 ChangesResponseMethod::ShareNotification(transmute_response(self.response))
             }
+            MethodObject::SieveScript => {
+                ChangesResponseMethod::Sieve(transmute_response(self.response))
+            }
             MethodObject::ParticipantIdentity
             | MethodObject::Core
             | MethodObject::Blob
             | MethodObject::PushSubscription
+            | MethodObject::Webhook
+            | MethodObject::Recipient
             | MethodObject::SearchSnippet
             | MethodObject::VacationResponse
-            | MethodObject::SieveScript
             | MethodObject::Principal
             | MethodObject::Quota
             | MethodObject::Registry(_) => unreachable!(),
@@ -0,0 +1,125 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use common::{Server, auth::AccessToken};
+use groupware::{
+    cache::GroupwareCache,
+    contact::{ContactCard, duplicate::DuplicateFinder},
+};
+use jmap_proto::{
+    method::find_duplicates::{
+        DuplicateCluster, DuplicateMatch, FindDuplicatesRequest, FindDuplicatesResponse,
+    },
+    request::MaybeInvalid,
+};
+use store::{
+    ValueKey,
+    roaring::RoaringBitmap,
+    search::ContactSearchField,
+    write::{AlignedBytes, Archive},
+};
+use trc::AddContext;
+use types::{
+    acl::Acl,
+    collection::{Collection, SyncCollection},
+    id::Id,
+};
+
+pub trait ContactCardFindDuplicates: Sync + Send {
+    fn contact_card_find_duplicates(
+        &self,
+        request: FindDuplicatesRequest,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<FindDuplicatesResponse>> + Send;
+}
+
+impl ContactCardFindDuplicates for Server {
+    async fn contact_card_find_duplicates(
+        &self,
+        request: FindDuplicatesRequest,
+        access_token: &AccessToken,
+    ) -> trc::Result<FindDuplicatesResponse> {
+        let account_id = request.account_id.document_id();
+        let cache = self
+            .fetch_dav_resources(
+                access_token.account_id(),
+                account_id,
+                SyncCollection::AddressBook,
+            )
+            .await?;
+
+        // Obtain the cards to compare
+        let mut document_ids = if access_token.is_shared(account_id) {
+            cache.shared_items(access_token, [Acl::ReadItems], true)
+        } else {
+            cache.document_ids(false).collect::<RoaringBitmap>()
+        };
+        match request.address_book_id {
+            Some(MaybeInvalid::Value(id)) if cache.has_container_id(&id.document_id()) => {
+                document_ids &= cache
+                    .children_ids(id.document_id())
+                    .collect::<RoaringBitmap>();
+            }
+            Some(_) => {
+                return Err(trc::JmapEvent::InvalidArguments
+                    .into_err()
+                    .details("addressBookId does not exist."));
+            }
+            None => {}
+        }
+
+        let mut finder = DuplicateFinder::default();
+        for document_id in document_ids {
+            let Some(archive) = self
+                .store()
+                .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                    account_id,
+                    Collection::ContactCard,
+                    document_id,
+                ))
+                .await
+                .caused_by(trc::location!())?
+            else {
+                continue;
+            };
+
+            finder.add(
+                document_id,
+                archive
+                    .unarchive::<ContactCard>()
+                    .caused_by(trc::location!())?,
+            );
+        }
+
+        let limit = request
+            .limit
+            .filter(|limit| *limit > 0)
+            .unwrap_or(usize::MAX)
+            .min(self.core.jmap.query_max_results);
+
+        Ok(FindDuplicatesResponse {
+            account_id: request.account_id,
+            clusters: finder
+                .clusters()
+                .into_iter()
+                .take(limit)
+                .map(|cluster| DuplicateCluster {
+                    ids: cluster.document_ids.into_iter().map(Id::from).collect(),
+                    matched_on: cluster
+                        .matched_on
+                        .into_iter()
+                        .filter_map(|field| match field {
+                            ContactSearchField::Email => Some(DuplicateMatch::Email),
+                            ContactSearchField::Phone => Some(DuplicateMatch::Phone),
+                            ContactSearchField::Name => Some(DuplicateMatch::Name),
+                            _ => None,
+                        })
+                        .collect(),
+                })
+                .collect(),
+        })
+    }
+}
@@ -0,0 +1,343 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::changes::state::JmapCacheState;
+use common::{Server, auth::AccessToken};
+use groupware::{DestroyArchive, cache::GroupwareCache, contact::ContactCard};
+use jmap_proto::{
+    method::merge::{MergeContactCardsRequest, MergeContactCardsResponse},
+    request::MaybeInvalid,
+    types::state::State,
+};
+use store::{
+    ValueKey,
+    ahash::AHashSet,
+    roaring::RoaringBitmap,
+    search::{ContactSearchField, SearchFilter, SearchQuery},
+    write::{AlignedBytes, Archive, BatchBuilder, SearchIndex},
+};
+use trc::AddContext;
+use types::{
+    acl::Acl,
+    collection::{Collection, SyncCollection, VanishedCollection},
+    id::Id,
+};
+
+pub trait ContactCardMerge: Sync + Send {
+    fn contact_card_merge(
+        &self,
+        request: MergeContactCardsRequest,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<MergeContactCardsResponse>> + Send;
+}
+
+impl ContactCardMerge for Server {
+    async fn contact_card_merge(
+        &self,
+        request: MergeContactCardsRequest,
+        access_token: &AccessToken,
+    ) -> trc::Result<MergeContactCardsResponse> {
+        let account_id = request.account_id.document_id();
+        let cache = self
+            .fetch_dav_resources(
+                access_token.account_id(),
+                account_id,
+                SyncCollection::AddressBook,
+            )
+            .await?;
+        let old_state = cache.assert_state(false, &request.if_in_state)?;
+
+        // Validate ids
+        let MaybeInvalid::Value(target_id) = request.target_id else {
+            return Err(trc::JmapEvent::InvalidArguments
+                .into_err()
+                .details("Invalid targetId."));
+        };
+        if request.source_ids.is_empty() {
+            return Err(trc::JmapEvent::InvalidArguments
+                .into_err()
+                .details("sourceIds cannot be empty."));
+        } else if request.source_ids.len() > self.core.jmap.set_max_objects {
+            return Err(trc::JmapEvent::RequestTooLarge.into_err());
+        }
+        let mut source_ids = Vec::with_capacity(request.source_ids.len());
+        for source_id in request.source_ids {
+            match source_id {
+                MaybeInvalid::Value(id) if id != target_id && !source_ids.contains(&id) => {
+                    source_ids.push(id);
+                }
+                _ => {
+                    return Err(trc::JmapEvent::InvalidArguments
+                        .into_err()
+                        .details("sourceIds must be distinct valid ids other than targetId."));
+                }
+            }
+        }
+        for id in std::iter::once(&target_id).chain(source_ids.iter()) {
+            if !cache.has_item_id(&id.document_id()) {
+                return Err(trc::JmapEvent::InvalidArguments
+                    .into_err()
+                    .details(format!("Contact card {id} does not exist.")));
+            }
+        }
+
+        // Obtain addressBookIds
+        let (can_add_address_books, can_delete_address_books, can_modify_address_books) =
+            if access_token.is_shared(account_id) {
+                (
+                    cache
+                        .shared_containers(access_token, [Acl::AddItems], true)
+                        .into(),
+                    cache
+                        .shared_containers(access_token, [Acl::RemoveItems], true)
+                        .into(),
+                    cache
+                        .shared_containers(access_token, [Acl::ModifyItems], true)
+                        .into(),
+                )
+            } else {
+                (None, None, None)
+            };
+
+        // Obtain target card
+        let target_document_id = target_id.document_id();
+        let target_ = self
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                account_id,
+                Collection::ContactCard,
+                target_document_id,
+            ))
+            .await
+            .caused_by(trc::location!())?
+            .ok_or_else(|| {
+                trc::JmapEvent::InvalidArguments
+                    .into_err()
+                    .details(format!("Contact card {target_id} does not exist."))
+            })?;
+        let target = target_
+            .to_unarchived::<ContactCard>()
+            .caused_by(trc::location!())?;
+        let mut new_target = target
+            .deserialize::<ContactCard>()
+            .caused_by(trc::location!())?;
+        assert_address_books(
+            &can_modify_address_books,
+            new_target.names.iter().map(|name| name.parent_id),
+            "modify",
+        )?;
+
+        // Merge source cards into the target
+        let mut batch = BatchBuilder::new();
+        let mut destroyed = Vec::with_capacity(source_ids.len());
+        let mut source_uids = Vec::with_capacity(source_ids.len());
+        for source_id in source_ids {
+            let document_id = source_id.document_id();
+            let source_ = self
+                .store()
+                .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                    account_id,
+                    Collection::ContactCard,
+                    document_id,
+                ))
+                .await
+                .caused_by(trc::location!())?
+                .ok_or_else(|| {
+                    trc::JmapEvent::InvalidArguments
+                        .into_err()
+                        .details(format!("Contact card {source_id} does not exist."))
+                })?;
+            let source = source_
+                .to_unarchived::<ContactCard>()
+                .caused_by(trc::location!())?;
+            let source_card = source
+                .deserialize::<ContactCard>()
+                .caused_by(trc::location!())?;
+
+            // Validate ACLs
+            assert_address_books(
+                &can_delete_address_books,
+                source_card.names.iter().map(|name| name.parent_id),
+                "remove contacts from",
+            )?;
+            let adopted_ids = source_card
+                .names
+                .iter()
+                .filter(|name| {
+                    new_target
+                        .names
+                        .iter()
+                        .all(|n| n.parent_id != name.parent_id)
+                })
+                .map(|name| name.parent_id)
+                .collect::<AHashSet<_>>();
+            assert_address_books(
+                &can_add_address_books,
+                adopted_ids.iter().copied(),
+                "add contacts to",
+            )?;
+
+            if let Some(uid) = source_card.card.uid() {
+                source_uids.push(uid.to_string());
+            }
+            new_target.merge(source_card);
+
+            // Delete source, paths taken over by the target do not vanish
+            let vanished_paths = source
+                .inner
+                .names
+                .iter()
+                .map(|name| name.parent_id.to_native())
+                .filter(|parent_id| !adopted_ids.contains(parent_id))
+                .filter_map(|parent_id| {
+                    cache.format_resource_path_by_parent(document_id, parent_id)
+                })
+                .collect::<Vec<_>>();
+            DestroyArchive(source)
+                .delete_all(
+                    access_token.account_tenant_ids(),
+                    account_id,
+                    document_id,
+                    &mut batch,
+                )
+                .caused_by(trc::location!())?;
+            for path in vanished_paths {
+                batch.log_vanished_item(VanishedCollection::AddressBook, path);
+            }
+            destroyed.push(source_id);
+        }
+
+        // Check size
+        if new_target.size as usize > self.core.groupware.max_vcard_size {
+            return Err(trc::JmapEvent::InvalidArguments.into_err().details(format!(
+                "Merged contact size {} exceeds the maximum allowed size of {} bytes.",
+                new_target.size, self.core.groupware.max_vcard_size
+            )));
+        }
+        let target_uid = new_target.card.uid().map(|uid| uid.to_string());
+        new_target
+            .update(
+                access_token.account_tenant_ids(),
+                target,
+                account_id,
+                target_document_id,
+                &mut batch,
+            )
+            .caused_by(trc::location!())?;
+        let mut updated = vec![target_id];
+
+        // Point groups referencing the sources to the target
+        source_uids.retain(|uid| target_uid.as_deref() != Some(uid.as_str()));
+        if let Some(target_uid) = target_uid.filter(|_| !source_uids.is_empty()) {
+            let mut filters = Vec::with_capacity(source_uids.len() + 2);
+            filters.push(SearchFilter::Or);
+            for uid in &source_uids {
+                filters.push(SearchFilter::has_keyword(
+                    ContactSearchField::Member,
+                    uid.strip_prefix("urn:uuid:").unwrap_or(uid),
+                ));
+            }
+            filters.push(SearchFilter::End);
+
+            let mut mask = cache.document_ids(false).collect::<RoaringBitmap>();
+            mask.remove(target_document_id);
+            for id in &destroyed {
+                mask.remove(id.document_id());
+            }
+            let old_uids = source_uids
+                .iter()
+                .map(|uid| uid.as_str())
+                .collect::<Vec<_>>();
+
+            for document_id in self
+                .search_store()
+                .query_account(
+                    SearchQuery::new(SearchIndex::Contacts)
+                        .with_filters(filters)
+                        .with_account_id(account_id)
+                        .with_mask(mask),
+                )
+                .await?
+            {
+                let Some(group_) = self
+                    .store()
+                    .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                        account_id,
+                        Collection::ContactCard,
+                        document_id,
+                    ))
+                    .await
+                    .caused_by(trc::location!())?
+                else {
+                    continue;
+                };
+                let group = group_
+                    .to_unarchived::<ContactCard>()
+                    .caused_by(trc::location!())?;
+                if can_modify_address_books.as_ref().is_some_and(|ids| {
+                    group
+                        .inner
+                        .names
+                        .iter()
+                        .any(|name| !ids.contains(name.parent_id.to_native()))
+                }) {
+                    continue;
+                }
+                let mut new_group = group
+                    .deserialize::<ContactCard>()
+                    .caused_by(trc::location!())?;
+                if new_group.replace_members(&old_uids, &target_uid) {
+                    new_group
+                        .update(
+                            access_token.account_tenant_ids(),
+                            group,
+                            account_id,
+                            document_id,
+                            &mut batch,
+                        )
+                        .caused_by(trc::location!())?;
+                    updated.push(Id::from(document_id));
+                }
+            }
+        }
+
+        // Write changes
+        let change_id = self
+            .commit_batch(batch)
+            .await
+            .and_then(|ids| ids.last_change_id(account_id))
+            .caused_by(trc::location!())?;
+
+        self.notify_task_queue();
+
+        Ok(MergeContactCardsResponse {
+            account_id: request.account_id,
+            old_state,
+            new_state: State::Exact(change_id),
+            updated,
+            destroyed,
+        })
+    }
+}
+
+fn assert_address_books(
+    allowed_ids: &Option<RoaringBitmap>,
+    address_book_ids: impl IntoIterator<Item = u32>,
+    action: &str,
+) -> trc::Result<()> {
+    if let Some(allowed_ids) = allowed_ids {
+        for address_book_id in address_book_ids {
+            if !allowed_ids.contains(address_book_id) {
+                return Err(trc::JmapEvent::Forbidden.into_err().details(format!(
+                    "You are not allowed to {action} address book {}.",
+                    Id::from(address_book_id)
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -11,7 +11,9 @@ use trc::AddContext;
 use types::{collection::Collection, field::ContactField, id::Id};
 
 pub mod copy;
+pub mod duplicates;
 pub mod get;
+pub mod merge;
 pub mod parse;
 pub mod query;
 pub mod set;
@@ -15,6 +15,7 @@ use email::{
             ArchivedMetadataPartType, MESSAGE_HAS_ATTACHMENT, MESSAGE_RECEIVED_MASK,
             MessageMetadata, MetadataHeaderName, PART_ENCODING_PROBLEM,
         },
+        snooze::EmailSnoozeFetch,
     },
 };
 use jmap_proto::{
@@ -288,6 +289,46 @@ impl EmailGet for Server {
                             (metadata.rcvd_attach.to_native() & MESSAGE_HAS_ATTACHMENT) != 0,
                         );
                     }
+                    EmailProperty::Snoozed => {
+                        let snoozed = if let Some(snooze) = self
+                            .email_snooze(account_id, id.document_id())
+                            .await
+                            .caused_by(trc::location!())?
+                        {
+                            let mut obj = Map::with_capacity(3);
+                            obj.insert_unchecked(
+                                EmailProperty::Until,
+                                EmailValue::Date(UTCDate::from_timestamp(snooze.until as i64)),
+                            );
+                            if let Some(mailbox_id) = snooze.move_to_mailbox_id {
+                                obj.insert_unchecked(
+                                    EmailProperty::MoveToMailboxId,
+                                    Id::from(mailbox_id),
+                                );
+                            }
+                            if snooze.has_keyword_changes() {
+                                let mut keywords = Map::with_capacity(
+                                    snooze.add_keywords.len() + snooze.remove_keywords.len(),
+                                );
+                                for keyword in snooze.add_keywords {
+                                    keywords
+                                        .insert_unchecked(EmailProperty::Keyword(keyword), true);
+                                }
+                                for keyword in snooze.remove_keywords {
+                                    keywords
+                                        .insert_unchecked(EmailProperty::Keyword(keyword), false);
+                                }
+                                obj.insert_unchecked(
+                                    EmailProperty::SetKeywords,
+                                    Value::Object(keywords),
+                                );
+                            }
+                            Value::Object(obj)
+                        } else {
+                            Value::Null
+                        };
+                        email.insert_unchecked(EmailProperty::Snoozed, snoozed);
+                    }
                     EmailProperty::Subject => {
                         email.insert_unchecked(
                             EmailProperty::Subject,
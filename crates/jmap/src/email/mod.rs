@@ -4,12 +4,13 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use email::message::ingest::IngestedEmail;
+use email::message::{ingest::IngestedEmail, snooze::EmailSnooze};
 use jmap_proto::{
     error::set::SetError,
     object::email::{EmailProperty, EmailValue},
 };
 use jmap_tools::{JsonPointer, JsonPointerItem, Key, Map, Value};
+use store::write::now;
 use types::{id::Id, keyword::Keyword};
 
 pub mod copy;
@@ -73,3 +74,67 @@ pub(crate) fn handle_email_patch<'x>(
             .with_description("Invalid patch value".to_string()),
     )
 }
+
+pub(crate) fn parse_email_snooze(
+    snoozed: Map<'_, EmailProperty, EmailValue>,
+) -> Result<EmailSnooze, SetError<EmailProperty>> {
+    let mut snooze = EmailSnooze::new(0);
+    let mut add_keywords = Vec::new();
+    let mut remove_keywords = Vec::new();
+    let mut has_until = false;
+
+    for (property, value) in snoozed.into_vec() {
+        match (property, value) {
+            (Key::Property(EmailProperty::Until), Value::Element(EmailValue::Date(until))) => {
+                snooze.until = until.timestamp().max(0) as u64;
+                has_until = true;
+            }
+            (Key::Property(EmailProperty::MoveToMailboxId), Value::Element(EmailValue::Id(id))) => {
+                snooze.move_to_mailbox_id = Some(id.document_id());
+            }
+            (
+                Key::Property(EmailProperty::MoveToMailboxId | EmailProperty::SetKeywords),
+                Value::Null,
+            ) => {}
+            (Key::Property(EmailProperty::SetKeywords), Value::Object(keywords)) => {
+                for (keyword, value) in keywords.into_vec() {
+                    match (keyword, value) {
+                        (Key::Property(EmailProperty::Keyword(keyword)), Value::Bool(true)) => {
+                            add_keywords.push(keyword);
+                        }
+                        (
+                            Key::Property(EmailProperty::Keyword(keyword)),
+                            Value::Bool(false) | Value::Null,
+                        ) => {
+                            remove_keywords.push(keyword);
+                        }
+                        _ => {
+                            return Err(SetError::invalid_properties()
+                                .with_property(EmailProperty::Snoozed)
+                                .with_description("Invalid setKeywords value."));
+                        }
+                    }
+                }
+            }
+            _ => {
+                return Err(SetError::invalid_properties()
+                    .with_property(EmailProperty::Snoozed)
+                    .with_description("Invalid snoozed property."));
+            }
+        }
+    }
+
+    if has_until && snooze.until <= now() {
+        Err(SetError::invalid_properties()
+            .with_property(EmailProperty::Snoozed)
+            .with_description("The until date must be in the future."))
+    } else if has_until {
+        snooze.add_keywords = add_keywords.into_boxed_slice();
+        snooze.remove_keywords = remove_keywords.into_boxed_slice();
+        Ok(snooze)
+    } else {
+        Err(SetError::invalid_properties()
+            .with_property(EmailProperty::Snoozed)
+            .with_description("Missing until property."))
+    }
+}
@@ -7,7 +7,7 @@
 use crate::{
     blob::download::BlobDownload,
     changes::state::JmapCacheState,
-    email::{PatchResult, handle_email_patch, ingested_into_object},
+    email::{PatchResult, handle_email_patch, ingested_into_object, parse_email_snooze},
 };
 use common::{
     Server, auth::AccessToken, ipc::PushNotification, storage::index::ObjectIndexBuilder,
@@ -15,11 +15,12 @@ use common::{
 use email::message::headers::{BuildHeader, ValueToHeader};
 use email::{
     cache::{MessageCacheFetch, email::MessageCacheAccess, mailbox::MailboxCacheAccess},
-    mailbox::{JUNK_ID, TRASH_ID, UidMailbox},
+    mailbox::{INBOX_ID, JUNK_ID, TRASH_ID, UidMailbox},
     message::{
         delete::EmailDeletion,
         ingest::{EmailIngest, IngestEmail, IngestSource},
         metadata::MessageData,
+        snooze::EmailSnoozeFetch,
     },
 };
 use http_proto::HttpSessionData;
@@ -53,8 +54,10 @@ use trc::AddContext;
 use types::{
     acl::Acl,
     collection::{Collection, SyncCollection, VanishedCollection},
+    field::EmailField,
     id::Id,
     keyword::{ArchivedKeyword, Keyword},
+    special_use::SpecialUse,
     type_state::{DataType, StateChange},
 };
 
@@ -792,6 +795,7 @@ impl EmailSet for Server {
         let mut batch = BatchBuilder::new();
         let mut changed_mailboxes: AHashMap<u32, Vec<u32>> = AHashMap::new();
         let mut will_update = Vec::with_capacity(request.update.as_ref().map_or(0, |u| u.len()));
+        let mut has_snooze_tasks = false;
         'update: for (id, object) in request.unwrap_update() {
             let id = match id {
                 MaybeInvalid::Value(id) => id,
@@ -827,6 +831,7 @@ impl EmailSet for Server {
                 .to_unarchived::<MessageData>()
                 .caused_by(trc::location!())?;
             let mut new_data = data.inner.to_builder();
+            let mut snooze = None;
 
             for (property, mut value) in object.into_expanded_object() {
                 if let Err(err) = response.resolve_self_references(&mut value, 0, false) {
@@ -857,6 +862,20 @@ impl EmailSet for Server {
                                 .collect(),
                         );
                     }
+                    (Key::Property(EmailProperty::Snoozed), Value::Object(snoozed)) => {
+                        match parse_email_snooze(snoozed) {
+                            Ok(value) => {
+                                snooze = Some(Some(value));
+                            }
+                            Err(err) => {
+                                response.not_updated.append(id, err);
+                                continue 'update;
+                            }
+                        }
+                    }
+                    (Key::Property(EmailProperty::Snoozed), Value::Null) => {
+                        snooze = Some(None);
+                    }
                     (Key::Property(EmailProperty::Pointer(pointer)), value) => {
                         match handle_email_patch(&pointer, value) {
                             PatchResult::SetKeyword(keyword) => {
@@ -895,9 +914,73 @@ impl EmailSet for Server {
                 }
             }
 
+            // Process snooze
+            if let Some(snooze) = &snooze {
+                // Verify permissions on shared accounts
+                if can_modify_mailbox_ids.as_ref().is_some_and(|ids| {
+                    !new_data
+                        .mailboxes
+                        .iter()
+                        .any(|mb| ids.contains(mb.mailbox_id))
+                }) {
+                    response.not_updated.append(
+                        id,
+                        SetError::forbidden()
+                            .with_description("You are not allowed to snooze this message."),
+                    );
+                    continue 'update;
+                }
+
+                if let Some(snooze) = snooze {
+                    if let Some(mailbox_id) = snooze.move_to_mailbox_id
+                        && !cache.has_mailbox_id(&mailbox_id)
+                    {
+                        response.not_updated.append(
+                            id,
+                            SetError::invalid_properties()
+                                .with_property(EmailProperty::Snoozed)
+                                .with_description(format!(
+                                    "moveToMailboxId {} does not exist.",
+                                    Id::from(mailbox_id)
+                                )),
+                        );
+                        continue 'update;
+                    }
+
+                    // Move the message to the snoozed mailbox, unless the client
+                    // is explicitly changing the mailboxes
+                    if !new_data.has_mailbox_changes(data.inner)
+                        && let Some(snoozed) = cache.mailbox_by_role(&SpecialUse::Snoozed)
+                        && !data.inner.has_mailbox_id(snoozed.document_id)
+                    {
+                        new_data
+                            .set_mailboxes(vec![UidMailbox::new_unassigned(snoozed.document_id)]);
+                    }
+                } else if !new_data.has_mailbox_changes(data.inner)
+                    && let Some(snoozed) = cache.mailbox_by_role(&SpecialUse::Snoozed)
+                    && data.inner.has_mailbox_id(snoozed.document_id)
+                {
+                    // Unsnoozing moves the message out of the snoozed mailbox
+                    new_data.remove_mailbox(snoozed.document_id);
+                    if new_data.mailboxes.is_empty() {
+                        let mailbox_id = self
+                            .email_snooze(account_id, document_id)
+                            .await
+                            .caused_by(trc::location!())?
+                            .and_then(|snooze| snooze.move_to_mailbox_id)
+                            .filter(|mailbox_id| {
+                                *mailbox_id != snoozed.document_id
+                                    && cache.has_mailbox_id(mailbox_id)
+                            })
+                            .unwrap_or(INBOX_ID);
+                        new_data.add_mailbox(UidMailbox::new_unassigned(mailbox_id));
+                    }
+                }
+            }
+
             let has_keyword_changes = new_data.has_keyword_changes(data.inner);
             let has_mailbox_changes = new_data.has_mailbox_changes(data.inner);
-            if !has_keyword_changes && !has_mailbox_changes {
+            if !has_keyword_changes && !has_mailbox_changes && snooze.is_none() {
                 response.updated.append(id, None);
                 continue 'update;
             }
@@ -1073,6 +1156,19 @@ impl EmailSet for Server {
                 )
                 .caused_by(trc::location!())?;
 
+            match snooze {
+                Some(Some(snooze)) => {
+                    snooze
+                        .write(&mut batch, account_id, document_id)
+                        .caused_by(trc::location!())?;
+                    has_snooze_tasks = true;
+                }
+                Some(None) => {
+                    batch.clear(EmailField::Snoozed);
+                }
+                None => {}
+            }
+
             if let Some(train_spam) = train_spam {
                 self.add_account_spam_sample(
                     &mut batch,
@@ -1110,6 +1206,10 @@ impl EmailSet for Server {
                     for id in will_update {
                         response.updated.append(id, None);
                     }
+
+                    if has_snooze_tasks {
+                        self.notify_task_queue();
+                    }
                 }
                 Err(err) if err.is_assertion_failure() => {
                     for id in will_update {
@@ -6,13 +6,20 @@
 
 use crate::{api::acl::JmapRights, changes::state::JmapCacheState};
 use common::{Server, auth::AccessToken, sharing::EffectiveAcl};
-use groupware::{cache::GroupwareCache, file::FileNode};
+use groupware::{
+    cache::GroupwareCache,
+    file::{
+        FileNode,
+        share::{FileShare, FileShareLink, FileShareLinks},
+        version::{FileVersioning, FileVersions},
+    },
+};
 use jmap_proto::{
     method::get::{GetRequest, GetResponse},
     object::file_node::{self, FileNodeNodeType, FileNodeProperty, FileNodeValue},
     types::date::UTCDate,
 };
-use jmap_tools::{Map, Value};
+use jmap_tools::{Key, Map, Value};
 use store::{
     ValueKey,
     roaring::RoaringBitmap,
@@ -296,6 +303,103 @@ impl FileNodeGet for Server {
                         // TODO: needs serialization change (per-user subscription state); always true for now
                         result.insert_unchecked(FileNodeProperty::IsSubscribed, Value::Bool(true));
                     }
+                    FileNodeProperty::Versions => {
+                        let versions = if file_node.file.is_some()
+                            && let Some(versions_) = self
+                                .file_versions(account_id, document_id)
+                                .await
+                                .caused_by(trc::location!())?
+                        {
+                            versions_
+                                .unarchive::<FileVersions>()
+                                .caused_by(trc::location!())?
+                                .versions
+                                .iter()
+                                .rev()
+                                .map(|version| {
+                                    let mut map = Map::with_capacity(6);
+                                    map.insert_unchecked(
+                                        FileNodeProperty::Id,
+                                        Value::Str(version.id.to_string().into()),
+                                    );
+                                    map.insert_unchecked(
+                                        FileNodeProperty::BlobId,
+                                        Value::Element(FileNodeValue::BlobId(BlobId::new(
+                                            BlobHash::from(&version.blob_hash),
+                                            BlobClass::Linked {
+                                                account_id,
+                                                collection: Collection::FileNode.into(),
+                                                document_id,
+                                            },
+                                        ))),
+                                    );
+                                    map.insert_unchecked(
+                                        FileNodeProperty::Size,
+                                        Value::Number(version.size.to_native().into()),
+                                    );
+                                    map.insert_unchecked(
+                                        FileNodeProperty::Type,
+                                        Value::Str(
+                                            version
+                                                .media_type
+                                                .as_ref()
+                                                .map(|t| t.to_string())
+                                                .unwrap_or_else(|| {
+                                                    "application/octet-stream".to_string()
+                                                })
+                                                .into(),
+                                        ),
+                                    );
+                                    map.insert_unchecked(
+                                        FileNodeProperty::Modified,
+                                        Value::Element(FileNodeValue::Date(
+                                            UTCDate::from_timestamp(version.modified.to_native()),
+                                        )),
+                                    );
+                                    map.insert_unchecked(
+                                        Key::Borrowed("replaced"),
+                                        Value::Element(FileNodeValue::Date(
+                                            UTCDate::from_timestamp(version.replaced.to_native()),
+                                        )),
+                                    );
+                                    Value::Object(map)
+                                })
+                                .collect()
+                        } else {
+                            vec![]
+                        };
+                        result.insert_unchecked(FileNodeProperty::Versions, Value::Array(versions));
+                    }
+                    FileNodeProperty::ShareLinks => {
+                        let mut links = Map::new();
+                        if (access_token.is_member(account_id)
+                            || file_node
+                                .acls
+                                .effective_acl(access_token)
+                                .contains(Acl::Share))
+                            && let Some(links_) = self
+                                .file_share_links(account_id, document_id)
+                                .await
+                                .caused_by(trc::location!())?
+                        {
+                            for link in links_
+                                .deserialize::<FileShareLinks>()
+                                .caused_by(trc::location!())?
+                                .links
+                            {
+                                links.insert_unchecked(
+                                    Key::Owned(link.token.clone()),
+                                    share_link_to_value(
+                                        &self.core.network.http.url_https,
+                                        account_id,
+                                        document_id,
+                                        &link,
+                                    ),
+                                );
+                            }
+                        }
+                        result.insert_unchecked(FileNodeProperty::ShareLinks, Value::Object(links));
+                    }
                     property => {
                         result.insert_unchecked(property.clone(), Value::Null);
                     }
@@ -307,3 +411,42 @@ impl FileNodeGet for Server {
         Ok(response)
     }
 }
+
+pub(crate) fn share_link_to_value(
+    base_url: &str,
+    account_id: u32,
+    document_id: u32,
+    link: &FileShareLink,
+) -> Value<'static, FileNodeProperty, FileNodeValue> {
+    let mut map = Map::with_capacity(7);
+    map.insert_unchecked(
+        Key::Borrowed("url"),
+        Value::Str(link.url(base_url, account_id, document_id).into()),
+    );
+    map.insert_unchecked(
+        Key::Borrowed("hasPassword"),
+        Value::Bool(link.password_hash.is_some()),
+    );
+    map.insert_unchecked(
+        Key::Borrowed("expires"),
+        link.expires
+            .map(|expires| Value::Element(FileNodeValue::Date(UTCDate::from_timestamp(expires))))
+            .unwrap_or(Value::Null),
+    );
+    map.insert_unchecked(
+        Key::Borrowed("maxDownloads"),
+        link.max_downloads
+            .map(|max_downloads| Value::Number(max_downloads.into()))
+            .unwrap_or(Value::Null),
+    );
+    map.insert_unchecked(
+        Key::Borrowed("downloads"),
+        Value::Number(link.downloads.into()),
+    );
+    map.insert_unchecked(Key::Borrowed("fileDrop"), Value::Bool(link.file_drop));
+    map.insert_unchecked(
+        FileNodeProperty::Created,
+        Value::Element(FileNodeValue::Date(UTCDate::from_timestamp(link.created))),
+    );
+    Value::Object(map)
+}
@@ -8,9 +8,20 @@ use crate::{
     api::acl::{JmapAcl, JmapRights},
     blob::download::BlobDownload,
     changes::state::JmapCacheState,
+    file::get::share_link_to_value,
 };
 use common::{DavResourceMetadata, DavResources, Server, auth::AccessToken, sharing::EffectiveAcl};
-use groupware::{DestroyArchive, cache::GroupwareCache, file::FileNode};
+use directory::core::secret::hash_secret;
+use groupware::{
+    DestroyArchive,
+    cache::GroupwareCache,
+    file::{
+        FileNode,
+        share::{FileShare, FileShareLink, FileShareLinks},
+        validate_file_name,
+        version::{FileVersion, FileVersioning, FileVersions},
+    },
+};
 use http_proto::HttpSessionData;
 use jmap_proto::{
     error::set::SetError,
@@ -21,9 +32,10 @@ use jmap_proto::{
     },
     references::resolve::ResolveCreatedReference,
     request::MaybeInvalid,
-    types::state::State,
+    types::{date::UTCDate, state::State},
 };
-use jmap_tools::{JsonPointerItem, Key, Value};
+use jmap_tools::{JsonPointerItem, Key, Map, Value};
+use std::str::FromStr;
 use store::{
     ValueKey,
     ahash::{AHashMap, AHashSet},
@@ -37,13 +49,6 @@ use types::{
     id::Id,
 };
 
-const FORBIDDEN_NAME_CHARS: &str = "/<>:\"\\|?*";
-const FORBIDDEN_NODE_NAMES: &[&str] = &[
-    ".", "..", "CON", "PRN", "AUX", "NUL", "COM0", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6",
-    "COM7", "COM8", "COM9", "LPT0", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8",
-    "LPT9",
-];
-
 pub trait FileNodeSet: Sync + Send {
     fn file_node_set(
         &self,
@@ -352,7 +357,7 @@ impl FileNodeSet for Server {
                 .caused_by(trc::location!())?;
 
             // Apply changes
-            let (has_acl_changes, modified_set) =
+            let (has_acl_changes, modified_set, share_links) =
                 match update_file_node(Some(id), object, &mut new_file_node, false, &response) {
                     Ok(result) => {
                         let modified_set = result.modified_set;
@@ -383,9 +388,36 @@ impl FileNodeSet for Server {
                             }
 
                             file_details.blob_hash = blob_id.hash;
+                        } else if let Some(version_id) = result.restore_version {
+                            let version = if let Some(versions_) = self
+                                .file_versions(account_id, document_id)
+                                .await
+                                .caused_by(trc::location!())?
+                            {
+                                versions_
+                                    .deserialize::<FileVersions>()
+                                    .caused_by(trc::location!())?
+                                    .by_id(version_id)
+                                    .cloned()
+                            } else {
+                                None
+                            };
+                            let Some(version) = version else {
+                                response.not_updated.append(
+                                    id,
+                                    SetError::invalid_properties()
+                                        .with_property(FileNodeProperty::RestoreVersion)
+                                        .with_description("Version could not be found."),
+                                );
+                                continue 'update;
+                            };
+                            let file_details = new_file_node.file.get_or_insert_default();
+                            file_details.blob_hash = version.blob_hash;
+                            file_details.size = version.size;
+                            file_details.media_type = version.media_type;
                         }
 
-                        (result.has_acl_changes, modified_set)
+                        (result.has_acl_changes, modified_set, result.share_links)
                     }
                     Err(err) => {
                         response.not_updated.append(id, err);
@@ -501,6 +533,21 @@ impl FileNodeSet for Server {
                     continue 'update;
                 }
             }
+            if share_links.is_some()
+                && is_shared
+                && !file_node
+                    .inner
+                    .acls
+                    .effective_acl(access_token)
+                    .contains(Acl::Share)
+            {
+                response.not_updated.append(
+                    id,
+                    SetError::forbidden()
+                        .with_description("You are not allowed to share this file node."),
+                );
+                continue 'update;
+            }
             if has_acl_changes {
                 if let Err(err) = self.acl_validate(&new_file_node.acls).await {
                     response.not_updated.append(id, err.into());
@@ -522,11 +569,47 @@ impl FileNodeSet for Server {
                 .caused_by(trc::location!())?;
             }
 
+            // Build share links
+            let share_links = if let Some(update) = share_links {
+                let current = self
+                    .file_share_links(account_id, document_id)
+                    .await
+                    .caused_by(trc::location!())?;
+                let mut links = if let Some(current) = &current {
+                    current
+                        .deserialize::<FileShareLinks>()
+                        .caused_by(trc::location!())?
+                } else {
+                    FileShareLinks::default()
+                };
+                if let Err(err) = self
+                    .apply_share_links(&mut links, update, new_file_node.file.is_none())
+                    .await?
+                {
+                    response.not_updated.append(id, err);
+                    continue 'update;
+                }
+                Some((current, links))
+            } else {
+                None
+            };
+
             let final_name = new_file_node.name.clone();
             pending_names.insert(
                 pending_key(&new_file_node, case_insensitive),
                 Some(document_id),
             );
+            // Keep the previous contents when the file is overwritten
+            let file_version = match (
+                FileVersion::from_node(file_node.inner),
+                new_file_node.file.as_ref(),
+            ) {
+                (Some(previous), Some(file)) if previous.blob_hash != file.blob_hash => {
+                    Some((previous, file.blob_hash.clone()))
+                }
+                _ => None,
+            };
+
             // Update record. Bump modified to now() unless the client supplied a value.
             new_file_node
                 .update(
@@ -538,16 +621,60 @@ impl FileNodeSet for Server {
                     &mut batch,
                 )
                 .caused_by(trc::location!())?;
-            let updated_value = if renamed {
-                let mut map = jmap_tools::Map::with_capacity(1);
-                map.insert_unchecked(
+            if let Some((previous, blob_hash)) = file_version {
+                self.archive_file_version(
+                    account_id,
+                    document_id,
+                    previous,
+                    &blob_hash,
+                    access_token.account_tenant_ids(),
+                    &mut batch,
+                )
+                .await
+                .caused_by(trc::location!())?;
+            }
+            let mut updated_value = Map::with_capacity(2);
+            if renamed {
+                updated_value.insert_unchecked(
                     Key::Property(FileNodeProperty::Name),
                     Value::Str(std::borrow::Cow::Owned(final_name)),
                 );
-                Some(Value::Object(map))
-            } else {
-                None
-            };
+            }
+            if let Some((current, links)) = share_links {
+                let mut value = Map::with_capacity(links.links.len());
+                for link in &links.links {
+                    value.insert_unchecked(
+                        Key::Owned(link.token.clone()),
+                        share_link_to_value(
+                            &self.core.network.http.url_https,
+                            account_id,
+                            document_id,
+                            link,
+                        ),
+                    );
+                }
+                updated_value.insert_unchecked(
+                    Key::Property(FileNodeProperty::ShareLinks),
+                    Value::Object(value),
+                );
+
+                batch
+                    .with_account_id(account_id)
+                    .with_collection(Collection::FileNode)
+                    .with_document(document_id);
+                links
+                    .write(
+                        current
+                            .as_ref()
+                            .map(|current| current.to_unarchived::<FileShareLinks>())
+                            .transpose()
+                            .caused_by(trc::location!())?,
+                        &mut batch,
+                    )
+                    .caused_by(trc::location!())?;
+            }
+            let updated_value =
+                (!updated_value.as_vec().is_empty()).then_some(Value::Object(updated_value));
             response.updated.append(id, updated_value);
         }
 
@@ -650,6 +777,22 @@ pub(super) struct UpdateResult {
     pub(super) has_acl_changes: bool,
     pub(super) blob_id: Option<BlobId>,
     pub(super) modified_set: bool,
+    pub(super) restore_version: Option<u32>,
+    pub(super) share_links: Option<ShareLinksUpdate>,
+}
+
+#[derive(Default)]
+pub(super) struct ShareLinksUpdate {
+    replace: bool,
+    links: Vec<(String, Option<ShareLinkUpdate>)>,
+}
+
+#[derive(Default)]
+struct ShareLinkUpdate {
+    password: Option<Option<String>>,
+    expires: Option<Option<i64>>,
+    max_downloads: Option<Option<u32>>,
+    file_drop: Option<bool>,
 }
 
 pub(super) struct NoResolver;
@@ -673,6 +816,8 @@ pub(super) fn update_file_node<R: ResolveCreatedReference<FileNodeProperty, File
     let mut pending_type: Option<Option<String>> = None;
     let mut pending_executable: Option<bool> = None;
     let mut modified_set = false;
+    let mut restore_version = None;
+    let mut share_links: Option<ShareLinksUpdate> = None;
 
     for (property, mut value) in updates.into_expanded_object() {
         let Key::Property(property) = property else {
@@ -685,22 +830,11 @@ pub(super) fn update_file_node<R: ResolveCreatedReference<FileNodeProperty, File
 
         match (property, value) {
             (FileNodeProperty::Name, Value::Str(value)) => {
-                if !(1..=255).contains(&value.len()) {
-                    return Err(SetError::invalid_properties()
-                        .with_property(FileNodeProperty::Name)
-                        .with_description("Name must be between 1 and 255 octets."));
-                } else if value.contains(|c: char| FORBIDDEN_NAME_CHARS.contains(c)) {
-                    return Err(SetError::invalid_properties()
+                validate_file_name(value.as_ref()).map_err(|description| {
+                    SetError::invalid_properties()
                         .with_property(FileNodeProperty::Name)
-                        .with_description("Name contains a forbidden character."));
-                } else if FORBIDDEN_NODE_NAMES
-                    .iter()
-                    .any(|n| n.eq_ignore_ascii_case(value.as_ref()))
-                {
-                    return Err(SetError::invalid_properties()
-                        .with_property(FileNodeProperty::Name)
-                        .with_description("Name is reserved and cannot be used."));
-                }
+                        .with_description(description)
+                })?;
                 file_node.name = value.into_owned();
             }
             (FileNodeProperty::ParentId, Value::Element(FileNodeValue::Id(value))) => {
@@ -803,6 +937,53 @@ pub(super) fn update_file_node<R: ResolveCreatedReference<FileNodeProperty, File
                 )?;
                 has_acl_changes = true;
             }
+            (FileNodeProperty::ShareLinks, Value::Object(value)) if !is_create => {
+                let mut update = ShareLinksUpdate {
+                    replace: true,
+                    links: Vec::with_capacity(value.as_vec().len()),
+                };
+                for (key, value) in value.into_vec() {
+                    update
+                        .links
+                        .push((key.to_string().into_owned(), value_to_share_link(value)?));
+                }
+                share_links = Some(update);
+            }
+            (FileNodeProperty::ShareLinks, Value::Null) if !is_create => {
+                share_links = Some(ShareLinksUpdate {
+                    replace: true,
+                    links: vec![],
+                });
+            }
+            (FileNodeProperty::Pointer(pointer), value)
+                if !is_create
+                    && matches!(
+                        pointer.first(),
+                        Some(JsonPointerItem::Key(Key::Property(
+                            FileNodeProperty::ShareLinks
+                        )))
+                    ) =>
+            {
+                let mut ptr_iter = pointer.iter();
+                ptr_iter.next();
+                let (Some(JsonPointerItem::Key(key)), None) = (ptr_iter.next(), ptr_iter.next())
+                else {
+                    return Err(SetError::invalid_properties()
+                        .with_property(FileNodeProperty::Pointer(pointer))
+                        .with_description("Field could not be patched."));
+                };
+                share_links
+                    .get_or_insert_default()
+                    .links
+                    .push((key.to_string().into_owned(), value_to_share_link(value)?));
+            }
+            (FileNodeProperty::RestoreVersion, Value::Str(value)) if !is_create => {
+                restore_version = Some(value.parse::<u32>().map_err(|_| {
+                    SetError::invalid_properties()
+                        .with_property(FileNodeProperty::RestoreVersion)
+                        .with_description("Invalid version id.")
+                })?);
+            }
             (FileNodeProperty::Id, value) => {
                 if !expected_id.is_some_and(|expected| crate::matches_id(&value, expected)) {
                     return Err(SetError::invalid_properties()
@@ -818,6 +999,14 @@ pub(super) fn update_file_node<R: ResolveCreatedReference<FileNodeProperty, File
         }
     }
 
+    if restore_version.is_some() && (blob_id.is_some() || file_node.file.is_none()) {
+        return Err(SetError::invalid_properties()
+            .with_property(FileNodeProperty::RestoreVersion)
+            .with_description(
+                "Versions can only be restored on file nodes without a new blobId.",
+            ));
+    }
+
     let will_be_file = file_node.file.is_some() || blob_id.is_some();
     if will_be_file {
         let file = file_node.file.get_or_insert_default();
@@ -852,9 +1041,158 @@ pub(super) fn update_file_node<R: ResolveCreatedReference<FileNodeProperty, File
         has_acl_changes,
         blob_id,
         modified_set,
+        restore_version,
+        share_links,
     })
 }
 
+fn value_to_share_link(
+    value: Value<'_, FileNodeProperty, FileNodeValue>,
+) -> Result<Option<ShareLinkUpdate>, SetError<FileNodeProperty>> {
+    let value = match value {
+        Value::Object(value) => value,
+        Value::Null => return Ok(None),
+        _ => return Err(share_link_error("Share links must be objects.")),
+    };
+    let mut link = ShareLinkUpdate::default();
+
+    for (key, value) in value.into_vec() {
+        let key = key.to_string();
+        hashify::fnc_map!(key.as_bytes(),
+            b"password" => {
+                link.password = Some(match value {
+                    Value::Str(value) if !value.is_empty() => Some(value.into_owned()),
+                    Value::Null => None,
+                    _ => return Err(share_link_error("Invalid password.")),
+                });
+            },
+            b"expires" => {
+                link.expires = Some(match value {
+                    Value::Element(FileNodeValue::Date(value)) => Some(value.timestamp()),
+                    Value::Str(value) => Some(
+                        UTCDate::from_str(value.as_ref())
+                            .map_err(|_| share_link_error("Invalid expiration date."))?
+                            .timestamp(),
+                    ),
+                    Value::Null => None,
+                    _ => return Err(share_link_error("Invalid expiration date.")),
+                });
+            },
+            b"maxDownloads" => {
+                link.max_downloads = Some(match value {
+                    Value::Number(value) => Some(
+                        u32::try_from(value.cast_to_u64())
+                            .ok()
+                            .filter(|value| *value > 0)
+                            .ok_or_else(|| share_link_error("Invalid maxDownloads value."))?,
+                    ),
+                    Value::Null => None,
+                    _ => return Err(share_link_error("Invalid maxDownloads value.")),
+                });
+            },
+            b"fileDrop" => {
+                link.file_drop = Some(match value {
+                    Value::Bool(value) => value,
+                    Value::Null => false,
+                    _ => return Err(share_link_error("Invalid fileDrop value.")),
+                });
+            },
+            // Server-set properties are ignored
+            b"url" | b"hasPassword" | b"downloads" | b"created" => {},
+            _ => {
+                return Err(share_link_error("Unknown share link property."));
+            }
+        );
+    }
+
+    Ok(Some(link))
+}
+
+fn share_link_error(description: &'static str) -> SetError<FileNodeProperty> {
+    SetError::invalid_properties()
+        .with_property(FileNodeProperty::ShareLinks)
+        .with_description(description)
+}
+
+trait ShareLinksApply: Sync + Send {
+    fn apply_share_links(
+        &self,
+        links: &mut FileShareLinks,
+        update: ShareLinksUpdate,
+        is_folder: bool,
+    ) -> impl Future<Output = trc::Result<Result<(), SetError<FileNodeProperty>>>> + Send;
+}
+
+impl ShareLinksApply for Server {
+    async fn apply_share_links(
+        &self,
+        links: &mut FileShareLinks,
+        update: ShareLinksUpdate,
+        is_folder: bool,
+    ) -> trc::Result<Result<(), SetError<FileNodeProperty>>> {
+        if update.replace {
+            // Links not present in the new value are removed, existing ones are reset
+            links.links.retain_mut(|link| {
+                if update.links.iter().any(|(token, _)| token == &link.token) {
+                    *link = FileShareLink {
+                        token: std::mem::take(&mut link.token),
+                        downloads: link.downloads,
+                        created: link.created,
+                        ..Default::default()
+                    };
+                    true
+                } else {
+                    false
+                }
+            });
+        }
+
+        for (token, changes) in update.links {
+            let Some(changes) = changes else {
+                links.links.retain(|link| link.token != token);
+                continue;
+            };
+            let link = if let Some(idx) = links.links.iter().position(|link| link.token == token) {
+                &mut links.links[idx]
+            } else {
+                // Unknown keys act as creation ids
+                links.links.push(FileShareLink::new());
+                links.links.last_mut().unwrap()
+            };
+
+            if let Some(password) = changes.password {
+                link.password_hash = if let Some(password) = password {
+                    hash_secret(
+                        self.core.network.security.password_hash_algorithm,
+                        password.into_bytes(),
+                    )
+                    .await
+                    .caused_by(trc::location!())?
+                    .into()
+                } else {
+                    None
+                };
+            }
+            if let Some(expires) = changes.expires {
+                link.expires = expires;
+            }
+            if let Some(max_downloads) = changes.max_downloads {
+                link.max_downloads = max_downloads;
+            }
+            if let Some(file_drop) = changes.file_drop {
+                if file_drop && !is_folder {
+                    return Ok(Err(share_link_error(
+                        "File drop links can only be created for folders.",
+                    )));
+                }
+                link.file_drop = file_drop;
+            }
+        }
+
+        Ok(Ok(()))
+    }
+}
+
 pub(super) fn validate_file_node_hierarchy(
     document_id: Option<u32>,
     node: &FileNode,
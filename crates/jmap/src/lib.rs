@@ -38,10 +38,12 @@ pub mod participant_identity;
 pub mod principal;
 pub mod push;
 pub mod quota;
+pub mod recipient;
 pub mod registry;
 pub mod share_notification;
 pub mod sieve;
 pub mod submission;
 pub mod thread;
 pub mod vacation;
+pub mod webhook;
 pub mod websocket;
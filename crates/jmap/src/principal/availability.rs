@@ -20,7 +20,7 @@ use common::{
 };
 use groupware::{
     cache::GroupwareCache,
-    calendar::{CALENDAR_SUBSCRIBED, CalendarEvent},
+    calendar::{CALENDAR_SUBSCRIBED, CalendarEvent, availability::CalendarAvailability},
     strip_mailto_scheme,
 };
 use jmap_proto::{
@@ -105,6 +105,7 @@ impl PrincipalGetAvailability for Server {
             .await
             .caused_by(trc::location!())?;
         let mut periods = Vec::new();
+        let mut principal_tz = None;
 
         for account_id in principal.all_ids_by_collection(Collection::Calendar) {
             let resources = self
@@ -134,6 +135,17 @@ impl PrincipalGetAvailability for Server {
                 None
             };
 
+            // Working hours are only disclosed to users with access to the principal's calendars
+            if is_account_owner {
+                principal_tz = resources
+                    .resources
+                    .iter()
+                    .find_map(|r| r.calendar_preferences(principal_id))
+                    .map(|prefs| prefs.tz)
+                    .unwrap_or(Tz::UTC)
+                    .into();
+            }
+
             // Condition: The event finishes after the "utcStart" argument and starts before the "utcEnd" argument.
             let mut preferences_cache: AHashMap<u32, Option<&TinyCalendarPreferences>> =
                 AHashMap::default();
@@ -327,7 +339,7 @@ impl PrincipalGetAvailability for Server {
                             utc_end: expansion.end,
                             busy_status: *busy_status,
                             expansion_id: expansion.comp_id,
-                            document_id,
+                            document_id: Some(document_id),
                         });
                     } else {
                         return Err(trc::JmapEvent::RequestTooLarge
@@ -338,6 +350,30 @@ impl PrincipalGetAvailability for Server {
             }
         }
 
+        // Time outside the principal's working hours is unavailable
+        let unavailable_periods = if let Some(principal_tz) = principal_tz {
+            self.calendar_unavailable_periods(principal_id, filter, principal_tz)
+                .await
+                .caused_by(trc::location!())?
+        } else {
+            Vec::new()
+        };
+        for (utc_start, utc_end) in unavailable_periods {
+            if periods.len() < max_instances {
+                periods.push(FreeBusyResult {
+                    utc_start,
+                    utc_end,
+                    busy_status: BusyStatus::Unavailable,
+                    expansion_id: 0,
+                    document_id: None,
+                });
+            } else {
+                return Err(trc::JmapEvent::RequestTooLarge
+                    .into_err()
+                    .details("The number of expanded instances exceeds the server limit"));
+            }
+        }
+
         let mut result = GetAvailabilityResponse {
             list: Vec::with_capacity(periods.len()),
         };
@@ -396,7 +432,7 @@ struct FreeBusyResult {
     utc_end: i64,
     busy_status: BusyStatus,
     expansion_id: u32,
-    document_id: u32,
+    document_id: Option<u32>,
 }
 
 impl From<FreeBusyResult> for BusyPeriod {
@@ -405,20 +441,21 @@ impl From<FreeBusyResult> for BusyPeriod {
             utc_start: UTCDate::from_timestamp(value.utc_start),
             utc_end: UTCDate::from_timestamp(value.utc_end),
             busy_status: Some(value.busy_status),
-            event: JSCalendar(Value::Object(Map::from(vec![
-                (
-                    Key::Property(JSCalendarProperty::Id),
-                    Value::Element(JSCalendarValue::Id(<Id as CalendarSyntheticId>::new(
-                        value.expansion_id,
-                        value.document_id,
-                    ))),
-                ),
-                (
-                    Key::Property(JSCalendarProperty::BaseEventId),
-                    Value::Element(JSCalendarValue::Id(Id::from(value.document_id))),
-                ),
-            ])))
-            .into(),
+            event: value.document_id.map(|document_id| {
+                JSCalendar(Value::Object(Map::from(vec![
+                    (
+                        Key::Property(JSCalendarProperty::Id),
+                        Value::Element(JSCalendarValue::Id(<Id as CalendarSyntheticId>::new(
+                            value.expansion_id,
+                            document_id,
+                        ))),
+                    ),
+                    (
+                        Key::Property(JSCalendarProperty::BaseEventId),
+                        Value::Element(JSCalendarValue::Id(Id::from(document_id))),
+                    ),
+                ])))
+            }),
         }
     }
 }
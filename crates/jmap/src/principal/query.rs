@@ -13,7 +13,7 @@ use jmap_proto::{
 };
 use registry::{
     schema::{
-        enums::AccountType,
+        enums::{AccountType, ResourceType},
         prelude::{ObjectType, Permission, Property},
     },
     types::EnumImpl,
@@ -98,25 +98,47 @@ impl PrincipalQuery for Server {
                         ));
                     }
                     PrincipalFilter::Type(principal_type) => {
-                        let typ = match principal_type {
-                            PrincipalType::Individual => AccountType::User,
-                            PrincipalType::Group => AccountType::Group,
-                            _ => {
+                        let (typ, resource_type) = match principal_type {
+                            PrincipalType::Individual => (AccountType::User, None),
+                            PrincipalType::Group => (AccountType::Group, None),
+                            PrincipalType::Location => {
+                                (AccountType::Resource, Some(ResourceType::Room))
+                            }
+                            PrincipalType::Resource => {
+                                (AccountType::Resource, Some(ResourceType::Equipment))
+                            }
+                            PrincipalType::Other => {
                                 filters.push(SearchFilter::is_in_set(Default::default()));
                                 continue;
                             }
                         };
 
-                        filters.push(SearchFilter::is_in_set(
-                            self.registry()
-                                .query::<RoaringBitmap>(
-                                    RegistryQuery::new(ObjectType::Account)
-                                        .equal(Property::Type, typ.to_id())
-                                        .with_tenant(access_token.tenant_id()),
-                                )
-                                .await
-                                .caused_by(trc::location!())?,
-                        ));
+                        let mut account_ids = self
+                            .registry()
+                            .query::<RoaringBitmap>(
+                                RegistryQuery::new(ObjectType::Account)
+                                    .equal(Property::Type, typ.to_id())
+                                    .with_tenant(access_token.tenant_id()),
+                            )
+                            .await
+                            .caused_by(trc::location!())?;
+
+                        // Rooms and equipment share the same account type
+                        if let Some(resource_type) = resource_type {
+                            for account_id in account_ids.clone() {
+                                if self
+                                    .account(account_id)
+                                    .await
+                                    .caused_by(trc::location!())?
+                                    .resource_type()
+                                    != Some(resource_type)
+                                {
+                                    account_ids.remove(account_id);
+                                }
+                            }
+                        }
+
+                        filters.push(SearchFilter::is_in_set(account_ids));
                     }
                     other => {
                         return Err(trc::JmapEvent::UnsupportedFilter
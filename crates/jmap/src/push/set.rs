@@ -421,7 +421,7 @@ fn validate_push_value(
     Ok(())
 }
 
-fn validate_push_url(url: &str) -> Result<(), &'static str> {
+pub(crate) fn validate_push_url(url: &str) -> Result<(), &'static str> {
     let url = Url::parse(url).map_err(|_| "Invalid push subscription URL.")?;
 
     if url.scheme() != "https" {
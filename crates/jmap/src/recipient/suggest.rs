@@ -0,0 +1,251 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use common::{Server, auth::AccessToken};
+use email::submission::history::{SentRecipientHistory, SentRecipients};
+use groupware::{cache::GroupwareCache, contact::ContactCard};
+use jmap_proto::method::suggest::{
+    RecipientSource, RecipientSuggestion, SuggestRecipientsRequest, SuggestRecipientsResponse,
+};
+use registry::schema::prelude::{ObjectType, Permission, Property};
+use std::future::Future;
+use store::{
+    ValueKey,
+    ahash::AHashMap,
+    registry::RegistryQuery,
+    roaring::RoaringBitmap,
+    write::{AlignedBytes, Archive, now},
+};
+use trc::AddContext;
+use types::{
+    acl::Acl,
+    collection::{Collection, SyncCollection},
+    id::Id,
+};
+
+const DEFAULT_LIMIT: usize = 10;
+
+pub trait RecipientSuggest: Sync + Send {
+    fn recipient_suggest(
+        &self,
+        request: SuggestRecipientsRequest,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<SuggestRecipientsResponse>> + Send;
+}
+
+impl RecipientSuggest for Server {
+    async fn recipient_suggest(
+        &self,
+        request: SuggestRecipientsRequest,
+        access_token: &AccessToken,
+    ) -> trc::Result<SuggestRecipientsResponse> {
+        let mut response = SuggestRecipientsResponse {
+            account_id: request.account_id,
+            suggestions: Vec::new(),
+        };
+        let text = request.text.trim().to_lowercase();
+        if text.is_empty() {
+            return Ok(response);
+        }
+        let account_id = request.account_id.document_id();
+        let limit = request
+            .limit
+            .unwrap_or(DEFAULT_LIMIT)
+            .clamp(1, self.core.jmap.query_max_results);
+        let now = now();
+        let mut suggestions = Suggestions {
+            text,
+            sent: self
+                .sent_recipients(account_id)
+                .await
+                .caused_by(trc::location!())?,
+            now,
+            results: AHashMap::new(),
+        };
+
+        // Personal and shared address books
+        for book_account_id in [account_id].into_iter().chain(
+            access_token
+                .shared_accounts(Collection::AddressBook)
+                .copied()
+                .filter(|id| *id != account_id),
+        ) {
+            let cache = self
+                .fetch_dav_resources(
+                    access_token.account_id(),
+                    book_account_id,
+                    SyncCollection::AddressBook,
+                )
+                .await
+                .caused_by(trc::location!())?;
+            let document_ids = if access_token.is_shared(book_account_id) {
+                cache.shared_items(access_token, [Acl::ReadItems], true)
+            } else {
+                cache.document_ids(false).collect::<RoaringBitmap>()
+            };
+            let source = if book_account_id == account_id {
+                RecipientSource::Personal
+            } else {
+                RecipientSource::Shared
+            };
+
+            for document_id in document_ids {
+                let Some(archive) = self
+                    .store()
+                    .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                        book_account_id,
+                        Collection::ContactCard,
+                        document_id,
+                    ))
+                    .await
+                    .caused_by(trc::location!())?
+                else {
+                    continue;
+                };
+                let contact = archive
+                    .unarchive::<ContactCard>()
+                    .caused_by(trc::location!())?;
+                let name = contact.full_name();
+                let contact_card_id =
+                    (source == RecipientSource::Personal).then(|| Id::from(document_id));
+
+                for email in contact.emails() {
+                    suggestions.insert(email, name, source, contact_card_id, None);
+                }
+            }
+        }
+
+        // Sent history
+        let sent_addresses = suggestions
+            .sent
+            .recipients
+            .iter()
+            .map(|recipient| recipient.address.clone())
+            .collect::<Vec<_>>();
+        for address in sent_addresses {
+            suggestions.insert(address, None, RecipientSource::Sent, None, None);
+        }
+
+        // Directory
+        if self.core.groupware.allow_directory_query
+            || access_token.has_permission(Permission::JmapPrincipalQuery)
+        {
+            let principal_ids = self
+                .registry()
+                .query::<RoaringBitmap>(
+                    RegistryQuery::new(ObjectType::Account)
+                        .with_tenant(access_token.tenant_id())
+                        .text(Property::Text, suggestions.text.clone()),
+                )
+                .await
+                .caused_by(trc::location!())?;
+
+            for principal_id in principal_ids
+                .into_iter()
+                .take(self.core.jmap.query_max_results)
+            {
+                let principal = self
+                    .account(principal_id)
+                    .await
+                    .caused_by(trc::location!())?;
+                suggestions.insert(
+                    principal.name().to_string(),
+                    principal.description(),
+                    RecipientSource::Directory,
+                    None,
+                    Some(Id::from(principal_id)),
+                );
+            }
+        }
+
+        // Rank results
+        response.suggestions = suggestions.results.into_values().collect();
+        response.suggestions.sort_unstable_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.email.cmp(&b.email))
+        });
+        response.suggestions.truncate(limit);
+
+        Ok(response)
+    }
+}
+
+struct Suggestions {
+    text: String,
+    sent: SentRecipients,
+    now: u64,
+    results: AHashMap<String, RecipientSuggestion>,
+}
+
+impl Suggestions {
+    fn insert(
+        &mut self,
+        email: String,
+        name: Option<&str>,
+        source: RecipientSource,
+        contact_card_id: Option<Id>,
+        principal_id: Option<Id>,
+    ) {
+        let email = email.to_lowercase();
+        if !self.matches(&email, name) {
+            return;
+        }
+
+        let score = source_weight(source);
+        if let Some(suggestion) = self.results.get_mut(&email) {
+            // Keep the highest ranked source, fill in missing details
+            if score > source_weight(suggestion.source) {
+                suggestion.score += score - source_weight(suggestion.source);
+                suggestion.source = source;
+            }
+            if suggestion.name.is_none() {
+                suggestion.name = name.map(|name| name.to_string());
+            }
+            if suggestion.contact_card_id.is_none() {
+                suggestion.contact_card_id = contact_card_id;
+            }
+            if suggestion.principal_id.is_none() {
+                suggestion.principal_id = principal_id;
+            }
+        } else {
+            let score = score + self.sent.score(&email, self.now).unwrap_or_default();
+            self.results.insert(
+                email.clone(),
+                RecipientSuggestion {
+                    email,
+                    name: name.map(|name| name.to_string()),
+                    source,
+                    score,
+                    contact_card_id,
+                    principal_id,
+                },
+            );
+        }
+    }
+
+    fn matches(&self, email: &str, name: Option<&str>) -> bool {
+        let text = self.text.as_str();
+
+        email.starts_with(text)
+            || email
+                .split_once('@')
+                .is_some_and(|(_, domain)| domain.starts_with(text))
+            || name.is_some_and(|name| {
+                let name = name.to_lowercase();
+                name.starts_with(text) || name.split_whitespace().any(|word| word.starts_with(text))
+            })
+    }
+}
+
+fn source_weight(source: RecipientSource) -> f64 {
+    match source {
+        RecipientSource::Personal => 4.0,
+        RecipientSource::Sent => 3.0,
+        RecipientSource::Shared => 2.0,
+        RecipientSource::Directory => 1.0,
+    }
+}
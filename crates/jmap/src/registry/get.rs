@@ -21,7 +21,7 @@ use jmap_tools::Key;
 use registry::{
     jmap::{IntoValue, JmapValue, RegistryValue},
     schema::{
-        enums::Permission,
+        enums::{Permission, StorageUsageType},
         prelude::{
             OBJ_FILTER_ACCOUNT, OBJ_FILTER_TENANT, OBJ_SINGLETON, Object, ObjectInner, ObjectType,
             Property,
@@ -103,6 +103,8 @@ impl RegistryGet for Server {
             | ObjectType::Application
             | ObjectType::Asn
             | ObjectType::Authentication
+            | ObjectType::Backup
+            | ObjectType::BlobEncryptionKey
             | ObjectType::BlobStore
             | ObjectType::BlockedIp
             | ObjectType::Cache
@@ -113,6 +115,7 @@ impl RegistryGet for Server {
             | ObjectType::Coordinator
             | ObjectType::DataRetention
             | ObjectType::DataStore
+            | ObjectType::DataStoreShard
             | ObjectType::Directory
             | ObjectType::DkimReportSettings
             | ObjectType::DmarcReportSettings
@@ -155,6 +158,7 @@ impl RegistryGet for Server {
             | ObjectType::MtaVirtualQueue
             | ObjectType::NetworkListener
             | ObjectType::ClusterRole
+            | ObjectType::CompressionDictionary
             | ObjectType::OidcProvider
             | ObjectType::ReportSettings
             | ObjectType::Search
@@ -255,12 +259,58 @@ impl RegistryGet for Server {
                                     JmapValue::Number(quota.into()),
                                 );
                             }
+                            let usage_properties = [
+                                Property::UsedDiskQuotaByType,
+                                Property::UsedDiskQuotaByMailbox,
+                                Property::UsedDiskQuotaBySize,
+                                Property::ItemCountBySize,
+                            ];
+                            if get.properties.is_empty()
+                                || usage_properties
+                                    .iter()
+                                    .any(|prop| get.properties.contains(prop))
+                            {
+                                let usage = self.get_storage_usage(id.document_id()).await?;
+                                for prop in usage_properties {
+                                    if !get.properties.is_empty() && !get.properties.contains(&prop)
+                                    {
+                                        continue;
+                                    }
+                                    let value = match prop {
+                                        Property::UsedDiskQuotaByType => {
+                                            let mut by_type = usage.by_type();
+                                            let archived =
+                                                self.get_archived_usage(id.document_id()).await?;
+                                            if archived > 0 {
+                                                by_type.set(StorageUsageType::Archived, archived);
+                                            }
+                                            by_type.into_value()
+                                        }
+                                        Property::UsedDiskQuotaByMailbox => {
+                                            let by_mailbox = usage.by_mailbox();
+                                            let mut mailboxes =
+                                                VecMap::with_capacity(by_mailbox.len());
+                                            for (mailbox_id, used) in by_mailbox {
+                                                mailboxes
+                                                    .append(Id::from(mailbox_id).to_string(), used);
+                                            }
+                                            mailboxes.into_value()
+                                        }
+                                        Property::UsedDiskQuotaBySize => {
+                                            usage.by_size().into_value()
+                                        }
+                                        _ => usage.items_by_size().into_value(),
+                                    };
+                                    extra_properties.append(prop, value);
+                                }
+                            }
                             if get.properties.is_empty()
                                 || get.properties.contains(&Property::EmailAddress)
                             {
                                 let (name, domain_id) = match &obj {
                                     Account::User(obj) => (obj.name.as_str(), obj.domain_id),
                                     Account::Group(obj) => (obj.name.as_str(), obj.domain_id),
+                                    Account::Resource(obj) => (obj.name.as_str(), obj.domain_id),
                                 };
                                 let domain = self.domain_by_id(domain_id.document_id()).await?;
                                 let email = format!(
@@ -214,6 +214,9 @@ pub(crate) async fn validate_account(
         (Account::Group(account), AccountUpdate::Update(Account::Group(old_account))) => {
             account.permissions != old_account.permissions || account.roles != old_account.roles
         }
+        (Account::Resource(account), AccountUpdate::Update(Account::Resource(old_account))) => {
+            account.permissions != old_account.permissions || account.roles != old_account.roles
+        }
         (Account::User(account), AccountUpdate::Create(_)) => {
             // Validate tenant quotas
             if let Err(err) = validate_tenant_quota(set, TenantStorageQuota::MaxAccounts).await? {
@@ -245,8 +248,17 @@ pub(crate) async fn validate_account(
 
             true
         }
-        (Account::User(_), AccountUpdate::Update(Account::Group(_)))
-        | (Account::Group(_), AccountUpdate::Update(Account::User(_))) => {
+        (Account::Resource(_), AccountUpdate::Create(_)) => {
+            // Resources count towards the tenant's account quota
+            if let Err(err) = validate_tenant_quota(set, TenantStorageQuota::MaxAccounts).await? {
+                return Ok(Err(err));
+            }
+
+            true
+        }
+        (Account::User(_), AccountUpdate::Update(_))
+        | (Account::Group(_), AccountUpdate::Update(_))
+        | (Account::Resource(_), AccountUpdate::Update(_)) => {
             return Ok(Err(SetError::invalid_properties()
                 .with_property(Property::Type)
                 .with_description(
@@ -379,40 +391,52 @@ pub(crate) async fn validate_tenant_quota(
             .map(|quotas| quotas.get(quota))
             .filter(|quota| *quota != u32::MAX)
         {
-            let (object_type, type_filter, description) = match quota {
-                TenantStorageQuota::MaxAccounts => {
-                    (ObjectType::Account, Some(AccountType::User), "accounts")
-                }
+            let (object_type, type_filter, description): (_, &[AccountType], _) = match quota {
+                TenantStorageQuota::MaxAccounts => (
+                    ObjectType::Account,
+                    &[AccountType::User, AccountType::Resource],
+                    "accounts",
+                ),
                 TenantStorageQuota::MaxGroups => {
-                    (ObjectType::Account, Some(AccountType::Group), "groups")
+                    (ObjectType::Account, &[AccountType::Group], "groups")
                 }
-                TenantStorageQuota::MaxDomains => (ObjectType::Domain, None, "domains"),
+                TenantStorageQuota::MaxDomains => (ObjectType::Domain, &[], "domains"),
                 TenantStorageQuota::MaxMailingLists => {
-                    (ObjectType::MailingList, None, "mailing lists")
+                    (ObjectType::MailingList, &[], "mailing lists")
                 }
-                TenantStorageQuota::MaxRoles => (ObjectType::Role, None, "roles"),
+                TenantStorageQuota::MaxRoles => (ObjectType::Role, &[], "roles"),
                 TenantStorageQuota::MaxOauthClients => {
-                    (ObjectType::OAuthClient, None, "OAuth clients")
+                    (ObjectType::OAuthClient, &[], "OAuth clients")
                 }
-                TenantStorageQuota::MaxDkimKeys => (ObjectType::DkimSignature, None, "DKIM keys"),
-                TenantStorageQuota::MaxDnsServers => (ObjectType::DnsServer, None, "DNS servers"),
-                TenantStorageQuota::MaxDirectories => (ObjectType::Directory, None, "directories"),
+                TenantStorageQuota::MaxDkimKeys => (ObjectType::DkimSignature, &[], "DKIM keys"),
+                TenantStorageQuota::MaxDnsServers => (ObjectType::DnsServer, &[], "DNS servers"),
+                TenantStorageQuota::MaxDirectories => (ObjectType::Directory, &[], "directories"),
                 TenantStorageQuota::MaxAcmeProviders => {
-                    (ObjectType::AcmeProvider, None, "ACME providers")
+                    (ObjectType::AcmeProvider, &[], "ACME providers")
                 }
                 TenantStorageQuota::MaxDiskQuota => unreachable!(),
             };
-            let query = RegistryQuery::new(object_type).with_tenant(tenant_id.into());
-            let count = if let Some(type_filter) = type_filter {
-                set.server
-                    .registry()
-                    .query::<Vec<Id>>(query.equal(Property::Type, type_filter.to_id()))
-                    .await?
-                    .len() as u32
+            let count = if !type_filter.is_empty() {
+                let mut count = 0;
+                for type_filter in type_filter {
+                    count += set
+                        .server
+                        .registry()
+                        .query::<Vec<Id>>(
+                            RegistryQuery::new(object_type)
+                                .with_tenant(tenant_id.into())
+                                .equal(Property::Type, type_filter.to_id()),
+                        )
+                        .await?
+                        .len() as u32;
+                }
+                count
             } else {
                 set.server
                     .registry()
-                    .query::<RegistryObjectCounter>(query)
+                    .query::<RegistryObjectCounter>(
+                        RegistryQuery::new(object_type).with_tenant(tenant_id.into()),
+                    )
                     .await?
                     .0 as u32
             };
@@ -470,6 +494,11 @@ pub(crate) async fn schedule_account_destruction(
             account.name.clone(),
             AccountType::Group,
         ),
+        Account::Resource(account) => (
+            account.domain_id,
+            account.name.clone(),
+            AccountType::Resource,
+        ),
     };
 
     let mut batch = BatchBuilder::new();
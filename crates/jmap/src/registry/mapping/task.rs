@@ -92,7 +92,9 @@ pub(crate) async fn task_set(
             | TaskType::SpamFilterMaintenance
             | TaskType::AcmeRenewal
             | TaskType::DkimManagement
-            | TaskType::DnsManagement => {
+            | TaskType::DnsManagement
+            | TaskType::DataStoreMigration
+            | TaskType::MoveAccount => {
                 let mut index = IndexBuilder::default();
                 task.index(&mut index);
 
@@ -134,7 +136,9 @@ pub(crate) async fn task_set(
             | TaskType::DmarcReport
             | TaskType::TlsReport
             | TaskType::DestroyAccount
-            | TaskType::RestoreArchivedItem => {
+            | TaskType::RestoreArchivedItem
+            | TaskType::EmailSnooze
+            | TaskType::CalendarSubscription => {
                 set.response.not_created.append(
                     id,
                     SetError::forbidden().with_description(format!(
@@ -271,7 +275,7 @@ pub(crate) async fn task_set(
             use crate::registry::set::map_write_error;
             use registry::schema::{
                 enums::AccountType,
-                structs::{Account, GroupAccount, UserAccount},
+                structs::{Account, GroupAccount, ResourceAccount, UserAccount},
             };
             use store::registry::write::{RegistryWrite, RegistryWriteResult};
 
@@ -296,6 +300,11 @@ pub(crate) async fn task_set(
                     domain_id: task.account_domain_id,
                     ..Default::default()
                 }),
+                AccountType::Resource => Account::Resource(ResourceAccount {
+                    name: task.account_name,
+                    domain_id: task.account_domain_id,
+                    ..Default::default()
+                }),
             }
             .into();
 
@@ -50,8 +50,8 @@ use registry::{
             Property,
         },
         structs::{
-            Certificate, DkimSignature, DnsServer, Domain, PublicKey, Role, SieveSystemScript,
-            SieveUserScript, Task,
+            BlobEncryptionKey, Certificate, DkimSignature, DnsServer, Domain, PublicKey, Role,
+            SieveSystemScript, SieveUserScript, Task,
         },
     },
     types::id::ObjectId,
@@ -166,10 +166,15 @@ impl RegistrySet for Server {
             update,
             destroy,
         };
+        if object_type == ObjectType::BlobEncryptionKey {
+            set.fail_all_destroy("Blob encryption keys are needed to read stored blobs");
+        }
+
         match object_type {
             ObjectType::AddressBook
             | ObjectType::Asn
             | ObjectType::Authentication
+            | ObjectType::Backup
             | ObjectType::BlobStore
             | ObjectType::Cache
             | ObjectType::Calendar
@@ -229,8 +234,10 @@ impl RegistrySet for Server {
             | ObjectType::Alert
             | ObjectType::AllowedIp
             | ObjectType::Application
+            | ObjectType::BlobEncryptionKey
             | ObjectType::BlockedIp
             | ObjectType::Certificate
+            | ObjectType::DataStoreShard
             | ObjectType::Directory
             | ObjectType::DnsServer
             | ObjectType::EventTracingLevel
@@ -495,6 +502,21 @@ impl RegistrySet for Server {
                         ObjectInner::Certificate(cert) => {
                             validate_certificate(cert, modification.as_certificate()).await?
                         }
+                        ObjectInner::BlobEncryptionKey(key) => {
+                            // Blob data keys are wrapped with keys derived from the secret
+                            if modification
+                                .as_blob_encryption_key()
+                                .is_some_and(|current| current.secret != key.secret)
+                            {
+                                Err(SetError::invalid_properties()
+                                    .with_property(Property::Secret)
+                                    .with_description(
+                                        "The secret of a blob encryption key in use cannot be changed",
+                                    ))
+                            } else {
+                                Ok(ObjectResponse::default())
+                            }
+                        }
                         ObjectInner::SieveUserScript(SieveUserScript { contents, .. }) => {
                             validate_sieve_script(
                                 set.server,
@@ -740,6 +762,13 @@ impl RegistrySet for Server {
                 .await
                 .map(|set| set.into_response()),
 
+            ObjectType::CompressionDictionary => {
+                set.fail_all_create("Compression dictionaries are trained by the server");
+                set.fail_all_update("Compression dictionaries cannot be modified");
+                set.fail_all_destroy("Compression dictionaries are needed to read stored blobs");
+                Ok(set.into_response())
+            }
+
             ObjectType::Log | ObjectType::Metric | ObjectType::Trace | ObjectType::ClusterNode => {
                 set.fail_all_create("Telemetry objects cannot be created");
                 set.fail_all_update("Telemetry objects cannot be modified");
@@ -883,6 +912,16 @@ impl Modification {
         }
     }
 
+    fn as_blob_encryption_key(&self) -> Option<&BlobEncryptionKey> {
+        match self {
+            Modification::Create { .. } => None,
+            Modification::Update { object, .. } => match &object.inner {
+                ObjectInner::BlobEncryptionKey(key) => Some(key),
+                _ => None,
+            },
+        }
+    }
+
     fn as_sieve_script(&self) -> Option<&str> {
         match self {
             Modification::Create { .. } => None,
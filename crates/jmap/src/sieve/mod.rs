@@ -7,4 +7,5 @@
 pub mod get;
 pub mod query;
 pub mod set;
+pub mod test;
 pub mod validate;
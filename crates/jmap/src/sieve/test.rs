@@ -0,0 +1,314 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::blob::download::BlobDownload;
+use common::{Server, auth::AccessToken};
+use email::{
+    cache::{MessageCacheFetch, mailbox::MailboxCacheAccess},
+    mailbox::{INBOX_ID, TRASH_ID},
+    sieve::ingest::SieveScriptIngest,
+};
+use jmap_proto::{
+    error::set::{SetError, SetErrorType},
+    method::test::{SieveTestAction, TestSieveScriptRequest, TestSieveScriptResponse},
+    request::MaybeInvalid,
+};
+use mail_parser::MessageParser;
+use sieve::{Envelope, Event, Input, Mailbox, Recipient, Script};
+use std::{future::Future, str::FromStr, sync::Arc};
+use trc::AddContext;
+use types::{id::Id, special_use::SpecialUse};
+
+pub trait SieveScriptTest: Sync + Send {
+    fn sieve_script_test(
+        &self,
+        request: TestSieveScriptRequest,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<TestSieveScriptResponse>> + Send;
+}
+
+impl SieveScriptTest for Server {
+    async fn sieve_script_test(
+        &self,
+        request: TestSieveScriptRequest,
+        access_token: &AccessToken,
+    ) -> trc::Result<TestSieveScriptResponse> {
+        let mut response = TestSieveScriptResponse {
+            account_id: request.account_id,
+            actions: Vec::new(),
+            runtime_errors: Vec::new(),
+            error: None,
+        };
+        let account_id = request.account_id.document_id();
+
+        // Obtain the script to test
+        let (script_name, script) = match (request.script_blob_id, request.sieve_script_id) {
+            (Some(MaybeInvalid::Value(blob_id)), None) => {
+                match self
+                    .blob_download(&blob_id, access_token)
+                    .await?
+                    .map(|bytes| self.core.sieve.untrusted_compiler.compile(&bytes))
+                {
+                    Some(Ok(script)) => ("test".to_string(), script),
+                    Some(Err(err)) => {
+                        response.error = SetError::new(SetErrorType::InvalidScript)
+                            .with_description(err.to_string())
+                            .into();
+                        return Ok(response);
+                    }
+                    None => {
+                        response.error = SetError::new(SetErrorType::BlobNotFound).into();
+                        return Ok(response);
+                    }
+                }
+            }
+            (None, Some(MaybeInvalid::Value(id))) => {
+                if let Some(script) = self
+                    .sieve_script_compile(account_id, id.document_id())
+                    .await
+                    .caused_by(trc::location!())?
+                {
+                    (script.name, script.script)
+                } else {
+                    response.error = SetError::not_found()
+                        .with_description(format!("SieveScript {id} not found."))
+                        .into();
+                    return Ok(response);
+                }
+            }
+            (Some(MaybeInvalid::Invalid(_)), None) => {
+                response.error = SetError::new(SetErrorType::BlobNotFound).into();
+                return Ok(response);
+            }
+            (None, Some(MaybeInvalid::Invalid(_))) => {
+                response.error = SetError::not_found().into();
+                return Ok(response);
+            }
+            _ => {
+                response.error = SetError::invalid_properties()
+                    .with_description("Exactly one of scriptBlobId or sieveScriptId is required.")
+                    .into();
+                return Ok(response);
+            }
+        };
+
+        // Obtain the sample message
+        let raw_message = match request.email_blob_id {
+            MaybeInvalid::Value(blob_id) => self.blob_download(&blob_id, access_token).await?,
+            MaybeInvalid::Invalid(_) => None,
+        };
+        let Some(raw_message) = raw_message else {
+            response.error = SetError::new(SetErrorType::BlobNotFound)
+                .with_description("Email blob not found.")
+                .into();
+            return Ok(response);
+        };
+        if raw_message.len() > self.core.email.mail_max_size {
+            response.error = SetError::too_large()
+                .with_description(format!(
+                    "Message exceeds maximum size of {} bytes.",
+                    self.core.email.mail_max_size
+                ))
+                .into();
+            return Ok(response);
+        }
+        let Some(message) = MessageParser::new().parse(&raw_message) else {
+            response.error = SetError::invalid_properties()
+                .with_description("Failed to parse e-mail message.")
+                .into();
+            return Ok(response);
+        };
+
+        // Create Sieve instance
+        let cache = self
+            .get_cached_messages(account_id)
+            .await
+            .caused_by(trc::location!())?;
+        let mut instance = self.core.sieve.untrusted_runtime.filter_parsed(message);
+        let account_info = self.account(account_id).await.caused_by(trc::location!())?;
+        let user_address = account_info.name().to_string();
+        instance.set_user_full_name(
+            account_info
+                .description()
+                .unwrap_or_else(|| account_info.name()),
+        );
+        instance.set_user_address(&user_address);
+        instance.set_envelope(
+            Envelope::From,
+            request.envelope_from.as_deref().unwrap_or_default(),
+        );
+        instance.set_envelope(
+            Envelope::To,
+            request.envelope_to.as_deref().unwrap_or(&user_address),
+        );
+
+        // Run the script without side effects
+        let mut input = Input::script(script_name, Arc::new(script));
+        let mut has_disposition = false;
+
+        while let Some(event) = instance.run(input) {
+            match event {
+                Ok(event) => match event {
+                    Event::IncludeScript { name, .. } => match &name {
+                        Script::Personal(name_) => {
+                            if let Ok(Some(script)) =
+                                self.sieve_script_get_by_name(account_id, name_).await
+                            {
+                                input = Input::script(name, script);
+                            } else {
+                                input = false.into();
+                            }
+                        }
+                        Script::Global(name_) => {
+                            if let Some(script) = self.get_untrusted_sieve_script(name_, 0) {
+                                input = Input::script(name, script.clone());
+                            } else {
+                                input = false.into();
+                            }
+                        }
+                    },
+                    Event::MailboxExists {
+                        mailboxes,
+                        special_use,
+                    } => {
+                        let special_use_ids = special_use
+                            .iter()
+                            .map(|role| match SpecialUse::parse(role) {
+                                Some(SpecialUse::Inbox) => INBOX_ID,
+                                Some(SpecialUse::Trash) => TRASH_ID,
+                                Some(role) => cache
+                                    .mailbox_by_role(&role)
+                                    .map(|m| m.document_id)
+                                    .unwrap_or(u32::MAX),
+                                None => u32::MAX,
+                            })
+                            .collect::<Vec<_>>();
+
+                        input = if !mailboxes.is_empty() {
+                            mailboxes.into_iter().all(|mailbox| {
+                                let document_id = match mailbox {
+                                    Mailbox::Name(name) => {
+                                        cache.mailbox_by_path(&name).map(|m| m.document_id)
+                                    }
+                                    Mailbox::Id(id) => Id::from_str(&id)
+                                        .ok()
+                                        .map(|id| id.document_id())
+                                        .filter(|id| cache.has_mailbox_id(id)),
+                                };
+                                document_id.is_some_and(|document_id| {
+                                    special_use_ids.is_empty()
+                                        || special_use_ids.contains(&document_id)
+                                })
+                            })
+                        } else {
+                            !special_use_ids.is_empty()
+                                && special_use_ids.iter().all(|id| *id != u32::MAX)
+                        }
+                        .into();
+                    }
+                    Event::DuplicateId { .. } => {
+                        // Do not record message ids during a dry run
+                        input = false.into();
+                    }
+                    Event::Discard => {
+                        response.actions.push(SieveTestAction::Discard);
+                        has_disposition = true;
+                        input = true.into();
+                    }
+                    Event::Reject { reason, .. } => {
+                        response.actions.push(SieveTestAction::Reject { reason });
+                        has_disposition = true;
+                        input = true.into();
+                    }
+                    Event::Keep { flags, .. } => {
+                        response.actions.push(SieveTestAction::Keep { flags });
+                        has_disposition = true;
+                        input = true.into();
+                    }
+                    Event::FileInto {
+                        folder,
+                        flags,
+                        mailbox_id,
+                        special_use,
+                        create,
+                        ..
+                    } => {
+                        // Resolve the target mailbox without creating it
+                        let mut target_id = mailbox_id
+                            .and_then(|id| Id::from_str(&id).ok())
+                            .map(|id| id.document_id())
+                            .filter(|id| cache.has_mailbox_id(id));
+                        if target_id.is_none()
+                            && let Some(special_use) =
+                                special_use.as_deref().and_then(SpecialUse::parse)
+                        {
+                            target_id = match special_use {
+                                SpecialUse::Inbox => Some(INBOX_ID),
+                                SpecialUse::Trash => Some(TRASH_ID),
+                                role => cache.mailbox_by_role(&role).map(|m| m.document_id),
+                            };
+                        }
+                        if target_id.is_none() {
+                            target_id = cache.mailbox_by_path(&folder).map(|m| m.document_id);
+                        }
+
+                        response.actions.push(SieveTestAction::FileInto {
+                            mailbox: folder,
+                            mailbox_id: target_id.map(Id::from),
+                            flags,
+                            create,
+                        });
+                        has_disposition = true;
+                        input = true.into();
+                    }
+                    Event::SendMessage {
+                        recipient,
+                        message_id,
+                        ..
+                    } => {
+                        let recipients = match recipient {
+                            Recipient::Address(rcpt) => vec![rcpt],
+                            Recipient::Group(rcpts) => rcpts,
+                            Recipient::List(_) => vec![],
+                        };
+
+                        // Messages created by the script itself are vacation responses
+                        response.actions.push(if message_id == 0 {
+                            has_disposition = true;
+                            SieveTestAction::Redirect { recipients }
+                        } else {
+                            SieveTestAction::Vacation { recipients }
+                        });
+                        input = true.into();
+                    }
+                    Event::CreatedMessage { .. } => {
+                        input = true.into();
+                    }
+                    Event::ListContains { .. }
+                    | Event::Notify { .. }
+                    | Event::SetEnvelope { .. }
+                    | Event::Function { .. } => {
+                        // Not available during a dry run
+                        input = false.into();
+                    }
+                },
+                Err(err) => {
+                    response.runtime_errors.push(err.to_string());
+                    input = true.into();
+                }
+            }
+        }
+
+        // Implicit keep
+        if !has_disposition {
+            response
+                .actions
+                .push(SieveTestAction::Keep { flags: Vec::new() });
+        }
+
+        Ok(response)
+    }
+}
@@ -13,7 +13,10 @@ use common::{
 use email::{
     identity::Identity,
     message::metadata::{ArchivedMetadataHeaderName, ArchivedMetadataHeaderValue, MessageMetadata},
-    submission::{Address, Delivered, DeliveryStatus, EmailSubmission, UndoStatus},
+    submission::{
+        Address, Delivered, DeliveryStatus, EmailSubmission, UndoStatus,
+        history::SentRecipientHistory,
+    },
 };
 use jmap_proto::{
     error::set::{SetError, SetErrorType},
@@ -88,6 +91,22 @@ impl EmailSubmissionSet for Server {
                         Id::from_parts(submission.thread_id, submission.email_id),
                     );
 
+                    // Update sent recipient history
+                    if let Err(err) = self
+                        .record_sent_recipients(
+                            account_id,
+                            submission
+                                .delivery_status
+                                .iter()
+                                .filter(|(_, status)| status.delivered != Delivered::No)
+                                .map(|(address, _)| address.clone())
+                                .collect::<Vec<_>>(),
+                        )
+                        .await
+                    {
+                        trc::error!(err.account_id(account_id).caused_by(trc::location!()));
+                    }
+
                     let send_at = submission.send_at;
                     let undo_status = match submission.undo_status {
                         UndoStatus::Pending => email_submission::UndoStatus::Pending,
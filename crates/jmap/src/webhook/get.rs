@@ -0,0 +1,129 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use common::{Server, auth::AccessToken};
+use email::push::Webhooks;
+use jmap_proto::{
+    method::get::{GetRequest, GetResponse},
+    object::webhook::{self, WebhookProperty, WebhookValue},
+};
+use jmap_tools::{Map, Value};
+use std::future::Future;
+use store::{
+    ValueKey,
+    write::{AlignedBytes, Archive},
+};
+use trc::AddContext;
+use types::{collection::Collection, field::PrincipalField, id::Id};
+use utils::map::bitmap::Bitmap;
+
+pub trait WebhookFetch: Sync + Send {
+    fn webhook_get(
+        &self,
+        request: GetRequest<webhook::Webhook>,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<GetResponse<webhook::Webhook>>> + Send;
+}
+
+impl WebhookFetch for Server {
+    async fn webhook_get(
+        &self,
+        mut request: GetRequest<webhook::Webhook>,
+        access_token: &AccessToken,
+    ) -> trc::Result<GetResponse<webhook::Webhook>> {
+        let (ids, not_found_ids) = request.unwrap_ids(self.core.jmap.get_max_objects)?;
+        let properties = request.unwrap_properties(&[
+            WebhookProperty::Id,
+            WebhookProperty::Url,
+            WebhookProperty::Types,
+            WebhookProperty::IsEnabled,
+        ]);
+
+        let account_id = access_token.account_id();
+
+        let mut response = GetResponse {
+            account_id: request.account_id.into(),
+            state: None,
+            list: Vec::new(),
+            not_found: not_found_ids,
+        };
+
+        let Some(webhooks_) = self
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                account_id,
+                Collection::Principal,
+                0,
+                PrincipalField::Webhooks,
+            ))
+            .await?
+        else {
+            for id in ids.unwrap_or_default() {
+                response.push_not_found(id);
+            }
+            return Ok(response);
+        };
+        let webhooks = webhooks_
+            .to_unarchived::<Webhooks>()
+            .caused_by(trc::location!())?;
+
+        let ids = if let Some(ids) = ids {
+            ids
+        } else {
+            webhooks
+                .inner
+                .webhooks
+                .iter()
+                .take(self.core.jmap.get_max_objects)
+                .map(|w| Id::from(w.id.to_native()))
+                .collect::<Vec<_>>()
+        };
+
+        for id in ids {
+            // Obtain the webhook object
+            let document_id = id.document_id();
+            let Some(webhook) = webhooks
+                .inner
+                .webhooks
+                .iter()
+                .find(|w| w.id.to_native() == document_id)
+            else {
+                response.push_not_found(id);
+                continue;
+            };
+
+            let mut result = Map::with_capacity(properties.len());
+            for property in &properties {
+                match property {
+                    WebhookProperty::Id => {
+                        result.insert_unchecked(WebhookProperty::Id, id);
+                    }
+                    WebhookProperty::Url => {
+                        result.insert_unchecked(WebhookProperty::Url, &webhook.url);
+                    }
+                    WebhookProperty::Types => {
+                        let mut types = Vec::new();
+                        for typ in Bitmap::from(&webhook.types).into_iter() {
+                            types.push(Value::Element(WebhookValue::Types(typ)));
+                        }
+                        result.insert_unchecked(WebhookProperty::Types, Value::Array(types));
+                    }
+                    WebhookProperty::IsEnabled => {
+                        result.insert_unchecked(WebhookProperty::IsEnabled, webhook.enabled);
+                    }
+                    WebhookProperty::Secret => {
+                        return Err(trc::JmapEvent::Forbidden
+                            .into_err()
+                            .details("The 'secret' property is not readable".to_string()));
+                    }
+                }
+            }
+            response.list.push(result.into());
+        }
+
+        Ok(response)
+    }
+}
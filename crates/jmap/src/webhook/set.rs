@@ -0,0 +1,328 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::push::set::validate_push_url;
+use common::{Server, auth::AccessToken, ipc::PushEvent};
+use email::push::{Webhook, Webhooks};
+use jmap_proto::{
+    error::set::{SetError, SetErrorType},
+    method::set::{SetRequest, SetResponse},
+    object::webhook::{self, WebhookProperty, WebhookValue},
+    request::MaybeInvalid,
+};
+use jmap_tools::{Key, Map, Value};
+use rand::distr::Alphanumeric;
+use registry::schema::enums::StorageQuota;
+use std::future::Future;
+use store::{
+    Serialize, ValueKey,
+    rand::{RngExt, rng},
+    write::{AlignedBytes, Archive, Archiver, BatchBuilder},
+};
+use trc::{AddContext, ServerEvent};
+use types::{collection::Collection, field::PrincipalField, id::Id, type_state::DataType};
+use utils::map::bitmap::Bitmap;
+
+const SECRET_LEN: usize = 32;
+const SECRET_MIN_LEN: usize = 16;
+
+pub trait WebhookSet: Sync + Send {
+    fn webhook_set(
+        &self,
+        request: SetRequest<'_, webhook::Webhook>,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<SetResponse<webhook::Webhook>>> + Send;
+}
+
+impl WebhookSet for Server {
+    async fn webhook_set(
+        &self,
+        mut request: SetRequest<'_, webhook::Webhook>,
+        access_token: &AccessToken,
+    ) -> trc::Result<SetResponse<webhook::Webhook>> {
+        // Load existing webhooks
+        let account_id = access_token.account_id();
+        let webhooks_archive = self
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                account_id,
+                Collection::Principal,
+                0,
+                PrincipalField::Webhooks,
+            ))
+            .await?;
+        let mut webhooks = if let Some(webhooks) = &webhooks_archive {
+            webhooks
+                .deserialize::<Webhooks>()
+                .caused_by(trc::location!())?
+        } else {
+            Webhooks::default()
+        };
+        let mut has_changes = false;
+
+        // Prepare response
+        let mut response = SetResponse::from_request(&request, self.core.jmap.set_max_objects)?;
+        let will_destroy = response.collect_will_destroy(request.unwrap_destroy());
+        let account = self.account(account_id).await.caused_by(trc::location!())?;
+
+        // Process creates
+        'create: for (id, object) in request.unwrap_create() {
+            let mut webhook = Webhook {
+                enabled: true,
+                ..Default::default()
+            };
+
+            if webhooks.webhooks.len()
+                >= self.object_quota(account.object_quotas(), StorageQuota::MaxWebhooks) as usize
+            {
+                response.not_created.append(
+                    id,
+                    SetError::new(SetErrorType::OverQuota).with_description(
+                        "There are too many webhooks, please delete some before adding a new one.",
+                    ),
+                );
+                continue 'create;
+            }
+
+            for (property, mut value) in object.into_expanded_object() {
+                if let Err(err) = response
+                    .resolve_self_references(&mut value, 0, false)
+                    .and_then(|_| validate_webhook_value(None, &property, value, &mut webhook))
+                {
+                    response.not_created.append(id, err);
+                    continue 'create;
+                }
+            }
+
+            if webhook.url.is_empty() {
+                response.not_created.append(
+                    id,
+                    SetError::invalid_properties()
+                        .with_property(WebhookProperty::Url)
+                        .with_description("Missing required properties"),
+                );
+                continue 'create;
+            }
+
+            // Generate a signing secret if none was provided
+            let generated_secret = if webhook.secret.is_empty() {
+                webhook.secret = rng()
+                    .sample_iter(Alphanumeric)
+                    .take(SECRET_LEN)
+                    .map(char::from)
+                    .collect::<String>();
+                Some(webhook.secret.clone())
+            } else {
+                None
+            };
+            if webhook.types.is_empty() {
+                webhook.types = default_types();
+            }
+
+            // Ids are assigned from a counter so ids of deleted webhooks are never reused
+            let document_id = self
+                .store()
+                .assign_document_ids(account_id, Collection::Principal, 1)
+                .await
+                .caused_by(trc::location!())?;
+            webhook.id = document_id;
+
+            // Insert record
+            webhooks.webhooks.push(webhook);
+            let mut result = Map::with_capacity(2)
+                .with_key_value(WebhookProperty::Id, WebhookValue::Id(document_id.into()));
+            if let Some(secret) = generated_secret {
+                result = result.with_key_value(WebhookProperty::Secret, Value::Str(secret.into()));
+            }
+            response.created.insert(id, result.into());
+            has_changes = true;
+        }
+
+        // Process updates
+        'update: for (id, object) in request.unwrap_update() {
+            let id = match id {
+                MaybeInvalid::Value(id) => id,
+                invalid => {
+                    response.not_updated.append(invalid, SetError::not_found());
+                    continue 'update;
+                }
+            };
+            // Make sure id won't be destroyed
+            if will_destroy.contains(&id) {
+                response.not_updated.append(id, SetError::will_destroy());
+                continue 'update;
+            }
+
+            // Obtain webhook
+            let document_id = id.document_id();
+            let Some(webhook) = webhooks.webhooks.iter_mut().find(|w| w.id == document_id) else {
+                response.not_updated.append(id, SetError::not_found());
+                continue 'update;
+            };
+
+            for (property, mut value) in object.into_expanded_object() {
+                if let Err(err) = response
+                    .resolve_self_references(&mut value, 0, false)
+                    .and_then(|_| validate_webhook_value(Some(id), &property, value, webhook))
+                {
+                    response.not_updated.append(id, err);
+                    continue 'update;
+                }
+            }
+
+            has_changes = true;
+            response.updated.append(id, None);
+        }
+
+        // Process deletions
+        for id in will_destroy {
+            let document_id = id.document_id();
+            if let Some(idx) = webhooks.webhooks.iter().position(|w| w.id == document_id) {
+                webhooks.webhooks.swap_remove(idx);
+                has_changes = true;
+                response.destroyed.push(id);
+            } else {
+                response.not_destroyed.append(id, SetError::not_found());
+            }
+        }
+
+        // Update webhooks
+        if has_changes {
+            // Save changes
+            let mut batch = BatchBuilder::new();
+
+            if webhooks_archive.is_none() {
+                batch
+                    .with_account_id(u32::MAX)
+                    .with_collection(Collection::Principal)
+                    .with_document(account_id)
+                    .tag(PrincipalField::Webhooks);
+            } else if webhooks.webhooks.is_empty() {
+                batch
+                    .with_account_id(u32::MAX)
+                    .with_collection(Collection::Principal)
+                    .with_document(account_id)
+                    .untag(PrincipalField::Webhooks);
+            }
+
+            batch
+                .with_account_id(account_id)
+                .with_collection(Collection::Principal)
+                .with_document(0);
+
+            if let Some(webhooks_archive) = webhooks_archive {
+                batch.assert_value(PrincipalField::Webhooks, webhooks_archive);
+            }
+
+            if !webhooks.webhooks.is_empty() {
+                batch.set(
+                    PrincipalField::Webhooks,
+                    Archiver::new(webhooks)
+                        .serialize()
+                        .caused_by(trc::location!())?,
+                );
+            } else {
+                batch.clear(PrincipalField::Webhooks);
+            }
+
+            self.commit_batch(batch).await.caused_by(trc::location!())?;
+
+            // Notify webhook manager
+            if self
+                .inner
+                .ipc
+                .push_tx
+                .clone()
+                .send(PushEvent::PushServerUpdate {
+                    account_id,
+                    broadcast: true,
+                })
+                .await
+                .is_err()
+            {
+                trc::event!(
+                    Server(ServerEvent::ThreadError),
+                    Details = "Error sending webhook updates.",
+                    CausedBy = trc::location!()
+                );
+            }
+        }
+
+        Ok(response)
+    }
+}
+
+fn validate_webhook_value(
+    expected_id: Option<Id>,
+    property: &Key<WebhookProperty>,
+    value: Value<'_, WebhookProperty, WebhookValue>,
+    webhook: &mut Webhook,
+) -> Result<(), SetError<WebhookProperty>> {
+    let Key::Property(property) = property else {
+        return Err(SetError::invalid_properties()
+            .with_property(property.to_owned())
+            .with_description("Invalid property."));
+    };
+
+    match (property, value) {
+        (WebhookProperty::Url, Value::Str(value)) if value.len() < 512 => {
+            validate_push_url(value.as_ref()).map_err(|description| {
+                SetError::invalid_properties()
+                    .with_property(property.clone())
+                    .with_description(description)
+            })?;
+            webhook.url = value.into_owned();
+        }
+        (WebhookProperty::Secret, Value::Str(value))
+            if (SECRET_MIN_LEN..=255).contains(&value.len()) =>
+        {
+            webhook.secret = value.into_owned();
+        }
+        (WebhookProperty::Types, Value::Array(value)) => {
+            webhook.types.clear();
+
+            for item in value {
+                if let Value::Element(WebhookValue::Types(dt)) = item
+                    && dt.try_to_sync().is_some()
+                {
+                    webhook.types.insert(dt);
+                } else {
+                    return Err(SetError::invalid_properties()
+                        .with_property(property.clone())
+                        .with_description("Invalid or unsupported data type."));
+                }
+            }
+        }
+        (WebhookProperty::Types, Value::Null) => {
+            webhook.types = default_types();
+        }
+        (WebhookProperty::IsEnabled, Value::Bool(value)) => {
+            webhook.enabled = value;
+        }
+        (WebhookProperty::Id, value) => {
+            if !expected_id.is_some_and(|expected| crate::matches_id(&value, expected)) {
+                return Err(SetError::invalid_properties()
+                    .with_property(WebhookProperty::Id)
+                    .with_description("The id property is immutable."));
+            }
+        }
+        (property, _) => {
+            return Err(SetError::invalid_properties()
+                .with_property(property.clone())
+                .with_description("Field could not be set."));
+        }
+    }
+
+    Ok(())
+}
+
+fn default_types() -> Bitmap<DataType> {
+    Bitmap::from_iter([
+        DataType::Email,
+        DataType::CalendarEvent,
+        DataType::ContactCard,
+    ])
+}
@@ -12,6 +12,7 @@ pub enum AccountType {
     #[default]
     User = 0,
     Group = 1,
+    Resource = 2,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -160,6 +161,14 @@ pub enum AzureEnvironment {
     UsGovernment = 2,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum BackupType {
+    #[default]
+    Disabled = 0,
+    Enabled = 1,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[repr(u16)]
 pub enum BlobStoreBaseType {
@@ -184,6 +193,7 @@ pub enum BlobStoreType {
     FoundationDb = 5,
     PostgreSql = 6,
     MySql = 7,
+    Tiered = 8,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -257,6 +267,15 @@ pub enum CompressionAlgo {
     #[default]
     Lz4 = 0,
     None = 1,
+    Zstd = 2,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum CompressionContentClass {
+    #[default]
+    Headers = 0,
+    HtmlBody = 1,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -300,6 +319,15 @@ pub enum CronType {
     Hourly = 2,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum DataStoreMigrationStage {
+    #[default]
+    Copy = 0,
+    CatchUp = 1,
+    Cutover = 2,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[repr(u16)]
 pub enum DataStoreType {
@@ -309,6 +337,7 @@ pub enum DataStoreType {
     FoundationDb = 2,
     PostgreSql = 3,
     MySql = 4,
+    Ephemeral = 5,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -2179,6 +2208,37 @@ pub enum Permission {
     SysWebHookUpdate = 656,
     SysWebHookDestroy = 657,
     SysWebHookQuery = 658,
+    TaskEmailSnooze = 660,
+    JmapBlobConvert = 661,
+    JmapWebhookGet = 662,
+    JmapWebhookCreate = 663,
+    JmapWebhookUpdate = 664,
+    JmapWebhookDestroy = 665,
+    JmapSieveScriptChanges = 666,
+    JmapSieveScriptTest = 667,
+    JmapRecipientSuggest = 668,
+    TaskCalendarSubscription = 669,
+    JmapContactCardFindDuplicates = 670,
+    JmapContactCardMerge = 671,
+    SysCompressionDictionaryGet = 672,
+    SysCompressionDictionaryCreate = 673,
+    SysCompressionDictionaryUpdate = 674,
+    SysCompressionDictionaryDestroy = 675,
+    SysCompressionDictionaryQuery = 676,
+    SysBlobEncryptionKeyGet = 677,
+    SysBlobEncryptionKeyCreate = 678,
+    SysBlobEncryptionKeyUpdate = 679,
+    SysBlobEncryptionKeyDestroy = 680,
+    SysBlobEncryptionKeyQuery = 681,
+    TaskDataStoreMigration = 682,
+    SysBackupGet = 683,
+    SysBackupUpdate = 684,
+    SysDataStoreShardGet = 685,
+    SysDataStoreShardCreate = 686,
+    SysDataStoreShardUpdate = 687,
+    SysDataStoreShardDestroy = 688,
+    SysDataStoreShardQuery = 689,
+    TaskMoveAccount = 690,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -2275,6 +2335,14 @@ pub enum RedisProtocol {
     Resp3 = 1,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum ResourceType {
+    #[default]
+    Room = 0,
+    Equipment = 1,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[repr(u16)]
 pub enum RolesType {
@@ -2678,6 +2746,30 @@ pub enum StorageQuota {
     MaxApiKeys = 16,
     MaxPublicKeys = 17,
     MaxDiskQuota = 18,
+    MaxWebhooks = 19,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum StorageSizeBucket {
+    #[default]
+    UpTo100Kb = 0,
+    UpTo1Mb = 1,
+    UpTo10Mb = 2,
+    UpTo100Mb = 3,
+    Over100Mb = 4,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum StorageUsageType {
+    #[default]
+    Email = 0,
+    Calendar = 1,
+    Contact = 2,
+    File = 3,
+    Sieve = 4,
+    Archived = 5,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -2697,6 +2789,8 @@ pub enum TaskAccountMaintenanceType {
     Reindex = 1,
     RecalculateImapUid = 2,
     RecalculateQuota = 3,
+    CheckConsistency = 4,
+    RepairConsistency = 5,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -2746,6 +2840,12 @@ pub enum TaskStoreMaintenanceType {
     RemoveLockDav = 12,
     RemoveSieveId = 13,
     RemoveGreylist = 14,
+    MigrateBlob = 15,
+    TrainCompressionDictionaries = 16,
+    RecompressBlob = 17,
+    RewrapBlobKeys = 18,
+    Backup = 19,
+    CheckAccounts = 20,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -2777,6 +2877,10 @@ pub enum TaskType {
     AcmeRenewal = 15,
     DkimManagement = 16,
     DnsManagement = 17,
+    EmailSnooze = 18,
+    CalendarSubscription = 19,
+    DataStoreMigration = 20,
+    MoveAccount = 21,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
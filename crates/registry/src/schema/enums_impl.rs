@@ -14,6 +14,7 @@ impl EnumImpl for AccountType {
             value.as_bytes(),
             b"User" => AccountType::User,
             b"Group" => AccountType::Group,
+            b"Resource" => AccountType::Resource,
         }
     }
 
@@ -21,6 +22,7 @@ impl EnumImpl for AccountType {
         match self {
             AccountType::User => "User",
             AccountType::Group => "Group",
+            AccountType::Resource => "Resource",
         }
     }
 
@@ -32,11 +34,12 @@ impl EnumImpl for AccountType {
         match id {
             0 => Some(AccountType::User),
             1 => Some(AccountType::Group),
+            2 => Some(AccountType::Resource),
             _ => None,
         }
     }
 
-    const COUNT: usize = 2;
+    const COUNT: usize = 3;
 }
 
 impl serde::Serialize for AccountType {
@@ -860,6 +863,56 @@ impl<'de> serde::Deserialize<'de> for AzureEnvironment {
     }
 }
 
+impl EnumImpl for BackupType {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"Disabled" => BackupType::Disabled,
+            b"Enabled" => BackupType::Enabled,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            BackupType::Disabled => "Disabled",
+            BackupType::Enabled => "Enabled",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(BackupType::Disabled),
+            1 => Some(BackupType::Enabled),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 2;
+}
+
+impl serde::Serialize for BackupType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for BackupType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
 impl EnumImpl for BlobStoreBaseType {
     fn parse(value: &str) -> Option<Self> {
         hashify::tiny_map! {
@@ -934,6 +987,7 @@ impl EnumImpl for BlobStoreType {
             b"FoundationDb" => BlobStoreType::FoundationDb,
             b"PostgreSql" => BlobStoreType::PostgreSql,
             b"MySql" => BlobStoreType::MySql,
+            b"Tiered" => BlobStoreType::Tiered,
         }
     }
 
@@ -947,6 +1001,7 @@ impl EnumImpl for BlobStoreType {
             BlobStoreType::FoundationDb => "FoundationDb",
             BlobStoreType::PostgreSql => "PostgreSql",
             BlobStoreType::MySql => "MySql",
+            BlobStoreType::Tiered => "Tiered",
         }
     }
 
@@ -964,11 +1019,12 @@ impl EnumImpl for BlobStoreType {
             5 => Some(BlobStoreType::FoundationDb),
             6 => Some(BlobStoreType::PostgreSql),
             7 => Some(BlobStoreType::MySql),
+            8 => Some(BlobStoreType::Tiered),
             _ => None,
         }
     }
 
-    const COUNT: usize = 8;
+    const COUNT: usize = 9;
 }
 
 impl serde::Serialize for BlobStoreType {
@@ -1347,6 +1403,7 @@ impl EnumImpl for CompressionAlgo {
             value.as_bytes(),
             b"lz4" => CompressionAlgo::Lz4,
             b"none" => CompressionAlgo::None,
+            b"zstd" => CompressionAlgo::Zstd,
         }
     }
 
@@ -1354,6 +1411,7 @@ impl EnumImpl for CompressionAlgo {
         match self {
             CompressionAlgo::Lz4 => "lz4",
             CompressionAlgo::None => "none",
+            CompressionAlgo::Zstd => "zstd",
         }
     }
 
@@ -1365,11 +1423,12 @@ impl EnumImpl for CompressionAlgo {
         match id {
             0 => Some(CompressionAlgo::Lz4),
             1 => Some(CompressionAlgo::None),
+            2 => Some(CompressionAlgo::Zstd),
             _ => None,
         }
     }
 
-    const COUNT: usize = 2;
+    const COUNT: usize = 3;
 }
 
 impl serde::Serialize for CompressionAlgo {
@@ -1391,6 +1450,56 @@ impl<'de> serde::Deserialize<'de> for CompressionAlgo {
     }
 }
 
+impl EnumImpl for CompressionContentClass {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"headers" => CompressionContentClass::Headers,
+            b"htmlBody" => CompressionContentClass::HtmlBody,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            CompressionContentClass::Headers => "headers",
+            CompressionContentClass::HtmlBody => "htmlBody",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(CompressionContentClass::Headers),
+            1 => Some(CompressionContentClass::HtmlBody),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 2;
+}
+
+impl serde::Serialize for CompressionContentClass {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for CompressionContentClass {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
 impl EnumImpl for CoordinatorType {
     fn parse(value: &str) -> Option<Self> {
         hashify::tiny_map! {
@@ -1618,6 +1727,59 @@ impl<'de> serde::Deserialize<'de> for CronType {
     }
 }
 
+impl EnumImpl for DataStoreMigrationStage {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"copy" => DataStoreMigrationStage::Copy,
+            b"catchUp" => DataStoreMigrationStage::CatchUp,
+            b"cutover" => DataStoreMigrationStage::Cutover,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            DataStoreMigrationStage::Copy => "copy",
+            DataStoreMigrationStage::CatchUp => "catchUp",
+            DataStoreMigrationStage::Cutover => "cutover",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(DataStoreMigrationStage::Copy),
+            1 => Some(DataStoreMigrationStage::CatchUp),
+            2 => Some(DataStoreMigrationStage::Cutover),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 3;
+}
+
+impl serde::Serialize for DataStoreMigrationStage {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for DataStoreMigrationStage {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
 impl EnumImpl for DataStoreType {
     fn parse(value: &str) -> Option<Self> {
         hashify::tiny_map! {
@@ -1627,6 +1789,7 @@ impl EnumImpl for DataStoreType {
             b"FoundationDb" => DataStoreType::FoundationDb,
             b"PostgreSql" => DataStoreType::PostgreSql,
             b"MySql" => DataStoreType::MySql,
+            b"Ephemeral" => DataStoreType::Ephemeral,
         }
     }
 
@@ -1637,6 +1800,7 @@ impl EnumImpl for DataStoreType {
             DataStoreType::FoundationDb => "FoundationDb",
             DataStoreType::PostgreSql => "PostgreSql",
             DataStoreType::MySql => "MySql",
+            DataStoreType::Ephemeral => "Ephemeral",
         }
     }
 
@@ -1651,11 +1815,12 @@ impl EnumImpl for DataStoreType {
             2 => Some(DataStoreType::FoundationDb),
             3 => Some(DataStoreType::PostgreSql),
             4 => Some(DataStoreType::MySql),
+            5 => Some(DataStoreType::Ephemeral),
             _ => None,
         }
     }
 
-    const COUNT: usize = 5;
+    const COUNT: usize = 6;
 }
 
 impl serde::Serialize for DataStoreType {
@@ -7548,6 +7713,37 @@ impl EnumImpl for Permission {
             b"sysWebHookUpdate" => Permission::SysWebHookUpdate,
             b"sysWebHookDestroy" => Permission::SysWebHookDestroy,
             b"sysWebHookQuery" => Permission::SysWebHookQuery,
+            b"taskEmailSnooze" => Permission::TaskEmailSnooze,
+            b"jmapBlobConvert" => Permission::JmapBlobConvert,
+            b"jmapWebhookGet" => Permission::JmapWebhookGet,
+            b"jmapWebhookCreate" => Permission::JmapWebhookCreate,
+            b"jmapWebhookUpdate" => Permission::JmapWebhookUpdate,
+            b"jmapWebhookDestroy" => Permission::JmapWebhookDestroy,
+            b"jmapSieveScriptChanges" => Permission::JmapSieveScriptChanges,
+            b"jmapSieveScriptTest" => Permission::JmapSieveScriptTest,
+            b"jmapRecipientSuggest" => Permission::JmapRecipientSuggest,
+            b"taskCalendarSubscription" => Permission::TaskCalendarSubscription,
+            b"jmapContactCardFindDuplicates" => Permission::JmapContactCardFindDuplicates,
+            b"jmapContactCardMerge" => Permission::JmapContactCardMerge,
+            b"sysCompressionDictionaryGet" => Permission::SysCompressionDictionaryGet,
+            b"sysCompressionDictionaryCreate" => Permission::SysCompressionDictionaryCreate,
+            b"sysCompressionDictionaryUpdate" => Permission::SysCompressionDictionaryUpdate,
+            b"sysCompressionDictionaryDestroy" => Permission::SysCompressionDictionaryDestroy,
+            b"sysCompressionDictionaryQuery" => Permission::SysCompressionDictionaryQuery,
+            b"sysBlobEncryptionKeyGet" => Permission::SysBlobEncryptionKeyGet,
+            b"sysBlobEncryptionKeyCreate" => Permission::SysBlobEncryptionKeyCreate,
+            b"sysBlobEncryptionKeyUpdate" => Permission::SysBlobEncryptionKeyUpdate,
+            b"sysBlobEncryptionKeyDestroy" => Permission::SysBlobEncryptionKeyDestroy,
+            b"sysBlobEncryptionKeyQuery" => Permission::SysBlobEncryptionKeyQuery,
+            b"taskDataStoreMigration" => Permission::TaskDataStoreMigration,
+            b"sysBackupGet" => Permission::SysBackupGet,
+            b"sysBackupUpdate" => Permission::SysBackupUpdate,
+            b"sysDataStoreShardGet" => Permission::SysDataStoreShardGet,
+            b"sysDataStoreShardCreate" => Permission::SysDataStoreShardCreate,
+            b"sysDataStoreShardUpdate" => Permission::SysDataStoreShardUpdate,
+            b"sysDataStoreShardDestroy" => Permission::SysDataStoreShardDestroy,
+            b"sysDataStoreShardQuery" => Permission::SysDataStoreShardQuery,
+            b"taskMoveAccount" => Permission::TaskMoveAccount,
         }
         .copied()
     }
@@ -8224,6 +8420,37 @@ impl EnumImpl for Permission {
             Permission::SysWebHookUpdate => "sysWebHookUpdate",
             Permission::SysWebHookDestroy => "sysWebHookDestroy",
             Permission::SysWebHookQuery => "sysWebHookQuery",
+            Permission::TaskEmailSnooze => "taskEmailSnooze",
+            Permission::JmapBlobConvert => "jmapBlobConvert",
+            Permission::JmapWebhookGet => "jmapWebhookGet",
+            Permission::JmapWebhookCreate => "jmapWebhookCreate",
+            Permission::JmapWebhookUpdate => "jmapWebhookUpdate",
+            Permission::JmapWebhookDestroy => "jmapWebhookDestroy",
+            Permission::JmapSieveScriptChanges => "jmapSieveScriptChanges",
+            Permission::JmapSieveScriptTest => "jmapSieveScriptTest",
+            Permission::JmapRecipientSuggest => "jmapRecipientSuggest",
+            Permission::TaskCalendarSubscription => "taskCalendarSubscription",
+            Permission::JmapContactCardFindDuplicates => "jmapContactCardFindDuplicates",
+            Permission::JmapContactCardMerge => "jmapContactCardMerge",
+            Permission::SysCompressionDictionaryGet => "sysCompressionDictionaryGet",
+            Permission::SysCompressionDictionaryCreate => "sysCompressionDictionaryCreate",
+            Permission::SysCompressionDictionaryUpdate => "sysCompressionDictionaryUpdate",
+            Permission::SysCompressionDictionaryDestroy => "sysCompressionDictionaryDestroy",
+            Permission::SysCompressionDictionaryQuery => "sysCompressionDictionaryQuery",
+            Permission::SysBlobEncryptionKeyGet => "sysBlobEncryptionKeyGet",
+            Permission::SysBlobEncryptionKeyCreate => "sysBlobEncryptionKeyCreate",
+            Permission::SysBlobEncryptionKeyUpdate => "sysBlobEncryptionKeyUpdate",
+            Permission::SysBlobEncryptionKeyDestroy => "sysBlobEncryptionKeyDestroy",
+            Permission::SysBlobEncryptionKeyQuery => "sysBlobEncryptionKeyQuery",
+            Permission::TaskDataStoreMigration => "taskDataStoreMigration",
+            Permission::SysBackupGet => "sysBackupGet",
+            Permission::SysBackupUpdate => "sysBackupUpdate",
+            Permission::SysDataStoreShardGet => "sysDataStoreShardGet",
+            Permission::SysDataStoreShardCreate => "sysDataStoreShardCreate",
+            Permission::SysDataStoreShardUpdate => "sysDataStoreShardUpdate",
+            Permission::SysDataStoreShardDestroy => "sysDataStoreShardDestroy",
+            Permission::SysDataStoreShardQuery => "sysDataStoreShardQuery",
+            Permission::TaskMoveAccount => "taskMoveAccount",
         }
     }
 
@@ -8893,11 +9120,42 @@ impl EnumImpl for Permission {
             656 => Some(Permission::SysWebHookUpdate),
             657 => Some(Permission::SysWebHookDestroy),
             658 => Some(Permission::SysWebHookQuery),
-            _ => None,
-        }
-    }
-
-    const COUNT: usize = 660;
+            660 => Some(Permission::TaskEmailSnooze),
+            661 => Some(Permission::JmapBlobConvert),
+            662 => Some(Permission::JmapWebhookGet),
+            663 => Some(Permission::JmapWebhookCreate),
+            664 => Some(Permission::JmapWebhookUpdate),
+            665 => Some(Permission::JmapWebhookDestroy),
+            666 => Some(Permission::JmapSieveScriptChanges),
+            667 => Some(Permission::JmapSieveScriptTest),
+            668 => Some(Permission::JmapRecipientSuggest),
+            669 => Some(Permission::TaskCalendarSubscription),
+            670 => Some(Permission::JmapContactCardFindDuplicates),
+            671 => Some(Permission::JmapContactCardMerge),
+            672 => Some(Permission::SysCompressionDictionaryGet),
+            673 => Some(Permission::SysCompressionDictionaryCreate),
+            674 => Some(Permission::SysCompressionDictionaryUpdate),
+            675 => Some(Permission::SysCompressionDictionaryDestroy),
+            676 => Some(Permission::SysCompressionDictionaryQuery),
+            677 => Some(Permission::SysBlobEncryptionKeyGet),
+            678 => Some(Permission::SysBlobEncryptionKeyCreate),
+            679 => Some(Permission::SysBlobEncryptionKeyUpdate),
+            680 => Some(Permission::SysBlobEncryptionKeyDestroy),
+            681 => Some(Permission::SysBlobEncryptionKeyQuery),
+            682 => Some(Permission::TaskDataStoreMigration),
+            683 => Some(Permission::SysBackupGet),
+            684 => Some(Permission::SysBackupUpdate),
+            685 => Some(Permission::SysDataStoreShardGet),
+            686 => Some(Permission::SysDataStoreShardCreate),
+            687 => Some(Permission::SysDataStoreShardUpdate),
+            688 => Some(Permission::SysDataStoreShardDestroy),
+            689 => Some(Permission::SysDataStoreShardQuery),
+            690 => Some(Permission::TaskMoveAccount),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 691;
 }
 
 impl serde::Serialize for Permission {
@@ -9461,6 +9719,56 @@ impl<'de> serde::Deserialize<'de> for RedisProtocol {
     }
 }
 
+impl EnumImpl for ResourceType {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"room" => ResourceType::Room,
+            b"equipment" => ResourceType::Equipment,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            ResourceType::Room => "room",
+            ResourceType::Equipment => "equipment",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(ResourceType::Room),
+            1 => Some(ResourceType::Equipment),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 2;
+}
+
+impl serde::Serialize for ResourceType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ResourceType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
 impl EnumImpl for RolesType {
     fn parse(value: &str) -> Option<Self> {
         hashify::tiny_map! {
@@ -11328,6 +11636,7 @@ impl EnumImpl for StorageQuota {
             b"maxApiKeys" => StorageQuota::MaxApiKeys,
             b"maxPublicKeys" => StorageQuota::MaxPublicKeys,
             b"maxDiskQuota" => StorageQuota::MaxDiskQuota,
+            b"maxWebhooks" => StorageQuota::MaxWebhooks,
         }
     }
 
@@ -11352,6 +11661,7 @@ impl EnumImpl for StorageQuota {
             StorageQuota::MaxApiKeys => "maxApiKeys",
             StorageQuota::MaxPublicKeys => "maxPublicKeys",
             StorageQuota::MaxDiskQuota => "maxDiskQuota",
+            StorageQuota::MaxWebhooks => "maxWebhooks",
         }
     }
 
@@ -11380,11 +11690,12 @@ impl EnumImpl for StorageQuota {
             16 => Some(StorageQuota::MaxApiKeys),
             17 => Some(StorageQuota::MaxPublicKeys),
             18 => Some(StorageQuota::MaxDiskQuota),
+            19 => Some(StorageQuota::MaxWebhooks),
             _ => None,
         }
     }
 
-    const COUNT: usize = 19;
+    const COUNT: usize = 20;
 }
 
 impl serde::Serialize for StorageQuota {
@@ -11406,6 +11717,127 @@ impl<'de> serde::Deserialize<'de> for StorageQuota {
     }
 }
 
+impl EnumImpl for StorageSizeBucket {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"upTo100KB" => StorageSizeBucket::UpTo100Kb,
+            b"upTo1MB" => StorageSizeBucket::UpTo1Mb,
+            b"upTo10MB" => StorageSizeBucket::UpTo10Mb,
+            b"upTo100MB" => StorageSizeBucket::UpTo100Mb,
+            b"over100MB" => StorageSizeBucket::Over100Mb,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            StorageSizeBucket::UpTo100Kb => "upTo100KB",
+            StorageSizeBucket::UpTo1Mb => "upTo1MB",
+            StorageSizeBucket::UpTo10Mb => "upTo10MB",
+            StorageSizeBucket::UpTo100Mb => "upTo100MB",
+            StorageSizeBucket::Over100Mb => "over100MB",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(StorageSizeBucket::UpTo100Kb),
+            1 => Some(StorageSizeBucket::UpTo1Mb),
+            2 => Some(StorageSizeBucket::UpTo10Mb),
+            3 => Some(StorageSizeBucket::UpTo100Mb),
+            4 => Some(StorageSizeBucket::Over100Mb),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 5;
+}
+
+impl serde::Serialize for StorageSizeBucket {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for StorageSizeBucket {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
+impl EnumImpl for StorageUsageType {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"email" => StorageUsageType::Email,
+            b"calendar" => StorageUsageType::Calendar,
+            b"contact" => StorageUsageType::Contact,
+            b"file" => StorageUsageType::File,
+            b"sieve" => StorageUsageType::Sieve,
+            b"archived" => StorageUsageType::Archived,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            StorageUsageType::Email => "email",
+            StorageUsageType::Calendar => "calendar",
+            StorageUsageType::Contact => "contact",
+            StorageUsageType::File => "file",
+            StorageUsageType::Sieve => "sieve",
+            StorageUsageType::Archived => "archived",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(StorageUsageType::Email),
+            1 => Some(StorageUsageType::Calendar),
+            2 => Some(StorageUsageType::Contact),
+            3 => Some(StorageUsageType::File),
+            4 => Some(StorageUsageType::Sieve),
+            5 => Some(StorageUsageType::Archived),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 6;
+}
+
+impl serde::Serialize for StorageUsageType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for StorageUsageType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
 impl EnumImpl for SubAddressingType {
     fn parse(value: &str) -> Option<Self> {
         hashify::tiny_map! {
@@ -11467,6 +11899,8 @@ impl EnumImpl for TaskAccountMaintenanceType {
             b"reindex" => TaskAccountMaintenanceType::Reindex,
             b"recalculateImapUid" => TaskAccountMaintenanceType::RecalculateImapUid,
             b"recalculateQuota" => TaskAccountMaintenanceType::RecalculateQuota,
+            b"checkConsistency" => TaskAccountMaintenanceType::CheckConsistency,
+            b"repairConsistency" => TaskAccountMaintenanceType::RepairConsistency,
         }
     }
 
@@ -11476,6 +11910,8 @@ impl EnumImpl for TaskAccountMaintenanceType {
             TaskAccountMaintenanceType::Reindex => "reindex",
             TaskAccountMaintenanceType::RecalculateImapUid => "recalculateImapUid",
             TaskAccountMaintenanceType::RecalculateQuota => "recalculateQuota",
+            TaskAccountMaintenanceType::CheckConsistency => "checkConsistency",
+            TaskAccountMaintenanceType::RepairConsistency => "repairConsistency",
         }
     }
 
@@ -11489,11 +11925,13 @@ impl EnumImpl for TaskAccountMaintenanceType {
             1 => Some(TaskAccountMaintenanceType::Reindex),
             2 => Some(TaskAccountMaintenanceType::RecalculateImapUid),
             3 => Some(TaskAccountMaintenanceType::RecalculateQuota),
+            4 => Some(TaskAccountMaintenanceType::CheckConsistency),
+            5 => Some(TaskAccountMaintenanceType::RepairConsistency),
             _ => None,
         }
     }
 
-    const COUNT: usize = 4;
+    const COUNT: usize = 6;
 }
 
 impl serde::Serialize for TaskAccountMaintenanceType {
@@ -11696,6 +12134,12 @@ impl EnumImpl for TaskStoreMaintenanceType {
             b"removeLockDav" => TaskStoreMaintenanceType::RemoveLockDav,
             b"removeSieveId" => TaskStoreMaintenanceType::RemoveSieveId,
             b"removeGreylist" => TaskStoreMaintenanceType::RemoveGreylist,
+            b"migrateBlob" => TaskStoreMaintenanceType::MigrateBlob,
+            b"trainCompressionDictionaries" => TaskStoreMaintenanceType::TrainCompressionDictionaries,
+            b"recompressBlob" => TaskStoreMaintenanceType::RecompressBlob,
+            b"rewrapBlobKeys" => TaskStoreMaintenanceType::RewrapBlobKeys,
+            b"backup" => TaskStoreMaintenanceType::Backup,
+            b"checkAccounts" => TaskStoreMaintenanceType::CheckAccounts,
         }
     }
 
@@ -11716,6 +12160,14 @@ impl EnumImpl for TaskStoreMaintenanceType {
             TaskStoreMaintenanceType::RemoveLockDav => "removeLockDav",
             TaskStoreMaintenanceType::RemoveSieveId => "removeSieveId",
             TaskStoreMaintenanceType::RemoveGreylist => "removeGreylist",
+            TaskStoreMaintenanceType::MigrateBlob => "migrateBlob",
+            TaskStoreMaintenanceType::TrainCompressionDictionaries => {
+                "trainCompressionDictionaries"
+            }
+            TaskStoreMaintenanceType::RecompressBlob => "recompressBlob",
+            TaskStoreMaintenanceType::RewrapBlobKeys => "rewrapBlobKeys",
+            TaskStoreMaintenanceType::Backup => "backup",
+            TaskStoreMaintenanceType::CheckAccounts => "checkAccounts",
         }
     }
 
@@ -11740,11 +12192,17 @@ impl EnumImpl for TaskStoreMaintenanceType {
             12 => Some(TaskStoreMaintenanceType::RemoveLockDav),
             13 => Some(TaskStoreMaintenanceType::RemoveSieveId),
             14 => Some(TaskStoreMaintenanceType::RemoveGreylist),
+            15 => Some(TaskStoreMaintenanceType::MigrateBlob),
+            16 => Some(TaskStoreMaintenanceType::TrainCompressionDictionaries),
+            17 => Some(TaskStoreMaintenanceType::RecompressBlob),
+            18 => Some(TaskStoreMaintenanceType::RewrapBlobKeys),
+            19 => Some(TaskStoreMaintenanceType::Backup),
+            20 => Some(TaskStoreMaintenanceType::CheckAccounts),
             _ => None,
         }
     }
 
-    const COUNT: usize = 15;
+    const COUNT: usize = 21;
 }
 
 impl serde::Serialize for TaskStoreMaintenanceType {
@@ -11835,6 +12293,10 @@ impl EnumImpl for TaskType {
             b"AcmeRenewal" => TaskType::AcmeRenewal,
             b"DkimManagement" => TaskType::DkimManagement,
             b"DnsManagement" => TaskType::DnsManagement,
+            b"EmailSnooze" => TaskType::EmailSnooze,
+            b"CalendarSubscription" => TaskType::CalendarSubscription,
+            b"DataStoreMigration" => TaskType::DataStoreMigration,
+            b"MoveAccount" => TaskType::MoveAccount,
         }
     }
 
@@ -11858,6 +12320,10 @@ impl EnumImpl for TaskType {
             TaskType::AcmeRenewal => "AcmeRenewal",
             TaskType::DkimManagement => "DkimManagement",
             TaskType::DnsManagement => "DnsManagement",
+            TaskType::EmailSnooze => "EmailSnooze",
+            TaskType::CalendarSubscription => "CalendarSubscription",
+            TaskType::DataStoreMigration => "DataStoreMigration",
+            TaskType::MoveAccount => "MoveAccount",
         }
     }
 
@@ -11885,11 +12351,15 @@ impl EnumImpl for TaskType {
             15 => Some(TaskType::AcmeRenewal),
             16 => Some(TaskType::DkimManagement),
             17 => Some(TaskType::DnsManagement),
+            18 => Some(TaskType::EmailSnooze),
+            19 => Some(TaskType::CalendarSubscription),
+            20 => Some(TaskType::DataStoreMigration),
+            21 => Some(TaskType::MoveAccount),
             _ => None,
         }
     }
 
-    const COUNT: usize = 18;
+    const COUNT: usize = 22;
 }
 
 impl serde::Serialize for TaskType {
@@ -26,6 +26,8 @@ pub enum ObjectInner {
     ArfExternalReport(ArfExternalReport),
     Asn(Asn),
     Authentication(Authentication),
+    Backup(Backup),
+    BlobEncryptionKey(BlobEncryptionKey),
     BlobStore(BlobStore),
     BlockedIp(BlockedIp),
     Bootstrap(Bootstrap),
@@ -36,9 +38,11 @@ pub enum ObjectInner {
     Certificate(Certificate),
     ClusterNode(ClusterNode),
     ClusterRole(ClusterRole),
+    CompressionDictionary(CompressionDictionary),
     Coordinator(Coordinator),
     DataRetention(DataRetention),
     DataStore(DataStore),
+    DataStoreShard(DataStoreShard),
     Directory(Directory),
     DkimReportSettings(DkimReportSettings),
     DkimSignature(DkimSignature),
@@ -148,6 +152,8 @@ pub enum ObjectType {
     ArfExternalReport = 13,
     Asn = 14,
     Authentication = 15,
+    Backup = 119,
+    BlobEncryptionKey = 118,
     BlobStore = 16,
     BlockedIp = 17,
     Bootstrap = 18,
@@ -158,9 +164,11 @@ pub enum ObjectType {
     Certificate = 23,
     ClusterNode = 24,
     ClusterRole = 25,
+    CompressionDictionary = 117,
     Coordinator = 26,
     DataRetention = 27,
     DataStore = 28,
+    DataStoreShard = 120,
     Directory = 29,
     DkimReportSettings = 30,
     DkimSignature = 31,
@@ -301,6 +309,7 @@ pub enum Property {
     AllowRelaying = 348,
     AllowSpamTraining = 369,
     AllowedEndpoints = 398,
+    AllowedGroupIds = 939,
     AllowedIps = 49,
     AllowedNotifyUris = 712,
     Alpha = 388,
@@ -341,6 +350,7 @@ pub enum Property {
     AuthUsername = 502,
     AuthenticatedAs = 740,
     AuthenticationResults = 69,
+    AutoAccept = 934,
     AutoAddInvitations = 171,
     AutoUpdateFrequency = 53,
     BaseDn = 463,
@@ -357,12 +367,14 @@ pub enum Property {
     BlobStore = 126,
     BlockCount = 766,
     Body = 38,
+    BookingPolicy = 932,
     Brokers = 459,
     Bucket = 658,
     BufferSize = 656,
     Buffered = 863,
     CacheSize = 929,
     Canonicalization = 216,
+    Capacity = 931,
     CapacityClient = 584,
     CapacityReadBuffer = 585,
     CapacitySubscription = 586,
@@ -371,6 +383,7 @@ pub enum Property {
     Certificate = 176,
     CertificateManagement = 342,
     ChallengeType = 10,
+    ChangedKeys = 951,
     ChangesMaxResults = 435,
     Chunking = 517,
     ClaimGroups = 612,
@@ -382,6 +395,7 @@ pub enum Property {
     ClientSecret = 878,
     ClientToken = 889,
     ClusterFile = 382,
+    Cold = 942,
     ColumnClass = 781,
     ColumnDescription = 782,
     ColumnEmail = 779,
@@ -389,6 +403,7 @@ pub enum Property {
     Comment = 240,
     CompartmentOcid = 905,
     CompressionAlgorithm = 359,
+    CompressionLevel = 945,
     Concurrency = 304,
     Condition = 34,
     Confidence = 760,
@@ -402,6 +417,7 @@ pub enum Property {
     Contacts = 133,
     Container = 117,
     Content = 65,
+    ContentClass = 946,
     ContentTypes = 758,
     Contents = 708,
     Context = 877,
@@ -427,6 +443,7 @@ pub enum Property {
     DateRangeStart = 845,
     Day = 192,
     DeadPropertyMaxSize = 868,
+    DeclineConflicts = 935,
     DefaultAdminRoleIds = 108,
     DefaultCertificateId = 790,
     DefaultDisplayName = 20,
@@ -449,6 +466,7 @@ pub enum Property {
     DefaultUserRoleIds = 105,
     Definition = 235,
     Delay = 825,
+    DelegateIds = 933,
     DeleteAfter = 229,
     DeleteAfterUse = 777,
     DeliverAt = 238,
@@ -457,6 +475,7 @@ pub enum Property {
     DeliveryResult = 82,
     Depth = 381,
     Description = 6,
+    Destination = 952,
     Details = 297,
     Directory = 12,
     DirectoryId = 104,
@@ -614,6 +633,7 @@ pub enum Property {
     FromAddress = 39,
     FromEmail = 165,
     FromName = 40,
+    FullBackupInterval = 953,
     FutureRelease = 521,
     GenerateDkimKeys = 124,
     Generator = 918,
@@ -632,6 +652,7 @@ pub enum Property {
     Host = 333,
     HostedZoneId = 331,
     Hostname = 185,
+    Hot = 943,
     Hour = 190,
     HttpAuth = 32,
     HttpHeaders = 33,
@@ -689,12 +710,14 @@ pub enum Property {
     IsTls = 741,
     Issuer = 181,
     IssuerUrl = 606,
+    ItemCountBySize = 957,
     ItipMaxSize = 172,
     Jitter = 824,
     Key = 334,
     KeyName = 337,
     KeyPrefix = 120,
     KeyValues = 853,
+    KeysCopied = 949,
     L1Ratio = 391,
     L2Ratio = 392,
     LastRenewal = 186,
@@ -802,6 +825,8 @@ pub enum Property {
     MaxVCardSize = 22,
     MaxVarNameLength = 725,
     MaxVarSize = 706,
+    MaxVersions = 940,
+    MaxWebhooks = 965,
     MemberGroupIds = 864,
     MemberTenantId = 19,
     Message = 92,
@@ -812,6 +837,7 @@ pub enum Property {
     Metrics = 497,
     MetricsCollectionInterval = 207,
     MetricsPolicy = 498,
+    MigrateAfter = 944,
     MinHamSamples = 731,
     MinRetryWait = 649,
     MinSpamSamples = 732,
@@ -927,6 +953,7 @@ pub enum Property {
     ProxyTrustedNetworks = 792,
     PublicKey = 218,
     PublishRecords = 302,
+    PublishedCalendars = 964,
     PushAttemptWait = 448,
     PushMaxAttempts = 449,
     PushRequestTimeout = 452,
@@ -984,6 +1011,7 @@ pub enum Property {
     RequireTls = 525,
     ReservoirCapacity = 733,
     ResourceGroup = 880,
+    ResourceType = 930,
     ResourceUrl = 51,
     ResponseCode = 212,
     ResponseEnhanced = 213,
@@ -995,6 +1023,8 @@ pub enum Property {
     ResponsePosExplanation = 763,
     Result = 233,
     ResultType = 832,
+    RetainDaily = 954,
+    RetainWeekly = 955,
     RetireAfter = 228,
     Retry = 420,
     RetryCount = 640,
@@ -1009,6 +1039,7 @@ pub enum Property {
     RotateAfter = 227,
     Route = 540,
     Rua = 236,
+    SampleCount = 947,
     Sandbox = 896,
     SasToken = 119,
     SaslMechanisms = 549,
@@ -1045,6 +1076,7 @@ pub enum Property {
     Services = 794,
     SessionToken = 329,
     SetMaxObjects = 440,
+    ShardId = 956,
     ShardIndex = 830,
     SharedSecret = 895,
     Sig0Algorithm = 336,
@@ -1055,6 +1087,7 @@ pub enum Property {
     SkipDeploy = 885,
     SkipFirst = 423,
     SmtpGreeting = 552,
+    SnapshotInterval = 962,
     SnippetMaxResults = 441,
     SocketBacklog = 591,
     SocketNoDelay = 592,
@@ -1091,11 +1124,14 @@ pub enum Property {
     SubjectAlternativeNames = 178,
     Subscribe = 368,
     SubscriptionId = 879,
+    SubspacesCopied = 950,
     Sum = 494,
     Summary = 808,
     SupportedLanguages = 666,
+    SyncWrites = 963,
     Tag = 748,
     Tags = 746,
+    Target = 948,
     TaskTypes = 189,
     Tasks = 187,
     TcpOnError = 307,
@@ -1136,6 +1172,7 @@ pub enum Property {
     TlsTimeout = 573,
     To = 42,
     Token = 888,
+    TopAccounts = 961,
     TotalDeadline = 817,
     TotalFailedSessions = 850,
     TotalSuccessfulSessions = 849,
@@ -1162,6 +1199,9 @@ pub enum Property {
     UseTls = 309,
     UseXForwarded = 402,
     UsedDiskQuota = 395,
+    UsedDiskQuotaByMailbox = 958,
+    UsedDiskQuotaBySize = 959,
+    UsedDiskQuotaByType = 960,
     UserAgent = 79,
     UserCodeExpiry = 620,
     UserOcid = 901,
@@ -1172,6 +1212,7 @@ pub enum Property {
     VariableName = 675,
     VerifyAfterWrite = 874,
     Version = 80,
+    VersionRetention = 941,
     ViewName = 884,
     Vrfy = 526,
     WaitOnFail = 548,
@@ -1181,6 +1222,9 @@ pub enum Property {
     WebsocketHeartbeat = 455,
     WebsocketThrottle = 456,
     WebsocketTimeout = 457,
+    WeekdaysOnly = 938,
+    WorkingHoursEnd = 937,
+    WorkingHoursStart = 936,
     Zone = 749,
     ZoneIpV4 = 98,
     ZoneIpV6 = 99,
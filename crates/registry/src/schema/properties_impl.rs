@@ -28,6 +28,8 @@ impl EnumImpl for ObjectType {
             b"ArfExternalReport" => ObjectType::ArfExternalReport,
             b"Asn" => ObjectType::Asn,
             b"Authentication" => ObjectType::Authentication,
+            b"Backup" => ObjectType::Backup,
+            b"BlobEncryptionKey" => ObjectType::BlobEncryptionKey,
             b"BlobStore" => ObjectType::BlobStore,
             b"BlockedIp" => ObjectType::BlockedIp,
             b"Bootstrap" => ObjectType::Bootstrap,
@@ -38,9 +40,11 @@ impl EnumImpl for ObjectType {
             b"Certificate" => ObjectType::Certificate,
             b"ClusterNode" => ObjectType::ClusterNode,
             b"ClusterRole" => ObjectType::ClusterRole,
+            b"CompressionDictionary" => ObjectType::CompressionDictionary,
             b"Coordinator" => ObjectType::Coordinator,
             b"DataRetention" => ObjectType::DataRetention,
             b"DataStore" => ObjectType::DataStore,
+            b"DataStoreShard" => ObjectType::DataStoreShard,
             b"Directory" => ObjectType::Directory,
             b"DkimReportSettings" => ObjectType::DkimReportSettings,
             b"DkimSignature" => ObjectType::DkimSignature,
@@ -150,6 +154,8 @@ impl EnumImpl for ObjectType {
             ObjectType::ArfExternalReport => "ArfExternalReport",
             ObjectType::Asn => "Asn",
             ObjectType::Authentication => "Authentication",
+            ObjectType::Backup => "Backup",
+            ObjectType::BlobEncryptionKey => "BlobEncryptionKey",
             ObjectType::BlobStore => "BlobStore",
             ObjectType::BlockedIp => "BlockedIp",
             ObjectType::Bootstrap => "Bootstrap",
@@ -160,9 +166,11 @@ impl EnumImpl for ObjectType {
             ObjectType::Certificate => "Certificate",
             ObjectType::ClusterNode => "ClusterNode",
             ObjectType::ClusterRole => "ClusterRole",
+            ObjectType::CompressionDictionary => "CompressionDictionary",
             ObjectType::Coordinator => "Coordinator",
             ObjectType::DataRetention => "DataRetention",
             ObjectType::DataStore => "DataStore",
+            ObjectType::DataStoreShard => "DataStoreShard",
             ObjectType::Directory => "Directory",
             ObjectType::DkimReportSettings => "DkimReportSettings",
             ObjectType::DkimSignature => "DkimSignature",
@@ -276,6 +284,8 @@ impl EnumImpl for ObjectType {
             13 => Some(ObjectType::ArfExternalReport),
             14 => Some(ObjectType::Asn),
             15 => Some(ObjectType::Authentication),
+            118 => Some(ObjectType::BlobEncryptionKey),
+            119 => Some(ObjectType::Backup),
             16 => Some(ObjectType::BlobStore),
             17 => Some(ObjectType::BlockedIp),
             18 => Some(ObjectType::Bootstrap),
@@ -286,9 +296,11 @@ impl EnumImpl for ObjectType {
             23 => Some(ObjectType::Certificate),
             24 => Some(ObjectType::ClusterNode),
             25 => Some(ObjectType::ClusterRole),
+            117 => Some(ObjectType::CompressionDictionary),
             26 => Some(ObjectType::Coordinator),
             27 => Some(ObjectType::DataRetention),
             28 => Some(ObjectType::DataStore),
+            120 => Some(ObjectType::DataStoreShard),
             29 => Some(ObjectType::Directory),
             30 => Some(ObjectType::DkimReportSettings),
             31 => Some(ObjectType::DkimSignature),
@@ -381,7 +393,7 @@ impl EnumImpl for ObjectType {
         }
     }
 
-    const COUNT: usize = 117;
+    const COUNT: usize = 121;
 }
 
 impl serde::Serialize for ObjectType {
@@ -454,6 +466,7 @@ impl EnumImpl for Property {
             b"allowRelaying" => Property::AllowRelaying,
             b"allowSpamTraining" => Property::AllowSpamTraining,
             b"allowedEndpoints" => Property::AllowedEndpoints,
+            b"allowedGroupIds" => Property::AllowedGroupIds,
             b"allowedIps" => Property::AllowedIps,
             b"allowedNotifyUris" => Property::AllowedNotifyUris,
             b"alpha" => Property::Alpha,
@@ -494,6 +507,7 @@ impl EnumImpl for Property {
             b"authUsername" => Property::AuthUsername,
             b"authenticatedAs" => Property::AuthenticatedAs,
             b"authenticationResults" => Property::AuthenticationResults,
+            b"autoAccept" => Property::AutoAccept,
             b"autoAddInvitations" => Property::AutoAddInvitations,
             b"autoUpdateFrequency" => Property::AutoUpdateFrequency,
             b"baseDn" => Property::BaseDn,
@@ -510,12 +524,14 @@ impl EnumImpl for Property {
             b"blobStore" => Property::BlobStore,
             b"blockCount" => Property::BlockCount,
             b"body" => Property::Body,
+            b"bookingPolicy" => Property::BookingPolicy,
             b"brokers" => Property::Brokers,
             b"bucket" => Property::Bucket,
             b"bufferSize" => Property::BufferSize,
             b"buffered" => Property::Buffered,
             b"cacheSize" => Property::CacheSize,
             b"canonicalization" => Property::Canonicalization,
+            b"capacity" => Property::Capacity,
             b"capacityClient" => Property::CapacityClient,
             b"capacityReadBuffer" => Property::CapacityReadBuffer,
             b"capacitySubscription" => Property::CapacitySubscription,
@@ -524,6 +540,7 @@ impl EnumImpl for Property {
             b"certificate" => Property::Certificate,
             b"certificateManagement" => Property::CertificateManagement,
             b"challengeType" => Property::ChallengeType,
+            b"changedKeys" => Property::ChangedKeys,
             b"changesMaxResults" => Property::ChangesMaxResults,
             b"chunking" => Property::Chunking,
             b"claimGroups" => Property::ClaimGroups,
@@ -535,6 +552,7 @@ impl EnumImpl for Property {
             b"clientSecret" => Property::ClientSecret,
             b"clientToken" => Property::ClientToken,
             b"clusterFile" => Property::ClusterFile,
+            b"cold" => Property::Cold,
             b"columnClass" => Property::ColumnClass,
             b"columnDescription" => Property::ColumnDescription,
             b"columnEmail" => Property::ColumnEmail,
@@ -542,6 +560,7 @@ impl EnumImpl for Property {
             b"comment" => Property::Comment,
             b"compartmentOcid" => Property::CompartmentOcid,
             b"compressionAlgorithm" => Property::CompressionAlgorithm,
+            b"compressionLevel" => Property::CompressionLevel,
             b"concurrency" => Property::Concurrency,
             b"condition" => Property::Condition,
             b"confidence" => Property::Confidence,
@@ -555,6 +574,7 @@ impl EnumImpl for Property {
             b"contacts" => Property::Contacts,
             b"container" => Property::Container,
             b"content" => Property::Content,
+            b"contentClass" => Property::ContentClass,
             b"contentTypes" => Property::ContentTypes,
             b"contents" => Property::Contents,
             b"context" => Property::Context,
@@ -580,6 +600,7 @@ impl EnumImpl for Property {
             b"dateRangeStart" => Property::DateRangeStart,
             b"day" => Property::Day,
             b"deadPropertyMaxSize" => Property::DeadPropertyMaxSize,
+            b"declineConflicts" => Property::DeclineConflicts,
             b"defaultAdminRoleIds" => Property::DefaultAdminRoleIds,
             b"defaultCertificateId" => Property::DefaultCertificateId,
             b"defaultDisplayName" => Property::DefaultDisplayName,
@@ -602,6 +623,7 @@ impl EnumImpl for Property {
             b"defaultUserRoleIds" => Property::DefaultUserRoleIds,
             b"definition" => Property::Definition,
             b"delay" => Property::Delay,
+            b"delegateIds" => Property::DelegateIds,
             b"deleteAfter" => Property::DeleteAfter,
             b"deleteAfterUse" => Property::DeleteAfterUse,
             b"deliverAt" => Property::DeliverAt,
@@ -610,6 +632,7 @@ impl EnumImpl for Property {
             b"deliveryResult" => Property::DeliveryResult,
             b"depth" => Property::Depth,
             b"description" => Property::Description,
+            b"destination" => Property::Destination,
             b"details" => Property::Details,
             b"directory" => Property::Directory,
             b"directoryId" => Property::DirectoryId,
@@ -767,6 +790,7 @@ impl EnumImpl for Property {
             b"fromAddress" => Property::FromAddress,
             b"fromEmail" => Property::FromEmail,
             b"fromName" => Property::FromName,
+            b"fullBackupInterval" => Property::FullBackupInterval,
             b"futureRelease" => Property::FutureRelease,
             b"generateDkimKeys" => Property::GenerateDkimKeys,
             b"generator" => Property::Generator,
@@ -785,6 +809,7 @@ impl EnumImpl for Property {
             b"host" => Property::Host,
             b"hostedZoneId" => Property::HostedZoneId,
             b"hostname" => Property::Hostname,
+            b"hot" => Property::Hot,
             b"hour" => Property::Hour,
             b"httpAuth" => Property::HttpAuth,
             b"httpHeaders" => Property::HttpHeaders,
@@ -842,12 +867,14 @@ impl EnumImpl for Property {
             b"isTls" => Property::IsTls,
             b"issuer" => Property::Issuer,
             b"issuerUrl" => Property::IssuerUrl,
+            b"itemCountBySize" => Property::ItemCountBySize,
             b"itipMaxSize" => Property::ItipMaxSize,
             b"jitter" => Property::Jitter,
             b"key" => Property::Key,
             b"keyName" => Property::KeyName,
             b"keyPrefix" => Property::KeyPrefix,
             b"keyValues" => Property::KeyValues,
+            b"keysCopied" => Property::KeysCopied,
             b"l1Ratio" => Property::L1Ratio,
             b"l2Ratio" => Property::L2Ratio,
             b"lastRenewal" => Property::LastRenewal,
@@ -955,6 +982,8 @@ impl EnumImpl for Property {
             b"maxVCardSize" => Property::MaxVCardSize,
             b"maxVarNameLength" => Property::MaxVarNameLength,
             b"maxVarSize" => Property::MaxVarSize,
+            b"maxVersions" => Property::MaxVersions,
+            b"maxWebhooks" => Property::MaxWebhooks,
             b"memberGroupIds" => Property::MemberGroupIds,
             b"memberTenantId" => Property::MemberTenantId,
             b"message" => Property::Message,
@@ -965,6 +994,7 @@ impl EnumImpl for Property {
             b"metrics" => Property::Metrics,
             b"metricsCollectionInterval" => Property::MetricsCollectionInterval,
             b"metricsPolicy" => Property::MetricsPolicy,
+            b"migrateAfter" => Property::MigrateAfter,
             b"minHamSamples" => Property::MinHamSamples,
             b"minRetryWait" => Property::MinRetryWait,
             b"minSpamSamples" => Property::MinSpamSamples,
@@ -1080,6 +1110,7 @@ impl EnumImpl for Property {
             b"proxyTrustedNetworks" => Property::ProxyTrustedNetworks,
             b"publicKey" => Property::PublicKey,
             b"publishRecords" => Property::PublishRecords,
+            b"publishedCalendars" => Property::PublishedCalendars,
             b"pushAttemptWait" => Property::PushAttemptWait,
             b"pushMaxAttempts" => Property::PushMaxAttempts,
             b"pushRequestTimeout" => Property::PushRequestTimeout,
@@ -1137,6 +1168,7 @@ impl EnumImpl for Property {
             b"requireTls" => Property::RequireTls,
             b"reservoirCapacity" => Property::ReservoirCapacity,
             b"resourceGroup" => Property::ResourceGroup,
+            b"resourceType" => Property::ResourceType,
             b"resourceUrl" => Property::ResourceUrl,
             b"responseCode" => Property::ResponseCode,
             b"responseEnhanced" => Property::ResponseEnhanced,
@@ -1148,6 +1180,8 @@ impl EnumImpl for Property {
             b"responsePosExplanation" => Property::ResponsePosExplanation,
             b"result" => Property::Result,
             b"resultType" => Property::ResultType,
+            b"retainDaily" => Property::RetainDaily,
+            b"retainWeekly" => Property::RetainWeekly,
             b"retireAfter" => Property::RetireAfter,
             b"retry" => Property::Retry,
             b"retryCount" => Property::RetryCount,
@@ -1162,6 +1196,7 @@ impl EnumImpl for Property {
             b"rotateAfter" => Property::RotateAfter,
             b"route" => Property::Route,
             b"rua" => Property::Rua,
+            b"sampleCount" => Property::SampleCount,
             b"sandbox" => Property::Sandbox,
             b"sasToken" => Property::SasToken,
             b"saslMechanisms" => Property::SaslMechanisms,
@@ -1198,6 +1233,7 @@ impl EnumImpl for Property {
             b"services" => Property::Services,
             b"sessionToken" => Property::SessionToken,
             b"setMaxObjects" => Property::SetMaxObjects,
+            b"shardId" => Property::ShardId,
             b"shardIndex" => Property::ShardIndex,
             b"sharedSecret" => Property::SharedSecret,
             b"sig0Algorithm" => Property::Sig0Algorithm,
@@ -1208,6 +1244,7 @@ impl EnumImpl for Property {
             b"skipDeploy" => Property::SkipDeploy,
             b"skipFirst" => Property::SkipFirst,
             b"smtpGreeting" => Property::SmtpGreeting,
+            b"snapshotInterval" => Property::SnapshotInterval,
             b"snippetMaxResults" => Property::SnippetMaxResults,
             b"socketBacklog" => Property::SocketBacklog,
             b"socketNoDelay" => Property::SocketNoDelay,
@@ -1244,11 +1281,14 @@ impl EnumImpl for Property {
             b"subjectAlternativeNames" => Property::SubjectAlternativeNames,
             b"subscribe" => Property::Subscribe,
             b"subscriptionId" => Property::SubscriptionId,
+            b"subspacesCopied" => Property::SubspacesCopied,
             b"sum" => Property::Sum,
             b"summary" => Property::Summary,
             b"supportedLanguages" => Property::SupportedLanguages,
+            b"syncWrites" => Property::SyncWrites,
             b"tag" => Property::Tag,
             b"tags" => Property::Tags,
+            b"target" => Property::Target,
             b"taskTypes" => Property::TaskTypes,
             b"tasks" => Property::Tasks,
             b"tcpOnError" => Property::TcpOnError,
@@ -1289,6 +1329,7 @@ impl EnumImpl for Property {
             b"tlsTimeout" => Property::TlsTimeout,
             b"to" => Property::To,
             b"token" => Property::Token,
+            b"topAccounts" => Property::TopAccounts,
             b"totalDeadline" => Property::TotalDeadline,
             b"totalFailedSessions" => Property::TotalFailedSessions,
             b"totalSuccessfulSessions" => Property::TotalSuccessfulSessions,
@@ -1315,6 +1356,9 @@ impl EnumImpl for Property {
             b"useTls" => Property::UseTls,
             b"useXForwarded" => Property::UseXForwarded,
             b"usedDiskQuota" => Property::UsedDiskQuota,
+            b"usedDiskQuotaByMailbox" => Property::UsedDiskQuotaByMailbox,
+            b"usedDiskQuotaBySize" => Property::UsedDiskQuotaBySize,
+            b"usedDiskQuotaByType" => Property::UsedDiskQuotaByType,
             b"userAgent" => Property::UserAgent,
             b"userCodeExpiry" => Property::UserCodeExpiry,
             b"userOcid" => Property::UserOcid,
@@ -1325,6 +1369,7 @@ impl EnumImpl for Property {
             b"variableName" => Property::VariableName,
             b"verifyAfterWrite" => Property::VerifyAfterWrite,
             b"version" => Property::Version,
+            b"versionRetention" => Property::VersionRetention,
             b"viewName" => Property::ViewName,
             b"vrfy" => Property::Vrfy,
             b"waitOnFail" => Property::WaitOnFail,
@@ -1334,6 +1379,9 @@ impl EnumImpl for Property {
             b"websocketHeartbeat" => Property::WebsocketHeartbeat,
             b"websocketThrottle" => Property::WebsocketThrottle,
             b"websocketTimeout" => Property::WebsocketTimeout,
+            b"weekdaysOnly" => Property::WeekdaysOnly,
+            b"workingHoursEnd" => Property::WorkingHoursEnd,
+            b"workingHoursStart" => Property::WorkingHoursStart,
             b"zone" => Property::Zone,
             b"zoneIpV4" => Property::ZoneIpV4,
             b"zoneIpV6" => Property::ZoneIpV6,
@@ -1389,6 +1437,7 @@ impl EnumImpl for Property {
             Property::AllowRelaying => "allowRelaying",
             Property::AllowSpamTraining => "allowSpamTraining",
             Property::AllowedEndpoints => "allowedEndpoints",
+            Property::AllowedGroupIds => "allowedGroupIds",
             Property::AllowedIps => "allowedIps",
             Property::AllowedNotifyUris => "allowedNotifyUris",
             Property::Alpha => "alpha",
@@ -1429,6 +1478,7 @@ impl EnumImpl for Property {
             Property::AuthUsername => "authUsername",
             Property::AuthenticatedAs => "authenticatedAs",
             Property::AuthenticationResults => "authenticationResults",
+            Property::AutoAccept => "autoAccept",
             Property::AutoAddInvitations => "autoAddInvitations",
             Property::AutoUpdateFrequency => "autoUpdateFrequency",
             Property::BaseDn => "baseDn",
@@ -1445,12 +1495,14 @@ impl EnumImpl for Property {
             Property::BlobStore => "blobStore",
             Property::BlockCount => "blockCount",
             Property::Body => "body",
+            Property::BookingPolicy => "bookingPolicy",
             Property::Brokers => "brokers",
             Property::Bucket => "bucket",
             Property::BufferSize => "bufferSize",
             Property::Buffered => "buffered",
             Property::CacheSize => "cacheSize",
             Property::Canonicalization => "canonicalization",
+            Property::Capacity => "capacity",
             Property::CapacityClient => "capacityClient",
             Property::CapacityReadBuffer => "capacityReadBuffer",
             Property::CapacitySubscription => "capacitySubscription",
@@ -1459,6 +1511,7 @@ impl EnumImpl for Property {
             Property::Certificate => "certificate",
             Property::CertificateManagement => "certificateManagement",
             Property::ChallengeType => "challengeType",
+            Property::ChangedKeys => "changedKeys",
             Property::ChangesMaxResults => "changesMaxResults",
             Property::Chunking => "chunking",
             Property::ClaimGroups => "claimGroups",
@@ -1470,6 +1523,7 @@ impl EnumImpl for Property {
             Property::ClientSecret => "clientSecret",
             Property::ClientToken => "clientToken",
             Property::ClusterFile => "clusterFile",
+            Property::Cold => "cold",
             Property::ColumnClass => "columnClass",
             Property::ColumnDescription => "columnDescription",
             Property::ColumnEmail => "columnEmail",
@@ -1477,6 +1531,7 @@ impl EnumImpl for Property {
             Property::Comment => "comment",
             Property::CompartmentOcid => "compartmentOcid",
             Property::CompressionAlgorithm => "compressionAlgorithm",
+            Property::CompressionLevel => "compressionLevel",
             Property::Concurrency => "concurrency",
             Property::Condition => "condition",
             Property::Confidence => "confidence",
@@ -1490,6 +1545,7 @@ impl EnumImpl for Property {
             Property::Contacts => "contacts",
             Property::Container => "container",
             Property::Content => "content",
+            Property::ContentClass => "contentClass",
             Property::ContentTypes => "contentTypes",
             Property::Contents => "contents",
             Property::Context => "context",
@@ -1515,6 +1571,7 @@ impl EnumImpl for Property {
             Property::DateRangeStart => "dateRangeStart",
             Property::Day => "day",
             Property::DeadPropertyMaxSize => "deadPropertyMaxSize",
+            Property::DeclineConflicts => "declineConflicts",
             Property::DefaultAdminRoleIds => "defaultAdminRoleIds",
             Property::DefaultCertificateId => "defaultCertificateId",
             Property::DefaultDisplayName => "defaultDisplayName",
@@ -1537,6 +1594,7 @@ impl EnumImpl for Property {
             Property::DefaultUserRoleIds => "defaultUserRoleIds",
             Property::Definition => "definition",
             Property::Delay => "delay",
+            Property::DelegateIds => "delegateIds",
             Property::DeleteAfter => "deleteAfter",
             Property::DeleteAfterUse => "deleteAfterUse",
             Property::DeliverAt => "deliverAt",
@@ -1545,6 +1603,7 @@ impl EnumImpl for Property {
             Property::DeliveryResult => "deliveryResult",
             Property::Depth => "depth",
             Property::Description => "description",
+            Property::Destination => "destination",
             Property::Details => "details",
             Property::Directory => "directory",
             Property::DirectoryId => "directoryId",
@@ -1702,6 +1761,7 @@ impl EnumImpl for Property {
             Property::FromAddress => "fromAddress",
             Property::FromEmail => "fromEmail",
             Property::FromName => "fromName",
+            Property::FullBackupInterval => "fullBackupInterval",
             Property::FutureRelease => "futureRelease",
             Property::GenerateDkimKeys => "generateDkimKeys",
             Property::Generator => "generator",
@@ -1720,6 +1780,7 @@ impl EnumImpl for Property {
             Property::Host => "host",
             Property::HostedZoneId => "hostedZoneId",
             Property::Hostname => "hostname",
+            Property::Hot => "hot",
             Property::Hour => "hour",
             Property::HttpAuth => "httpAuth",
             Property::HttpHeaders => "httpHeaders",
@@ -1777,12 +1838,14 @@ impl EnumImpl for Property {
             Property::IsTls => "isTls",
             Property::Issuer => "issuer",
             Property::IssuerUrl => "issuerUrl",
+            Property::ItemCountBySize => "itemCountBySize",
             Property::ItipMaxSize => "itipMaxSize",
             Property::Jitter => "jitter",
             Property::Key => "key",
             Property::KeyName => "keyName",
             Property::KeyPrefix => "keyPrefix",
             Property::KeyValues => "keyValues",
+            Property::KeysCopied => "keysCopied",
             Property::L1Ratio => "l1Ratio",
             Property::L2Ratio => "l2Ratio",
             Property::LastRenewal => "lastRenewal",
@@ -1890,6 +1953,8 @@ impl EnumImpl for Property {
             Property::MaxVCardSize => "maxVCardSize",
             Property::MaxVarNameLength => "maxVarNameLength",
             Property::MaxVarSize => "maxVarSize",
+            Property::MaxVersions => "maxVersions",
+            Property::MaxWebhooks => "maxWebhooks",
             Property::MemberGroupIds => "memberGroupIds",
             Property::MemberTenantId => "memberTenantId",
             Property::Message => "message",
@@ -1900,6 +1965,7 @@ impl EnumImpl for Property {
             Property::Metrics => "metrics",
             Property::MetricsCollectionInterval => "metricsCollectionInterval",
             Property::MetricsPolicy => "metricsPolicy",
+            Property::MigrateAfter => "migrateAfter",
             Property::MinHamSamples => "minHamSamples",
             Property::MinRetryWait => "minRetryWait",
             Property::MinSpamSamples => "minSpamSamples",
@@ -2015,6 +2081,7 @@ impl EnumImpl for Property {
             Property::ProxyTrustedNetworks => "proxyTrustedNetworks",
             Property::PublicKey => "publicKey",
             Property::PublishRecords => "publishRecords",
+            Property::PublishedCalendars => "publishedCalendars",
             Property::PushAttemptWait => "pushAttemptWait",
             Property::PushMaxAttempts => "pushMaxAttempts",
             Property::PushRequestTimeout => "pushRequestTimeout",
@@ -2072,6 +2139,7 @@ impl EnumImpl for Property {
             Property::RequireTls => "requireTls",
             Property::ReservoirCapacity => "reservoirCapacity",
             Property::ResourceGroup => "resourceGroup",
+            Property::ResourceType => "resourceType",
             Property::ResourceUrl => "resourceUrl",
             Property::ResponseCode => "responseCode",
             Property::ResponseEnhanced => "responseEnhanced",
@@ -2083,6 +2151,8 @@ impl EnumImpl for Property {
             Property::ResponsePosExplanation => "responsePosExplanation",
             Property::Result => "result",
             Property::ResultType => "resultType",
+            Property::RetainDaily => "retainDaily",
+            Property::RetainWeekly => "retainWeekly",
             Property::RetireAfter => "retireAfter",
             Property::Retry => "retry",
             Property::RetryCount => "retryCount",
@@ -2097,6 +2167,7 @@ impl EnumImpl for Property {
             Property::RotateAfter => "rotateAfter",
             Property::Route => "route",
             Property::Rua => "rua",
+            Property::SampleCount => "sampleCount",
             Property::Sandbox => "sandbox",
             Property::SasToken => "sasToken",
             Property::SaslMechanisms => "saslMechanisms",
@@ -2133,6 +2204,7 @@ impl EnumImpl for Property {
             Property::Services => "services",
             Property::SessionToken => "sessionToken",
             Property::SetMaxObjects => "setMaxObjects",
+            Property::ShardId => "shardId",
             Property::ShardIndex => "shardIndex",
             Property::SharedSecret => "sharedSecret",
             Property::Sig0Algorithm => "sig0Algorithm",
@@ -2143,6 +2215,7 @@ impl EnumImpl for Property {
             Property::SkipDeploy => "skipDeploy",
             Property::SkipFirst => "skipFirst",
             Property::SmtpGreeting => "smtpGreeting",
+            Property::SnapshotInterval => "snapshotInterval",
             Property::SnippetMaxResults => "snippetMaxResults",
             Property::SocketBacklog => "socketBacklog",
             Property::SocketNoDelay => "socketNoDelay",
@@ -2179,11 +2252,14 @@ impl EnumImpl for Property {
             Property::SubjectAlternativeNames => "subjectAlternativeNames",
             Property::Subscribe => "subscribe",
             Property::SubscriptionId => "subscriptionId",
+            Property::SubspacesCopied => "subspacesCopied",
             Property::Sum => "sum",
             Property::Summary => "summary",
             Property::SupportedLanguages => "supportedLanguages",
+            Property::SyncWrites => "syncWrites",
             Property::Tag => "tag",
             Property::Tags => "tags",
+            Property::Target => "target",
             Property::TaskTypes => "taskTypes",
             Property::Tasks => "tasks",
             Property::TcpOnError => "tcpOnError",
@@ -2224,6 +2300,7 @@ impl EnumImpl for Property {
             Property::TlsTimeout => "tlsTimeout",
             Property::To => "to",
             Property::Token => "token",
+            Property::TopAccounts => "topAccounts",
             Property::TotalDeadline => "totalDeadline",
             Property::TotalFailedSessions => "totalFailedSessions",
             Property::TotalSuccessfulSessions => "totalSuccessfulSessions",
@@ -2250,6 +2327,9 @@ impl EnumImpl for Property {
             Property::UseTls => "useTls",
             Property::UseXForwarded => "useXForwarded",
             Property::UsedDiskQuota => "usedDiskQuota",
+            Property::UsedDiskQuotaByMailbox => "usedDiskQuotaByMailbox",
+            Property::UsedDiskQuotaBySize => "usedDiskQuotaBySize",
+            Property::UsedDiskQuotaByType => "usedDiskQuotaByType",
             Property::UserAgent => "userAgent",
             Property::UserCodeExpiry => "userCodeExpiry",
             Property::UserOcid => "userOcid",
@@ -2260,6 +2340,7 @@ impl EnumImpl for Property {
             Property::VariableName => "variableName",
             Property::VerifyAfterWrite => "verifyAfterWrite",
             Property::Version => "version",
+            Property::VersionRetention => "versionRetention",
             Property::ViewName => "viewName",
             Property::Vrfy => "vrfy",
             Property::WaitOnFail => "waitOnFail",
@@ -2269,6 +2350,9 @@ impl EnumImpl for Property {
             Property::WebsocketHeartbeat => "websocketHeartbeat",
             Property::WebsocketThrottle => "websocketThrottle",
             Property::WebsocketTimeout => "websocketTimeout",
+            Property::WeekdaysOnly => "weekdaysOnly",
+            Property::WorkingHoursEnd => "workingHoursEnd",
+            Property::WorkingHoursStart => "workingHoursStart",
             Property::Zone => "zone",
             Property::ZoneIpV4 => "zoneIpV4",
             Property::ZoneIpV6 => "zoneIpV6",
@@ -2328,6 +2412,7 @@ impl EnumImpl for Property {
             348 => Some(Property::AllowRelaying),
             369 => Some(Property::AllowSpamTraining),
             398 => Some(Property::AllowedEndpoints),
+            939 => Some(Property::AllowedGroupIds),
             49 => Some(Property::AllowedIps),
             712 => Some(Property::AllowedNotifyUris),
             388 => Some(Property::Alpha),
@@ -2368,6 +2453,7 @@ impl EnumImpl for Property {
             502 => Some(Property::AuthUsername),
             740 => Some(Property::AuthenticatedAs),
             69 => Some(Property::AuthenticationResults),
+            934 => Some(Property::AutoAccept),
             171 => Some(Property::AutoAddInvitations),
             53 => Some(Property::AutoUpdateFrequency),
             463 => Some(Property::BaseDn),
@@ -2384,12 +2470,14 @@ impl EnumImpl for Property {
             126 => Some(Property::BlobStore),
             766 => Some(Property::BlockCount),
             38 => Some(Property::Body),
+            932 => Some(Property::BookingPolicy),
             459 => Some(Property::Brokers),
             658 => Some(Property::Bucket),
             656 => Some(Property::BufferSize),
             863 => Some(Property::Buffered),
             929 => Some(Property::CacheSize),
             216 => Some(Property::Canonicalization),
+            931 => Some(Property::Capacity),
             584 => Some(Property::CapacityClient),
             585 => Some(Property::CapacityReadBuffer),
             586 => Some(Property::CapacitySubscription),
@@ -2398,6 +2486,7 @@ impl EnumImpl for Property {
             176 => Some(Property::Certificate),
             342 => Some(Property::CertificateManagement),
             10 => Some(Property::ChallengeType),
+            951 => Some(Property::ChangedKeys),
             435 => Some(Property::ChangesMaxResults),
             517 => Some(Property::Chunking),
             612 => Some(Property::ClaimGroups),
@@ -2409,6 +2498,7 @@ impl EnumImpl for Property {
             878 => Some(Property::ClientSecret),
             889 => Some(Property::ClientToken),
             382 => Some(Property::ClusterFile),
+            942 => Some(Property::Cold),
             781 => Some(Property::ColumnClass),
             782 => Some(Property::ColumnDescription),
             779 => Some(Property::ColumnEmail),
@@ -2416,6 +2506,7 @@ impl EnumImpl for Property {
             240 => Some(Property::Comment),
             905 => Some(Property::CompartmentOcid),
             359 => Some(Property::CompressionAlgorithm),
+            945 => Some(Property::CompressionLevel),
             304 => Some(Property::Concurrency),
             34 => Some(Property::Condition),
             760 => Some(Property::Confidence),
@@ -2429,6 +2520,7 @@ impl EnumImpl for Property {
             133 => Some(Property::Contacts),
             117 => Some(Property::Container),
             65 => Some(Property::Content),
+            946 => Some(Property::ContentClass),
             758 => Some(Property::ContentTypes),
             708 => Some(Property::Contents),
             877 => Some(Property::Context),
@@ -2454,6 +2546,7 @@ impl EnumImpl for Property {
             845 => Some(Property::DateRangeStart),
             192 => Some(Property::Day),
             868 => Some(Property::DeadPropertyMaxSize),
+            935 => Some(Property::DeclineConflicts),
             108 => Some(Property::DefaultAdminRoleIds),
             790 => Some(Property::DefaultCertificateId),
             20 => Some(Property::DefaultDisplayName),
@@ -2476,6 +2569,7 @@ impl EnumImpl for Property {
             105 => Some(Property::DefaultUserRoleIds),
             235 => Some(Property::Definition),
             825 => Some(Property::Delay),
+            933 => Some(Property::DelegateIds),
             229 => Some(Property::DeleteAfter),
             777 => Some(Property::DeleteAfterUse),
             238 => Some(Property::DeliverAt),
@@ -2484,6 +2578,7 @@ impl EnumImpl for Property {
             82 => Some(Property::DeliveryResult),
             381 => Some(Property::Depth),
             6 => Some(Property::Description),
+            952 => Some(Property::Destination),
             297 => Some(Property::Details),
             12 => Some(Property::Directory),
             104 => Some(Property::DirectoryId),
@@ -2641,6 +2736,7 @@ impl EnumImpl for Property {
             39 => Some(Property::FromAddress),
             165 => Some(Property::FromEmail),
             40 => Some(Property::FromName),
+            953 => Some(Property::FullBackupInterval),
             521 => Some(Property::FutureRelease),
             124 => Some(Property::GenerateDkimKeys),
             918 => Some(Property::Generator),
@@ -2659,6 +2755,7 @@ impl EnumImpl for Property {
             333 => Some(Property::Host),
             331 => Some(Property::HostedZoneId),
             185 => Some(Property::Hostname),
+            943 => Some(Property::Hot),
             190 => Some(Property::Hour),
             32 => Some(Property::HttpAuth),
             33 => Some(Property::HttpHeaders),
@@ -2716,12 +2813,14 @@ impl EnumImpl for Property {
             741 => Some(Property::IsTls),
             181 => Some(Property::Issuer),
             606 => Some(Property::IssuerUrl),
+            957 => Some(Property::ItemCountBySize),
             172 => Some(Property::ItipMaxSize),
             824 => Some(Property::Jitter),
             334 => Some(Property::Key),
             337 => Some(Property::KeyName),
             120 => Some(Property::KeyPrefix),
             853 => Some(Property::KeyValues),
+            949 => Some(Property::KeysCopied),
             391 => Some(Property::L1Ratio),
             392 => Some(Property::L2Ratio),
             186 => Some(Property::LastRenewal),
@@ -2829,6 +2928,8 @@ impl EnumImpl for Property {
             22 => Some(Property::MaxVCardSize),
             725 => Some(Property::MaxVarNameLength),
             706 => Some(Property::MaxVarSize),
+            940 => Some(Property::MaxVersions),
+            965 => Some(Property::MaxWebhooks),
             864 => Some(Property::MemberGroupIds),
             19 => Some(Property::MemberTenantId),
             92 => Some(Property::Message),
@@ -2839,6 +2940,7 @@ impl EnumImpl for Property {
             497 => Some(Property::Metrics),
             207 => Some(Property::MetricsCollectionInterval),
             498 => Some(Property::MetricsPolicy),
+            944 => Some(Property::MigrateAfter),
             731 => Some(Property::MinHamSamples),
             649 => Some(Property::MinRetryWait),
             732 => Some(Property::MinSpamSamples),
@@ -2954,6 +3056,7 @@ impl EnumImpl for Property {
             792 => Some(Property::ProxyTrustedNetworks),
             218 => Some(Property::PublicKey),
             302 => Some(Property::PublishRecords),
+            964 => Some(Property::PublishedCalendars),
             448 => Some(Property::PushAttemptWait),
             449 => Some(Property::PushMaxAttempts),
             452 => Some(Property::PushRequestTimeout),
@@ -3011,6 +3114,7 @@ impl EnumImpl for Property {
             525 => Some(Property::RequireTls),
             733 => Some(Property::ReservoirCapacity),
             880 => Some(Property::ResourceGroup),
+            930 => Some(Property::ResourceType),
             51 => Some(Property::ResourceUrl),
             212 => Some(Property::ResponseCode),
             213 => Some(Property::ResponseEnhanced),
@@ -3022,6 +3126,8 @@ impl EnumImpl for Property {
             763 => Some(Property::ResponsePosExplanation),
             233 => Some(Property::Result),
             832 => Some(Property::ResultType),
+            954 => Some(Property::RetainDaily),
+            955 => Some(Property::RetainWeekly),
             228 => Some(Property::RetireAfter),
             420 => Some(Property::Retry),
             640 => Some(Property::RetryCount),
@@ -3036,6 +3142,7 @@ impl EnumImpl for Property {
             227 => Some(Property::RotateAfter),
             540 => Some(Property::Route),
             236 => Some(Property::Rua),
+            947 => Some(Property::SampleCount),
             896 => Some(Property::Sandbox),
             119 => Some(Property::SasToken),
             549 => Some(Property::SaslMechanisms),
@@ -3072,6 +3179,7 @@ impl EnumImpl for Property {
             794 => Some(Property::Services),
             329 => Some(Property::SessionToken),
             440 => Some(Property::SetMaxObjects),
+            956 => Some(Property::ShardId),
             830 => Some(Property::ShardIndex),
             895 => Some(Property::SharedSecret),
             336 => Some(Property::Sig0Algorithm),
@@ -3082,6 +3190,7 @@ impl EnumImpl for Property {
             885 => Some(Property::SkipDeploy),
             423 => Some(Property::SkipFirst),
             552 => Some(Property::SmtpGreeting),
+            962 => Some(Property::SnapshotInterval),
             441 => Some(Property::SnippetMaxResults),
             591 => Some(Property::SocketBacklog),
             592 => Some(Property::SocketNoDelay),
@@ -3118,11 +3227,14 @@ impl EnumImpl for Property {
             178 => Some(Property::SubjectAlternativeNames),
             368 => Some(Property::Subscribe),
             879 => Some(Property::SubscriptionId),
+            950 => Some(Property::SubspacesCopied),
             494 => Some(Property::Sum),
             808 => Some(Property::Summary),
             666 => Some(Property::SupportedLanguages),
+            963 => Some(Property::SyncWrites),
             748 => Some(Property::Tag),
             746 => Some(Property::Tags),
+            948 => Some(Property::Target),
             189 => Some(Property::TaskTypes),
             187 => Some(Property::Tasks),
             307 => Some(Property::TcpOnError),
@@ -3163,6 +3275,7 @@ impl EnumImpl for Property {
             573 => Some(Property::TlsTimeout),
             42 => Some(Property::To),
             888 => Some(Property::Token),
+            961 => Some(Property::TopAccounts),
             817 => Some(Property::TotalDeadline),
             850 => Some(Property::TotalFailedSessions),
             849 => Some(Property::TotalSuccessfulSessions),
@@ -3189,6 +3302,9 @@ impl EnumImpl for Property {
             309 => Some(Property::UseTls),
             402 => Some(Property::UseXForwarded),
             395 => Some(Property::UsedDiskQuota),
+            958 => Some(Property::UsedDiskQuotaByMailbox),
+            959 => Some(Property::UsedDiskQuotaBySize),
+            960 => Some(Property::UsedDiskQuotaByType),
             79 => Some(Property::UserAgent),
             620 => Some(Property::UserCodeExpiry),
             901 => Some(Property::UserOcid),
@@ -3199,6 +3315,7 @@ impl EnumImpl for Property {
             675 => Some(Property::VariableName),
             874 => Some(Property::VerifyAfterWrite),
             80 => Some(Property::Version),
+            941 => Some(Property::VersionRetention),
             884 => Some(Property::ViewName),
             526 => Some(Property::Vrfy),
             548 => Some(Property::WaitOnFail),
@@ -3208,6 +3325,9 @@ impl EnumImpl for Property {
             455 => Some(Property::WebsocketHeartbeat),
             456 => Some(Property::WebsocketThrottle),
             457 => Some(Property::WebsocketTimeout),
+            938 => Some(Property::WeekdaysOnly),
+            937 => Some(Property::WorkingHoursEnd),
+            936 => Some(Property::WorkingHoursStart),
             749 => Some(Property::Zone),
             98 => Some(Property::ZoneIpV4),
             99 => Some(Property::ZoneIpV6),
@@ -3215,7 +3335,7 @@ impl EnumImpl for Property {
         }
     }
 
-    const COUNT: usize = 930;
+    const COUNT: usize = 966;
 }
 
 impl serde::Serialize for Property {
@@ -3256,6 +3376,8 @@ impl ObjectType {
             ObjectType::ArfExternalReport => ArfExternalReport::FLAGS,
             ObjectType::Asn => Asn::FLAGS,
             ObjectType::Authentication => Authentication::FLAGS,
+            ObjectType::Backup => Backup::FLAGS,
+            ObjectType::BlobEncryptionKey => BlobEncryptionKey::FLAGS,
             ObjectType::BlobStore => BlobStore::FLAGS,
             ObjectType::BlockedIp => BlockedIp::FLAGS,
             ObjectType::Bootstrap => Bootstrap::FLAGS,
@@ -3266,9 +3388,11 @@ impl ObjectType {
             ObjectType::Certificate => Certificate::FLAGS,
             ObjectType::ClusterNode => ClusterNode::FLAGS,
             ObjectType::ClusterRole => ClusterRole::FLAGS,
+            ObjectType::CompressionDictionary => CompressionDictionary::FLAGS,
             ObjectType::Coordinator => Coordinator::FLAGS,
             ObjectType::DataRetention => DataRetention::FLAGS,
             ObjectType::DataStore => DataStore::FLAGS,
+            ObjectType::DataStoreShard => DataStoreShard::FLAGS,
             ObjectType::Directory => Directory::FLAGS,
             ObjectType::DkimReportSettings => DkimReportSettings::FLAGS,
             ObjectType::DkimSignature => DkimSignature::FLAGS,
@@ -3628,6 +3752,8 @@ impl ObjectType {
             ObjectType::ArfExternalReport => Permission::SysArfExternalReportGet,
             ObjectType::Asn => Permission::SysAsnGet,
             ObjectType::Authentication => Permission::SysAuthenticationGet,
+            ObjectType::Backup => Permission::SysBackupGet,
+            ObjectType::BlobEncryptionKey => Permission::SysBlobEncryptionKeyGet,
             ObjectType::BlobStore => Permission::SysBlobStoreGet,
             ObjectType::BlockedIp => Permission::SysBlockedIpGet,
             ObjectType::Bootstrap => Permission::SysBootstrapGet,
@@ -3638,9 +3764,11 @@ impl ObjectType {
             ObjectType::Certificate => Permission::SysCertificateGet,
             ObjectType::ClusterNode => Permission::SysClusterNodeGet,
             ObjectType::ClusterRole => Permission::SysClusterRoleGet,
+            ObjectType::CompressionDictionary => Permission::SysCompressionDictionaryGet,
             ObjectType::Coordinator => Permission::SysCoordinatorGet,
             ObjectType::DataRetention => Permission::SysDataRetentionGet,
             ObjectType::DataStore => Permission::SysDataStoreGet,
+            ObjectType::DataStoreShard => Permission::SysDataStoreShardGet,
             ObjectType::Directory => Permission::SysDirectoryGet,
             ObjectType::DkimReportSettings => Permission::SysDkimReportSettingsGet,
             ObjectType::DkimSignature => Permission::SysDkimSignatureGet,
@@ -3745,10 +3873,13 @@ impl ObjectType {
             ObjectType::Application => Permission::SysApplicationQuery,
             ObjectType::ArchivedItem => Permission::SysArchivedItemQuery,
             ObjectType::ArfExternalReport => Permission::SysArfExternalReportQuery,
+            ObjectType::BlobEncryptionKey => Permission::SysBlobEncryptionKeyQuery,
             ObjectType::BlockedIp => Permission::SysBlockedIpQuery,
             ObjectType::Certificate => Permission::SysCertificateQuery,
             ObjectType::ClusterNode => Permission::SysClusterNodeQuery,
             ObjectType::ClusterRole => Permission::SysClusterRoleQuery,
+            ObjectType::CompressionDictionary => Permission::SysCompressionDictionaryQuery,
+            ObjectType::DataStoreShard => Permission::SysDataStoreShardQuery,
             ObjectType::Directory => Permission::SysDirectoryQuery,
             ObjectType::DkimSignature => Permission::SysDkimSignatureQuery,
             ObjectType::DmarcExternalReport => Permission::SysDmarcExternalReportQuery,
@@ -3879,6 +4010,16 @@ impl ObjectType {
                 Permission::SysAuthenticationUpdate,
                 Permission::SysAuthenticationUpdate,
             ],
+            ObjectType::Backup => [
+                Permission::SysBackupUpdate,
+                Permission::SysBackupUpdate,
+                Permission::SysBackupUpdate,
+            ],
+            ObjectType::BlobEncryptionKey => [
+                Permission::SysBlobEncryptionKeyCreate,
+                Permission::SysBlobEncryptionKeyUpdate,
+                Permission::SysBlobEncryptionKeyDestroy,
+            ],
             ObjectType::BlobStore => [
                 Permission::SysBlobStoreUpdate,
                 Permission::SysBlobStoreUpdate,
@@ -3929,6 +4070,11 @@ impl ObjectType {
                 Permission::SysClusterRoleUpdate,
                 Permission::SysClusterRoleDestroy,
             ],
+            ObjectType::CompressionDictionary => [
+                Permission::SysCompressionDictionaryCreate,
+                Permission::SysCompressionDictionaryUpdate,
+                Permission::SysCompressionDictionaryDestroy,
+            ],
             ObjectType::Coordinator => [
                 Permission::SysCoordinatorUpdate,
                 Permission::SysCoordinatorUpdate,
@@ -3944,6 +4090,11 @@ impl ObjectType {
                 Permission::SysDataStoreUpdate,
                 Permission::SysDataStoreUpdate,
             ],
+            ObjectType::DataStoreShard => [
+                Permission::SysDataStoreShardCreate,
+                Permission::SysDataStoreShardUpdate,
+                Permission::SysDataStoreShardDestroy,
+            ],
             ObjectType::Directory => [
                 Permission::SysDirectoryCreate,
                 Permission::SysDirectoryUpdate,
@@ -4393,6 +4544,7 @@ impl ObjectInner {
         match self {
             ObjectInner::Account(Account::User(obj)) => obj.member_tenant_id,
             ObjectInner::Account(Account::Group(obj)) => obj.member_tenant_id,
+            ObjectInner::Account(Account::Resource(obj)) => obj.member_tenant_id,
             ObjectInner::AcmeProvider(obj) => obj.member_tenant_id,
             ObjectInner::ArfExternalReport(obj) => obj.member_tenant_id,
             ObjectInner::Directory(Directory::Ldap(obj)) => obj.member_tenant_id,
@@ -4489,6 +4641,7 @@ impl ObjectInner {
         match self {
             ObjectInner::Account(Account::User(obj)) => obj.member_tenant_id = Some(id),
             ObjectInner::Account(Account::Group(obj)) => obj.member_tenant_id = Some(id),
+            ObjectInner::Account(Account::Resource(obj)) => obj.member_tenant_id = Some(id),
             ObjectInner::AcmeProvider(obj) => obj.member_tenant_id = Some(id),
             ObjectInner::ArfExternalReport(obj) => obj.member_tenant_id = Some(id),
             ObjectInner::Directory(Directory::Ldap(obj)) => obj.member_tenant_id = Some(id),
@@ -4606,6 +4759,8 @@ impl ObjectInner {
             ObjectInner::Task(Task::RestoreArchivedItem(obj)) => Some(obj.account_id),
             ObjectInner::Task(Task::DestroyAccount(obj)) => Some(obj.account_id),
             ObjectInner::Task(Task::AccountMaintenance(obj)) => Some(obj.account_id),
+            ObjectInner::Task(Task::EmailSnooze(obj)) => Some(obj.account_id),
+            ObjectInner::Task(Task::CalendarSubscription(obj)) => Some(obj.account_id),
             _ => None,
         }
     }
@@ -4629,6 +4784,8 @@ impl ObjectInner {
             ObjectInner::Task(Task::RestoreArchivedItem(obj)) => obj.account_id = id,
             ObjectInner::Task(Task::DestroyAccount(obj)) => obj.account_id = id,
             ObjectInner::Task(Task::AccountMaintenance(obj)) => obj.account_id = id,
+            ObjectInner::Task(Task::EmailSnooze(obj)) => obj.account_id = id,
+            ObjectInner::Task(Task::CalendarSubscription(obj)) => obj.account_id = id,
             _ => {}
         }
     }
@@ -4651,6 +4808,8 @@ impl ObjectInner {
             ObjectInner::ArfExternalReport(obj) => obj.to_pickled_vec(),
             ObjectInner::Asn(obj) => obj.to_pickled_vec(),
             ObjectInner::Authentication(obj) => obj.to_pickled_vec(),
+            ObjectInner::Backup(obj) => obj.to_pickled_vec(),
+            ObjectInner::BlobEncryptionKey(obj) => obj.to_pickled_vec(),
             ObjectInner::BlobStore(obj) => obj.to_pickled_vec(),
             ObjectInner::BlockedIp(obj) => obj.to_pickled_vec(),
             ObjectInner::Bootstrap(obj) => obj.to_pickled_vec(),
@@ -4661,9 +4820,11 @@ impl ObjectInner {
             ObjectInner::Certificate(obj) => obj.to_pickled_vec(),
             ObjectInner::ClusterNode(obj) => obj.to_pickled_vec(),
             ObjectInner::ClusterRole(obj) => obj.to_pickled_vec(),
+            ObjectInner::CompressionDictionary(obj) => obj.to_pickled_vec(),
             ObjectInner::Coordinator(obj) => obj.to_pickled_vec(),
             ObjectInner::DataRetention(obj) => obj.to_pickled_vec(),
             ObjectInner::DataStore(obj) => obj.to_pickled_vec(),
+            ObjectInner::DataStoreShard(obj) => obj.to_pickled_vec(),
             ObjectInner::Directory(obj) => obj.to_pickled_vec(),
             ObjectInner::DkimReportSettings(obj) => obj.to_pickled_vec(),
             ObjectInner::DkimSignature(obj) => obj.to_pickled_vec(),
@@ -4782,6 +4943,10 @@ impl ObjectInner {
             }
             ObjectType::Asn => Pickle::unpickle(stream).map(ObjectInner::Asn),
             ObjectType::Authentication => Pickle::unpickle(stream).map(ObjectInner::Authentication),
+            ObjectType::Backup => Pickle::unpickle(stream).map(ObjectInner::Backup),
+            ObjectType::BlobEncryptionKey => {
+                Pickle::unpickle(stream).map(ObjectInner::BlobEncryptionKey)
+            }
             ObjectType::BlobStore => Pickle::unpickle(stream).map(ObjectInner::BlobStore),
             ObjectType::BlockedIp => Pickle::unpickle(stream).map(ObjectInner::BlockedIp),
             ObjectType::Bootstrap => Pickle::unpickle(stream).map(ObjectInner::Bootstrap),
@@ -4794,9 +4959,13 @@ impl ObjectInner {
             ObjectType::Certificate => Pickle::unpickle(stream).map(ObjectInner::Certificate),
             ObjectType::ClusterNode => Pickle::unpickle(stream).map(ObjectInner::ClusterNode),
             ObjectType::ClusterRole => Pickle::unpickle(stream).map(ObjectInner::ClusterRole),
+            ObjectType::CompressionDictionary => {
+                Pickle::unpickle(stream).map(ObjectInner::CompressionDictionary)
+            }
             ObjectType::Coordinator => Pickle::unpickle(stream).map(ObjectInner::Coordinator),
             ObjectType::DataRetention => Pickle::unpickle(stream).map(ObjectInner::DataRetention),
             ObjectType::DataStore => Pickle::unpickle(stream).map(ObjectInner::DataStore),
+            ObjectType::DataStoreShard => Pickle::unpickle(stream).map(ObjectInner::DataStoreShard),
             ObjectType::Directory => Pickle::unpickle(stream).map(ObjectInner::Directory),
             ObjectType::DkimReportSettings => {
                 Pickle::unpickle(stream).map(ObjectInner::DkimReportSettings)
@@ -4987,6 +5156,10 @@ impl ObjectInner {
             ObjectType::Authentication => {
                 Authentication::deserialize(deserializer).map(ObjectInner::Authentication)
             }
+            ObjectType::Backup => Backup::deserialize(deserializer).map(ObjectInner::Backup),
+            ObjectType::BlobEncryptionKey => {
+                BlobEncryptionKey::deserialize(deserializer).map(ObjectInner::BlobEncryptionKey)
+            }
             ObjectType::BlobStore => {
                 BlobStore::deserialize(deserializer).map(ObjectInner::BlobStore)
             }
@@ -5013,6 +5186,8 @@ impl ObjectInner {
             ObjectType::ClusterRole => {
                 ClusterRole::deserialize(deserializer).map(ObjectInner::ClusterRole)
             }
+            ObjectType::CompressionDictionary => CompressionDictionary::deserialize(deserializer)
+                .map(ObjectInner::CompressionDictionary),
             ObjectType::Coordinator => {
                 Coordinator::deserialize(deserializer).map(ObjectInner::Coordinator)
             }
@@ -5022,6 +5197,9 @@ impl ObjectInner {
             ObjectType::DataStore => {
                 DataStore::deserialize(deserializer).map(ObjectInner::DataStore)
             }
+            ObjectType::DataStoreShard => {
+                DataStoreShard::deserialize(deserializer).map(ObjectInner::DataStoreShard)
+            }
             ObjectType::Directory => {
                 Directory::deserialize(deserializer).map(ObjectInner::Directory)
             }
@@ -5287,6 +5465,8 @@ impl Object {
             ObjectInner::ArfExternalReport(_) => ArfExternalReport::FLAGS,
             ObjectInner::Asn(_) => Asn::FLAGS,
             ObjectInner::Authentication(_) => Authentication::FLAGS,
+            ObjectInner::Backup(_) => Backup::FLAGS,
+            ObjectInner::BlobEncryptionKey(_) => BlobEncryptionKey::FLAGS,
             ObjectInner::BlobStore(_) => BlobStore::FLAGS,
             ObjectInner::BlockedIp(_) => BlockedIp::FLAGS,
             ObjectInner::Bootstrap(_) => Bootstrap::FLAGS,
@@ -5297,9 +5477,11 @@ impl Object {
             ObjectInner::Certificate(_) => Certificate::FLAGS,
             ObjectInner::ClusterNode(_) => ClusterNode::FLAGS,
             ObjectInner::ClusterRole(_) => ClusterRole::FLAGS,
+            ObjectInner::CompressionDictionary(_) => CompressionDictionary::FLAGS,
             ObjectInner::Coordinator(_) => Coordinator::FLAGS,
             ObjectInner::DataRetention(_) => DataRetention::FLAGS,
             ObjectInner::DataStore(_) => DataStore::FLAGS,
+            ObjectInner::DataStoreShard(_) => DataStoreShard::FLAGS,
             ObjectInner::Directory(_) => Directory::FLAGS,
             ObjectInner::DkimReportSettings(_) => DkimReportSettings::FLAGS,
             ObjectInner::DkimSignature(_) => DkimSignature::FLAGS,
@@ -5409,6 +5591,8 @@ impl Object {
             ObjectInner::ArfExternalReport(_) => ObjectType::ArfExternalReport,
             ObjectInner::Asn(_) => ObjectType::Asn,
             ObjectInner::Authentication(_) => ObjectType::Authentication,
+            ObjectInner::Backup(_) => ObjectType::Backup,
+            ObjectInner::BlobEncryptionKey(_) => ObjectType::BlobEncryptionKey,
             ObjectInner::BlobStore(_) => ObjectType::BlobStore,
             ObjectInner::BlockedIp(_) => ObjectType::BlockedIp,
             ObjectInner::Bootstrap(_) => ObjectType::Bootstrap,
@@ -5419,9 +5603,11 @@ impl Object {
             ObjectInner::Certificate(_) => ObjectType::Certificate,
             ObjectInner::ClusterNode(_) => ObjectType::ClusterNode,
             ObjectInner::ClusterRole(_) => ObjectType::ClusterRole,
+            ObjectInner::CompressionDictionary(_) => ObjectType::CompressionDictionary,
             ObjectInner::Coordinator(_) => ObjectType::Coordinator,
             ObjectInner::DataRetention(_) => ObjectType::DataRetention,
             ObjectInner::DataStore(_) => ObjectType::DataStore,
+            ObjectInner::DataStoreShard(_) => ObjectType::DataStoreShard,
             ObjectInner::Directory(_) => ObjectType::Directory,
             ObjectInner::DkimReportSettings(_) => ObjectType::DkimReportSettings,
             ObjectInner::DkimSignature(_) => ObjectType::DkimSignature,
@@ -5519,6 +5705,7 @@ impl Object {
             ObjectInner::Action(obj) => Some(obj.object_type().as_str()),
             ObjectInner::ArchivedItem(obj) => Some(obj.object_type().as_str()),
             ObjectInner::Asn(obj) => Some(obj.object_type().as_str()),
+            ObjectInner::Backup(obj) => Some(obj.object_type().as_str()),
             ObjectInner::BlobStore(obj) => Some(obj.object_type().as_str()),
             ObjectInner::Coordinator(obj) => Some(obj.object_type().as_str()),
             ObjectInner::DataStore(obj) => Some(obj.object_type().as_str()),
@@ -5560,6 +5747,8 @@ impl Object {
             ObjectInner::ArfExternalReport(obj) => obj.validate(errors),
             ObjectInner::Asn(obj) => obj.validate(errors),
             ObjectInner::Authentication(obj) => obj.validate(errors),
+            ObjectInner::Backup(obj) => obj.validate(errors),
+            ObjectInner::BlobEncryptionKey(obj) => obj.validate(errors),
             ObjectInner::BlobStore(obj) => obj.validate(errors),
             ObjectInner::BlockedIp(obj) => obj.validate(errors),
             ObjectInner::Bootstrap(obj) => obj.validate(errors),
@@ -5570,9 +5759,11 @@ impl Object {
             ObjectInner::Certificate(obj) => obj.validate(errors),
             ObjectInner::ClusterNode(obj) => obj.validate(errors),
             ObjectInner::ClusterRole(obj) => obj.validate(errors),
+            ObjectInner::CompressionDictionary(obj) => obj.validate(errors),
             ObjectInner::Coordinator(obj) => obj.validate(errors),
             ObjectInner::DataRetention(obj) => obj.validate(errors),
             ObjectInner::DataStore(obj) => obj.validate(errors),
+            ObjectInner::DataStoreShard(obj) => obj.validate(errors),
             ObjectInner::Directory(obj) => obj.validate(errors),
             ObjectInner::DkimReportSettings(obj) => obj.validate(errors),
             ObjectInner::DkimSignature(obj) => obj.validate(errors),
@@ -5682,6 +5873,8 @@ impl Object {
             ObjectInner::ArfExternalReport(obj) => obj.index(i),
             ObjectInner::Asn(obj) => obj.index(i),
             ObjectInner::Authentication(obj) => obj.index(i),
+            ObjectInner::Backup(obj) => obj.index(i),
+            ObjectInner::BlobEncryptionKey(obj) => obj.index(i),
             ObjectInner::BlobStore(obj) => obj.index(i),
             ObjectInner::BlockedIp(obj) => obj.index(i),
             ObjectInner::Bootstrap(obj) => obj.index(i),
@@ -5692,9 +5885,11 @@ impl Object {
             ObjectInner::Certificate(obj) => obj.index(i),
             ObjectInner::ClusterNode(obj) => obj.index(i),
             ObjectInner::ClusterRole(obj) => obj.index(i),
+            ObjectInner::CompressionDictionary(obj) => obj.index(i),
             ObjectInner::Coordinator(obj) => obj.index(i),
             ObjectInner::DataRetention(obj) => obj.index(i),
             ObjectInner::DataStore(obj) => obj.index(i),
+            ObjectInner::DataStoreShard(obj) => obj.index(i),
             ObjectInner::Directory(obj) => obj.index(i),
             ObjectInner::DkimReportSettings(obj) => obj.index(i),
             ObjectInner::DkimSignature(obj) => obj.index(i),
@@ -5808,6 +6003,8 @@ impl Object {
             ObjectInner::ArfExternalReport(obj) => obj.patch(pointer, value),
             ObjectInner::Asn(obj) => obj.patch(pointer, value),
             ObjectInner::Authentication(obj) => obj.patch(pointer, value),
+            ObjectInner::Backup(obj) => obj.patch(pointer, value),
+            ObjectInner::BlobEncryptionKey(obj) => obj.patch(pointer, value),
             ObjectInner::BlobStore(obj) => obj.patch(pointer, value),
             ObjectInner::BlockedIp(obj) => obj.patch(pointer, value),
             ObjectInner::Bootstrap(obj) => obj.patch(pointer, value),
@@ -5818,9 +6015,11 @@ impl Object {
             ObjectInner::Certificate(obj) => obj.patch(pointer, value),
             ObjectInner::ClusterNode(obj) => obj.patch(pointer, value),
             ObjectInner::ClusterRole(obj) => obj.patch(pointer, value),
+            ObjectInner::CompressionDictionary(obj) => obj.patch(pointer, value),
             ObjectInner::Coordinator(obj) => obj.patch(pointer, value),
             ObjectInner::DataRetention(obj) => obj.patch(pointer, value),
             ObjectInner::DataStore(obj) => obj.patch(pointer, value),
+            ObjectInner::DataStoreShard(obj) => obj.patch(pointer, value),
             ObjectInner::Directory(obj) => obj.patch(pointer, value),
             ObjectInner::DkimReportSettings(obj) => obj.patch(pointer, value),
             ObjectInner::DkimSignature(obj) => obj.patch(pointer, value),
@@ -5932,6 +6131,8 @@ impl IntoValue for Object {
             ObjectInner::ArfExternalReport(obj) => obj.into_value(),
             ObjectInner::Asn(obj) => obj.into_value(),
             ObjectInner::Authentication(obj) => obj.into_value(),
+            ObjectInner::Backup(obj) => obj.into_value(),
+            ObjectInner::BlobEncryptionKey(obj) => obj.into_value(),
             ObjectInner::BlobStore(obj) => obj.into_value(),
             ObjectInner::BlockedIp(obj) => obj.into_value(),
             ObjectInner::Bootstrap(obj) => obj.into_value(),
@@ -5942,9 +6143,11 @@ impl IntoValue for Object {
             ObjectInner::Certificate(obj) => obj.into_value(),
             ObjectInner::ClusterNode(obj) => obj.into_value(),
             ObjectInner::ClusterRole(obj) => obj.into_value(),
+            ObjectInner::CompressionDictionary(obj) => obj.into_value(),
             ObjectInner::Coordinator(obj) => obj.into_value(),
             ObjectInner::DataRetention(obj) => obj.into_value(),
             ObjectInner::DataStore(obj) => obj.into_value(),
+            ObjectInner::DataStoreShard(obj) => obj.into_value(),
             ObjectInner::Directory(obj) => obj.into_value(),
             ObjectInner::DkimReportSettings(obj) => obj.into_value(),
             ObjectInner::DkimSignature(obj) => obj.into_value(),
@@ -6056,6 +6259,8 @@ impl From<ObjectType> for ObjectInner {
             ObjectType::ArfExternalReport => ObjectInner::ArfExternalReport(Default::default()),
             ObjectType::Asn => ObjectInner::Asn(Default::default()),
             ObjectType::Authentication => ObjectInner::Authentication(Default::default()),
+            ObjectType::Backup => ObjectInner::Backup(Default::default()),
+            ObjectType::BlobEncryptionKey => ObjectInner::BlobEncryptionKey(Default::default()),
             ObjectType::BlobStore => ObjectInner::BlobStore(Default::default()),
             ObjectType::BlockedIp => ObjectInner::BlockedIp(Default::default()),
             ObjectType::Bootstrap => ObjectInner::Bootstrap(Default::default()),
@@ -6066,9 +6271,13 @@ impl From<ObjectType> for ObjectInner {
             ObjectType::Certificate => ObjectInner::Certificate(Default::default()),
             ObjectType::ClusterNode => ObjectInner::ClusterNode(Default::default()),
             ObjectType::ClusterRole => ObjectInner::ClusterRole(Default::default()),
+            ObjectType::CompressionDictionary => {
+                ObjectInner::CompressionDictionary(Default::default())
+            }
             ObjectType::Coordinator => ObjectInner::Coordinator(Default::default()),
             ObjectType::DataRetention => ObjectInner::DataRetention(Default::default()),
             ObjectType::DataStore => ObjectInner::DataStore(Default::default()),
+            ObjectType::DataStoreShard => ObjectInner::DataStoreShard(Default::default()),
             ObjectType::Directory => ObjectInner::Directory(Default::default()),
             ObjectType::DkimReportSettings => ObjectInner::DkimReportSettings(Default::default()),
             ObjectType::DkimSignature => ObjectInner::DkimSignature(Default::default()),
@@ -6409,6 +6618,36 @@ impl From<Object> for Authentication {
     }
 }
 
+impl From<Backup> for ObjectInner {
+    fn from(value: Backup) -> Self {
+        ObjectInner::Backup(value)
+    }
+}
+
+impl From<Object> for Backup {
+    fn from(obj: Object) -> Self {
+        match obj.inner {
+            ObjectInner::Backup(obj) => obj,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl From<BlobEncryptionKey> for ObjectInner {
+    fn from(value: BlobEncryptionKey) -> Self {
+        ObjectInner::BlobEncryptionKey(value)
+    }
+}
+
+impl From<Object> for BlobEncryptionKey {
+    fn from(obj: Object) -> Self {
+        match obj.inner {
+            ObjectInner::BlobEncryptionKey(obj) => obj,
+            _ => unreachable!(),
+        }
+    }
+}
+
 impl From<BlobStore> for ObjectInner {
     fn from(value: BlobStore) -> Self {
         ObjectInner::BlobStore(value)
@@ -6559,6 +6798,21 @@ impl From<Object> for ClusterRole {
     }
 }
 
+impl From<CompressionDictionary> for ObjectInner {
+    fn from(value: CompressionDictionary) -> Self {
+        ObjectInner::CompressionDictionary(value)
+    }
+}
+
+impl From<Object> for CompressionDictionary {
+    fn from(obj: Object) -> Self {
+        match obj.inner {
+            ObjectInner::CompressionDictionary(obj) => obj,
+            _ => unreachable!(),
+        }
+    }
+}
+
 impl From<Coordinator> for ObjectInner {
     fn from(value: Coordinator) -> Self {
         ObjectInner::Coordinator(value)
@@ -6604,6 +6858,21 @@ impl From<Object> for DataStore {
     }
 }
 
+impl From<DataStoreShard> for ObjectInner {
+    fn from(value: DataStoreShard) -> Self {
+        ObjectInner::DataStoreShard(value)
+    }
+}
+
+impl From<Object> for DataStoreShard {
+    fn from(obj: Object) -> Self {
+        match obj.inner {
+            ObjectInner::DataStoreShard(obj) => obj,
+            _ => unreachable!(),
+        }
+    }
+}
+
 impl From<Directory> for ObjectInner {
     fn from(value: Directory) -> Self {
         ObjectInner::Directory(value)
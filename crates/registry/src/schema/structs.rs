@@ -12,6 +12,7 @@ use crate::schema::prelude::*;
 pub enum Account {
     User(UserAccount),
     Group(GroupAccount),
+    Resource(ResourceAccount),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -503,6 +504,41 @@ pub struct AzureStore {
     pub key_prefix: Option<String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "@type")]
+pub enum Backup {
+    Disabled,
+    Enabled(BackupProperties),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupProperties {
+    #[serde(rename = "destination")]
+    pub destination: BlobStoreBase,
+    #[serde(rename = "prefix")]
+    pub prefix: String,
+    #[serde(rename = "schedule")]
+    pub schedule: Cron,
+    #[serde(rename = "fullBackupInterval")]
+    pub full_backup_interval: u64,
+    #[serde(rename = "retainDaily")]
+    pub retain_daily: u64,
+    #[serde(rename = "retainWeekly")]
+    pub retain_weekly: u64,
+    #[serde(rename = "encryptionKey")]
+    pub encryption_key: SecretKeyOptional,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BlobEncryptionKey {
+    #[serde(rename = "description")]
+    pub description: String,
+    #[serde(rename = "secret")]
+    pub secret: SecretKey,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "@type")]
 pub enum BlobStore {
@@ -514,6 +550,7 @@ pub enum BlobStore {
     FoundationDb(FoundationDbStore),
     PostgreSql(PostgreSqlStore),
     MySql(MySqlStore),
+    Tiered(TieredBlobStore),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -540,6 +577,25 @@ pub struct BlockedIp {
     pub expires_at: Option<UTCDateTime>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BookingPolicy {
+    #[serde(rename = "autoAccept")]
+    pub auto_accept: bool,
+    #[serde(rename = "declineConflicts")]
+    pub decline_conflicts: bool,
+    #[serde(rename = "maxDuration")]
+    pub max_duration: Option<Duration>,
+    #[serde(rename = "workingHoursStart")]
+    pub working_hours_start: Option<u64>,
+    #[serde(rename = "workingHoursEnd")]
+    pub working_hours_end: Option<u64>,
+    #[serde(rename = "weekdaysOnly")]
+    pub weekdays_only: bool,
+    #[serde(rename = "allowedGroupIds")]
+    pub allowed_group_ids: Map<Id>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Bootstrap {
@@ -620,6 +676,8 @@ pub struct Cache {
     pub mailing_lists: u64,
     #[serde(rename = "dkimSignatures")]
     pub dkim_signatures: u64,
+    #[serde(rename = "publishedCalendars")]
+    pub published_calendars: u64,
     #[serde(rename = "negativeTtl")]
     pub negative_ttl: Duration,
 }
@@ -778,6 +836,19 @@ pub struct ClusterTaskGroupProperties {
     pub task_types: Map<ClusterTaskType>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CompressionDictionary {
+    #[serde(rename = "contentClass")]
+    pub content_class: CompressionContentClass,
+    #[serde(rename = "blobId")]
+    pub blob_id: BlobId,
+    #[serde(rename = "sampleCount")]
+    pub sample_count: u64,
+    #[serde(rename = "createdAt")]
+    pub created_at: UTCDateTime,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "@type")]
 pub enum Coordinator {
@@ -897,6 +968,16 @@ pub enum DataStore {
     FoundationDb(FoundationDbStore),
     PostgreSql(PostgreSqlStore),
     MySql(MySqlStore),
+    Ephemeral(EphemeralStore),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DataStoreShard {
+    #[serde(rename = "description")]
+    pub description: String,
+    #[serde(rename = "store")]
+    pub store: DataStore,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -2773,6 +2854,8 @@ pub struct Email {
     pub encrypt_at_rest: bool,
     #[serde(rename = "compressionAlgorithm")]
     pub compression_algorithm: CompressionAlgo,
+    #[serde(rename = "compressionLevel")]
+    pub compression_level: u64,
     #[serde(rename = "defaultFolders")]
     pub default_folders: VecMap<SpecialUse, EmailFolder>,
     #[serde(rename = "maxMessages")]
@@ -2847,6 +2930,17 @@ pub struct Enterprise {
     pub logo_url: Option<String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EphemeralStore {
+    #[serde(rename = "path")]
+    pub path: Option<String>,
+    #[serde(rename = "snapshotInterval")]
+    pub snapshot_interval: Duration,
+    #[serde(rename = "syncWrites")]
+    pub sync_writes: bool,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct EventTracingLevel {
@@ -2883,6 +2977,10 @@ pub struct FileStorage {
     pub max_files: Option<u64>,
     #[serde(rename = "maxFolders")]
     pub max_folders: Option<u64>,
+    #[serde(rename = "maxVersions")]
+    pub max_versions: u64,
+    #[serde(rename = "versionRetention")]
+    pub version_retention: Option<Duration>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -3195,6 +3293,8 @@ pub struct Jmap {
     pub web_push_contact: Option<String>,
     #[serde(rename = "maxPushSize")]
     pub max_push_size: u64,
+    #[serde(rename = "maxWebhooks")]
+    pub max_webhooks: Option<u64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -3496,6 +3596,8 @@ pub struct MetricsPrometheusProperties {
     pub auth_secret: SecretKeyOptional,
     #[serde(rename = "authUsername")]
     pub auth_username: Option<String>,
+    #[serde(rename = "topAccounts")]
+    pub top_accounts: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -4456,6 +4558,41 @@ pub struct ReportSettings {
     pub inbound_report_max_size: i64,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResourceAccount {
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "domainId")]
+    pub domain_id: Id,
+    #[serde(rename = "resourceType")]
+    pub resource_type: ResourceType,
+    #[serde(rename = "description")]
+    pub description: Option<String>,
+    #[serde(rename = "capacity")]
+    pub capacity: Option<u64>,
+    #[serde(rename = "createdAt")]
+    pub created_at: UTCDateTime,
+    #[serde(rename = "memberTenantId")]
+    pub member_tenant_id: Option<Id>,
+    #[serde(rename = "bookingPolicy")]
+    pub booking_policy: BookingPolicy,
+    #[serde(rename = "delegateIds")]
+    pub delegate_ids: Map<Id>,
+    #[serde(rename = "roles")]
+    pub roles: Roles,
+    #[serde(rename = "quotas")]
+    pub quotas: VecMap<StorageQuota, u64>,
+    #[serde(rename = "permissions")]
+    pub permissions: Permissions,
+    #[serde(rename = "aliases")]
+    pub aliases: List<EmailAlias>,
+    #[serde(rename = "locale")]
+    pub locale: Locale,
+    #[serde(rename = "timeZone")]
+    pub time_zone: Option<TimeZone>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct RocksDbStore {
@@ -5497,6 +5634,10 @@ pub enum Task {
     AcmeRenewal(TaskDomainManagement),
     DkimManagement(TaskDomainManagement),
     DnsManagement(TaskDnsManagement),
+    EmailSnooze(TaskEmailSnooze),
+    CalendarSubscription(TaskCalendarSubscription),
+    DataStoreMigration(TaskDataStoreMigration),
+    MoveAccount(TaskMoveAccount),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -5578,6 +5719,34 @@ pub struct TaskCalendarItipMessage {
     pub status: TaskStatus,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TaskCalendarSubscription {
+    #[serde(rename = "accountId")]
+    pub account_id: Id,
+    #[serde(rename = "documentId")]
+    pub document_id: Id,
+    #[serde(rename = "status")]
+    pub status: TaskStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TaskDataStoreMigration {
+    #[serde(rename = "target")]
+    pub target: DataStore,
+    #[serde(rename = "stage")]
+    pub stage: DataStoreMigrationStage,
+    #[serde(rename = "subspacesCopied")]
+    pub subspaces_copied: u64,
+    #[serde(rename = "keysCopied")]
+    pub keys_copied: u64,
+    #[serde(rename = "changedKeys")]
+    pub changed_keys: u64,
+    #[serde(rename = "status")]
+    pub status: TaskStatus,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct TaskDestroyAccount {
@@ -5624,6 +5793,17 @@ pub struct TaskDomainManagement {
     pub status: TaskStatus,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TaskEmailSnooze {
+    #[serde(rename = "accountId")]
+    pub account_id: Id,
+    #[serde(rename = "documentId")]
+    pub document_id: Id,
+    #[serde(rename = "status")]
+    pub status: TaskStatus,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct TaskIndexDocument {
@@ -5670,6 +5850,21 @@ pub struct TaskMergeThreads {
     pub status: TaskStatus,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TaskMoveAccount {
+    #[serde(rename = "accountId")]
+    pub account_id: Id,
+    #[serde(rename = "shardId")]
+    pub shard_id: Option<Id>,
+    #[serde(rename = "stage")]
+    pub stage: DataStoreMigrationStage,
+    #[serde(rename = "keysCopied")]
+    pub keys_copied: u64,
+    #[serde(rename = "status")]
+    pub status: TaskStatus,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct TaskRestoreArchivedItem {
@@ -5814,6 +6009,17 @@ pub struct Tenant {
     pub quotas: VecMap<TenantStorageQuota, u64>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TieredBlobStore {
+    #[serde(rename = "hot")]
+    pub hot: BlobStoreBase,
+    #[serde(rename = "cold")]
+    pub cold: BlobStoreBase,
+    #[serde(rename = "migrateAfter")]
+    pub migrate_after: Duration,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct TlsExternalReport {
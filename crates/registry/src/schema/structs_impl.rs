@@ -17,6 +17,7 @@ impl ObjectImpl for Account {
         match self {
             Account::User(inner) => inner.validate(errors),
             Account::Group(inner) => inner.validate(errors),
+            Account::Resource(inner) => inner.validate(errors),
         }
     }
 
@@ -30,6 +31,10 @@ impl ObjectImpl for Account {
                 i.typ(1);
                 object.index(i);
             }
+            Account::Resource(object) => {
+                i.typ(2);
+                object.index(i);
+            }
         }
     }
 }
@@ -51,6 +56,10 @@ impl Pickle for Account {
                 1u16.pickle(out);
                 inner.pickle(out);
             }
+            Account::Resource(inner) => {
+                2u16.pickle(out);
+                inner.pickle(out);
+            }
         }
     }
 
@@ -58,6 +67,7 @@ impl Pickle for Account {
         match u16::unpickle(stream)? {
             0 => Pickle::unpickle(stream).map(Account::User),
             1 => Pickle::unpickle(stream).map(Account::Group),
+            2 => Pickle::unpickle(stream).map(Account::Resource),
             _ => None,
         }
     }
@@ -80,6 +90,13 @@ impl IntoValue for Account {
                     .insert_unchecked(Property::Type, JmapValue::Str("Group".into()));
                 obj
             }
+            Account::Resource(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut()
+                    .unwrap()
+                    .insert_unchecked(Property::Type, JmapValue::Str("Resource".into()));
+                obj
+            }
         }
     }
 }
@@ -94,11 +111,13 @@ impl RegistryJsonPatch for Account {
             match object_type(&pointer, &value)? {
                 AccountType::User => *self = Account::User(Default::default()),
                 AccountType::Group => *self = Account::Group(Default::default()),
+                AccountType::Resource => *self = Account::Resource(Default::default()),
             }
         }
         match self {
             Account::User(inner) => inner.patch(pointer, value),
             Account::Group(inner) => inner.patch(pointer, value),
+            Account::Resource(inner) => inner.patch(pointer, value),
         }
     }
 }
@@ -108,6 +127,7 @@ impl Account {
         match self {
             Account::User(_) => AccountType::User,
             Account::Group(_) => AccountType::Group,
+            Account::Resource(_) => AccountType::Resource,
         }
     }
 }
@@ -3616,6 +3636,275 @@ impl RegistryJsonPropertyPatch for AzureStore {
     }
 }
 
+impl ObjectImpl for Backup {
+    const FLAGS: u64 = OBJ_SINGLETON;
+    const VERSION: u8 = 0;
+    const OBJECT: ObjectType = ObjectType::Backup;
+
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        match self {
+            Backup::Disabled => true,
+            Backup::Enabled(inner) => inner.validate(errors),
+        }
+    }
+
+    fn index<'x>(&'x self, _: &mut IndexBuilder<'x>) {}
+}
+
+impl Default for Backup {
+    fn default() -> Self {
+        Backup::Disabled
+    }
+}
+
+impl Pickle for Backup {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        match self {
+            Backup::Disabled => {
+                0u16.pickle(out);
+            }
+            Backup::Enabled(inner) => {
+                1u16.pickle(out);
+                inner.pickle(out);
+            }
+        }
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        match u16::unpickle(stream)? {
+            0 => Some(Backup::Disabled),
+            1 => Pickle::unpickle(stream).map(Backup::Enabled),
+            _ => None,
+        }
+    }
+}
+
+impl IntoValue for Backup {
+    fn into_value(self) -> JmapValue<'static> {
+        match self {
+            Backup::Disabled => {
+                let mut obj = jmap_tools::Map::new();
+                obj.insert_unchecked(Property::Type, JmapValue::Str("Disabled".into()));
+                JmapValue::Object(obj)
+            }
+            Backup::Enabled(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut()
+                    .unwrap()
+                    .insert_unchecked(Property::Type, JmapValue::Str("Enabled".into()));
+                obj
+            }
+        }
+    }
+}
+
+impl RegistryJsonPatch for Backup {
+    fn patch<'x>(
+        &mut self,
+        pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        if !pointer.has_next() {
+            match object_type(&pointer, &value)? {
+                BackupType::Disabled => *self = Backup::Disabled,
+                BackupType::Enabled => *self = Backup::Enabled(Default::default()),
+            }
+        }
+        match self {
+            Backup::Disabled => pointer.assert_eof(),
+            Backup::Enabled(inner) => inner.patch(pointer, value),
+        }
+    }
+}
+
+impl Backup {
+    pub fn object_type(&self) -> BackupType {
+        match self {
+            Backup::Disabled => BackupType::Disabled,
+            Backup::Enabled(_) => BackupType::Enabled,
+        }
+    }
+}
+
+impl BackupProperties {
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.destination;
+        value.validate(errors);
+        let value = &self.prefix;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::Prefix));
+        }
+        let value = &self.schedule;
+        value.validate(errors);
+        let value = &self.full_backup_interval;
+        if *value < 1 {
+            errors.push(ValidationError::min_value(Property::FullBackupInterval, 1));
+        }
+        let value = &self.retain_daily;
+        if *value < 1 {
+            errors.push(ValidationError::min_value(Property::RetainDaily, 1));
+        }
+        let value = &self.encryption_key;
+        value.validate(errors);
+        errors.len() == neb
+    }
+}
+
+impl Pickle for BackupProperties {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.destination.pickle(out);
+        self.prefix.pickle(out);
+        self.schedule.pickle(out);
+        self.full_backup_interval.pickle(out);
+        self.retain_daily.pickle(out);
+        self.retain_weekly.pickle(out);
+        self.encryption_key.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.destination = Pickle::unpickle(stream)?;
+        this.prefix = Pickle::unpickle(stream)?;
+        this.schedule = Pickle::unpickle(stream)?;
+        this.full_backup_interval = Pickle::unpickle(stream)?;
+        this.retain_daily = Pickle::unpickle(stream)?;
+        this.retain_weekly = Pickle::unpickle(stream)?;
+        this.encryption_key = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for BackupProperties {
+    fn default() -> Self {
+        Self {
+            destination: Default::default(),
+            prefix: "backups/".to_string(),
+            schedule: Cron::Daily(CronDaily {
+                hour: 3u64,
+                minute: 0u64,
+            }),
+            full_backup_interval: 7u64,
+            retain_daily: 7u64,
+            retain_weekly: 4u64,
+            encryption_key: Default::default(),
+        }
+    }
+}
+
+impl IntoValue for BackupProperties {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(8);
+        map.insert_unchecked(Property::Destination, self.destination.into_value());
+        map.insert_unchecked(Property::Prefix, self.prefix.into_value());
+        map.insert_unchecked(Property::Schedule, self.schedule.into_value());
+        map.insert_unchecked(
+            Property::FullBackupInterval,
+            self.full_backup_interval.into_value(),
+        );
+        map.insert_unchecked(Property::RetainDaily, self.retain_daily.into_value());
+        map.insert_unchecked(Property::RetainWeekly, self.retain_weekly.into_value());
+        map.insert_unchecked(Property::EncryptionKey, self.encryption_key.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for BackupProperties {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::Destination) => self.destination.patch(pointer, value),
+            Some(Property::Prefix) => self
+                .prefix
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::Schedule) => self.schedule.patch(pointer, value),
+            Some(Property::FullBackupInterval) => self.full_backup_interval.patch(pointer, value),
+            Some(Property::RetainDaily) => self.retain_daily.patch(pointer, value),
+            Some(Property::RetainWeekly) => self.retain_weekly.patch(pointer, value),
+            Some(Property::EncryptionKey) => self.encryption_key.patch(pointer, value),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
+impl ObjectImpl for BlobEncryptionKey {
+    const FLAGS: u64 = 0;
+    const VERSION: u8 = 0;
+    const OBJECT: ObjectType = ObjectType::BlobEncryptionKey;
+
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.description;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::Description));
+        }
+        let value = &self.secret;
+        value.validate(errors);
+        errors.len() == neb
+    }
+
+    fn index<'x>(&'x self, _: &mut IndexBuilder<'x>) {}
+}
+
+impl Pickle for BlobEncryptionKey {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.description.pickle(out);
+        self.secret.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.description = Pickle::unpickle(stream)?;
+        this.secret = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for BlobEncryptionKey {
+    fn default() -> Self {
+        Self {
+            description: Default::default(),
+            secret: Default::default(),
+        }
+    }
+}
+
+impl IntoValue for BlobEncryptionKey {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(4);
+        map.insert_unchecked(Property::Description, self.description.into_value());
+        map.insert_unchecked(Property::Secret, self.secret.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for BlobEncryptionKey {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::Description) => self
+                .description
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::Secret) => self.secret.patch(pointer.assert_read_only()?, value),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
 impl ObjectImpl for BlobStore {
     const FLAGS: u64 = OBJ_SINGLETON;
     const VERSION: u8 = 1;
@@ -3631,6 +3920,7 @@ impl ObjectImpl for BlobStore {
             BlobStore::FoundationDb(inner) => inner.validate(errors),
             BlobStore::PostgreSql(inner) => inner.validate(errors),
             BlobStore::MySql(inner) => inner.validate(errors),
+            BlobStore::Tiered(inner) => inner.validate(errors),
         }
     }
 
@@ -3677,6 +3967,10 @@ impl Pickle for BlobStore {
                 7u16.pickle(out);
                 inner.pickle(out);
             }
+            BlobStore::Tiered(inner) => {
+                8u16.pickle(out);
+                inner.pickle(out);
+            }
         }
     }
 
@@ -3690,6 +3984,7 @@ impl Pickle for BlobStore {
             5 => Pickle::unpickle(stream).map(BlobStore::FoundationDb),
             6 => Pickle::unpickle(stream).map(BlobStore::PostgreSql),
             7 => Pickle::unpickle(stream).map(BlobStore::MySql),
+            8 => Pickle::unpickle(stream).map(BlobStore::Tiered),
             _ => None,
         }
     }
@@ -3752,6 +4047,13 @@ impl IntoValue for BlobStore {
                     .insert_unchecked(Property::Type, JmapValue::Str("MySql".into()));
                 obj
             }
+            BlobStore::Tiered(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut()
+                    .unwrap()
+                    .insert_unchecked(Property::Type, JmapValue::Str("Tiered".into()));
+                obj
+            }
         }
     }
 }
@@ -3772,6 +4074,7 @@ impl RegistryJsonPatch for BlobStore {
                 BlobStoreType::FoundationDb => *self = BlobStore::FoundationDb(Default::default()),
                 BlobStoreType::PostgreSql => *self = BlobStore::PostgreSql(Default::default()),
                 BlobStoreType::MySql => *self = BlobStore::MySql(Default::default()),
+                BlobStoreType::Tiered => *self = BlobStore::Tiered(Default::default()),
             }
         }
         match self {
@@ -3783,6 +4086,7 @@ impl RegistryJsonPatch for BlobStore {
             BlobStore::FoundationDb(inner) => inner.patch(pointer, value),
             BlobStore::PostgreSql(inner) => inner.patch(pointer, value),
             BlobStore::MySql(inner) => inner.patch(pointer, value),
+            BlobStore::Tiered(inner) => inner.patch(pointer, value),
         }
     }
 }
@@ -3798,6 +4102,7 @@ impl BlobStore {
             BlobStore::FoundationDb(_) => BlobStoreType::FoundationDb,
             BlobStore::PostgreSql(_) => BlobStoreType::PostgreSql,
             BlobStore::MySql(_) => BlobStoreType::MySql,
+            BlobStore::Tiered(_) => BlobStoreType::Tiered,
         }
     }
 }
@@ -4047,6 +4352,132 @@ impl RegistryJsonPropertyPatch for BlockedIp {
     }
 }
 
+impl BookingPolicy {
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        if let Some(value) = &self.working_hours_start {
+            if *value > 1439 {
+                errors.push(ValidationError::max_value(
+                    Property::WorkingHoursStart,
+                    1439,
+                ));
+            }
+        }
+        if let Some(value) = &self.working_hours_end {
+            if *value < 1 {
+                errors.push(ValidationError::min_value(Property::WorkingHoursEnd, 1));
+            }
+            if *value > 1440 {
+                errors.push(ValidationError::max_value(Property::WorkingHoursEnd, 1440));
+            }
+        }
+        let value = &self.allowed_group_ids;
+        for value in value.iter() {
+            if !value.is_valid() {
+                errors.push(ValidationError::required(Property::AllowedGroupIds));
+            }
+        }
+        errors.len() == neb
+    }
+
+    fn index<'x>(&'x self, i: &mut IndexBuilder<'x>) {
+        for id in self.allowed_group_ids.iter() {
+            i.foreign_key(
+                ObjectType::Account,
+                Some(*id),
+                Some(AccountType::Group.to_id()),
+            );
+        }
+    }
+}
+
+impl Pickle for BookingPolicy {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.auto_accept.pickle(out);
+        self.decline_conflicts.pickle(out);
+        self.max_duration.pickle(out);
+        self.working_hours_start.pickle(out);
+        self.working_hours_end.pickle(out);
+        self.weekdays_only.pickle(out);
+        self.allowed_group_ids.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.auto_accept = Pickle::unpickle(stream)?;
+        this.decline_conflicts = Pickle::unpickle(stream)?;
+        this.max_duration = Pickle::unpickle(stream)?;
+        this.working_hours_start = Pickle::unpickle(stream)?;
+        this.working_hours_end = Pickle::unpickle(stream)?;
+        this.weekdays_only = Pickle::unpickle(stream)?;
+        this.allowed_group_ids = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for BookingPolicy {
+    fn default() -> Self {
+        Self {
+            auto_accept: true,
+            decline_conflicts: true,
+            max_duration: Default::default(),
+            working_hours_start: Default::default(),
+            working_hours_end: Default::default(),
+            weekdays_only: false,
+            allowed_group_ids: Default::default(),
+        }
+    }
+}
+
+impl IntoValue for BookingPolicy {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(7);
+        map.insert_unchecked(Property::AutoAccept, self.auto_accept.into_value());
+        map.insert_unchecked(
+            Property::DeclineConflicts,
+            self.decline_conflicts.into_value(),
+        );
+        map.insert_unchecked(Property::MaxDuration, self.max_duration.into_value());
+        map.insert_unchecked(
+            Property::WorkingHoursStart,
+            self.working_hours_start.into_value(),
+        );
+        map.insert_unchecked(
+            Property::WorkingHoursEnd,
+            self.working_hours_end.into_value(),
+        );
+        map.insert_unchecked(Property::WeekdaysOnly, self.weekdays_only.into_value());
+        map.insert_unchecked(
+            Property::AllowedGroupIds,
+            self.allowed_group_ids.into_value(),
+        );
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for BookingPolicy {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::AutoAccept) => self.auto_accept.patch(pointer, value),
+            Some(Property::DeclineConflicts) => self.decline_conflicts.patch(pointer, value),
+            Some(Property::MaxDuration) => self.max_duration.patch(pointer, value),
+            Some(Property::WorkingHoursStart) => self.working_hours_start.patch(pointer, value),
+            Some(Property::WorkingHoursEnd) => self.working_hours_end.patch(pointer, value),
+            Some(Property::WeekdaysOnly) => self.weekdays_only.patch(pointer, value),
+            Some(Property::AllowedGroupIds) => self.allowed_group_ids.patch(pointer, value),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
 impl ObjectImpl for Bootstrap {
     const FLAGS: u64 = OBJ_SINGLETON;
     const VERSION: u8 = 1;
@@ -4195,7 +4626,7 @@ impl RegistryJsonPropertyPatch for Bootstrap {
 
 impl ObjectImpl for Cache {
     const FLAGS: u64 = OBJ_SINGLETON;
-    const VERSION: u8 = 0;
+    const VERSION: u8 = 1;
     const OBJECT: ObjectType = ObjectType::Cache;
 
     fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
@@ -4306,6 +4737,13 @@ impl ObjectImpl for Cache {
         if *value < 2048 {
             errors.push(ValidationError::min_value(Property::DkimSignatures, 2048));
         }
+        let value = &self.published_calendars;
+        if *value < 2048 {
+            errors.push(ValidationError::min_value(
+                Property::PublishedCalendars,
+                2048,
+            ));
+        }
         errors.len() == neb
     }
 
@@ -4340,6 +4778,7 @@ impl Pickle for Cache {
         self.mailing_lists.pickle(out);
         self.dkim_signatures.pickle(out);
         self.negative_ttl.pickle(out);
+        self.published_calendars.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -4370,6 +4809,9 @@ impl Pickle for Cache {
         this.mailing_lists = Pickle::unpickle(stream)?;
         this.dkim_signatures = Pickle::unpickle(stream)?;
         this.negative_ttl = Pickle::unpickle(stream)?;
+        if stream.version() >= 1 {
+            this.published_calendars = Pickle::unpickle(stream)?;
+        }
         Some(this)
     }
 }
@@ -4402,6 +4844,7 @@ impl Default for Cache {
             tenants: 5242880,
             mailing_lists: 2097152,
             dkim_signatures: 10485760,
+            published_calendars: 5242880,
             negative_ttl: Duration::from_millis(3600000),
         }
     }
@@ -4409,7 +4852,7 @@ impl Default for Cache {
 
 impl IntoValue for Cache {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(28);
+        let mut map = jmap_tools::Map::with_capacity(29);
         map.insert_unchecked(Property::AccessTokens, self.access_tokens.into_value());
         map.insert_unchecked(Property::Contacts, self.contacts.into_value());
         map.insert_unchecked(Property::DnsIpv4, self.dns_ipv4.into_value());
@@ -4441,6 +4884,10 @@ impl IntoValue for Cache {
         map.insert_unchecked(Property::Tenants, self.tenants.into_value());
         map.insert_unchecked(Property::MailingLists, self.mailing_lists.into_value());
         map.insert_unchecked(Property::DkimSignatures, self.dkim_signatures.into_value());
+        map.insert_unchecked(
+            Property::PublishedCalendars,
+            self.published_calendars.into_value(),
+        );
         map.insert_unchecked(Property::NegativeTtl, self.negative_ttl.into_value());
         JmapValue::Object(map)
     }
@@ -4480,6 +4927,7 @@ impl RegistryJsonPropertyPatch for Cache {
             Some(Property::Tenants) => self.tenants.patch(pointer, value),
             Some(Property::MailingLists) => self.mailing_lists.patch(pointer, value),
             Some(Property::DkimSignatures) => self.dkim_signatures.patch(pointer, value),
+            Some(Property::PublishedCalendars) => self.published_calendars.patch(pointer, value),
             Some(Property::NegativeTtl) => self.negative_ttl.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
@@ -5710,6 +6158,87 @@ impl RegistryJsonPropertyPatch for ClusterTaskGroupProperties {
     }
 }
 
+impl ObjectImpl for CompressionDictionary {
+    const FLAGS: u64 = 0;
+    const VERSION: u8 = 0;
+    const OBJECT: ObjectType = ObjectType::CompressionDictionary;
+
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.blob_id;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::BlobId));
+        }
+        let value = &self.created_at;
+        if !value.is_valid() {
+            errors.push(ValidationError::invalid(Property::CreatedAt, value));
+        }
+        errors.len() == neb
+    }
+
+    fn index<'x>(&'x self, _: &mut IndexBuilder<'x>) {}
+}
+
+impl Pickle for CompressionDictionary {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.content_class.pickle(out);
+        self.blob_id.pickle(out);
+        self.sample_count.pickle(out);
+        self.created_at.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.content_class = Pickle::unpickle(stream)?;
+        this.blob_id = Pickle::unpickle(stream)?;
+        this.sample_count = Pickle::unpickle(stream)?;
+        this.created_at = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for CompressionDictionary {
+    fn default() -> Self {
+        Self {
+            content_class: CompressionContentClass::Headers,
+            blob_id: Default::default(),
+            sample_count: Default::default(),
+            created_at: Default::default(),
+        }
+    }
+}
+
+impl IntoValue for CompressionDictionary {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(6);
+        map.insert_unchecked(Property::ContentClass, self.content_class.into_value());
+        map.insert_unchecked(Property::BlobId, self.blob_id.into_value());
+        map.insert_unchecked(Property::SampleCount, self.sample_count.into_value());
+        map.insert_unchecked(Property::CreatedAt, self.created_at.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for CompressionDictionary {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::ContentClass) => pointer.assert_server_set(),
+            Some(Property::BlobId) => pointer.assert_server_set(),
+            Some(Property::SampleCount) => pointer.assert_server_set(),
+            Some(Property::CreatedAt) => pointer.assert_server_set(),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
 impl ObjectImpl for Coordinator {
     const FLAGS: u64 = OBJ_SINGLETON;
     const VERSION: u8 = 0;
@@ -6715,6 +7244,7 @@ impl ObjectImpl for DataStore {
             DataStore::FoundationDb(inner) => inner.validate(errors),
             DataStore::PostgreSql(inner) => inner.validate(errors),
             DataStore::MySql(inner) => inner.validate(errors),
+            DataStore::Ephemeral(inner) => inner.validate(errors),
         }
     }
 
@@ -6750,6 +7280,10 @@ impl Pickle for DataStore {
                 4u16.pickle(out);
                 inner.pickle(out);
             }
+            DataStore::Ephemeral(inner) => {
+                5u16.pickle(out);
+                inner.pickle(out);
+            }
         }
     }
 
@@ -6760,6 +7294,7 @@ impl Pickle for DataStore {
             2 => Pickle::unpickle(stream).map(DataStore::FoundationDb),
             3 => Pickle::unpickle(stream).map(DataStore::PostgreSql),
             4 => Pickle::unpickle(stream).map(DataStore::MySql),
+            5 => Pickle::unpickle(stream).map(DataStore::Ephemeral),
             _ => None,
         }
     }
@@ -6803,6 +7338,13 @@ impl IntoValue for DataStore {
                     .insert_unchecked(Property::Type, JmapValue::Str("MySql".into()));
                 obj
             }
+            DataStore::Ephemeral(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut()
+                    .unwrap()
+                    .insert_unchecked(Property::Type, JmapValue::Str("Ephemeral".into()));
+                obj
+            }
         }
     }
 }
@@ -6820,6 +7362,7 @@ impl RegistryJsonPatch for DataStore {
                 DataStoreType::FoundationDb => *self = DataStore::FoundationDb(Default::default()),
                 DataStoreType::PostgreSql => *self = DataStore::PostgreSql(Default::default()),
                 DataStoreType::MySql => *self = DataStore::MySql(Default::default()),
+                DataStoreType::Ephemeral => *self = DataStore::Ephemeral(Default::default()),
             }
         }
         match self {
@@ -6828,6 +7371,7 @@ impl RegistryJsonPatch for DataStore {
             DataStore::FoundationDb(inner) => inner.patch(pointer, value),
             DataStore::PostgreSql(inner) => inner.patch(pointer, value),
             DataStore::MySql(inner) => inner.patch(pointer, value),
+            DataStore::Ephemeral(inner) => inner.patch(pointer, value),
         }
     }
 }
@@ -6840,6 +7384,78 @@ impl DataStore {
             DataStore::FoundationDb(_) => DataStoreType::FoundationDb,
             DataStore::PostgreSql(_) => DataStoreType::PostgreSql,
             DataStore::MySql(_) => DataStoreType::MySql,
+            DataStore::Ephemeral(_) => DataStoreType::Ephemeral,
+        }
+    }
+}
+
+impl ObjectImpl for DataStoreShard {
+    const FLAGS: u64 = 0;
+    const VERSION: u8 = 0;
+    const OBJECT: ObjectType = ObjectType::DataStoreShard;
+
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.description;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::Description));
+        }
+        let value = &self.store;
+        value.validate(errors);
+        errors.len() == neb
+    }
+
+    fn index<'x>(&'x self, _: &mut IndexBuilder<'x>) {}
+}
+
+impl Pickle for DataStoreShard {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.description.pickle(out);
+        self.store.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.description = Pickle::unpickle(stream)?;
+        this.store = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for DataStoreShard {
+    fn default() -> Self {
+        Self {
+            description: Default::default(),
+            store: Default::default(),
+        }
+    }
+}
+
+impl IntoValue for DataStoreShard {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(4);
+        map.insert_unchecked(Property::Description, self.description.into_value());
+        map.insert_unchecked(Property::Store, self.store.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for DataStoreShard {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::Description) => self
+                .description
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::Store) => self.store.patch(pointer, value),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
         }
     }
 }
@@ -20109,7 +20725,7 @@ impl RegistryJsonPropertyPatch for ElasticSearchStore {
 
 impl ObjectImpl for Email {
     const FLAGS: u64 = OBJ_SINGLETON;
-    const VERSION: u8 = 0;
+    const VERSION: u8 = 1;
     const OBJECT: ObjectType = ObjectType::Email;
 
     fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
@@ -20133,6 +20749,13 @@ impl ObjectImpl for Email {
                 1,
             ));
         }
+        let value = &self.compression_level;
+        if *value < 1 {
+            errors.push(ValidationError::min_value(Property::CompressionLevel, 1));
+        }
+        if *value > 22 {
+            errors.push(ValidationError::max_value(Property::CompressionLevel, 22));
+        }
         let value = &self.default_folders;
         for value in value.values() {
             value.validate(errors);
@@ -20189,6 +20812,7 @@ impl Pickle for Email {
         self.max_mailboxes.pickle(out);
         self.max_masked_addresses.pickle(out);
         self.max_public_keys.pickle(out);
+        self.compression_level.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -20207,6 +20831,9 @@ impl Pickle for Email {
         this.max_mailboxes = Pickle::unpickle(stream)?;
         this.max_masked_addresses = Pickle::unpickle(stream)?;
         this.max_public_keys = Pickle::unpickle(stream)?;
+        if stream.version() >= 1 {
+            this.compression_level = Pickle::unpickle(stream)?;
+        }
         Some(this)
     }
 }
@@ -20221,6 +20848,7 @@ impl Default for Email {
             encrypt_on_append: false,
             encrypt_at_rest: true,
             compression_algorithm: CompressionAlgo::Lz4,
+            compression_level: 3u64,
             default_folders: Default::default(),
             max_messages: Default::default(),
             max_submissions: Some(500u64),
@@ -20234,7 +20862,7 @@ impl Default for Email {
 
 impl IntoValue for Email {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(16);
+        let mut map = jmap_tools::Map::with_capacity(17);
         map.insert_unchecked(
             Property::MaxAttachmentSize,
             self.max_attachment_size.into_value(),
@@ -20257,6 +20885,10 @@ impl IntoValue for Email {
             Property::CompressionAlgorithm,
             self.compression_algorithm.into_value(),
         );
+        map.insert_unchecked(
+            Property::CompressionLevel,
+            self.compression_level.into_value(),
+        );
         map.insert_unchecked(Property::DefaultFolders, self.default_folders.into_value());
         map.insert_unchecked(Property::MaxMessages, self.max_messages.into_value());
         map.insert_unchecked(Property::MaxSubmissions, self.max_submissions.into_value());
@@ -20289,6 +20921,7 @@ impl RegistryJsonPropertyPatch for Email {
             Some(Property::CompressionAlgorithm) => {
                 self.compression_algorithm.patch(pointer, value)
             }
+            Some(Property::CompressionLevel) => self.compression_level.patch(pointer, value),
             Some(Property::DefaultFolders) => self.default_folders.patch(pointer, value),
             Some(Property::MaxMessages) => self.max_messages.patch(pointer, value),
             Some(Property::MaxSubmissions) => self.max_submissions.patch(pointer, value),
@@ -20788,6 +21421,72 @@ impl RegistryJsonPropertyPatch for Enterprise {
     }
 }
 
+impl EphemeralStore {
+    fn validate(&self, _: &mut Vec<ValidationError>) -> bool {
+        true
+    }
+}
+
+impl Pickle for EphemeralStore {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.path.pickle(out);
+        self.snapshot_interval.pickle(out);
+        self.sync_writes.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.path = Pickle::unpickle(stream)?;
+        this.snapshot_interval = Pickle::unpickle(stream)?;
+        this.sync_writes = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for EphemeralStore {
+    fn default() -> Self {
+        Self {
+            path: Default::default(),
+            snapshot_interval: Duration::from_millis(3600000),
+            sync_writes: false,
+        }
+    }
+}
+
+impl IntoValue for EphemeralStore {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(5);
+        map.insert_unchecked(Property::Path, self.path.into_value());
+        map.insert_unchecked(
+            Property::SnapshotInterval,
+            self.snapshot_interval.into_value(),
+        );
+        map.insert_unchecked(Property::SyncWrites, self.sync_writes.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for EphemeralStore {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::Path) => self
+                .path
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::SnapshotInterval) => self.snapshot_interval.patch(pointer, value),
+            Some(Property::SyncWrites) => self.sync_writes.patch(pointer, value),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
 impl ObjectImpl for EventTracingLevel {
     const FLAGS: u64 = 0;
     const VERSION: u8 = 0;
@@ -20984,7 +21683,7 @@ impl RegistryJsonPropertyPatch for ExpressionMatch {
 
 impl ObjectImpl for FileStorage {
     const FLAGS: u64 = OBJ_SINGLETON;
-    const VERSION: u8 = 0;
+    const VERSION: u8 = 1;
     const OBJECT: ObjectType = ObjectType::FileStorage;
 
     fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
@@ -20999,6 +21698,10 @@ impl ObjectImpl for FileStorage {
                 errors.push(ValidationError::min_value(Property::MaxFolders, 1));
             }
         }
+        let value = &self.max_versions;
+        if *value > 1000 {
+            errors.push(ValidationError::max_value(Property::MaxVersions, 1000));
+        }
         errors.len() == neb
     }
 
@@ -21010,6 +21713,8 @@ impl Pickle for FileStorage {
         self.max_size.pickle(out);
         self.max_files.pickle(out);
         self.max_folders.pickle(out);
+        self.max_versions.pickle(out);
+        self.version_retention.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -21017,6 +21722,12 @@ impl Pickle for FileStorage {
         this.max_size = Pickle::unpickle(stream)?;
         this.max_files = Pickle::unpickle(stream)?;
         this.max_folders = Pickle::unpickle(stream)?;
+        if stream.version() >= 1 {
+            this.max_versions = Pickle::unpickle(stream)?;
+        }
+        if stream.version() >= 1 {
+            this.version_retention = Pickle::unpickle(stream)?;
+        }
         Some(this)
     }
 }
@@ -21027,6 +21738,8 @@ impl Default for FileStorage {
             max_size: 26214400,
             max_files: Default::default(),
             max_folders: Default::default(),
+            max_versions: 10,
+            version_retention: Some(Duration::from_millis(2592000000)),
         }
     }
 }
@@ -21037,6 +21750,11 @@ impl IntoValue for FileStorage {
         map.insert_unchecked(Property::MaxSize, self.max_size.into_value());
         map.insert_unchecked(Property::MaxFiles, self.max_files.into_value());
         map.insert_unchecked(Property::MaxFolders, self.max_folders.into_value());
+        map.insert_unchecked(Property::MaxVersions, self.max_versions.into_value());
+        map.insert_unchecked(
+            Property::VersionRetention,
+            self.version_retention.into_value(),
+        );
         JmapValue::Object(map)
     }
 }
@@ -21051,6 +21769,8 @@ impl RegistryJsonPropertyPatch for FileStorage {
             Some(Property::MaxSize) => self.max_size.patch(pointer, value),
             Some(Property::MaxFiles) => self.max_files.patch(pointer, value),
             Some(Property::MaxFolders) => self.max_folders.patch(pointer, value),
+            Some(Property::MaxVersions) => self.max_versions.patch(pointer, value),
+            Some(Property::VersionRetention) => self.version_retention.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -21485,6 +22205,10 @@ impl RegistryJsonPropertyPatch for GroupAccount {
             Some(Property::Roles) => self.roles.patch(pointer, value),
             Some(Property::Quotas) => self.quotas.patch(pointer, value),
             Some(Property::UsedDiskQuota) => pointer.assert_server_set(),
+            Some(Property::UsedDiskQuotaByType) => pointer.assert_server_set(),
+            Some(Property::UsedDiskQuotaByMailbox) => pointer.assert_server_set(),
+            Some(Property::UsedDiskQuotaBySize) => pointer.assert_server_set(),
+            Some(Property::ItemCountBySize) => pointer.assert_server_set(),
             Some(Property::Permissions) => self.permissions.patch(pointer, value),
             Some(Property::Aliases) => self.aliases.patch(pointer, value),
             Some(Property::Locale) => self.locale.patch(pointer, value),
@@ -22866,7 +23590,7 @@ impl InMemoryStoreBase {
 
 impl ObjectImpl for Jmap {
     const FLAGS: u64 = OBJ_SINGLETON;
-    const VERSION: u8 = 2;
+    const VERSION: u8 = 3;
     const OBJECT: ObjectType = ObjectType::Jmap;
 
     fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
@@ -22970,6 +23694,11 @@ impl ObjectImpl for Jmap {
         if *value < 512 {
             errors.push(ValidationError::min_value(Property::MaxPushSize, 512));
         }
+        if let Some(value) = &self.max_webhooks {
+            if *value < 1 {
+                errors.push(ValidationError::min_value(Property::MaxWebhooks, 1));
+            }
+        }
         errors.len() == neb
     }
 
@@ -23009,6 +23738,7 @@ impl Pickle for Jmap {
         self.web_push_key.pickle(out);
         self.web_push_contact.pickle(out);
         self.max_push_size.pickle(out);
+        self.max_webhooks.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -23050,6 +23780,9 @@ impl Pickle for Jmap {
         if stream.version() >= 2 {
             this.max_push_size = Pickle::unpickle(stream)?;
         }
+        if stream.version() >= 3 {
+            this.max_webhooks = Pickle::unpickle(stream)?;
+        }
         Some(this)
     }
 }
@@ -23088,13 +23821,14 @@ impl Default for Jmap {
             web_push_key: Default::default(),
             web_push_contact: Default::default(),
             max_push_size: 4096u64,
+            max_webhooks: Some(15u64),
         }
     }
 }
 
 impl IntoValue for Jmap {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(33);
+        let mut map = jmap_tools::Map::with_capacity(34);
         map.insert_unchecked(
             Property::ParseLimitEvent,
             self.parse_limit_event.into_value(),
@@ -23180,6 +23914,7 @@ impl IntoValue for Jmap {
         map.insert_unchecked(Property::WebPushKey, self.web_push_key.into_value());
         map.insert_unchecked(Property::WebPushContact, self.web_push_contact.into_value());
         map.insert_unchecked(Property::MaxPushSize, self.max_push_size.into_value());
+        map.insert_unchecked(Property::MaxWebhooks, self.max_webhooks.into_value());
         JmapValue::Object(map)
     }
 }
@@ -23228,6 +23963,7 @@ impl RegistryJsonPropertyPatch for Jmap {
                 .web_push_contact
                 .patch(pointer.with_validators(&[StringValidator::Trim]), value),
             Some(Property::MaxPushSize) => self.max_push_size.patch(pointer, value),
+            Some(Property::MaxWebhooks) => self.max_webhooks.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -24977,7 +25713,7 @@ impl RegistryJsonPropertyPatch for MetricSum {
 
 impl ObjectImpl for Metrics {
     const FLAGS: u64 = OBJ_SINGLETON;
-    const VERSION: u8 = 0;
+    const VERSION: u8 = 1;
     const OBJECT: ObjectType = ObjectType::Metrics;
 
     fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
@@ -25405,6 +26141,10 @@ impl MetricsPrometheusProperties {
                 errors.push(ValidationError::required(Property::AuthUsername));
             }
         }
+        let value = &self.top_accounts;
+        if *value > 1000 {
+            errors.push(ValidationError::max_value(Property::TopAccounts, 1000));
+        }
         errors.len() == neb
     }
 }
@@ -25413,12 +26153,16 @@ impl Pickle for MetricsPrometheusProperties {
     fn pickle(&self, out: &mut Vec<u8>) {
         self.auth_secret.pickle(out);
         self.auth_username.pickle(out);
+        self.top_accounts.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
         let mut this = Self::default();
         this.auth_secret = Pickle::unpickle(stream)?;
         this.auth_username = Pickle::unpickle(stream)?;
+        if stream.version() >= 1 {
+            this.top_accounts = Pickle::unpickle(stream)?;
+        }
         Some(this)
     }
 }
@@ -25428,6 +26172,7 @@ impl Default for MetricsPrometheusProperties {
         Self {
             auth_secret: Default::default(),
             auth_username: Default::default(),
+            top_accounts: 10,
         }
     }
 }
@@ -25437,6 +26182,7 @@ impl IntoValue for MetricsPrometheusProperties {
         let mut map = jmap_tools::Map::with_capacity(4);
         map.insert_unchecked(Property::AuthSecret, self.auth_secret.into_value());
         map.insert_unchecked(Property::AuthUsername, self.auth_username.into_value());
+        map.insert_unchecked(Property::TopAccounts, self.top_accounts.into_value());
         JmapValue::Object(map)
     }
 }
@@ -25452,6 +26198,7 @@ impl RegistryJsonPropertyPatch for MetricsPrometheusProperties {
             Some(Property::AuthUsername) => self
                 .auth_username
                 .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::TopAccounts) => self.top_accounts.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -33034,6 +33781,212 @@ impl RegistryJsonPropertyPatch for ReportSettings {
     }
 }
 
+impl ResourceAccount {
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.name;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::Name));
+        }
+        let value = &self.domain_id;
+        if !value.is_valid() {
+            errors.push(ValidationError::required(Property::DomainId));
+        }
+        if let Some(value) = &self.description {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::Description));
+            }
+        }
+        if let Some(value) = &self.capacity {
+            if *value < 1 {
+                errors.push(ValidationError::min_value(Property::Capacity, 1));
+            }
+        }
+        let value = &self.created_at;
+        if !value.is_valid() {
+            errors.push(ValidationError::invalid(Property::CreatedAt, value));
+        }
+        if let Some(value) = &self.member_tenant_id {
+            if !value.is_valid() {
+                errors.push(ValidationError::required(Property::MemberTenantId));
+            }
+        }
+        let value = &self.booking_policy;
+        value.validate(errors);
+        let value = &self.delegate_ids;
+        for value in value.iter() {
+            if !value.is_valid() {
+                errors.push(ValidationError::required(Property::DelegateIds));
+            }
+        }
+        let value = &self.roles;
+        value.validate(errors);
+        let value = &self.permissions;
+        value.validate(errors);
+        let value = &self.aliases;
+        for value in value.values() {
+            value.validate(errors);
+        }
+        errors.len() == neb
+    }
+
+    fn index<'x>(&'x self, i: &mut IndexBuilder<'x>) {
+        i.unique_global_composite(Property::Email, &self.name, &self.domain_id);
+        i.text(Property::Text, &self.name);
+        i.search(Property::Name, &self.name);
+        i.foreign_key(ObjectType::Domain, self.domain_id.into(), None);
+        i.search(Property::DomainId, &self.domain_id);
+        if let Some(value) = &self.description {
+            i.text(Property::Text, value);
+        }
+        i.foreign_key(ObjectType::Tenant, self.member_tenant_id, None);
+        if let Some(value) = &self.member_tenant_id {
+            i.search(Property::MemberTenantId, value);
+        }
+        self.booking_policy.index(i);
+        for id in self.delegate_ids.iter() {
+            i.foreign_key(
+                ObjectType::Account,
+                Some(*id),
+                Some(AccountType::User.to_id()),
+            );
+        }
+        for value in self.delegate_ids.iter() {
+            i.search(Property::DelegateIds, value);
+        }
+        self.roles.index(i);
+        for item in self.aliases.values() {
+            item.index(i);
+        }
+    }
+}
+
+impl Pickle for ResourceAccount {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.name.pickle(out);
+        self.domain_id.pickle(out);
+        self.resource_type.pickle(out);
+        self.description.pickle(out);
+        self.capacity.pickle(out);
+        self.created_at.pickle(out);
+        self.member_tenant_id.pickle(out);
+        self.booking_policy.pickle(out);
+        self.delegate_ids.pickle(out);
+        self.roles.pickle(out);
+        self.quotas.pickle(out);
+        self.permissions.pickle(out);
+        self.aliases.pickle(out);
+        self.locale.pickle(out);
+        self.time_zone.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.name = Pickle::unpickle(stream)?;
+        this.domain_id = Pickle::unpickle(stream)?;
+        this.resource_type = Pickle::unpickle(stream)?;
+        this.description = Pickle::unpickle(stream)?;
+        this.capacity = Pickle::unpickle(stream)?;
+        this.created_at = Pickle::unpickle(stream)?;
+        this.member_tenant_id = Pickle::unpickle(stream)?;
+        this.booking_policy = Pickle::unpickle(stream)?;
+        this.delegate_ids = Pickle::unpickle(stream)?;
+        this.roles = Pickle::unpickle(stream)?;
+        this.quotas = Pickle::unpickle(stream)?;
+        this.permissions = Pickle::unpickle(stream)?;
+        this.aliases = Pickle::unpickle(stream)?;
+        this.locale = Pickle::unpickle(stream)?;
+        this.time_zone = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for ResourceAccount {
+    fn default() -> Self {
+        Self {
+            name: Default::default(),
+            domain_id: Default::default(),
+            resource_type: ResourceType::Room,
+            description: Default::default(),
+            capacity: Default::default(),
+            created_at: Default::default(),
+            member_tenant_id: Default::default(),
+            booking_policy: Default::default(),
+            delegate_ids: Default::default(),
+            roles: Default::default(),
+            quotas: Default::default(),
+            permissions: Default::default(),
+            aliases: Default::default(),
+            locale: Locale::EnUS,
+            time_zone: Default::default(),
+        }
+    }
+}
+
+impl IntoValue for ResourceAccount {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(17);
+        map.insert_unchecked(Property::Name, self.name.into_value());
+        map.insert_unchecked(Property::DomainId, self.domain_id.into_value());
+        map.insert_unchecked(Property::ResourceType, self.resource_type.into_value());
+        map.insert_unchecked(Property::Description, self.description.into_value());
+        map.insert_unchecked(Property::Capacity, self.capacity.into_value());
+        map.insert_unchecked(Property::CreatedAt, self.created_at.into_value());
+        map.insert_unchecked(Property::MemberTenantId, self.member_tenant_id.into_value());
+        map.insert_unchecked(Property::BookingPolicy, self.booking_policy.into_value());
+        map.insert_unchecked(Property::DelegateIds, self.delegate_ids.into_value());
+        map.insert_unchecked(Property::Roles, self.roles.into_value());
+        map.insert_unchecked(Property::Quotas, self.quotas.into_value());
+        map.insert_unchecked(Property::Permissions, self.permissions.into_value());
+        map.insert_unchecked(Property::Aliases, self.aliases.into_value());
+        map.insert_unchecked(Property::Locale, self.locale.into_value());
+        map.insert_unchecked(Property::TimeZone, self.time_zone.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for ResourceAccount {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::Name) => self.name.patch(
+                pointer.with_validators(&[StringValidator::EmailLocalPart]),
+                value,
+            ),
+            Some(Property::DomainId) => self.domain_id.patch(pointer, value),
+            Some(Property::EmailAddress) => pointer.assert_server_set(),
+            Some(Property::ResourceType) => self.resource_type.patch(pointer, value),
+            Some(Property::Description) => self.description.patch(pointer, value),
+            Some(Property::Capacity) => self.capacity.patch(pointer, value),
+            Some(Property::CreatedAt) => pointer.assert_server_set(),
+            Some(Property::MemberTenantId) => self
+                .member_tenant_id
+                .patch(pointer.assert_can_set_tenant()?, value),
+            Some(Property::BookingPolicy) => self.booking_policy.patch(pointer, value),
+            Some(Property::DelegateIds) => self.delegate_ids.patch(pointer, value),
+            Some(Property::Roles) => self.roles.patch(pointer, value),
+            Some(Property::Quotas) => self.quotas.patch(pointer, value),
+            Some(Property::UsedDiskQuota) => pointer.assert_server_set(),
+            Some(Property::UsedDiskQuotaByType) => pointer.assert_server_set(),
+            Some(Property::UsedDiskQuotaByMailbox) => pointer.assert_server_set(),
+            Some(Property::UsedDiskQuotaBySize) => pointer.assert_server_set(),
+            Some(Property::ItemCountBySize) => pointer.assert_server_set(),
+            Some(Property::Permissions) => self.permissions.patch(pointer, value),
+            Some(Property::Aliases) => self.aliases.patch(pointer, value),
+            Some(Property::Locale) => self.locale.patch(pointer, value),
+            Some(Property::TimeZone) => self.time_zone.patch(pointer, value),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
 impl RocksDbStore {
     fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
         let neb = errors.len();
@@ -41627,6 +42580,10 @@ impl ObjectImpl for Task {
             Task::AcmeRenewal(inner) => inner.validate(errors),
             Task::DkimManagement(inner) => inner.validate(errors),
             Task::DnsManagement(inner) => inner.validate(errors),
+            Task::EmailSnooze(inner) => inner.validate(errors),
+            Task::CalendarSubscription(inner) => inner.validate(errors),
+            Task::DataStoreMigration(inner) => inner.validate(errors),
+            Task::MoveAccount(inner) => inner.validate(errors),
         }
     }
 
@@ -41682,6 +42639,16 @@ impl ObjectImpl for Task {
             Task::DnsManagement(object) => {
                 object.index(i);
             }
+            Task::EmailSnooze(object) => {
+                object.index(i);
+            }
+            Task::CalendarSubscription(object) => {
+                object.index(i);
+            }
+            Task::DataStoreMigration(_) => {}
+            Task::MoveAccount(object) => {
+                object.index(i);
+            }
         }
     }
 }
@@ -41767,6 +42734,22 @@ impl Pickle for Task {
                 17u16.pickle(out);
                 inner.pickle(out);
             }
+            Task::EmailSnooze(inner) => {
+                18u16.pickle(out);
+                inner.pickle(out);
+            }
+            Task::CalendarSubscription(inner) => {
+                19u16.pickle(out);
+                inner.pickle(out);
+            }
+            Task::DataStoreMigration(inner) => {
+                20u16.pickle(out);
+                inner.pickle(out);
+            }
+            Task::MoveAccount(inner) => {
+                21u16.pickle(out);
+                inner.pickle(out);
+            }
         }
     }
 
@@ -41790,6 +42773,10 @@ impl Pickle for Task {
             15 => Pickle::unpickle(stream).map(Task::AcmeRenewal),
             16 => Pickle::unpickle(stream).map(Task::DkimManagement),
             17 => Pickle::unpickle(stream).map(Task::DnsManagement),
+            18 => Pickle::unpickle(stream).map(Task::EmailSnooze),
+            19 => Pickle::unpickle(stream).map(Task::CalendarSubscription),
+            20 => Pickle::unpickle(stream).map(Task::DataStoreMigration),
+            21 => Pickle::unpickle(stream).map(Task::MoveAccount),
             _ => None,
         }
     }
@@ -41926,6 +42913,35 @@ impl IntoValue for Task {
                     .insert_unchecked(Property::Type, JmapValue::Str("DnsManagement".into()));
                 obj
             }
+            Task::EmailSnooze(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut()
+                    .unwrap()
+                    .insert_unchecked(Property::Type, JmapValue::Str("EmailSnooze".into()));
+                obj
+            }
+            Task::CalendarSubscription(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut().unwrap().insert_unchecked(
+                    Property::Type,
+                    JmapValue::Str("CalendarSubscription".into()),
+                );
+                obj
+            }
+            Task::DataStoreMigration(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut()
+                    .unwrap()
+                    .insert_unchecked(Property::Type, JmapValue::Str("DataStoreMigration".into()));
+                obj
+            }
+            Task::MoveAccount(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut()
+                    .unwrap()
+                    .insert_unchecked(Property::Type, JmapValue::Str("MoveAccount".into()));
+                obj
+            }
         }
     }
 }
@@ -41968,6 +42984,14 @@ impl RegistryJsonPatch for Task {
                 TaskType::AcmeRenewal => *self = Task::AcmeRenewal(Default::default()),
                 TaskType::DkimManagement => *self = Task::DkimManagement(Default::default()),
                 TaskType::DnsManagement => *self = Task::DnsManagement(Default::default()),
+                TaskType::EmailSnooze => *self = Task::EmailSnooze(Default::default()),
+                TaskType::CalendarSubscription => {
+                    *self = Task::CalendarSubscription(Default::default())
+                }
+                TaskType::DataStoreMigration => {
+                    *self = Task::DataStoreMigration(Default::default())
+                }
+                TaskType::MoveAccount => *self = Task::MoveAccount(Default::default()),
             }
         }
         match self {
@@ -41989,6 +43013,10 @@ impl RegistryJsonPatch for Task {
             Task::AcmeRenewal(inner) => inner.patch(pointer, value),
             Task::DkimManagement(inner) => inner.patch(pointer, value),
             Task::DnsManagement(inner) => inner.patch(pointer, value),
+            Task::EmailSnooze(inner) => inner.patch(pointer, value),
+            Task::CalendarSubscription(inner) => inner.patch(pointer, value),
+            Task::DataStoreMigration(inner) => inner.patch(pointer, value),
+            Task::MoveAccount(inner) => inner.patch(pointer, value),
         }
     }
 }
@@ -42014,6 +43042,10 @@ impl Task {
             Task::AcmeRenewal(_) => TaskType::AcmeRenewal,
             Task::DkimManagement(_) => TaskType::DkimManagement,
             Task::DnsManagement(_) => TaskType::DnsManagement,
+            Task::EmailSnooze(_) => TaskType::EmailSnooze,
+            Task::CalendarSubscription(_) => TaskType::CalendarSubscription,
+            Task::DataStoreMigration(_) => TaskType::DataStoreMigration,
+            Task::MoveAccount(_) => TaskType::MoveAccount,
         }
     }
 }
@@ -42496,6 +43528,172 @@ impl RegistryJsonPropertyPatch for TaskCalendarItipMessage {
     }
 }
 
+impl TaskCalendarSubscription {
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.account_id;
+        if !value.is_valid() {
+            errors.push(ValidationError::required(Property::AccountId));
+        }
+        let value = &self.document_id;
+        if !value.is_valid() {
+            errors.push(ValidationError::invalid(Property::DocumentId, value));
+        }
+        let value = &self.status;
+        value.validate(errors);
+        errors.len() == neb
+    }
+
+    fn index<'x>(&'x self, i: &mut IndexBuilder<'x>) {
+        i.foreign_key(ObjectType::Account, self.account_id.into(), None);
+    }
+}
+
+impl Pickle for TaskCalendarSubscription {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.account_id.pickle(out);
+        self.document_id.pickle(out);
+        self.status.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.account_id = Pickle::unpickle(stream)?;
+        this.document_id = Pickle::unpickle(stream)?;
+        this.status = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for TaskCalendarSubscription {
+    fn default() -> Self {
+        Self {
+            account_id: Default::default(),
+            document_id: Default::default(),
+            status: Default::default(),
+        }
+    }
+}
+
+impl IntoValue for TaskCalendarSubscription {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(5);
+        map.insert_unchecked(Property::AccountId, self.account_id.into_value());
+        map.insert_unchecked(Property::DocumentId, self.document_id.into_value());
+        map.insert_unchecked(Property::Status, self.status.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for TaskCalendarSubscription {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::AccountId) => self
+                .account_id
+                .patch(pointer.assert_read_only()?.assert_can_set_account()?, value),
+            Some(Property::DocumentId) => {
+                self.document_id.patch(pointer.assert_read_only()?, value)
+            }
+            Some(Property::Status) => self.status.patch(pointer, value),
+            Some(Property::Due) => pointer.assert_server_set(),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
+impl TaskDataStoreMigration {
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.target;
+        value.validate(errors);
+        let value = &self.status;
+        value.validate(errors);
+        errors.len() == neb
+    }
+}
+
+impl Pickle for TaskDataStoreMigration {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.target.pickle(out);
+        self.stage.pickle(out);
+        self.subspaces_copied.pickle(out);
+        self.keys_copied.pickle(out);
+        self.changed_keys.pickle(out);
+        self.status.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.target = Pickle::unpickle(stream)?;
+        this.stage = Pickle::unpickle(stream)?;
+        this.subspaces_copied = Pickle::unpickle(stream)?;
+        this.keys_copied = Pickle::unpickle(stream)?;
+        this.changed_keys = Pickle::unpickle(stream)?;
+        this.status = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for TaskDataStoreMigration {
+    fn default() -> Self {
+        Self {
+            target: Default::default(),
+            stage: DataStoreMigrationStage::Copy,
+            subspaces_copied: 0,
+            keys_copied: 0,
+            changed_keys: 0,
+            status: Default::default(),
+        }
+    }
+}
+
+impl IntoValue for TaskDataStoreMigration {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(8);
+        map.insert_unchecked(Property::Target, self.target.into_value());
+        map.insert_unchecked(Property::Stage, self.stage.into_value());
+        map.insert_unchecked(
+            Property::SubspacesCopied,
+            self.subspaces_copied.into_value(),
+        );
+        map.insert_unchecked(Property::KeysCopied, self.keys_copied.into_value());
+        map.insert_unchecked(Property::ChangedKeys, self.changed_keys.into_value());
+        map.insert_unchecked(Property::Status, self.status.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for TaskDataStoreMigration {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::Target) => self.target.patch(pointer.assert_read_only()?, value),
+            Some(Property::Stage) => pointer.assert_server_set(),
+            Some(Property::SubspacesCopied) => pointer.assert_server_set(),
+            Some(Property::KeysCopied) => pointer.assert_server_set(),
+            Some(Property::ChangedKeys) => pointer.assert_server_set(),
+            Some(Property::Status) => self.status.patch(pointer, value),
+            Some(Property::Due) => pointer.assert_server_set(),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
 impl TaskDestroyAccount {
     fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
         let neb = errors.len();
@@ -42810,6 +44008,87 @@ impl RegistryJsonPropertyPatch for TaskDomainManagement {
     }
 }
 
+impl TaskEmailSnooze {
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.account_id;
+        if !value.is_valid() {
+            errors.push(ValidationError::required(Property::AccountId));
+        }
+        let value = &self.document_id;
+        if !value.is_valid() {
+            errors.push(ValidationError::invalid(Property::DocumentId, value));
+        }
+        let value = &self.status;
+        value.validate(errors);
+        errors.len() == neb
+    }
+
+    fn index<'x>(&'x self, i: &mut IndexBuilder<'x>) {
+        i.foreign_key(ObjectType::Account, self.account_id.into(), None);
+    }
+}
+
+impl Pickle for TaskEmailSnooze {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.account_id.pickle(out);
+        self.document_id.pickle(out);
+        self.status.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.account_id = Pickle::unpickle(stream)?;
+        this.document_id = Pickle::unpickle(stream)?;
+        this.status = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for TaskEmailSnooze {
+    fn default() -> Self {
+        Self {
+            account_id: Default::default(),
+            document_id: Default::default(),
+            status: Default::default(),
+        }
+    }
+}
+
+impl IntoValue for TaskEmailSnooze {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(5);
+        map.insert_unchecked(Property::AccountId, self.account_id.into_value());
+        map.insert_unchecked(Property::DocumentId, self.document_id.into_value());
+        map.insert_unchecked(Property::Status, self.status.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for TaskEmailSnooze {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::AccountId) => self
+                .account_id
+                .patch(pointer.assert_read_only()?.assert_can_set_account()?, value),
+            Some(Property::DocumentId) => {
+                self.document_id.patch(pointer.assert_read_only()?, value)
+            }
+            Some(Property::Status) => self.status.patch(pointer, value),
+            Some(Property::Due) => pointer.assert_server_set(),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
 impl TaskIndexDocument {
     fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
         let neb = errors.len();
@@ -43128,6 +44407,97 @@ impl RegistryJsonPropertyPatch for TaskMergeThreads {
     }
 }
 
+impl TaskMoveAccount {
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.account_id;
+        if !value.is_valid() {
+            errors.push(ValidationError::required(Property::AccountId));
+        }
+        if let Some(value) = &self.shard_id {
+            if !value.is_valid() {
+                errors.push(ValidationError::required(Property::ShardId));
+            }
+        }
+        let value = &self.status;
+        value.validate(errors);
+        errors.len() == neb
+    }
+
+    fn index<'x>(&'x self, i: &mut IndexBuilder<'x>) {
+        i.foreign_key(ObjectType::Account, self.account_id.into(), None);
+        i.foreign_key(ObjectType::DataStoreShard, self.shard_id, None);
+    }
+}
+
+impl Pickle for TaskMoveAccount {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.account_id.pickle(out);
+        self.shard_id.pickle(out);
+        self.stage.pickle(out);
+        self.keys_copied.pickle(out);
+        self.status.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.account_id = Pickle::unpickle(stream)?;
+        this.shard_id = Pickle::unpickle(stream)?;
+        this.stage = Pickle::unpickle(stream)?;
+        this.keys_copied = Pickle::unpickle(stream)?;
+        this.status = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for TaskMoveAccount {
+    fn default() -> Self {
+        Self {
+            account_id: Default::default(),
+            shard_id: Default::default(),
+            stage: DataStoreMigrationStage::Copy,
+            keys_copied: 0,
+            status: Default::default(),
+        }
+    }
+}
+
+impl IntoValue for TaskMoveAccount {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(6);
+        map.insert_unchecked(Property::AccountId, self.account_id.into_value());
+        map.insert_unchecked(Property::ShardId, self.shard_id.into_value());
+        map.insert_unchecked(Property::Stage, self.stage.into_value());
+        map.insert_unchecked(Property::KeysCopied, self.keys_copied.into_value());
+        map.insert_unchecked(Property::Status, self.status.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for TaskMoveAccount {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::AccountId) => self
+                .account_id
+                .patch(pointer.assert_read_only()?.assert_can_set_account()?, value),
+            Some(Property::ShardId) => self.shard_id.patch(pointer.assert_read_only()?, value),
+            Some(Property::Stage) => pointer.assert_server_set(),
+            Some(Property::KeysCopied) => pointer.assert_server_set(),
+            Some(Property::Status) => self.status.patch(pointer, value),
+            Some(Property::Due) => pointer.assert_server_set(),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
 impl TaskRestoreArchivedItem {
     fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
         let neb = errors.len();
@@ -44158,6 +45528,72 @@ impl RegistryJsonPropertyPatch for Tenant {
     }
 }
 
+impl TieredBlobStore {
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.hot;
+        value.validate(errors);
+        let value = &self.cold;
+        value.validate(errors);
+        errors.len() == neb
+    }
+}
+
+impl Pickle for TieredBlobStore {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.hot.pickle(out);
+        self.cold.pickle(out);
+        self.migrate_after.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.hot = Pickle::unpickle(stream)?;
+        this.cold = Pickle::unpickle(stream)?;
+        this.migrate_after = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for TieredBlobStore {
+    fn default() -> Self {
+        Self {
+            hot: Default::default(),
+            cold: Default::default(),
+            migrate_after: Duration::from_millis(31536000000),
+        }
+    }
+}
+
+impl IntoValue for TieredBlobStore {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(4);
+        map.insert_unchecked(Property::Hot, self.hot.into_value());
+        map.insert_unchecked(Property::Cold, self.cold.into_value());
+        map.insert_unchecked(Property::MigrateAfter, self.migrate_after.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for TieredBlobStore {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::Hot) => self.hot.patch(pointer, value),
+            Some(Property::Cold) => self.cold.patch(pointer, value),
+            Some(Property::MigrateAfter) => self.migrate_after.patch(pointer, value),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
 impl ObjectImpl for TlsExternalReport {
     const FLAGS: u64 = OBJ_FILTER_TENANT;
     const VERSION: u8 = 0;
@@ -46953,6 +48389,10 @@ impl RegistryJsonPropertyPatch for UserAccount {
             Some(Property::Permissions) => self.permissions.patch(pointer, value),
             Some(Property::Quotas) => self.quotas.patch(pointer, value),
             Some(Property::UsedDiskQuota) => pointer.assert_server_set(),
+            Some(Property::UsedDiskQuotaByType) => pointer.assert_server_set(),
+            Some(Property::UsedDiskQuotaByMailbox) => pointer.assert_server_set(),
+            Some(Property::UsedDiskQuotaBySize) => pointer.assert_server_set(),
+            Some(Property::ItemCountBySize) => pointer.assert_server_set(),
             Some(Property::Aliases) => self.aliases.patch(pointer, value),
             Some(Property::Description) => self.description.patch(pointer, value),
             Some(Property::Locale) => self.locale.patch(pointer, value),
@@ -7,7 +7,8 @@
 use types::id::Id;
 
 use crate::schema::prelude::{
-    Account, Credential, GroupAccount, PasswordCredential, SecondaryCredential, UserAccount,
+    Account, Credential, GroupAccount, PasswordCredential, ResourceAccount, SecondaryCredential,
+    UserAccount,
 };
 
 impl Account {
@@ -26,6 +27,14 @@ impl Account {
             None
         }
     }
+
+    pub fn into_resource(self) -> Option<ResourceAccount> {
+        if let Account::Resource(resource) = self {
+            Some(resource)
+        } else {
+            None
+        }
+    }
 }
 
 impl UserAccount {
@@ -29,6 +29,10 @@ impl Task {
             Task::AcmeRenewal(task) => task.status = status,
             Task::DkimManagement(task) => task.status = status,
             Task::DnsManagement(task) => task.status = status,
+            Task::EmailSnooze(task) => task.status = status,
+            Task::CalendarSubscription(task) => task.status = status,
+            Task::DataStoreMigration(task) => task.status = status,
+            Task::MoveAccount(task) => task.status = status,
             Task::TenantMaintenance(task) => task.status = status,
         }
     }
@@ -52,6 +56,10 @@ impl Task {
             Task::AcmeRenewal(task) => &task.status,
             Task::DkimManagement(task) => &task.status,
             Task::DnsManagement(task) => &task.status,
+            Task::EmailSnooze(task) => &task.status,
+            Task::CalendarSubscription(task) => &task.status,
+            Task::DataStoreMigration(task) => &task.status,
+            Task::MoveAccount(task) => &task.status,
             Task::TenantMaintenance(task) => &task.status,
         }
     }
@@ -91,6 +99,10 @@ impl Task {
             Task::AcmeRenewal(_) => Permission::TaskAcmeRenewal,
             Task::DkimManagement(_) => Permission::TaskDkimManagement,
             Task::DnsManagement(_) => Permission::TaskDnsManagement,
+            Task::EmailSnooze(_) => Permission::TaskEmailSnooze,
+            Task::CalendarSubscription(_) => Permission::TaskCalendarSubscription,
+            Task::DataStoreMigration(_) => Permission::TaskDataStoreMigration,
+            Task::MoveAccount(_) => Permission::TaskMoveAccount,
             Task::TenantMaintenance(_) => Permission::TaskTenantMaintenance,
         }
     }
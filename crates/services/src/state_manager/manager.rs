@@ -4,11 +4,15 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use super::{Event, PURGE_EVERY, SEND_TIMEOUT, push::spawn_push_manager};
+use super::{
+    Event, PURGE_EVERY, SEND_TIMEOUT,
+    push::spawn_push_manager,
+    webhook::{WebhookEvent, spawn_webhook_manager},
+};
 use crate::state_manager::IpcSubscriber;
 use common::{
     Inner,
-    ipc::{BroadcastEvent, PushEvent},
+    ipc::{BroadcastEvent, PushEvent, PushNotification},
 };
 use std::{sync::Arc, time::Instant};
 use store::ahash::AHashMap;
@@ -19,11 +23,13 @@ use trc::ServerEvent;
 struct Subscriber {
     ipc: Vec<IpcSubscriber>,
     is_push: bool,
+    is_webhook: bool,
 }
 
 #[allow(clippy::unwrap_or_default)]
 pub fn spawn_push_router(inner: Arc<Inner>, mut change_rx: mpsc::Receiver<PushEvent>) {
     let push_tx = spawn_push_manager(inner.clone());
+    let webhook_tx = spawn_webhook_manager(inner.clone());
 
     tokio::spawn(async move {
         let mut subscribers: AHashMap<u32, Subscriber> = AHashMap::default();
@@ -41,6 +47,13 @@ pub fn spawn_push_router(inner: Arc<Inner>, mut change_rx: mpsc::Receiver<PushEv
                             CausedBy = trc::location!()
                         );
                     }
+                    if webhook_tx.send(WebhookEvent::Reset).await.is_err() {
+                        trc::event!(
+                            Server(ServerEvent::ThreadError),
+                            Details = "Error sending webhook reset.",
+                            CausedBy = trc::location!()
+                        );
+                    }
                     break;
                 }
 
@@ -70,7 +83,26 @@ pub fn spawn_push_router(inner: Arc<Inner>, mut change_rx: mpsc::Receiver<PushEv
                         let mut remove_account = false;
                         if let Some(subscriber_list) = subscribers.get_mut(&account_id) {
                             subscriber_list.is_push = false;
-                            remove_account = subscriber_list.ipc.is_empty();
+                            remove_account =
+                                subscriber_list.ipc.is_empty() && !subscriber_list.is_webhook;
+                        }
+                        if remove_account {
+                            subscribers.remove(&account_id);
+                        }
+                    }
+                }
+
+                PushEvent::WebhookRegister { activate, expired } => {
+                    for account_id in activate {
+                        subscribers.entry(account_id).or_default().is_webhook = true;
+                    }
+
+                    for account_id in expired {
+                        let mut remove_account = false;
+                        if let Some(subscriber_list) = subscribers.get_mut(&account_id) {
+                            subscriber_list.is_webhook = false;
+                            remove_account =
+                                subscriber_list.ipc.is_empty() && !subscriber_list.is_push;
                         }
                         if remove_account {
                             subscribers.remove(&account_id);
@@ -126,6 +158,22 @@ pub fn spawn_push_router(inner: Arc<Inner>, mut change_rx: mpsc::Receiver<PushEv
                             }
                         }
 
+                        if subscribers.is_webhook
+                            && let PushNotification::StateChange(state_change) = &notification
+                            && webhook_tx
+                                .send(WebhookEvent::Publish {
+                                    state_change: *state_change,
+                                })
+                                .await
+                                .is_err()
+                        {
+                            trc::event!(
+                                Server(ServerEvent::ThreadError),
+                                Details = "Error sending webhook updates.",
+                                CausedBy = trc::location!()
+                            );
+                        }
+
                         if subscribers.is_push
                             && push_tx.send(Event::Push { notification }).await.is_err()
                         {
@@ -165,6 +213,19 @@ pub fn spawn_push_router(inner: Arc<Inner>, mut change_rx: mpsc::Receiver<PushEv
                             CausedBy = trc::location!()
                         );
                     }
+
+                    // Notify webhook manager
+                    if webhook_tx
+                        .send(WebhookEvent::Update { account_id })
+                        .await
+                        .is_err()
+                    {
+                        trc::event!(
+                            Server(ServerEvent::ThreadError),
+                            Details = "Error sending webhook updates.",
+                            CausedBy = trc::location!()
+                        );
+                    }
                 }
             }
 
@@ -174,7 +235,8 @@ pub fn spawn_push_router(inner: Arc<Inner>, mut change_rx: mpsc::Receiver<PushEv
                 for (account_id, subscribers) in &mut subscribers {
                     subscribers.ipc.retain(|subscriber| subscriber.is_valid());
 
-                    if subscribers.ipc.is_empty() && !subscribers.is_push {
+                    if subscribers.ipc.is_empty() && !subscribers.is_push && !subscribers.is_webhook
+                    {
                         remove_account_ids.push(*account_id);
                     }
                 }
@@ -9,6 +9,7 @@ pub mod email_push;
 pub mod http;
 pub mod manager;
 pub mod push;
+pub mod webhook;
 
 use common::ipc::PushNotification;
 use email::push::PushSubscription;
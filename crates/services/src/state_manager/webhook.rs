@@ -0,0 +1,586 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::http::build_push_client;
+use aws_lc_rs::hmac;
+use base64::{Engine, engine::general_purpose::STANDARD};
+use common::{BuildServer, IPC_CHANNEL_BUFFER, Inner, LONG_1Y_SLUMBER, Server, ipc::PushEvent};
+use email::push::{Webhook, Webhooks};
+use reqwest::{Client, header::CONTENT_TYPE};
+use serde_json::{Map, Value, json};
+use std::{
+    collections::hash_map::Entry,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use store::{
+    ValueKey,
+    ahash::{AHashMap, AHashSet},
+    query::log::{Change, Query},
+    write::{AlignedBytes, Archive, now},
+};
+use tokio::sync::mpsc;
+use trc::{AddContext, PushSubscriptionEvent, ServerEvent};
+use types::{
+    collection::Collection,
+    field::PrincipalField,
+    id::Id,
+    type_state::{DataType, StateChange},
+};
+use utils::map::bitmap::Bitmap;
+
+const MAX_ERROR_RESPONSE_LEN: usize = 1024;
+
+#[derive(Debug)]
+pub enum WebhookEvent {
+    Publish { state_change: StateChange },
+    Update { account_id: u32 },
+    DeliverySuccess { id: Id },
+    DeliveryFailure { id: Id, changes: PendingChanges },
+    Reset,
+}
+
+#[derive(Debug)]
+struct WebhookRegistration {
+    webhook: Arc<Webhook>,
+    account_id: u32,
+    num_attempts: u32,
+    last_request: Instant,
+    changes: Option<PendingChanges>,
+    in_flight: bool,
+    client: Client,
+}
+
+// Changes waiting to be delivered are merged into a single range, so the
+// backlog of a failing endpoint does not grow with each state change
+#[derive(Debug, Clone, Copy)]
+pub struct PendingChanges {
+    from_change_id: u64,
+    to_change_id: u64,
+    types: Bitmap<DataType>,
+}
+
+pub fn spawn_webhook_manager(inner: Arc<Inner>) -> mpsc::Sender<WebhookEvent> {
+    let (webhook_tx_, mut webhook_rx) = mpsc::channel::<WebhookEvent>(IPC_CHANNEL_BUFFER);
+    let webhook_tx = webhook_tx_.clone();
+
+    tokio::spawn(async move {
+        let mut webhooks: AHashMap<Id, WebhookRegistration> = AHashMap::default();
+        let mut account_webhook_ids: AHashMap<u32, AHashSet<Id>> = AHashMap::default();
+        let mut last_retry = Instant::now();
+        let mut retry_timeout = LONG_1Y_SLUMBER;
+        let mut retry_ids = AHashSet::default();
+        let webhook_client = build_push_client();
+
+        // Load active webhooks on startup
+        {
+            let server = inner.build_server();
+
+            if server.core.network.roles.push_notifications {
+                match server
+                    .document_ids(u32::MAX, Collection::Principal, PrincipalField::Webhooks)
+                    .await
+                {
+                    Ok(account_ids) => {
+                        for account_id in account_ids {
+                            if !is_local_shard(&server, account_id) {
+                                continue;
+                            }
+
+                            let account_webhooks = match load_webhooks(&server, account_id).await {
+                                Ok(account_webhooks) => account_webhooks,
+                                Err(err) => {
+                                    trc::error!(err.caused_by(trc::location!()));
+                                    continue;
+                                }
+                            };
+
+                            for webhook in
+                                account_webhooks.webhooks.into_iter().filter(|w| w.enabled)
+                            {
+                                let id = Id::from_parts(webhook.id, account_id);
+                                account_webhook_ids
+                                    .entry(account_id)
+                                    .or_default()
+                                    .insert(id);
+                                webhooks.insert(
+                                    id,
+                                    WebhookRegistration {
+                                        webhook: Arc::new(webhook),
+                                        account_id,
+                                        num_attempts: 0,
+                                        last_request: Instant::now()
+                                            - (server.core.jmap.push_throttle
+                                                + Duration::from_millis(1)),
+                                        changes: None,
+                                        in_flight: false,
+                                        client: webhook_client.clone(),
+                                    },
+                                );
+                            }
+                        }
+                    }
+                    Err(err) => {
+                        trc::error!(err.caused_by(trc::location!()));
+                    }
+                }
+
+                // Subscribe to state changes
+                if !account_webhook_ids.is_empty()
+                    && server
+                        .inner
+                        .ipc
+                        .push_tx
+                        .clone()
+                        .send(PushEvent::WebhookRegister {
+                            activate: account_webhook_ids.keys().copied().collect(),
+                            expired: vec![],
+                        })
+                        .await
+                        .is_err()
+                {
+                    trc::event!(
+                        Server(ServerEvent::ThreadError),
+                        Details = "Error sending state change.",
+                        CausedBy = trc::location!()
+                    );
+                }
+            }
+        }
+
+        loop {
+            // Wait for the next event or timeout
+            let event_or_timeout = tokio::time::timeout(retry_timeout, webhook_rx.recv()).await;
+
+            // Load settings
+            let server = inner.build_server();
+            let push_attempt_interval = server.core.jmap.push_attempt_interval;
+            let push_attempts_max = server.core.jmap.push_attempts_max;
+            let push_retry_interval = server.core.jmap.push_retry_interval;
+            let push_timeout = server.core.jmap.push_timeout;
+            let push_throttle = server.core.jmap.push_throttle;
+
+            match event_or_timeout {
+                Ok(Some(event)) => match event {
+                    WebhookEvent::Update { account_id } => {
+                        if !is_local_shard(&server, account_id) {
+                            continue;
+                        }
+
+                        // Load webhooks for account
+                        let account_webhooks = match load_webhooks(&server, account_id).await {
+                            Ok(account_webhooks) => account_webhooks,
+                            Err(err) => {
+                                trc::error!(err.caused_by(trc::location!()));
+                                continue;
+                            }
+                        };
+                        let was_active = account_webhook_ids
+                            .remove(&account_id)
+                            .filter(|ids| !ids.is_empty());
+                        let mut current_ids = AHashSet::new();
+
+                        for webhook in account_webhooks.webhooks.into_iter().filter(|w| w.enabled) {
+                            let id = Id::from_parts(webhook.id, account_id);
+                            let webhook = Arc::new(webhook);
+                            current_ids.insert(id);
+
+                            match webhooks.entry(id) {
+                                Entry::Occupied(mut entry) => {
+                                    entry.get_mut().webhook = webhook;
+                                }
+                                Entry::Vacant(entry) => {
+                                    entry.insert(WebhookRegistration {
+                                        webhook,
+                                        account_id,
+                                        num_attempts: 0,
+                                        last_request: Instant::now()
+                                            - (push_throttle + Duration::from_millis(1)),
+                                        changes: None,
+                                        in_flight: false,
+                                        client: webhook_client.clone(),
+                                    });
+                                }
+                            }
+                        }
+
+                        // Remove deleted or disabled webhooks
+                        if let Some(old_ids) = &was_active {
+                            for id in old_ids.difference(&current_ids) {
+                                webhooks.remove(id);
+                                retry_ids.remove(id);
+                            }
+                        }
+
+                        let (activate, expired) =
+                            match (was_active.is_some(), !current_ids.is_empty()) {
+                                (false, true) => (vec![account_id], vec![]),
+                                (true, false) => (vec![], vec![account_id]),
+                                _ => (vec![], vec![]),
+                            };
+                        if !current_ids.is_empty() {
+                            account_webhook_ids.insert(account_id, current_ids);
+                        }
+
+                        if (!activate.is_empty() || !expired.is_empty())
+                            && server
+                                .inner
+                                .ipc
+                                .push_tx
+                                .clone()
+                                .send(PushEvent::WebhookRegister { activate, expired })
+                                .await
+                                .is_err()
+                        {
+                            trc::event!(
+                                Server(ServerEvent::ThreadError),
+                                Details = "Error sending state change.",
+                                CausedBy = trc::location!()
+                            );
+                        }
+                    }
+                    WebhookEvent::Publish { state_change } => {
+                        if let Some(ids) = account_webhook_ids.get(&state_change.account_id) {
+                            for id in ids {
+                                let Some(registration) = webhooks.get_mut(id) else {
+                                    continue;
+                                };
+                                let mut types = state_change.types;
+                                types.intersection(&registration.webhook.types);
+                                if types.is_empty() {
+                                    continue;
+                                }
+
+                                registration.merge_changes(PendingChanges {
+                                    from_change_id: state_change.change_id,
+                                    to_change_id: state_change.change_id,
+                                    types,
+                                });
+                                let last_request = registration.last_request.elapsed();
+
+                                if !registration.in_flight
+                                    && ((registration.num_attempts == 0
+                                        && last_request > push_throttle)
+                                        || ((1..push_attempts_max)
+                                            .contains(&registration.num_attempts)
+                                            && last_request > push_attempt_interval))
+                                {
+                                    registration.send(
+                                        *id,
+                                        webhook_tx.clone(),
+                                        push_timeout,
+                                        server.clone(),
+                                    );
+                                    retry_ids.remove(id);
+                                } else {
+                                    retry_ids.insert(*id);
+                                }
+                            }
+                        }
+                    }
+                    WebhookEvent::Reset => {
+                        webhooks.clear();
+                        account_webhook_ids.clear();
+                    }
+                    WebhookEvent::DeliverySuccess { id } => {
+                        if let Some(registration) = webhooks.get_mut(&id) {
+                            registration.num_attempts = 0;
+                            registration.in_flight = false;
+                            retry_ids.remove(&id);
+                        }
+                    }
+                    WebhookEvent::DeliveryFailure { id, changes } => {
+                        if let Some(registration) = webhooks.get_mut(&id) {
+                            registration.last_request = Instant::now();
+                            registration.num_attempts += 1;
+                            registration.merge_changes(changes);
+                            registration.in_flight = false;
+                            retry_ids.insert(id);
+                        }
+                    }
+                },
+                Ok(None) => {
+                    break;
+                }
+                Err(_) => (),
+            }
+
+            retry_timeout = if !retry_ids.is_empty() {
+                let last_retry_elapsed = last_retry.elapsed();
+
+                if last_retry_elapsed >= push_retry_interval {
+                    let mut remove_ids = Vec::with_capacity(retry_ids.len());
+
+                    for retry_id in &retry_ids {
+                        if let Some(registration) = webhooks.get_mut(retry_id) {
+                            // Back off exponentially between failed attempts
+                            let last_request = registration.last_request.elapsed();
+                            let backoff = push_attempt_interval
+                                .saturating_mul(1 << registration.num_attempts.min(16))
+                                / 2;
+
+                            if !registration.in_flight
+                                && ((registration.num_attempts == 0
+                                    && last_request >= push_throttle)
+                                    || (registration.num_attempts > 0 && last_request >= backoff))
+                            {
+                                if registration.num_attempts < push_attempts_max {
+                                    registration.send(
+                                        *retry_id,
+                                        webhook_tx.clone(),
+                                        push_timeout,
+                                        server.clone(),
+                                    );
+                                } else {
+                                    trc::event!(
+                                        PushSubscription(PushSubscriptionEvent::Error),
+                                        Details = "Failed to deliver account webhook",
+                                        Url = registration.webhook.url.clone(),
+                                        AccountId = registration.account_id,
+                                        Reason = "Too many failed attempts"
+                                    );
+
+                                    registration.changes = None;
+                                    registration.num_attempts = 0;
+                                }
+                                remove_ids.push(*retry_id);
+                            }
+                        } else {
+                            remove_ids.push(*retry_id);
+                        }
+                    }
+
+                    if remove_ids.len() < retry_ids.len() {
+                        for remove_id in remove_ids {
+                            retry_ids.remove(&remove_id);
+                        }
+                        last_retry = Instant::now();
+                        push_retry_interval
+                    } else {
+                        retry_ids.clear();
+                        LONG_1Y_SLUMBER
+                    }
+                } else {
+                    push_retry_interval - last_retry_elapsed
+                }
+            } else {
+                LONG_1Y_SLUMBER
+            };
+        }
+    });
+
+    webhook_tx_
+}
+
+impl WebhookRegistration {
+    fn send(
+        &mut self,
+        id: Id,
+        webhook_tx: mpsc::Sender<WebhookEvent>,
+        timeout: Duration,
+        server: Server,
+    ) {
+        let webhook = self.webhook.clone();
+        let client = self.client.clone();
+        let account_id = self.account_id;
+        let Some(changes) = self.changes.take() else {
+            return;
+        };
+
+        self.in_flight = true;
+        self.last_request = Instant::now();
+
+        tokio::spawn(async move {
+            let result = match build_webhook_payload(&server, id, account_id, &changes).await {
+                Ok(Some(payload)) => {
+                    if post_webhook(&client, &webhook, account_id, payload, timeout).await {
+                        WebhookEvent::DeliverySuccess { id }
+                    } else {
+                        WebhookEvent::DeliveryFailure { id, changes }
+                    }
+                }
+                Ok(None) => WebhookEvent::DeliverySuccess { id },
+                Err(err) => {
+                    trc::error!(err.details("Failed to build webhook payload."));
+                    WebhookEvent::DeliveryFailure { id, changes }
+                }
+            };
+
+            webhook_tx.send(result).await.ok();
+        });
+    }
+
+    fn merge_changes(&mut self, changes: PendingChanges) {
+        if let Some(pending) = &mut self.changes {
+            pending.from_change_id = pending.from_change_id.min(changes.from_change_id);
+            pending.to_change_id = pending.to_change_id.max(changes.to_change_id);
+            pending.types.union(&changes.types);
+        } else {
+            self.changes = Some(changes);
+        }
+    }
+}
+
+async fn build_webhook_payload(
+    server: &Server,
+    id: Id,
+    account_id: u32,
+    changes: &PendingChanges,
+) -> trc::Result<Option<String>> {
+    let from_change_id = changes.from_change_id;
+    let to_change_id = changes.to_change_id;
+
+    let mut changed = Map::new();
+    for data_type in changes.types {
+        let Some((collection, is_container)) = data_type.try_to_sync() else {
+            continue;
+        };
+
+        let changelog = server
+            .store()
+            .changes(
+                account_id,
+                collection.into(),
+                Query::RangeInclusive(from_change_id, to_change_id),
+            )
+            .await
+            .caused_by(trc::location!())?;
+        let mut created = Vec::new();
+        let mut updated = Vec::new();
+        let mut destroyed = Vec::new();
+
+        for change in changelog.changes.into_iter().filter(|change| {
+            (is_container && change.is_container_change())
+                || (!is_container && change.is_item_change())
+        }) {
+            match change {
+                Change::InsertContainer(item) | Change::InsertItem(item) => {
+                    created.push(Value::String(Id::from(item).to_string()));
+                }
+                Change::UpdateContainer(item)
+                | Change::UpdateItem(item)
+                | Change::UpdateContainerProperty(item) => {
+                    updated.push(Value::String(Id::from(item).to_string()));
+                }
+                Change::DeleteContainer(item) | Change::DeleteItem(item) => {
+                    destroyed.push(Value::String(Id::from(item).to_string()));
+                }
+            }
+        }
+
+        changed.insert(
+            data_type.as_str().to_string(),
+            json!({
+                "created": created,
+                "updated": updated,
+                "destroyed": destroyed,
+            }),
+        );
+    }
+
+    Ok(Some(
+        json!({
+            "@type": "WebhookEvent",
+            "webhookId": Id::from(id.document_id()).to_string(),
+            "accountId": Id::from(account_id).to_string(),
+            "changeId": to_change_id,
+            "changed": changed,
+        })
+        .to_string(),
+    ))
+}
+
+async fn post_webhook(
+    client: &Client,
+    webhook: &Webhook,
+    account_id: u32,
+    body: String,
+    timeout: Duration,
+) -> bool {
+    // Add HMAC-SHA256 signature, the timestamp is included in the signed
+    // payload so receivers can reject replayed requests
+    let timestamp = now().to_string();
+    let key = hmac::Key::new(hmac::HMAC_SHA256, webhook.secret.as_bytes());
+    let mut ctx = hmac::Context::with_key(&key);
+    ctx.update(timestamp.as_bytes());
+    ctx.update(b".");
+    ctx.update(body.as_bytes());
+    let signature = STANDARD.encode(ctx.sign().as_ref());
+
+    match client
+        .post(webhook.url.as_str())
+        .timeout(timeout)
+        .header(CONTENT_TYPE, "application/json")
+        .header("X-Signature", signature)
+        .header("X-Timestamp", timestamp)
+        .body(body)
+        .send()
+        .await
+    {
+        Ok(response) => {
+            let status = response.status();
+
+            if status.is_success() {
+                trc::event!(
+                    PushSubscription(PushSubscriptionEvent::Success),
+                    Url = webhook.url.clone(),
+                    AccountId = account_id
+                );
+
+                true
+            } else {
+                let mut reason = response.text().await.unwrap_or_default();
+                reason.truncate(reason.ceil_char_boundary(MAX_ERROR_RESPONSE_LEN));
+
+                trc::event!(
+                    PushSubscription(PushSubscriptionEvent::Error),
+                    Details = "HTTP POST failed",
+                    Url = webhook.url.clone(),
+                    AccountId = account_id,
+                    Code = status.as_u16(),
+                    Reason = reason,
+                );
+
+                false
+            }
+        }
+        Err(err) => {
+            trc::event!(
+                PushSubscription(PushSubscriptionEvent::Error),
+                Details = "HTTP POST failed",
+                Url = webhook.url.clone(),
+                AccountId = account_id,
+                Reason = err.to_string()
+            );
+
+            false
+        }
+    }
+}
+
+fn is_local_shard(server: &Server, account_id: u32) -> bool {
+    server.core.jmap.push_total_shards <= 1
+        || account_id % server.core.jmap.push_total_shards == server.registry().cluster_push_shard()
+}
+
+async fn load_webhooks(server: &Server, account_id: u32) -> trc::Result<Webhooks> {
+    if let Some(webhooks) = server
+        .store()
+        .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+            account_id,
+            Collection::Principal,
+            0,
+            PrincipalField::Webhooks,
+        ))
+        .await?
+    {
+        webhooks
+            .deserialize::<Webhooks>()
+            .caused_by(trc::location!())
+    } else {
+        Ok(Webhooks::default())
+    }
+}
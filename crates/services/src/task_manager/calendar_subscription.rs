@@ -0,0 +1,404 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::task_manager::TaskResult;
+use calcard::{Entry, Parser, common::timezone::Tz};
+use common::{DavName, Server, network::is_global_ip};
+use groupware::{
+    DestroyArchive,
+    cache::GroupwareCache,
+    calendar::{
+        CalendarEvent, CalendarEventData,
+        subscription::{CalendarSubscriptionFetch, split_calendar_feed},
+    },
+};
+use registry::schema::structs::TaskCalendarSubscription;
+use reqwest::{
+    Client, StatusCode, Url,
+    header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+    redirect::Policy,
+};
+use std::{net::IpAddr, time::Duration};
+use store::{
+    ValueKey,
+    ahash::AHashMap,
+    write::{AlignedBytes, Archive, BatchBuilder, now},
+};
+use trc::AddContext;
+use types::collection::{Collection, SyncCollection};
+use utils::HttpLimitResponse;
+
+const MAX_FEED_SIZE: usize = 10 * 1024 * 1024;
+const MAX_REDIRECTS: usize = 4;
+const FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub(crate) trait CalendarSubscriptionTask: Sync + Send {
+    fn refresh_calendar_subscription(
+        &self,
+        task: &TaskCalendarSubscription,
+    ) -> impl Future<Output = TaskResult> + Send;
+}
+
+enum FeedResponse {
+    Modified {
+        contents: Vec<u8>,
+        etag: Option<String>,
+        last_modified: Option<String>,
+    },
+    NotModified,
+}
+
+impl CalendarSubscriptionTask for Server {
+    async fn refresh_calendar_subscription(&self, task: &TaskCalendarSubscription) -> TaskResult {
+        match refresh_calendar_subscription(self, task).await {
+            Ok(result) => result,
+            Err(err) => {
+                let result = TaskResult::temporary(err.to_string());
+                trc::error!(
+                    err.account_id(task.account_id.document_id())
+                        .document_id(task.document_id.document_id())
+                        .caused_by(trc::location!())
+                        .details("Failed to refresh calendar subscription")
+                );
+                result
+            }
+        }
+    }
+}
+
+async fn refresh_calendar_subscription(
+    server: &Server,
+    task: &TaskCalendarSubscription,
+) -> trc::Result<TaskResult> {
+    let account_id = task.account_id.document_id();
+    let calendar_id = task.document_id.document_id();
+
+    // Make sure the calendar is still subscribed and the refresh is due
+    let Some(mut subscription) = server
+        .calendar_subscription(account_id, calendar_id)
+        .await
+        .caused_by(trc::location!())?
+    else {
+        return Ok(TaskResult::Ignored);
+    };
+    let now = now();
+    if subscription.next_refresh() > now {
+        // The subscription was refreshed or changed, a newer task will handle it
+        return Ok(TaskResult::Ignored);
+    }
+
+    // Fetch and synchronize the feed
+    match fetch_feed(
+        &subscription.url,
+        subscription.etag.as_deref(),
+        subscription.last_modified.as_deref(),
+    )
+    .await
+    {
+        Ok(FeedResponse::Modified {
+            contents,
+            etag,
+            last_modified,
+        }) => match sync_feed(server, account_id, calendar_id, &contents).await? {
+            Some(Ok(())) => {
+                subscription.etag = etag;
+                subscription.last_modified = last_modified;
+                subscription.last_error = None;
+            }
+            Some(Err(err)) => {
+                subscription.last_error = Some(err);
+            }
+            None => {
+                // Calendar was deleted
+                return Ok(TaskResult::Ignored);
+            }
+        },
+        Ok(FeedResponse::NotModified) => {
+            subscription.last_error = None;
+        }
+        Err(err) => {
+            subscription.last_error = Some(err);
+        }
+    }
+
+    // Store the subscription state and schedule the next refresh
+    subscription.last_refresh = now;
+    let refresh_at = subscription.next_refresh();
+    let mut batch = BatchBuilder::new();
+    batch
+        .with_account_id(account_id)
+        .with_collection(Collection::Calendar)
+        .with_document(calendar_id);
+    subscription
+        .write(&mut batch, account_id, calendar_id, refresh_at)
+        .caused_by(trc::location!())?;
+    server
+        .commit_batch(batch)
+        .await
+        .caused_by(trc::location!())?;
+
+    Ok(TaskResult::Success(vec![]))
+}
+
+// Replaces the contents of the calendar with the events in the feed, events are
+// matched by UID and only rewritten when their contents changed.
+async fn sync_feed(
+    server: &Server,
+    account_id: u32,
+    calendar_id: u32,
+    contents: &[u8],
+) -> trc::Result<Option<Result<(), String>>> {
+    let Ok(ical_raw) = std::str::from_utf8(contents) else {
+        return Ok(Some(Err("Invalid UTF-8 in calendar feed".to_string())));
+    };
+    let feed = match Parser::new(ical_raw).entry() {
+        Entry::ICalendar(ical) => ical,
+        _ => {
+            return Ok(Some(Err("Failed to parse calendar feed".to_string())));
+        }
+    };
+    let mut events = split_calendar_feed(&feed)
+        .into_iter()
+        .filter(|(_, ical)| ical.size() <= server.core.groupware.max_ical_size)
+        .collect::<AHashMap<_, _>>();
+
+    let resources = server
+        .fetch_dav_resources(account_id, account_id, SyncCollection::Calendar)
+        .await
+        .caused_by(trc::location!())?;
+    if !resources.has_container_id(&calendar_id) {
+        return Ok(None);
+    }
+    let account_info = server
+        .account_info(account_id)
+        .await
+        .caused_by(trc::location!())?;
+    let max_ical_instances = server.core.groupware.max_ical_instances;
+    let mut batch = BatchBuilder::new();
+
+    // Update or remove existing events
+    let mut added_size = 0;
+    for resource in resources.children(calendar_id) {
+        let document_id = resource.document_id();
+        let Some(event_) = server
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                account_id,
+                Collection::CalendarEvent,
+                document_id,
+            ))
+            .await
+            .caused_by(trc::location!())?
+        else {
+            continue;
+        };
+        let event = event_
+            .to_unarchived::<CalendarEvent>()
+            .caused_by(trc::location!())?;
+
+        let ical = event
+            .inner
+            .data
+            .event
+            .uids()
+            .next()
+            .and_then(|uid| events.remove(uid));
+        match ical {
+            Some(ical) if ical == event.inner.data.event => {}
+            Some(ical) => {
+                let mut new_event = event
+                    .deserialize::<CalendarEvent>()
+                    .caused_by(trc::location!())?;
+                let size = ical.size() as u32;
+                added_size += size.saturating_sub(new_event.size) as u64;
+                new_event.size = size;
+                new_event.data =
+                    CalendarEventData::new(ical, Tz::Floating, max_ical_instances, &mut None);
+                new_event
+                    .update(
+                        account_info.account_tenant_ids(),
+                        event,
+                        account_id,
+                        document_id,
+                        &mut batch,
+                    )
+                    .caused_by(trc::location!())?;
+            }
+            None => {
+                DestroyArchive(event)
+                    .delete(
+                        &account_info,
+                        account_id,
+                        document_id,
+                        calendar_id,
+                        resources.format_resource(resource).into(),
+                        false,
+                        &mut batch,
+                    )
+                    .caused_by(trc::location!())?;
+            }
+        }
+    }
+
+    // Check quota
+    added_size += events.values().map(|ical| ical.size() as u64).sum::<u64>();
+    if added_size > 0 {
+        let account = server
+            .account(account_id)
+            .await
+            .caused_by(trc::location!())?;
+        if let Err(err) = server.has_available_quota(&account, added_size).await {
+            return if err.matches(trc::EventType::Limit(trc::LimitEvent::Quota))
+                || err.matches(trc::EventType::Limit(trc::LimitEvent::TenantQuota))
+            {
+                Ok(Some(Err("Quota exceeded".to_string())))
+            } else {
+                Err(err.caused_by(trc::location!()))
+            };
+        }
+    }
+
+    // Add new events
+    if !events.is_empty() {
+        let mut next_document_id = server
+            .store()
+            .assign_document_ids(account_id, Collection::CalendarEvent, events.len() as u64)
+            .await
+            .caused_by(trc::location!())?;
+        for ical in events.into_values() {
+            let document_id = next_document_id;
+            next_document_id -= 1;
+
+            let size = ical.size() as u32;
+            let mut next_email_alarm = None;
+            CalendarEvent {
+                names: vec![DavName::new_with_rand_name(calendar_id)],
+                data: CalendarEventData::new(
+                    ical,
+                    Tz::Floating,
+                    max_ical_instances,
+                    &mut next_email_alarm,
+                ),
+                size,
+                ..Default::default()
+            }
+            .insert(
+                account_info.account_tenant_ids(),
+                account_id,
+                document_id,
+                next_email_alarm,
+                &mut batch,
+            )
+            .caused_by(trc::location!())?;
+
+            if batch.is_large_batch() {
+                server
+                    .commit_batch(std::mem::take(&mut batch))
+                    .await
+                    .caused_by(trc::location!())?;
+            }
+        }
+    }
+
+    if !batch.is_empty() {
+        server
+            .commit_batch(batch)
+            .await
+            .caused_by(trc::location!())?;
+    }
+
+    Ok(Some(Ok(())))
+}
+
+async fn fetch_feed(
+    url: &str,
+    etag: Option<&str>,
+    last_modified: Option<&str>,
+) -> Result<FeedResponse, String> {
+    let url = Url::parse(url).map_err(|_| "Invalid feed URL".to_string())?;
+    if !is_allowed_feed_url(&url) {
+        return Err("Feed URL is not allowed".to_string());
+    }
+
+    let mut request = build_feed_client().get(url).timeout(FETCH_TIMEOUT);
+    if let Some(etag) = etag {
+        request = request.header(IF_NONE_MATCH, etag);
+    }
+    if let Some(last_modified) = last_modified {
+        request = request.header(IF_MODIFIED_SINCE, last_modified);
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|err| format!("Failed to fetch feed: {err}"))?;
+
+    match response.status() {
+        StatusCode::NOT_MODIFIED => Ok(FeedResponse::NotModified),
+        status if status.is_success() => {
+            let header = |name| {
+                response
+                    .headers()
+                    .get(name)
+                    .and_then(|v| v.to_str().ok())
+                    .map(|v| v.to_string())
+            };
+            let etag = header(ETAG);
+            let last_modified = header(LAST_MODIFIED);
+
+            response
+                .bytes_with_limit(MAX_FEED_SIZE)
+                .await
+                .map_err(|err| format!("Failed to fetch feed: {err}"))?
+                .map(|contents| FeedResponse::Modified {
+                    contents,
+                    etag,
+                    last_modified,
+                })
+                .ok_or_else(|| format!("Feed exceeds the maximum size of {MAX_FEED_SIZE} bytes"))
+        }
+        status => Err(format!("Feed server returned status {status}")),
+    }
+}
+
+fn build_feed_client() -> Client {
+    utils::http::http_client_builder(cfg!(feature = "test_mode"))
+        .redirect(Policy::custom(|attempt| {
+            if attempt.previous().len() > MAX_REDIRECTS {
+                attempt.error("Too many redirects.")
+            } else if is_allowed_feed_url(attempt.url()) {
+                attempt.follow()
+            } else {
+                attempt.stop()
+            }
+        }))
+        .build()
+        .unwrap_or_default()
+}
+
+fn is_allowed_feed_url(url: &Url) -> bool {
+    if url.scheme() != "https" {
+        return false;
+    }
+
+    let Some(host) = url.host_str() else {
+        return false;
+    };
+    let host = host
+        .strip_prefix('[')
+        .and_then(|host| host.strip_suffix(']'))
+        .unwrap_or(host);
+
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        #[cfg(feature = "test_mode")]
+        if ip.is_loopback() {
+            return true;
+        }
+
+        is_global_ip(&ip)
+    } else {
+        true
+    }
+}
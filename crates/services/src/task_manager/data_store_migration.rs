@@ -0,0 +1,205 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::task_manager::TaskResult;
+use common::{
+    Server,
+    ipc::{BroadcastEvent, RegistryChange},
+};
+use registry::{
+    schema::{
+        enums::DataStoreMigrationStage,
+        prelude::ObjectType,
+        structs::{DataStore, Task, TaskDataStoreMigration, TaskStatus},
+    },
+    types::EnumImpl,
+};
+use std::time::{Duration, Instant};
+use store::{
+    BlobStore, Store,
+    write::migrate::{MIGRATION_SUBSPACES, StoreMigration},
+};
+use trc::{AddContext, StoreEvent};
+use types::id::Id;
+
+const MAX_KEYS_PER_RUN: usize = 100_000;
+const MAX_CUTOVER_KEYS: u64 = 1_000;
+const FENCE_ACK_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub(crate) trait DataStoreMigrationTask: Sync + Send {
+    fn migrate_data_store(
+        &self,
+        task: &TaskDataStoreMigration,
+    ) -> impl Future<Output = TaskResult> + Send;
+}
+
+impl DataStoreMigrationTask for Server {
+    async fn migrate_data_store(&self, task: &TaskDataStoreMigration) -> TaskResult {
+        match migrate_data_store(self, task).await {
+            Ok(result) => result,
+            Err(err) => {
+                let result = TaskResult::temporary(err.to_string());
+                trc::error!(err.details("Failed to migrate data store"));
+                result
+            }
+        }
+    }
+}
+
+async fn migrate_data_store(
+    server: &Server,
+    task: &TaskDataStoreMigration,
+) -> trc::Result<TaskResult> {
+    // The task queue is migrated as well, so this task is found again
+    // after restarting with the target store
+    if server
+        .registry()
+        .object::<DataStore>(Id::singleton())
+        .await
+        .caused_by(trc::location!())?
+        .is_some_and(|data_store| data_store == task.target)
+    {
+        return Ok(TaskResult::Ignored);
+    }
+
+    let started = Instant::now();
+    let target = Store::build(task.target.clone()).await.map_err(|err| {
+        StoreEvent::UnexpectedError
+            .caused_by(trc::location!())
+            .reason(err)
+            .details("Failed to open target data store")
+    })?;
+    let migration = StoreMigration::new(server.store().clone(), target).with_blobs(
+        matches!(server.blob_store(), BlobStore::Store(store) if store.is_same(server.store())),
+    );
+    let mut next_task = task.clone();
+    let mut keys_written = 0;
+
+    match task.stage {
+        DataStoreMigrationStage::Copy => {
+            if let Some(subspace) = MIGRATION_SUBSPACES.get(task.subspaces_copied as usize) {
+                let progress = migration
+                    .copy_subspace(*subspace, MAX_KEYS_PER_RUN)
+                    .await
+                    .caused_by(trc::location!())?;
+                keys_written = progress.keys_written;
+                if progress.is_done {
+                    next_task.subspaces_copied += 1;
+                }
+            }
+
+            if next_task.subspaces_copied as usize >= MIGRATION_SUBSPACES.len() {
+                next_task.stage = DataStoreMigrationStage::CatchUp;
+            }
+        }
+        DataStoreMigrationStage::CatchUp => {
+            keys_written = migration
+                .sync_changed_accounts()
+                .await
+                .caused_by(trc::location!())?;
+
+            next_task.changed_keys = keys_written;
+            if keys_written <= MAX_CUTOVER_KEYS {
+                next_task.stage = DataStoreMigrationStage::Cutover;
+            }
+        }
+        DataStoreMigrationStage::Cutover => {
+            // Reject writes to the current store on all nodes while the last changes
+            // are copied, the fence is kept on the retired store after a successful cutover
+            let fence_id = server
+                .registry()
+                .fence_writes()
+                .await
+                .caused_by(trc::location!())?;
+            broadcast_fence(server).await?;
+
+            match cutover(server, &migration, &task.target, fence_id).await {
+                Ok(keys_written) => {
+                    trc::event!(
+                        Store(StoreEvent::DataStoreMigrated),
+                        Total = task.keys_copied + keys_written,
+                        Details = "Restart the server to start using the new data store",
+                        Elapsed = started.elapsed()
+                    );
+
+                    return Ok(TaskResult::Success(vec![]));
+                }
+                Err(err) => {
+                    server
+                        .registry()
+                        .unfence_writes()
+                        .await
+                        .caused_by(trc::location!())?;
+                    broadcast_fence(server).await?;
+                    return Err(err);
+                }
+            }
+        }
+    }
+
+    trc::event!(
+        Store(StoreEvent::DataStoreSynced),
+        Details = task.stage.as_str(),
+        Total = keys_written,
+        Elapsed = started.elapsed()
+    );
+
+    next_task.keys_copied += keys_written;
+    next_task.status = TaskStatus::now();
+    Ok(TaskResult::Success(vec![Task::DataStoreMigration(
+        next_task,
+    )]))
+}
+
+async fn cutover(
+    server: &Server,
+    migration: &StoreMigration,
+    target: &DataStore,
+    fence_id: u64,
+) -> trc::Result<u64> {
+    // Wait for all nodes to complete their in-flight writes
+    let started = Instant::now();
+    while !server
+        .registry()
+        .is_write_fence_acknowledged(fence_id)
+        .await
+        .caused_by(trc::location!())?
+    {
+        if started.elapsed() > FENCE_ACK_TIMEOUT {
+            return Err(StoreEvent::UnexpectedError
+                .caused_by(trc::location!())
+                .details("Timed out waiting for all nodes to acknowledge the write fence"));
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    // Compare all keys as a final check, writes that do not bump a change id are copied here
+    let keys_written = migration.sync_all().await.caused_by(trc::location!())?;
+
+    server
+        .registry()
+        .write_data_store(target)
+        .await
+        .caused_by(trc::location!())?;
+
+    Ok(keys_written)
+}
+
+// Applies the write fence locally and notifies the other nodes
+async fn broadcast_fence(server: &Server) -> trc::Result<()> {
+    server
+        .registry()
+        .sync_write_fence()
+        .await
+        .caused_by(trc::location!())?;
+    server
+        .cluster_broadcast(BroadcastEvent::RegistryChange(RegistryChange::Reload(
+            ObjectType::DataStore,
+        )))
+        .await;
+
+    Ok(())
+}
@@ -7,7 +7,7 @@
 use crate::task_manager::TaskResult;
 use common::Server;
 use email::{message::metadata::MessageMetadata, sieve::SieveScript};
-use groupware::file::FileNode;
+use groupware::file::{FileNode, version::FileVersions};
 use registry::{
     schema::{
         prelude::{ObjectType, Property},
@@ -25,7 +25,7 @@ use trc::AddContext;
 use types::{
     blob_hash::BlobHash,
     collection::Collection,
-    field::{EmailField, Field},
+    field::{EmailField, Field, FileNodeField},
     id::Id,
 };
 
@@ -164,6 +164,7 @@ pub async fn destroy_account_blobs(server: &Server, account_id: u32) -> trc::Res
     for (collection, field) in [
         (Collection::Email, u8::from(EmailField::Metadata)),
         (Collection::FileNode, u8::from(Field::ARCHIVE)),
+        (Collection::FileNode, u8::from(FileNodeField::Versions)),
         (Collection::SieveScript, u8::from(Field::ARCHIVE)),
     ] {
         server
@@ -177,6 +178,15 @@ pub async fn destroy_account_blobs(server: &Server, account_id: u32) -> trc::Res
                             BlobHash::from(&message.blob_hash),
                         ));
                     }
+                    Collection::FileNode if field == u8::from(FileNodeField::Versions) => {
+                        for version in archive.unarchive::<FileVersions>()?.versions.iter() {
+                            delete_keys.push((
+                                collection,
+                                document_id,
+                                BlobHash::from(&version.blob_hash),
+                            ));
+                        }
+                    }
                     Collection::FileNode => {
                         if let Some(file) = archive.unarchive::<FileNode>()?.file.as_ref() {
                             delete_keys.push((
@@ -0,0 +1,705 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::task_manager::maintenance::{calculate_usage, recalculate_quota};
+use common::{
+    Server,
+    storage::index::{IndexValue, IndexableObject},
+};
+use email::{
+    identity::Identity,
+    mailbox::Mailbox,
+    message::{
+        delete::EmailDeletion,
+        metadata::{MessageData, MessageMetadata},
+    },
+    sieve::SieveScript,
+    submission::EmailSubmission,
+};
+use groupware::{
+    calendar::{Calendar, CalendarEvent, CalendarEventNotification},
+    contact::{AddressBook, ContactCard},
+    file::{FileNode, version::FileVersions},
+};
+use registry::schema::{
+    enums::IndexDocumentType,
+    structs::{Task, TaskIndexDocument, TaskStatus},
+};
+use std::time::Instant;
+use store::{
+    IterateParams, SUBSPACE_BLOB_LINK, SUBSPACE_INDEXES, SUBSPACE_PROPERTY, U32_LEN,
+    ahash::{AHashMap, AHashSet},
+    roaring::RoaringBitmap,
+    write::{
+        AlignedBytes, AnyClass, AnyKey, Archive, ArchiveVersion, BatchBuilder, BlobLink, BlobOp,
+        ValueClass,
+        assert::AssertValue,
+        key::{DeserializeBigEndian, KeySerializer},
+    },
+};
+use trc::{AddContext, StoreEvent};
+use types::{
+    blob_hash::{BLOB_HASH_LEN, BlobHash},
+    collection::Collection,
+    field::{EmailField, Field, FileNodeField},
+};
+
+const CHECK_COLLECTIONS: [Collection; 11] = [
+    Collection::Email,
+    Collection::Mailbox,
+    Collection::Identity,
+    Collection::EmailSubmission,
+    Collection::SieveScript,
+    Collection::AddressBook,
+    Collection::ContactCard,
+    Collection::Calendar,
+    Collection::CalendarEvent,
+    Collection::CalendarEventNotification,
+    Collection::FileNode,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IssueKind {
+    OrphanIndex,
+    MissingIndex,
+    OrphanMetadata,
+    MissingMetadata,
+    ThreadMismatch,
+    OrphanBlobLink,
+    MissingBlobLink,
+    MissingBlob,
+    QuotaMismatch,
+    CorruptedDocument,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsistencyIssue {
+    pub kind: IssueKind,
+    pub collection: Collection,
+    pub document_id: u32,
+}
+
+#[derive(Debug, Default)]
+pub struct ConsistencyReport {
+    pub issues: Vec<ConsistencyIssue>,
+    pub repaired: usize,
+}
+
+struct ConsistencyCheck<'x> {
+    server: &'x Server,
+    account_id: u32,
+    repair: bool,
+    batch: BatchBuilder,
+    report: ConsistencyReport,
+}
+
+#[derive(Default)]
+struct ExpectedValues {
+    documents: AHashMap<u8, RoaringBitmap>,
+    versions: AHashMap<(u8, u32), ArchiveVersion>,
+    indexes: AHashSet<(u8, u32, u8, Vec<u8>)>,
+    blob_links: AHashSet<(u8, u32, BlobHash)>,
+    thread_ids: AHashMap<u32, u32>,
+    orphan_metadata: RoaringBitmap,
+    corrupted: Vec<(Collection, u32)>,
+}
+
+pub async fn check_account(
+    server: &Server,
+    account_id: u32,
+    repair: bool,
+) -> trc::Result<ConsistencyReport> {
+    let started = Instant::now();
+    let mut check = ConsistencyCheck {
+        server,
+        account_id,
+        repair,
+        batch: BatchBuilder::new(),
+        report: ConsistencyReport::default(),
+    };
+
+    // Obtain all documents along with the index entries and blob links they should have
+    let mut expected = ExpectedValues::default();
+    for collection in CHECK_COLLECTIONS {
+        let mut documents = RoaringBitmap::new();
+        server
+            .archives(account_id, collection, &(), |document_id, archive| {
+                documents.insert(document_id);
+                expected
+                    .versions
+                    .insert((collection.into(), document_id), archive.version);
+                if expected.add(collection, document_id, &archive).is_err() {
+                    expected.corrupted.push((collection, document_id));
+                }
+                Ok(true)
+            })
+            .await
+            .caused_by(trc::location!())?;
+        expected.documents.insert(collection.into(), documents);
+    }
+    for (collection, document_id) in std::mem::take(&mut expected.corrupted) {
+        check.issue(IssueKind::CorruptedDocument, collection, document_id);
+    }
+    let emails = expected.documents(Collection::Email);
+
+    // File versions share the lifetime of their file node
+    server
+        .all_archives(
+            account_id,
+            Collection::FileNode,
+            FileNodeField::Versions.into(),
+            |document_id, archive| {
+                for version in archive.unarchive::<FileVersions>()?.versions.iter() {
+                    expected.blob_links.insert((
+                        Collection::FileNode.into(),
+                        document_id,
+                        BlobHash::from(&version.blob_hash),
+                    ));
+                }
+                Ok(())
+            },
+        )
+        .await
+        .caused_by(trc::location!())?;
+
+    // Verify that every e-mail has metadata and that no metadata outlives its e-mail
+    let mut metadata_ids = RoaringBitmap::new();
+    server
+        .all_archives(
+            account_id,
+            Collection::Email,
+            EmailField::Metadata.into(),
+            |document_id, archive| {
+                metadata_ids.insert(document_id);
+                if emails.contains(document_id) {
+                    expected.blob_links.insert((
+                        Collection::Email.into(),
+                        document_id,
+                        BlobHash::from(&archive.unarchive::<MessageMetadata>()?.blob_hash),
+                    ));
+                }
+                Ok(())
+            },
+        )
+        .await
+        .caused_by(trc::location!())?;
+
+    let mut tombstone_ids = RoaringBitmap::new();
+    for document_id in &emails - &metadata_ids {
+        check.issue(IssueKind::MissingMetadata, Collection::Email, document_id);
+        tombstone_ids.insert(document_id);
+    }
+    expected.orphan_metadata = &metadata_ids - &emails;
+    for document_id in &expected.orphan_metadata {
+        check.issue(IssueKind::OrphanMetadata, Collection::Email, document_id);
+        if repair {
+            check
+                .batch
+                .schedule_task(Task::UnindexDocument(TaskIndexDocument {
+                    account_id: account_id.into(),
+                    document_id: document_id.into(),
+                    document_type: IndexDocumentType::Email,
+                    status: TaskStatus::now(),
+                }));
+            check.repaired(IssueKind::OrphanMetadata, Collection::Email, document_id);
+        }
+    }
+
+    check.indexes(&mut expected).await?;
+    check.threads(&expected).await?;
+    check.blob_links(&expected, &mut tombstone_ids).await?;
+
+    // Tombstone e-mails that cannot be recovered
+    if repair && !tombstone_ids.is_empty() {
+        let tenant_id = server
+            .account(account_id)
+            .await
+            .caused_by(trc::location!())?
+            .tenant_id();
+        let mut batch = BatchBuilder::new();
+        let not_destroyed = server
+            .emails_delete(account_id, tenant_id, &mut batch, tombstone_ids.clone())
+            .await
+            .caused_by(trc::location!())?;
+        if !batch.is_empty() {
+            server
+                .commit_batch(batch)
+                .await
+                .caused_by(trc::location!())?;
+            server.notify_task_queue();
+        }
+
+        for document_id in &tombstone_ids - &not_destroyed {
+            trc::event!(
+                Store(StoreEvent::ConsistencyIssueRepaired),
+                AccountId = account_id,
+                Collection = Collection::Email.as_str(),
+                DocumentId = document_id,
+                Details = "tombstoned",
+            );
+            check.report.repaired += 1;
+        }
+    }
+    check.flush().await?;
+
+    // Verify quota and usage counters
+    let (quota, mut usage) = calculate_usage(server, account_id).await?;
+    usage.counters.retain(|_, value| *value != 0);
+    let used_quota = server
+        .get_used_quota_account(account_id)
+        .await
+        .caused_by(trc::location!())?;
+    let used_usage = server
+        .get_storage_usage(account_id)
+        .await
+        .caused_by(trc::location!())?;
+    if quota != used_quota || usage.counters != used_usage.counters {
+        check.issue(IssueKind::QuotaMismatch, Collection::None, u32::MAX);
+        if repair {
+            recalculate_quota(server, account_id).await?;
+            check.repaired(IssueKind::QuotaMismatch, Collection::None, u32::MAX);
+        }
+    }
+
+    if check.repair {
+        server.notify_task_queue();
+    }
+
+    trc::event!(
+        Store(StoreEvent::ConsistencyCheckCompleted),
+        AccountId = account_id,
+        Total = check.report.issues.len(),
+        Elapsed = started.elapsed(),
+    );
+
+    Ok(check.report)
+}
+
+impl ConsistencyCheck<'_> {
+    fn issue(&mut self, kind: IssueKind, collection: Collection, document_id: u32) {
+        trc::event!(
+            Store(StoreEvent::ConsistencyIssue),
+            AccountId = self.account_id,
+            Collection = collection.as_str(),
+            DocumentId = document_id,
+            Details = kind.as_str(),
+        );
+        self.report.issues.push(ConsistencyIssue {
+            kind,
+            collection,
+            document_id,
+        });
+    }
+
+    fn repaired(&mut self, kind: IssueKind, collection: Collection, document_id: u32) {
+        trc::event!(
+            Store(StoreEvent::ConsistencyIssueRepaired),
+            AccountId = self.account_id,
+            Collection = collection.as_str(),
+            DocumentId = document_id,
+            Details = kind.as_str(),
+        );
+        self.report.repaired += 1;
+    }
+
+    async fn indexes(&mut self, expected: &mut ExpectedValues) -> trc::Result<()> {
+        let mut orphans = Vec::new();
+        self.server
+            .store()
+            .iterate(
+                IterateParams::new(
+                    AnyKey {
+                        subspace: SUBSPACE_INDEXES,
+                        key: KeySerializer::new(U32_LEN)
+                            .write(self.account_id)
+                            .finalize(),
+                    },
+                    AnyKey {
+                        subspace: SUBSPACE_INDEXES,
+                        key: KeySerializer::new(U32_LEN + 1)
+                            .write(self.account_id)
+                            .write(u8::MAX)
+                            .finalize(),
+                    },
+                )
+                .no_values(),
+                |key, _| {
+                    if key.len() >= (U32_LEN * 2) + 2 {
+                        let document_id = key.deserialize_be_u32(key.len() - U32_LEN)?;
+                        let entry = (
+                            key[U32_LEN],
+                            document_id,
+                            key[U32_LEN + 1],
+                            key[U32_LEN + 2..key.len() - U32_LEN].to_vec(),
+                        );
+                        if !expected.indexes.remove(&entry)
+                            && expected.documents.contains_key(&entry.0)
+                        {
+                            orphans.push(entry);
+                        }
+                    }
+
+                    Ok(true)
+                },
+            )
+            .await
+            .caused_by(trc::location!())?;
+
+        for (collection, document_id, field, value) in orphans {
+            let collection = Collection::from(collection);
+            self.issue(IssueKind::OrphanIndex, collection, document_id);
+            if self.repair {
+                self.batch
+                    .with_account_id(self.account_id)
+                    .with_collection(collection)
+                    .with_document(document_id)
+                    .unindex(Field::new(field), value);
+                self.repaired(IssueKind::OrphanIndex, collection, document_id);
+                self.flush_if_large().await?;
+            }
+        }
+
+        for (collection, document_id, field, value) in std::mem::take(&mut expected.indexes) {
+            let collection = Collection::from(collection);
+            self.issue(IssueKind::MissingIndex, collection, document_id);
+            if self.repair {
+                self.batch
+                    .with_account_id(self.account_id)
+                    .with_collection(collection)
+                    .with_document(document_id)
+                    .index(Field::new(field), value);
+                self.repaired(IssueKind::MissingIndex, collection, document_id);
+                self.flush_if_large().await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn threads(&mut self, expected: &ExpectedValues) -> trc::Result<()> {
+        let prefix = KeySerializer::new(U32_LEN + 2)
+            .write(self.account_id)
+            .write(u8::from(Collection::Email))
+            .write(u8::from(EmailField::Threading))
+            .finalize();
+        let mut mismatches = Vec::new();
+        self.server
+            .store()
+            .iterate(
+                IterateParams::new(
+                    AnyKey {
+                        subspace: SUBSPACE_PROPERTY,
+                        key: prefix.clone(),
+                    },
+                    AnyKey {
+                        subspace: SUBSPACE_PROPERTY,
+                        key: KeySerializer::new(prefix.len() + BLOB_HASH_LEN)
+                            .write(prefix.as_slice())
+                            .write(&[u8::MAX; BLOB_HASH_LEN][..])
+                            .finalize(),
+                    },
+                )
+                .ascending(),
+                |key, value| {
+                    if key.starts_with(&prefix) && key.len() > prefix.len() + U32_LEN {
+                        let document_id = key.deserialize_be_u32(key.len() - U32_LEN)?;
+                        if let Some(&thread_id) = expected.thread_ids.get(&document_id)
+                            && value.deserialize_be_u32(0)? != thread_id
+                        {
+                            let mut value = value.to_vec();
+                            value[..U32_LEN].copy_from_slice(&thread_id.to_be_bytes());
+                            mismatches.push((document_id, key.to_vec(), value));
+                        }
+                    }
+
+                    Ok(true)
+                },
+            )
+            .await
+            .caused_by(trc::location!())?;
+
+        for (document_id, key, value) in mismatches {
+            self.issue(IssueKind::ThreadMismatch, Collection::Email, document_id);
+            if self.repair {
+                self.batch.set(
+                    ValueClass::Any(AnyClass {
+                        subspace: SUBSPACE_PROPERTY,
+                        key,
+                    }),
+                    value,
+                );
+                self.repaired(IssueKind::ThreadMismatch, Collection::Email, document_id);
+                self.flush_if_large().await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn blob_links(
+        &mut self,
+        expected: &ExpectedValues,
+        tombstone_ids: &mut RoaringBitmap,
+    ) -> trc::Result<()> {
+        // Blob links are keyed by hash and have no account prefix, so the whole subspace is scanned
+        let account_id = self.account_id.to_be_bytes();
+        let mut linked = AHashSet::new();
+        let mut orphans = Vec::new();
+        self.server
+            .store()
+            .iterate(
+                IterateParams::new(
+                    AnyKey {
+                        subspace: SUBSPACE_BLOB_LINK,
+                        key: vec![0u8],
+                    },
+                    AnyKey {
+                        subspace: SUBSPACE_BLOB_LINK,
+                        key: vec![u8::MAX; BLOB_HASH_LEN + (U32_LEN * 2) + 1],
+                    },
+                )
+                .no_values(),
+                |key, _| {
+                    if key.len() == BLOB_HASH_LEN + (U32_LEN * 2) + 1
+                        && key[BLOB_HASH_LEN..BLOB_HASH_LEN + U32_LEN] == account_id
+                    {
+                        let link = (
+                            key[BLOB_HASH_LEN + U32_LEN],
+                            key.deserialize_be_u32(key.len() - U32_LEN)?,
+                            BlobHash::try_from_hash_slice(&key[..BLOB_HASH_LEN]).unwrap(),
+                        );
+                        if expected.blob_links.contains(&link) {
+                            linked.insert(link);
+                        } else if expected.documents.contains_key(&link.0)
+                            && (link.0 != u8::from(Collection::Email)
+                                || !expected.orphan_metadata.contains(link.1))
+                        {
+                            // Links of orphaned metadata are removed when unindexing
+                            orphans.push(link);
+                        }
+                    }
+
+                    Ok(true)
+                },
+            )
+            .await
+            .caused_by(trc::location!())?;
+
+        for (collection, document_id, hash) in orphans {
+            let version = expected.versions.get(&(collection, document_id)).copied();
+            let collection = Collection::from(collection);
+            self.issue(IssueKind::OrphanBlobLink, collection, document_id);
+            if self.repair {
+                // Links of documents created or updated after the scan are kept
+                let mut batch = BatchBuilder::new();
+                batch
+                    .with_account_id(self.account_id)
+                    .with_collection(collection)
+                    .with_document(document_id)
+                    .assert_value(
+                        ValueClass::Property(Field::ARCHIVE.into()),
+                        version.map_or(AssertValue::None, AssertValue::Archive),
+                    )
+                    .clear(BlobOp::Link {
+                        hash,
+                        to: BlobLink::Document,
+                    });
+                match self.server.store().write(batch.build_all()).await {
+                    Ok(_) => {
+                        self.repaired(IssueKind::OrphanBlobLink, collection, document_id);
+                    }
+                    Err(err)
+                        if err.matches(trc::EventType::Store(StoreEvent::AssertValueFailed)) => {}
+                    Err(err) => {
+                        return Err(err.caused_by(trc::location!()));
+                    }
+                }
+            }
+        }
+
+        // Make sure all referenced blobs are linked and present in the blob store
+        let mut blob_exists = AHashMap::new();
+        for link in &expected.blob_links {
+            let (collection, document_id, hash) = link;
+            let collection = Collection::from(*collection);
+            let exists = if let Some(exists) = blob_exists.get(hash) {
+                *exists
+            } else {
+                let exists = self
+                    .server
+                    .blob_store()
+                    .get_stored_blob_range(hash.as_ref(), 0..1)
+                    .await
+                    .caused_by(trc::location!())?
+                    .is_some();
+                blob_exists.insert(hash.clone(), exists);
+                exists
+            };
+
+            if !exists {
+                self.issue(IssueKind::MissingBlob, collection, *document_id);
+                if collection == Collection::Email {
+                    tombstone_ids.insert(*document_id);
+                }
+            } else if !linked.contains(link) {
+                self.issue(IssueKind::MissingBlobLink, collection, *document_id);
+                if self.repair {
+                    self.batch
+                        .with_account_id(self.account_id)
+                        .with_collection(collection)
+                        .with_document(*document_id)
+                        .set(
+                            BlobOp::Link {
+                                hash: hash.clone(),
+                                to: BlobLink::Document,
+                            },
+                            Vec::new(),
+                        );
+                    self.repaired(IssueKind::MissingBlobLink, collection, *document_id);
+                    self.flush_if_large().await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn flush_if_large(&mut self) -> trc::Result<()> {
+        if self.batch.is_large_batch() {
+            self.flush().await
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn flush(&mut self) -> trc::Result<()> {
+        if !self.batch.is_empty() {
+            let batch = std::mem::take(&mut self.batch);
+            self.server
+                .store()
+                .write(batch.build_all())
+                .await
+                .caused_by(trc::location!())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ExpectedValues {
+    fn add(
+        &mut self,
+        collection: Collection,
+        document_id: u32,
+        archive: &Archive<AlignedBytes>,
+    ) -> trc::Result<()> {
+        match collection {
+            Collection::Email => {
+                let data = archive.unarchive::<MessageData>()?;
+                self.thread_ids
+                    .insert(document_id, data.thread_id.to_native());
+                self.add_values(collection, document_id, data.index_values());
+            }
+            Collection::Mailbox => {
+                let data = archive.unarchive::<Mailbox>()?;
+                self.add_values(collection, document_id, data.index_values());
+            }
+            Collection::Identity => {
+                let data = archive.unarchive::<Identity>()?;
+                self.add_values(collection, document_id, data.index_values());
+            }
+            Collection::EmailSubmission => {
+                let data = archive.unarchive::<EmailSubmission>()?;
+                self.add_values(collection, document_id, data.index_values());
+            }
+            Collection::SieveScript => {
+                let data = archive.unarchive::<SieveScript>()?;
+                self.add_values(collection, document_id, data.index_values());
+            }
+            Collection::AddressBook => {
+                let data = archive.unarchive::<AddressBook>()?;
+                self.add_values(collection, document_id, data.index_values());
+            }
+            Collection::ContactCard => {
+                let data = archive.unarchive::<ContactCard>()?;
+                self.add_values(collection, document_id, data.index_values());
+            }
+            Collection::Calendar => {
+                let data = archive.unarchive::<Calendar>()?;
+                self.add_values(collection, document_id, data.index_values());
+            }
+            Collection::CalendarEvent => {
+                let data = archive.unarchive::<CalendarEvent>()?;
+                self.add_values(collection, document_id, data.index_values());
+            }
+            Collection::CalendarEventNotification => {
+                let data = archive.unarchive::<CalendarEventNotification>()?;
+                self.add_values(collection, document_id, data.index_values());
+            }
+            Collection::FileNode => {
+                let data = archive.unarchive::<FileNode>()?;
+                self.add_values(collection, document_id, data.index_values());
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn add_values<'x>(
+        &mut self,
+        collection: Collection,
+        document_id: u32,
+        values: impl Iterator<Item = IndexValue<'x>>,
+    ) {
+        let collection = u8::from(collection);
+        for value in values {
+            match value {
+                IndexValue::Index { field, value } if !value.is_empty() => {
+                    self.indexes.insert((
+                        collection,
+                        document_id,
+                        field.into(),
+                        value.into_owned(),
+                    ));
+                }
+                IndexValue::Blob { value } => {
+                    self.blob_links.insert((collection, document_id, value));
+                }
+                IndexValue::Blobs { values } => {
+                    for value in values {
+                        self.blob_links.insert((collection, document_id, value));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn documents(&self, collection: Collection) -> RoaringBitmap {
+        self.documents
+            .get(&u8::from(collection))
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+impl IssueKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IssueKind::OrphanIndex => "orphan-index",
+            IssueKind::MissingIndex => "missing-index",
+            IssueKind::OrphanMetadata => "orphan-metadata",
+            IssueKind::MissingMetadata => "missing-metadata",
+            IssueKind::ThreadMismatch => "thread-mismatch",
+            IssueKind::OrphanBlobLink => "orphan-blob-link",
+            IssueKind::MissingBlobLink => "missing-blob-link",
+            IssueKind::MissingBlob => "missing-blob",
+            IssueKind::QuotaMismatch => "quota-mismatch",
+            IssueKind::CorruptedDocument => "corrupted-document",
+        }
+    }
+}
@@ -7,7 +7,9 @@
 use crate::task_manager::{Task, TaskDetails, TaskResult};
 use common::Server;
 use email::{cache::MessageCacheFetch, message::metadata::MessageMetadata};
-use groupware::{cache::GroupwareCache, calendar::CalendarEvent, contact::ContactCard};
+use groupware::{
+    cache::GroupwareCache, calendar::CalendarEvent, contact::ContactCard, file::FileNode,
+};
 use registry::{
     schema::{
         enums::IndexDocumentType,
@@ -39,6 +41,7 @@ pub(crate) trait SearchIndexTask: Sync + Send {
 }
 
 const NUM_INDEXES: usize = 5;
+const MAX_FILE_INDEX_SIZE: usize = 1024 * 1024;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum TaskType {
@@ -78,8 +81,7 @@ impl SearchIndexTask for Server {
                             build_contact_document(self, account_id, document_id).await
                         }
                         IndexDocumentType::File => {
-                            // File indexing not implemented yet
-                            continue;
+                            build_file_document(self, account_id, document_id).await
                         }
                     };
 
@@ -235,6 +237,7 @@ impl SearchIndexTask for Server {
             SearchIndex::Email,
             SearchIndex::Calendar,
             SearchIndex::Contacts,
+            SearchIndex::File,
         ]) {
             let multi_account = match accounts.len().cmp(&1) {
                 Ordering::Greater => true,
@@ -354,21 +357,35 @@ pub(crate) async fn reindex_account(server: &Server, account_id: u32) -> trc::Re
         }
     }
 
-    for document_type in [IndexDocumentType::Calendar, IndexDocumentType::Contacts] {
+    for document_type in [
+        IndexDocumentType::Calendar,
+        IndexDocumentType::Contacts,
+        IndexDocumentType::File,
+    ] {
         let cache = server
             .fetch_dav_resources(
                 account_id,
                 account_id,
-                if document_type == IndexDocumentType::Calendar {
-                    SyncCollection::Calendar
-                } else {
-                    SyncCollection::AddressBook
+                match document_type {
+                    IndexDocumentType::Calendar => SyncCollection::Calendar,
+                    IndexDocumentType::Contacts => SyncCollection::AddressBook,
+                    _ => SyncCollection::FileNode,
                 },
             )
             .await
             .caused_by(trc::location!())?;
 
-        for document_id in cache.document_ids(false) {
+        // File folders are indexed by name as well
+        let document_ids = if document_type == IndexDocumentType::File {
+            cache
+                .document_ids(false)
+                .chain(cache.document_ids(true))
+                .collect::<Vec<_>>()
+        } else {
+            cache.document_ids(false).collect::<Vec<_>>()
+        };
+
+        for document_id in document_ids {
             batch.schedule_task(Task::IndexDocument(TaskIndexDocument {
                 account_id: account_id.into(),
                 document_id: document_id.into(),
@@ -506,6 +523,63 @@ async fn build_contact_document(
     }
 }
 
+async fn build_file_document(
+    server: &Server,
+    account_id: u32,
+    document_id: u32,
+) -> trc::Result<Option<IndexDocument>> {
+    let Some(index_fields) = server.core.email.index_fields.get(&SearchIndex::File) else {
+        return Ok(None);
+    };
+
+    match server
+        .store()
+        .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+            account_id,
+            Collection::FileNode,
+            document_id,
+        ))
+        .await?
+    {
+        Some(node_) => {
+            let node = node_.unarchive::<FileNode>().caused_by(trc::location!())?;
+
+            // Only plain text contents are indexed
+            let contents = if let Some(file) = node.file.as_ref()
+                && (file.size.to_native() as usize) <= MAX_FILE_INDEX_SIZE
+                && file.media_type.as_ref().is_none_or(|media_type| {
+                    let media_type = media_type.as_str();
+                    media_type.starts_with("text/")
+                        || matches!(
+                            media_type,
+                            "application/json"
+                                | "application/xml"
+                                | "application/javascript"
+                                | "application/x-sh"
+                        )
+                }) {
+                server
+                    .blob_store()
+                    .get_blob(file.blob_hash.0.as_slice(), 0..usize::MAX)
+                    .await
+                    .caused_by(trc::location!())?
+                    .and_then(|bytes| String::from_utf8(bytes).ok())
+            } else {
+                None
+            };
+
+            Ok(Some(node.index_document(
+                account_id,
+                document_id,
+                contents.as_deref(),
+                index_fields,
+                server.core.email.default_language,
+            )))
+        }
+        None => Ok(None),
+    }
+}
+
 // SPDX-SnippetBegin
 // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
 // SPDX-License-Identifier: LicenseRef-SEL
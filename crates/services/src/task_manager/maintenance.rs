@@ -8,6 +8,7 @@ use std::time::Instant;
 
 use crate::task_manager::{
     TaskResult,
+    fsck::check_account,
     index::{reindex_account, reindex_telemetry},
 };
 use common::{
@@ -15,7 +16,7 @@ use common::{
     KV_QUOTA_BLOB, KV_RATE_LIMIT_AUTH, KV_RATE_LIMIT_CONTACT, KV_RATE_LIMIT_HTTP_ANONYMOUS,
     KV_RATE_LIMIT_HTTP_AUTHENTICATED, KV_RATE_LIMIT_IMAP, KV_RATE_LIMIT_LOITER, KV_RATE_LIMIT_RCPT,
     KV_RATE_LIMIT_SCAN, KV_RATE_LIMIT_SMTP, KV_SIEVE_ID, Server,
-    storage::index::ObjectIndexBuilder,
+    storage::{index::ObjectIndexBuilder, usage::StorageUsage},
 };
 use email::{
     cache::MessageCacheFetch,
@@ -25,14 +26,15 @@ use email::{
 use groupware::{
     calendar::{Calendar, CalendarEvent, CalendarEventNotification},
     contact::{AddressBook, ContactCard},
-    file::FileNode,
+    file::{FileNode, version::FileVersions},
 };
 use registry::{
     schema::{
         enums::{TaskAccountMaintenanceType, TaskStoreMaintenanceType, TaskTenantMaintenanceType},
         prelude::{Object, ObjectInner, ObjectType, Property},
         structs::{
-            Task, TaskAccountMaintenance, TaskStatus, TaskStoreMaintenance, TaskTenantMaintenance,
+            Backup, Task, TaskAccountMaintenance, TaskStatus, TaskStoreMaintenance,
+            TaskTenantMaintenance,
         },
     },
     types::EnumImpl,
@@ -48,7 +50,7 @@ use store::{
 use trc::{AddContext, StoreEvent};
 use types::{
     collection::Collection,
-    field::{EmailField, MailboxField},
+    field::{EmailField, FileNodeField, MailboxField},
     id::Id,
 };
 
@@ -112,7 +114,8 @@ async fn store_maintenance(
     match task.maintenance_type {
         TaskStoreMaintenanceType::ReindexAccounts
         | TaskStoreMaintenanceType::PurgeAccounts
-        | TaskStoreMaintenanceType::ResetUserQuotas => {
+        | TaskStoreMaintenanceType::ResetUserQuotas
+        | TaskStoreMaintenanceType::CheckAccounts => {
             let mut batch = BatchBuilder::new();
             let now = now() as i64;
             let maintenance_type = match task.maintenance_type {
@@ -121,6 +124,9 @@ async fn store_maintenance(
                 TaskStoreMaintenanceType::ResetUserQuotas => {
                     TaskAccountMaintenanceType::RecalculateQuota
                 }
+                TaskStoreMaintenanceType::CheckAccounts => {
+                    TaskAccountMaintenanceType::CheckConsistency
+                }
                 _ => unreachable!(),
             };
             for account_id in server
@@ -264,19 +270,97 @@ async fn store_maintenance(
                 Elapsed = started.elapsed()
             );
         }
-        TaskStoreMaintenanceType::PurgeBlob => {
+        TaskStoreMaintenanceType::TrainCompressionDictionaries => {
+            server
+                .train_compression_dictionaries()
+                .await
+                .caused_by(trc::location!())?;
+        }
+        TaskStoreMaintenanceType::Backup => {
+            let Some(Backup::Enabled(settings)) = server
+                .registry()
+                .object::<Backup>(Id::singleton())
+                .await
+                .caused_by(trc::location!())?
+            else {
+                return Ok(TaskResult::Ignored);
+            };
+
+            let started = Instant::now();
+            let backup = server
+                .core
+                .scheduled_backup(&settings)
+                .await
+                .map_err(|err| {
+                    StoreEvent::UnexpectedError
+                        .caused_by(trc::location!())
+                        .reason(err)
+                        .details("Backup failed")
+                })?;
+
+            for name in backup.deleted {
+                trc::event!(Store(StoreEvent::BackupDeleted), Id = name);
+            }
+
+            trc::event!(
+                Store(StoreEvent::BackupCompleted),
+                Id = backup.name,
+                Total = backup.manifest.sequence,
+                Elapsed = started.elapsed()
+            );
+        }
+        TaskStoreMaintenanceType::PurgeBlob
+        | TaskStoreMaintenanceType::MigrateBlob
+        | TaskStoreMaintenanceType::RecompressBlob
+        | TaskStoreMaintenanceType::RewrapBlobKeys => {
             if let Some(shard_index) = task.shard_index {
-                server
-                    .store()
-                    .purge_blobs(server.blob_store().clone(), shard_index as u8)
-                    .await
-                    .caused_by(trc::location!())?;
+                match task.maintenance_type {
+                    TaskStoreMaintenanceType::PurgeBlob => {
+                        server
+                            .store()
+                            .purge_blobs(server.blob_store().clone(), shard_index as u8)
+                            .await
+                            .caused_by(trc::location!())?;
+                    }
+                    TaskStoreMaintenanceType::RecompressBlob => {
+                        server
+                            .store()
+                            .recompress_blobs(
+                                server.blob_store().clone(),
+                                shard_index as u8,
+                                server.core.email.compression,
+                            )
+                            .await
+                            .caused_by(trc::location!())?;
+                    }
+                    TaskStoreMaintenanceType::RewrapBlobKeys => {
+                        server
+                            .store()
+                            .rewrap_blob_keys(server.blob_store().clone(), shard_index as u8)
+                            .await
+                            .caused_by(trc::location!())?;
+                    }
+                    // SPDX-SnippetBegin
+                    // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+                    // SPDX-License-Identifier: LicenseRef-SEL
+                    #[cfg(feature = "enterprise")]
+                    TaskStoreMaintenanceType::MigrateBlob => {
+                        if let store::BlobStore::Tiered(blob_store) = server.blob_store() {
+                            blob_store
+                                .migrate_blobs(server.store(), shard_index as u8)
+                                .await
+                                .caused_by(trc::location!())?;
+                        }
+                    }
+                    // SPDX-SnippetEnd
+                    _ => {}
+                }
             } else {
                 let mut batch = BatchBuilder::new();
                 let now = now() as i64;
                 for shard_index in 0..=u8::MAX {
                     batch.schedule_task(Task::StoreMaintenance(TaskStoreMaintenance {
-                        maintenance_type: TaskStoreMaintenanceType::PurgeBlob,
+                        maintenance_type: task.maintenance_type,
                         shard_index: Some(shard_index as u64),
                         status: TaskStatus::at(now),
                     }));
@@ -422,6 +506,15 @@ async fn account_maintenance(
         TaskAccountMaintenanceType::RecalculateQuota => {
             recalculate_quota(server, task.account_id.document_id()).await?;
         }
+        TaskAccountMaintenanceType::CheckConsistency
+        | TaskAccountMaintenanceType::RepairConsistency => {
+            check_account(
+                server,
+                task.account_id.document_id(),
+                task.maintenance_type == TaskAccountMaintenanceType::RepairConsistency,
+            )
+            .await?;
+        }
     }
 
     Ok(TaskResult::Success(vec![]))
@@ -440,8 +533,37 @@ async fn tenant_maintenance(
     Ok(TaskResult::Success(vec![]))
 }
 
-async fn recalculate_quota(server: &Server, account_id: u32) -> trc::Result<()> {
+pub(crate) async fn recalculate_quota(server: &Server, account_id: u32) -> trc::Result<()> {
+    let (quota, usage) = calculate_usage(server, account_id).await?;
+    let mut batch = BatchBuilder::new();
+    batch
+        .with_account_id(account_id)
+        .clear(ValueClass::Quota)
+        .add(ValueClass::Quota, quota);
+    for class in server
+        .get_storage_usage(account_id)
+        .await
+        .caused_by(trc::location!())?
+        .counters
+        .into_keys()
+    {
+        batch.clear(ValueClass::Usage(class));
+    }
+    usage.write(&mut batch);
+    server
+        .store()
+        .write(batch.build_all())
+        .await
+        .caused_by(trc::location!())
+        .map(|_| ())
+}
+
+pub(crate) async fn calculate_usage(
+    server: &Server,
+    account_id: u32,
+) -> trc::Result<(i64, StorageUsage)> {
     let mut quota = 0;
+    let mut usage = StorageUsage::default();
 
     for collection in [
         Collection::Email,
@@ -455,50 +577,56 @@ async fn recalculate_quota(server: &Server, account_id: u32) -> trc::Result<()>
     ] {
         server
             .archives(account_id, collection, &(), |_, archive| {
-                match collection {
+                let size = match collection {
                     Collection::Email => {
-                        quota += archive.unarchive::<MessageData>()?.size.to_native() as i64;
-                    }
-                    Collection::Calendar => {
-                        quota += archive.unarchive::<Calendar>()?.size() as i64;
+                        let message = archive.unarchive::<MessageData>()?;
+                        let size = message.size.to_native();
+                        usage.add_mailboxes(
+                            message.mailboxes.iter().map(|m| m.mailbox_id.to_native()),
+                            size,
+                        );
+                        size
                     }
+                    Collection::Calendar => archive.unarchive::<Calendar>()?.size() as u32,
                     Collection::CalendarEvent => {
-                        quota += archive.unarchive::<CalendarEvent>()?.size() as i64;
+                        archive.unarchive::<CalendarEvent>()?.size() as u32
                     }
                     Collection::CalendarEventNotification => {
-                        quota += archive.unarchive::<CalendarEventNotification>()?.size() as i64;
-                    }
-                    Collection::AddressBook => {
-                        quota += archive.unarchive::<AddressBook>()?.size() as i64;
-                    }
-                    Collection::ContactCard => {
-                        quota += archive.unarchive::<ContactCard>()?.size() as i64;
-                    }
-                    Collection::FileNode => {
-                        quota += archive.unarchive::<FileNode>()?.size() as i64;
+                        archive.unarchive::<CalendarEventNotification>()?.size() as u32
                     }
-                    Collection::SieveScript => {
-                        quota += u32::from(archive.unarchive::<SieveScript>()?.size) as i64;
-                    }
-                    _ => {}
-                }
+                    Collection::AddressBook => archive.unarchive::<AddressBook>()?.size() as u32,
+                    Collection::ContactCard => archive.unarchive::<ContactCard>()?.size() as u32,
+                    Collection::FileNode => archive.unarchive::<FileNode>()?.size() as u32,
+                    Collection::SieveScript => u32::from(archive.unarchive::<SieveScript>()?.size),
+                    _ => return Ok(true),
+                };
+                quota += size as i64;
+                usage.add_item(collection, size);
                 Ok(true)
             })
             .await
             .caused_by(trc::location!())?;
     }
 
-    let mut batch = BatchBuilder::new();
-    batch
-        .with_account_id(account_id)
-        .clear(ValueClass::Quota)
-        .add(ValueClass::Quota, quota);
+    // Add retained file versions
     server
-        .store()
-        .write(batch.build_all())
+        .all_archives(
+            account_id,
+            Collection::FileNode,
+            FileNodeField::Versions.into(),
+            |_, archive| {
+                for version in archive.unarchive::<FileVersions>()?.versions.iter() {
+                    let size = version.size.to_native();
+                    quota += size as i64;
+                    usage.add_item(Collection::FileNode, size);
+                }
+                Ok(())
+            },
+        )
         .await
-        .caused_by(trc::location!())
-        .map(|_| ())
+        .caused_by(trc::location!())?;
+
+    Ok((quota, usage))
 }
 
 // SPDX-SnippetBegin
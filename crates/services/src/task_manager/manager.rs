@@ -6,6 +6,8 @@
 
 use crate::task_manager::acme::AcmeTask;
 use crate::task_manager::alarm::SendAlarmTask;
+use crate::task_manager::calendar_subscription::CalendarSubscriptionTask;
+use crate::task_manager::data_store_migration::DataStoreMigrationTask;
 use crate::task_manager::destroy_account::DestroyAccountTask;
 use crate::task_manager::dkim::DkimManagementTask;
 use crate::task_manager::dns::DnsManagementTask;
@@ -14,8 +16,10 @@ use crate::task_manager::index::SearchIndexTask;
 use crate::task_manager::lock::TaskLockManager;
 use crate::task_manager::maintenance::MaintenanceTask;
 use crate::task_manager::merge_threads::MergeThreadsTask;
+use crate::task_manager::move_account::MoveAccountTask;
 use crate::task_manager::report::{self, SubmitReportTask};
 use crate::task_manager::restore_item::RestoreItemTask;
+use crate::task_manager::snooze::EmailSnoozeTask;
 use crate::task_manager::spam_classifier::SpamFilterMaintenanceTask;
 use crate::task_manager::{
     DEFAULT_LOCK_EXPIRY, Locked, QUEUE_REFRESH_INTERVAL, TaskDetails, TaskFailureType, TaskInfo,
@@ -95,7 +99,9 @@ pub fn spawn_task_manager(inner: Arc<Inner>) {
             TaskType::DestroyAccount
             | TaskType::AccountMaintenance
             | TaskType::TenantMaintenance
-            | TaskType::StoreMaintenance => 1,
+            | TaskType::StoreMaintenance
+            | TaskType::DataStoreMigration
+            | TaskType::MoveAccount => 1,
             TaskType::SpamFilterMaintenance => 2,
             TaskType::CalendarAlarmEmail
             | TaskType::CalendarAlarmNotification
@@ -106,7 +112,9 @@ pub fn spawn_task_manager(inner: Arc<Inner>) {
             | TaskType::RestoreArchivedItem
             | TaskType::AcmeRenewal
             | TaskType::DkimManagement
-            | TaskType::DnsManagement => TASK_QUEUE_BUFFER,
+            | TaskType::DnsManagement
+            | TaskType::EmailSnooze
+            | TaskType::CalendarSubscription => TASK_QUEUE_BUFFER,
         };
 
         let (tx, mut rx) = mpsc::channel::<TaskJob>(channel_capacity);
@@ -252,6 +260,14 @@ pub fn spawn_task_manager(inner: Arc<Inner>) {
                                 Task::DnsManagement(task_dns_management) => {
                                     server.dns_management(task_dns_management).await
                                 }
+                                Task::EmailSnooze(task) => server.wake_snoozed_email(task).await,
+                                Task::CalendarSubscription(task) => {
+                                    server.refresh_calendar_subscription(task).await
+                                }
+                                Task::DataStoreMigration(task) => {
+                                    server.migrate_data_store(task).await
+                                }
+                                Task::MoveAccount(task) => server.move_account(task).await,
                                 Task::IndexDocument(_)
                                 | Task::UnindexDocument(_)
                                 | Task::IndexTrace(_) => unreachable!(),
@@ -363,7 +379,9 @@ impl TaskQueueManager for Server {
                                 TaskType::AccountMaintenance
                                 | TaskType::TenantMaintenance
                                 | TaskType::DestroyAccount => roles.account_maintenance,
-                                TaskType::StoreMaintenance => roles.store_maintenance,
+                                TaskType::StoreMaintenance
+                                | TaskType::DataStoreMigration
+                                | TaskType::MoveAccount => roles.store_maintenance,
                                 TaskType::SpamFilterMaintenance => roles.spam_training,
                                 TaskType::CalendarAlarmEmail
                                 | TaskType::CalendarAlarmNotification
@@ -374,7 +392,9 @@ impl TaskQueueManager for Server {
                                 | TaskType::RestoreArchivedItem
                                 | TaskType::AcmeRenewal
                                 | TaskType::DkimManagement
-                                | TaskType::DnsManagement => true,
+                                | TaskType::DnsManagement
+                                | TaskType::EmailSnooze
+                                | TaskType::CalendarSubscription => true,
                             };
 
                             if !enabled {
@@ -17,18 +17,23 @@ use trc::TaskManagerEvent;
 
 pub mod acme;
 pub mod alarm;
+pub mod calendar_subscription;
+pub mod data_store_migration;
 pub mod destroy_account;
 pub mod dkim;
 pub mod dns;
+pub mod fsck;
 pub mod imip;
 pub mod index;
 pub mod lock;
 pub mod maintenance;
 pub mod manager;
 pub mod merge_threads;
+pub mod move_account;
 pub mod report;
 pub mod restore_item;
 pub mod scheduler;
+pub mod snooze;
 pub mod spam_classifier;
 
 const QUEUE_REFRESH_INTERVAL: u64 = 60 * 5; // 5 minutes
@@ -104,6 +109,10 @@ impl TaskInfo for Task {
             Task::AcmeRenewal(_) => "AcmeRenewal",
             Task::DkimManagement(_) => "DkimManagement",
             Task::DnsManagement(_) => "DnsManagement",
+            Task::EmailSnooze(_) => "EmailSnooze",
+            Task::CalendarSubscription(_) => "CalendarSubscription",
+            Task::DataStoreMigration(_) => "DataStoreMigration",
+            Task::MoveAccount(_) => "MoveAccount",
             Task::TenantMaintenance(_) => "TenantMaintenance",
         }
     }
@@ -0,0 +1,206 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::task_manager::TaskResult;
+use common::Server;
+use registry::schema::structs::TaskMoveAccount;
+
+pub(crate) trait MoveAccountTask: Sync + Send {
+    fn move_account(&self, task: &TaskMoveAccount) -> impl Future<Output = TaskResult> + Send;
+}
+
+impl MoveAccountTask for Server {
+    async fn move_account(&self, task: &TaskMoveAccount) -> TaskResult {
+        // SPDX-SnippetBegin
+        // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+        // SPDX-License-Identifier: LicenseRef-SEL
+        #[cfg(feature = "enterprise")]
+        match enterprise::move_account(self, task).await {
+            Ok(result) => result,
+            Err(err) => {
+                let result = TaskResult::temporary(err.to_string());
+                trc::error!(
+                    err.account_id(task.account_id.document_id())
+                        .details("Failed to move account")
+                );
+                result
+            }
+        }
+        // SPDX-SnippetEnd
+
+        #[cfg(not(feature = "enterprise"))]
+        {
+            let _ = task;
+            TaskResult::permanent("Account sharding is only available in the enterprise edition")
+        }
+    }
+}
+
+// SPDX-SnippetBegin
+// SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+// SPDX-License-Identifier: LicenseRef-SEL
+#[cfg(feature = "enterprise")]
+mod enterprise {
+    use crate::task_manager::TaskResult;
+    use common::{
+        Server,
+        ipc::{BroadcastEvent, RegistryChange},
+    };
+    use registry::{
+        schema::{
+            enums::{ClusterNodeStatus, DataStoreMigrationStage},
+            prelude::ObjectType,
+            structs::{Task, TaskMoveAccount, TaskStatus},
+        },
+        types::EnumImpl,
+    };
+    use std::time::{Duration, Instant};
+    use store::backend::composite::sharded_data::ShardedStore;
+    use trc::{AddContext, StoreEvent};
+
+    const MAX_CUTOVER_KEYS: u64 = 1_000;
+    const FENCE_ACK_TIMEOUT: Duration = Duration::from_secs(30);
+
+    pub(super) async fn move_account(
+        server: &Server,
+        task: &TaskMoveAccount,
+    ) -> trc::Result<TaskResult> {
+        let Some(store) = server.store().as_sharded() else {
+            return Ok(TaskResult::permanent(
+                "The data store has no shards configured",
+            ));
+        };
+        let account_id = task.account_id.document_id();
+        let to = task.shard_id.map(|id| id.id());
+        if let Some(shard_id) = to
+            && store.shard(shard_id).is_none()
+        {
+            return Ok(TaskResult::permanent(
+                "The target shard is not loaded, restart the server to open new shards",
+            ));
+        }
+        let from = store.account_shard(account_id);
+        if from == to {
+            return Ok(TaskResult::Ignored);
+        }
+
+        let started = Instant::now();
+        let mut next_task = task.clone();
+        let keys_written = match task.stage {
+            DataStoreMigrationStage::Copy | DataStoreMigrationStage::CatchUp => {
+                let keys_written = store
+                    .sync_account(account_id, to)
+                    .await
+                    .caused_by(trc::location!())?;
+                if task.stage == DataStoreMigrationStage::Copy {
+                    next_task.stage = DataStoreMigrationStage::CatchUp;
+                } else if keys_written <= MAX_CUTOVER_KEYS {
+                    next_task.stage = DataStoreMigrationStage::Cutover;
+                }
+                keys_written
+            }
+            DataStoreMigrationStage::Cutover => {
+                // Reject writes to the account on all nodes while the last changes are copied
+                let fence_id = store
+                    .fence_account(account_id)
+                    .await
+                    .caused_by(trc::location!())?;
+                broadcast_placement(server, store).await?;
+                let result = cutover(server, store, account_id, to, fence_id).await;
+                store
+                    .unfence_account(account_id)
+                    .await
+                    .caused_by(trc::location!())?;
+                broadcast_placement(server, store).await?;
+                let keys_written = result?;
+
+                trc::event!(
+                    Store(StoreEvent::AccountMoved),
+                    AccountId = account_id,
+                    Id = to,
+                    Total = task.keys_copied + keys_written,
+                    Elapsed = started.elapsed()
+                );
+
+                // Remove the copy left on the previous store
+                store
+                    .purge_account(account_id, from)
+                    .await
+                    .caused_by(trc::location!())?;
+
+                return Ok(TaskResult::Success(vec![]));
+            }
+        };
+
+        trc::event!(
+            Store(StoreEvent::DataStoreSynced),
+            AccountId = account_id,
+            Details = task.stage.as_str(),
+            Total = keys_written,
+            Elapsed = started.elapsed()
+        );
+
+        next_task.keys_copied += keys_written;
+        next_task.status = TaskStatus::now();
+        Ok(TaskResult::Success(vec![Task::MoveAccount(next_task)]))
+    }
+
+    async fn cutover(
+        server: &Server,
+        store: &ShardedStore,
+        account_id: u32,
+        to: Option<u64>,
+        fence_id: u64,
+    ) -> trc::Result<u64> {
+        // Wait for all nodes to complete their in-flight writes to the account
+        let started = Instant::now();
+        loop {
+            let node_ids = server
+                .registry()
+                .cluster_node_list()
+                .await
+                .caused_by(trc::location!())?
+                .into_iter()
+                .filter(|node| node.status == ClusterNodeStatus::Active)
+                .map(|node| node.node_id as u16);
+            if store
+                .is_fence_acknowledged(account_id, fence_id, node_ids)
+                .await
+                .caused_by(trc::location!())?
+            {
+                break;
+            } else if started.elapsed() > FENCE_ACK_TIMEOUT {
+                return Err(StoreEvent::UnexpectedError
+                    .caused_by(trc::location!())
+                    .details("Timed out waiting for all nodes to acknowledge the account fence")
+                    .account_id(account_id));
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        store
+            .commit_move(account_id, to)
+            .await
+            .caused_by(trc::location!())
+    }
+
+    // Applies the placements and fences locally and notifies the other nodes
+    async fn broadcast_placement(server: &Server, store: &ShardedStore) -> trc::Result<()> {
+        store.reload_placement().await.caused_by(trc::location!())?;
+        store
+            .acknowledge_fences(server.registry().node_id())
+            .await
+            .caused_by(trc::location!())?;
+        server
+            .cluster_broadcast(BroadcastEvent::RegistryChange(RegistryChange::Reload(
+                ObjectType::DataStoreShard,
+            )))
+            .await;
+
+        Ok(())
+    }
+}
+// SPDX-SnippetEnd
@@ -36,6 +36,7 @@ enum Event {
     PurgeAccount,
     PurgeDataStore,
     PurgeBlobStore,
+    Backup,
     OtelMetrics,
     CalculateMetrics,
     TrainSpamClassifier,
@@ -88,6 +89,14 @@ pub fn spawn_task_scheduler(inner: Arc<Inner>) {
                 Event::PurgeBlobStore,
             );
 
+            // Scheduled backups
+            if let Some(backup_frequency) = &server.core.email.backup_frequency {
+                queue.schedule(
+                    Instant::now() + backup_frequency.time_to_next(),
+                    Event::Backup,
+                );
+            }
+
             // Node ID lease renewal
             if server.core.storage.coordinator.is_enabled() {
                 queue.schedule(
@@ -227,6 +236,47 @@ pub fn spawn_task_scheduler(inner: Arc<Inner>) {
                                 status: TaskStatus::now(),
                                 shard_index: None,
                             }));
+
+                            // SPDX-SnippetBegin
+                            // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+                            // SPDX-License-Identifier: LicenseRef-SEL
+                            #[cfg(feature = "enterprise")]
+                            if let store::BlobStore::Tiered(blob_store) = server.blob_store()
+                                && blob_store.migrate_after.is_some()
+                            {
+                                trc::event!(
+                                    TaskManager(TaskManagerEvent::TaskQueued),
+                                    Type = TaskStoreMaintenanceType::MigrateBlob.as_str()
+                                );
+
+                                batch.schedule_task(Task::StoreMaintenance(TaskStoreMaintenance {
+                                    maintenance_type: TaskStoreMaintenanceType::MigrateBlob,
+                                    status: TaskStatus::now(),
+                                    shard_index: None,
+                                }));
+                            }
+                            // SPDX-SnippetEnd
+                        }
+                    }
+                    Event::Backup => {
+                        if let Some(backup_frequency) = &server.core.email.backup_frequency {
+                            queue.schedule(
+                                Instant::now() + backup_frequency.time_to_next(),
+                                Event::Backup,
+                            );
+                        }
+
+                        if let Some(batch) = batch.as_mut() {
+                            trc::event!(
+                                TaskManager(TaskManagerEvent::TaskQueued),
+                                Type = TaskStoreMaintenanceType::Backup.as_str()
+                            );
+
+                            batch.schedule_task(Task::StoreMaintenance(TaskStoreMaintenance {
+                                maintenance_type: TaskStoreMaintenanceType::Backup,
+                                status: TaskStatus::now(),
+                                shard_index: None,
+                            }));
                         }
                     }
                     Event::RenewNodeIdLease => {
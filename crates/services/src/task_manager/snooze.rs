@@ -0,0 +1,191 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::task_manager::TaskResult;
+use common::{Server, storage::index::ObjectIndexBuilder};
+use email::{
+    cache::{MessageCacheFetch, mailbox::MailboxCacheAccess},
+    mailbox::{INBOX_ID, UidMailbox},
+    message::{ingest::EmailIngest, metadata::MessageData, snooze::EmailSnoozeFetch},
+};
+use registry::schema::structs::TaskEmailSnooze;
+use std::time::Duration;
+use store::{
+    ValueKey,
+    rand::RngExt,
+    write::{AlignedBytes, Archive, BatchBuilder, now},
+};
+use trc::AddContext;
+use types::{
+    collection::{Collection, SyncCollection, VanishedCollection},
+    field::EmailField,
+    keyword::Keyword,
+    special_use::SpecialUse,
+};
+
+const MAX_RETRIES: usize = 5;
+
+pub(crate) trait EmailSnoozeTask: Sync + Send {
+    fn wake_snoozed_email(
+        &self,
+        task: &TaskEmailSnooze,
+    ) -> impl Future<Output = TaskResult> + Send;
+}
+
+impl EmailSnoozeTask for Server {
+    async fn wake_snoozed_email(&self, task: &TaskEmailSnooze) -> TaskResult {
+        match wake_snoozed_email(self, task).await {
+            Ok(result) => result,
+            Err(err) => {
+                let result = TaskResult::temporary(err.to_string());
+                trc::error!(
+                    err.account_id(task.account_id.document_id())
+                        .document_id(task.document_id.document_id())
+                        .caused_by(trc::location!())
+                        .details("Failed to wake snoozed e-mail")
+                );
+                result
+            }
+        }
+    }
+}
+
+async fn wake_snoozed_email(server: &Server, task: &TaskEmailSnooze) -> trc::Result<TaskResult> {
+    let account_id = task.account_id.document_id();
+    let document_id = task.document_id.document_id();
+    let mut try_count = 0;
+
+    loop {
+        // Make sure the message is still snoozed
+        let Some(snooze) = server
+            .email_snooze(account_id, document_id)
+            .await
+            .caused_by(trc::location!())?
+        else {
+            return Ok(TaskResult::Ignored);
+        };
+        if snooze.until > now() {
+            // The snooze was extended, a newer task will wake the message
+            return Ok(TaskResult::Ignored);
+        }
+
+        let Some(data_) = server
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                account_id,
+                Collection::Email,
+                document_id,
+            ))
+            .await
+            .caused_by(trc::location!())?
+        else {
+            return Ok(TaskResult::Ignored);
+        };
+        let data = data_
+            .to_unarchived::<MessageData>()
+            .caused_by(trc::location!())?;
+        let cache = server
+            .get_cached_messages(account_id)
+            .await
+            .caused_by(trc::location!())?;
+        let mut new_data = data.inner.to_builder();
+
+        // Move the message out of the snoozed mailbox, unless it was already
+        // moved elsewhere while snoozed
+        if let Some(snoozed) = cache.mailbox_by_role(&SpecialUse::Snoozed)
+            && data.inner.has_mailbox_id(snoozed.document_id)
+        {
+            let mailbox_id = snooze
+                .move_to_mailbox_id
+                .filter(|mailbox_id| cache.has_mailbox_id(mailbox_id))
+                .unwrap_or(INBOX_ID);
+            if snoozed.document_id != mailbox_id {
+                new_data.remove_mailbox(snoozed.document_id);
+            }
+            if !new_data.has_mailbox_id(mailbox_id) {
+                new_data.add_mailbox(UidMailbox::new_unassigned(mailbox_id));
+            }
+        }
+
+        // Apply keywords, by default the message is marked as unread
+        if snooze.has_keyword_changes() {
+            for keyword in snooze.remove_keywords.iter() {
+                new_data.remove_keyword(keyword);
+            }
+            for keyword in snooze.add_keywords.iter() {
+                new_data.add_keyword(keyword.clone());
+            }
+        } else {
+            new_data.remove_keyword(&Keyword::Seen);
+        }
+
+        // Obtain IMAP UIDs for added mailboxes
+        let ids = server
+            .assign_email_ids(
+                account_id,
+                new_data
+                    .mailboxes
+                    .iter()
+                    .filter(|m| m.uid == 0)
+                    .map(|m| m.mailbox_id),
+                false,
+            )
+            .await
+            .caused_by(trc::location!())?;
+        for (uid_mailbox, uid) in new_data
+            .mailboxes
+            .iter_mut()
+            .filter(|m| m.uid == 0)
+            .zip(ids)
+        {
+            uid_mailbox.uid = uid;
+        }
+
+        let mut batch = BatchBuilder::new();
+        batch
+            .with_account_id(account_id)
+            .with_collection(Collection::Email)
+            .with_document(document_id);
+
+        // Log mailbox changes
+        for mailbox in new_data.removed_mailboxes(data.inner) {
+            batch
+                .log_container_property_change(
+                    SyncCollection::Email,
+                    mailbox.mailbox_id.to_native(),
+                )
+                .log_vanished_item(
+                    VanishedCollection::Email,
+                    (mailbox.mailbox_id.to_native(), mailbox.uid.to_native()),
+                );
+        }
+        for mailbox in new_data.mailboxes.iter() {
+            batch.log_container_property_change(SyncCollection::Email, mailbox.mailbox_id);
+        }
+
+        // Write changes
+        batch
+            .custom(
+                ObjectIndexBuilder::new()
+                    .with_current(data)
+                    .with_changes(new_data.seal()),
+            )
+            .caused_by(trc::location!())?
+            .clear(EmailField::Snoozed);
+
+        match server.commit_batch(batch).await {
+            Ok(_) => return Ok(TaskResult::Success(vec![])),
+            Err(err) if err.is_assertion_failure() && try_count < MAX_RETRIES => {
+                let backoff = store::rand::rng().random_range(50..=300);
+                tokio::time::sleep(Duration::from_millis(backoff)).await;
+                try_count += 1;
+            }
+            Err(err) => {
+                return Err(err.caused_by(trc::location!()));
+            }
+        }
+    }
+}
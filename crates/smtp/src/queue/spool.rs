@@ -570,7 +570,7 @@ impl MessageWrapper {
                 BlobOp::Commit {
                     hash: self.message.blob_hash.clone(),
                 },
-                vec![],
+                now().serialize(),
             )
             .set(
                 ValueClass::Queue(QueueClass::Message(self.queue_id)),
@@ -11,4 +11,6 @@
 #[cfg(any(feature = "postgres", feature = "mysql"))]
 pub mod read_replica;
 pub mod sharded_blob;
+pub mod sharded_data;
 pub mod sharded_lookup;
+pub mod tiered_blob;
@@ -8,8 +8,8 @@
  *
  */
 
-use crate::{BlobStore, Store, backend::fs::FsStore};
-use registry::schema::structs::{BlobStoreBase, ShardedBlobStore};
+use crate::{BlobStore, Store};
+use registry::schema::structs::ShardedBlobStore;
 use std::{ops::Range, sync::Arc};
 
 pub struct ShardedBlob {
@@ -23,42 +23,13 @@ impl ShardedBlob {
             let mut stores = Vec::new();
 
             for store in config.stores {
-                let result = match store {
-                    #[cfg(feature = "s3")]
-                    BlobStoreBase::S3(s3_store) => {
-                        crate::backend::s3::S3Store::open(s3_store).await
-                    }
-                    #[cfg(feature = "azure")]
-                    BlobStoreBase::Azure(azure_store) => {
-                        crate::backend::azure::AzureStore::open(azure_store).await
-                    }
-                    BlobStoreBase::FileSystem(file_system_store) => {
-                        FsStore::open(file_system_store).await
-                    }
-                    #[cfg(feature = "foundation")]
-                    BlobStoreBase::FoundationDb(foundation_db_store) => {
-                        crate::backend::foundationdb::FdbStore::open(foundation_db_store)
-                            .await
-                            .map(BlobStore::Store)
-                    }
-                    #[cfg(feature = "postgres")]
-                    BlobStoreBase::PostgreSql(postgre_sql_store) => {
-                        crate::backend::postgres::PostgresStore::open(postgre_sql_store)
-                            .await
-                            .map(BlobStore::Store)
-                    }
-                    #[cfg(feature = "mysql")]
-                    BlobStoreBase::MySql(my_sql_store) => {
-                        crate::backend::mysql::MysqlStore::open(my_sql_store)
-                            .await
-                            .map(BlobStore::Store)
-                    }
-                    _ => Err(
-                        "Binary was not compiled with the selected blob store backend".to_string(),
-                    ),
-                };
-
-                stores.push(result?);
+                let store = BlobStore::open(store).await?;
+                if store.is_composite() {
+                    return Err(
+                        "Sharded blob stores cannot contain composite blob stores".to_string()
+                    );
+                }
+                stores.push(store);
             }
             Ok(BlobStore::Sharded(Arc::new(ShardedBlob { stores })))
         } else {
@@ -99,6 +70,7 @@ impl ShardedBlob {
                     ))]
                     Store::SQLReadReplica(store) => store.get_blob(key, read_range).await,
                     // SPDX-SnippetEnd
+                    Store::Sharded(store) => store.get_blob(key, read_range).await,
                     Store::None => Err(trc::StoreEvent::NotConfigured.into()),
                 },
                 BlobStore::Fs(store) => store.get_blob(key, read_range).await,
@@ -106,7 +78,9 @@ impl ShardedBlob {
                 BlobStore::S3(store) => store.get_blob(key, read_range).await,
                 #[cfg(feature = "azure")]
                 BlobStore::Azure(store) => store.get_blob(key, read_range).await,
-                BlobStore::Sharded(_) => unimplemented!(),
+                BlobStore::Sharded(_) | BlobStore::Tiered(_) => {
+                    Err(trc::StoreEvent::NotSupported.into())
+                }
             }
         }
         .await
@@ -136,6 +110,7 @@ impl ShardedBlob {
                     ))]
                     // SPDX-SnippetEnd
                     Store::SQLReadReplica(store) => store.put_blob(key, data).await,
+                    Store::Sharded(store) => store.put_blob(key, data).await,
                     Store::None => Err(trc::StoreEvent::NotConfigured.into()),
                 },
                 BlobStore::Fs(store) => store.put_blob(key, data).await,
@@ -143,7 +118,9 @@ impl ShardedBlob {
                 BlobStore::S3(store) => store.put_blob(key, data).await,
                 #[cfg(feature = "azure")]
                 BlobStore::Azure(store) => store.put_blob(key, data).await,
-                BlobStore::Sharded(_) => unimplemented!(),
+                BlobStore::Sharded(_) | BlobStore::Tiered(_) => {
+                    Err(trc::StoreEvent::NotSupported.into())
+                }
             }
         }
         .await
@@ -173,6 +150,7 @@ impl ShardedBlob {
                     ))]
                     Store::SQLReadReplica(store) => store.delete_blob(key).await,
                     // SPDX-SnippetEnd
+                    Store::Sharded(store) => store.delete_blob(key).await,
                     Store::None => Err(trc::StoreEvent::NotConfigured.into()),
                 },
                 BlobStore::Fs(store) => store.delete_blob(key).await,
@@ -180,7 +158,9 @@ impl ShardedBlob {
                 BlobStore::S3(store) => store.delete_blob(key).await,
                 #[cfg(feature = "azure")]
                 BlobStore::Azure(store) => store.delete_blob(key).await,
-                BlobStore::Sharded(_) => unimplemented!(),
+                BlobStore::Sharded(_) | BlobStore::Tiered(_) => {
+                    Err(trc::StoreEvent::NotSupported.into())
+                }
             }
         }
         .await
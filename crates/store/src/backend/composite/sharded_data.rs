@@ -0,0 +1,1024 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: LicenseRef-SEL
+ *
+ * This file is subject to the Stalwart Enterprise License Agreement (SEL) and
+ * is NOT open source software.
+ *
+ */
+
+use crate::{
+    Deserialize, IterateParams, Key, SUBSPACE_ACL, SUBSPACE_BLOB_LINK, SUBSPACE_COUNTER,
+    SUBSPACE_INDEXES, SUBSPACE_LOGS, SUBSPACE_PROPERTY, SUBSPACE_SEARCH_INDEX, SerializeInfallible,
+    Store, U16_LEN, U32_LEN, U64_LEN, ValueKey,
+    write::{
+        AnyClass, AnyKey, AssignedIds, Batch, BatchBuilder, ChangedCollection, Operation,
+        RegistryClass, ValueClass, ValueOp,
+        key::{DeserializeBigEndian, account_key_offset, key_account_id},
+        migrate::{StoreMigration, account_key_ranges},
+    },
+};
+use ahash::AHashMap;
+use arc_swap::ArcSwap;
+use parking_lot::Mutex;
+use registry::schema::prelude::ObjectType;
+use std::{collections::VecDeque, ops::Range, sync::Arc, time::Duration};
+use trc::AddContext;
+use utils::map::vec_map::VecMap;
+
+// Accounts are placed on the primary store unless a placement reference
+// (DataStoreShard <- Account) exists in the registry of the primary store.
+pub struct ShardedStore {
+    primary: Store,
+    shards: AHashMap<u64, Store>,
+    placement: ArcSwap<AHashMap<u32, u64>>,
+    writes: Mutex<AccountWrites>,
+}
+
+// Local copy of the account fences persisted in the primary store, along
+// with the number of in-flight writes of each account
+#[derive(Default)]
+struct AccountWrites {
+    fenced: AHashMap<u32, u64>,
+    in_flight: AHashMap<u32, usize>,
+}
+
+struct InFlightWrite<'x> {
+    store: &'x ShardedStore,
+    account_ids: Vec<u32>,
+}
+
+// Calls a backend directly, dispatching through `Store` would make the
+// futures recursive.
+macro_rules! backend {
+    ($store:expr, |$inner:ident| $op:expr) => {
+        match $store {
+            #[cfg(feature = "sqlite")]
+            Store::SQLite($inner) => $op,
+            #[cfg(feature = "foundation")]
+            Store::FoundationDb($inner) => $op,
+            #[cfg(feature = "postgres")]
+            Store::PostgreSQL($inner) => $op,
+            #[cfg(feature = "mysql")]
+            Store::MySQL($inner) => $op,
+            #[cfg(feature = "rocks")]
+            Store::RocksDb($inner) => $op,
+            Store::Ephemeral($inner) => $op,
+            #[cfg(any(feature = "postgres", feature = "mysql"))]
+            Store::SQLReadReplica($inner) => $op,
+            Store::Sharded(_) | Store::None => Err(trc::StoreEvent::NotConfigured.into()),
+        }
+    };
+}
+
+const REG_ACCOUNT: u16 = ObjectType::Account as u16;
+const REG_DATA_STORE_SHARD: u16 = ObjectType::DataStoreShard as u16;
+const PAGE_SIZE: usize = 1000;
+const ACCOUNT_FENCE_LEN: usize = (U32_LEN * 2) + U16_LEN + 1;
+
+#[derive(Default)]
+struct ShardBatch {
+    changes: VecMap<u32, ChangedCollection>,
+    ops: Vec<Operation>,
+    account_id: Option<u32>,
+    collection: Option<u8>,
+    document_id: Option<u32>,
+    detached: Vec<(usize, u32)>,
+}
+
+impl ShardedStore {
+    pub async fn open(primary: Store, shards: Vec<(u64, Store)>) -> trc::Result<Store> {
+        if primary.is_sharded() || shards.iter().any(|(_, store)| store.is_sharded()) {
+            return Err(trc::StoreEvent::NotSupported
+                .into_err()
+                .details("Sharded stores cannot be nested"));
+        }
+
+        let store = Self {
+            primary,
+            shards: shards.into_iter().collect(),
+            placement: ArcSwap::from_pointee(AHashMap::new()),
+            writes: Mutex::new(AccountWrites::default()),
+        };
+        store.reload_placement().await?;
+
+        Ok(Store::Sharded(Arc::new(store)))
+    }
+
+    // Reads the account placements from the registry references and applies
+    // the account fences, waiting for the in-flight writes of fenced accounts
+    pub async fn reload_placement(&self) -> trc::Result<()> {
+        let mut placement = AHashMap::new();
+        backend!(&self.primary, |store| store
+            .iterate(
+                IterateParams::new(
+                    ValueKey::from(ValueClass::Registry(RegistryClass::Reference {
+                        to_object_id: REG_DATA_STORE_SHARD,
+                        to_item_id: 0,
+                        from_object_id: 0,
+                        from_item_id: 0,
+                    })),
+                    ValueKey::from(ValueClass::Registry(RegistryClass::Reference {
+                        to_object_id: REG_DATA_STORE_SHARD,
+                        to_item_id: u64::MAX,
+                        from_object_id: u16::MAX,
+                        from_item_id: u64::MAX,
+                    })),
+                )
+                .no_values(),
+                |key, _| {
+                    if key.len() == (U16_LEN * 2) + (U64_LEN * 2)
+                        && key.deserialize_be_u16(U16_LEN + U64_LEN)? == REG_ACCOUNT
+                    {
+                        placement.insert(
+                            key.deserialize_be_u64((U16_LEN * 2) + U64_LEN)? as u32,
+                            key.deserialize_be_u64(U16_LEN)?,
+                        );
+                    }
+
+                    Ok(true)
+                },
+            )
+            .await)
+        .caused_by(trc::location!())?;
+
+        let mut fenced = AHashMap::new();
+        backend!(&self.primary, |store| store
+            .iterate(
+                IterateParams::new(
+                    ValueKey::from(ValueClass::AccountFence(0)),
+                    ValueKey::from(ValueClass::AccountFenceAck(u32::MAX, u16::MAX)),
+                ),
+                |key, value| {
+                    if key.len() == ACCOUNT_FENCE_LEN {
+                        fenced.insert(
+                            key.deserialize_be_u32(ACCOUNT_FENCE_LEN - U32_LEN)?,
+                            value.deserialize_be_u64(0)?,
+                        );
+                    }
+
+                    Ok(true)
+                },
+            )
+            .await)
+        .caused_by(trc::location!())?;
+
+        self.placement.store(Arc::new(placement));
+        self.writes.lock().fenced = fenced;
+
+        while self.has_fenced_writes() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        Ok(())
+    }
+
+    pub fn primary_store(&self) -> &Store {
+        &self.primary
+    }
+
+    pub fn shard(&self, shard_id: u64) -> Option<&Store> {
+        self.shards.get(&shard_id)
+    }
+
+    pub fn shard_ids(&self) -> impl Iterator<Item = u64> + '_ {
+        self.shards.keys().copied()
+    }
+
+    // Returns the shard an account is placed on, None for the primary store
+    pub fn account_shard(&self, account_id: u32) -> Option<u64> {
+        self.placement
+            .load()
+            .get(&account_id)
+            .copied()
+            .filter(|shard_id| self.shards.contains_key(shard_id))
+    }
+
+    pub fn account_store(&self, account_id: u32) -> &Store {
+        self.destination(self.account_shard(account_id))
+    }
+
+    pub fn is_account_fenced(&self, account_id: u32) -> bool {
+        self.writes.lock().fenced.contains_key(&account_id)
+    }
+
+    pub fn has_placements(&self) -> bool {
+        !self.placement.load().is_empty()
+    }
+
+    // Persists a fence rejecting all writes to an account while it is being
+    // moved, nodes apply it with `reload_placement`. Returns the fence id.
+    pub async fn fence_account(&self, account_id: u32) -> trc::Result<u64> {
+        let fence_id = rand::random::<u64>();
+        let mut batch = BatchBuilder::new();
+        batch.set(ValueClass::AccountFence(account_id), fence_id.serialize());
+        backend!(&self.primary, |store| store.write(batch.build_all()).await)
+            .caused_by(trc::location!())?;
+
+        Ok(fence_id)
+    }
+
+    // Removes the fence of an account and the acknowledgements of all nodes
+    pub async fn unfence_account(&self, account_id: u32) -> trc::Result<()> {
+        let mut batch = BatchBuilder::new();
+        batch.clear(ValueClass::AccountFence(account_id));
+        backend!(&self.primary, |store| store
+            .iterate(
+                IterateParams::new(
+                    ValueKey::from(ValueClass::AccountFenceAck(account_id, 0)),
+                    ValueKey::from(ValueClass::AccountFenceAck(account_id, u16::MAX)),
+                )
+                .no_values(),
+                |key, _| {
+                    if key.len() == ACCOUNT_FENCE_LEN + U16_LEN {
+                        batch.clear(ValueClass::AccountFenceAck(
+                            account_id,
+                            key.deserialize_be_u16(ACCOUNT_FENCE_LEN)?,
+                        ));
+                    }
+                    Ok(true)
+                },
+            )
+            .await)
+        .caused_by(trc::location!())?;
+        backend!(&self.primary, |store| store.write(batch.build_all()).await)
+            .caused_by(trc::location!())?;
+
+        self.reload_placement().await
+    }
+
+    // Acknowledges the account fences applied by this node
+    pub async fn acknowledge_fences(&self, node_id: u16) -> trc::Result<()> {
+        let fenced = self.writes.lock().fenced.clone();
+        if fenced.is_empty() {
+            return Ok(());
+        }
+
+        let mut batch = BatchBuilder::new();
+        for (account_id, fence_id) in fenced {
+            batch.set(
+                ValueClass::AccountFenceAck(account_id, node_id),
+                fence_id.serialize(),
+            );
+        }
+        backend!(&self.primary, |store| store.write(batch.build_all()).await)
+            .caused_by(trc::location!())
+            .map(|_| ())
+    }
+
+    // Returns whether the given nodes have stopped writing to an account
+    pub async fn is_fence_acknowledged(
+        &self,
+        account_id: u32,
+        fence_id: u64,
+        node_ids: impl IntoIterator<Item = u16>,
+    ) -> trc::Result<bool> {
+        for node_id in node_ids {
+            if backend!(&self.primary, |store| store
+                .get_value::<u64>(ValueKey::from(ValueClass::AccountFenceAck(
+                    account_id, node_id,
+                )))
+                .await)
+            .caused_by(trc::location!())?
+                != Some(fence_id)
+            {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    // Copies the account data from its current store to the target store,
+    // returns the number of keys written
+    pub async fn sync_account(&self, account_id: u32, to: Option<u64>) -> trc::Result<u64> {
+        let target = self.target_store(to)?;
+        let source = self.account_store(account_id);
+        if source.is_same(target) {
+            return Ok(0);
+        }
+
+        StoreMigration::new(source.clone(), target.clone())
+            .sync_account(account_id)
+            .await
+            .caused_by(trc::location!())
+    }
+
+    // Performs a final synchronization and switches the account placement,
+    // the account must be fenced. Returns the number of keys written.
+    pub async fn commit_move(&self, account_id: u32, to: Option<u64>) -> trc::Result<u64> {
+        if !self.is_account_fenced(account_id) {
+            return Err(trc::StoreEvent::UnexpectedError
+                .into_err()
+                .details("Account must be fenced before committing a move")
+                .account_id(account_id));
+        }
+
+        let keys_written = self.sync_account(account_id, to).await?;
+        let from = self.account_shard(account_id);
+        if from == to {
+            return Ok(keys_written);
+        }
+
+        let mut batch = BatchBuilder::new();
+        if let Some(from) = from {
+            batch.clear(placement_class(account_id, from));
+        }
+        if let Some(to) = to {
+            batch.set(placement_class(account_id, to), vec![]);
+        }
+        backend!(&self.primary, |store| store.write(batch.build_all()).await)
+            .caused_by(trc::location!())?;
+
+        let mut placement = self.placement.load().as_ref().clone();
+        if let Some(to) = to {
+            placement.insert(account_id, to);
+        } else {
+            placement.remove(&account_id);
+        }
+        self.placement.store(Arc::new(placement));
+
+        Ok(keys_written)
+    }
+
+    // Deletes the account data left behind on a store after a move
+    pub async fn purge_account(&self, account_id: u32, shard_id: Option<u64>) -> trc::Result<()> {
+        if self.account_shard(account_id) == shard_id {
+            return Err(trc::StoreEvent::UnexpectedError
+                .into_err()
+                .details("Cannot purge the store an account is placed on")
+                .account_id(account_id));
+        }
+
+        let store = self.target_store(shard_id)?;
+        for (subspace, from_key, to_key) in account_key_ranges(account_id) {
+            let mut keys = Vec::new();
+            backend!(store, |store| store
+                .iterate(
+                    IterateParams::new(
+                        AnyKey {
+                            subspace,
+                            key: from_key,
+                        },
+                        AnyKey {
+                            subspace,
+                            key: to_key,
+                        },
+                    )
+                    .no_values(),
+                    |key, _| {
+                        if key_account_id(subspace, key) == Some(account_id) {
+                            keys.push(key.to_vec());
+                        }
+                        Ok(true)
+                    },
+                )
+                .await)
+            .caused_by(trc::location!())?;
+
+            for keys in keys.chunks(1000) {
+                let mut ops = keys
+                    .iter()
+                    .map(|key| Operation::Value {
+                        class: ValueClass::Any(AnyClass {
+                            subspace,
+                            key: key.clone(),
+                        }),
+                        op: ValueOp::Clear,
+                    })
+                    .collect::<Vec<_>>();
+                backend!(store, |store| store
+                    .write(Batch {
+                        changes: &VecMap::new(),
+                        ops: &mut ops,
+                    })
+                    .await)
+                .caused_by(trc::location!())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Removes the placement of a destroyed account
+    pub async fn remove_placement(&self, account_id: u32) -> trc::Result<()> {
+        if let Some(shard_id) = self.placement.load().get(&account_id).copied() {
+            let mut ops = vec![Operation::Value {
+                class: placement_class(account_id, shard_id),
+                op: ValueOp::Clear,
+            }];
+            backend!(&self.primary, |store| store
+                .write(Batch {
+                    changes: &VecMap::new(),
+                    ops: &mut ops,
+                })
+                .await)
+            .caused_by(trc::location!())?;
+
+            let mut placement = self.placement.load().as_ref().clone();
+            placement.remove(&account_id);
+            self.placement.store(Arc::new(placement));
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_blob(&self, key: &[u8], range: Range<usize>) -> trc::Result<Option<Vec<u8>>> {
+        backend!(&self.primary, |store| store.get_blob(key, range).await)
+    }
+
+    pub async fn put_blob(&self, key: &[u8], data: &[u8]) -> trc::Result<()> {
+        backend!(&self.primary, |store| store.put_blob(key, data).await)
+    }
+
+    pub async fn delete_blob(&self, key: &[u8]) -> trc::Result<bool> {
+        backend!(&self.primary, |store| store.delete_blob(key).await)
+    }
+
+    pub async fn get_value<U>(&self, key: impl Key) -> trc::Result<Option<U>>
+    where
+        U: Deserialize + 'static,
+    {
+        let store = self.key_store(key.subspace(), &key.serialize(0));
+        backend!(store, |store| store.get_value(key).await)
+    }
+
+    pub(crate) async fn key_exists(&self, key: impl Key) -> trc::Result<bool> {
+        let store = self.key_store(key.subspace(), &key.serialize(0));
+        backend!(store, |store| store.key_exists(key).await)
+    }
+
+    pub async fn get_counter(
+        &self,
+        key: impl Into<ValueKey<ValueClass>> + Sync + Send,
+    ) -> trc::Result<i64> {
+        let key = key.into();
+        let store = self.key_store(key.subspace(), &key.serialize(0));
+        backend!(store, |store| store.get_counter(key).await)
+    }
+
+    // Ranges spanning several accounts are read from every store, merging
+    // the keys of each store in order
+    pub async fn iterate<T: Key>(
+        &self,
+        params: IterateParams<T>,
+        mut cb: impl for<'x> FnMut(&'x [u8], &'x [u8]) -> trc::Result<bool> + Sync + Send,
+    ) -> trc::Result<()> {
+        let subspace = params.begin.subspace();
+        let begin = params.begin.serialize(0);
+        let end = params.end.serialize(0);
+        if let Some(store) = self.range_store(subspace, &begin, &end) {
+            return backend!(store, |store| store.iterate(params, cb).await);
+        }
+
+        let limit = if params.first { 1 } else { PAGE_SIZE };
+        let mut cursors = self
+            .stores()
+            .map(|(shard_id, store)| StoreCursor {
+                shard_id,
+                store,
+                entries: VecDeque::new(),
+                last_key: None,
+                is_done: false,
+            })
+            .collect::<Vec<_>>();
+
+        loop {
+            for cursor in &mut cursors {
+                while cursor.entries.is_empty() && !cursor.is_done {
+                    self.read_page(
+                        cursor,
+                        subspace,
+                        &begin,
+                        &end,
+                        params.ascending,
+                        params.values,
+                        limit,
+                    )
+                    .await?;
+                }
+            }
+
+            let Some(cursor) = cursors
+                .iter_mut()
+                .filter(|cursor| !cursor.entries.is_empty())
+                .reduce(|next, cursor| {
+                    let (key, next_key) = (&cursor.entries[0].0, &next.entries[0].0);
+                    if (params.ascending && key < next_key) || (!params.ascending && key > next_key)
+                    {
+                        cursor
+                    } else {
+                        next
+                    }
+                })
+            else {
+                return Ok(());
+            };
+
+            let (key, value) = cursor.entries.pop_front().unwrap();
+            if !cb(&key, &value)? || params.first {
+                return Ok(());
+            }
+        }
+    }
+
+    // Reads the next keys of a store that belong to it, resuming after the
+    // last key read
+    #[allow(clippy::too_many_arguments)]
+    async fn read_page(
+        &self,
+        cursor: &mut StoreCursor<'_>,
+        subspace: u8,
+        begin: &[u8],
+        end: &[u8],
+        ascending: bool,
+        values: bool,
+        limit: usize,
+    ) -> trc::Result<()> {
+        let (from_key, to_key) = match &cursor.last_key {
+            Some(last_key) if ascending => (last_key.clone(), end.to_vec()),
+            Some(last_key) => (begin.to_vec(), last_key.clone()),
+            None => (begin.to_vec(), end.to_vec()),
+        };
+        let shard_id = cursor.shard_id;
+        let skip_key = cursor.last_key.take();
+        let entries = &mut cursor.entries;
+        let mut last_key = None;
+        let mut keys_read = 0;
+        let mut is_done = true;
+
+        backend!(cursor.store, |store| store
+            .iterate(
+                IterateParams {
+                    begin: AnyKey {
+                        subspace,
+                        key: from_key,
+                    },
+                    end: AnyKey {
+                        subspace,
+                        key: to_key,
+                    },
+                    first: false,
+                    ascending,
+                    values,
+                },
+                |key, value| {
+                    if skip_key.as_deref() == Some(key) {
+                        return Ok(true);
+                    }
+
+                    if self.key_shard(subspace, key) == shard_id {
+                        entries.push_back((key.to_vec(), value.to_vec()));
+                    }
+                    keys_read += 1;
+
+                    if entries.len() < limit && keys_read < PAGE_SIZE {
+                        Ok(true)
+                    } else {
+                        last_key = Some(key.to_vec());
+                        is_done = false;
+                        Ok(false)
+                    }
+                },
+            )
+            .await)?;
+
+        cursor.last_key = last_key;
+        cursor.is_done = is_done;
+
+        Ok(())
+    }
+
+    pub async fn delete_range(&self, from: impl Key, to: impl Key) -> trc::Result<()> {
+        if let Some(store) = self.range_store(from.subspace(), &from.serialize(0), &to.serialize(0))
+        {
+            return backend!(store, |store| store.delete_range(from, to).await);
+        }
+
+        for (_, store) in self.stores() {
+            backend!(store, |store| store
+                .delete_range(from.clone(), to.clone())
+                .await)?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn purge_store(&self) -> trc::Result<()> {
+        for (_, store) in self.stores() {
+            backend!(store, |store| store.purge_store().await)?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn create_tables(&self) -> trc::Result<()> {
+        for (_, store) in self.stores() {
+            Box::pin(store.create_tables()).await?;
+        }
+
+        Ok(())
+    }
+
+    // Writes a batch to the store holding the accounts it modifies, keys shared
+    // by all accounts are written to the primary store. Batches modifying accounts
+    // placed on different stores or asserting values on both stores are rejected.
+    pub async fn write(&self, batch: Batch<'_>) -> trc::Result<AssignedIds> {
+        let mut is_account_op = Vec::with_capacity(batch.ops.len());
+        let mut account_ids = batch
+            .changes
+            .iter()
+            .filter(|(_, changes)| !is_share_notification(changes))
+            .map(|(account_id, _)| *account_id)
+            .collect::<Vec<_>>();
+        let mut account_id = None;
+        let mut collection = 0u8;
+        let mut document_id = 0u32;
+
+        for op in batch.ops.iter() {
+            let is_account = match op {
+                Operation::AccountId { account_id: id } => {
+                    account_id = Some(*id);
+                    None
+                }
+                Operation::Collection { collection: c } => {
+                    collection = u8::from(*c);
+                    None
+                }
+                Operation::DocumentId { document_id: id } => {
+                    document_id = *id;
+                    None
+                }
+                Operation::Value { class, .. } | Operation::AssertValue { class, .. } => {
+                    let subspace = class.subspace(collection);
+                    let key =
+                        class.serialize(account_id.unwrap_or_default(), collection, document_id, 0);
+                    if let Some(key_account_id) = key_account_id(subspace, &key) {
+                        account_ids.push(key_account_id);
+                        Some(true)
+                    } else {
+                        Some(false)
+                    }
+                }
+                Operation::Index { .. } | Operation::Log { .. } => {
+                    account_ids.push(account_id.unwrap_or_default());
+                    Some(true)
+                }
+            };
+            is_account_op.push(is_account);
+        }
+
+        // Placements are read once the write is registered, moves wait for it
+        account_ids.sort_unstable();
+        account_ids.dedup();
+        let in_flight = self.begin_write(account_ids)?;
+        let mut account_shard = None;
+        for (pos, &account_id) in in_flight.account_ids.iter().enumerate() {
+            let shard_id = self.account_shard(account_id);
+            if pos == 0 {
+                account_shard = shard_id;
+            } else if shard_id != account_shard {
+                return Err(trc::StoreEvent::NotSupported
+                    .into_err()
+                    .details("Batch modifies accounts placed on different data stores")
+                    .account_id(account_id)
+                    .caused_by(trc::location!()));
+            }
+        }
+
+        // Share notifications do not change the notified account when it is
+        // placed on another store
+        let has_shared_keys = account_shard.is_some() && is_account_op.contains(&Some(false));
+        let has_foreign_changes = batch.changes.iter().any(|(account_id, changes)| {
+            is_share_notification(changes) && self.account_shard(*account_id) != account_shard
+        });
+        if !has_shared_keys && !has_foreign_changes {
+            return backend!(self.destination(account_shard), |store| store
+                .write(batch)
+                .await);
+        }
+
+        let mut account_batch = ShardBatch::default();
+        let mut shared_batch = ShardBatch::default();
+        for (&change_account_id, changes) in batch.changes.iter() {
+            if !is_share_notification(changes) {
+                account_batch
+                    .changes
+                    .append(change_account_id, ChangedCollection::default());
+            }
+        }
+
+        let mut account_id = None;
+        let mut collection = None;
+        let mut document_id = None;
+        for (op, is_account) in batch.ops.iter_mut().zip(is_account_op) {
+            let op = std::mem::replace(op, Operation::AccountId { account_id: 0 });
+            match op {
+                Operation::AccountId { account_id: id } => {
+                    account_id = Some(id);
+                }
+                Operation::Collection { collection: c } => {
+                    collection = Some(u8::from(c));
+                }
+                Operation::DocumentId { document_id: id } => {
+                    document_id = Some(id);
+                }
+                op if is_account == Some(true) || account_shard.is_none() => {
+                    account_batch.with_context(account_id, collection, document_id);
+                    account_batch.ops.push(op);
+                }
+                op => {
+                    // Shared keys are written to the primary store without context
+                    let collection = collection.unwrap_or_default();
+                    let document_id = document_id.unwrap_or_default();
+                    let account_id = account_id.unwrap_or_default();
+                    let detach = |class: ValueClass| {
+                        ValueClass::Any(AnyClass {
+                            subspace: class.subspace(collection),
+                            key: class.serialize(account_id, collection, document_id, 0),
+                        })
+                    };
+
+                    match op {
+                        Operation::Value { class, op } => {
+                            if matches!(op, ValueOp::SetFnc(_)) {
+                                shared_batch
+                                    .detached
+                                    .push((shared_batch.ops.len(), account_id));
+                            }
+                            shared_batch.ops.push(Operation::Value {
+                                class: detach(class),
+                                op,
+                            });
+                        }
+                        Operation::AssertValue {
+                            class,
+                            assert_value,
+                        } => {
+                            shared_batch.ops.push(Operation::AssertValue {
+                                class: detach(class),
+                                assert_value,
+                            });
+                        }
+                        op => {
+                            shared_batch.ops.push(op);
+                        }
+                    }
+                }
+            }
+        }
+
+        // Assertions are only checked by the store they are written to, the half holding
+        // them is written first so the other half can only fail with errors that the
+        // backends retry. Shared keys that reference change ids need the account half first.
+        let account_asserts = account_batch.has_assertions();
+        let shared_asserts = shared_batch.has_assertions();
+        if (account_asserts && shared_asserts)
+            || (shared_asserts && !shared_batch.detached.is_empty())
+        {
+            return Err(trc::StoreEvent::NotSupported
+                .into_err()
+                .details("Batch asserts values on more than one data store")
+                .caused_by(trc::location!()));
+        }
+
+        if shared_asserts {
+            let shared_ids = self
+                .write_shared(&mut shared_batch, &mut AssignedIds::default())
+                .await?;
+            let mut result = backend!(self.destination(account_shard), |store| store
+                .write(Batch {
+                    changes: &account_batch.changes,
+                    ops: &mut account_batch.ops,
+                })
+                .await)?;
+            result.ids.extend(shared_ids.ids);
+
+            Ok(result)
+        } else {
+            let mut result = backend!(self.destination(account_shard), |store| store
+                .write(Batch {
+                    changes: &account_batch.changes,
+                    ops: &mut account_batch.ops,
+                })
+                .await)?;
+            if !shared_batch.ops.is_empty() {
+                let shared_ids = self.write_shared(&mut shared_batch, &mut result).await?;
+                result.ids.extend(shared_ids.ids);
+            }
+
+            Ok(result)
+        }
+    }
+
+    // Writes the keys shared by all accounts to the primary store, values that
+    // depend on a change id are built from the ids assigned by the account store
+    async fn write_shared(
+        &self,
+        shared_batch: &mut ShardBatch,
+        account_ids: &mut AssignedIds,
+    ) -> trc::Result<AssignedIds> {
+        for (pos, account_id) in std::mem::take(&mut shared_batch.detached) {
+            if let Operation::Value { op, .. } = &mut shared_batch.ops[pos]
+                && let ValueOp::SetFnc(set_op) = &*op
+            {
+                let _ = account_ids.set_current_change_id(account_id);
+                let value = (set_op.fnc)(&set_op.params, account_ids)?;
+                *op = ValueOp::Set(value);
+            }
+        }
+
+        backend!(&self.primary, |store| store
+            .write(Batch {
+                changes: &shared_batch.changes,
+                ops: &mut shared_batch.ops,
+            })
+            .await)
+    }
+
+    // Registers a write to the given accounts so that fencing waits for it to
+    // complete, fails if any of them is fenced
+    fn begin_write(&self, account_ids: Vec<u32>) -> trc::Result<InFlightWrite<'_>> {
+        let mut writes = self.writes.lock();
+        if let Some(account_id) = account_ids
+            .iter()
+            .find(|account_id| writes.fenced.contains_key(account_id))
+        {
+            return Err(trc::StoreEvent::WriteFenced
+                .into_err()
+                .account_id(*account_id)
+                .caused_by(trc::location!()));
+        }
+
+        for account_id in &account_ids {
+            *writes.in_flight.entry(*account_id).or_default() += 1;
+        }
+
+        Ok(InFlightWrite {
+            store: self,
+            account_ids,
+        })
+    }
+
+    fn has_fenced_writes(&self) -> bool {
+        let writes = self.writes.lock();
+        writes
+            .fenced
+            .keys()
+            .any(|account_id| writes.in_flight.contains_key(account_id))
+    }
+
+    fn target_store(&self, shard_id: Option<u64>) -> trc::Result<&Store> {
+        match shard_id {
+            Some(shard_id) => self.shards.get(&shard_id).ok_or_else(|| {
+                trc::StoreEvent::NotConfigured
+                    .into_err()
+                    .details("Data store shard is not loaded")
+                    .id(shard_id)
+            }),
+            None => Ok(&self.primary),
+        }
+    }
+
+    fn destination(&self, shard_id: Option<u64>) -> &Store {
+        shard_id
+            .and_then(|shard_id| self.shards.get(&shard_id))
+            .unwrap_or(&self.primary)
+    }
+
+    fn stores(&self) -> impl Iterator<Item = (Option<u64>, &Store)> {
+        [(None, &self.primary)].into_iter().chain(
+            self.shards
+                .iter()
+                .map(|(shard_id, store)| (Some(*shard_id), store)),
+        )
+    }
+
+    fn key_shard(&self, subspace: u8, key: &[u8]) -> Option<u64> {
+        key_account_id(subspace, key).and_then(|account_id| self.account_shard(account_id))
+    }
+
+    fn key_store(&self, subspace: u8, key: &[u8]) -> &Store {
+        self.destination(self.key_shard(subspace, key))
+    }
+
+    // Returns the store holding a range, None if it spans several stores
+    fn range_store(&self, subspace: u8, begin: &[u8], end: &[u8]) -> Option<&Store> {
+        if self.shards.is_empty() {
+            return Some(&self.primary);
+        }
+
+        match (
+            account_key_offset(subspace, begin),
+            account_key_offset(subspace, end),
+        ) {
+            (Some(offset), Some(end_offset))
+                if offset == end_offset && begin.get(..offset) == end.get(..offset) =>
+            {
+                let account_id = begin.deserialize_be_u32(offset).ok()?;
+                (range_end_account(end, offset)? == account_id)
+                    .then(|| self.account_store(account_id))
+            }
+            (None, None) if is_shared_range(subspace, begin, end) => Some(&self.primary),
+            _ => None,
+        }
+    }
+}
+
+struct StoreCursor<'x> {
+    shard_id: Option<u64>,
+    store: &'x Store,
+    entries: VecDeque<(Vec<u8>, Vec<u8>)>,
+    last_key: Option<Vec<u8>>,
+    is_done: bool,
+}
+
+impl Drop for InFlightWrite<'_> {
+    fn drop(&mut self) {
+        let mut writes = self.store.writes.lock();
+        for account_id in &self.account_ids {
+            if let Some(count) = writes.in_flight.get_mut(account_id) {
+                *count -= 1;
+                if *count == 0 {
+                    writes.in_flight.remove(account_id);
+                }
+            }
+        }
+    }
+}
+
+impl ShardBatch {
+    fn has_assertions(&self) -> bool {
+        self.ops
+            .iter()
+            .any(|op| matches!(op, Operation::AssertValue { .. }))
+    }
+
+    fn with_context(
+        &mut self,
+        account_id: Option<u32>,
+        collection: Option<u8>,
+        document_id: Option<u32>,
+    ) {
+        if let Some(account_id) = account_id
+            && self.account_id != Some(account_id)
+        {
+            self.account_id = Some(account_id);
+            self.ops.push(Operation::AccountId { account_id });
+        }
+        if let Some(collection) = collection
+            && self.collection != Some(collection)
+        {
+            self.collection = Some(collection);
+            self.ops.push(Operation::Collection {
+                collection: collection.into(),
+            });
+        }
+        if let Some(document_id) = document_id
+            && self.document_id != Some(document_id)
+        {
+            self.document_id = Some(document_id);
+            self.ops.push(Operation::DocumentId { document_id });
+        }
+    }
+}
+
+// Changes only holding a share notification, these are written by the
+// sharing account
+fn is_share_notification(changes: &ChangedCollection) -> bool {
+    changes.share_notification_id.is_some()
+        && changes.changed_containers.is_empty()
+        && changes.changed_items.is_empty()
+}
+
+// Returns whether a range only holds keys shared by all accounts, called
+// when neither end has an account id
+fn is_shared_range(subspace: u8, begin: &[u8], end: &[u8]) -> bool {
+    match subspace {
+        SUBSPACE_LOGS => begin.len() > U32_LEN && begin.get(..=U32_LEN) == end.get(..=U32_LEN),
+        SUBSPACE_SEARCH_INDEX => !begin.is_empty() && begin.first() == end.first(),
+        SUBSPACE_PROPERTY | SUBSPACE_INDEXES | SUBSPACE_COUNTER | SUBSPACE_ACL
+        | SUBSPACE_BLOB_LINK => false,
+        _ => true,
+    }
+}
+
+// Range ends are usually the first key of the next account
+fn range_end_account(key: &[u8], offset: usize) -> Option<u32> {
+    let account_id = key.deserialize_be_u32(offset).ok()?;
+    if key[offset + U32_LEN..].iter().all(|byte| *byte == 0) {
+        account_id.checked_sub(1)
+    } else {
+        Some(account_id)
+    }
+}
+
+fn placement_class(account_id: u32, shard_id: u64) -> ValueClass {
+    ValueClass::Registry(RegistryClass::Reference {
+        to_object_id: REG_DATA_STORE_SHARD,
+        to_item_id: shard_id,
+        from_object_id: REG_ACCOUNT,
+        from_item_id: account_id as u64,
+    })
+}
@@ -0,0 +1,294 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: LicenseRef-SEL
+ *
+ * This file is subject to the Stalwart Enterprise License Agreement (SEL) and
+ * is NOT open source software.
+ *
+ */
+
+use crate::{
+    BlobStore, IterateParams, Store, U64_LEN, ValueKey,
+    write::{
+        BatchBuilder, BlobOp, ValueClass, assert::AssertValue, key::DeserializeBigEndian, now,
+    },
+};
+use registry::schema::structs::TieredBlobStore;
+use std::{ops::Range, sync::Arc, time::Instant};
+use trc::{AddContext, StoreEvent};
+use types::blob_hash::{BLOB_HASH_LEN, BlobHash};
+
+// Appended to the commit timestamp once a blob has been moved to the cold tier
+const COLD_TIER_MARKER: u8 = 1;
+
+pub struct TieredBlob {
+    pub hot: BlobStore,
+    pub cold: BlobStore,
+    pub migrate_after: Option<u64>,
+}
+
+impl TieredBlob {
+    pub async fn open(config: TieredBlobStore) -> Result<BlobStore, String> {
+        let hot = BlobStore::open(config.hot).await?;
+        let cold = BlobStore::open(config.cold).await?;
+        if hot.is_composite() || cold.is_composite() {
+            return Err("Tiered blob stores cannot contain composite blob stores".to_string());
+        }
+
+        Ok(BlobStore::Tiered(Arc::new(TieredBlob {
+            hot,
+            cold,
+            migrate_after: Some(config.migrate_after.as_secs()),
+        })))
+    }
+
+    // Keeps both tiers readable while no longer moving blobs to the cold tier
+    pub fn without_migration(&self) -> BlobStore {
+        BlobStore::Tiered(Arc::new(TieredBlob {
+            hot: self.hot.clone(),
+            cold: self.cold.clone(),
+            migrate_after: None,
+        }))
+    }
+
+    pub async fn get_blob(
+        &self,
+        key: &[u8],
+        read_range: Range<usize>,
+    ) -> trc::Result<Option<Vec<u8>>> {
+        if let Some(data) = get_blob(&self.hot, key, read_range.clone()).await? {
+            trc::event!(Store(StoreEvent::BlobReadHot), Key = key, Size = data.len());
+            return Ok(Some(data));
+        }
+
+        let result = get_blob(&self.cold, key, read_range).await?;
+        if let Some(data) = &result {
+            trc::event!(
+                Store(StoreEvent::BlobReadCold),
+                Key = key,
+                Size = data.len()
+            );
+        }
+
+        Ok(result)
+    }
+
+    pub async fn put_blob(&self, key: &[u8], data: &[u8]) -> trc::Result<()> {
+        put_blob(&self.hot, key, data).await
+    }
+
+    pub async fn delete_blob(&self, key: &[u8]) -> trc::Result<bool> {
+        let deleted_hot = delete_blob(&self.hot, key).await?;
+        let deleted_cold = delete_blob(&self.cold, key).await?;
+
+        Ok(deleted_hot || deleted_cold)
+    }
+
+    // Returns the tier holding a blob, new blobs are written to the hot tier
+    pub async fn stored_tier(&self, key: &[u8]) -> trc::Result<&BlobStore> {
+        if get_blob(&self.hot, key, 0..1).await?.is_none()
+            && get_blob(&self.cold, key, 0..1).await?.is_some()
+        {
+            Ok(&self.cold)
+        } else {
+            Ok(&self.hot)
+        }
+    }
+
+    pub async fn migrate_blobs(&self, store: &Store, shard_index: u8) -> trc::Result<()> {
+        let Some(migrate_after) = self.migrate_after else {
+            return Ok(());
+        };
+        let started = Instant::now();
+        let cutoff = now().saturating_sub(migrate_after);
+
+        // Find committed blobs older than the cutoff that are still in the hot tier
+        let mut from_hash = BlobHash::default();
+        let mut to_hash = BlobHash::new_max();
+        from_hash.0[0] = shard_index;
+        to_hash.0[0] = shard_index;
+        let mut candidates = Vec::new();
+        store
+            .iterate(
+                IterateParams::new(
+                    ValueKey::from(ValueClass::Blob(BlobOp::Commit { hash: from_hash })),
+                    ValueKey::from(ValueClass::Blob(BlobOp::Commit { hash: to_hash })),
+                )
+                .ascending(),
+                |key, value| {
+                    if key.len() == BLOB_HASH_LEN && value.get(U64_LEN) != Some(&COLD_TIER_MARKER) {
+                        // Blobs committed before timestamps were recorded are considered old
+                        let committed_at = if value.len() >= U64_LEN {
+                            value.deserialize_be_u64(0)?
+                        } else {
+                            0
+                        };
+
+                        if committed_at <= cutoff {
+                            candidates.push((
+                                BlobHash::try_from_hash_slice(key).unwrap(),
+                                committed_at,
+                                xxhash_rust::xxh3::xxh3_64(value),
+                            ));
+                        }
+                    }
+
+                    Ok(true)
+                },
+            )
+            .await
+            .caused_by(trc::location!())?;
+
+        // Move blobs to the cold tier
+        let mut total_migrated = 0;
+        for (hash, committed_at, value_hash) in candidates {
+            self.migrate_blob(hash.as_ref())
+                .await
+                .caused_by(trc::location!())?;
+
+            let mut value = Vec::with_capacity(U64_LEN + 1);
+            value.extend_from_slice(&committed_at.to_be_bytes());
+            value.push(COLD_TIER_MARKER);
+            let class = ValueClass::Blob(BlobOp::Commit { hash: hash.clone() });
+            let mut batch = BatchBuilder::new();
+            batch
+                .assert_value(class.clone(), AssertValue::Hash(value_hash))
+                .set(class, value);
+
+            match store.write(batch.build_all()).await {
+                Ok(_) => {
+                    total_migrated += 1;
+                }
+                Err(err) if err.matches(trc::EventType::Store(StoreEvent::AssertValueFailed)) => {
+                    // The blob was purged while it was being migrated
+                    if !store.blob_exists(&hash).await.caused_by(trc::location!())? {
+                        delete_blob(&self.cold, hash.as_ref())
+                            .await
+                            .caused_by(trc::location!())?;
+                    }
+                }
+                Err(err) => return Err(err.caused_by(trc::location!())),
+            }
+        }
+
+        trc::event!(
+            Store(StoreEvent::BlobStoreMigrated),
+            Id = shard_index as u16,
+            Total = total_migrated,
+            Elapsed = started.elapsed()
+        );
+
+        Ok(())
+    }
+
+    async fn migrate_blob(&self, key: &[u8]) -> trc::Result<()> {
+        let started = Instant::now();
+
+        // Blobs are copied as stored, without decompressing them
+        if let Some(data) = get_blob(&self.hot, key, 0..usize::MAX).await? {
+            put_blob(&self.cold, key, &data).await?;
+            delete_blob(&self.hot, key).await?;
+
+            trc::event!(
+                Store(StoreEvent::BlobMigrated),
+                Key = key,
+                Size = data.len(),
+                Elapsed = started.elapsed(),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[allow(unreachable_patterns)]
+async fn get_blob(
+    store: &BlobStore,
+    key: &[u8],
+    read_range: Range<usize>,
+) -> trc::Result<Option<Vec<u8>>> {
+    match store {
+        BlobStore::Store(store) => match store {
+            #[cfg(feature = "sqlite")]
+            Store::SQLite(store) => store.get_blob(key, read_range).await,
+            #[cfg(feature = "foundation")]
+            Store::FoundationDb(store) => store.get_blob(key, read_range).await,
+            #[cfg(feature = "postgres")]
+            Store::PostgreSQL(store) => store.get_blob(key, read_range).await,
+            #[cfg(feature = "mysql")]
+            Store::MySQL(store) => store.get_blob(key, read_range).await,
+            #[cfg(feature = "rocks")]
+            Store::RocksDb(store) => store.get_blob(key, read_range).await,
+            Store::Ephemeral(store) => store.get_blob(key, read_range).await,
+            #[cfg(any(feature = "postgres", feature = "mysql"))]
+            Store::SQLReadReplica(store) => store.get_blob(key, read_range).await,
+            Store::Sharded(store) => store.get_blob(key, read_range).await,
+            Store::None => Err(trc::StoreEvent::NotConfigured.into()),
+        },
+        BlobStore::Fs(store) => store.get_blob(key, read_range).await,
+        #[cfg(feature = "s3")]
+        BlobStore::S3(store) => store.get_blob(key, read_range).await,
+        #[cfg(feature = "azure")]
+        BlobStore::Azure(store) => store.get_blob(key, read_range).await,
+        BlobStore::Sharded(_) | BlobStore::Tiered(_) => Err(trc::StoreEvent::NotSupported.into()),
+    }
+}
+
+#[allow(unreachable_patterns)]
+async fn put_blob(store: &BlobStore, key: &[u8], data: &[u8]) -> trc::Result<()> {
+    match store {
+        BlobStore::Store(store) => match store {
+            #[cfg(feature = "sqlite")]
+            Store::SQLite(store) => store.put_blob(key, data).await,
+            #[cfg(feature = "foundation")]
+            Store::FoundationDb(store) => store.put_blob(key, data).await,
+            #[cfg(feature = "postgres")]
+            Store::PostgreSQL(store) => store.put_blob(key, data).await,
+            #[cfg(feature = "mysql")]
+            Store::MySQL(store) => store.put_blob(key, data).await,
+            #[cfg(feature = "rocks")]
+            Store::RocksDb(store) => store.put_blob(key, data).await,
+            Store::Ephemeral(store) => store.put_blob(key, data).await,
+            #[cfg(any(feature = "postgres", feature = "mysql"))]
+            Store::SQLReadReplica(store) => store.put_blob(key, data).await,
+            Store::Sharded(store) => store.put_blob(key, data).await,
+            Store::None => Err(trc::StoreEvent::NotConfigured.into()),
+        },
+        BlobStore::Fs(store) => store.put_blob(key, data).await,
+        #[cfg(feature = "s3")]
+        BlobStore::S3(store) => store.put_blob(key, data).await,
+        #[cfg(feature = "azure")]
+        BlobStore::Azure(store) => store.put_blob(key, data).await,
+        BlobStore::Sharded(_) | BlobStore::Tiered(_) => Err(trc::StoreEvent::NotSupported.into()),
+    }
+}
+
+#[allow(unreachable_patterns)]
+async fn delete_blob(store: &BlobStore, key: &[u8]) -> trc::Result<bool> {
+    match store {
+        BlobStore::Store(store) => match store {
+            #[cfg(feature = "sqlite")]
+            Store::SQLite(store) => store.delete_blob(key).await,
+            #[cfg(feature = "foundation")]
+            Store::FoundationDb(store) => store.delete_blob(key).await,
+            #[cfg(feature = "postgres")]
+            Store::PostgreSQL(store) => store.delete_blob(key).await,
+            #[cfg(feature = "mysql")]
+            Store::MySQL(store) => store.delete_blob(key).await,
+            #[cfg(feature = "rocks")]
+            Store::RocksDb(store) => store.delete_blob(key).await,
+            Store::Ephemeral(store) => store.delete_blob(key).await,
+            #[cfg(any(feature = "postgres", feature = "mysql"))]
+            Store::SQLReadReplica(store) => store.delete_blob(key).await,
+            Store::Sharded(store) => store.delete_blob(key).await,
+            Store::None => Err(trc::StoreEvent::NotConfigured.into()),
+        },
+        BlobStore::Fs(store) => store.delete_blob(key).await,
+        #[cfg(feature = "s3")]
+        BlobStore::S3(store) => store.delete_blob(key).await,
+        #[cfg(feature = "azure")]
+        BlobStore::Azure(store) => store.delete_blob(key).await,
+        BlobStore::Sharded(_) | BlobStore::Tiered(_) => Err(trc::StoreEvent::NotSupported.into()),
+    }
+}
@@ -33,18 +33,24 @@ impl EphemeralStore {
 
     pub(crate) async fn put_blob(&self, key: &[u8], data: &[u8]) -> trc::Result<()> {
         let mut state = self.state.write();
+        let mut log = self.log_batch();
+        log.set(SUBSPACE_BLOBS, key, data);
         state
             .subspaces
             .entry(SUBSPACE_BLOBS)
             .or_default()
             .insert(key.to_vec(), data.to_vec());
-        Ok(())
+        self.append_log(log)
     }
 
     pub(crate) async fn delete_blob(&self, key: &[u8]) -> trc::Result<bool> {
         let mut state = self.state.write();
-        if let Some(map) = state.subspaces.get_mut(&SUBSPACE_BLOBS) {
-            map.remove(key);
+        if let Some(map) = state.subspaces.get_mut(&SUBSPACE_BLOBS)
+            && map.remove(key).is_some()
+        {
+            let mut log = self.log_batch();
+            log.delete(SUBSPACE_BLOBS, key);
+            self.append_log(log)?;
         }
         Ok(true)
     }
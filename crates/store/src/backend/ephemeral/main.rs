@@ -6,9 +6,10 @@
 
 use super::{EphemeralState, EphemeralStore};
 use crate::Store;
+use ::registry::schema::structs;
 use ahash::AHashMap;
 use parking_lot::RwLock;
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
 impl EphemeralStore {
     pub fn open() -> Store {
@@ -16,6 +17,19 @@ impl EphemeralStore {
             state: RwLock::new(EphemeralState {
                 subspaces: AHashMap::new(),
             }),
+            log: None,
         }))
     }
+
+    pub async fn open_with_config(config: structs::EphemeralStore) -> Result<Store, String> {
+        match config.path.filter(|path| !path.is_empty()) {
+            Some(path) => Self::open_persistent(
+                PathBuf::from(path),
+                config.snapshot_interval.into_inner(),
+                config.sync_writes,
+            )
+            .map(Store::Ephemeral),
+            None => Ok(Self::open()),
+        }
+    }
 }
@@ -0,0 +1,277 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{EphemeralState, EphemeralStore};
+use crate::{U32_LEN, U64_LEN};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+use xxhash_rust::xxh3::xxh3_64;
+
+const SNAPSHOT_FILE: &str = "snapshot";
+const SNAPSHOT_TMP_FILE: &str = "snapshot.tmp";
+const LOG_FILE: &str = "write.log";
+
+const RECORD_HEADER_LEN: usize = U32_LEN + U64_LEN;
+const SNAPSHOT_RECORD_SIZE: usize = 1024 * 1024;
+
+const OP_SET: u8 = 0;
+const OP_DELETE: u8 = 1;
+
+// Files are made of records, each one holding a list of set or delete
+// operations: [payload len u32][xxh3 of payload u64][payload]
+pub(crate) struct WriteLog {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    sync_writes: bool,
+}
+
+pub(crate) struct LogBatch {
+    ops: Option<Vec<u8>>,
+}
+
+impl LogBatch {
+    pub(crate) fn set(&mut self, subspace: u8, key: &[u8], value: &[u8]) {
+        if let Some(ops) = &mut self.ops {
+            write_op(ops, OP_SET, subspace, key, Some(value));
+        }
+    }
+
+    pub(crate) fn delete(&mut self, subspace: u8, key: &[u8]) {
+        if let Some(ops) = &mut self.ops {
+            write_op(ops, OP_DELETE, subspace, key, None);
+        }
+    }
+}
+
+impl EphemeralStore {
+    pub(crate) fn open_persistent(
+        path: PathBuf,
+        snapshot_interval: Duration,
+        sync_writes: bool,
+    ) -> Result<Arc<Self>, String> {
+        fs::create_dir_all(&path)
+            .map_err(|err| format!("Failed to create directory {}: {err}", path.display()))?;
+
+        // Load the last snapshot
+        let mut state = EphemeralState::default();
+        if let Some(data) = read_file(&path.join(SNAPSHOT_FILE))?
+            && state.replay(&data)? != data.len()
+        {
+            return Err(format!(
+                "Snapshot file {} is corrupted",
+                path.join(SNAPSHOT_FILE).display()
+            ));
+        }
+
+        // Replay the changes written since, a partially written record at the
+        // end of the log is the result of an interrupted write and is discarded
+        let log_path = path.join(LOG_FILE);
+        let log_size = match read_file(&log_path)? {
+            Some(data) => {
+                state.replay(&data)?;
+                data.len() as u64
+            }
+            None => 0,
+        };
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log_path)
+            .map_err(|err| format!("Failed to open {}: {err}", log_path.display()))?;
+
+        let store = Arc::new(EphemeralStore {
+            state: state.into(),
+            log: Some(
+                WriteLog {
+                    path,
+                    file,
+                    size: log_size,
+                    sync_writes,
+                }
+                .into(),
+            ),
+        });
+
+        // Compact the replayed changes into a new snapshot
+        store
+            .snapshot()
+            .map_err(|err| format!("Failed to write snapshot: {err}"))?;
+
+        if !snapshot_interval.is_zero() {
+            let store = Arc::downgrade(&store);
+            tokio::spawn(async move {
+                loop {
+                    tokio::time::sleep(snapshot_interval).await;
+                    let Some(store) = store.upgrade() else {
+                        break;
+                    };
+                    match tokio::task::spawn_blocking(move || store.snapshot()).await {
+                        Ok(Ok(())) => {}
+                        Ok(Err(err)) => {
+                            trc::error!(err.details("Failed to write ephemeral store snapshot"));
+                        }
+                        Err(_) => break,
+                    }
+                }
+            });
+        }
+
+        Ok(store)
+    }
+
+    #[inline(always)]
+    pub fn is_persistent(&self) -> bool {
+        self.log.is_some()
+    }
+
+    pub(crate) fn log_batch(&self) -> LogBatch {
+        LogBatch {
+            ops: self.log.is_some().then(Vec::new),
+        }
+    }
+
+    // Must be called while holding the state write lock, so the log order
+    // matches the order in which changes were applied.
+    pub(crate) fn append_log(&self, batch: LogBatch) -> trc::Result<()> {
+        if let (Some(log), Some(ops)) = (&self.log, batch.ops)
+            && !ops.is_empty()
+        {
+            let mut log = log.lock();
+            let mut record = Vec::with_capacity(RECORD_HEADER_LEN + ops.len());
+            write_record(&mut record, &ops).map_err(into_error)?;
+            log.file.write_all(&record).map_err(into_error)?;
+            if log.sync_writes {
+                log.file.sync_data().map_err(into_error)?;
+            }
+            log.size += record.len() as u64;
+        }
+        Ok(())
+    }
+
+    // Writes the full contents of the store to a new snapshot and truncates the log
+    pub fn snapshot(&self) -> trc::Result<()> {
+        let Some(log) = &self.log else {
+            return Ok(());
+        };
+        let state = self.state.read();
+        let mut log = log.lock();
+        if log.size == 0 {
+            return Ok(());
+        }
+
+        let tmp_path = log.path.join(SNAPSHOT_TMP_FILE);
+        let mut file = BufWriter::new(File::create(&tmp_path).map_err(into_error)?);
+        let mut ops = Vec::with_capacity(SNAPSHOT_RECORD_SIZE);
+        for (subspace, map) in &state.subspaces {
+            for (key, value) in map {
+                write_op(&mut ops, OP_SET, *subspace, key, Some(value.as_slice()));
+                if ops.len() >= SNAPSHOT_RECORD_SIZE {
+                    write_record(&mut file, &ops).map_err(into_error)?;
+                    ops.clear();
+                }
+            }
+        }
+        if !ops.is_empty() {
+            write_record(&mut file, &ops).map_err(into_error)?;
+        }
+        file.into_inner()
+            .map_err(|err| into_error(err.into_error()))?
+            .sync_all()
+            .map_err(into_error)?;
+        fs::rename(&tmp_path, log.path.join(SNAPSHOT_FILE)).map_err(into_error)?;
+        #[cfg(unix)]
+        File::open(&log.path)
+            .and_then(|dir| dir.sync_all())
+            .map_err(into_error)?;
+
+        log.file.set_len(0).map_err(into_error)?;
+        log.file.sync_all().map_err(into_error)?;
+        log.size = 0;
+
+        Ok(())
+    }
+}
+
+impl EphemeralState {
+    // Applies all valid records and returns the number of bytes read
+    fn replay(&mut self, data: &[u8]) -> Result<usize, String> {
+        let mut pos = 0;
+        while let Some(header) = data.get(pos..pos + RECORD_HEADER_LEN) {
+            let len = u32::from_le_bytes(header[..U32_LEN].try_into().unwrap()) as usize;
+            let hash = u64::from_le_bytes(header[U32_LEN..].try_into().unwrap());
+            let Some(ops) = data
+                .get(pos + RECORD_HEADER_LEN..pos + RECORD_HEADER_LEN + len)
+                .filter(|ops| xxh3_64(ops) == hash)
+            else {
+                break;
+            };
+            self.apply(ops)
+                .ok_or_else(|| format!("Invalid operation in record at offset {pos}"))?;
+            pos += RECORD_HEADER_LEN + len;
+        }
+        Ok(pos)
+    }
+
+    fn apply(&mut self, mut ops: &[u8]) -> Option<()> {
+        while let Some((&op, bytes)) = ops.split_first() {
+            let (&subspace, bytes) = bytes.split_first()?;
+            let (key, bytes) = read_bytes(bytes)?;
+            let map = self.subspaces.entry(subspace).or_default();
+            ops = match op {
+                OP_SET => {
+                    let (value, bytes) = read_bytes(bytes)?;
+                    map.insert(key.to_vec(), value.to_vec());
+                    bytes
+                }
+                OP_DELETE => {
+                    map.remove(key);
+                    bytes
+                }
+                _ => return None,
+            };
+        }
+        Some(())
+    }
+}
+
+fn write_op(ops: &mut Vec<u8>, op: u8, subspace: u8, key: &[u8], value: Option<&[u8]>) {
+    ops.push(op);
+    ops.push(subspace);
+    for bytes in [Some(key), value].into_iter().flatten() {
+        ops.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        ops.extend_from_slice(bytes);
+    }
+}
+
+fn read_bytes(bytes: &[u8]) -> Option<(&[u8], &[u8])> {
+    let len = u32::from_le_bytes(bytes.get(..U32_LEN)?.try_into().ok()?) as usize;
+    let value = bytes.get(U32_LEN..U32_LEN + len)?;
+    Some((value, &bytes[U32_LEN + len..]))
+}
+
+fn write_record(out: &mut impl Write, ops: &[u8]) -> io::Result<()> {
+    out.write_all(&(ops.len() as u32).to_le_bytes())?;
+    out.write_all(&xxh3_64(ops).to_le_bytes())?;
+    out.write_all(ops)
+}
+
+fn read_file(path: &Path) -> Result<Option<Vec<u8>>, String> {
+    match fs::read(path) {
+        Ok(data) => Ok(Some(data)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(format!("Failed to read {}: {err}", path.display())),
+    }
+}
+
+fn into_error(err: io::Error) -> trc::Error {
+    trc::StoreEvent::FilesystemError.reason(err)
+}
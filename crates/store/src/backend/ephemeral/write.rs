@@ -4,7 +4,7 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use super::EphemeralStore;
+use super::{EphemeralState, EphemeralStore, persist::LogBatch};
 use crate::{
     IndexKey, Key, LogKey, SUBSPACE_COUNTER, SUBSPACE_IN_MEMORY_COUNTER, SUBSPACE_INDEXES,
     SUBSPACE_LOGS, SUBSPACE_QUOTA,
@@ -14,6 +14,20 @@ use crate::{
 
 impl EphemeralStore {
     pub(crate) async fn write(&self, batch: Batch<'_>) -> trc::Result<AssignedIds> {
+        let mut state = self.state.write();
+        let mut log = self.log_batch();
+        let result = self.write_batch(&mut state, &mut log, batch);
+        self.append_log(log)?;
+        result
+    }
+
+    // Changes applied before an error are kept, as the store has no transactions
+    fn write_batch(
+        &self,
+        state: &mut EphemeralState,
+        log: &mut LogBatch,
+        batch: Batch<'_>,
+    ) -> trc::Result<AssignedIds> {
         let mut account_id = u32::MAX;
         let mut collection = u8::MAX;
         let mut document_id = u32::MAX;
@@ -21,8 +35,6 @@ impl EphemeralStore {
         let mut result = AssignedIds::default();
         let has_changes = !batch.changes.is_empty();
 
-        let mut state = self.state.write();
-
         if has_changes {
             let map = state.subspaces.entry(SUBSPACE_COUNTER).or_default();
             for &account_id in batch.changes.keys() {
@@ -31,7 +43,9 @@ impl EphemeralStore {
                     Some(bytes) => deserialize_i64_le(&key, bytes)? + 1,
                     None => 1,
                 };
-                map.insert(key, next.to_le_bytes().to_vec());
+                let value = next.to_le_bytes().to_vec();
+                log.set(SUBSPACE_COUNTER, &key, &value);
+                map.insert(key, value);
                 result.push_change_id(account_id, next as u64);
             }
         }
@@ -63,10 +77,13 @@ impl EphemeralStore {
 
                     match op {
                         ValueOp::Set(value) => {
-                            map.insert(key, std::mem::take(value));
+                            let value = std::mem::take(value);
+                            log.set(subspace, &key, &value);
+                            map.insert(key, value);
                         }
                         ValueOp::SetFnc(set_op) => {
                             let value = (set_op.fnc)(&set_op.params, &result)?;
+                            log.set(subspace, &key, &value);
                             map.insert(key, value);
                         }
                         ValueOp::MergeFnc(merge_op) => {
@@ -78,9 +95,11 @@ impl EphemeralStore {
 
                             match merge_result {
                                 MergeResult::Update(value) => {
+                                    log.set(subspace, &key, &value);
                                     map.insert(key, value);
                                 }
                                 MergeResult::Delete => {
+                                    log.delete(subspace, &key);
                                     map.remove(&key);
                                 }
                                 MergeResult::Skip => (),
@@ -91,8 +110,9 @@ impl EphemeralStore {
                                 Some(bytes) => deserialize_i64_le(&key, bytes)?,
                                 None => 0,
                             };
-                            let next = current + *by;
-                            map.insert(key, next.to_le_bytes().to_vec());
+                            let value = (current + *by).to_le_bytes().to_vec();
+                            log.set(subspace, &key, &value);
+                            map.insert(key, value);
                         }
                         ValueOp::AddAndGet(by) => {
                             let current = match map.get(&key) {
@@ -100,10 +120,13 @@ impl EphemeralStore {
                                 None => 0,
                             };
                             let next = current + *by;
-                            map.insert(key, next.to_le_bytes().to_vec());
+                            let value = next.to_le_bytes().to_vec();
+                            log.set(subspace, &key, &value);
+                            map.insert(key, value);
                             result.push_counter_id(next);
                         }
                         ValueOp::Clear => {
+                            log.delete(subspace, &key);
                             map.remove(&key);
                         }
                     }
@@ -119,8 +142,10 @@ impl EphemeralStore {
                     .serialize(0);
                     let map = state.subspaces.entry(SUBSPACE_INDEXES).or_default();
                     if *set {
+                        log.set(SUBSPACE_INDEXES, &index_key, &[]);
                         map.insert(index_key, Vec::new());
                     } else {
+                        log.delete(SUBSPACE_INDEXES, &index_key);
                         map.remove(&index_key);
                     }
                 }
@@ -132,7 +157,9 @@ impl EphemeralStore {
                     }
                     .serialize(0);
                     let map = state.subspaces.entry(SUBSPACE_LOGS).or_default();
-                    map.insert(log_key, std::mem::take(set));
+                    let value = std::mem::take(set);
+                    log.set(SUBSPACE_LOGS, &log_key, &value);
+                    map.insert(log_key, value);
                 }
                 Operation::AssertValue {
                     class,
@@ -162,20 +189,23 @@ impl EphemeralStore {
         let from_key = from.serialize(0);
         let to_key = to.serialize(0);
         let mut state = self.state.write();
+        let mut log = self.log_batch();
         if let Some(map) = state.subspaces.get_mut(&subspace) {
             let keys: Vec<Vec<u8>> = map
                 .range(from_key..to_key)
                 .map(|(k, _)| k.clone())
                 .collect();
             for k in keys {
+                log.delete(subspace, &k);
                 map.remove(&k);
             }
         }
-        Ok(())
+        self.append_log(log)
     }
 
     pub(crate) async fn purge_store(&self) -> trc::Result<()> {
         let mut state = self.state.write();
+        let mut log = self.log_batch();
         for subspace in [SUBSPACE_QUOTA, SUBSPACE_COUNTER, SUBSPACE_IN_MEMORY_COUNTER] {
             if let Some(map) = state.subspaces.get_mut(&subspace) {
                 let keys: Vec<Vec<u8>> = map
@@ -191,10 +221,11 @@ impl EphemeralStore {
                     })
                     .collect();
                 for k in keys {
+                    log.delete(subspace, &k);
                     map.remove(&k);
                 }
             }
         }
-        Ok(())
+        self.append_log(log)
     }
 }
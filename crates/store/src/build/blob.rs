@@ -5,7 +5,10 @@
  */
 
 use crate::{BlobStore, backend::fs::FsStore, registry::bootstrap::Bootstrap};
-use registry::schema::{prelude::ObjectType, structs};
+use registry::schema::{
+    prelude::ObjectType,
+    structs::{self, BlobStoreBase},
+};
 
 #[allow(unreachable_patterns)]
 impl BlobStore {
@@ -45,6 +48,10 @@ impl BlobStore {
             #[cfg(feature = "enterprise")]
             structs::BlobStore::Sharded(store) => {
                 crate::backend::composite::sharded_blob::ShardedBlob::open(store).await
+            }
+            #[cfg(feature = "enterprise")]
+            structs::BlobStore::Tiered(store) => {
+                crate::backend::composite::tiered_blob::TieredBlob::open(store).await
             } // SPDX-SnippetEnd
             _ => Err("Binary was not compiled with the selected blob store backend".to_string()),
         };
@@ -58,6 +65,38 @@ impl BlobStore {
         }
     }
 
+    // Opens a standalone blob store, such as a backup destination or a shard
+    pub async fn open(store: BlobStoreBase) -> Result<Self, String> {
+        match store {
+            #[cfg(feature = "s3")]
+            BlobStoreBase::S3(s3_store) => crate::backend::s3::S3Store::open(s3_store).await,
+            #[cfg(feature = "azure")]
+            BlobStoreBase::Azure(azure_store) => {
+                crate::backend::azure::AzureStore::open(azure_store).await
+            }
+            BlobStoreBase::FileSystem(file_system_store) => FsStore::open(file_system_store).await,
+            #[cfg(feature = "foundation")]
+            BlobStoreBase::FoundationDb(foundation_db_store) => {
+                crate::backend::foundationdb::FdbStore::open(foundation_db_store)
+                    .await
+                    .map(BlobStore::Store)
+            }
+            #[cfg(feature = "postgres")]
+            BlobStoreBase::PostgreSql(postgre_sql_store) => {
+                crate::backend::postgres::PostgresStore::open(postgre_sql_store)
+                    .await
+                    .map(BlobStore::Store)
+            }
+            #[cfg(feature = "mysql")]
+            BlobStoreBase::MySql(my_sql_store) => {
+                crate::backend::mysql::MysqlStore::open(my_sql_store)
+                    .await
+                    .map(BlobStore::Store)
+            }
+            _ => Err("Binary was not compiled with the selected blob store backend".to_string()),
+        }
+    }
+
     // SPDX-SnippetBegin
     // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
     // SPDX-License-Identifier: LicenseRef-SEL
@@ -65,13 +104,23 @@ impl BlobStore {
     pub fn downgrade_store(self) -> BlobStore {
         match self {
             BlobStore::Sharded(_) => BlobStore::default(),
+            BlobStore::Tiered(store) => store.without_migration(),
             other => other,
         }
     }
 
     #[cfg(feature = "enterprise")]
     pub fn is_enterprise(&self) -> bool {
-        matches!(self, BlobStore::Sharded(_))
+        match self {
+            BlobStore::Sharded(_) => true,
+            BlobStore::Tiered(store) => store.migrate_after.is_some(),
+            _ => false,
+        }
+    }
+
+    #[cfg(feature = "enterprise")]
+    pub fn is_composite(&self) -> bool {
+        matches!(self, BlobStore::Sharded(_) | BlobStore::Tiered(_))
     }
     // SPDX-SnippetEnd
 }
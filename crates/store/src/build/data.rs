@@ -29,10 +29,72 @@ impl Store {
             DataStore::MySql(store) => crate::backend::mysql::MysqlStore::open(store).await,
             #[cfg(feature = "sqlite")]
             DataStore::Sqlite(store) => crate::backend::sqlite::SqliteStore::open(store),
+            DataStore::Ephemeral(store) => {
+                crate::backend::ephemeral::EphemeralStore::open_with_config(store).await
+            }
             _ => Err("Binary was not compiled with the selected data store backend".to_string()),
         }
     }
 
+    // SPDX-SnippetBegin
+    // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+    // SPDX-License-Identifier: LicenseRef-SEL
+    #[cfg(feature = "enterprise")]
+    pub async fn build_sharded(bp: &mut Bootstrap) -> Self {
+        let primary = bp.data_store.clone();
+        let mut shards = Vec::new();
+
+        for shard in bp
+            .list_infallible::<registry::schema::structs::DataStoreShard>()
+            .await
+        {
+            let result = match Store::build(shard.object.store).await {
+                Ok(store) => store
+                    .create_tables()
+                    .await
+                    .map(|_| store)
+                    .map_err(|err| format!("Failed to create tables: {err}")),
+                Err(err) => Err(err),
+            };
+
+            match result {
+                Ok(store) => shards.push((shard.id.id().id(), store)),
+                Err(err) => {
+                    bp.build_error(shard.id, format!("Failed to open data store shard: {err}"))
+                }
+            }
+        }
+
+        if shards.is_empty() {
+            return primary;
+        }
+
+        match crate::backend::composite::sharded_data::ShardedStore::open(primary.clone(), shards)
+            .await
+        {
+            Ok(store) => {
+                // Nodes starting while an account is being moved acknowledge its fence
+                if let Some(sharded) = store.as_sharded()
+                    && let Err(err) = sharded.acknowledge_fences(bp.registry.node_id()).await
+                {
+                    bp.build_error(
+                        ObjectType::DataStore.singleton(),
+                        format!("Failed to acknowledge account fences: {err}"),
+                    );
+                }
+                store
+            }
+            Err(err) => {
+                bp.build_error(
+                    ObjectType::DataStore.singleton(),
+                    format!("Failed to open sharded data store: {err}"),
+                );
+                primary
+            }
+        }
+    }
+    // SPDX-SnippetEnd
+
     pub async fn build_tracing(bp: &mut Bootstrap) -> Option<Self> {
         let result = match bp.setting_infallible::<TracingStore>().await {
             TracingStore::Disabled => Ok(None),
@@ -182,7 +182,14 @@ impl RegistryStore {
             }
         }
 
-        Ok(Self(inner.into()))
+        // Nodes starting while the data store is fenced must not write to it
+        let registry = Self(inner.into());
+        registry
+            .sync_write_fence()
+            .await
+            .map_err(|err| format!("Failed to read write fence: {err}"))?;
+
+        Ok(registry)
     }
 
     pub fn node_id(&self) -> u16 {
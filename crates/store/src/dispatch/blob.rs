@@ -4,49 +4,164 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use crate::{BlobStore, CompressionAlgo, Store, U32_LEN};
+use super::{
+    compression::{BlobCompression, Decompressed, decompress},
+    encryption::{self, BlobMasterKeys},
+};
+use crate::{BlobStore, Store};
 use std::{ops::Range, time::Instant};
 use trc::{AddContext, StoreEvent};
 
-const MAGIC_MARKER: u8 = 0xa0;
-const LZ4_MARKER: u8 = MAGIC_MARKER | 0x01;
-//const ZSTD_MARKER: u8 = MAGIC_MARKER | 0x02;
-const NONE_MARKER: u8 = 0x00;
-
 impl BlobStore {
     pub async fn get_blob(&self, key: &[u8], range: Range<usize>) -> trc::Result<Option<Vec<u8>>> {
+        let Some(data) = self.get_stored_blob(key).await? else {
+            return Ok(None);
+        };
+
+        let data = encryption::decrypt(key, data)?;
+        self.load_missing_dictionary(&data).await?;
+        let mut data = match decompress(key, data)? {
+            Decompressed::Data(data) => data,
+            Decompressed::MissingMarker(data) => {
+                trc::event!(Store(StoreEvent::BlobMissingMarker), Key = key);
+
+                data
+            }
+        };
+
+        if range.start == 0 {
+            if range.end > data.len() {
+                Ok(Some(data))
+            } else {
+                data.truncate(range.end);
+                Ok(Some(data))
+            }
+        } else {
+            Ok(Some(
+                data.get(range.start..range.end)
+                    .unwrap_or_default()
+                    .to_vec(),
+            ))
+        }
+    }
+
+    pub async fn put_blob(
+        &self,
+        key: &[u8],
+        data: &[u8],
+        compression: impl Into<BlobCompression>,
+    ) -> trc::Result<()> {
+        let data = encryption::encrypt(key, compression.into().compress(data)?)?;
+        self.put_stored_blob(key, &data).await
+    }
+
+    pub async fn recompress_blob(
+        &self,
+        key: &[u8],
+        compression: BlobCompression,
+    ) -> trc::Result<bool> {
+        let Some(stored) = self.get_stored_blob(key).await? else {
+            return Ok(false);
+        };
+
+        let is_encrypted = encryption::is_encrypted(&stored);
+        let stored = encryption::decrypt(key, stored)?;
+        self.load_missing_dictionary(&stored).await?;
+        let Decompressed::Data(data) = decompress(key, stored.clone())? else {
+            return Ok(false);
+        };
+
+        if compression.is_outdated(&stored, &data) || is_encrypted != BlobMasterKeys::is_enabled() {
+            // Rewrite the blob where it is currently stored
+            let data = encryption::encrypt(key, compression.compress(&data)?)?;
+            self.stored_location(key)
+                .await?
+                .put_stored_blob(key, &data)
+                .await?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    pub async fn rewrap_blob_key(&self, key: &[u8]) -> trc::Result<bool> {
+        let Some(stored) = self.get_stored_blob(key).await? else {
+            return Ok(false);
+        };
+
+        if let Some(stored) = encryption::rewrap(key, &stored)? {
+            self.stored_location(key)
+                .await?
+                .put_stored_blob(key, &stored)
+                .await?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    pub async fn delete_blob(&self, key: &[u8]) -> trc::Result<bool> {
+        self.delete_stored_blob(key).await
+    }
+
+    async fn stored_location(&self, key: &[u8]) -> trc::Result<BlobStore> {
+        match self {
+            // SPDX-SnippetBegin
+            // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+            // SPDX-License-Identifier: LicenseRef-SEL
+            #[cfg(feature = "enterprise")]
+            BlobStore::Tiered(store) => store.stored_tier(key).await.cloned(),
+            // SPDX-SnippetEnd
+            _ => Ok(self.clone()),
+        }
+    }
+
+    // Raw access to stored blobs, bypassing compression and encryption
+    pub async fn get_stored_blob(&self, key: &[u8]) -> trc::Result<Option<Vec<u8>>> {
+        self.get_stored_blob_range(key, 0..usize::MAX).await
+    }
+
+    pub async fn get_stored_blob_range(
+        &self,
+        key: &[u8],
+        range: Range<usize>,
+    ) -> trc::Result<Option<Vec<u8>>> {
         let start_time = Instant::now();
         let result = match &self {
             BlobStore::Store(store) => match store {
                 #[cfg(feature = "sqlite")]
-                Store::SQLite(store) => store.get_blob(key, 0..usize::MAX).await,
+                Store::SQLite(store) => store.get_blob(key, range).await,
                 #[cfg(feature = "foundation")]
-                Store::FoundationDb(store) => store.get_blob(key, 0..usize::MAX).await,
+                Store::FoundationDb(store) => store.get_blob(key, range).await,
                 #[cfg(feature = "postgres")]
-                Store::PostgreSQL(store) => store.get_blob(key, 0..usize::MAX).await,
+                Store::PostgreSQL(store) => store.get_blob(key, range).await,
                 #[cfg(feature = "mysql")]
-                Store::MySQL(store) => store.get_blob(key, 0..usize::MAX).await,
+                Store::MySQL(store) => store.get_blob(key, range).await,
                 #[cfg(feature = "rocks")]
-                Store::RocksDb(store) => store.get_blob(key, 0..usize::MAX).await,
-                Store::Ephemeral(store) => store.get_blob(key, 0..usize::MAX).await,
+                Store::RocksDb(store) => store.get_blob(key, range).await,
+                Store::Ephemeral(store) => store.get_blob(key, range).await,
                 // SPDX-SnippetBegin
                 // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
                 // SPDX-License-Identifier: LicenseRef-SEL
                 #[cfg(all(feature = "enterprise", any(feature = "postgres", feature = "mysql")))]
-                Store::SQLReadReplica(store) => store.get_blob(key, 0..usize::MAX).await,
+                Store::SQLReadReplica(store) => store.get_blob(key, range).await,
+                #[cfg(feature = "enterprise")]
+                Store::Sharded(store) => store.get_blob(key, range).await,
                 // SPDX-SnippetEnd
                 Store::None => Err(trc::StoreEvent::NotConfigured.into()),
             },
-            BlobStore::Fs(store) => store.get_blob(key, 0..usize::MAX).await,
+            BlobStore::Fs(store) => store.get_blob(key, range).await,
             #[cfg(feature = "s3")]
-            BlobStore::S3(store) => store.get_blob(key, 0..usize::MAX).await,
+            BlobStore::S3(store) => store.get_blob(key, range).await,
             #[cfg(feature = "azure")]
-            BlobStore::Azure(store) => store.get_blob(key, 0..usize::MAX).await,
+            BlobStore::Azure(store) => store.get_blob(key, range).await,
             // SPDX-SnippetBegin
             // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
             // SPDX-License-Identifier: LicenseRef-SEL
             #[cfg(feature = "enterprise")]
-            BlobStore::Sharded(store) => store.get_blob(key, 0..usize::MAX).await,
+            BlobStore::Sharded(store) => store.get_blob(key, range).await,
+            #[cfg(feature = "enterprise")]
+            BlobStore::Tiered(store) => store.get_blob(key, range).await,
             // SPDX-SnippetEnd
         }
         .caused_by(trc::location!())?;
@@ -58,117 +173,46 @@ impl BlobStore {
             Size = result.as_ref().map_or(0, |data| data.len()),
         );
 
-        let Some(mut data) = result else {
-            return Ok(None);
-        };
-
-        let mut data = match data.last().copied() {
-            Some(LZ4_MARKER) => {
-                lz4_flex::decompress_size_prepended(data.get(..data.len() - 1).unwrap_or_default())
-                    .map_err(|err| {
-                        trc::StoreEvent::DecompressError
-                            .reason(err)
-                            .ctx(trc::Key::Key, key)
-                            .ctx(trc::Key::CausedBy, trc::location!())
-                    })?
-            }
-            Some(NONE_MARKER) => {
-                if !data.is_empty() {
-                    data.truncate(data.len() - 1);
-                }
-                data
-            }
-            Some(_) => {
-                trc::event!(Store(StoreEvent::BlobMissingMarker), Key = key);
-
-                data
-            }
-            None => {
-                return Ok(Some(data));
-            }
-        };
-
-        if range.start == 0 {
-            if range.end > data.len() {
-                Ok(Some(data))
-            } else {
-                data.truncate(range.end);
-                Ok(Some(data))
-            }
-        } else {
-            Ok(Some(
-                data.get(range.start..range.end)
-                    .unwrap_or_default()
-                    .to_vec(),
-            ))
-        }
+        result
     }
 
-    pub async fn put_blob(
-        &self,
-        key: &[u8],
-        data: &[u8],
-        compression: CompressionAlgo,
-    ) -> trc::Result<()> {
-        let data = match compression {
-            CompressionAlgo::None => {
-                let mut uncompressed = Vec::with_capacity(data.len() + 1);
-                uncompressed.extend_from_slice(data);
-                uncompressed.push(NONE_MARKER);
-                uncompressed
-            }
-            CompressionAlgo::Lz4 => {
-                let mut compressed =
-                    vec![
-                        LZ4_MARKER;
-                        lz4_flex::block::get_maximum_output_size(data.len()) + U32_LEN + 1
-                    ];
-
-                // Compress the data
-                let compressed_len =
-                    lz4_flex::compress_into(data, &mut compressed[U32_LEN..]).unwrap();
-
-                // Prepend the length of the uncompressed data
-                compressed[..U32_LEN].copy_from_slice(&(data.len() as u32).to_le_bytes());
-
-                // Truncate to the actual size
-                compressed.truncate(compressed_len + U32_LEN + 1);
-                compressed
-            }
-        };
-
+    pub async fn put_stored_blob(&self, key: &[u8], data: &[u8]) -> trc::Result<()> {
         let start_time = Instant::now();
         let result = match &self {
             BlobStore::Store(store) => match store {
                 #[cfg(feature = "sqlite")]
-                Store::SQLite(store) => store.put_blob(key, &data).await,
+                Store::SQLite(store) => store.put_blob(key, data).await,
                 #[cfg(feature = "foundation")]
-                Store::FoundationDb(store) => store.put_blob(key, &data).await,
+                Store::FoundationDb(store) => store.put_blob(key, data).await,
                 #[cfg(feature = "postgres")]
-                Store::PostgreSQL(store) => store.put_blob(key, &data).await,
+                Store::PostgreSQL(store) => store.put_blob(key, data).await,
                 #[cfg(feature = "mysql")]
-                Store::MySQL(store) => store.put_blob(key, &data).await,
+                Store::MySQL(store) => store.put_blob(key, data).await,
                 #[cfg(feature = "rocks")]
-                Store::RocksDb(store) => store.put_blob(key, &data).await,
-                Store::Ephemeral(store) => store.put_blob(key, &data).await,
+                Store::RocksDb(store) => store.put_blob(key, data).await,
+                Store::Ephemeral(store) => store.put_blob(key, data).await,
                 // SPDX-SnippetBegin
                 // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
                 // SPDX-License-Identifier: LicenseRef-SEL
                 #[cfg(all(feature = "enterprise", any(feature = "postgres", feature = "mysql")))]
-                Store::SQLReadReplica(store) => store.put_blob(key, &data).await,
+                Store::SQLReadReplica(store) => store.put_blob(key, data).await,
+                #[cfg(feature = "enterprise")]
+                Store::Sharded(store) => store.put_blob(key, data).await,
                 // SPDX-SnippetEnd
                 Store::None => Err(trc::StoreEvent::NotConfigured.into()),
             },
-            BlobStore::Fs(store) => store.put_blob(key, &data).await,
+            BlobStore::Fs(store) => store.put_blob(key, data).await,
             #[cfg(feature = "s3")]
-            BlobStore::S3(store) => store.put_blob(key, &data).await,
+            BlobStore::S3(store) => store.put_blob(key, data).await,
             #[cfg(feature = "azure")]
-            BlobStore::Azure(store) => store.put_blob(key, &data).await,
+            BlobStore::Azure(store) => store.put_blob(key, data).await,
             // SPDX-SnippetBegin
             // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
             // SPDX-License-Identifier: LicenseRef-SEL
             #[cfg(feature = "enterprise")]
-            BlobStore::Sharded(store) => store.put_blob(key, &data).await,
+            BlobStore::Sharded(store) => store.put_blob(key, data).await,
+            #[cfg(feature = "enterprise")]
+            BlobStore::Tiered(store) => store.put_blob(key, data).await,
             // SPDX-SnippetEnd
         }
         .caused_by(trc::location!());
@@ -183,7 +227,7 @@ impl BlobStore {
         result
     }
 
-    pub async fn delete_blob(&self, key: &[u8]) -> trc::Result<bool> {
+    pub async fn delete_stored_blob(&self, key: &[u8]) -> trc::Result<bool> {
         let start_time = Instant::now();
         let result = match &self {
             BlobStore::Store(store) => match store {
@@ -203,6 +247,8 @@ impl BlobStore {
                 // SPDX-License-Identifier: LicenseRef-SEL
                 #[cfg(all(feature = "enterprise", any(feature = "postgres", feature = "mysql")))]
                 Store::SQLReadReplica(store) => store.delete_blob(key).await,
+                #[cfg(feature = "enterprise")]
+                Store::Sharded(store) => store.delete_blob(key).await,
                 // SPDX-SnippetEnd
                 Store::None => Err(trc::StoreEvent::NotConfigured.into()),
             },
@@ -216,6 +262,8 @@ impl BlobStore {
             // SPDX-License-Identifier: LicenseRef-SEL
             #[cfg(feature = "enterprise")]
             BlobStore::Sharded(store) => store.delete_blob(key).await,
+            #[cfg(feature = "enterprise")]
+            BlobStore::Tiered(store) => store.delete_blob(key).await,
             // SPDX-SnippetEnd
         }
         .caused_by(trc::location!());
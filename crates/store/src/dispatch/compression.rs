@@ -0,0 +1,377 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::encryption::decrypt;
+use crate::{BlobStore, CompressionAlgo, RegistryStore, U32_LEN, U64_LEN};
+use ahash::AHashMap;
+use arc_swap::{ArcSwap, ArcSwapOption};
+use registry::{
+    schema::{enums::CompressionContentClass, structs::CompressionDictionary},
+    types::id::Id,
+};
+use std::{
+    io::Read,
+    sync::{Arc, LazyLock},
+};
+use trc::AddContext;
+
+// Blobs compressed with LZ4 or stored uncompressed end with a marker byte:
+//
+// - NONE_MARKER: [data][marker]
+// - LZ4_MARKER:  [uncompressed len: u32 LE][lz4 block][marker]
+//
+// Zstd blobs start with an explicit header followed by the frame:
+//
+// - [ZSTD_HEADER_MAGIC][dictionary id: u64 LE][zstd frame]
+//
+// A dictionary id of zero means the frame was compressed without a dictionary.
+const MAGIC_MARKER: u8 = 0xa0;
+const LZ4_MARKER: u8 = MAGIC_MARKER | 0x01;
+const NONE_MARKER: u8 = 0x00;
+
+const ZSTD_HEADER_MAGIC: &[u8] = &[MAGIC_MARKER | 0x02, b'Z', b'S', b'T'];
+const ZSTD_HEADER_LEN: usize = ZSTD_HEADER_MAGIC.len() + U64_LEN;
+const ZSTD_FRAME_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+const ZSTD_DICTIONARY_MAGIC: &[u8] = &[0x37, 0xa4, 0x30, 0xec];
+pub const ZSTD_DEFAULT_LEVEL: i32 = 3;
+pub const DICTIONARY_MAX_SIZE: usize = 64 * 1024;
+pub const DICTIONARY_MAX_SAMPLE_SIZE: usize = 16 * 1024;
+
+static DICTIONARIES: LazyLock<ArcSwap<CompressionDictionaries>> =
+    LazyLock::new(|| ArcSwap::from_pointee(CompressionDictionaries::default()));
+
+// Registry used to load dictionaries trained by other nodes on first use
+static DICTIONARY_REGISTRY: ArcSwapOption<RegistryStore> = ArcSwapOption::const_empty();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlobCompression {
+    pub algorithm: CompressionAlgo,
+    pub level: i32,
+}
+
+#[derive(Debug, Default)]
+pub struct CompressionDictionaries {
+    dictionaries: AHashMap<u64, Arc<[u8]>>,
+    latest: AHashMap<CompressionContentClass, u64>,
+}
+
+pub(crate) enum Decompressed {
+    Data(Vec<u8>),
+    MissingMarker(Vec<u8>),
+}
+
+impl BlobCompression {
+    pub fn new(algorithm: CompressionAlgo, level: i32) -> Self {
+        Self { algorithm, level }
+    }
+
+    pub(crate) fn compress(&self, data: &[u8]) -> trc::Result<Vec<u8>> {
+        match self.algorithm {
+            CompressionAlgo::None => {
+                let mut uncompressed = Vec::with_capacity(data.len() + 1);
+                uncompressed.extend_from_slice(data);
+                uncompressed.push(NONE_MARKER);
+                Ok(uncompressed)
+            }
+            CompressionAlgo::Lz4 => {
+                let mut compressed =
+                    vec![
+                        LZ4_MARKER;
+                        lz4_flex::block::get_maximum_output_size(data.len()) + U32_LEN + 1
+                    ];
+
+                // Compress the data
+                let compressed_len =
+                    lz4_flex::compress_into(data, &mut compressed[U32_LEN..]).unwrap();
+
+                // Prepend the length of the uncompressed data
+                compressed[..U32_LEN].copy_from_slice(&(data.len() as u32).to_le_bytes());
+
+                // Truncate to the actual size
+                compressed.truncate(compressed_len + U32_LEN + 1);
+                Ok(compressed)
+            }
+            CompressionAlgo::Zstd => {
+                let dictionary = CompressionContentClass::detect(data)
+                    .and_then(|class| DICTIONARIES.load().latest(class));
+                let result = if let Some((_, dictionary)) = &dictionary {
+                    zstd::bulk::Compressor::with_dictionary(self.level, dictionary)
+                        .and_then(|mut compressor| compressor.compress(data))
+                } else {
+                    zstd::bulk::compress(data, self.level)
+                };
+                let mut compressed = result.map_err(|err| {
+                    trc::StoreEvent::UnexpectedError
+                        .reason(err)
+                        .ctx(trc::Key::CausedBy, trc::location!())
+                })?;
+
+                // Prepend the header
+                let mut blob = Vec::with_capacity(ZSTD_HEADER_LEN + compressed.len());
+                blob.extend_from_slice(ZSTD_HEADER_MAGIC);
+                blob.extend_from_slice(&dictionary.map_or(0, |(id, _)| id).to_le_bytes());
+                blob.append(&mut compressed);
+                Ok(blob)
+            }
+        }
+    }
+
+    // Returns true if the stored blob was not written with these settings
+    pub(crate) fn is_outdated(&self, stored: &[u8], data: &[u8]) -> bool {
+        match (self.algorithm, zstd_dictionary_id(stored)) {
+            (CompressionAlgo::Zstd, Some(stored_id)) => {
+                let dictionary_id = CompressionContentClass::detect(data)
+                    .and_then(|class| DICTIONARIES.load().latest(class))
+                    .map_or(0, |(id, _)| id);
+
+                stored_id != dictionary_id
+            }
+            (CompressionAlgo::None, None) => stored.last() != Some(&NONE_MARKER),
+            (CompressionAlgo::Lz4, None) => stored.last() != Some(&LZ4_MARKER),
+            _ => true,
+        }
+    }
+}
+
+impl From<CompressionAlgo> for BlobCompression {
+    fn from(algorithm: CompressionAlgo) -> Self {
+        Self {
+            algorithm,
+            level: ZSTD_DEFAULT_LEVEL,
+        }
+    }
+}
+
+impl Default for BlobCompression {
+    fn default() -> Self {
+        CompressionAlgo::default().into()
+    }
+}
+
+impl CompressionDictionaries {
+    pub fn register(id: u64, class: CompressionContentClass, dictionary: Vec<u8>) {
+        let dictionary: Arc<[u8]> = dictionary.into();
+
+        DICTIONARIES.rcu(|current| {
+            let mut dictionaries = current.dictionaries.clone();
+            let mut latest = current.latest.clone();
+            dictionaries.insert(id, dictionary.clone());
+
+            // Dictionary ids are generated in ascending order, the newest one is used for compression
+            if latest.get(&class).is_none_or(|latest_id| *latest_id < id) {
+                latest.insert(class, id);
+            }
+
+            CompressionDictionaries {
+                dictionaries,
+                latest,
+            }
+        });
+    }
+
+    pub fn unregister(id: u64) {
+        DICTIONARIES.rcu(|current| {
+            let mut dictionaries = current.dictionaries.clone();
+            let mut latest = current.latest.clone();
+            dictionaries.remove(&id);
+            latest.retain(|_, latest_id| *latest_id != id);
+
+            CompressionDictionaries {
+                dictionaries,
+                latest,
+            }
+        });
+    }
+
+    pub fn set_registry(registry: RegistryStore) {
+        DICTIONARY_REGISTRY.store(Some(Arc::new(registry)));
+    }
+
+    pub fn is_registered(id: u64) -> bool {
+        DICTIONARIES.load().dictionaries.contains_key(&id)
+    }
+
+    fn latest(&self, class: CompressionContentClass) -> Option<(u64, Arc<[u8]>)> {
+        self.latest.get(&class).and_then(|id| {
+            self.dictionaries
+                .get(id)
+                .map(|dictionary| (*id, dictionary.clone()))
+        })
+    }
+}
+
+pub trait ContentClassDetect: Sized {
+    fn detect(data: &[u8]) -> Option<Self>;
+    fn training_sample(&self, data: &[u8]) -> Option<Vec<u8>>;
+}
+
+impl ContentClassDetect for CompressionContentClass {
+    fn detect(data: &[u8]) -> Option<Self> {
+        // Dictionaries must be readable without loading other dictionaries
+        if data.starts_with(ZSTD_DICTIONARY_MAGIC) {
+            return None;
+        }
+
+        let data = data.trim_ascii_start();
+
+        if data
+            .get(..5)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(b"<html"))
+            || data
+                .get(..14)
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(b"<!doctype html"))
+        {
+            Some(CompressionContentClass::HtmlBody)
+        } else {
+            // RFC 5322 header field name followed by a colon
+            let name_len = data
+                .iter()
+                .take(256)
+                .position(|&ch| ch == b':')
+                .filter(|&pos| pos > 0)?;
+
+            data[..name_len]
+                .iter()
+                .all(|&ch| (33..=126).contains(&ch))
+                .then_some(CompressionContentClass::Headers)
+        }
+    }
+
+    fn training_sample(&self, data: &[u8]) -> Option<Vec<u8>> {
+        let sample = match self {
+            CompressionContentClass::Headers => {
+                let end = data
+                    .windows(4)
+                    .position(|window| window == b"\r\n\r\n")
+                    .map(|pos| pos + 4)
+                    .or_else(|| {
+                        data.windows(2)
+                            .position(|window| window == b"\n\n")
+                            .map(|pos| pos + 2)
+                    })
+                    .unwrap_or(data.len());
+                &data[..end]
+            }
+            CompressionContentClass::HtmlBody => data,
+        };
+
+        (!sample.is_empty())
+            .then(|| sample[..sample.len().min(DICTIONARY_MAX_SAMPLE_SIZE)].to_vec())
+    }
+}
+
+pub fn train_dictionary(samples: &[Vec<u8>]) -> Result<Vec<u8>, String> {
+    zstd::dict::from_samples(samples, DICTIONARY_MAX_SIZE).map_err(|err| err.to_string())
+}
+
+pub(crate) fn decompress(key: &[u8], mut data: Vec<u8>) -> trc::Result<Decompressed> {
+    if let Some(dictionary_id) = zstd_dictionary_id(&data) {
+        let frame = &data[ZSTD_HEADER_LEN..];
+        let mut uncompressed = Vec::with_capacity(frame.len() * 3);
+        let result = if dictionary_id != 0 {
+            let dictionary = DICTIONARIES
+                .load()
+                .dictionaries
+                .get(&dictionary_id)
+                .cloned()
+                .ok_or_else(|| {
+                    trc::StoreEvent::DecompressError
+                        .reason("Unknown compression dictionary")
+                        .ctx(trc::Key::Id, dictionary_id)
+                        .ctx(trc::Key::Key, key)
+                        .ctx(trc::Key::CausedBy, trc::location!())
+                })?;
+            zstd::stream::read::Decoder::with_dictionary(frame, &dictionary)
+                .and_then(|mut decoder| decoder.read_to_end(&mut uncompressed))
+        } else {
+            zstd::stream::read::Decoder::with_buffer(frame)
+                .and_then(|mut decoder| decoder.read_to_end(&mut uncompressed))
+        };
+
+        match result {
+            Ok(_) => return Ok(Decompressed::Data(uncompressed)),
+            // Uncompressed data that happens to start with a Zstd header
+            Err(_) if data.last() == Some(&NONE_MARKER) => {}
+            Err(err) => {
+                return Err(trc::StoreEvent::DecompressError
+                    .reason(err)
+                    .ctx(trc::Key::Key, key)
+                    .ctx(trc::Key::CausedBy, trc::location!()));
+            }
+        }
+    }
+
+    match data.last().copied() {
+        Some(LZ4_MARKER) => {
+            lz4_flex::decompress_size_prepended(data.get(..data.len() - 1).unwrap_or_default())
+                .map(Decompressed::Data)
+                .map_err(|err| {
+                    trc::StoreEvent::DecompressError
+                        .reason(err)
+                        .ctx(trc::Key::Key, key)
+                        .ctx(trc::Key::CausedBy, trc::location!())
+                })
+        }
+        Some(NONE_MARKER) => {
+            data.truncate(data.len() - 1);
+            Ok(Decompressed::Data(data))
+        }
+        Some(_) => Ok(Decompressed::MissingMarker(data)),
+        None => Ok(Decompressed::Data(data)),
+    }
+}
+
+impl BlobStore {
+    // Returns the id of the dictionary a stored blob was compressed with
+    pub async fn blob_dictionary_id(&self, key: &[u8]) -> trc::Result<Option<u64>> {
+        if let Some(stored) = self.get_stored_blob(key).await? {
+            Ok(zstd_dictionary_id(&decrypt(key, stored)?).filter(|id| *id != 0))
+        } else {
+            Ok(None)
+        }
+    }
+
+    // Loads dictionaries trained by other nodes before they are broadcast
+    pub(crate) async fn load_missing_dictionary(&self, stored: &[u8]) -> trc::Result<()> {
+        let Some(dictionary_id) = zstd_dictionary_id(stored)
+            .filter(|id| *id != 0 && !CompressionDictionaries::is_registered(*id))
+        else {
+            return Ok(());
+        };
+        let Some(registry) = DICTIONARY_REGISTRY.load_full() else {
+            return Ok(());
+        };
+
+        if let Some(dictionary) = registry
+            .object::<CompressionDictionary>(Id::new(dictionary_id))
+            .await
+            .caused_by(trc::location!())?
+            && let Some(data) =
+                Box::pin(self.get_blob(dictionary.blob_id.hash.as_ref(), 0..usize::MAX))
+                    .await
+                    .caused_by(trc::location!())?
+        {
+            CompressionDictionaries::register(dictionary_id, dictionary.content_class, data);
+        }
+
+        Ok(())
+    }
+}
+
+fn zstd_dictionary_id(stored: &[u8]) -> Option<u64> {
+    if stored.starts_with(ZSTD_HEADER_MAGIC)
+        && stored
+            .get(ZSTD_HEADER_LEN..)
+            .is_some_and(|frame| frame.starts_with(ZSTD_FRAME_MAGIC))
+    {
+        stored[ZSTD_HEADER_MAGIC.len()..ZSTD_HEADER_LEN]
+            .try_into()
+            .ok()
+            .map(u64::from_le_bytes)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,254 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::U64_LEN;
+use aes_gcm_siv::{
+    Aes256GcmSiv, Key, KeyInit, Nonce,
+    aead::{Aead, Payload},
+};
+use ahash::AHashMap;
+use arc_swap::ArcSwap;
+use std::sync::{Arc, LazyLock};
+
+// Encrypted blobs start with a header that holds their wrapped data key and end with a marker:
+//
+// [ENCRYPTED_MAGIC][master key id: u64 LE][key nonce][wrapped data key][data nonce][ciphertext][marker]
+//
+// Compressed blobs end with a different marker or start with the Zstd header, blobs
+// without both the magic and the marker are always read as plaintext.
+//
+// Blob stores cannot update a blob in place, so rotating the master key rewrites each
+// blob in full even though only the wrapped data key changes.
+const ENCRYPTED_MAGIC: &[u8] = &[0xb1, b'E', b'N', b'C'];
+const ENCRYPTED_MARKER: u8 = 0xa3;
+
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+const WRAPPED_KEY_LEN: usize = KEY_LEN + TAG_LEN;
+const KEY_NONCE_OFFSET: usize = ENCRYPTED_MAGIC.len() + U64_LEN;
+const WRAPPED_KEY_OFFSET: usize = KEY_NONCE_OFFSET + NONCE_LEN;
+const DATA_NONCE_OFFSET: usize = WRAPPED_KEY_OFFSET + WRAPPED_KEY_LEN;
+const HEADER_LEN: usize = DATA_NONCE_OFFSET + NONCE_LEN;
+const MASTER_KEY_CONTEXT: &str = "Stalwart blob encryption master key";
+const SECRET_KEY_CONTEXT: &str = "Stalwart secret encryption key";
+
+static MASTER_KEYS: LazyLock<ArcSwap<BlobMasterKeys>> =
+    LazyLock::new(|| ArcSwap::from_pointee(BlobMasterKeys::default()));
+
+#[derive(Default)]
+pub struct BlobMasterKeys {
+    keys: AHashMap<u64, Arc<Aes256GcmSiv>>,
+    active: Option<u64>,
+}
+
+// Encrypts data with a key derived from a secret, stored as [ciphertext][nonce]
+#[derive(Clone)]
+pub struct SecretCipher {
+    cipher: Arc<Aes256GcmSiv>,
+}
+
+impl BlobMasterKeys {
+    // Replaces the master keys, the key with the highest id is used to wrap new data keys
+    pub fn install(keys: impl IntoIterator<Item = (u64, String)>) {
+        let keys = keys
+            .into_iter()
+            .map(|(id, secret)| {
+                let key = blake3::derive_key(MASTER_KEY_CONTEXT, secret.as_bytes());
+                (
+                    id,
+                    Arc::new(Aes256GcmSiv::new(Key::<Aes256GcmSiv>::from_slice(&key))),
+                )
+            })
+            .collect::<AHashMap<_, _>>();
+        let active = keys.keys().max().copied();
+
+        MASTER_KEYS.store(Arc::new(BlobMasterKeys { keys, active }));
+    }
+
+    pub fn is_enabled() -> bool {
+        MASTER_KEYS.load().active.is_some()
+    }
+
+    pub fn active_id() -> Option<u64> {
+        MASTER_KEYS.load().active
+    }
+
+    fn active_key(&self) -> Option<(u64, &Aes256GcmSiv)> {
+        self.active.and_then(|id| {
+            self.keys
+                .get(&id)
+                .map(|master_key| (id, master_key.as_ref()))
+        })
+    }
+}
+
+impl SecretCipher {
+    pub fn new(secret: &str) -> Self {
+        let key = blake3::derive_key(SECRET_KEY_CONTEXT, secret.as_bytes());
+        SecretCipher {
+            cipher: Arc::new(Aes256GcmSiv::new(Key::<Aes256GcmSiv>::from_slice(&key))),
+        }
+    }
+
+    pub fn encrypt(&self, aad: &[u8], data: &[u8]) -> trc::Result<Vec<u8>> {
+        let nonce = rand::random::<[u8; NONCE_LEN]>();
+        let mut encrypted = self
+            .cipher
+            .encrypt(Nonce::from_slice(&nonce), Payload { msg: data, aad })
+            .map_err(|err| crypto_error(aad, err))?;
+        encrypted.extend_from_slice(&nonce);
+        Ok(encrypted)
+    }
+
+    pub fn decrypt(&self, aad: &[u8], stored: &[u8]) -> trc::Result<Vec<u8>> {
+        if stored.len() < NONCE_LEN + TAG_LEN {
+            return Err(crypto_error(aad, "Encrypted data is too short"));
+        }
+
+        let (ciphertext, nonce) = stored.split_at(stored.len() - NONCE_LEN);
+        self.cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad,
+                },
+            )
+            .map_err(|err| crypto_error(aad, err))
+    }
+}
+
+pub(crate) fn is_encrypted(stored: &[u8]) -> bool {
+    stored.len() > HEADER_LEN + TAG_LEN
+        && stored.starts_with(ENCRYPTED_MAGIC)
+        && stored.last() == Some(&ENCRYPTED_MARKER)
+}
+
+// Encrypts a blob with a new data key wrapped by the active master key
+pub(crate) fn encrypt(key: &[u8], data: Vec<u8>) -> trc::Result<Vec<u8>> {
+    let master_keys = MASTER_KEYS.load();
+    let Some((master_key_id, master_key)) = master_keys.active_key() else {
+        return Ok(data);
+    };
+
+    let data_key = rand::random::<[u8; KEY_LEN]>();
+    let nonce = rand::random::<[u8; NONCE_LEN]>();
+    let ciphertext = Aes256GcmSiv::new(Key::<Aes256GcmSiv>::from_slice(&data_key))
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: &data,
+                aad: key,
+            },
+        )
+        .map_err(|err| crypto_error(key, err))?;
+
+    let mut encrypted = Vec::with_capacity(HEADER_LEN + ciphertext.len() + 1);
+    wrap(key, master_key_id, master_key, &data_key, &mut encrypted)?;
+    encrypted.extend_from_slice(&nonce);
+    encrypted.extend_from_slice(&ciphertext);
+    encrypted.push(ENCRYPTED_MARKER);
+    Ok(encrypted)
+}
+
+// Decrypts a blob, unencrypted blobs are returned as is
+pub(crate) fn decrypt(key: &[u8], stored: Vec<u8>) -> trc::Result<Vec<u8>> {
+    if !is_encrypted(&stored) {
+        return Ok(stored);
+    }
+
+    let data_key = unwrap(key, &stored)?;
+    Aes256GcmSiv::new(Key::<Aes256GcmSiv>::from_slice(&data_key))
+        .decrypt(
+            Nonce::from_slice(&stored[DATA_NONCE_OFFSET..HEADER_LEN]),
+            Payload {
+                msg: &stored[HEADER_LEN..stored.len() - 1],
+                aad: key,
+            },
+        )
+        .map_err(|err| crypto_error(key, err))
+}
+
+// Rewraps the data key with the active master key, returns None if no changes are needed.
+// The ciphertext is kept as is but the caller has to store the whole blob again.
+pub(crate) fn rewrap(key: &[u8], stored: &[u8]) -> trc::Result<Option<Vec<u8>>> {
+    let master_keys = MASTER_KEYS.load();
+    let Some((master_key_id, master_key)) = master_keys.active_key() else {
+        return Ok(None);
+    };
+
+    if !is_encrypted(stored) || master_key_id_of(stored) == master_key_id {
+        return Ok(None);
+    }
+
+    let data_key = unwrap(key, stored)?;
+    let mut rewrapped = Vec::with_capacity(stored.len());
+    wrap(key, master_key_id, master_key, &data_key, &mut rewrapped)?;
+    rewrapped.extend_from_slice(&stored[DATA_NONCE_OFFSET..]);
+    Ok(Some(rewrapped))
+}
+
+// Writes the header up to the data nonce
+fn wrap(
+    key: &[u8],
+    master_key_id: u64,
+    master_key: &Aes256GcmSiv,
+    data_key: &[u8],
+    out: &mut Vec<u8>,
+) -> trc::Result<()> {
+    let nonce = rand::random::<[u8; NONCE_LEN]>();
+    let wrapped_key = master_key
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: data_key,
+                aad: key,
+            },
+        )
+        .map_err(|err| crypto_error(key, err))?;
+
+    out.extend_from_slice(ENCRYPTED_MAGIC);
+    out.extend_from_slice(&master_key_id.to_le_bytes());
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&wrapped_key);
+    Ok(())
+}
+
+fn unwrap(key: &[u8], stored: &[u8]) -> trc::Result<Vec<u8>> {
+    let master_key_id = master_key_id_of(stored);
+    let master_key = MASTER_KEYS
+        .load()
+        .keys
+        .get(&master_key_id)
+        .cloned()
+        .ok_or_else(|| crypto_error(key, "Unknown master key").ctx(trc::Key::Id, master_key_id))?;
+
+    master_key
+        .decrypt(
+            Nonce::from_slice(&stored[KEY_NONCE_OFFSET..WRAPPED_KEY_OFFSET]),
+            Payload {
+                msg: &stored[WRAPPED_KEY_OFFSET..DATA_NONCE_OFFSET],
+                aad: key,
+            },
+        )
+        .map_err(|err| crypto_error(key, err))
+}
+
+fn master_key_id_of(stored: &[u8]) -> u64 {
+    u64::from_le_bytes(
+        stored[ENCRYPTED_MAGIC.len()..KEY_NONCE_OFFSET]
+            .try_into()
+            .unwrap(),
+    )
+}
+
+fn crypto_error(key: &[u8], reason: impl std::fmt::Display) -> trc::Error {
+    trc::StoreEvent::CryptoError
+        .reason(reason)
+        .ctx(trc::Key::Key, key)
+        .caused_by(trc::location!())
+}
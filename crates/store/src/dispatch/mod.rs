@@ -8,6 +8,8 @@ use crate::Store;
 use roaring::RoaringBitmap;
 
 pub mod blob;
+pub mod compression;
+pub mod encryption;
 pub mod lookup;
 pub mod search;
 pub mod store;
@@ -31,6 +33,8 @@ impl Store {
             // SPDX-License-Identifier: LicenseRef-SEL
             #[cfg(all(feature = "enterprise", any(feature = "postgres", feature = "mysql")))]
             Self::SQLReadReplica(_) => "read_replica",
+            #[cfg(feature = "enterprise")]
+            Self::Sharded(_) => "sharded",
             // SPDX-SnippetEnd
             Self::None => "none",
         }
@@ -40,6 +40,8 @@ impl Store {
             // SPDX-License-Identifier: LicenseRef-SEL
             #[cfg(all(feature = "enterprise", any(feature = "postgres", feature = "mysql")))]
             Self::SQLReadReplica(store) => store.get_value(key).await,
+            #[cfg(feature = "enterprise")]
+            Self::Sharded(store) => store.get_value(key).await,
             // SPDX-SnippetEnd
             Self::None => Err(trc::StoreEvent::NotConfigured.into()),
         }
@@ -64,6 +66,8 @@ impl Store {
             // SPDX-License-Identifier: LicenseRef-SEL
             #[cfg(all(feature = "enterprise", any(feature = "postgres", feature = "mysql")))]
             Self::SQLReadReplica(store) => store.key_exists(key).await,
+            #[cfg(feature = "enterprise")]
+            Self::Sharded(store) => store.key_exists(key).await,
             // SPDX-SnippetEnd
             Self::None => Err(trc::StoreEvent::NotConfigured.into()),
         }
@@ -93,6 +97,8 @@ impl Store {
             // SPDX-License-Identifier: LicenseRef-SEL
             #[cfg(all(feature = "enterprise", any(feature = "postgres", feature = "mysql")))]
             Self::SQLReadReplica(store) => store.iterate(params, cb).await,
+            #[cfg(feature = "enterprise")]
+            Self::Sharded(store) => store.iterate(params, cb).await,
             // SPDX-SnippetEnd
             Self::None => Err(trc::StoreEvent::NotConfigured.into()),
         }
@@ -127,6 +133,8 @@ impl Store {
             // SPDX-License-Identifier: LicenseRef-SEL
             #[cfg(all(feature = "enterprise", any(feature = "postgres", feature = "mysql")))]
             Self::SQLReadReplica(store) => store.get_counter(key).await,
+            #[cfg(feature = "enterprise")]
+            Self::Sharded(store) => store.get_counter(key).await,
             // SPDX-SnippetEnd
             Self::None => Err(trc::StoreEvent::NotConfigured.into()),
         }
@@ -161,6 +169,12 @@ impl Store {
     }
 
     pub async fn write(&self, batch: Batch<'_>) -> trc::Result<AssignedIds> {
+        let _in_flight = self.begin_write()?;
+        self.write_unfenced(batch).await
+    }
+
+    // Writes a batch even if the store is fenced, only used to manage the fence itself
+    pub(crate) async fn write_unfenced(&self, batch: Batch<'_>) -> trc::Result<AssignedIds> {
         let start_time = Instant::now();
         let ops = batch.ops.len();
 
@@ -181,6 +195,8 @@ impl Store {
             // SPDX-License-Identifier: LicenseRef-SEL
             #[cfg(all(feature = "enterprise", any(feature = "postgres", feature = "mysql")))]
             Self::SQLReadReplica(store) => store.write(batch).await,
+            #[cfg(feature = "enterprise")]
+            Self::Sharded(store) => store.write(batch).await,
             // SPDX-SnippetEnd
             Self::None => Err(trc::StoreEvent::NotConfigured.into()),
         };
@@ -232,6 +248,8 @@ impl Store {
             // SPDX-License-Identifier: LicenseRef-SEL
             #[cfg(all(feature = "enterprise", any(feature = "postgres", feature = "mysql")))]
             Self::SQLReadReplica(store) => store.purge_store().await,
+            #[cfg(feature = "enterprise")]
+            Self::Sharded(store) => store.purge_store().await,
             // SPDX-SnippetEnd
             Self::None => Err(trc::StoreEvent::NotConfigured.into()),
         }
@@ -256,6 +274,8 @@ impl Store {
             // SPDX-License-Identifier: LicenseRef-SEL
             #[cfg(all(feature = "enterprise", any(feature = "postgres", feature = "mysql")))]
             Self::SQLReadReplica(store) => store.delete_range(from, to).await,
+            #[cfg(feature = "enterprise")]
+            Self::Sharded(store) => store.delete_range(from, to).await,
             // SPDX-SnippetEnd
             Self::None => Err(trc::StoreEvent::NotConfigured.into()),
         }
@@ -391,6 +411,18 @@ impl Store {
         .await
         .caused_by(trc::location!())?;
 
+        // SPDX-SnippetBegin
+        // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+        // SPDX-License-Identifier: LicenseRef-SEL
+        #[cfg(feature = "enterprise")]
+        if let Self::Sharded(store) = self {
+            store
+                .remove_placement(account_id)
+                .await
+                .caused_by(trc::location!())?;
+        }
+        // SPDX-SnippetEnd
+
         Ok(())
     }
 
@@ -407,6 +439,8 @@ impl Store {
             // SPDX-License-Identifier: LicenseRef-SEL
             #[cfg(all(feature = "enterprise", any(feature = "postgres", feature = "mysql")))]
             Store::SQLReadReplica(store) => Box::pin(store.primary_store().create_tables()).await,
+            #[cfg(feature = "enterprise")]
+            Store::Sharded(store) => store.create_tables().await,
             // SPDX-SnippetEnd
             _ => Ok(()),
         }
@@ -161,6 +161,8 @@ pub enum Store {
     // SPDX-License-Identifier: LicenseRef-SEL
     #[cfg(all(feature = "enterprise", any(feature = "postgres", feature = "mysql")))]
     SQLReadReplica(Arc<backend::composite::read_replica::SQLReadReplica>),
+    #[cfg(feature = "enterprise")]
+    Sharded(Arc<backend::composite::sharded_data::ShardedStore>),
     // SPDX-SnippetEnd
     #[default]
     None,
@@ -179,6 +181,8 @@ pub enum BlobStore {
     // SPDX-License-Identifier: LicenseRef-SEL
     #[cfg(feature = "enterprise")]
     Sharded(Arc<backend::composite::sharded_blob::ShardedBlob>),
+    #[cfg(feature = "enterprise")]
+    Tiered(Arc<backend::composite::tiered_blob::TieredBlob>),
     // SPDX-SnippetEnd
 }
 
@@ -667,6 +671,8 @@ impl Store {
             (Store::Ephemeral(a), Store::Ephemeral(b)) => Arc::ptr_eq(a, b),
             #[cfg(all(feature = "enterprise", any(feature = "postgres", feature = "mysql")))]
             (Store::SQLReadReplica(a), Store::SQLReadReplica(b)) => Arc::ptr_eq(a, b),
+            #[cfg(feature = "enterprise")]
+            (Store::Sharded(a), Store::Sharded(b)) => Arc::ptr_eq(a, b),
             (Store::None, Store::None) => true,
             _ => false,
         }
@@ -686,6 +692,8 @@ impl Store {
             // SPDX-License-Identifier: LicenseRef-SEL
             #[cfg(all(feature = "enterprise", any(feature = "postgres", feature = "mysql")))]
             Store::SQLReadReplica(_) => true,
+            #[cfg(feature = "enterprise")]
+            Store::Sharded(store) => store.primary_store().is_sql(),
             // SPDX-SnippetEnd
             _ => false,
         }
@@ -713,17 +721,20 @@ impl Store {
 
     #[inline(always)]
     pub fn is_ephemeral(&self) -> bool {
-        matches!(self, Self::Ephemeral(_))
+        matches!(self, Self::Ephemeral(store) if !store.is_persistent())
     }
 
     // SPDX-SnippetBegin
     // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
     // SPDX-License-Identifier: LicenseRef-SEL
+    // Sharded stores are kept while accounts are placed on shards, their data
+    // would otherwise be unreachable
     #[cfg(feature = "enterprise")]
     pub fn downgrade_store(self) -> Self {
         match self {
             #[cfg(any(feature = "postgres", feature = "mysql"))]
             Store::SQLReadReplica(store) => store.primary_store().clone(),
+            Store::Sharded(store) if !store.has_placements() => store.primary_store().clone(),
             other => other,
         }
     }
@@ -733,9 +744,23 @@ impl Store {
         match self {
             #[cfg(any(feature = "postgres", feature = "mysql"))]
             Store::SQLReadReplica(_) => true,
+            Store::Sharded(_) => true,
             _ => false,
         }
     }
+
+    #[cfg(feature = "enterprise")]
+    pub fn is_sharded(&self) -> bool {
+        matches!(self, Store::Sharded(_))
+    }
+
+    #[cfg(feature = "enterprise")]
+    pub fn as_sharded(&self) -> Option<&backend::composite::sharded_data::ShardedStore> {
+        match self {
+            Store::Sharded(store) => Some(store),
+            _ => None,
+        }
+    }
     // SPDX-SnippetEnd
 }
 
@@ -759,6 +784,8 @@ impl std::fmt::Debug for Store {
             // SPDX-License-Identifier: LicenseRef-SEL
             #[cfg(all(feature = "enterprise", any(feature = "postgres", feature = "mysql")))]
             Self::SQLReadReplica(_) => f.debug_tuple("SQLReadReplica").finish(),
+            #[cfg(feature = "enterprise")]
+            Self::Sharded(_) => f.debug_tuple("Sharded").finish(),
             // SPDX-SnippetEnd
             Self::None => f.debug_tuple("None").finish(),
         }
@@ -8,6 +8,7 @@ use super::{BlobOp, Operation, ValueClass, ValueOp, key::DeserializeBigEndian, n
 use crate::{
     BlobStore, Deserialize, IterateParams, SerializeInfallible, Store, U16_LEN, U32_LEN, U64_LEN,
     ValueKey,
+    dispatch::compression::BlobCompression,
     write::{BatchBuilder, BlobLink, RegistryClass},
 };
 use registry::{
@@ -142,15 +143,20 @@ impl Store {
             }
         }
 
-        // Delete hashes
+        // Delete hashes, each batch modifies a single account
         let mut batch = BatchBuilder::new();
+        let mut last_account_id = None;
+        state
+            .delete_keys
+            .sort_unstable_by_key(|(account_id, _)| *account_id);
         for (account_id, op) in state.delete_keys {
-            if batch.is_large_batch() {
+            if batch.is_large_batch() || (!batch.is_empty() && account_id != last_account_id) {
                 self.write(batch.build_all())
                     .await
                     .caused_by(trc::location!())?;
                 batch = BatchBuilder::new();
             }
+            last_account_id = account_id;
 
             if let Some(account_id) = account_id {
                 batch.with_account_id(account_id);
@@ -203,6 +209,119 @@ impl Store {
 
         Ok(())
     }
+
+    // Returns the hashes of all blobs in a shard, including reserved and temporary ones
+    pub async fn blob_hashes(&self, shard_index: u8) -> trc::Result<Vec<BlobHash>> {
+        let mut from_hash = BlobHash::default();
+        let mut to_hash = BlobHash::new_max();
+        from_hash.0[0] = shard_index;
+        to_hash.0[0] = shard_index;
+        let from_key = ValueKey {
+            account_id: 0,
+            collection: 0,
+            document_id: 0,
+            class: ValueClass::Blob(BlobOp::Commit { hash: from_hash }),
+        };
+        let to_key = ValueKey {
+            account_id: u32::MAX,
+            collection: u8::MAX,
+            document_id: u32::MAX,
+            class: ValueClass::Blob(BlobOp::Link {
+                hash: to_hash,
+                to: BlobLink::Document,
+            }),
+        };
+
+        let mut hashes: Vec<BlobHash> = Vec::new();
+        self.iterate(
+            IterateParams::new(from_key, to_key).ascending().no_values(),
+            |key, _| {
+                let hash = key
+                    .get(0..BLOB_HASH_LEN)
+                    .and_then(|hash| BlobHash::try_from_hash_slice(hash).ok())
+                    .ok_or_else(|| trc::Error::corrupted_key(key, None, trc::location!()))?;
+                if hashes.last() != Some(&hash) {
+                    hashes.push(hash);
+                }
+
+                Ok(true)
+            },
+        )
+        .await
+        .caused_by(trc::location!())?;
+
+        Ok(hashes)
+    }
+
+    pub async fn recompress_blobs(
+        &self,
+        blob_store: BlobStore,
+        shard_index: u8,
+        compression: BlobCompression,
+    ) -> trc::Result<()> {
+        let started = Instant::now();
+
+        let hashes = self
+            .blob_hashes(shard_index)
+            .await
+            .caused_by(trc::location!())?;
+
+        // Rewrite blobs that were stored with different compression settings
+        let mut total_recompressed = 0;
+        for hash in hashes {
+            if blob_store
+                .recompress_blob(hash.as_ref(), compression)
+                .await
+                .caused_by(trc::location!())?
+            {
+                total_recompressed += 1;
+            }
+        }
+
+        trc::event!(
+            Store(StoreEvent::BlobStoreRecompressed),
+            Id = shard_index as u16,
+            Total = total_recompressed,
+            Elapsed = started.elapsed()
+        );
+
+        Ok(())
+    }
+
+    // Blob stores have no partial updates, each rewrapped blob is uploaded again in full
+    pub async fn rewrap_blob_keys(
+        &self,
+        blob_store: BlobStore,
+        shard_index: u8,
+    ) -> trc::Result<()> {
+        let started = Instant::now();
+
+        let hashes = self
+            .blob_hashes(shard_index)
+            .await
+            .caused_by(trc::location!())?;
+
+        // Wrap the data keys of each blob with the active master key
+        let mut total_rewrapped = 0;
+        for hash in hashes {
+            if blob_store
+                .rewrap_blob_key(hash.as_ref())
+                .await
+                .caused_by(trc::location!())?
+            {
+                total_rewrapped += 1;
+            }
+        }
+
+        trc::event!(
+            Store(StoreEvent::BlobKeysRewrapped),
+            Id = shard_index as u16,
+            Total = total_rewrapped,
+            Elapsed = started.elapsed()
+        );
+
+        Ok(())
+    }
 }
 
 struct BlobPurgeState {
@@ -5,7 +5,8 @@
  */
 
 use super::{
-    AnyKey, BlobOp, InMemoryClass, QueueClass, TaskQueueClass, TelemetryClass, ValueClass,
+    AnyKey, BlobOp, InMemoryClass, QueueClass, TaskQueueClass, TelemetryClass, UsageClass,
+    ValueClass,
 };
 use crate::{
     IndexKey, IndexKeyPrefix, Key, LogKey, SUBSPACE_ACL, SUBSPACE_BLOB_LINK, SUBSPACE_COUNTER,
@@ -353,8 +354,34 @@ impl ValueClass {
             ValueClass::DocumentId => serializer.write(account_id).write(collection),
             ValueClass::ChangeId => serializer.write(account_id),
             ValueClass::Quota => serializer.write(account_id).write(u8::MAX),
+            ValueClass::Usage(usage) => {
+                let serializer = serializer.write(account_id).write(USAGE_MARKER);
+                match usage {
+                    UsageClass::Collection(collection) => serializer.write(0u8).write(*collection),
+                    UsageClass::Mailbox(mailbox_id) => serializer.write(1u8).write(*mailbox_id),
+                    UsageClass::SizeBucket(bucket) => serializer.write(2u8).write(*bucket),
+                    UsageClass::SizeBucketItems(bucket) => serializer.write(3u8).write(*bucket),
+                }
+            }
             ValueClass::TenantQuota(tenant_id) => serializer.write(*tenant_id).write(u8::MAX - 1),
             ValueClass::NodeId(node_id) => serializer.write(u32::MAX).write(*node_id),
+            ValueClass::WriteFence => serializer.write(u32::MAX).write(u16::MAX).write(u8::MAX),
+            ValueClass::WriteFenceAck(node_id) => serializer
+                .write(u32::MAX)
+                .write(u16::MAX)
+                .write(u8::MAX)
+                .write(*node_id),
+            ValueClass::AccountFence(account_id) => serializer
+                .write(u32::MAX)
+                .write(u16::MAX)
+                .write(u8::MAX - 1)
+                .write(*account_id),
+            ValueClass::AccountFenceAck(account_id, node_id) => serializer
+                .write(u32::MAX)
+                .write(u16::MAX)
+                .write(u8::MAX - 1)
+                .write(*account_id)
+                .write(*node_id),
             ValueClass::ShareNotification {
                 notification_id,
                 notify_account_id,
@@ -466,6 +493,7 @@ impl<T: AsRef<[u8]> + Sync + Send + Clone> Key for AnyKey<T> {
 
 const MAILBOX_COLLECTION: u8 = Collection::Mailbox as u8;
 const MAILBOX_COUNTER_FIELD: u8 = MailboxField::UidCounter as u8;
+const USAGE_MARKER: u8 = u8::MAX - 2;
 const REG_ARCHIVED_ITEM: u16 = ObjectType::ArchivedItem as u16;
 const REG_SPAM_SAMPLE: u16 = ObjectType::SpamTrainingSample as u16;
 const REG_ACCOUNT: u16 = ObjectType::Account as u16;
@@ -524,8 +552,16 @@ impl ValueClass {
             },
             ValueClass::DocumentId | ValueClass::Quota | ValueClass::TenantQuota(_) => U32_LEN + 1,
             ValueClass::ChangeId => U32_LEN,
+            ValueClass::Usage(usage) => match usage {
+                UsageClass::Mailbox(_) => (U32_LEN * 2) + 2,
+                _ => U32_LEN + 3,
+            },
             ValueClass::ShareNotification { .. } => U32_LEN + U64_LEN + 1,
             ValueClass::NodeId(_) => (U16_LEN * 3) + 1,
+            ValueClass::WriteFence => (U16_LEN * 3) + 2,
+            ValueClass::WriteFenceAck(_) => (U16_LEN * 4) + 2,
+            ValueClass::AccountFence(_) => (U32_LEN * 2) + U16_LEN + 2,
+            ValueClass::AccountFenceAck(_, _) => (U32_LEN * 2) + (U16_LEN * 2) + 2,
             ValueClass::SearchIndex(v) => match &v.typ {
                 SearchIndexType::Term { hash, .. } => U64_LEN + hash.len() + 2,
                 SearchIndexType::Index { field, .. } => 1 + field.data.len() + U64_LEN,
@@ -575,7 +611,11 @@ impl ValueClass {
                 }
                 RegistryClass::IdCounter { .. } => SUBSPACE_COUNTER,
             },
-            ValueClass::NodeId(_) => SUBSPACE_REGISTRY_PK,
+            ValueClass::NodeId(_)
+            | ValueClass::WriteFence
+            | ValueClass::WriteFenceAck(_)
+            | ValueClass::AccountFence(_)
+            | ValueClass::AccountFenceAck(_, _) => SUBSPACE_REGISTRY_PK,
             ValueClass::InMemory(lookup) => match lookup {
                 InMemoryClass::Key(_) => SUBSPACE_IN_MEMORY_VALUE,
                 InMemoryClass::Counter(_) => SUBSPACE_IN_MEMORY_COUNTER,
@@ -592,6 +632,7 @@ impl ValueClass {
             ValueClass::DocumentId
             | ValueClass::ChangeId
             | ValueClass::Quota
+            | ValueClass::Usage(_)
             | ValueClass::TenantQuota(_) => SUBSPACE_COUNTER,
             ValueClass::ShareNotification { .. } => SUBSPACE_LOGS,
             ValueClass::SearchIndex(_) => SUBSPACE_SEARCH_INDEX,
@@ -634,6 +675,55 @@ impl From<BlobOp> for ValueClass {
     }
 }
 
+impl UsageClass {
+    // Parses a usage counter key, without the subspace prefix
+    pub fn deserialize(key: &[u8]) -> Option<Self> {
+        if key.get(U32_LEN) != Some(&USAGE_MARKER) {
+            return None;
+        }
+        let value = key.get(U32_LEN + 2..)?;
+        match (key[U32_LEN + 1], value.len()) {
+            (0, 1) => Some(UsageClass::Collection(value[0])),
+            (1, U32_LEN) => value.deserialize_be_u32(0).ok().map(UsageClass::Mailbox),
+            (2, 1) => Some(UsageClass::SizeBucket(value[0])),
+            (3, 1) => Some(UsageClass::SizeBucketItems(value[0])),
+            _ => None,
+        }
+    }
+}
+
+// Returns the offset of the id of the account owning a key, None for keys
+// shared by all accounts. Keys are expected without the subspace prefix.
+pub(crate) fn account_key_offset(subspace: u8, key: &[u8]) -> Option<usize> {
+    match subspace {
+        SUBSPACE_PROPERTY | SUBSPACE_INDEXES => Some(0),
+        // Share notifications are written by the sharing account
+        SUBSPACE_LOGS => {
+            (key.get(U32_LEN) != Some(&u8::from(SyncCollection::ShareNotification))).then_some(0)
+        }
+        // Registry id counters and tenant quotas are not account data
+        SUBSPACE_COUNTER => (key.len() != U32_LEN + 1 || key[U32_LEN] != u8::MAX - 1).then_some(0),
+        // ACLs are prefixed by the grantee and owned by the shared object's account
+        SUBSPACE_ACL => Some(U32_LEN),
+        // Blob commits and links to other objects are not account data
+        SUBSPACE_BLOB_LINK => matches!(
+            key.len().checked_sub(BLOB_HASH_LEN),
+            Some(len) if len == (U32_LEN * 2) + 1 || len == U32_LEN + U64_LEN
+        )
+        .then_some(BLOB_HASH_LEN),
+        // Tracing indexes are not account data
+        SUBSPACE_SEARCH_INDEX => key
+            .first()
+            .is_some_and(|class| class & 0x3f != SearchIndex::Tracing.to_u8())
+            .then_some(1),
+        _ => None,
+    }
+}
+
+pub(crate) fn key_account_id(subspace: u8, key: &[u8]) -> Option<u32> {
+    account_key_offset(subspace, key).and_then(|offset| key.deserialize_be_u32(offset).ok())
+}
+
 impl SearchIndex {
     pub fn to_u8(&self) -> u8 {
         match self {
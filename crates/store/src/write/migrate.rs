@@ -0,0 +1,642 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{
+    AnyClass, AnyKey, BatchBuilder, Operation, SearchIndex, ValueClass,
+    key::{DeserializeBigEndian, KeySerializer, key_account_id},
+};
+use crate::{
+    BlobStore, IterateParams, RegistryStore, SUBSPACE_ACL, SUBSPACE_BLOB_LINK, SUBSPACE_COUNTER,
+    SUBSPACE_DELETED_ITEMS, SUBSPACE_DIRECTORY, SUBSPACE_INDEXES, SUBSPACE_LOGS, SUBSPACE_PROPERTY,
+    SUBSPACE_QUEUE_EVENT, SUBSPACE_QUEUE_MESSAGE, SUBSPACE_QUOTA, SUBSPACE_REGISTRY,
+    SUBSPACE_REGISTRY_IDX, SUBSPACE_REGISTRY_PK, SUBSPACE_REPORT_IN, SUBSPACE_REPORT_OUT,
+    SUBSPACE_SEARCH_INDEX, SUBSPACE_SPAM_SAMPLES, SUBSPACE_TASK_QUEUE, SUBSPACE_TELEMETRY_METRIC,
+    SUBSPACE_TELEMETRY_SPAN, SerializeInfallible, Store, U16_LEN, U32_LEN, ValueKey,
+};
+use ahash::AHashMap;
+use arc_swap::ArcSwapOption;
+use registry::schema::enums::ClusterNodeStatus;
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+use trc::{AddContext, StoreEvent};
+use types::{blob_hash::BLOB_HASH_LEN, collection::Collection};
+
+// Subspaces copied during a migration, in-memory and search index subspaces
+// are not migrated.
+pub const MIGRATION_SUBSPACES: &[u8] = &[
+    SUBSPACE_COUNTER,
+    SUBSPACE_QUOTA,
+    SUBSPACE_REGISTRY,
+    SUBSPACE_REGISTRY_IDX,
+    SUBSPACE_REGISTRY_PK,
+    SUBSPACE_DIRECTORY,
+    SUBSPACE_ACL,
+    SUBSPACE_BLOB_LINK,
+    SUBSPACE_PROPERTY,
+    SUBSPACE_INDEXES,
+    SUBSPACE_LOGS,
+    SUBSPACE_DELETED_ITEMS,
+    SUBSPACE_SPAM_SAMPLES,
+    SUBSPACE_QUEUE_MESSAGE,
+    SUBSPACE_QUEUE_EVENT,
+    SUBSPACE_REPORT_OUT,
+    SUBSPACE_REPORT_IN,
+    SUBSPACE_TELEMETRY_SPAN,
+    SUBSPACE_TELEMETRY_METRIC,
+    SUBSPACE_TASK_QUEUE,
+];
+
+const CHUNK_SIZE: usize = 1000;
+
+// Write fence keys, stored after the node leases in the registry subspace
+const WRITE_FENCE_PREFIX: &[u8] = &[u8::MAX; U32_LEN + U16_LEN + 1];
+
+// Local copy of the fence persisted in the data store
+static WRITE_FENCE: ArcSwapOption<Store> = ArcSwapOption::const_empty();
+static WRITES_IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
+
+pub struct StoreMigration {
+    source: Store,
+    target: Store,
+    copy_blobs: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SyncProgress {
+    pub keys_written: u64,
+    pub is_done: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyFilter {
+    All,
+    Account(u32),
+}
+
+impl StoreMigration {
+    pub fn new(source: Store, target: Store) -> Self {
+        Self {
+            source,
+            target,
+            copy_blobs: false,
+        }
+    }
+
+    // Also copies the blobs committed in the source store, used when the
+    // data store is also the blob store
+    pub fn with_blobs(mut self, copy_blobs: bool) -> Self {
+        self.copy_blobs = copy_blobs;
+        self
+    }
+
+    // Copies up to `max_keys` keys of a subspace, resuming from the last key
+    // present in the target store
+    pub async fn copy_subspace(&self, subspace: u8, max_keys: usize) -> trc::Result<SyncProgress> {
+        let mut from_key = vec![0u8];
+        self.target
+            .iterate(
+                IterateParams::new(
+                    AnyKey {
+                        subspace,
+                        key: vec![0u8],
+                    },
+                    AnyKey {
+                        subspace,
+                        key: vec![u8::MAX; 32],
+                    },
+                )
+                .descending()
+                .only_first()
+                .no_values(),
+                |key, _| {
+                    from_key = key.to_vec();
+                    Ok(false)
+                },
+            )
+            .await
+            .caused_by(trc::location!())?;
+
+        self.sync_range(
+            subspace,
+            from_key,
+            vec![u8::MAX; 32],
+            max_keys,
+            KeyFilter::All,
+        )
+        .await
+    }
+
+    // Synchronizes all keys of an account
+    pub async fn sync_account(&self, account_id: u32) -> trc::Result<u64> {
+        let mut keys_written = 0;
+        for (subspace, from_key, to_key) in account_key_ranges(account_id) {
+            keys_written += self
+                .sync_range(
+                    subspace,
+                    from_key,
+                    to_key,
+                    usize::MAX,
+                    KeyFilter::Account(account_id),
+                )
+                .await?
+                .keys_written;
+        }
+
+        Ok(keys_written)
+    }
+
+    // Tails the change log by comparing the last change id of each account and
+    // synchronizes the accounts that changed since they were copied
+    pub async fn sync_changed_accounts(&self) -> trc::Result<u64> {
+        let source = read_change_ids(&self.source).await?;
+        let mut target = read_change_ids(&self.target).await?;
+        let mut keys_written = 0;
+
+        for (account_id, change_id) in source {
+            if target.remove(&account_id) != Some(change_id) {
+                keys_written += self.sync_account(account_id).await?;
+            }
+        }
+
+        // Accounts removed from the source store
+        for account_id in target.into_keys() {
+            keys_written += self.sync_account(account_id).await?;
+        }
+
+        Ok(keys_written)
+    }
+
+    // Compares every key of the migrated subspaces and copies the differences,
+    // used as a final check since writes that do not bump a change id are also detected
+    pub async fn sync_all(&self) -> trc::Result<u64> {
+        let mut keys_written = 0;
+        for subspace in MIGRATION_SUBSPACES {
+            keys_written += self
+                .sync_range(
+                    *subspace,
+                    vec![0u8],
+                    vec![u8::MAX; 32],
+                    usize::MAX,
+                    KeyFilter::All,
+                )
+                .await?
+                .keys_written;
+        }
+
+        Ok(keys_written)
+    }
+
+    async fn sync_range(
+        &self,
+        subspace: u8,
+        mut from_key: Vec<u8>,
+        to_key: Vec<u8>,
+        max_keys: usize,
+        filter: KeyFilter,
+    ) -> trc::Result<SyncProgress> {
+        let mut progress = SyncProgress::default();
+        let mut keys_read = 0;
+
+        loop {
+            let source = read_range(
+                &self.source,
+                subspace,
+                &from_key,
+                &to_key,
+                CHUNK_SIZE,
+                filter,
+            )
+            .await?;
+            let is_last = source.len() < CHUNK_SIZE;
+            let chunk_end = match source.last() {
+                Some((key, _)) if !is_last => key.clone(),
+                _ => to_key.clone(),
+            };
+            let mut target = read_range(
+                &self.target,
+                subspace,
+                &from_key,
+                &chunk_end,
+                usize::MAX,
+                filter,
+            )
+            .await?
+            .into_iter()
+            .collect::<AHashMap<_, _>>();
+            keys_read += source.len();
+
+            let mut batch = BatchBuilder::new();
+            let mut deleted_blobs = Vec::new();
+            for (key, value) in source {
+                let old_value = target.remove(&key);
+                if old_value.as_ref() == Some(&value) {
+                    continue;
+                }
+
+                if self.copy_blobs
+                    && subspace == SUBSPACE_BLOB_LINK
+                    && key.len() == BLOB_HASH_LEN
+                    && old_value.is_none()
+                {
+                    self.copy_blob(&key).await?;
+                }
+
+                match subspace {
+                    SUBSPACE_COUNTER | SUBSPACE_QUOTA => {
+                        batch.add(
+                            ValueClass::Any(AnyClass {
+                                subspace,
+                                key: key.clone(),
+                            }),
+                            counter_value(&value) - old_value.as_deref().map_or(0, counter_value),
+                        );
+                    }
+                    SUBSPACE_INDEXES => {
+                        index_op(&mut batch, &key, true)?;
+                    }
+                    _ => {
+                        batch.set(
+                            ValueClass::Any(AnyClass {
+                                subspace,
+                                key: key.clone(),
+                            }),
+                            value,
+                        );
+                    }
+                }
+
+                progress.keys_written += 1;
+                if batch.is_large_batch() {
+                    self.target
+                        .write(std::mem::take(&mut batch).build_all())
+                        .await
+                        .caused_by(trc::location!())?;
+                }
+            }
+
+            // Remove keys that no longer exist in the source store
+            for key in target.into_keys() {
+                if subspace == SUBSPACE_INDEXES {
+                    index_op(&mut batch, &key, false)?;
+                } else {
+                    if self.copy_blobs
+                        && subspace == SUBSPACE_BLOB_LINK
+                        && key.len() == BLOB_HASH_LEN
+                    {
+                        deleted_blobs.push(key.clone());
+                    }
+
+                    batch.clear(ValueClass::Any(AnyClass { subspace, key }));
+                }
+
+                progress.keys_written += 1;
+                if batch.is_large_batch() {
+                    self.target
+                        .write(std::mem::take(&mut batch).build_all())
+                        .await
+                        .caused_by(trc::location!())?;
+                }
+            }
+
+            if !batch.is_empty() {
+                self.target
+                    .write(batch.build_all())
+                    .await
+                    .caused_by(trc::location!())?;
+            }
+
+            for key in deleted_blobs {
+                self.delete_blob(&key).await?;
+            }
+
+            if is_last {
+                progress.is_done = true;
+                return Ok(progress);
+            } else if keys_read >= max_keys {
+                return Ok(progress);
+            }
+
+            from_key = chunk_end;
+            from_key.push(0);
+        }
+    }
+
+    async fn copy_blob(&self, key: &[u8]) -> trc::Result<()> {
+        let source = BlobStore::Store(self.source.clone());
+        let target = BlobStore::Store(self.target.clone());
+
+        if let Some(data) = source.get_stored_blob(key).await? {
+            target.put_stored_blob(key, &data).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn delete_blob(&self, key: &[u8]) -> trc::Result<()> {
+        BlobStore::Store(self.target.clone())
+            .delete_stored_blob(key)
+            .await
+            .map(|_| ())
+    }
+}
+
+pub(crate) struct InFlightWrite;
+
+impl Store {
+    // Registers a write so that fencing waits for it to complete, fails
+    // if the store is fenced
+    pub(crate) fn begin_write(&self) -> trc::Result<InFlightWrite> {
+        WRITES_IN_FLIGHT.fetch_add(1, Ordering::SeqCst);
+        let in_flight = InFlightWrite;
+
+        if !self.is_write_fenced() {
+            Ok(in_flight)
+        } else {
+            Err(StoreEvent::WriteFenced
+                .into_err()
+                .caused_by(trc::location!()))
+        }
+    }
+
+    pub fn is_write_fenced(&self) -> bool {
+        WRITE_FENCE.load().as_ref().is_some_and(|store| {
+            if store.is_same(self) {
+                return true;
+            }
+
+            // SPDX-SnippetBegin
+            // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+            // SPDX-License-Identifier: LicenseRef-SEL
+            #[cfg(feature = "enterprise")]
+            if let Some(sharded) = self.as_sharded() {
+                return sharded.primary_store().is_same(store);
+            }
+            // SPDX-SnippetEnd
+
+            false
+        })
+    }
+}
+
+impl Drop for InFlightWrite {
+    fn drop(&mut self) {
+        WRITES_IN_FLIGHT.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl RegistryStore {
+    // Persists a write fence in the data store, nodes stop writing once they
+    // apply it with `sync_write_fence`. Returns the fence id.
+    pub async fn fence_writes(&self) -> trc::Result<u64> {
+        let fence_id = rand::random::<u64>();
+        let mut batch = BatchBuilder::new();
+        batch.set(ValueClass::WriteFence, fence_id.serialize());
+        self.store()
+            .write_unfenced(batch.build_all())
+            .await
+            .caused_by(trc::location!())?;
+
+        Ok(fence_id)
+    }
+
+    // Removes the write fence and the acknowledgements of all nodes
+    pub async fn unfence_writes(&self) -> trc::Result<()> {
+        let mut batch = BatchBuilder::new();
+        batch.clear(ValueClass::WriteFence);
+        self.store()
+            .iterate(
+                IterateParams::new(
+                    ValueKey::from(ValueClass::WriteFenceAck(0)),
+                    ValueKey::from(ValueClass::WriteFenceAck(u16::MAX)),
+                )
+                .no_values(),
+                |key, _| {
+                    batch.clear(ValueClass::WriteFenceAck(
+                        key.deserialize_be_u16(WRITE_FENCE_PREFIX.len())?,
+                    ));
+                    Ok(true)
+                },
+            )
+            .await
+            .caused_by(trc::location!())?;
+        self.store()
+            .write_unfenced(batch.build_all())
+            .await
+            .caused_by(trc::location!())?;
+
+        self.sync_write_fence().await
+    }
+
+    // Applies the write fence persisted in the data store to this node and,
+    // once all in-flight writes have completed, acknowledges it
+    pub async fn sync_write_fence(&self) -> trc::Result<()> {
+        let store = self.store();
+        let Some(fence_id) = store
+            .get_value::<u64>(ValueKey::from(ValueClass::WriteFence))
+            .await
+            .caused_by(trc::location!())?
+        else {
+            if WRITE_FENCE
+                .load()
+                .as_ref()
+                .is_some_and(|fenced| fenced.is_same(store))
+            {
+                WRITE_FENCE.store(None);
+            }
+            return Ok(());
+        };
+
+        WRITE_FENCE.store(Some(Arc::new(store.clone())));
+        while WRITES_IN_FLIGHT.load(Ordering::SeqCst) > 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let mut batch = BatchBuilder::new();
+        batch.set(
+            ValueClass::WriteFenceAck(self.node_id()),
+            fence_id.serialize(),
+        );
+        store
+            .write_unfenced(batch.build_all())
+            .await
+            .caused_by(trc::location!())
+            .map(|_| ())
+    }
+
+    // Returns whether all active nodes have stopped writing to the data store
+    pub async fn is_write_fence_acknowledged(&self, fence_id: u64) -> trc::Result<bool> {
+        for node in self.cluster_node_list().await? {
+            if node.status == ClusterNodeStatus::Active
+                && self
+                    .store()
+                    .get_value::<u64>(ValueKey::from(ValueClass::WriteFenceAck(
+                        node.node_id as u16,
+                    )))
+                    .await
+                    .caused_by(trc::location!())?
+                    != Some(fence_id)
+            {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+// Returns the key ranges holding the data of an account, keys owned by other
+// accounts are also found in the ranges not prefixed by the account id
+pub(crate) fn account_key_ranges(account_id: u32) -> Vec<(u8, Vec<u8>, Vec<u8>)> {
+    let range = |prefix: Vec<u8>| {
+        let mut to_key = prefix.clone();
+        to_key.extend_from_slice(&[u8::MAX; 32]);
+        (prefix, to_key)
+    };
+    let mut ranges = Vec::new();
+
+    for subspace in [
+        SUBSPACE_COUNTER,
+        SUBSPACE_PROPERTY,
+        SUBSPACE_INDEXES,
+        SUBSPACE_LOGS,
+    ] {
+        let (from_key, to_key) = range(account_id.to_be_bytes().to_vec());
+        ranges.push((subspace, from_key, to_key));
+    }
+    for typ in 0..3u8 {
+        for index in [
+            SearchIndex::Email,
+            SearchIndex::Calendar,
+            SearchIndex::Contacts,
+            SearchIndex::File,
+        ] {
+            let (from_key, to_key) = range(
+                KeySerializer::new(U32_LEN + 1)
+                    .write(index.to_u8() | typ << 6)
+                    .write(account_id)
+                    .finalize(),
+            );
+            ranges.push((SUBSPACE_SEARCH_INDEX, from_key, to_key));
+        }
+    }
+    for subspace in [SUBSPACE_ACL, SUBSPACE_BLOB_LINK] {
+        ranges.push((subspace, vec![0u8], vec![u8::MAX; 32]));
+    }
+
+    ranges
+}
+
+// Reads a range of keys, counters are always returned as little endian integers
+async fn read_range(
+    store: &Store,
+    subspace: u8,
+    from_key: &[u8],
+    to_key: &[u8],
+    limit: usize,
+    filter: KeyFilter,
+) -> trc::Result<Vec<(Vec<u8>, Vec<u8>)>> {
+    let is_counter = matches!(subspace, SUBSPACE_COUNTER | SUBSPACE_QUOTA);
+    let with_values = !matches!(subspace, SUBSPACE_INDEXES | SUBSPACE_REGISTRY_IDX)
+        && !(is_counter && store.is_sql());
+
+    let mut entries = Vec::new();
+    store
+        .iterate(
+            IterateParams::new(
+                AnyKey {
+                    subspace,
+                    key: from_key.to_vec(),
+                },
+                AnyKey {
+                    subspace,
+                    key: to_key.to_vec(),
+                },
+            )
+            .set_values(with_values),
+            |key, value| {
+                if match filter {
+                    // Write and account fences are never copied to the target store
+                    KeyFilter::All => {
+                        subspace != SUBSPACE_REGISTRY_PK
+                            || key.len() <= U32_LEN + U16_LEN
+                            || !key.starts_with(&WRITE_FENCE_PREFIX[..U32_LEN + U16_LEN])
+                    }
+                    KeyFilter::Account(account_id) => {
+                        key_account_id(subspace, key) == Some(account_id)
+                    }
+                } {
+                    entries.push((
+                        key.to_vec(),
+                        if with_values { value.to_vec() } else { vec![] },
+                    ));
+                }
+
+                Ok(entries.len() < limit)
+            },
+        )
+        .await
+        .caused_by(trc::location!())?;
+
+    if is_counter && store.is_sql() {
+        for (key, value) in &mut entries {
+            *value = store
+                .get_counter(ValueClass::Any(AnyClass {
+                    subspace,
+                    key: key.clone(),
+                }))
+                .await
+                .caused_by(trc::location!())?
+                .to_le_bytes()
+                .to_vec();
+        }
+    }
+
+    Ok(entries)
+}
+
+// Returns the last change id of each account
+async fn read_change_ids(store: &Store) -> trc::Result<AHashMap<u32, i64>> {
+    read_range(
+        store,
+        SUBSPACE_COUNTER,
+        &[0u8],
+        &[u8::MAX; 32],
+        usize::MAX,
+        KeyFilter::All,
+    )
+    .await?
+    .into_iter()
+    .filter(|(key, _)| key.len() == U32_LEN)
+    .map(|(key, value)| Ok((key.as_slice().deserialize_be_u32(0)?, counter_value(&value))))
+    .collect()
+}
+
+fn index_op(batch: &mut BatchBuilder, key: &[u8], set: bool) -> trc::Result<()> {
+    if key.len() < (U32_LEN * 2) + 2 {
+        return Err(trc::Error::corrupted_key(key, None, trc::location!()));
+    }
+
+    batch
+        .with_account_id(key.deserialize_be_u32(0)?)
+        .with_collection(Collection::from(key[U32_LEN]))
+        .with_document(key.deserialize_be_u32(key.len() - U32_LEN)?)
+        .any_op(Operation::Index {
+            field: key[U32_LEN + 1],
+            key: key[U32_LEN + 2..key.len() - U32_LEN].to_vec(),
+            set,
+        });
+
+    Ok(())
+}
+
+fn counter_value(value: &[u8]) -> i64 {
+    value.try_into().map(i64::from_le_bytes).unwrap_or_default()
+}
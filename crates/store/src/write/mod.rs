@@ -18,7 +18,7 @@ use types::{
     blob_hash::BlobHash,
     collection::{Collection, SyncCollection, VanishedCollection},
     field::{
-        CalendarEventField, CalendarNotificationField, ContactField, EmailField,
+        CalendarEventField, CalendarField, CalendarNotificationField, ContactField, EmailField,
         EmailSubmissionField, Field, MailboxField, PrincipalField, SieveField,
     },
 };
@@ -33,6 +33,7 @@ pub mod bitpack;
 pub mod blob;
 pub mod key;
 pub mod log;
+pub mod migrate;
 pub mod serialize;
 
 pub(crate) const ARCHIVE_ALIGNMENT: usize = 16;
@@ -200,8 +201,21 @@ pub enum ValueClass {
     DocumentId,
     ChangeId,
     Quota,
+    Usage(UsageClass),
     TenantQuota(u32),
     NodeId(u16),
+    WriteFence,
+    WriteFenceAck(u16),
+    AccountFence(u32),
+    AccountFenceAck(u32, u16),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub enum UsageClass {
+    Collection(u8),
+    Mailbox(u32),
+    SizeBucket(u8),
+    SizeBucketItems(u8),
 }
 
 #[derive(Debug, PartialEq, Clone, Eq, Hash)]
@@ -522,6 +536,12 @@ impl From<CalendarEventField> for ValueClass {
     }
 }
 
+impl From<CalendarField> for ValueClass {
+    fn from(value: CalendarField) -> Self {
+        ValueClass::Property(value.into())
+    }
+}
+
 impl From<CalendarNotificationField> for ValueClass {
     fn from(value: CalendarNotificationField) -> Self {
         ValueClass::Property(value.into())
@@ -6,8 +6,8 @@
 
 // This file is auto-generated. Do not edit directly.
 
-pub const TOTAL_EVENT_COUNT: usize = 637;
-pub const TOTAL_METRIC_COUNT: usize = 369;
+pub const TOTAL_EVENT_COUNT: usize = 654;
+pub const TOTAL_METRIC_COUNT: usize = 372;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum EventType {
@@ -824,6 +824,22 @@ pub enum StoreEvent {
     BlobRead = 508,
     BlobWrite = 509,
     BlobDelete = 506,
+    BlobReadHot = 638,
+    BlobReadCold = 639,
+    BlobMigrated = 640,
+    BlobStoreMigrated = 641,
+    BlobStoreRecompressed = 642,
+    CompressionDictionaryTrained = 643,
+    BlobKeysRewrapped = 644,
+    DataStoreSynced = 645,
+    DataStoreMigrated = 646,
+    WriteFenced = 647,
+    BackupCompleted = 648,
+    BackupDeleted = 649,
+    ConsistencyIssue = 650,
+    ConsistencyIssueRepaired = 651,
+    ConsistencyCheckCompleted = 652,
+    AccountMoved = 653,
     SqlQuery = 531,
     LdapQuery = 521,
     LdapWarning = 519,
@@ -904,6 +920,7 @@ pub enum WebDavEvent {
     Lock = 569,
     Unlock = 570,
     Acl = 571,
+    Search = 637,
     Options = 573,
     Error = 572,
 }
@@ -1268,6 +1285,9 @@ pub enum MetricType {
     StoreBlobRead = 325,
     StoreBlobWrite = 326,
     StoreBlobDelete = 327,
+    StoreBlobReadHot = 369,
+    StoreBlobReadCold = 370,
+    StoreBlobMigrated = 371,
     TaskManagerBlobNotFound = 328,
     TaskManagerMetadataNotFound = 329,
     TelemetryAlertEvent = 338,
@@ -594,6 +594,22 @@ impl EventType {
             b"store.blob-read" => EventType::Store(StoreEvent::BlobRead),
             b"store.blob-write" => EventType::Store(StoreEvent::BlobWrite),
             b"store.blob-delete" => EventType::Store(StoreEvent::BlobDelete),
+            b"store.blob-read-hot" => EventType::Store(StoreEvent::BlobReadHot),
+            b"store.blob-read-cold" => EventType::Store(StoreEvent::BlobReadCold),
+            b"store.blob-migrated" => EventType::Store(StoreEvent::BlobMigrated),
+            b"store.blob-store-migrated" => EventType::Store(StoreEvent::BlobStoreMigrated),
+            b"store.blob-store-recompressed" => EventType::Store(StoreEvent::BlobStoreRecompressed),
+            b"store.compression-dictionary-trained" => EventType::Store(StoreEvent::CompressionDictionaryTrained),
+            b"store.blob-keys-rewrapped" => EventType::Store(StoreEvent::BlobKeysRewrapped),
+            b"store.data-store-synced" => EventType::Store(StoreEvent::DataStoreSynced),
+            b"store.data-store-migrated" => EventType::Store(StoreEvent::DataStoreMigrated),
+            b"store.write-fenced" => EventType::Store(StoreEvent::WriteFenced),
+            b"store.backup-completed" => EventType::Store(StoreEvent::BackupCompleted),
+            b"store.backup-deleted" => EventType::Store(StoreEvent::BackupDeleted),
+            b"store.consistency-issue" => EventType::Store(StoreEvent::ConsistencyIssue),
+            b"store.consistency-issue-repaired" => EventType::Store(StoreEvent::ConsistencyIssueRepaired),
+            b"store.consistency-check-completed" => EventType::Store(StoreEvent::ConsistencyCheckCompleted),
+            b"store.account-moved" => EventType::Store(StoreEvent::AccountMoved),
             b"store.sql-query" => EventType::Store(StoreEvent::SqlQuery),
             b"store.ldap-query" => EventType::Store(StoreEvent::LdapQuery),
             b"store.ldap-warning" => EventType::Store(StoreEvent::LdapWarning),
@@ -649,6 +665,7 @@ impl EventType {
             b"web-dav.lock" => EventType::WebDav(WebDavEvent::Lock),
             b"web-dav.unlock" => EventType::WebDav(WebDavEvent::Unlock),
             b"web-dav.acl" => EventType::WebDav(WebDavEvent::Acl),
+            b"web-dav.search" => EventType::WebDav(WebDavEvent::Search),
             b"web-dav.options" => EventType::WebDav(WebDavEvent::Options),
             b"web-dav.error" => EventType::WebDav(WebDavEvent::Error),
         }
@@ -1355,6 +1372,28 @@ impl EventType {
             EventType::Store(StoreEvent::BlobRead) => "store.blob-read",
             EventType::Store(StoreEvent::BlobWrite) => "store.blob-write",
             EventType::Store(StoreEvent::BlobDelete) => "store.blob-delete",
+            EventType::Store(StoreEvent::BlobReadHot) => "store.blob-read-hot",
+            EventType::Store(StoreEvent::BlobReadCold) => "store.blob-read-cold",
+            EventType::Store(StoreEvent::BlobMigrated) => "store.blob-migrated",
+            EventType::Store(StoreEvent::BlobStoreMigrated) => "store.blob-store-migrated",
+            EventType::Store(StoreEvent::BlobStoreRecompressed) => "store.blob-store-recompressed",
+            EventType::Store(StoreEvent::CompressionDictionaryTrained) => {
+                "store.compression-dictionary-trained"
+            }
+            EventType::Store(StoreEvent::BlobKeysRewrapped) => "store.blob-keys-rewrapped",
+            EventType::Store(StoreEvent::DataStoreSynced) => "store.data-store-synced",
+            EventType::Store(StoreEvent::DataStoreMigrated) => "store.data-store-migrated",
+            EventType::Store(StoreEvent::WriteFenced) => "store.write-fenced",
+            EventType::Store(StoreEvent::BackupCompleted) => "store.backup-completed",
+            EventType::Store(StoreEvent::BackupDeleted) => "store.backup-deleted",
+            EventType::Store(StoreEvent::ConsistencyIssue) => "store.consistency-issue",
+            EventType::Store(StoreEvent::ConsistencyIssueRepaired) => {
+                "store.consistency-issue-repaired"
+            }
+            EventType::Store(StoreEvent::ConsistencyCheckCompleted) => {
+                "store.consistency-check-completed"
+            }
+            EventType::Store(StoreEvent::AccountMoved) => "store.account-moved",
             EventType::Store(StoreEvent::SqlQuery) => "store.sql-query",
             EventType::Store(StoreEvent::LdapQuery) => "store.ldap-query",
             EventType::Store(StoreEvent::LdapWarning) => "store.ldap-warning",
@@ -1428,6 +1467,7 @@ impl EventType {
             EventType::WebDav(WebDavEvent::Lock) => "web-dav.lock",
             EventType::WebDav(WebDavEvent::Unlock) => "web-dav.unlock",
             EventType::WebDav(WebDavEvent::Acl) => "web-dav.acl",
+            EventType::WebDav(WebDavEvent::Search) => "web-dav.search",
             EventType::WebDav(WebDavEvent::Options) => "web-dav.options",
             EventType::WebDav(WebDavEvent::Error) => "web-dav.error",
         }
@@ -2015,6 +2055,22 @@ impl EventType {
             EventType::Store(StoreEvent::BlobRead) => 508,
             EventType::Store(StoreEvent::BlobWrite) => 509,
             EventType::Store(StoreEvent::BlobDelete) => 506,
+            EventType::Store(StoreEvent::BlobReadHot) => 638,
+            EventType::Store(StoreEvent::BlobReadCold) => 639,
+            EventType::Store(StoreEvent::BlobMigrated) => 640,
+            EventType::Store(StoreEvent::BlobStoreMigrated) => 641,
+            EventType::Store(StoreEvent::BlobStoreRecompressed) => 642,
+            EventType::Store(StoreEvent::CompressionDictionaryTrained) => 643,
+            EventType::Store(StoreEvent::BlobKeysRewrapped) => 644,
+            EventType::Store(StoreEvent::DataStoreSynced) => 645,
+            EventType::Store(StoreEvent::DataStoreMigrated) => 646,
+            EventType::Store(StoreEvent::WriteFenced) => 647,
+            EventType::Store(StoreEvent::BackupCompleted) => 648,
+            EventType::Store(StoreEvent::BackupDeleted) => 649,
+            EventType::Store(StoreEvent::ConsistencyIssue) => 650,
+            EventType::Store(StoreEvent::ConsistencyIssueRepaired) => 651,
+            EventType::Store(StoreEvent::ConsistencyCheckCompleted) => 652,
+            EventType::Store(StoreEvent::AccountMoved) => 653,
             EventType::Store(StoreEvent::SqlQuery) => 531,
             EventType::Store(StoreEvent::LdapQuery) => 521,
             EventType::Store(StoreEvent::LdapWarning) => 519,
@@ -2070,6 +2126,7 @@ impl EventType {
             EventType::WebDav(WebDavEvent::Lock) => 569,
             EventType::WebDav(WebDavEvent::Unlock) => 570,
             EventType::WebDav(WebDavEvent::Acl) => 571,
+            EventType::WebDav(WebDavEvent::Search) => 637,
             EventType::WebDav(WebDavEvent::Options) => 573,
             EventType::WebDav(WebDavEvent::Error) => 572,
         }
@@ -2695,6 +2752,22 @@ impl EventType {
             508 => Some(EventType::Store(StoreEvent::BlobRead)),
             509 => Some(EventType::Store(StoreEvent::BlobWrite)),
             506 => Some(EventType::Store(StoreEvent::BlobDelete)),
+            638 => Some(EventType::Store(StoreEvent::BlobReadHot)),
+            639 => Some(EventType::Store(StoreEvent::BlobReadCold)),
+            640 => Some(EventType::Store(StoreEvent::BlobMigrated)),
+            641 => Some(EventType::Store(StoreEvent::BlobStoreMigrated)),
+            642 => Some(EventType::Store(StoreEvent::BlobStoreRecompressed)),
+            643 => Some(EventType::Store(StoreEvent::CompressionDictionaryTrained)),
+            644 => Some(EventType::Store(StoreEvent::BlobKeysRewrapped)),
+            645 => Some(EventType::Store(StoreEvent::DataStoreSynced)),
+            646 => Some(EventType::Store(StoreEvent::DataStoreMigrated)),
+            647 => Some(EventType::Store(StoreEvent::WriteFenced)),
+            648 => Some(EventType::Store(StoreEvent::BackupCompleted)),
+            649 => Some(EventType::Store(StoreEvent::BackupDeleted)),
+            650 => Some(EventType::Store(StoreEvent::ConsistencyIssue)),
+            651 => Some(EventType::Store(StoreEvent::ConsistencyIssueRepaired)),
+            652 => Some(EventType::Store(StoreEvent::ConsistencyCheckCompleted)),
+            653 => Some(EventType::Store(StoreEvent::AccountMoved)),
             531 => Some(EventType::Store(StoreEvent::SqlQuery)),
             521 => Some(EventType::Store(StoreEvent::LdapQuery)),
             519 => Some(EventType::Store(StoreEvent::LdapWarning)),
@@ -2754,6 +2827,7 @@ impl EventType {
             569 => Some(EventType::WebDav(WebDavEvent::Lock)),
             570 => Some(EventType::WebDav(WebDavEvent::Unlock)),
             571 => Some(EventType::WebDav(WebDavEvent::Acl)),
+            637 => Some(EventType::WebDav(WebDavEvent::Search)),
             573 => Some(EventType::WebDav(WebDavEvent::Options)),
             572 => Some(EventType::WebDav(WebDavEvent::Error)),
             _ => None,
@@ -3048,6 +3122,22 @@ impl EventType {
             EventType::Store(StoreEvent::BlobRead) => Level::Trace,
             EventType::Store(StoreEvent::BlobWrite) => Level::Trace,
             EventType::Store(StoreEvent::BlobDelete) => Level::Trace,
+            EventType::Store(StoreEvent::BlobReadHot) => Level::Trace,
+            EventType::Store(StoreEvent::BlobReadCold) => Level::Trace,
+            EventType::Store(StoreEvent::BlobMigrated) => Level::Trace,
+            EventType::Store(StoreEvent::BlobStoreMigrated) => Level::Info,
+            EventType::Store(StoreEvent::BlobStoreRecompressed) => Level::Info,
+            EventType::Store(StoreEvent::CompressionDictionaryTrained) => Level::Info,
+            EventType::Store(StoreEvent::BlobKeysRewrapped) => Level::Info,
+            EventType::Store(StoreEvent::DataStoreSynced) => Level::Info,
+            EventType::Store(StoreEvent::DataStoreMigrated) => Level::Info,
+            EventType::Store(StoreEvent::WriteFenced) => Level::Error,
+            EventType::Store(StoreEvent::BackupCompleted) => Level::Info,
+            EventType::Store(StoreEvent::BackupDeleted) => Level::Info,
+            EventType::Store(StoreEvent::ConsistencyIssue) => Level::Warn,
+            EventType::Store(StoreEvent::ConsistencyIssueRepaired) => Level::Info,
+            EventType::Store(StoreEvent::ConsistencyCheckCompleted) => Level::Info,
+            EventType::Store(StoreEvent::AccountMoved) => Level::Info,
             EventType::Store(StoreEvent::SqlQuery) => Level::Trace,
             EventType::Store(StoreEvent::LdapQuery) => Level::Trace,
             EventType::Acme(AcmeEvent::AuthError) => Level::Warn,
@@ -3825,6 +3915,26 @@ impl EventType {
             EventType::Store(StoreEvent::BlobRead) => "Blob read operation",
             EventType::Store(StoreEvent::BlobWrite) => "Blob write operation",
             EventType::Store(StoreEvent::BlobDelete) => "Blob delete operation",
+            EventType::Store(StoreEvent::BlobReadHot) => "Blob read from hot tier",
+            EventType::Store(StoreEvent::BlobReadCold) => "Blob read from cold tier",
+            EventType::Store(StoreEvent::BlobMigrated) => "Blob migrated to cold tier",
+            EventType::Store(StoreEvent::BlobStoreMigrated) => "Blob tier migration completed",
+            EventType::Store(StoreEvent::BlobStoreRecompressed) => "Blob recompression completed",
+            EventType::Store(StoreEvent::CompressionDictionaryTrained) => {
+                "Compression dictionary trained"
+            }
+            EventType::Store(StoreEvent::BlobKeysRewrapped) => "Blob keys rewrapped",
+            EventType::Store(StoreEvent::DataStoreSynced) => "Data store synced",
+            EventType::Store(StoreEvent::DataStoreMigrated) => "Data store migrated",
+            EventType::Store(StoreEvent::WriteFenced) => "Write rejected during data store cutover",
+            EventType::Store(StoreEvent::BackupCompleted) => "Backup completed",
+            EventType::Store(StoreEvent::BackupDeleted) => "Backup deleted",
+            EventType::Store(StoreEvent::ConsistencyIssue) => "Consistency issue found",
+            EventType::Store(StoreEvent::ConsistencyIssueRepaired) => "Consistency issue repaired",
+            EventType::Store(StoreEvent::ConsistencyCheckCompleted) => {
+                "Consistency check completed"
+            }
+            EventType::Store(StoreEvent::AccountMoved) => "Account moved",
             EventType::Store(StoreEvent::SqlQuery) => "SQL query executed",
             EventType::Store(StoreEvent::LdapQuery) => "LDAP query executed",
             EventType::Store(StoreEvent::LdapWarning) => "LDAP authentication warning",
@@ -3898,6 +4008,7 @@ impl EventType {
             EventType::WebDav(WebDavEvent::Lock) => "WebDAV LOCK request",
             EventType::WebDav(WebDavEvent::Unlock) => "WebDAV UNLOCK request",
             EventType::WebDav(WebDavEvent::Acl) => "WebDAV ACL request",
+            EventType::WebDav(WebDavEvent::Search) => "WebDAV SEARCH request",
             EventType::WebDav(WebDavEvent::Options) => "WebDAV OPTIONS request",
             EventType::WebDav(WebDavEvent::Error) => "WebDAV error",
         }
@@ -4159,6 +4270,7 @@ impl EventType {
             EventType::Store(StoreEvent::NotSupported) => "Operation not supported",
             EventType::Store(StoreEvent::UnexpectedError) => "Unexpected error",
             EventType::Store(StoreEvent::CryptoError) => "Crypto error",
+            EventType::Store(StoreEvent::WriteFenced) => "Data store is read-only during migration",
             EventType::Store(StoreEvent::HttpStoreError) => "Store error",
             EventType::Store(StoreEvent::CacheMiss) => "Store error",
             EventType::Store(StoreEvent::CacheHit) => "Store error",
@@ -4761,6 +4873,22 @@ impl EventType {
             EventType::Store(StoreEvent::BlobRead),
             EventType::Store(StoreEvent::BlobWrite),
             EventType::Store(StoreEvent::BlobDelete),
+            EventType::Store(StoreEvent::BlobReadHot),
+            EventType::Store(StoreEvent::BlobReadCold),
+            EventType::Store(StoreEvent::BlobMigrated),
+            EventType::Store(StoreEvent::BlobStoreMigrated),
+            EventType::Store(StoreEvent::BlobStoreRecompressed),
+            EventType::Store(StoreEvent::CompressionDictionaryTrained),
+            EventType::Store(StoreEvent::BlobKeysRewrapped),
+            EventType::Store(StoreEvent::DataStoreSynced),
+            EventType::Store(StoreEvent::DataStoreMigrated),
+            EventType::Store(StoreEvent::WriteFenced),
+            EventType::Store(StoreEvent::BackupCompleted),
+            EventType::Store(StoreEvent::BackupDeleted),
+            EventType::Store(StoreEvent::ConsistencyIssue),
+            EventType::Store(StoreEvent::ConsistencyIssueRepaired),
+            EventType::Store(StoreEvent::ConsistencyCheckCompleted),
+            EventType::Store(StoreEvent::AccountMoved),
             EventType::Store(StoreEvent::SqlQuery),
             EventType::Store(StoreEvent::LdapQuery),
             EventType::Store(StoreEvent::LdapWarning),
@@ -4816,6 +4944,7 @@ impl EventType {
             EventType::WebDav(WebDavEvent::Lock),
             EventType::WebDav(WebDavEvent::Unlock),
             EventType::WebDav(WebDavEvent::Acl),
+            EventType::WebDav(WebDavEvent::Search),
             EventType::WebDav(WebDavEvent::Options),
             EventType::WebDav(WebDavEvent::Error),
         ]
@@ -5184,6 +5313,9 @@ impl MetricType {
             b"store.blob-read" => MetricType::StoreBlobRead,
             b"store.blob-write" => MetricType::StoreBlobWrite,
             b"store.blob-delete" => MetricType::StoreBlobDelete,
+            b"store.blob-read-hot" => MetricType::StoreBlobReadHot,
+            b"store.blob-read-cold" => MetricType::StoreBlobReadCold,
+            b"store.blob-migrated" => MetricType::StoreBlobMigrated,
             b"task-manager.blob-not-found" => MetricType::TaskManagerBlobNotFound,
             b"task-manager.metadata-not-found" => MetricType::TaskManagerMetadataNotFound,
             b"telemetry.alert-event" => MetricType::TelemetryAlertEvent,
@@ -5569,6 +5701,9 @@ impl MetricType {
             MetricType::StoreBlobRead => "store.blob-read",
             MetricType::StoreBlobWrite => "store.blob-write",
             MetricType::StoreBlobDelete => "store.blob-delete",
+            MetricType::StoreBlobReadHot => "store.blob-read-hot",
+            MetricType::StoreBlobReadCold => "store.blob-read-cold",
+            MetricType::StoreBlobMigrated => "store.blob-migrated",
             MetricType::TaskManagerBlobNotFound => "task-manager.blob-not-found",
             MetricType::TaskManagerMetadataNotFound => "task-manager.metadata-not-found",
             MetricType::TelemetryAlertEvent => "telemetry.alert-event",
@@ -5945,6 +6080,9 @@ impl MetricType {
             MetricType::StoreBlobRead => 325,
             MetricType::StoreBlobWrite => 326,
             MetricType::StoreBlobDelete => 327,
+            MetricType::StoreBlobReadHot => 369,
+            MetricType::StoreBlobReadCold => 370,
+            MetricType::StoreBlobMigrated => 371,
             MetricType::TaskManagerBlobNotFound => 328,
             MetricType::TaskManagerMetadataNotFound => 329,
             MetricType::TelemetryAlertEvent => 338,
@@ -6319,6 +6457,9 @@ impl MetricType {
             325 => Some(MetricType::StoreBlobRead),
             326 => Some(MetricType::StoreBlobWrite),
             327 => Some(MetricType::StoreBlobDelete),
+            369 => Some(MetricType::StoreBlobReadHot),
+            370 => Some(MetricType::StoreBlobReadCold),
+            371 => Some(MetricType::StoreBlobMigrated),
             328 => Some(MetricType::TaskManagerBlobNotFound),
             329 => Some(MetricType::TaskManagerMetadataNotFound),
             338 => Some(MetricType::TelemetryAlertEvent),
@@ -6668,6 +6809,9 @@ impl MetricType {
             MetricType::StoreBlobRead => 508,
             MetricType::StoreBlobWrite => 509,
             MetricType::StoreBlobDelete => 506,
+            MetricType::StoreBlobReadHot => 638,
+            MetricType::StoreBlobReadCold => 639,
+            MetricType::StoreBlobMigrated => 640,
             MetricType::TaskManagerBlobNotFound => 141,
             MetricType::TaskManagerMetadataNotFound => 145,
             MetricType::TelemetryAlertEvent => 548,
@@ -7054,6 +7198,9 @@ impl MetricType {
             MetricType::StoreBlobRead => "Blob read operation",
             MetricType::StoreBlobWrite => "Blob write operation",
             MetricType::StoreBlobDelete => "Blob delete operation",
+            MetricType::StoreBlobReadHot => "Blob read from hot tier",
+            MetricType::StoreBlobReadCold => "Blob read from cold tier",
+            MetricType::StoreBlobMigrated => "Blob migrated to cold tier",
             MetricType::TaskManagerBlobNotFound => "Blob not found for task",
             MetricType::TaskManagerMetadataNotFound => "Metadata not found for task",
             MetricType::TelemetryAlertEvent => "Alert event triggered",
@@ -7412,6 +7559,9 @@ impl MetricType {
             | MetricType::StoreBlobRead
             | MetricType::StoreBlobWrite
             | MetricType::StoreBlobDelete
+            | MetricType::StoreBlobReadHot
+            | MetricType::StoreBlobReadCold
+            | MetricType::StoreBlobMigrated
             | MetricType::TaskManagerBlobNotFound
             | MetricType::TaskManagerMetadataNotFound
             | MetricType::TelemetryAlertEvent
@@ -7802,6 +7952,9 @@ impl MetricType {
             MetricType::StoreBlobRead,
             MetricType::StoreBlobWrite,
             MetricType::StoreBlobDelete,
+            MetricType::StoreBlobReadHot,
+            MetricType::StoreBlobReadCold,
+            MetricType::StoreBlobMigrated,
             MetricType::TaskManagerBlobNotFound,
             MetricType::TaskManagerMetadataNotFound,
             MetricType::TelemetryAlertEvent,
@@ -50,11 +50,35 @@ impl BlobHash {
         hex
     }
 
+    pub fn try_from_hex(value: &str) -> Option<Self> {
+        let value = value.as_bytes();
+        if value.len() != BLOB_HASH_LEN * 2 {
+            return None;
+        }
+
+        let mut hash = [0u8; BLOB_HASH_LEN];
+        for (byte, chunk) in hash.iter_mut().zip(value.chunks_exact(2)) {
+            *byte = (hex_value(chunk[0])? << 4) | hex_value(chunk[1])?;
+        }
+        Some(BlobHash(hash))
+    }
+
     pub fn is_empty(&self) -> bool {
         self.0 == [0; BLOB_HASH_LEN]
     }
 }
 
+// Strict hex digit decoding, unlike u8::from_str_radix which accepts a leading sign
+#[inline(always)]
+fn hex_value(ch: u8) -> Option<u8> {
+    match ch {
+        b'0'..=b'9' => Some(ch - b'0'),
+        b'a'..=b'f' => Some(ch - b'a' + 10),
+        b'A'..=b'F' => Some(ch - b'A' + 10),
+        _ => None,
+    }
+}
+
 impl From<&ArchivedBlobHash> for BlobHash {
     fn from(value: &ArchivedBlobHash) -> Self {
         BlobHash(value.0)
@@ -84,3 +108,29 @@ impl AsMut<[u8]> for BlobHash {
         self.0.as_mut()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::blob_hash::{BLOB_HASH_LEN, BlobHash};
+
+    #[test]
+    fn parse_hex_blob_hash() {
+        let hash = BlobHash::generate(b"hello world");
+        assert_eq!(BlobHash::try_from_hex(&hash.to_hex()), Some(hash.clone()));
+        assert_eq!(
+            BlobHash::try_from_hex(&hash.to_hex().to_uppercase()),
+            Some(hash)
+        );
+
+        for invalid in [
+            format!("+f{}", "0".repeat(BLOB_HASH_LEN * 2 - 2)),
+            format!("-0{}", "0".repeat(BLOB_HASH_LEN * 2 - 2)),
+            format!(" 0{}", "0".repeat(BLOB_HASH_LEN * 2 - 2)),
+            format!("0g{}", "0".repeat(BLOB_HASH_LEN * 2 - 2)),
+            "0".repeat(BLOB_HASH_LEN * 2 - 1),
+            "0".repeat(BLOB_HASH_LEN * 2 + 2),
+        ] {
+            assert_eq!(BlobHash::try_from_hex(&invalid), None, "{invalid}");
+        }
+    }
+}
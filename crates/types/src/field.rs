@@ -28,6 +28,14 @@ pub enum CalendarEventField {
     Archive,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CalendarField {
+    Archive,
+    Subscription,
+    Publication,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u8)]
 pub enum CalendarNotificationField {
@@ -35,6 +43,14 @@ pub enum CalendarNotificationField {
     Archive,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum FileNodeField {
+    Archive,
+    Versions,
+    ShareLinks,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u8)]
 pub enum EmailField {
@@ -42,6 +58,7 @@ pub enum EmailField {
     Metadata,
     Threading,
     DeletedAt,
+    Snoozed,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -83,6 +100,9 @@ pub enum PrincipalField {
     DefaultAddressBookId = 48,
     ActiveScriptId = 49,
     PushSubscriptions = 44,
+    Webhooks = 43,
+    SentRecipients = 42,
+    CalendarAvailability = 41,
 }
 
 impl From<ContactField> for u8 {
@@ -105,6 +125,16 @@ impl From<CalendarEventField> for u8 {
     }
 }
 
+impl From<CalendarField> for u8 {
+    fn from(value: CalendarField) -> Self {
+        match value {
+            CalendarField::Subscription => 1,
+            CalendarField::Publication => 2,
+            CalendarField::Archive => ARCHIVE_FIELD,
+        }
+    }
+}
+
 impl From<CalendarNotificationField> for u8 {
     fn from(value: CalendarNotificationField) -> Self {
         match value {
@@ -114,12 +144,23 @@ impl From<CalendarNotificationField> for u8 {
     }
 }
 
+impl From<FileNodeField> for u8 {
+    fn from(value: FileNodeField) -> Self {
+        match value {
+            FileNodeField::Versions => 1,
+            FileNodeField::ShareLinks => 2,
+            FileNodeField::Archive => ARCHIVE_FIELD,
+        }
+    }
+}
+
 impl From<EmailField> for u8 {
     fn from(value: EmailField) -> Self {
         match value {
             EmailField::Metadata => 71,
             EmailField::Threading => 90,
             EmailField::DeletedAt => 91,
+            EmailField::Snoozed => 92,
             EmailField::Archive => ARCHIVE_FIELD,
         }
     }
@@ -162,6 +203,9 @@ impl From<PrincipalField> for u8 {
             PrincipalField::DefaultAddressBookId => 48,
             PrincipalField::ActiveScriptId => 49,
             PrincipalField::PushSubscriptions => 44,
+            PrincipalField::Webhooks => 43,
+            PrincipalField::SentRecipients => 42,
+            PrincipalField::CalendarAvailability => 41,
             PrincipalField::Archive => ARCHIVE_FIELD,
         }
     }
@@ -194,12 +238,24 @@ impl From<CalendarEventField> for Field {
     }
 }
 
+impl From<CalendarField> for Field {
+    fn from(value: CalendarField) -> Self {
+        Field(u8::from(value))
+    }
+}
+
 impl From<CalendarNotificationField> for Field {
     fn from(value: CalendarNotificationField) -> Self {
         Field(u8::from(value))
     }
 }
 
+impl From<FileNodeField> for Field {
+    fn from(value: FileNodeField) -> Self {
+        Field(u8::from(value))
+    }
+}
+
 impl From<EmailField> for Field {
     fn from(value: EmailField) -> Self {
         Field(u8::from(value))
@@ -251,7 +307,9 @@ impl Field {
 impl FieldType for Field {}
 impl FieldType for ContactField {}
 impl FieldType for CalendarEventField {}
+impl FieldType for CalendarField {}
 impl FieldType for CalendarNotificationField {}
+impl FieldType for FileNodeField {}
 impl FieldType for EmailField {}
 impl FieldType for MailboxField {}
 impl FieldType for PrincipalField {}
@@ -164,6 +164,23 @@ impl DataType {
             _ => None,
         }
     }
+
+    pub fn try_to_sync(&self) -> Option<(SyncCollection, bool)> {
+        match self {
+            DataType::Email => (SyncCollection::Email, false).into(),
+            DataType::Mailbox => (SyncCollection::Email, true).into(),
+            DataType::Thread => (SyncCollection::Thread, true).into(),
+            DataType::Calendar => (SyncCollection::Calendar, true).into(),
+            DataType::CalendarEvent => (SyncCollection::Calendar, false).into(),
+            DataType::AddressBook => (SyncCollection::AddressBook, true).into(),
+            DataType::ContactCard => (SyncCollection::AddressBook, false).into(),
+            DataType::FileNode => (SyncCollection::FileNode, false).into(),
+            DataType::Identity => (SyncCollection::Identity, false).into(),
+            DataType::EmailSubmission => (SyncCollection::EmailSubmission, false).into(),
+            DataType::SieveScript => (SyncCollection::SieveScript, false).into(),
+            _ => None,
+        }
+    }
 }
 
 impl DataType {
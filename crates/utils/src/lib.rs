@@ -113,6 +113,17 @@ pub fn failed(message: &str) -> ! {
     std::process::exit(1);
 }
 
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff: u8 = 0;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
+
 pub async fn wait_for_shutdown() {
     #[cfg(not(target_env = "msvc"))]
     let signal = {
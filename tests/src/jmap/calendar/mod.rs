@@ -10,3 +10,6 @@ pub mod calendars;
 pub mod event;
 pub mod identity;
 pub mod notification;
+pub mod publish;
+pub mod resource;
+pub mod subscription;
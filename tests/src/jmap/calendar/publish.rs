@@ -0,0 +1,220 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::{
+    account::Account,
+    jmap::{JmapResponse, JmapUtils},
+    server::TestServer,
+};
+use jmap_proto::{object::calendar::CalendarProperty, request::method::MethodObject};
+use reqwest::StatusCode;
+use serde_json::json;
+
+pub async fn test(test: &TestServer) {
+    println!("Running Calendar publishing tests...");
+    let account = test.account("jdoe@example.com");
+
+    // Create a calendar with a public, a private and a recurring event
+    let calendar_id = account
+        .jmap_create(
+            MethodObject::Calendar,
+            [json!({
+                "name": "Team Events",
+            })],
+            Vec::<(&str, &str)>::new(),
+        )
+        .await
+        .created(0)
+        .id()
+        .to_string();
+    let response = account
+        .jmap_create(
+            MethodObject::CalendarEvent,
+            [
+                json!({
+                    "calendarIds": {
+                        &calendar_id: true
+                    },
+                    "@type": "Event",
+                    "uid": "9d6f1a84-3f34-4a6b-9a7d-59e2b3c1f001",
+                    "title": "Product Launch",
+                    "start": "2030-02-10T15:00:00",
+                    "timeZone": "Europe/Berlin",
+                    "duration": "PT2H",
+                    "alerts": {
+                        "a1": {
+                            "trigger": {
+                                "@type": "OffsetTrigger",
+                                "offset": "-PT15M"
+                            }
+                        }
+                    }
+                }),
+                json!({
+                    "calendarIds": {
+                        &calendar_id: true
+                    },
+                    "@type": "Event",
+                    "uid": "9d6f1a84-3f34-4a6b-9a7d-59e2b3c1f002",
+                    "title": "Doctor Appointment",
+                    "privacy": "private",
+                    "start": "2030-02-11T09:00:00",
+                    "timeZone": "Europe/Berlin",
+                    "duration": "PT1H"
+                }),
+                json!({
+                    "calendarIds": {
+                        &calendar_id: true
+                    },
+                    "@type": "Event",
+                    "uid": "9d6f1a84-3f34-4a6b-9a7d-59e2b3c1f003",
+                    "title": "Weekly Sync",
+                    "start": "2026-01-05T10:00:00",
+                    "timeZone": "Europe/Berlin",
+                    "duration": "PT30M",
+                    "recurrenceRules": [{
+                        "@type": "RecurrenceRule",
+                        "frequency": "weekly"
+                    }]
+                }),
+            ],
+            Vec::<(&str, &str)>::new(),
+        )
+        .await;
+    for i in 0..3 {
+        response.created(i);
+    }
+
+    // Calendars are not published by default
+    let response = get_publication(account, &calendar_id).await;
+    assert_eq!(
+        response.list()[0].pointer("/publishUrl"),
+        Some(&json!(null))
+    );
+
+    // Invalid publish modes are rejected
+    let response = account
+        .jmap_update(
+            MethodObject::Calendar,
+            [(&calendar_id, json!({ "publishMode": "everything" }))],
+            Vec::<(&str, &str)>::new(),
+        )
+        .await;
+    assert_eq!(
+        response.not_updated(&calendar_id).description(),
+        "Invalid publish mode."
+    );
+
+    // Publish with full details
+    account
+        .jmap_update(
+            MethodObject::Calendar,
+            [(&calendar_id, json!({ "publishMode": "full" }))],
+            Vec::<(&str, &str)>::new(),
+        )
+        .await
+        .updated(&calendar_id);
+    let response = get_publication(account, &calendar_id).await;
+    let calendar = &response.list()[0];
+    assert_eq!(calendar.text_field("publishMode"), "full");
+    let publish_url = calendar.text_field("publishUrl").to_string();
+    assert!(publish_url.ends_with(".ics"), "{publish_url}");
+
+    let (status, ical) = fetch_published(&publish_url).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(ical.contains("X-WR-CALNAME:Team Events"), "{ical}");
+    assert!(ical.contains("SUMMARY:Product Launch"), "{ical}");
+    assert!(ical.contains("SUMMARY:Weekly Sync"), "{ical}");
+    assert!(ical.contains("RRULE:FREQ=WEEKLY"), "{ical}");
+    assert!(!ical.contains("Doctor Appointment"), "{ical}");
+    assert!(ical.contains("SUMMARY:Busy"), "{ical}");
+    assert!(!ical.contains("BEGIN:VALARM"), "{ical}");
+    assert!(ical.matches("BEGIN:VTIMEZONE").count() <= 1, "{ical}");
+
+    // Invalid secrets are rejected
+    let (prefix, _) = publish_url.rsplit_once('/').unwrap();
+    let (status, _) = fetch_published(&format!("{prefix}/invalid.ics")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Switch to free/busy, the URL stays the same
+    account
+        .jmap_update(
+            MethodObject::Calendar,
+            [(&calendar_id, json!({ "publishMode": "freeBusy" }))],
+            Vec::<(&str, &str)>::new(),
+        )
+        .await
+        .updated(&calendar_id);
+    let response = get_publication(account, &calendar_id).await;
+    assert_eq!(response.list()[0].text_field("publishUrl"), publish_url);
+    let (status, ical) = fetch_published(&publish_url).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(ical.contains("BEGIN:VFREEBUSY"), "{ical}");
+    assert!(ical.contains("\nFREEBUSY;FBTYPE=BUSY:"), "{ical}");
+    assert!(!ical.contains("BEGIN:VEVENT"), "{ical}");
+    assert!(!ical.contains("Weekly Sync"), "{ical}");
+
+    // Revoke and republish, the old URL stops working
+    account
+        .jmap_update(
+            MethodObject::Calendar,
+            [(&calendar_id, json!({ "publishMode": null }))],
+            Vec::<(&str, &str)>::new(),
+        )
+        .await
+        .updated(&calendar_id);
+    let (status, _) = fetch_published(&publish_url).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    account
+        .jmap_update(
+            MethodObject::Calendar,
+            [(&calendar_id, json!({ "publishMode": "full" }))],
+            Vec::<(&str, &str)>::new(),
+        )
+        .await
+        .updated(&calendar_id);
+    let response = get_publication(account, &calendar_id).await;
+    let new_publish_url = response.list()[0].text_field("publishUrl").to_string();
+    assert_ne!(new_publish_url, publish_url);
+    let (status, _) = fetch_published(&publish_url).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = fetch_published(&new_publish_url).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Destroying the calendar removes the publication
+    account.destroy_all_calendars().await;
+    let (status, _) = fetch_published(&new_publish_url).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    test.assert_is_empty().await;
+}
+
+async fn get_publication(account: &Account, calendar_id: &str) -> JmapResponse {
+    account
+        .jmap_get(
+            MethodObject::Calendar,
+            [
+                CalendarProperty::Id,
+                CalendarProperty::PublishMode,
+                CalendarProperty::PublishUrl,
+            ],
+            [calendar_id],
+        )
+        .await
+}
+
+async fn fetch_published(url: &str) -> (StatusCode, String) {
+    let path = &url[url.find("/calendar/publish/").unwrap()..];
+    let response = reqwest::Client::builder()
+        .danger_accept_invalid_certs(true)
+        .build()
+        .unwrap()
+        .get(format!("https://127.0.0.1:8899{path}"))
+        .send()
+        .await
+        .unwrap();
+
+    (response.status(), response.text().await.unwrap())
+}
@@ -0,0 +1,352 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::{
+    account::Account,
+    jmap::{IntoJmapSet, JmapUtils},
+    server::TestServer,
+};
+use calcard::jscalendar::JSCalendarProperty;
+use jmap_proto::request::method::MethodObject;
+use registry::{
+    schema::{
+        enums::ResourceType,
+        structs::{self, BookingPolicy, ResourceAccount},
+    },
+    types::{duration::Duration, map::Map},
+};
+use serde_json::{Map as JsonMap, Value, json};
+use types::id::Id;
+
+pub async fn test(test: &TestServer) {
+    println!("Running Resource booking tests...");
+    let admin = test.account("admin@example.com");
+    let john = test.account("jdoe@example.com");
+    let jane = test.account("jane.smith@example.com");
+    let bill = test.account("bill@example.com");
+    let robert = test.account("robert@example.com");
+    let sales = test.account("sales@example.com");
+    let domain_id = admin.find_or_create_domain("example.com").await;
+
+    // Create resource accounts
+    let room = create_resource(
+        admin,
+        "boardroom@example.com",
+        "Boardroom",
+        ResourceAccount {
+            name: "boardroom".into(),
+            domain_id,
+            resource_type: ResourceType::Room,
+            capacity: Some(2),
+            booking_policy: BookingPolicy {
+                max_duration: Some(Duration::from_millis(2 * 60 * 60 * 1000)),
+                working_hours_start: Some(9 * 60),
+                working_hours_end: Some(17 * 60),
+                weekdays_only: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )
+    .await;
+    let projector = create_resource(
+        admin,
+        "projector@example.com",
+        "Projector",
+        ResourceAccount {
+            name: "projector".into(),
+            domain_id,
+            resource_type: ResourceType::Equipment,
+            booking_policy: BookingPolicy {
+                auto_accept: false,
+                ..Default::default()
+            },
+            delegate_ids: Map::new(vec![robert.id()]),
+            ..Default::default()
+        },
+    )
+    .await;
+    let lab = create_resource(
+        admin,
+        "lab@example.com",
+        "Lab",
+        ResourceAccount {
+            name: "lab".into(),
+            domain_id,
+            resource_type: ResourceType::Room,
+            booking_policy: BookingPolicy {
+                allowed_group_ids: Map::new(vec![sales.id()]),
+                auto_accept: false,
+                ..Default::default()
+            },
+            delegate_ids: Map::new(vec![robert.id()]),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let calendar_id = john
+        .jmap_create(
+            MethodObject::Calendar,
+            [json!({ "name": "Bookings" })],
+            Vec::<(&str, &str)>::new(),
+        )
+        .await
+        .created(0)
+        .id()
+        .to_string();
+
+    // Rooms accept bookings within their policy
+    let event_id = invite(
+        john,
+        &calendar_id,
+        "Weekly sync",
+        "2030-01-07T10:00:00",
+        "PT1H",
+        &[
+            ("jane.smith@example.com", "individual"),
+            (room.name(), "location"),
+        ],
+    )
+    .await;
+    assert_eq!(reply(test, john, &event_id, room.name()).await, "accepted");
+
+    // Overlapping bookings are declined
+    let event_id = invite(
+        john,
+        &calendar_id,
+        "Conflicting sync",
+        "2030-01-07T10:30:00",
+        "PT1H",
+        &[(room.name(), "location")],
+    )
+    .await;
+    assert_eq!(reply(test, john, &event_id, room.name()).await, "declined");
+
+    // Bookings starting once the previous one ended are accepted
+    let event_id = invite(
+        john,
+        &calendar_id,
+        "Follow-up sync",
+        "2030-01-07T11:00:00",
+        "PT30M",
+        &[(room.name(), "location")],
+    )
+    .await;
+    assert_eq!(reply(test, john, &event_id, room.name()).await, "accepted");
+
+    // Bookings exceeding the room capacity are declined
+    let event_id = invite(
+        john,
+        &calendar_id,
+        "All hands",
+        "2030-01-08T10:00:00",
+        "PT1H",
+        &[
+            ("jane.smith@example.com", "individual"),
+            ("bill@example.com", "individual"),
+            ("robert@example.com", "individual"),
+            (room.name(), "location"),
+        ],
+    )
+    .await;
+    assert_eq!(reply(test, john, &event_id, room.name()).await, "declined");
+
+    // Bookings outside working hours, on weekends or too long are declined
+    for (title, start, duration) in [
+        ("Early sync", "2030-01-09T08:30:00", "PT1H"),
+        ("Late sync", "2030-01-09T16:30:00", "PT1H"),
+        ("Weekend sync", "2030-01-12T10:00:00", "PT1H"),
+        ("Workshop", "2030-01-10T09:00:00", "PT3H"),
+    ] {
+        let event_id = invite(
+            john,
+            &calendar_id,
+            title,
+            start,
+            duration,
+            &[(room.name(), "location")],
+        )
+        .await;
+        assert_eq!(
+            reply(test, john, &event_id, room.name()).await,
+            "declined",
+            "{title}"
+        );
+    }
+
+    // Resources without auto-accept route invitations to their delegates
+    let event_id = invite(
+        john,
+        &calendar_id,
+        "Product demo",
+        "2030-01-11T10:00:00",
+        "PT1H",
+        &[(projector.name(), "resource")],
+    )
+    .await;
+    assert_eq!(
+        reply(test, john, &event_id, projector.name()).await,
+        "delegated"
+    );
+    let participant = participant(john, &event_id, projector.name()).await;
+    assert_eq!(
+        participant["delegatedTo"],
+        json!({ "mailto:robert@example.com": true }),
+        "{participant:?}"
+    );
+    let mut found = false;
+    for _ in 0..50 {
+        test.wait_for_tasks().await;
+        found = robert
+            .jmap_get(
+                MethodObject::CalendarEvent,
+                [JSCalendarProperty::<Id>::Title],
+                Vec::<&str>::new(),
+            )
+            .await
+            .list()
+            .iter()
+            .any(|event| event.text_field("title") == "Product demo");
+        if found {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    assert!(found, "Delegate did not receive the invitation");
+
+    // Organizers outside the allowed groups are declined rather than delegated
+    let event_id = invite(
+        john,
+        &calendar_id,
+        "Experiment",
+        "2030-01-11T14:00:00",
+        "PT1H",
+        &[(lab.name(), "location")],
+    )
+    .await;
+    assert_eq!(reply(test, john, &event_id, lab.name()).await, "declined");
+    let participant = participant(john, &event_id, lab.name()).await;
+    assert!(
+        participant.get("delegatedTo").is_none_or(Value::is_null),
+        "{participant:?}"
+    );
+
+    // Cleanup
+    test.wait_for_tasks().await;
+    for resource in [room, projector, lab] {
+        admin.destroy_account(resource).await;
+    }
+    for client in [john, jane, bill, robert] {
+        client.destroy_all_calendars().await;
+        client.destroy_all_event_notifications().await;
+        test.destroy_all_mailboxes(client).await;
+    }
+    test.wait_for_tasks().await;
+    test.assert_is_empty().await;
+}
+
+async fn create_resource(
+    admin: &Account,
+    name: &'static str,
+    description: &'static str,
+    resource: ResourceAccount,
+) -> Account {
+    let account_id = admin
+        .registry_create_object(structs::Account::Resource(ResourceAccount {
+            description: description.to_string().into(),
+            ..resource
+        }))
+        .await;
+    Account::new(name, "", &[], description, account_id)
+}
+
+async fn invite(
+    organizer: &Account,
+    calendar_id: &str,
+    title: &str,
+    start: &str,
+    duration: &str,
+    attendees: &[(&str, &str)],
+) -> String {
+    let mut participants = JsonMap::new();
+    participants.insert(
+        "organizer".into(),
+        json!({
+            "@type": "Participant",
+            "calendarAddress": format!("mailto:{}", organizer.name()),
+            "participationStatus": "accepted",
+            "roles": { "chair": true, "owner": true }
+        }),
+    );
+    for (idx, (email, kind)) in attendees.iter().enumerate() {
+        participants.insert(
+            format!("attendee{idx}"),
+            json!({
+                "@type": "Participant",
+                "calendarAddress": format!("mailto:{email}"),
+                "participationStatus": "needs-action",
+                "kind": kind,
+                "expectReply": true
+            }),
+        );
+    }
+
+    organizer
+        .jmap_create(
+            MethodObject::CalendarEvent,
+            [json!({
+                "@type": "Event",
+                "title": title,
+                "timeZone": "Europe/London",
+                "start": start,
+                "duration": duration,
+                "freeBusyStatus": "busy",
+                "organizerCalendarAddress": format!("mailto:{}", organizer.name()),
+                "participants": participants
+            })
+            .with_property(
+                JSCalendarProperty::<Id>::CalendarIds,
+                [calendar_id].into_jmap_set(),
+            )],
+            [("sendSchedulingMessages", true)],
+        )
+        .await
+        .created(0)
+        .id()
+        .to_string()
+}
+
+async fn participant(account: &Account, event_id: &str, email: &str) -> Value {
+    let address = format!("mailto:{email}");
+    account
+        .jmap_get(
+            MethodObject::CalendarEvent,
+            [JSCalendarProperty::<Id>::Participants],
+            [event_id],
+        )
+        .await
+        .list()[0]["participants"]
+        .as_object()
+        .unwrap()
+        .values()
+        .find(|participant| participant["calendarAddress"] == address.as_str())
+        .unwrap_or_else(|| panic!("Participant {email} not found"))
+        .clone()
+}
+
+// Waits for the resource to reply to the invitation
+async fn reply(test: &TestServer, organizer: &Account, event_id: &str, email: &str) -> String {
+    for _ in 0..50 {
+        test.wait_for_tasks().await;
+        let participant = participant(organizer, event_id, email).await;
+        match participant["participationStatus"].as_str() {
+            Some(status) if status != "needs-action" => return status.to_string(),
+            _ => tokio::time::sleep(std::time::Duration::from_millis(100)).await,
+        }
+    }
+    panic!("No reply received from {email}");
+}
@@ -0,0 +1,264 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::{jmap::JmapUtils, server::TestServer};
+use jmap_proto::request::method::MethodObject;
+use serde_json::{Value, json};
+
+pub async fn test(test: &TestServer) {
+    println!("Running Contact Card merge tests...");
+    let account = test.account("jdoe@example.com");
+
+    // Create test address books
+    let response = account
+        .jmap_create(
+            MethodObject::AddressBook,
+            [json!({ "name": "Personal" }), json!({ "name": "Work" })],
+            Vec::<(&str, &str)>::new(),
+        )
+        .await;
+    let book1_id = response.created(0).id().to_string();
+    let book2_id = response.created(1).id().to_string();
+
+    // Create duplicate contacts
+    let response = account
+        .jmap_create(
+            MethodObject::ContactCard,
+            [
+                json!({
+                    "uid": "urn:uuid:merge-1",
+                    "addressBookIds": { &book1_id: true },
+                    "name": { "full": "Jane Smith" },
+                    "emails": { "e1": { "address": "jane@example.org" } },
+                }),
+                json!({
+                    "uid": "urn:uuid:merge-2",
+                    "addressBookIds": { &book2_id: true },
+                    "name": { "full": "J. Smith" },
+                    "emails": { "e1": { "address": "JANE@example.org" } },
+                    "phones": { "p1": { "number": "+1 (555) 010-2030" } },
+                }),
+                json!({
+                    "uid": "urn:uuid:merge-3",
+                    "addressBookIds": { &book1_id: true },
+                    "name": { "full": "Janie" },
+                    "phones": { "p1": { "number": "555-010-2030" } },
+                    "notes": { "n1": { "note": "Met at the conference" } },
+                }),
+                json!({
+                    "uid": "urn:uuid:merge-4",
+                    "addressBookIds": { &book1_id: true },
+                    "name": { "full": "Bob Jones" },
+                }),
+                json!({
+                    "uid": "urn:uuid:merge-5",
+                    "addressBookIds": { &book2_id: true },
+                    "name": { "full": "  bob   JONES " },
+                }),
+                json!({
+                    "uid": "urn:uuid:merge-group",
+                    "kind": "group",
+                    "addressBookIds": { &book1_id: true },
+                    "name": { "full": "Friends" },
+                    "members": {
+                        "urn:uuid:merge-2": true,
+                        "urn:uuid:merge-4": true
+                    },
+                }),
+            ],
+            Vec::<(&str, &str)>::new(),
+        )
+        .await;
+    let jane_id = response.created(0).id().to_string();
+    let jane2_id = response.created(1).id().to_string();
+    let janie_id = response.created(2).id().to_string();
+    let bob_id = response.created(3).id().to_string();
+    let bob2_id = response.created(4).id().to_string();
+    let group_id = response.created(5).id().to_string();
+    test.wait_for_tasks().await;
+
+    // Find duplicates across all address books
+    let response = account
+        .jmap_method_call(
+            "ContactCard/findDuplicates",
+            json!({ "accountId": account.id_string() }),
+        )
+        .await;
+    let mut clusters = response
+        .method_response()
+        .pointer("/clusters")
+        .and_then(|v| v.as_array())
+        .unwrap()
+        .iter()
+        .map(|cluster| {
+            (
+                sorted(string_list(&cluster["ids"]).iter()),
+                string_list(&cluster["matchedOn"]),
+            )
+        })
+        .collect::<Vec<_>>();
+    clusters.sort();
+    let mut expected = vec![
+        (
+            sorted([&jane_id, &jane2_id, &janie_id]),
+            vec!["email".to_string(), "phone".to_string()],
+        ),
+        (sorted([&bob_id, &bob2_id]), vec!["name".to_string()]),
+    ];
+    expected.sort();
+    assert_eq!(clusters, expected);
+
+    // Restrict the search to one address book
+    let response = account
+        .jmap_method_call(
+            "ContactCard/findDuplicates",
+            json!({ "accountId": account.id_string(), "addressBookId": &book1_id }),
+        )
+        .await;
+    assert_eq!(
+        response.method_response().pointer("/clusters"),
+        Some(&json!([]))
+    );
+
+    // Invalid merges are rejected
+    for (target_id, source_ids) in [
+        (jane_id.as_str(), vec![]),
+        (jane_id.as_str(), vec![jane_id.as_str()]),
+        (jane_id.as_str(), vec!["not-an-id"]),
+    ] {
+        let response = account
+            .jmap_method_call(
+                "ContactCard/merge",
+                json!({
+                    "accountId": account.id_string(),
+                    "targetId": target_id,
+                    "sourceIds": source_ids,
+                }),
+            )
+            .await;
+        assert_eq!(response.error_type_at(0), Some("invalidArguments"));
+    }
+
+    // Merge the duplicates
+    let old_state = account
+        .jmap_get(
+            MethodObject::ContactCard,
+            Vec::<&str>::new(),
+            Vec::<&str>::new(),
+        )
+        .await
+        .state()
+        .to_string();
+    let response = account
+        .jmap_method_call(
+            "ContactCard/merge",
+            json!({
+                "accountId": account.id_string(),
+                "ifInState": &old_state,
+                "targetId": &jane_id,
+                "sourceIds": [&jane2_id, &janie_id],
+            }),
+        )
+        .await;
+    let merge = response.method_response();
+    assert_eq!(merge["oldState"], json!(old_state));
+    assert_ne!(merge["newState"], json!(old_state));
+    assert_eq!(
+        sorted(string_list(&merge["updated"]).iter()),
+        sorted([&jane_id, &group_id])
+    );
+    assert_eq!(
+        sorted(string_list(&merge["destroyed"]).iter()),
+        sorted([&jane2_id, &janie_id])
+    );
+
+    // Merging again with the old state fails
+    let response = account
+        .jmap_method_call(
+            "ContactCard/merge",
+            json!({
+                "accountId": account.id_string(),
+                "ifInState": &old_state,
+                "targetId": &bob_id,
+                "sourceIds": [&bob2_id],
+            }),
+        )
+        .await;
+    assert_eq!(response.error_type_at(0), Some("stateMismatch"));
+
+    // Verify the merged contact
+    let response = account
+        .jmap_get(
+            MethodObject::ContactCard,
+            [
+                "id",
+                "uid",
+                "addressBookIds",
+                "name",
+                "emails",
+                "phones",
+                "notes",
+            ],
+            [jane_id.as_str(), jane2_id.as_str(), janie_id.as_str()],
+        )
+        .await;
+    assert_eq!(sorted(response.not_found()), sorted([&jane2_id, &janie_id]));
+    let jane = &response.list()[0];
+    assert_eq!(jane.text_field("uid"), "urn:uuid:merge-1");
+    assert_eq!(jane["name"]["full"], json!("Jane Smith"));
+    assert_eq!(
+        jane["addressBookIds"],
+        json!({ &book1_id: true, &book2_id: true })
+    );
+    assert_eq!(jane["emails"].as_object().unwrap().len(), 1);
+    assert_eq!(jane["phones"].as_object().unwrap().len(), 1);
+    assert_eq!(jane["notes"].as_object().unwrap().len(), 1);
+
+    // Group members now point to the merged contact
+    let response = account
+        .jmap_get(MethodObject::ContactCard, ["members"], [group_id.as_str()])
+        .await;
+    assert_eq!(
+        response.list()[0]["members"],
+        json!({ "urn:uuid:merge-1": true, "urn:uuid:merge-4": true })
+    );
+
+    // Only the name duplicates remain
+    test.wait_for_tasks().await;
+    let response = account
+        .jmap_method_call(
+            "ContactCard/findDuplicates",
+            json!({ "accountId": account.id_string() }),
+        )
+        .await;
+    let clusters = response.method_response()["clusters"].as_array().unwrap();
+    assert_eq!(clusters.len(), 1);
+    assert_eq!(
+        sorted(string_list(&clusters[0]["ids"]).iter()),
+        sorted([&bob_id, &bob2_id])
+    );
+
+    account.destroy_all_addressbooks().await;
+    test.assert_is_empty().await;
+}
+
+fn string_list(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.as_str().unwrap().to_string())
+        .collect()
+}
+
+fn sorted<T: AsRef<str>>(items: impl IntoIterator<Item = T>) -> Vec<String> {
+    let mut items = items
+        .into_iter()
+        .map(|item| item.as_ref().to_string())
+        .collect::<Vec<_>>();
+    items.sort();
+    items
+}
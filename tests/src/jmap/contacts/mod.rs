@@ -7,3 +7,4 @@
 pub mod acl;
 pub mod addressbook;
 pub mod contact;
+pub mod merge;
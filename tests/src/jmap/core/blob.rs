@@ -4,7 +4,12 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use crate::utils::server::TestServer;
+use crate::utils::{
+    account::Account,
+    jmap::{JmapResponse, JmapUtils},
+    server::TestServer,
+};
+use base64::{Engine, engine::general_purpose};
 use email::mailbox::INBOX_ID;
 use serde_json::{Value, json};
 use types::id::Id;
@@ -367,6 +372,113 @@ pub async fn test(test: &TestServer) {
     }
     test.blob_expire_all().await;
 
+    // Blob/convert image resizing
+    let image_id = upload_blob(
+        account,
+        json!({ "data:asBase64": "iVBORw0KGgoAAAANSUhEUgAAAEAAAAAgCAIAAAAt/+nTAAAANElEQVR42u3PQQkAAAgAsetfWjP4FAYrsKZeS0BAQEBAQEBAQEBAQEBAQEBAQEBAQEDgagEyw/hqT+fjVwAAAABJRU5ErkJggg==" }),
+    )
+    .await;
+    let response = blob_convert(
+        account,
+        json!({ "imageConvert": { "blobId": &image_id, "width": 16, "height": 16 } }),
+    )
+    .await;
+    let resized = response.created(0);
+    assert_eq!(resized["type"], json!("image/png"));
+    let resized_id = resized.id().to_string();
+    let data = blob_data(account, &resized_id).await;
+    assert_eq!(png_dimensions(&data), (16, 8));
+
+    // Converted blobs are cached
+    let response = blob_convert(
+        account,
+        json!({ "imageConvert": { "blobId": &image_id, "width": 16, "height": 16 } }),
+    )
+    .await;
+    assert_eq!(response.created(0).id(), resized_id);
+
+    // Images can be converted to other formats
+    let response = blob_convert(
+        account,
+        json!({ "imageConvert": { "blobId": &image_id, "type": "image/jpeg" } }),
+    )
+    .await;
+    assert_eq!(response.created(0)["type"], json!("image/jpeg"));
+    let data = blob_data(account, response.created(0).id()).await;
+    assert!(data.starts_with(&[0xff, 0xd8]));
+
+    // Invalid dimensions and unsupported types are rejected
+    for convert in [
+        json!({ "blobId": &image_id, "width": 0 }),
+        json!({ "blobId": &image_id, "height": 100000 }),
+        json!({ "blobId": &image_id, "type": "image/x-unknown" }),
+    ] {
+        let response = blob_convert(account, json!({ "imageConvert": convert })).await;
+        assert_eq!(response.not_created(0).typ(), "invalidProperties");
+    }
+
+    // Images larger than the decoding limits are rejected before decoding
+    let bomb_id = upload_blob(
+        account,
+        json!({ "data:asBase64": "iVBORw0KGgoAAAANSUhEUgABhqAAAYagCAIAAAAnMJyfAAAACUlEQVR42mMAAAABAAGxDbaTAAAAAElFTkSuQmCC" }),
+    )
+    .await;
+    let response = blob_convert(account, json!({ "imageConvert": { "blobId": &bomb_id } })).await;
+    assert_eq!(response.not_created(0).typ(), "invalidProperties");
+
+    // Blob/convert text extraction
+    let html_id = upload_blob(
+        account,
+        json!({ "data:asText": "<html><body><p>Hello <b>world</b></p></body></html>" }),
+    )
+    .await;
+    let response = blob_convert(
+        account,
+        json!({ "textExtract": { "blobId": &html_id, "type": "text/html" } }),
+    )
+    .await;
+    assert_eq!(
+        response.created(0)["type"],
+        json!("text/plain; charset=utf-8")
+    );
+    let text = String::from_utf8(blob_data(account, response.created(0).id()).await).unwrap();
+    assert!(
+        text.contains("Hello") && text.contains("world") && !text.contains('<'),
+        "text = {text:?}"
+    );
+    let response = blob_convert(
+        account,
+        json!({ "textExtract": { "blobId": &html_id, "maxBytes": 6 } }),
+    )
+    .await;
+    assert_eq!(response.created(0)["size"], json!(6));
+    assert_eq!(
+        blob_data(account, response.created(0).id()).await,
+        b"<html>"
+    );
+
+    // Text cannot be extracted from binary or unsupported types
+    for extract in [
+        json!({ "blobId": &image_id }),
+        json!({ "blobId": &image_id, "type": "application/pdf" }),
+    ] {
+        let response = blob_convert(account, json!({ "textExtract": extract })).await;
+        assert_eq!(response.not_created(0).typ(), "unsupportedType");
+    }
+
+    // Exactly one conversion must be requested
+    let response = blob_convert(
+        account,
+        json!({
+            "imageConvert": { "blobId": &image_id },
+            "textExtract": { "blobId": &html_id }
+        }),
+    )
+    .await;
+    assert_eq!(response.not_created(0).typ(), "invalidProperties");
+
+    test.blob_expire_all().await;
+
     // Blob/lookup
     let client = account.jmap_client().await;
     let blob_id = client
@@ -427,3 +539,44 @@ pub async fn test(test: &TestServer) {
     test.destroy_all_mailboxes(account).await;
     test.assert_is_empty().await;
 }
+
+async fn upload_blob(account: &Account, data: Value) -> String {
+    account
+        .jmap_method_call(
+            "Blob/upload",
+            json!({
+                "accountId": account.id_string(),
+                "create": { "i0": { "data": [data] } }
+            }),
+        )
+        .await
+        .created(0)
+        .id()
+        .to_string()
+}
+
+async fn blob_convert(account: &Account, convert: Value) -> JmapResponse {
+    account
+        .jmap_method_call(
+            "Blob/convert",
+            json!({
+                "accountId": account.id_string(),
+                "create": { "i0": convert }
+            }),
+        )
+        .await
+}
+
+async fn blob_data(account: &Account, blob_id: &str) -> Vec<u8> {
+    let response = account.jmap_get("Blob", ["data:asBase64"], [blob_id]).await;
+    general_purpose::STANDARD
+        .decode(response.list()[0].text_field("data:asBase64"))
+        .unwrap()
+}
+
+fn png_dimensions(data: &[u8]) -> (u32, u32) {
+    (
+        u32::from_be_bytes(data[16..20].try_into().unwrap()),
+        u32::from_be_bytes(data[20..24].try_into().unwrap()),
+    )
+}
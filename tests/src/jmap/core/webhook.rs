@@ -0,0 +1,374 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::{
+    account::Account,
+    jmap::{JmapResponse, JmapUtils},
+    server::TestServer,
+};
+use aws_lc_rs::hmac;
+use base64::{Engine, engine::general_purpose::STANDARD};
+use common::{config::server::Listeners, network::SessionData};
+use http_proto::{HtmlResponse, ToHttpResponse, request::fetch_body};
+use hyper::{StatusCode, body, server::conn::http1, service::service_fn};
+use hyper_util::rt::TokioIo;
+use jmap_client::mailbox::Role;
+use registry::{
+    schema::{
+        enums::NetworkListenerProtocol,
+        prelude::{ObjectType, SocketAddr},
+        structs::{NetworkListener, SystemSettings},
+    },
+    types::{id::ObjectId, map::Map},
+};
+use serde_json::{Value, json};
+use std::{
+    str::FromStr,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+use store::registry::{RegistryObject, bootstrap::Bootstrap};
+use tokio::sync::mpsc;
+
+const WEBHOOK_URL: &str = "https://127.0.0.1:19010/webhook";
+
+pub async fn test(test: &TestServer) {
+    println!("Running Webhook tests...");
+    let account = test.account("robert@example.com");
+    let client = account.jmap_client().await;
+
+    // Start mock webhook receiver
+    let (event_tx, mut event_rx) = mpsc::channel::<WebhookRequest>(100);
+    let webhook_server = Arc::new(WebhookServer {
+        tx: event_tx,
+        fail_requests: false.into(),
+    });
+    let mut bp = Bootstrap::new_uninitialized(test.server.registry().clone());
+    let mut servers = Listeners::default();
+    servers.parse_server(
+        &mut bp,
+        RegistryObject {
+            id: ObjectId::new(ObjectType::NetworkListener, 0u64.into()),
+            object: NetworkListener {
+                name: "mock-webhook".into(),
+                bind: Map::new(vec![SocketAddr::from_str("127.0.0.1:19010").unwrap()]),
+                protocol: NetworkListenerProtocol::Http,
+                tls_implicit: true,
+                use_tls: true,
+                socket_reuse_address: true,
+                socket_reuse_port: true,
+                ..Default::default()
+            },
+            revision: 0,
+        },
+        &SystemSettings::default(),
+    );
+    servers
+        .parse_tcp_acceptors(&mut bp, test.server.inner.clone())
+        .await;
+    servers.bind_and_drop_priv(&mut bp);
+    bp.assert_no_errors();
+    let _shutdown_tx = servers.spawn(|server, acceptor, shutdown_rx| {
+        server.spawn(
+            SessionManager::from(webhook_server.clone()),
+            test.server.inner.clone(),
+            acceptor,
+            shutdown_rx,
+        );
+    });
+
+    // Invalid URLs, secrets and types are rejected
+    for (property, value) in [
+        ("url", json!("http://127.0.0.1:19010/webhook")),
+        ("secret", json!("short")),
+        ("types", json!(["NotAType"])),
+    ] {
+        let response = webhook_set(
+            account,
+            json!({ "create": { "i0": { "url": WEBHOOK_URL, (property): value } } }),
+        )
+        .await;
+        let error = response.not_created(0);
+        assert_eq!(error.typ(), "invalidProperties");
+        assert_eq!(error["properties"], json!([property]));
+    }
+    let response = webhook_set(
+        account,
+        json!({ "create": { "i0": { "types": ["Email"] } } }),
+    )
+    .await;
+    assert_eq!(response.not_created(0).typ(), "invalidProperties");
+
+    // Create a webhook with a generated secret
+    let response = webhook_set(
+        account,
+        json!({ "create": { "i0": { "url": WEBHOOK_URL, "types": ["Mailbox"] } } }),
+    )
+    .await;
+    let webhook_id = response.created(0).id().to_string();
+    let secret = response.created(0).text_field("secret").to_string();
+    assert!(secret.len() >= 16);
+
+    // Webhook/get returns every property except the secret
+    let response = webhook_get(account, Value::Null, &webhook_id).await;
+    response.list()[0].assert_is_equal(json!({
+        "id": &webhook_id,
+        "url": WEBHOOK_URL,
+        "types": ["Mailbox"],
+        "isEnabled": true
+    }));
+    let response = webhook_get(account, json!(["id", "secret"]), &webhook_id).await;
+    assert_eq!(
+        response
+            .pointer("/methodResponses/0/1/type")
+            .and_then(|v| v.as_str()),
+        Some("forbidden"),
+        "{response:?}"
+    );
+
+    // Changes to subscribed types are delivered with a signed payload
+    let mailbox_id = client
+        .mailbox_create("Webhook Test", None::<String>, Role::None)
+        .await
+        .unwrap()
+        .take_id();
+    let request = expect_webhook(&mut event_rx).await;
+    request.assert_signature(&secret);
+    request.payload().assert_is_equal(json!({
+        "@type": "WebhookEvent",
+        "webhookId": &webhook_id,
+        "accountId": account.id_string(),
+        "changeId": request.payload()["changeId"],
+        "changed": {
+            "Mailbox": {
+                "created": [&mailbox_id],
+                "updated": [],
+                "destroyed": []
+            }
+        }
+    }));
+
+    // Update the secret and verify that new deliveries use it
+    let new_secret = "a much longer and better secret";
+    webhook_set(
+        account,
+        json!({ "update": { &webhook_id: { "secret": new_secret } } }),
+    )
+    .await
+    .updated(&webhook_id);
+    client
+        .mailbox_rename(&mailbox_id, "Webhook Test 2")
+        .await
+        .unwrap();
+    let request = expect_webhook(&mut event_rx).await;
+    request.assert_signature(new_secret);
+    assert_eq!(
+        request.payload()["changed"]["Mailbox"]["updated"],
+        json!([&mailbox_id])
+    );
+
+    // Failed deliveries are retried
+    webhook_server.fail_requests.store(true, Ordering::Relaxed);
+    client
+        .mailbox_update_sort_order(&mailbox_id, 1)
+        .await
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    webhook_server.fail_requests.store(false, Ordering::Relaxed);
+    let request = expect_webhook(&mut event_rx).await;
+    request.assert_signature(new_secret);
+    assert_eq!(
+        request.payload()["changed"]["Mailbox"]["updated"],
+        json!([&mailbox_id])
+    );
+
+    // Disabled webhooks are not delivered
+    webhook_set(
+        account,
+        json!({ "update": { &webhook_id: { "isEnabled": false } } }),
+    )
+    .await
+    .updated(&webhook_id);
+    client
+        .mailbox_update_sort_order(&mailbox_id, 2)
+        .await
+        .unwrap();
+    expect_nothing(&mut event_rx).await;
+
+    // Destroy the webhook
+    webhook_set(account, json!({ "destroy": [&webhook_id] }))
+        .await
+        .destroyed()
+        .find(|id| *id == webhook_id)
+        .expect("webhook was not destroyed");
+    let response = webhook_get(account, Value::Null, &webhook_id).await;
+    assert_eq!(
+        response.not_found().collect::<Vec<_>>(),
+        vec![webhook_id.as_str()]
+    );
+
+    // Ids of destroyed webhooks are not reused
+    let response = webhook_set(
+        account,
+        json!({ "create": { "i0": { "url": WEBHOOK_URL } } }),
+    )
+    .await;
+    let new_webhook_id = response.created(0).id().to_string();
+    assert_ne!(new_webhook_id, webhook_id);
+    webhook_set(account, json!({ "destroy": [&new_webhook_id] }))
+        .await
+        .destroyed()
+        .find(|id| *id == new_webhook_id)
+        .expect("webhook was not destroyed");
+
+    test.destroy_all_mailboxes(account).await;
+    test.assert_is_empty().await;
+}
+
+async fn webhook_set(account: &Account, mut arguments: Value) -> JmapResponse {
+    arguments["accountId"] = json!(account.id_string());
+    account.jmap_method_call("Webhook/set", arguments).await
+}
+
+async fn webhook_get(account: &Account, properties: Value, id: &str) -> JmapResponse {
+    account
+        .jmap_method_call(
+            "Webhook/get",
+            json!({
+                "accountId": account.id_string(),
+                "ids": [id],
+                "properties": properties
+            }),
+        )
+        .await
+}
+
+async fn expect_webhook(event_rx: &mut mpsc::Receiver<WebhookRequest>) -> WebhookRequest {
+    match tokio::time::timeout(Duration::from_secs(5), event_rx.recv()).await {
+        Ok(Some(request)) => request,
+        result => {
+            panic!("Timeout waiting for webhook: {:?}", result);
+        }
+    }
+}
+
+async fn expect_nothing(event_rx: &mut mpsc::Receiver<WebhookRequest>) {
+    match tokio::time::timeout(Duration::from_millis(1000), event_rx.recv()).await {
+        Err(_) => {}
+        message => {
+            panic!("Received a webhook when expecting nothing: {:?}", message);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct WebhookRequest {
+    signature: String,
+    timestamp: String,
+    body: Vec<u8>,
+}
+
+impl WebhookRequest {
+    fn assert_signature(&self, secret: &str) {
+        let timestamp = self.timestamp.parse::<u64>().expect("Invalid X-Timestamp");
+        let now = store::write::now();
+        assert!(
+            timestamp <= now && timestamp + 60 >= now,
+            "Unexpected timestamp {timestamp} (now={now})"
+        );
+
+        let mut payload = format!("{}.", self.timestamp).into_bytes();
+        payload.extend_from_slice(&self.body);
+        let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+        hmac::verify(&key, &payload, &STANDARD.decode(&self.signature).unwrap())
+            .expect("Invalid webhook signature");
+    }
+
+    fn payload(&self) -> Value {
+        serde_json::from_slice(&self.body).unwrap()
+    }
+}
+
+struct WebhookServer {
+    tx: mpsc::Sender<WebhookRequest>,
+    fail_requests: AtomicBool,
+}
+
+#[derive(Clone)]
+struct SessionManager {
+    inner: Arc<WebhookServer>,
+}
+
+impl From<Arc<WebhookServer>> for SessionManager {
+    fn from(inner: Arc<WebhookServer>) -> Self {
+        SessionManager { inner }
+    }
+}
+
+impl common::network::SessionManager for SessionManager {
+    #[allow(clippy::manual_async_fn)]
+    fn handle<T: common::network::SessionStream>(
+        self,
+        session: SessionData<T>,
+    ) -> impl std::future::Future<Output = ()> + Send {
+        async move {
+            let webhook = self.inner;
+            let _ = http1::Builder::new()
+                .keep_alive(false)
+                .serve_connection(
+                    TokioIo::new(session.stream),
+                    service_fn(|mut req: hyper::Request<body::Incoming>| {
+                        let webhook = webhook.clone();
+
+                        async move {
+                            if webhook.fail_requests.load(Ordering::Relaxed) {
+                                return Ok(HtmlResponse::with_status(
+                                    StatusCode::SERVICE_UNAVAILABLE,
+                                    "unavailable".to_string(),
+                                )
+                                .into_http_response()
+                                .build());
+                            }
+
+                            let header = |name: &str| {
+                                req.headers()
+                                    .get(name)
+                                    .map(|value| value.to_str().unwrap().to_string())
+                                    .unwrap_or_else(|| panic!("Missing {name} header"))
+                            };
+                            let signature = header("X-Signature");
+                            let timestamp = header("X-Timestamp");
+                            let body = fetch_body(&mut req, 1024 * 1024, 0).await.unwrap();
+                            webhook
+                                .tx
+                                .send(WebhookRequest {
+                                    signature,
+                                    timestamp,
+                                    body,
+                                })
+                                .await
+                                .unwrap();
+
+                            Ok::<_, hyper::Error>(
+                                HtmlResponse::new("ok".to_string())
+                                    .into_http_response()
+                                    .build(),
+                            )
+                        }
+                    }),
+                )
+                .await;
+        }
+    }
+
+    #[allow(clippy::manual_async_fn)]
+    fn shutdown(&self) -> impl std::future::Future<Output = ()> + Send {
+        async {}
+    }
+}
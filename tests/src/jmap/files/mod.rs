@@ -6,3 +6,4 @@
 
 pub mod acl;
 pub mod node;
+pub mod share;
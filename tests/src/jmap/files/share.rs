@@ -0,0 +1,301 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::{
+    account::Account,
+    jmap::{JmapResponse, JmapUtils},
+    server::TestServer,
+};
+use ahash::AHashSet;
+use jmap_proto::{object::file_node::FileNodeProperty, request::method::MethodObject};
+use reqwest::{Method, StatusCode};
+use serde_json::json;
+
+pub async fn test(test: &TestServer) {
+    println!("Running File share link tests...");
+    let account = test.account("jdoe@example.com");
+
+    // Create a shared folder with a file and an upload folder
+    let response = account
+        .jmap_method_calls(json!([
+         [
+          "Blob/upload",
+          {
+           "accountId": account.id_string(),
+           "create": {
+            "report": {
+             "data": [
+              {
+               "data:asText": "quarterly numbers"
+              }
+            ]
+           }
+          }
+         },
+         "S0"
+        ],
+        [
+          "FileNode/set",
+          {
+            "accountId": account.id_string(),
+            "create": {
+              "i0": {
+                "name": "Shared",
+                "parentId": null,
+              },
+              "i1": {
+                "name": "report.txt",
+                "parentId": "#i0",
+                "blobId": "#report",
+                "type": "text/plain",
+              },
+              "i2": {
+                "name": "Uploads",
+                "parentId": null,
+              }
+            }
+          },
+          "S1"
+         ]
+        ]))
+        .await;
+    let folder_id = created_id(&response, "i0");
+    let file_id = created_id(&response, "i1");
+    let drop_id = created_id(&response, "i2");
+
+    // Nodes have no share links by default
+    let response = get_share_links(account, &file_id).await;
+    assert_eq!(response.list()[0].pointer("/shareLinks"), Some(&json!({})));
+
+    // Create a link limited to two downloads
+    account
+        .jmap_update(
+            MethodObject::FileNode,
+            [(
+                &file_id,
+                json!({ "shareLinks": { "new": { "maxDownloads": 2 } } }),
+            )],
+            Vec::<(&str, &str)>::new(),
+        )
+        .await
+        .updated(&file_id);
+    let (token, link) = share_link(account, &file_id).await;
+    assert_eq!(link["maxDownloads"], json!(2));
+    assert_eq!(link["downloads"], json!(0));
+    assert_eq!(link["hasPassword"], json!(false));
+    let url = link["url"].as_str().unwrap().to_string();
+    for _ in 0..2 {
+        let (status, body) = share_request(Method::GET, &url, None, "").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "quarterly numbers");
+    }
+    let (status, _) = share_request(Method::GET, &url, None, "").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (_, link) = share_link(account, &file_id).await;
+    assert_eq!(link["downloads"], json!(2));
+
+    // Invalid tokens are rejected
+    let (prefix, _) = url.rsplit_once('/').unwrap();
+    let (status, _) = share_request(Method::GET, &format!("{prefix}/invalid"), None, "").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // File drop links are only allowed on folders
+    let response = account
+        .jmap_update(
+            MethodObject::FileNode,
+            [(
+                &file_id,
+                json!({ format!("shareLinks/{token}"): { "fileDrop": true } }),
+            )],
+            Vec::<(&str, &str)>::new(),
+        )
+        .await;
+    assert_eq!(
+        response.not_updated(&file_id).description(),
+        "File drop links can only be created for folders."
+    );
+
+    // Share the folder with a password
+    account
+        .jmap_update(
+            MethodObject::FileNode,
+            [(
+                &folder_id,
+                json!({ "shareLinks/new": { "password": "s3cr3t" } }),
+            )],
+            Vec::<(&str, &str)>::new(),
+        )
+        .await
+        .updated(&folder_id);
+    let (folder_token, link) = share_link(account, &folder_id).await;
+    assert_eq!(link["hasPassword"], json!(true));
+    let folder_url = link["url"].as_str().unwrap().to_string();
+    let (status, _) = share_request(Method::GET, &folder_url, None, "").await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = share_request(Method::GET, &folder_url, Some("wrong"), "").await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, listing) = share_request(Method::GET, &folder_url, Some("s3cr3t"), "").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&listing).unwrap(),
+        json!([{"name": "report.txt", "type": "file", "size": 17}])
+    );
+    let (status, body) = share_request(
+        Method::GET,
+        &format!("{folder_url}/report.txt"),
+        Some("s3cr3t"),
+        "",
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "quarterly numbers");
+    let (status, _) = share_request(
+        Method::GET,
+        &format!("{folder_url}/missing.txt"),
+        Some("s3cr3t"),
+        "",
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Uploads are rejected on regular links
+    let (status, _) = share_request(
+        Method::PUT,
+        &format!("{folder_url}/upload.txt"),
+        Some("s3cr3t"),
+        "hello",
+    )
+    .await;
+    assert_eq!(status, StatusCode::METHOD_NOT_ALLOWED);
+
+    // Revoke the folder link
+    account
+        .jmap_update(
+            MethodObject::FileNode,
+            [(
+                &folder_id,
+                json!({ format!("shareLinks/{folder_token}"): null }),
+            )],
+            Vec::<(&str, &str)>::new(),
+        )
+        .await
+        .updated(&folder_id);
+    let (status, _) = share_request(Method::GET, &folder_url, Some("s3cr3t"), "").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Upload-only links accept files without exposing the folder contents
+    account
+        .jmap_update(
+            MethodObject::FileNode,
+            [(
+                &drop_id,
+                json!({ "shareLinks": { "new": { "fileDrop": true } } }),
+            )],
+            Vec::<(&str, &str)>::new(),
+        )
+        .await
+        .updated(&drop_id);
+    let (_, link) = share_link(account, &drop_id).await;
+    assert_eq!(link["fileDrop"], json!(true));
+    let drop_url = link["url"].as_str().unwrap().to_string();
+    for contents in ["first upload", "second upload"] {
+        let (status, _) = share_request(
+            Method::PUT,
+            &format!("{drop_url}/upload.txt"),
+            None,
+            contents,
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+    for name in ["a%2Fb.txt", "a%0Ab.txt", "a%3Ab.txt", "CON", "%FF"] {
+        let (status, _) =
+            share_request(Method::PUT, &format!("{drop_url}/{name}"), None, "x").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{name}");
+    }
+    let (status, _) = share_request(Method::GET, &drop_url, None, "").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = share_request(Method::GET, &format!("{drop_url}/upload.txt"), None, "").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let response = account
+        .jmap_get(
+            MethodObject::FileNode,
+            [FileNodeProperty::Id, FileNodeProperty::Name],
+            Vec::<&str>::new(),
+        )
+        .await;
+    let names = response
+        .list()
+        .iter()
+        .map(|node| node.text_field("name").to_string())
+        .collect::<AHashSet<_>>();
+    assert!(names.contains("upload.txt"), "{names:?}");
+    assert!(names.contains("upload-1.txt"), "{names:?}");
+
+    // Destroying the nodes removes their links
+    account
+        .jmap_destroy(
+            MethodObject::FileNode,
+            [&folder_id, &drop_id],
+            [("onDestroyRemoveChildren", true)],
+        )
+        .await
+        .destroyed()
+        .for_each(drop);
+    let (status, _) = share_request(Method::PUT, &format!("{drop_url}/x.txt"), None, "x").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    test.assert_is_empty().await;
+}
+
+fn created_id(response: &JmapResponse, id: &str) -> String {
+    response
+        .pointer(&format!("/methodResponses/1/1/created/{id}"))
+        .unwrap()
+        .id()
+        .to_string()
+}
+
+async fn get_share_links(account: &Account, id: &str) -> JmapResponse {
+    account
+        .jmap_get(
+            MethodObject::FileNode,
+            [FileNodeProperty::Id, FileNodeProperty::ShareLinks],
+            [id],
+        )
+        .await
+}
+
+async fn share_link(account: &Account, id: &str) -> (String, serde_json::Value) {
+    let response = get_share_links(account, id).await;
+    let (token, link) = response.list()[0]
+        .pointer("/shareLinks")
+        .and_then(|links| links.as_object())
+        .and_then(|links| links.iter().next())
+        .expect("share link");
+
+    (token.to_string(), link.clone())
+}
+
+async fn share_request(
+    method: Method,
+    url: &str,
+    password: Option<&str>,
+    body: &str,
+) -> (StatusCode, String) {
+    let path = &url[url.find("/file/share/").unwrap()..];
+    let mut request = reqwest::Client::builder()
+        .danger_accept_invalid_certs(true)
+        .build()
+        .unwrap()
+        .request(method, format!("https://127.0.0.1:8899{path}"))
+        .body(body.to_string());
+    if let Some(password) = password {
+        request = request.basic_auth("share", Some(password));
+    }
+    let response = request.send().await.unwrap();
+
+    (response.status(), response.text().await.unwrap())
+}
@@ -12,9 +12,11 @@ pub mod mailbox;
 pub mod parse;
 pub mod query;
 pub mod query_changes;
+pub mod recipient;
 pub mod search_snippet;
 pub mod set;
 pub mod sieve_script;
+pub mod snooze;
 pub mod submission;
 pub mod thread_get;
 pub mod thread_merge;
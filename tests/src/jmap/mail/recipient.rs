@@ -0,0 +1,213 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::{account::Account, jmap::JmapUtils, server::TestServer};
+use email::submission::history::SentRecipientHistory;
+use jmap_proto::request::method::MethodObject;
+use serde_json::{Value, json};
+use store::write::BatchBuilder;
+use types::{collection::Collection, field::PrincipalField};
+
+pub async fn test(test: &TestServer) {
+    println!("Running Recipient/suggest tests...");
+    let account = test.account("robert@example.com");
+    let john = test.account("jdoe@example.com");
+
+    // Empty queries return no suggestions
+    assert_eq!(suggest(account, "", None).await, json!([]));
+    assert_eq!(suggest(account, "   ", None).await, json!([]));
+
+    // Create personal contacts
+    let book_id = account
+        .jmap_create(
+            MethodObject::AddressBook,
+            [json!({ "name": "Personal" })],
+            Vec::<(&str, &str)>::new(),
+        )
+        .await
+        .created(0)
+        .id()
+        .to_string();
+    let response = account
+        .jmap_create(
+            MethodObject::ContactCard,
+            [
+                contact(&book_id, "Sarah Connor", "Sarah.Connor@resistance.org"),
+                contact(&book_id, "Kyle Reese", "kyle@resistance.org"),
+            ],
+            Vec::<(&str, &str)>::new(),
+        )
+        .await;
+    let sarah_id = response.created(0).id().to_string();
+    let kyle_id = response.created(1).id().to_string();
+
+    // Create a contact in an address book shared by John
+    let john_book_id = john
+        .jmap_create(
+            MethodObject::AddressBook,
+            [json!({ "name": "Shared" })],
+            Vec::<(&str, &str)>::new(),
+        )
+        .await
+        .created(0)
+        .id()
+        .to_string();
+    john.jmap_update(
+        MethodObject::AddressBook,
+        [(
+            &john_book_id,
+            json!({ "shareWith": { account.id_string(): { "mayRead": true } } }),
+        )],
+        Vec::<(&str, &str)>::new(),
+    )
+    .await
+    .updated(&john_book_id);
+    john.jmap_create(
+        MethodObject::ContactCard,
+        [contact(
+            &john_book_id,
+            "Marcus Wright",
+            "marcus@resistance.org",
+        )],
+        Vec::<(&str, &str)>::new(),
+    )
+    .await
+    .created(0);
+
+    // Record sent history
+    test.server
+        .record_sent_recipients(
+            account.id().document_id(),
+            [
+                "kyle@resistance.org".to_string(),
+                "tim@resistance.org".to_string(),
+            ],
+        )
+        .await
+        .unwrap();
+
+    // Domain prefixes match all sources, ranked by source and sent history
+    let suggestions = suggest(account, "resistance", None).await;
+    assert_eq!(
+        emails(&suggestions),
+        [
+            "kyle@resistance.org",
+            "sarah.connor@resistance.org",
+            "tim@resistance.org",
+            "marcus@resistance.org"
+        ]
+    );
+    assert_eq!(
+        sources(&suggestions),
+        ["personal", "personal", "sent", "shared"]
+    );
+    assert_eq!(suggestions[0]["name"], "Kyle Reese");
+    assert_eq!(suggestions[0]["contactCardId"], kyle_id.as_str());
+    assert_eq!(suggestions[1]["contactCardId"], sarah_id.as_str());
+    assert_eq!(suggestions[2]["name"], Value::Null);
+    assert_eq!(suggestions[3]["name"], "Marcus Wright");
+    assert_eq!(suggestions[3]["contactCardId"], Value::Null);
+    assert!(suggestions[0]["score"].as_f64().unwrap() > suggestions[1]["score"].as_f64().unwrap());
+
+    // Results are truncated to the requested limit
+    assert_eq!(
+        emails(&suggest(account, "resistance", Some(2)).await),
+        ["kyle@resistance.org", "sarah.connor@resistance.org"]
+    );
+
+    // Email prefixes and name words are matched case-insensitively
+    assert_eq!(
+        emails(&suggest(account, "KYLE", None).await),
+        ["kyle@resistance.org"]
+    );
+    assert_eq!(
+        emails(&suggest(account, "conn", None).await),
+        ["sarah.connor@resistance.org"]
+    );
+    assert_eq!(
+        emails(&suggest(account, "wright", None).await),
+        ["marcus@resistance.org"]
+    );
+    assert_eq!(suggest(account, "skynet", None).await, json!([]));
+
+    // Directory entries are suggested with their principal id
+    let suggestions = suggest(account, "foobar", None).await;
+    assert_eq!(
+        emails(&suggestions),
+        ["bill@example.com", "robert@example.com"]
+    );
+    assert_eq!(sources(&suggestions), ["directory", "directory"]);
+    assert_eq!(suggestions[0]["name"], "Bill Foobar");
+    assert_eq!(
+        suggestions[1]["principalId"],
+        account.id_string(),
+        "{suggestions:?}"
+    );
+
+    // Revoking access removes shared contacts from the suggestions
+    john.jmap_update(
+        MethodObject::AddressBook,
+        [(&john_book_id, json!({ "shareWith": {} }))],
+        Vec::<(&str, &str)>::new(),
+    )
+    .await
+    .updated(&john_book_id);
+    assert_eq!(suggest(account, "marcus", None).await, json!([]));
+
+    // Clean up
+    let mut batch = BatchBuilder::new();
+    batch
+        .with_account_id(account.id().document_id())
+        .with_collection(Collection::Principal)
+        .with_document(0)
+        .clear(PrincipalField::SentRecipients);
+    test.server.commit_batch(batch).await.unwrap();
+    account.destroy_all_addressbooks().await;
+    john.destroy_all_addressbooks().await;
+    test.assert_is_empty().await;
+}
+
+pub async fn suggest(account: &Account, text: &str, limit: Option<usize>) -> Value {
+    let mut arguments = json!({
+        "accountId": account.id_string(),
+        "text": text,
+    });
+    if let Some(limit) = limit {
+        arguments["limit"] = json!(limit);
+    }
+    account
+        .jmap_method_call("Recipient/suggest", arguments)
+        .await
+        .method_response()["suggestions"]
+        .clone()
+}
+
+fn contact(book_id: &str, name: &str, email: &str) -> Value {
+    json!({
+        "@type": "Card",
+        "addressBookIds": { book_id: true },
+        "name": { "full": name },
+        "emails": { "e1": { "address": email } }
+    })
+}
+
+fn emails(suggestions: &Value) -> Vec<&str> {
+    suggestions
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|suggestion| suggestion["email"].as_str().unwrap())
+        .collect()
+}
+
+fn sources(suggestions: &Value) -> Vec<&str> {
+    suggestions
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|suggestion| suggestion["source"].as_str().unwrap())
+        .collect()
+}
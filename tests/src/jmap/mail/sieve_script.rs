@@ -6,7 +6,13 @@
 
 use crate::{
     jmap::mail::submission::{MockMessage, assert_message_delivery, spawn_mock_smtp_server},
-    utils::{dns::DnsCache, server::TestServer, smtp::SmtpConnection},
+    utils::{
+        account::Account,
+        dns::DnsCache,
+        jmap::{JmapResponse, JmapUtils},
+        server::TestServer,
+        smtp::SmtpConnection,
+    },
 };
 use jmap_client::{
     Error,
@@ -15,6 +21,7 @@ use jmap_client::{
     sieve::query::{Comparator, Filter},
 };
 use registry::schema::{prelude::ObjectType, structs::SieveUserScript};
+use serde_json::{Value, json};
 use std::{
     fs,
     path::PathBuf,
@@ -140,6 +147,9 @@ pub async fn test(test: &TestServer) {
         Vec::<String>::new()
     );
 
+    // Run scripts against a sample message without side effects
+    test_dry_run(account, script_ids.first().unwrap()).await;
+
     // Connect to LMTP service
     let mut lmtp = SmtpConnection::connect().await;
 
@@ -555,6 +565,182 @@ pub async fn test(test: &TestServer) {
     test.assert_is_empty().await;
 }
 
+async fn test_dry_run(account: &Account, script_id: &str) {
+    // Track changes to scripts
+    let state = account
+        .jmap_method_call(
+            "SieveScript/get",
+            json!({ "accountId": account.id_string(), "ids": [] }),
+        )
+        .await
+        .state()
+        .to_string();
+    let client = account.jmap_client().await;
+    let new_script_id = client
+        .sieve_script_create("dry_run", b"keep;".to_vec(), false)
+        .await
+        .unwrap()
+        .take_id();
+    let response = sieve_changes(account, &state).await;
+    assert_eq!(
+        response.method_response()["created"],
+        json!([&new_script_id])
+    );
+    assert_eq!(response.method_response()["updated"], json!([]));
+    assert_eq!(response.method_response()["destroyed"], json!([]));
+    client.sieve_script_destroy(&new_script_id).await.unwrap();
+    let response = sieve_changes(account, response.new_state()).await;
+    assert_eq!(
+        response.method_response()["destroyed"],
+        json!([&new_script_id])
+    );
+
+    // Upload a sample message
+    let email_blob_id = upload_text(
+        account,
+        concat!(
+            "From: bill@remote.org\r\n",
+            "To: jdoe@example.com\r\n",
+            "Subject: TPS Report\r\n",
+            "\r\n",
+            "I'm going to need those TPS reports ASAP.",
+        ),
+    )
+    .await;
+
+    for (script, expected) in [
+        (
+            concat!(
+                "require [\"fileinto\", \"mailbox\", \"imap4flags\"];\n",
+                "if header :contains \"subject\" \"TPS\" {\n",
+                "  fileinto :create :flags \"\\\\Flagged\" \"Reports/TPS\";\n",
+                "}\n",
+            ),
+            json!([{
+                "type": "fileinto",
+                "mailbox": "Reports/TPS",
+                "mailboxId": null,
+                "flags": ["\\Flagged"],
+                "create": true
+            }]),
+        ),
+        (
+            "require \"reject\";\nreject \"No reports, please.\";\n",
+            json!([{ "type": "reject", "reason": "No reports, please." }]),
+        ),
+        (
+            "redirect \"jane@example.com\";\n",
+            json!([{ "type": "redirect", "recipients": ["jane@example.com"] }]),
+        ),
+        ("discard;\n", json!([{ "type": "discard" }])),
+        (
+            "if header :contains \"subject\" \"Invoice\" { discard; }\n",
+            json!([{ "type": "keep", "flags": [] }]),
+        ),
+    ] {
+        let script_blob_id = upload_text(account, script).await;
+        let response = sieve_test(
+            account,
+            json!({ "scriptBlobId": script_blob_id, "emailBlobId": &email_blob_id }),
+        )
+        .await;
+        assert_eq!(response["error"], Value::Null, "{response:?}");
+        assert_eq!(response["actions"], expected, "{script}");
+    }
+
+    // Vacation responses are reported but not sent
+    let script_blob_id = upload_text(
+        account,
+        "require \"vacation\";\nvacation :subject \"Out of office\" \"I'm away.\";\n",
+    )
+    .await;
+    let response = sieve_test(
+        account,
+        json!({
+            "scriptBlobId": script_blob_id,
+            "emailBlobId": &email_blob_id,
+            "envelopeFrom": "bill@remote.org",
+            "envelopeTo": "jdoe@example.com"
+        }),
+    )
+    .await;
+    assert_eq!(
+        response["actions"],
+        json!([
+            { "type": "vacation", "recipients": ["bill@remote.org"] },
+            { "type": "keep", "flags": [] }
+        ]),
+        "{response:?}"
+    );
+
+    // Dry runs do not create mailboxes
+    assert!(
+        client
+            .mailbox_query(mailbox::query::Filter::name("TPS").into(), None::<Vec<_>>)
+            .await
+            .unwrap()
+            .ids()
+            .is_empty()
+    );
+
+    // Stored scripts can be tested by id
+    let response = sieve_test(
+        account,
+        json!({ "sieveScriptId": script_id, "emailBlobId": &email_blob_id }),
+    )
+    .await;
+    assert_eq!(response["actions"][0]["type"], json!("fileinto"));
+    assert_eq!(response["actions"][0]["mailbox"], json!("1"));
+
+    // Invalid scripts and missing blobs are reported
+    let script_blob_id = upload_text(account, "if true {").await;
+    let response = sieve_test(
+        account,
+        json!({ "scriptBlobId": script_blob_id, "emailBlobId": &email_blob_id }),
+    )
+    .await;
+    assert_eq!(response["error"]["type"], json!("invalidScript"));
+    let response = sieve_test(
+        account,
+        json!({ "sieveScriptId": script_id, "emailBlobId": "not-a-blob" }),
+    )
+    .await;
+    assert_eq!(response["error"]["type"], json!("blobNotFound"));
+}
+
+async fn sieve_changes(account: &Account, state: &str) -> JmapResponse {
+    account
+        .jmap_method_call(
+            "SieveScript/changes",
+            json!({ "accountId": account.id_string(), "sinceState": state }),
+        )
+        .await
+}
+
+async fn sieve_test(account: &Account, mut arguments: Value) -> Value {
+    arguments["accountId"] = json!(account.id_string());
+    account
+        .jmap_method_call("SieveScript/test", arguments)
+        .await
+        .method_response()
+        .clone()
+}
+
+async fn upload_text(account: &Account, text: &str) -> String {
+    account
+        .jmap_method_call(
+            "Blob/upload",
+            json!({
+                "accountId": account.id_string(),
+                "create": { "i0": { "data": [{ "data:asText": text }] } }
+            }),
+        )
+        .await
+        .created(0)
+        .id()
+        .to_string()
+}
+
 fn get_script(name: &str) -> Vec<u8> {
     let mut script_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
     script_path.push("resources");
@@ -0,0 +1,171 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::{account::Account, jmap::JmapUtils, server::TestServer};
+use chrono::{TimeDelta, Utc};
+use jmap_proto::request::method::MethodObject;
+use serde_json::{Value, json};
+
+pub async fn test(test: &TestServer) {
+    println!("Running Email snooze tests...");
+    let account = test.account("jdoe@example.com");
+    let client = account.jmap_client().await;
+
+    // Create the snoozed and target mailboxes
+    let response = account
+        .jmap_create(
+            MethodObject::Mailbox,
+            [
+                json!({ "name": "Snoozed", "role": "snoozed" }),
+                json!({ "name": "Follow up" }),
+            ],
+            Vec::<(&str, &str)>::new(),
+        )
+        .await;
+    let snoozed_id = response.created(0).id().to_string();
+    let follow_up_id = response.created(1).id().to_string();
+    let inbox_id = account
+        .jmap_query(
+            MethodObject::Mailbox,
+            [("role", "inbox")],
+            Vec::<&str>::new(),
+            Vec::<(&str, &str)>::new(),
+        )
+        .await
+        .ids()
+        .next()
+        .unwrap()
+        .to_string();
+
+    let email_id = client
+        .email_import(
+            concat!(
+                "From: bill@example.com\r\n",
+                "To: jdoe@example.com\r\n",
+                "Subject: TPS Report\r\n",
+                "\r\n",
+                "Did you get the memo about the new cover sheets?"
+            )
+            .as_bytes()
+            .to_vec(),
+            [&inbox_id],
+            Some(["$seen"]),
+            None,
+        )
+        .await
+        .unwrap()
+        .take_id();
+
+    // Snoozing moves the message to the snoozed mailbox
+    let until = Utc::now() + TimeDelta::seconds(5);
+    snooze(account, &email_id, json!({ "until": until.to_rfc3339() })).await;
+    let email = get_email(account, &email_id).await;
+    assert_eq!(email["mailboxIds"], json!({ &snoozed_id: true }));
+    assert!(email["snoozed"]["until"].is_string());
+
+    // Unsnoozing moves the message back to the inbox
+    snooze(account, &email_id, Value::Null).await;
+    let email = get_email(account, &email_id).await;
+    assert_eq!(email["mailboxIds"], json!({ &inbox_id: true }));
+    assert_eq!(email["snoozed"], Value::Null);
+
+    // Unsnoozing a message with a target mailbox moves it there
+    snooze(
+        account,
+        &email_id,
+        json!({ "until": until.to_rfc3339(), "moveToMailboxId": &follow_up_id }),
+    )
+    .await;
+    snooze(account, &email_id, Value::Null).await;
+    let email = get_email(account, &email_id).await;
+    assert_eq!(email["mailboxIds"], json!({ &follow_up_id: true }));
+
+    // Snoozing until a past date is rejected
+    let past = Utc::now() - TimeDelta::seconds(1);
+    assert_eq!(
+        account
+            .jmap_update(
+                MethodObject::Email,
+                [(
+                    &email_id,
+                    json!({ "snoozed": { "until": past.to_rfc3339() } }),
+                )],
+                Vec::<(&str, &str)>::new(),
+            )
+            .await
+            .not_updated(&email_id)
+            .description(),
+        "The until date must be in the future."
+    );
+
+    // Expired snoozes move the message to the target mailbox and apply keywords
+    let wake_up = Utc::now() + TimeDelta::seconds(2);
+    snooze(
+        account,
+        &email_id,
+        json!({
+            "until": wake_up.to_rfc3339(),
+            "moveToMailboxId": &inbox_id,
+            "setKeywords": { "$flagged": true }
+        }),
+    )
+    .await;
+    if let Ok(wait) = (wake_up - Utc::now()).to_std() {
+        tokio::time::sleep(wait).await;
+    }
+    test.wait_for_tasks_skip_not_due().await;
+    let email = get_email(account, &email_id).await;
+    assert_eq!(email["mailboxIds"], json!({ &inbox_id: true }));
+    assert_eq!(
+        email["keywords"],
+        json!({ "$seen": true, "$flagged": true })
+    );
+    assert_eq!(email["snoozed"], Value::Null);
+
+    // Messages moved out of the snoozed mailbox are left in place on wake up
+    snooze(account, &email_id, json!({ "until": until.to_rfc3339() })).await;
+    account
+        .jmap_update(
+            MethodObject::Email,
+            [(&email_id, json!({ "mailboxIds": { &follow_up_id: true } }))],
+            Vec::<(&str, &str)>::new(),
+        )
+        .await
+        .updated(&email_id);
+    if let Ok(wait) = (until - Utc::now()).to_std() {
+        tokio::time::sleep(wait).await;
+    }
+    test.wait_for_tasks().await;
+    let email = get_email(account, &email_id).await;
+    assert_eq!(email["mailboxIds"], json!({ &follow_up_id: true }));
+    assert_eq!(email["snoozed"], Value::Null);
+
+    test.destroy_all_mailboxes(account).await;
+    test.assert_is_empty().await;
+}
+
+async fn snooze(account: &Account, email_id: &str, snoozed: Value) {
+    account
+        .jmap_update(
+            MethodObject::Email,
+            [(email_id, json!({ "snoozed": snoozed }))],
+            Vec::<(&str, &str)>::new(),
+        )
+        .await
+        .updated(email_id);
+}
+
+async fn get_email(account: &Account, email_id: &str) -> Value {
+    account
+        .jmap_get(
+            MethodObject::Email,
+            ["mailboxIds", "keywords", "snoozed"],
+            [email_id],
+        )
+        .await
+        .list()[0]
+        .clone()
+}
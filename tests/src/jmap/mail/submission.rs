@@ -5,7 +5,7 @@
  */
 
 use crate::{
-    jmap::mail::set::assert_email_properties,
+    jmap::mail::{recipient::suggest, set::assert_email_properties},
     utils::{dns::DnsCache, server::TestServer},
 };
 use ahash::AHashMap;
@@ -235,6 +235,19 @@ pub async fn test(test: &TestServer) {
     )
     .await;
 
+    // Recipients are added to the sent history
+    let suggestions = suggest(account, "remote.org", None).await;
+    assert_eq!(suggestions.as_array().unwrap().len(), 2, "{suggestions:?}");
+    for (suggestion, email) in suggestions
+        .as_array()
+        .unwrap()
+        .iter()
+        .zip(["bill@remote.org", "jane_smith@remote.org"])
+    {
+        assert_eq!(suggestion["email"], email, "{suggestions:?}");
+        assert_eq!(suggestion["source"], "sent", "{suggestions:?}");
+    }
+
     // Manually add recipients to the envelope and confirm submission
     let email_submission_id = client
         .email_submission_create_envelope(
@@ -581,7 +594,8 @@ pub async fn test(test: &TestServer) {
         .with_account_id(account.id().document_id())
         .with_collection(Collection::Principal)
         .with_document(0)
-        .clear(PrincipalField::IdentityAddresses);
+        .clear(PrincipalField::IdentityAddresses)
+        .clear(PrincipalField::SentRecipients);
     test.server.commit_batch(batch).await.unwrap();
 
     test.assert_is_empty().await;
@@ -194,25 +194,33 @@ pub async fn jmap_tests() {
     mail::mailbox::test(&test).await;
     mail::acl::test(&test).await;
     mail::sieve_script::test(&test).await;
+    mail::snooze::test(&test).await;
     mail::vacation_response::test(&test).await;
     mail::submission::test(&test).await;
+    mail::recipient::test(&test).await;
 
     core::event_source::test(&test).await;
     core::websocket::test(&test).await;
     core::push_subscription::test(&test).await;
+    core::webhook::test(&test).await;
     core::blob::test(&test).await;
 
     contacts::addressbook::test(&test).await;
     contacts::contact::test(&test).await;
     contacts::acl::test(&test).await;
+    contacts::merge::test(&test).await;
 
     files::node::test(&test).await;
     files::acl::test(&test).await;
+    files::share::test(&test).await;
 
     calendar::calendars::test(&test).await;
     calendar::event::test(&test).await;
     calendar::notification::test(&test).await;
+    calendar::resource::test(&test).await;
     calendar::alarm::test(&test).await;
+    calendar::subscription::test(&test).await;
+    calendar::publish::test(&test).await;
 
     calendar::identity::test(&test).await;
     calendar::acl::test(&test).await;
@@ -8,12 +8,22 @@ use crate::utils::{cleanup::store_destroy, server::TestServerBuilder};
 use ahash::AHashMap;
 use email::message::metadata::MessageMetadata;
 use registry::{
-    schema::{enums::CompressionAlgo, structs::Jmap},
+    schema::{
+        enums::{CompressionAlgo, CompressionContentClass},
+        structs::{BlobStoreBase, FileSystemStore, Jmap, TieredBlobStore},
+    },
     types::duration::Duration,
 };
 use services::task_manager::destroy_account::destroy_account_blobs;
 use store::{
     BlobStore, Serialize, SerializeInfallible,
+    backend::composite::tiered_blob::TieredBlob,
+    dispatch::{
+        compression::{
+            BlobCompression, CompressionDictionaries, ContentClassDetect, train_dictionary,
+        },
+        encryption::BlobMasterKeys,
+    },
     write::{Archiver, BatchBuilder, BlobLink, BlobOp, ValueClass, now},
 };
 use types::{blob::BlobClass, blob_hash::BlobHash, collection::Collection, field::EmailField};
@@ -48,6 +58,8 @@ pub async fn blob_tests() {
 
     // Test and reset store
     test_store(blob_store.clone()).await;
+    test_compression(blob_store.clone()).await;
+    test_encryption(blob_store.clone()).await;
     store_destroy(&store).await;
 
     // Blob hash exists
@@ -447,6 +459,199 @@ pub async fn blob_tests() {
     test.temp_dir.delete();
 }
 
+#[tokio::test]
+pub async fn blob_tiered_tests() {
+    let test = TestServerBuilder::new("blob_tiered_tests")
+        .await
+        .build()
+        .await;
+    let store = test.server.core.storage.data.clone();
+    let path = test.temp_dir.path.to_str().unwrap();
+    let blob_store = TieredBlob::open(TieredBlobStore {
+        hot: BlobStoreBase::FileSystem(FileSystemStore {
+            path: format!("{path}/hot"),
+            ..Default::default()
+        }),
+        cold: BlobStoreBase::FileSystem(FileSystemStore {
+            path: format!("{path}/cold"),
+            ..Default::default()
+        }),
+        migrate_after: Duration::from_millis(3600 * 1000),
+    })
+    .await
+    .unwrap();
+    let BlobStore::Tiered(tiered) = &blob_store else {
+        unreachable!()
+    };
+
+    println!("Testing tiered blob store...");
+
+    // Test and reset store
+    test_store(blob_store.clone()).await;
+    store_destroy(&store).await;
+
+    // Write a recent blob and a blob committed before timestamps were recorded
+    let recent = BlobHash::generate(b"recent".as_slice());
+    let legacy = BlobHash::generate(b"legacy".as_slice());
+    for (hash, data, committed_at) in [
+        (&recent, b"recent", now().serialize()),
+        (&legacy, b"legacy", vec![]),
+    ] {
+        blob_store
+            .put_blob(hash.as_ref(), data, CompressionAlgo::Lz4)
+            .await
+            .unwrap();
+        store
+            .write(
+                BatchBuilder::new()
+                    .with_account_id(0)
+                    .with_collection(Collection::Email)
+                    .with_document(0)
+                    .set(
+                        BlobOp::Link {
+                            hash: hash.clone(),
+                            to: BlobLink::Document,
+                        },
+                        vec![],
+                    )
+                    .set(BlobOp::Commit { hash: hash.clone() }, committed_at)
+                    .build_all(),
+            )
+            .await
+            .unwrap();
+    }
+
+    // Only the legacy blob is migrated, twice to make sure migration is idempotent
+    for _ in 0..2 {
+        for shard_index in 0u8..=255 {
+            tiered.migrate_blobs(&store, shard_index).await.unwrap();
+        }
+
+        for (hash, is_cold) in [(&recent, false), (&legacy, true)] {
+            assert_eq!(
+                tiered
+                    .hot
+                    .get_blob(hash.as_ref(), 0..usize::MAX)
+                    .await
+                    .unwrap()
+                    .is_some(),
+                !is_cold
+            );
+            assert_eq!(
+                tiered
+                    .cold
+                    .get_blob(hash.as_ref(), 0..usize::MAX)
+                    .await
+                    .unwrap()
+                    .is_some(),
+                is_cold
+            );
+            assert!(store.blob_exists(hash).await.unwrap());
+        }
+    }
+
+    // Blobs are read transparently from either tier
+    for (hash, data) in [(&recent, b"recent"), (&legacy, b"legacy")] {
+        assert_eq!(
+            blob_store
+                .get_blob(hash.as_ref(), 0..usize::MAX)
+                .await
+                .unwrap()
+                .unwrap(),
+            data
+        );
+    }
+
+    // Recompressed blobs are rewritten in the tier that holds them
+    for hash in [&recent, &legacy] {
+        assert!(
+            blob_store
+                .recompress_blob(hash.as_ref(), CompressionAlgo::Zstd.into())
+                .await
+                .unwrap()
+        );
+    }
+    for (hash, data, is_cold) in [(&recent, b"recent", false), (&legacy, b"legacy", true)] {
+        assert_eq!(
+            tiered
+                .hot
+                .get_blob(hash.as_ref(), 0..usize::MAX)
+                .await
+                .unwrap()
+                .is_some(),
+            !is_cold
+        );
+        assert_eq!(
+            tiered
+                .cold
+                .get_blob(hash.as_ref(), 0..usize::MAX)
+                .await
+                .unwrap()
+                .is_some(),
+            is_cold
+        );
+        assert_eq!(
+            blob_store
+                .get_blob(hash.as_ref(), 0..usize::MAX)
+                .await
+                .unwrap()
+                .unwrap(),
+            data
+        );
+    }
+
+    // Downgraded stores stop migrating but keep reading from both tiers
+    let downgraded = blob_store.clone().downgrade_store();
+    assert!(!downgraded.is_enterprise());
+    for (hash, data) in [(&recent, b"recent"), (&legacy, b"legacy")] {
+        assert_eq!(
+            downgraded
+                .get_blob(hash.as_ref(), 0..usize::MAX)
+                .await
+                .unwrap()
+                .unwrap(),
+            data
+        );
+    }
+
+    // Unlinked blobs are purged from both tiers
+    store
+        .write(
+            BatchBuilder::new()
+                .with_account_id(0)
+                .with_collection(Collection::Email)
+                .with_document(0)
+                .clear(BlobOp::Link {
+                    hash: recent.clone(),
+                    to: BlobLink::Document,
+                })
+                .clear(BlobOp::Link {
+                    hash: legacy.clone(),
+                    to: BlobLink::Document,
+                })
+                .build_all(),
+        )
+        .await
+        .unwrap();
+    store
+        .purge_blobs_all_shards(blob_store.clone())
+        .await
+        .unwrap();
+    for hash in [&recent, &legacy] {
+        assert!(!store.blob_exists(hash).await.unwrap());
+        for tier in [&tiered.hot, &tiered.cold] {
+            assert!(
+                tier.get_blob(hash.as_ref(), 0..usize::MAX)
+                    .await
+                    .unwrap()
+                    .is_none()
+            );
+        }
+    }
+
+    test.temp_dir.delete();
+}
+
 async fn test_store(store: BlobStore) {
     // Test small blob
     const DATA: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit. Fusce erat nisl, dignissim a porttitor id, varius nec arcu. Sed mauris.";
@@ -531,3 +736,249 @@ async fn test_store(store: BlobStore) {
             .is_none()
     );
 }
+
+async fn test_compression(store: BlobStore) {
+    // Build a set of message headers
+    let headers = (0..2000)
+        .map(|i| {
+            format!(
+                concat!(
+                    "Return-Path: <sender{i}@example.org>\r\n",
+                    "Received: from mx{r}.example.org (mx{r}.example.org [192.0.2.{r}])\r\n",
+                    "\tby mail.example.com with ESMTPS id {i:08x}\r\n",
+                    "Message-ID: <{i}.{r}@example.org>\r\n",
+                    "From: Sender {i} <sender{i}@example.org>\r\n",
+                    "To: Recipient {r} <recipient{r}@example.com>\r\n",
+                    "Subject: Report number {i} for account {r}\r\n",
+                    "Date: Mon, {d} Mar 2025 10:{r:02}:00 +0000\r\n",
+                    "MIME-Version: 1.0\r\n",
+                    "Content-Type: text/plain; charset=\"utf-8\"\r\n",
+                    "Content-Transfer-Encoding: quoted-printable\r\n\r\n",
+                    "Body of message {i}.\r\n"
+                ),
+                i = i,
+                r = i % 60,
+                d = (i % 28) + 1
+            )
+            .into_bytes()
+        })
+        .collect::<Vec<_>>();
+    let message = headers[1234].clone();
+    let hash = BlobHash::generate(&message);
+    assert_eq!(
+        CompressionContentClass::detect(&message),
+        Some(CompressionContentClass::Headers)
+    );
+
+    // LZ4 blobs are recompressed with Zstd
+    let zstd = BlobCompression::new(CompressionAlgo::Zstd, 19);
+    store
+        .put_blob(hash.as_slice(), &message, CompressionAlgo::Lz4)
+        .await
+        .unwrap();
+    assert!(store.recompress_blob(hash.as_slice(), zstd).await.unwrap());
+    assert!(!store.recompress_blob(hash.as_slice(), zstd).await.unwrap());
+    assert_eq!(
+        store
+            .get_blob(hash.as_slice(), 0..usize::MAX)
+            .await
+            .unwrap()
+            .unwrap(),
+        message
+    );
+    assert_eq!(
+        store
+            .get_blob(hash.as_slice(), 12..40)
+            .await
+            .unwrap()
+            .unwrap(),
+        message[12..40]
+    );
+
+    // Blobs are recompressed once a dictionary is available
+    let samples = headers
+        .iter()
+        .filter_map(|data| CompressionContentClass::Headers.training_sample(data))
+        .collect::<Vec<_>>();
+    assert_eq!(samples.len(), headers.len());
+    let dictionary = train_dictionary(&samples).unwrap();
+    CompressionDictionaries::register(u64::MAX, CompressionContentClass::Headers, dictionary);
+    assert!(CompressionDictionaries::is_registered(u64::MAX));
+    assert!(store.recompress_blob(hash.as_slice(), zstd).await.unwrap());
+    assert!(!store.recompress_blob(hash.as_slice(), zstd).await.unwrap());
+    assert_eq!(
+        store
+            .get_blob(hash.as_slice(), 0..usize::MAX)
+            .await
+            .unwrap()
+            .unwrap(),
+        message
+    );
+
+    // Content without a dictionary is compressed with plain Zstd
+    const DATA: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit.";
+    let plain_hash = BlobHash::generate(DATA);
+    store
+        .put_blob(plain_hash.as_slice(), DATA, zstd)
+        .await
+        .unwrap();
+    assert_eq!(
+        store
+            .get_blob(plain_hash.as_slice(), 0..usize::MAX)
+            .await
+            .unwrap()
+            .unwrap(),
+        DATA
+    );
+
+    // Zstd blobs can be converted back to LZ4
+    assert!(
+        store
+            .recompress_blob(hash.as_slice(), CompressionAlgo::Lz4.into())
+            .await
+            .unwrap()
+    );
+    assert_eq!(
+        store
+            .get_blob(hash.as_slice(), 0..usize::MAX)
+            .await
+            .unwrap()
+            .unwrap(),
+        message
+    );
+
+    for hash in [hash, plain_hash] {
+        assert!(store.delete_blob(hash.as_slice()).await.unwrap());
+    }
+}
+
+async fn test_encryption(store: BlobStore) {
+    const DATA: &[u8] = b"Sed ut perspiciatis unde omnis iste natus error sit voluptatem.";
+    let hash = BlobHash::generate(DATA);
+
+    // Blobs are encrypted with the active master key
+    BlobMasterKeys::install([(1, "first master key".to_string())]);
+    assert_eq!(BlobMasterKeys::active_id(), Some(1));
+    store
+        .put_blob(hash.as_slice(), DATA, CompressionAlgo::Lz4)
+        .await
+        .unwrap();
+    assert_eq!(
+        store
+            .get_blob(hash.as_slice(), 0..usize::MAX)
+            .await
+            .unwrap()
+            .unwrap(),
+        DATA
+    );
+    assert_eq!(
+        store
+            .get_blob(hash.as_slice(), 6..20)
+            .await
+            .unwrap()
+            .unwrap(),
+        DATA[6..20]
+    );
+    assert!(!store.rewrap_blob_key(hash.as_slice()).await.unwrap());
+    let stored = store
+        .get_stored_blob(hash.as_slice())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!stored.windows(DATA.len()).any(|window| window == DATA));
+
+    // Rotating the master key rewraps the data key only once
+    BlobMasterKeys::install([
+        (1, "first master key".to_string()),
+        (2, "second master key".to_string()),
+    ]);
+    assert_eq!(BlobMasterKeys::active_id(), Some(2));
+    assert!(store.rewrap_blob_key(hash.as_slice()).await.unwrap());
+    assert!(!store.rewrap_blob_key(hash.as_slice()).await.unwrap());
+
+    // Only the header is rewritten, the ciphertext is kept as is
+    let rewrapped = store
+        .get_stored_blob(hash.as_slice())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(rewrapped.len(), stored.len());
+    assert_ne!(rewrapped, stored);
+    assert_eq!(
+        rewrapped[stored.len() - DATA.len()..],
+        stored[stored.len() - DATA.len()..]
+    );
+
+    // The previous master key is no longer needed after rewrapping
+    BlobMasterKeys::install([(2, "second master key".to_string())]);
+    assert_eq!(
+        store
+            .get_blob(hash.as_slice(), 0..usize::MAX)
+            .await
+            .unwrap()
+            .unwrap(),
+        DATA
+    );
+
+    // Blobs cannot be read with an unknown master key
+    BlobMasterKeys::install([(3, "third master key".to_string())]);
+    assert!(
+        store
+            .get_blob(hash.as_slice(), 0..usize::MAX)
+            .await
+            .is_err()
+    );
+
+    // Blobs are decrypted when encryption is disabled
+    BlobMasterKeys::install([(2, "second master key".to_string())]);
+    let compression = BlobCompression::from(CompressionAlgo::Lz4);
+    assert!(
+        !store
+            .recompress_blob(hash.as_slice(), compression)
+            .await
+            .unwrap()
+    );
+    BlobMasterKeys::install([]);
+    assert!(
+        store
+            .recompress_blob(hash.as_slice(), compression)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !store
+            .recompress_blob(hash.as_slice(), compression)
+            .await
+            .unwrap()
+    );
+    assert_eq!(
+        store
+            .get_blob(hash.as_slice(), 0..usize::MAX)
+            .await
+            .unwrap()
+            .unwrap(),
+        DATA
+    );
+
+    assert!(store.delete_blob(hash.as_slice()).await.unwrap());
+
+    // Plaintext blobs that start with the encryption header are not decrypted
+    let mut plain_data = b"\xb1ENC".to_vec();
+    plain_data.extend_from_slice(&[0xa3; 128]);
+    let plain_hash = BlobHash::generate(&plain_data);
+    store
+        .put_blob(plain_hash.as_slice(), &plain_data, CompressionAlgo::None)
+        .await
+        .unwrap();
+    BlobMasterKeys::install([(2, "second master key".to_string())]);
+    assert_eq!(
+        store
+            .get_blob(plain_hash.as_slice(), 0..usize::MAX)
+            .await
+            .unwrap()
+            .unwrap(),
+        plain_data
+    );
+    BlobMasterKeys::install([]);
+    assert!(store.delete_blob(plain_hash.as_slice()).await.unwrap());
+}
@@ -0,0 +1,170 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::server::TestServer;
+use registry::{schema::structs, types::duration::Duration};
+use std::{io::Write, path::Path};
+use store::{
+    Store, ValueKey,
+    backend::ephemeral::EphemeralStore,
+    write::{BatchBuilder, ValueClass},
+};
+use types::collection::{Collection, SyncCollection};
+
+const FIELD: u8 = 1;
+const ACCOUNT_ID: u32 = 1;
+
+pub async fn test(test: &TestServer) {
+    println!("Running ephemeral store persistence tests...");
+    let path = test.temp_dir.path.join("ephemeral_persist");
+    if path.exists() {
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+
+    // Stores without a path are kept in memory only
+    let store = EphemeralStore::open_with_config(structs::EphemeralStore::default())
+        .await
+        .unwrap();
+    assert!(store.is_ephemeral());
+
+    // Write data and reopen the store
+    let store = open(&path).await;
+    assert!(!store.is_ephemeral());
+    for document_id in 0..10u32 {
+        write_property(&store, document_id, "initial").await;
+    }
+    store
+        .write(
+            BatchBuilder::new()
+                .with_account_id(ACCOUNT_ID)
+                .add(ValueClass::Quota, 1024)
+                .with_collection(Collection::Email)
+                .with_document(3)
+                .clear(ValueClass::Property(FIELD))
+                .build_all(),
+        )
+        .await
+        .unwrap();
+    let last_change_id = change_id(&store).await;
+    drop(store);
+
+    let store = open(&path).await;
+    assert_eq!(change_id(&store).await, last_change_id);
+    assert_eq!(quota(&store).await, 1024);
+    for document_id in 0..10u32 {
+        assert_eq!(
+            property(&store, document_id).await.as_deref(),
+            (document_id != 3).then_some("initial"),
+            "document {document_id}"
+        );
+    }
+
+    // Changes made after a snapshot are replayed from the write log
+    write_property(&store, 3, "after snapshot").await;
+    store
+        .write(
+            BatchBuilder::new()
+                .with_account_id(ACCOUNT_ID)
+                .add(ValueClass::Quota, -24)
+                .build_all(),
+        )
+        .await
+        .unwrap();
+    assert!(std::fs::metadata(path.join("write.log")).unwrap().len() > 0);
+    drop(store);
+
+    // Partially written records are discarded
+    std::fs::OpenOptions::new()
+        .append(true)
+        .open(path.join("write.log"))
+        .unwrap()
+        .write_all(&[0xff; 7])
+        .unwrap();
+    let store = open(&path).await;
+    assert_eq!(property(&store, 3).await.as_deref(), Some("after snapshot"));
+    assert_eq!(quota(&store).await, 1000);
+    assert_eq!(change_id(&store).await, last_change_id + 1);
+    assert_eq!(std::fs::metadata(path.join("write.log")).unwrap().len(), 0);
+
+    // Corrupted snapshots are rejected
+    drop(store);
+    let mut snapshot = std::fs::read(path.join("snapshot")).unwrap();
+    let last = snapshot.len() - 1;
+    snapshot[last] ^= 0xff;
+    std::fs::write(path.join("snapshot"), snapshot).unwrap();
+    assert!(
+        EphemeralStore::open_with_config(config(&path))
+            .await
+            .is_err()
+    );
+
+    std::fs::remove_dir_all(&path).unwrap();
+}
+
+fn config(path: &Path) -> structs::EphemeralStore {
+    structs::EphemeralStore {
+        path: path.to_string_lossy().to_string().into(),
+        snapshot_interval: Duration::from_millis(0),
+        sync_writes: true,
+    }
+}
+
+async fn open(path: &Path) -> Store {
+    EphemeralStore::open_with_config(config(path))
+        .await
+        .unwrap()
+}
+
+async fn write_property(store: &Store, document_id: u32, value: &str) {
+    store
+        .write(
+            BatchBuilder::new()
+                .with_account_id(ACCOUNT_ID)
+                .with_collection(Collection::Email)
+                .with_document(document_id)
+                .set(ValueClass::Property(FIELD), value.as_bytes().to_vec())
+                .log_item_update(SyncCollection::Email, None)
+                .build_all(),
+        )
+        .await
+        .unwrap();
+}
+
+async fn property(store: &Store, document_id: u32) -> Option<String> {
+    store
+        .get_value::<String>(ValueKey {
+            account_id: ACCOUNT_ID,
+            collection: Collection::Email.into(),
+            document_id,
+            class: ValueClass::Property(FIELD),
+        })
+        .await
+        .unwrap()
+}
+
+async fn change_id(store: &Store) -> i64 {
+    store
+        .get_counter(ValueKey {
+            account_id: ACCOUNT_ID,
+            collection: 0,
+            document_id: 0,
+            class: ValueClass::ChangeId,
+        })
+        .await
+        .unwrap()
+}
+
+async fn quota(store: &Store) -> i64 {
+    store
+        .get_counter(ValueKey {
+            account_id: ACCOUNT_ID,
+            collection: 0,
+            document_id: 0,
+            class: ValueClass::Quota,
+        })
+        .await
+        .unwrap()
+}
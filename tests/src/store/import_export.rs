@@ -9,9 +9,21 @@ use crate::utils::{
     server::TestServer,
     temp_dir::TempDir,
 };
-use ::registry::schema::enums::CompressionAlgo;
+use ::registry::schema::{
+    enums::CompressionAlgo,
+    structs::{BlobStoreBase, FileSystemStore},
+};
 use ahash::AHashSet;
-use common::{DATABASE_SCHEMA_VERSION, manager::backup::BackupParams};
+use common::{
+    DATABASE_SCHEMA_VERSION,
+    manager::{
+        backup::BackupParams,
+        location::BackupLocation,
+        manifest::{BackupIndex, BackupManifest, IndexedBackup, verify_backup},
+        restore::RestoreParams,
+    },
+};
+use std::path::{Path, PathBuf};
 use store::{
     rand,
     write::{
@@ -201,19 +213,270 @@ pub async fn test(test: &TestServer) {
 
     // Import store
     println!("Importing store...");
-    test.server.core.restore(temp_dir.path.clone()).await;
+    test.server
+        .core
+        .restore(RestoreParams::new(temp_dir.path.clone()))
+        .await;
 
     // Verify hash
     print!("Verifying store hash...");
     snapshot.assert_is_eq(&Snapshot::new(&db).await);
     println!(" GREAT SUCCESS!");
 
+    // Test incremental backups
+    test_incremental(test, &temp_dir.path).await;
+
+    // Test backups stored in a blob store
+    test_remote(test).await;
+
     // Destroy store
     store_destroy(&db).await;
     store_assert_is_empty(&db, db.clone().into(), true).await;
     temp_dir.delete();
 }
 
+async fn test_incremental(test: &TestServer, full_backup: &Path) {
+    let db = test.server.store().clone();
+    let temp_dir = TempDir::new("art_vandelay_incremental_tests", true);
+    let incr_1 = temp_dir.path.join("1");
+    let incr_2 = temp_dir.path.join("2");
+
+    // Modify some accounts and add a new blob
+    println!("Creating incremental backups...");
+    update_accounts(&db, &[1, 2], 3).await;
+    let data = random_bytes(1024);
+    let hash = BlobHash::generate(data.as_slice());
+    test.server
+        .blob_store()
+        .put_blob(hash.as_ref(), &data, CompressionAlgo::Lz4)
+        .await
+        .unwrap();
+    let mut batch = BatchBuilder::new();
+    batch
+        .with_account_id(1)
+        .with_collection(Collection::Email)
+        .with_document(100)
+        .set(
+            ValueClass::Blob(BlobOp::Commit { hash: hash.clone() }),
+            vec![],
+        )
+        .set(
+            ValueClass::Blob(BlobOp::Link {
+                hash,
+                to: BlobLink::Document,
+            }),
+            vec![],
+        );
+    db.write(batch.build_all()).await.unwrap();
+    let snapshot_1 = Snapshot::new(&db).await;
+    test.server
+        .core
+        .backup(BackupParams::new(incr_1.clone()).with_parent(full_backup.to_path_buf()))
+        .await;
+
+    let manifest = BackupManifest::read(&BackupLocation::Local, &incr_1)
+        .await
+        .unwrap();
+    assert_eq!(manifest.sequence, 1);
+    assert_eq!(manifest.accounts.len(), 10);
+    for (account_id, account) in &manifest.accounts {
+        let expected = if [1, 2, 3].contains(account_id) { 1 } else { 0 };
+        assert_eq!(account.data, expected, "account {account_id}");
+        assert_eq!(account.logs_from, 0, "account {account_id}");
+    }
+    if let Some(blobs) = manifest
+        .files
+        .iter()
+        .find(|file| file.subspace == SUBSPACE_BLOBS && !file.blob_index)
+    {
+        assert_eq!(blobs.keys, 1);
+    }
+
+    // Modify another account and create a second incremental backup
+    update_accounts(&db, &[4], 2).await;
+
+    // Writes that do not bump the change id are also exported
+    let mut batch = BatchBuilder::new();
+    batch
+        .with_account_id(5)
+        .with_collection(Collection::Email)
+        .with_document(10)
+        .set(ValueClass::Property(0), random_bytes(32));
+    db.write(batch.build_all()).await.unwrap();
+    let snapshot_2 = Snapshot::new(&db).await;
+    test.server
+        .core
+        .backup(BackupParams::new(incr_2.clone()).with_parent(incr_1.clone()))
+        .await;
+    let manifest = BackupManifest::read(&BackupLocation::Local, &incr_2)
+        .await
+        .unwrap();
+    assert_eq!(manifest.sequence, 2);
+    assert_eq!(
+        manifest.parent.as_ref().unwrap().path,
+        Path::new("1").to_path_buf()
+    );
+    assert_eq!(manifest.accounts[&1].data, 1);
+    assert_eq!(manifest.accounts[&2].data, 2);
+    assert_eq!(manifest.accounts[&4].data, 2);
+    assert_eq!(manifest.accounts[&5].data, 2);
+    assert_eq!(manifest.accounts[&6].data, 0);
+
+    // Verify the backup chain
+    let report = verify_backup(&BackupLocation::Local, &incr_2)
+        .await
+        .unwrap();
+    assert_eq!(report.backups, 3);
+    assert!(report.keys > 0);
+
+    // Restore the latest backup
+    println!("Restoring incremental backups...");
+    store_destroy(&db).await;
+    test.server
+        .core
+        .restore(RestoreParams::new(incr_2.clone()))
+        .await;
+    snapshot_2.assert_is_eq(&Snapshot::new(&db).await);
+
+    // Restore to a previous point in time
+    store_destroy(&db).await;
+    test.server
+        .core
+        .restore(RestoreParams::new(incr_2.clone()).with_sequence(1))
+        .await;
+    snapshot_1.assert_is_eq(&Snapshot::new(&db).await);
+
+    // Corrupted backups are detected
+    let manifest = BackupManifest::read(&BackupLocation::Local, &incr_1)
+        .await
+        .unwrap();
+    let file = incr_1.join(&manifest.files[0].name);
+    let mut contents = std::fs::read(&file).unwrap();
+    let last = contents.len() - 1;
+    contents[last] ^= 0xff;
+    std::fs::write(&file, contents).unwrap();
+    assert!(
+        verify_backup(&BackupLocation::Local, &incr_2)
+            .await
+            .is_err()
+    );
+
+    temp_dir.delete();
+}
+
+async fn test_remote(test: &TestServer) {
+    let db = test.server.store().clone();
+    let temp_dir = TempDir::new("art_vandelay_remote_tests", true);
+    let blob_store = BlobStore::open(BlobStoreBase::FileSystem(FileSystemStore {
+        path: temp_dir.path.to_str().unwrap().to_string(),
+        ..Default::default()
+    }))
+    .await
+    .unwrap();
+    let location = BackupLocation::remote(blob_store.clone(), "backups/", Some("secret"));
+    let full = PathBuf::from("full");
+    let incr = PathBuf::from("incr");
+
+    // Write a full and an incremental backup to the blob store
+    println!("Creating remote backups...");
+    update_accounts(&db, &[1, 2], 3).await;
+    let snapshot_1 = Snapshot::new(&db).await;
+    test.server
+        .core
+        .backup(BackupParams::new(full.clone()).with_location(location.clone()))
+        .await;
+    update_accounts(&db, &[5], 6).await;
+    let snapshot_2 = Snapshot::new(&db).await;
+    test.server
+        .core
+        .backup(
+            BackupParams::new(incr.clone())
+                .with_location(location.clone())
+                .with_parent(full.clone()),
+        )
+        .await;
+    let manifest = BackupManifest::read(&location, &incr).await.unwrap();
+    assert_eq!(manifest.sequence, 1);
+    assert_eq!(manifest.accounts[&5].data, 1);
+
+    // Backups can only be read with the right key
+    let report = verify_backup(&location, &incr).await.unwrap();
+    assert_eq!(report.backups, 2);
+    for secret in [Some("wrong secret"), None] {
+        let location = BackupLocation::remote(blob_store.clone(), "backups/", secret);
+        assert!(verify_backup(&location, &incr).await.is_err());
+    }
+
+    // Restore from the blob store
+    println!("Restoring remote backups...");
+    store_destroy(&db).await;
+    test.server
+        .core
+        .restore(RestoreParams::new(incr.clone()).with_location(location.clone()))
+        .await;
+    snapshot_2.assert_is_eq(&Snapshot::new(&db).await);
+    store_destroy(&db).await;
+    test.server
+        .core
+        .restore(RestoreParams::new(full.clone()).with_location(location.clone()))
+        .await;
+    snapshot_1.assert_is_eq(&Snapshot::new(&db).await);
+
+    // Retention keeps the requested backups and the backups they depend on
+    const DAY: u64 = 86400;
+    let index = BackupIndex {
+        backups: [
+            ("a", 80, None),
+            ("b", 81, Some("a")),
+            ("c", 95, None),
+            ("d", 99, Some("c")),
+            ("e", 100, Some("d")),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(id, (name, day, parent))| IndexedBackup {
+            name: name.to_string(),
+            id: id as u64,
+            sequence: parent.is_some() as u32,
+            parent: parent.map(|parent| parent.to_string()),
+            created_at: day * DAY,
+        })
+        .collect(),
+    };
+    let retained = |daily, weekly| {
+        let mut names = index
+            .retained(100 * DAY + 1, daily, weekly)
+            .into_iter()
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    };
+    assert_eq!(retained(2, 0), ["c", "d", "e"]);
+    assert_eq!(retained(1, 4), ["a", "b", "c", "d", "e"]);
+    assert_eq!(retained(0, 0), ["c", "d", "e"]);
+
+    temp_dir.delete();
+}
+
+async fn update_accounts(db: &Store, account_ids: &[u32], deleted_account_id: u32) {
+    let mut batch = BatchBuilder::new();
+    for account_id in account_ids {
+        batch
+            .with_account_id(*account_id)
+            .with_collection(Collection::Email)
+            .with_document(10)
+            .set(ValueClass::Property(0), random_bytes(32))
+            .log_item_update(SyncCollection::Email, None);
+    }
+    batch
+        .with_account_id(deleted_account_id)
+        .with_collection(Collection::Email)
+        .with_document(20)
+        .clear(ValueClass::Property(1))
+        .log_item_update(SyncCollection::Email, None);
+    db.write(batch.build_all()).await.unwrap();
+}
+
 #[derive(Debug, PartialEq, Eq)]
 struct Snapshot {
     keys: AHashSet<KeyValue>,
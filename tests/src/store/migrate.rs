@@ -0,0 +1,185 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::{cleanup::store_destroy, server::TestServer};
+use store::{
+    Store, ValueKey,
+    backend::ephemeral::EphemeralStore,
+    write::{
+        BatchBuilder, ValueClass,
+        migrate::{MIGRATION_SUBSPACES, StoreMigration},
+    },
+};
+use types::collection::{Collection, SyncCollection};
+
+const FIELD: u8 = 1;
+
+pub async fn test(test: &TestServer) {
+    println!("Running data store migration tests...");
+    let source = test.server.store().clone();
+    let target = EphemeralStore::open();
+    let migration = StoreMigration::new(source.clone(), target.clone());
+
+    // Populate the source store
+    for account_id in 0..3u32 {
+        for document_id in 0..10u32 {
+            write_property(&source, account_id, document_id, "initial").await;
+        }
+        source
+            .write(
+                BatchBuilder::new()
+                    .with_account_id(account_id)
+                    .add(ValueClass::Quota, 1024)
+                    .build_all(),
+            )
+            .await
+            .unwrap();
+    }
+
+    // Copy all subspaces in small chunks
+    for subspace in MIGRATION_SUBSPACES {
+        let mut chunks = 0;
+        loop {
+            let progress = migration.copy_subspace(*subspace, 7).await.unwrap();
+            chunks += 1;
+            if progress.is_done {
+                break;
+            }
+            assert!(chunks < 100, "Subspace {subspace} did not finish copying");
+        }
+    }
+    assert_eq!(migration.sync_all().await.unwrap(), 0);
+    assert_eq!(migration.sync_changed_accounts().await.unwrap(), 0);
+    for account_id in 0..3u32 {
+        assert_eq!(migration.sync_account(account_id).await.unwrap(), 0);
+        assert_eq!(quota(&target, account_id).await, 1024);
+    }
+
+    // Copying again should be a no-op
+    for subspace in MIGRATION_SUBSPACES {
+        assert!(migration.copy_subspace(*subspace, 7).await.unwrap().is_done);
+    }
+
+    // Changes made after the copy are detected and synchronized
+    write_property(&source, 1, 3, "updated").await;
+    write_property(&source, 2, 10, "inserted").await;
+    source
+        .write(
+            BatchBuilder::new()
+                .with_account_id(2)
+                .with_collection(Collection::Email)
+                .with_document(0)
+                .clear(ValueClass::Property(FIELD))
+                .log_item_delete(SyncCollection::Email, None)
+                .build_all(),
+        )
+        .await
+        .unwrap();
+    // Writes that do not bump a change id are only found by the final check
+    source
+        .write(
+            BatchBuilder::new()
+                .with_account_id(0)
+                .add(ValueClass::Quota, 1)
+                .build_all(),
+        )
+        .await
+        .unwrap();
+    assert!(migration.sync_changed_accounts().await.unwrap() > 0);
+    assert_eq!(migration.sync_changed_accounts().await.unwrap(), 0);
+    assert!(target.key_exists(property_key(1, 3)).await.unwrap());
+    assert!(target.key_exists(property_key(2, 10)).await.unwrap());
+    assert!(!target.key_exists(property_key(2, 0)).await.unwrap());
+    assert_eq!(quota(&target, 0).await, 1024);
+    assert!(migration.sync_all().await.unwrap() > 0);
+    assert_eq!(migration.sync_all().await.unwrap(), 0);
+    assert_eq!(quota(&target, 0).await, 1025);
+
+    // The fence is applied once synced and acknowledged by all nodes
+    let registry = test.server.registry();
+    let fence_id = registry.fence_writes().await.unwrap();
+    assert!(!source.is_write_fenced());
+    registry.sync_write_fence().await.unwrap();
+    assert!(source.is_write_fenced());
+    assert!(!target.is_write_fenced());
+    assert!(
+        registry
+            .is_write_fence_acknowledged(fence_id)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !registry
+            .is_write_fence_acknowledged(fence_id.wrapping_add(1))
+            .await
+            .unwrap()
+    );
+
+    // Fenced stores reject writes
+    assert!(
+        source
+            .write(
+                BatchBuilder::new()
+                    .with_account_id(0)
+                    .add(ValueClass::Quota, 1)
+                    .build_all()
+            )
+            .await
+            .is_err()
+    );
+    write_property(&target, 0, 0, "target").await;
+
+    // The fence is not copied to the target store
+    assert!(migration.sync_all().await.unwrap() > 0);
+    assert!(
+        !target
+            .key_exists(ValueKey::from(ValueClass::WriteFence))
+            .await
+            .unwrap()
+    );
+
+    registry.unfence_writes().await.unwrap();
+    assert!(!source.is_write_fenced());
+    write_property(&source, 0, 0, "unfenced").await;
+
+    store_destroy(&source).await;
+}
+
+async fn write_property(store: &Store, account_id: u32, document_id: u32, value: &str) {
+    store
+        .write(
+            BatchBuilder::new()
+                .with_account_id(account_id)
+                .with_collection(Collection::Email)
+                .with_document(document_id)
+                .set(ValueClass::Property(FIELD), value.as_bytes().to_vec())
+                .log_item_update(SyncCollection::Email, None)
+                .build_all(),
+        )
+        .await
+        .unwrap();
+}
+
+async fn quota(store: &Store, account_id: u32) -> i64 {
+    store
+        .get_counter(ValueKey {
+            account_id,
+            collection: 0,
+            document_id: 0,
+            class: ValueClass::Quota,
+        })
+        .await
+        .unwrap()
+}
+
+fn property_key(account_id: u32, document_id: u32) -> ValueKey<ValueClass> {
+    ValueKey {
+        account_id,
+        collection: Collection::Email.into(),
+        document_id,
+        class: ValueClass::Property(FIELD),
+    }
+}
@@ -5,11 +5,15 @@
  */
 
 pub mod blob;
+pub mod ephemeral;
 pub mod import_export;
 pub mod lookup;
+pub mod migrate;
 pub mod ops;
 pub mod query;
 pub mod registry;
+pub mod sharding;
+pub mod usage;
 
 use crate::utils::server::TestServerBuilder;
 use std::io::Read;
@@ -24,6 +28,10 @@ pub async fn store_tests() {
 
     registry::test(&test).await;
     import_export::test(&test).await;
+    migrate::test(&test).await;
+    ephemeral::test(&test).await;
+    usage::test(&test).await;
+    sharding::test(&test).await;
     ops::test(&test).await;
 
     if test.is_reset() {
@@ -0,0 +1,381 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::{cleanup::store_destroy, server::TestServer};
+use store::{
+    IterateParams, SUBSPACE_PROPERTY, Store, U32_LEN, ValueKey,
+    backend::{composite::sharded_data::ShardedStore, ephemeral::EphemeralStore},
+    write::{
+        AnyKey, BatchBuilder, InMemoryClass, ValueClass, assert::AssertValue,
+        key::DeserializeBigEndian,
+    },
+};
+use types::collection::{Collection, SyncCollection};
+
+const FIELD: u8 = 1;
+const SHARD_ID: u64 = 1;
+
+pub async fn test(test: &TestServer) {
+    println!("Running data store sharding tests...");
+    let primary = test.server.store().clone();
+    let shard = EphemeralStore::open();
+    let store = ShardedStore::open(primary.clone(), vec![(SHARD_ID, shard.clone())])
+        .await
+        .unwrap();
+    let sharded = store.as_sharded().unwrap();
+
+    // Accounts are placed on the primary store by default
+    for account_id in 0..3u32 {
+        for document_id in 0..5u32 {
+            write_property(&store, account_id, document_id, "initial").await;
+        }
+        assert_eq!(sharded.account_shard(account_id), None);
+    }
+    assert!(primary.key_exists(property_key(1, 0)).await.unwrap());
+    assert!(!shard.key_exists(property_key(1, 0)).await.unwrap());
+
+    // Move an account to the shard
+    assert!(sharded.sync_account(1, Some(SHARD_ID)).await.unwrap() > 0);
+    write_property(&store, 1, 5, "during copy").await;
+    assert!(sharded.sync_account(1, Some(SHARD_ID)).await.unwrap() > 0);
+    assert_eq!(sharded.sync_account(1, Some(SHARD_ID)).await.unwrap(), 0);
+    assert!(sharded.commit_move(1, Some(SHARD_ID)).await.is_err());
+
+    // Fences are applied on reload and acknowledged by each node
+    let node_id = test.server.registry().node_id();
+    let fence_id = sharded.fence_account(1).await.unwrap();
+    assert!(!sharded.is_account_fenced(1));
+    assert!(
+        !sharded
+            .is_fence_acknowledged(1, fence_id, [node_id])
+            .await
+            .unwrap()
+    );
+    sharded.reload_placement().await.unwrap();
+    sharded.acknowledge_fences(node_id).await.unwrap();
+    assert!(sharded.is_account_fenced(1));
+    assert!(
+        sharded
+            .is_fence_acknowledged(1, fence_id, [node_id])
+            .await
+            .unwrap()
+    );
+    assert!(
+        !sharded
+            .is_fence_acknowledged(1, fence_id + 1, [node_id])
+            .await
+            .unwrap()
+    );
+    assert!(
+        store
+            .write(
+                BatchBuilder::new()
+                    .with_account_id(1)
+                    .add(ValueClass::Quota, 1)
+                    .build_all()
+            )
+            .await
+            .is_err()
+    );
+    write_property(&store, 0, 0, "not fenced").await;
+    assert_eq!(sharded.commit_move(1, Some(SHARD_ID)).await.unwrap(), 0);
+    sharded.unfence_account(1).await.unwrap();
+    assert!(!sharded.is_account_fenced(1));
+    assert!(
+        !sharded
+            .is_fence_acknowledged(1, fence_id, [node_id])
+            .await
+            .unwrap()
+    );
+    assert!(sharded.purge_account(1, Some(SHARD_ID)).await.is_err());
+    sharded.purge_account(1, None).await.unwrap();
+    assert_eq!(sharded.account_shard(1), Some(SHARD_ID));
+    assert!(!primary.key_exists(property_key(1, 5)).await.unwrap());
+    assert!(shard.key_exists(property_key(1, 5)).await.unwrap());
+    assert_eq!(
+        store
+            .get_value::<String>(property_key(1, 5))
+            .await
+            .unwrap()
+            .as_deref(),
+        Some("during copy")
+    );
+
+    // Batches modifying accounts placed on different stores are rejected
+    let change_ids = [change_id(&store, 0).await, change_id(&store, 1).await];
+    assert!(
+        store
+            .write(
+                BatchBuilder::new()
+                    .with_account_id(0)
+                    .with_collection(Collection::Email)
+                    .with_document(10)
+                    .set(ValueClass::Property(FIELD), b"split".to_vec())
+                    .log_item_update(SyncCollection::Email, None)
+                    .with_account_id(1)
+                    .with_collection(Collection::Email)
+                    .with_document(10)
+                    .set(ValueClass::Property(FIELD), b"split".to_vec())
+                    .log_item_update(SyncCollection::Email, None)
+                    .build_all(),
+            )
+            .await
+            .is_err()
+    );
+    assert!(!primary.key_exists(property_key(0, 10)).await.unwrap());
+    assert!(!shard.key_exists(property_key(1, 10)).await.unwrap());
+    assert_eq!(change_id(&store, 0).await, change_ids[0]);
+    assert_eq!(change_id(&store, 1).await, change_ids[1]);
+
+    // ACLs are stored with the account, shared keys on the primary store
+    write_property(&store, 0, 10, "single").await;
+    store
+        .write(
+            BatchBuilder::new()
+                .with_account_id(1)
+                .with_collection(Collection::Email)
+                .with_document(10)
+                .set(ValueClass::Property(FIELD), b"single".to_vec())
+                .set(ValueClass::Acl(2), b"shared".to_vec())
+                .set(
+                    ValueClass::InMemory(InMemoryClass::Key(b"shared".to_vec())),
+                    vec![],
+                )
+                .log_item_update(SyncCollection::Email, None)
+                .build_all(),
+        )
+        .await
+        .unwrap();
+    let acl_key = ValueKey {
+        account_id: 1,
+        collection: Collection::Email.into(),
+        document_id: 10,
+        class: ValueClass::Acl(2),
+    };
+    let shared_key = ValueKey::from(ValueClass::InMemory(InMemoryClass::Key(b"shared".to_vec())));
+    assert!(primary.key_exists(property_key(0, 10)).await.unwrap());
+    assert!(!primary.key_exists(property_key(1, 10)).await.unwrap());
+    assert!(shard.key_exists(property_key(1, 10)).await.unwrap());
+    assert!(!primary.key_exists(acl_key.clone()).await.unwrap());
+    assert!(shard.key_exists(acl_key.clone()).await.unwrap());
+    assert!(store.key_exists(acl_key).await.unwrap());
+    assert!(primary.key_exists(shared_key.clone()).await.unwrap());
+    assert!(!shard.key_exists(shared_key).await.unwrap());
+    assert_eq!(change_id(&store, 0).await, change_ids[0] + 1);
+    assert_eq!(change_id(&store, 1).await, change_ids[1] + 1);
+
+    // Failed assertions on shared keys leave the account store untouched
+    let shared_class = ValueClass::InMemory(InMemoryClass::Key(b"shared".to_vec()));
+    assert!(
+        store
+            .write(
+                BatchBuilder::new()
+                    .with_account_id(1)
+                    .with_collection(Collection::Email)
+                    .with_document(11)
+                    .set(ValueClass::Property(FIELD), b"asserted".to_vec())
+                    .assert_value(shared_class.clone(), AssertValue::None)
+                    .log_item_update(SyncCollection::Email, None)
+                    .build_all(),
+            )
+            .await
+            .is_err()
+    );
+    assert!(!shard.key_exists(property_key(1, 11)).await.unwrap());
+    assert_eq!(change_id(&store, 1).await, change_ids[1] + 1);
+
+    // Batches asserting values on both stores are rejected
+    assert!(
+        store
+            .write(
+                BatchBuilder::new()
+                    .with_account_id(1)
+                    .with_collection(Collection::Email)
+                    .with_document(10)
+                    .assert_value(ValueClass::Property(FIELD), AssertValue::Some)
+                    .set(ValueClass::Property(FIELD), b"asserted".to_vec())
+                    .assert_value(shared_class, AssertValue::Some)
+                    .log_item_update(SyncCollection::Email, None)
+                    .build_all(),
+            )
+            .await
+            .is_err()
+    );
+    assert_eq!(change_id(&store, 1).await, change_ids[1] + 1);
+
+    // Share notifications are kept on the primary store
+    store
+        .write(
+            BatchBuilder::new()
+                .with_account_id(0)
+                .log_share_notification(1, 1, "notification")
+                .build_all(),
+        )
+        .await
+        .unwrap();
+    let notification_key = ValueKey::from(ValueClass::ShareNotification {
+        notification_id: 1,
+        notify_account_id: 1,
+    });
+    assert!(primary.key_exists(notification_key.clone()).await.unwrap());
+    assert!(!shard.key_exists(notification_key).await.unwrap());
+    assert_eq!(change_id(&store, 1).await, change_ids[1] + 1);
+
+    // Ranges spanning several accounts are read from all stores
+    let mut keys = Vec::new();
+    store
+        .iterate(
+            IterateParams::new(
+                AnyKey {
+                    subspace: SUBSPACE_PROPERTY,
+                    key: vec![0u8],
+                },
+                AnyKey {
+                    subspace: SUBSPACE_PROPERTY,
+                    key: vec![u8::MAX; 32],
+                },
+            )
+            .no_values(),
+            |key, _| {
+                keys.push((key.deserialize_be_u32(0)?, key.to_vec()));
+                Ok(true)
+            },
+        )
+        .await
+        .unwrap();
+    for account_id in 0..3u32 {
+        assert_eq!(
+            keys.iter().filter(|(id, _)| *id == account_id).count(),
+            [6, 7, 5][account_id as usize],
+            "account {account_id}"
+        );
+    }
+    assert!(keys.windows(2).all(|keys| keys[0].1 < keys[1].1));
+
+    // Merged ranges are read in order and stop when requested
+    for ascending in [true, false] {
+        let mut keys = Vec::new();
+        store
+            .iterate(
+                IterateParams::new(
+                    AnyKey {
+                        subspace: SUBSPACE_PROPERTY,
+                        key: vec![0u8],
+                    },
+                    AnyKey {
+                        subspace: SUBSPACE_PROPERTY,
+                        key: vec![u8::MAX; 32],
+                    },
+                )
+                .set_ascending(ascending)
+                .no_values(),
+                |key, _| {
+                    keys.push(key.to_vec());
+                    Ok(keys.len() < 10)
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(keys.len(), 10);
+        assert!(keys.windows(2).all(|keys| (keys[0] < keys[1]) == ascending));
+        assert_eq!(
+            keys[0][..U32_LEN],
+            if ascending { 0u32 } else { 2u32 }.to_be_bytes()
+        );
+    }
+
+    // Ranges within an account are read from its store
+    let mut account_keys = 0;
+    store
+        .iterate(
+            IterateParams::new(
+                AnyKey {
+                    subspace: SUBSPACE_PROPERTY,
+                    key: 1u32.to_be_bytes().to_vec(),
+                },
+                AnyKey {
+                    subspace: SUBSPACE_PROPERTY,
+                    key: 2u32.to_be_bytes().to_vec(),
+                },
+            )
+            .no_values(),
+            |key, _| {
+                assert_eq!(key.deserialize_be_u32(0)?, 1);
+                assert!(key.len() > U32_LEN);
+                account_keys += 1;
+                Ok(true)
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(account_keys, 7);
+
+    // Placements are persisted in the primary store
+    let reopened = ShardedStore::open(primary.clone(), vec![(SHARD_ID, shard.clone())])
+        .await
+        .unwrap();
+    assert_eq!(
+        reopened.as_sharded().unwrap().account_shard(1),
+        Some(SHARD_ID)
+    );
+    assert_eq!(reopened.as_sharded().unwrap().account_shard(0), None);
+
+    // Sharded stores are not downgraded while accounts are placed on shards
+    assert!(store.clone().downgrade_store().is_sharded());
+
+    // Move the account back to the primary store
+    assert!(sharded.sync_account(1, None).await.unwrap() > 0);
+    sharded.fence_account(1).await.unwrap();
+    sharded.reload_placement().await.unwrap();
+    sharded.commit_move(1, None).await.unwrap();
+    sharded.unfence_account(1).await.unwrap();
+    sharded.purge_account(1, Some(SHARD_ID)).await.unwrap();
+    assert_eq!(sharded.account_shard(1), None);
+    assert!(primary.key_exists(property_key(1, 10)).await.unwrap());
+    assert!(!shard.key_exists(property_key(1, 10)).await.unwrap());
+    assert_eq!(change_id(&store, 1).await, change_ids[1] + 1);
+    sharded.reload_placement().await.unwrap();
+    assert_eq!(sharded.account_shard(1), None);
+    assert!(!store.clone().downgrade_store().is_sharded());
+
+    store_destroy(&primary).await;
+}
+
+async fn write_property(store: &Store, account_id: u32, document_id: u32, value: &str) {
+    store
+        .write(
+            BatchBuilder::new()
+                .with_account_id(account_id)
+                .with_collection(Collection::Email)
+                .with_document(document_id)
+                .set(ValueClass::Property(FIELD), value.as_bytes().to_vec())
+                .log_item_update(SyncCollection::Email, None)
+                .build_all(),
+        )
+        .await
+        .unwrap();
+}
+
+async fn change_id(store: &Store, account_id: u32) -> i64 {
+    store
+        .get_counter(ValueKey {
+            account_id,
+            collection: 0,
+            document_id: 0,
+            class: ValueClass::ChangeId,
+        })
+        .await
+        .unwrap()
+}
+
+fn property_key(account_id: u32, document_id: u32) -> ValueKey<ValueClass> {
+    ValueKey {
+        account_id,
+        collection: Collection::Email.into(),
+        document_id,
+        class: ValueClass::Property(FIELD),
+    }
+}
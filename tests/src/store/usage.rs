@@ -0,0 +1,135 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::server::TestServer;
+use common::storage::usage::{UsageBatch, size_bucket};
+use registry::schema::enums::{StorageSizeBucket, StorageUsageType};
+use store::write::{BatchBuilder, ValueClass};
+use types::collection::Collection;
+use utils::map::vec_map::VecMap;
+
+const ACCOUNT_ID: u32 = 1000;
+
+pub async fn test(test: &TestServer) {
+    println!("Running storage usage tests...");
+    let server = &test.server;
+
+    // Size buckets
+    for (size, bucket) in [
+        (0, StorageSizeBucket::UpTo100Kb),
+        (100 * 1024 - 1, StorageSizeBucket::UpTo100Kb),
+        (100 * 1024, StorageSizeBucket::UpTo1Mb),
+        (5 * 1024 * 1024, StorageSizeBucket::UpTo10Mb),
+        (50 * 1024 * 1024, StorageSizeBucket::UpTo100Mb),
+        (u32::MAX as u64, StorageSizeBucket::Over100Mb),
+    ] {
+        assert_eq!(size_bucket(size), bucket, "size {size}");
+    }
+
+    // Add items
+    server
+        .store()
+        .write(
+            BatchBuilder::new()
+                .with_account_id(ACCOUNT_ID)
+                .item_usage(Collection::Email, 50_000, true)
+                .mailbox_usage(1, 50_000, true)
+                .item_usage(Collection::Email, 2_000_000, true)
+                .mailbox_usage(1, 2_000_000, true)
+                .mailbox_usage(2, 2_000_000, true)
+                .item_usage(Collection::FileNode, 200_000, true)
+                .item_usage(Collection::SieveScript, 1_000, true)
+                .build_all(),
+        )
+        .await
+        .unwrap();
+
+    let usage = server.get_storage_usage(ACCOUNT_ID).await.unwrap();
+    assert_eq!(
+        usage.by_type(),
+        VecMap::new()
+            .with_append(StorageUsageType::Email, 2_050_000)
+            .with_append(StorageUsageType::File, 200_000)
+            .with_append(StorageUsageType::Sieve, 1_000)
+    );
+    assert_eq!(
+        usage.by_mailbox(),
+        VecMap::new()
+            .with_append(1, 2_050_000)
+            .with_append(2, 2_000_000)
+    );
+    assert_eq!(usage.by_size(), buckets([51_000, 200_000, 2_000_000, 0, 0]));
+    assert_eq!(usage.items_by_size(), buckets([2, 1, 1, 0, 0]));
+
+    // Remove an item
+    server
+        .store()
+        .write(
+            BatchBuilder::new()
+                .with_account_id(ACCOUNT_ID)
+                .item_usage(Collection::Email, 2_000_000, false)
+                .mailbox_usage(1, 2_000_000, false)
+                .mailbox_usage(2, 2_000_000, false)
+                .build_all(),
+        )
+        .await
+        .unwrap();
+
+    let usage = server.get_storage_usage(ACCOUNT_ID).await.unwrap();
+    assert_eq!(
+        usage.by_type(),
+        VecMap::new()
+            .with_append(StorageUsageType::Email, 50_000)
+            .with_append(StorageUsageType::File, 200_000)
+            .with_append(StorageUsageType::Sieve, 1_000)
+    );
+    assert_eq!(usage.by_mailbox(), VecMap::new().with_append(1, 50_000));
+    assert_eq!(usage.by_size(), buckets([51_000, 200_000, 0, 0, 0]));
+    assert_eq!(usage.items_by_size(), buckets([2, 1, 0, 0, 0]));
+
+    // Other accounts are not affected
+    assert!(
+        server
+            .get_storage_usage(ACCOUNT_ID + 1)
+            .await
+            .unwrap()
+            .counters
+            .is_empty()
+    );
+
+    // Clear counters
+    let mut batch = BatchBuilder::new();
+    batch.with_account_id(ACCOUNT_ID);
+    for class in usage.counters.keys() {
+        batch.clear(ValueClass::Usage(*class));
+    }
+    server.store().write(batch.build_all()).await.unwrap();
+    assert!(
+        server
+            .get_storage_usage(ACCOUNT_ID)
+            .await
+            .unwrap()
+            .counters
+            .is_empty()
+    );
+}
+
+fn buckets(values: [u64; 5]) -> VecMap<StorageSizeBucket, u64> {
+    let mut map = VecMap::new();
+    for (bucket, value) in [
+        StorageSizeBucket::UpTo100Kb,
+        StorageSizeBucket::UpTo1Mb,
+        StorageSizeBucket::UpTo10Mb,
+        StorageSizeBucket::UpTo100Mb,
+        StorageSizeBucket::Over100Mb,
+    ]
+    .into_iter()
+    .zip(values)
+    {
+        map.append(bucket, value);
+    }
+    map
+}
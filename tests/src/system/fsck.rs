@@ -0,0 +1,225 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::server::TestServer;
+use common::Server;
+use email::{cache::MessageCacheFetch, mailbox::INBOX_ID, message::metadata::MessageMetadata};
+use registry::schema::{
+    enums::TaskAccountMaintenanceType,
+    structs::{Task, TaskAccountMaintenance, TaskStatus},
+};
+use services::task_manager::fsck::{IssueKind, check_account};
+use store::{
+    Serialize, ValueKey,
+    write::{AlignedBytes, Archive, Archiver, BatchBuilder, BlobLink, BlobOp, ValueClass},
+};
+use types::{
+    blob::BlobClass,
+    collection::Collection,
+    field::{EmailField, SieveField},
+    id::Id,
+};
+
+pub async fn test(test: &mut TestServer) {
+    println!("Running consistency checker tests...");
+    let inbox_id = Id::from(INBOX_ID).to_string();
+    let admin = test.account("admin@example.org");
+
+    // Create test account
+    let account = test
+        .create_user_account(
+            "admin@example.org",
+            "fsck@example.org",
+            "this is a very strong password",
+            &[],
+            "fsck@example.org",
+        )
+        .await;
+    let account_id = account.id().document_id();
+    let client = account.jmap_client().await;
+    for num in 0..3 {
+        client
+            .email_import(
+                format!(
+                    concat!(
+                        "From: bill@example.org\r\n",
+                        "To: fsck@example.org\r\n",
+                        "Subject: TPS Report #{}\r\n",
+                        "\r\n",
+                        "Did you get the memo about the new cover sheets?"
+                    ),
+                    num
+                )
+                .into_bytes(),
+                [&inbox_id],
+                None::<Vec<&str>>,
+                None,
+            )
+            .await
+            .unwrap();
+    }
+    test.wait_for_tasks().await;
+
+    // A healthy account should not report any issues
+    let report = check_account(&test.server, account_id, false)
+        .await
+        .unwrap();
+    assert_eq!(report.issues, vec![], "{report:?}");
+
+    // Corrupt the account
+    let mut document_ids = test
+        .server
+        .get_cached_messages(account_id)
+        .await
+        .unwrap()
+        .emails
+        .items
+        .iter()
+        .map(|item| item.document_id)
+        .collect::<Vec<_>>();
+    document_ids.sort_unstable();
+    let unlinked_id = document_ids[0];
+    let unlinked_hash = message_metadata(&test.server, account_id, unlinked_id)
+        .await
+        .blob_hash;
+    let missing_id = document_ids[1];
+    let missing_hash = message_metadata(&test.server, account_id, missing_id)
+        .await
+        .blob_hash;
+    let orphan_metadata = message_metadata(&test.server, account_id, document_ids[2]).await;
+    let orphan_hash = orphan_metadata.blob_hash.clone();
+    let mut batch = BatchBuilder::new();
+    batch
+        .with_account_id(account_id)
+        .with_collection(Collection::SieveScript)
+        .with_document(999)
+        .index(SieveField::Name, b"ghost".to_vec())
+        .with_collection(Collection::Email)
+        .with_document(999)
+        .set(
+            EmailField::Metadata,
+            Archiver::new(orphan_metadata).serialize().unwrap(),
+        )
+        .with_document(998)
+        .set(
+            BlobOp::Link {
+                hash: orphan_hash.clone(),
+                to: BlobLink::Document,
+            },
+            Vec::new(),
+        )
+        .with_document(unlinked_id)
+        .clear(BlobOp::Link {
+            hash: unlinked_hash.clone(),
+            to: BlobLink::Document,
+        })
+        .add(ValueClass::Quota, 1234);
+    test.server.store().write(batch.build_all()).await.unwrap();
+    assert!(
+        test.server
+            .blob_store()
+            .delete_blob(missing_hash.as_ref())
+            .await
+            .unwrap()
+    );
+
+    // Make sure all issues are detected
+    let report = check_account(&test.server, account_id, false)
+        .await
+        .unwrap();
+    let mut issues = report
+        .issues
+        .iter()
+        .map(|issue| (issue.kind, issue.collection, issue.document_id))
+        .collect::<Vec<_>>();
+    issues.sort_unstable_by_key(|(kind, _, document_id)| (kind.as_str(), *document_id));
+    assert_eq!(
+        issues,
+        vec![
+            (IssueKind::MissingBlob, Collection::Email, missing_id),
+            (IssueKind::MissingBlobLink, Collection::Email, unlinked_id),
+            (IssueKind::OrphanBlobLink, Collection::Email, 998),
+            (IssueKind::OrphanIndex, Collection::SieveScript, 999),
+            (IssueKind::OrphanMetadata, Collection::Email, 999),
+            (IssueKind::QuotaMismatch, Collection::None, u32::MAX),
+        ]
+    );
+    assert_eq!(report.repaired, 0);
+
+    // Repair the account and verify that no issues remain
+    admin
+        .registry_create_object(Task::AccountMaintenance(TaskAccountMaintenance {
+            account_id: account.id(),
+            maintenance_type: TaskAccountMaintenanceType::RepairConsistency,
+            status: TaskStatus::now(),
+        }))
+        .await;
+    test.wait_for_tasks().await;
+    let report = check_account(&test.server, account_id, false)
+        .await
+        .unwrap();
+    assert_eq!(report.issues, vec![], "{report:?}");
+    assert_eq!(
+        test.server
+            .get_cached_messages(account_id)
+            .await
+            .unwrap()
+            .emails
+            .items
+            .len(),
+        2
+    );
+    assert!(
+        test.server
+            .store()
+            .blob_has_access(
+                &unlinked_hash,
+                BlobClass::Linked {
+                    account_id,
+                    collection: Collection::Email.into(),
+                    document_id: unlinked_id,
+                }
+            )
+            .await
+            .unwrap()
+    );
+    assert!(
+        !test
+            .server
+            .store()
+            .blob_has_access(
+                &orphan_hash,
+                BlobClass::Linked {
+                    account_id,
+                    collection: Collection::Email.into(),
+                    document_id: 998,
+                }
+            )
+            .await
+            .unwrap()
+    );
+
+    // Delete account
+    admin.destroy_account(account).await;
+    test.wait_for_tasks().await;
+    test.assert_is_empty().await;
+}
+
+async fn message_metadata(server: &Server, account_id: u32, document_id: u32) -> MessageMetadata {
+    server
+        .store()
+        .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+            account_id,
+            Collection::Email,
+            document_id,
+            EmailField::Metadata,
+        ))
+        .await
+        .unwrap()
+        .unwrap()
+        .deserialize::<MessageMetadata>()
+        .unwrap()
+}
@@ -11,6 +11,7 @@ pub mod authorization;
 pub mod crypto;
 pub mod delivery;
 pub mod directory;
+pub mod fsck;
 pub mod oidc;
 pub mod purge;
 pub mod quota;
@@ -66,6 +67,7 @@ pub async fn system_tests() {
     security::test(&mut test).await;
     quota::test(&mut test).await;
     purge::test(&mut test).await;
+    fsck::test(&mut test).await;
     delivery::test(&mut test).await;
     crypto::test(&mut test).await;
     antispam::test(&mut test).await;
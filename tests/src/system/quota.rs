@@ -107,15 +107,14 @@ pub async fn test(test: &mut TestServer) {
     let raw_http =
         HttpRequest::with_credentials(8899, "user1@example.org", "this is a very strong password1");
     let upload_url = format!("/jmap/upload/{account_id}");
+    let mut blob_ids = Vec::new();
     for i in 0..3 {
-        assert_eq!(
-            client
-                .upload(None, vec![b'A' + i; 1024], None)
-                .await
-                .unwrap()
-                .size(),
-            1024
-        );
+        let mut blob = client
+            .upload(None, vec![b'A' + i; 1024], None)
+            .await
+            .unwrap();
+        assert_eq!(blob.size(), 1024);
+        blob_ids.push(blob.take_blob_id());
     }
     let resp = raw_http
         .send_full(
@@ -156,6 +155,20 @@ pub async fn test(test: &mut TestServer) {
         resp.headers
     );
     assert!(resp.body.contains("quota"), "body = {}", resp.body);
+
+    // Converted blobs count towards the temporary blob quota
+    let response = account
+        .jmap_method_call(
+            "Blob/convert",
+            json!({
+                "accountId": account.id_string(),
+                "create": {
+                    "i0": { "textExtract": { "blobId": &blob_ids[0], "maxBytes": 10 } }
+                }
+            }),
+        )
+        .await;
+    assert_eq!(response.not_created(0).typ(), "overQuota");
     test.blob_expire_all().await;
 
     // Test temporary blob quota (50000 bytes)
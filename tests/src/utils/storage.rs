@@ -13,11 +13,11 @@ use registry::{
         enums::{BlobStoreType, DataStoreType, InMemoryStoreType, SearchStoreType},
         prelude::Object,
         structs::{
-            BlobStore, DataStore, ElasticSearchStore, FileSystemStore, FoundationDbStore, HttpAuth,
-            HttpAuthBasic, HttpAuthBearer, InMemoryStore, MeilisearchStore, MySqlStore,
-            PostgreSqlStore, PublicStringOptional, PublicStringValue, RedisStore, RocksDbStore,
-            S3Store, S3StoreCustomRegion, S3StoreRegion, SearchStore, SecretKey, SecretKeyOptional,
-            SecretKeyValue, SqliteStore,
+            BlobStore, DataStore, ElasticSearchStore, EphemeralStore, FileSystemStore,
+            FoundationDbStore, HttpAuth, HttpAuthBasic, HttpAuthBearer, InMemoryStore,
+            MeilisearchStore, MySqlStore, PostgreSqlStore, PublicStringOptional, PublicStringValue,
+            RedisStore, RocksDbStore, S3Store, S3StoreCustomRegion, S3StoreRegion, SearchStore,
+            SecretKey, SecretKeyOptional, SecretKeyValue, SqliteStore,
         },
     },
     types::{EnumImpl, duration::Duration},
@@ -105,6 +105,10 @@ pub async fn build_data_store(typ: DataStoreType, path: &str) -> DataStore {
                 ..Default::default()
             })
         }
+        DataStoreType::Ephemeral => DataStore::Ephemeral(EphemeralStore {
+            path: format!("{path}/ephemeral").into(),
+            ..Default::default()
+        }),
     }
 }
 
@@ -20,16 +20,18 @@ pub async fn test(test: &TestServer) {
             "dav",
             concat!(
                 "1, 2, 3, access-control, extended-mkcol, calendar-access, ",
-                "calendar-auto-schedule, calendar-no-timezone, addressbook"
+                "calendar-auto-schedule, calendar-no-timezone, ",
+                "calendar-managed-attachments, addressbook"
             ),
         )
         .with_header(
             "allow",
             concat!(
                 "OPTIONS, GET, HEAD, POST, PUT, DELETE, COPY, MOVE, ",
-                "MKCALENDAR, MKCOL, PROPFIND, PROPPATCH, LOCK, UNLOCK, REPORT, ACL"
+                "MKCALENDAR, MKCOL, PROPFIND, PROPPATCH, LOCK, UNLOCK, REPORT, ACL, SEARCH"
             ),
-        );
+        )
+        .with_header("dasl", "<DAV:basicsearch>");
 
     // Test Discovery
     john.request("PROPFIND", "/.well-known/carddav", "")
@@ -0,0 +1,334 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::TEST_ICAL_2;
+use crate::utils::{server::TestServer, webdav::DummyWebDavClient};
+use hyper::StatusCode;
+
+const ATTACHMENT_1: &str = "first attachment contents";
+const ATTACHMENT_2: &str = "second attachment";
+const ATTACHMENT_3: &str = "updated attachment contents";
+
+pub async fn test(test: &TestServer) {
+    println!("Running managed attachment tests...");
+    let client = test.account("john@example.com").webdav_client();
+    let path = "/dav/cal/john%40example.com/default/attachments.ics";
+    client
+        .request_with_headers(
+            "PUT",
+            path,
+            [("content-type", "text/calendar; charset=utf-8")],
+            TEST_ICAL_2.replace('\n', "\r\n"),
+        )
+        .await
+        .with_status(StatusCode::CREATED);
+
+    // Invalid requests are rejected
+    for (query, body) in [
+        ("?action=attachment-unknown", ATTACHMENT_1),
+        ("?action=attachment-add", ""),
+        ("?action=attachment-update", ATTACHMENT_1),
+        ("?action=attachment-remove", ""),
+        (
+            "?action=attachment-update&managed-id=abc&rid=M",
+            ATTACHMENT_1,
+        ),
+        ("?action=attachment-add&rid=20990101T000000Z", ATTACHMENT_1),
+        ("?action=attachment-add&rid=invalid", ATTACHMENT_1),
+    ] {
+        client
+            .request("POST", &format!("{path}{query}"), body)
+            .await
+            .with_status(StatusCode::BAD_REQUEST);
+    }
+
+    // Attachments are added to all instances by default
+    let response = add_attachment(&client, path, "", ATTACHMENT_1, "first.txt").await;
+    let managed_id_1 = response.0;
+    let ical = response.1;
+    assert_eq!(
+        ical.matches(&format!("MANAGED-ID={managed_id_1}")).count(),
+        2,
+        "{ical}"
+    );
+    assert!(
+        ical.contains(&format!("SIZE={}", ATTACHMENT_1.len())),
+        "{ical}"
+    );
+    assert!(ical.contains("FILENAME=first.txt"), "{ical}");
+    assert!(ical.contains("FMTTYPE=text/plain"), "{ical}");
+    assert!(
+        ical.contains(&format!("/attachments.ics?managed-id={managed_id_1}")),
+        "{ical}"
+    );
+    get_attachment(&client, path, &managed_id_1)
+        .await
+        .with_status(StatusCode::OK)
+        .with_header("content-type", "text/plain")
+        .with_body(ATTACHMENT_1);
+
+    // Attachments can be added to specific instances
+    let (managed_id_2, ical) =
+        add_attachment(&client, path, "&rid=M", ATTACHMENT_2, "second.txt").await;
+    assert_eq!(
+        ical.matches(&format!("MANAGED-ID={managed_id_2}")).count(),
+        1,
+        "{ical}"
+    );
+    let (managed_id, ical) = add_attachment(
+        &client,
+        path,
+        "&rid=20150707T190000Z",
+        ATTACHMENT_2,
+        "second.txt",
+    )
+    .await;
+    assert_eq!(managed_id, managed_id_2);
+    assert_eq!(
+        ical.matches(&format!("MANAGED-ID={managed_id_2}")).count(),
+        2,
+        "{ical}"
+    );
+
+    // Managed ids must be valid and belong to the event
+    for managed_id in [
+        format!("+{}", &managed_id_1[1..]),
+        format!("{}g", &managed_id_1[1..]),
+        "0".repeat(managed_id_1.len()),
+        "abc".to_string(),
+    ] {
+        get_attachment(&client, path, &managed_id)
+            .await
+            .with_status(StatusCode::NOT_FOUND);
+    }
+
+    // Update an attachment
+    let response = client
+        .request_with_headers(
+            "POST",
+            &format!("{path}?action=attachment-update&managed-id={managed_id_1}"),
+            [
+                ("content-type", "text/plain"),
+                (
+                    "content-disposition",
+                    "attachment; filename=\"updated.txt\"",
+                ),
+            ],
+            ATTACHMENT_3,
+        )
+        .await
+        .with_status(StatusCode::OK);
+    let managed_id_3 = response.header("cal-managed-id").to_string();
+    let ical = unfold(response.expect_body());
+    assert_ne!(managed_id_3, managed_id_1);
+    assert!(!ical.contains(&managed_id_1), "{ical}");
+    assert_eq!(
+        ical.matches(&format!("MANAGED-ID={managed_id_3}")).count(),
+        2,
+        "{ical}"
+    );
+    assert!(ical.contains("FILENAME=updated.txt"), "{ical}");
+    get_attachment(&client, path, &managed_id_3)
+        .await
+        .with_status(StatusCode::OK)
+        .with_body(ATTACHMENT_3);
+    get_attachment(&client, path, &managed_id_1)
+        .await
+        .with_status(StatusCode::NOT_FOUND);
+    client
+        .request(
+            "POST",
+            &format!("{path}?action=attachment-update&managed-id={managed_id_1}"),
+            ATTACHMENT_3,
+        )
+        .await
+        .with_status(StatusCode::BAD_REQUEST);
+
+    // Remove an attachment from a single instance, then from all instances
+    client
+        .request(
+            "POST",
+            &format!("{path}?action=attachment-remove&managed-id={managed_id_2}&rid=M"),
+            "",
+        )
+        .await
+        .with_status(StatusCode::NO_CONTENT);
+    let ical = get_event(&client, path).await;
+    assert_eq!(
+        ical.matches(&format!("MANAGED-ID={managed_id_2}")).count(),
+        1,
+        "{ical}"
+    );
+    get_attachment(&client, path, &managed_id_2)
+        .await
+        .with_status(StatusCode::OK);
+    client
+        .request(
+            "POST",
+            &format!("{path}?action=attachment-remove&managed-id={managed_id_2}"),
+            "",
+        )
+        .await
+        .with_status(StatusCode::NO_CONTENT);
+    let ical = get_event(&client, path).await;
+    assert!(!ical.contains(&managed_id_2), "{ical}");
+    get_attachment(&client, path, &managed_id_2)
+        .await
+        .with_status(StatusCode::NOT_FOUND);
+    client
+        .request(
+            "POST",
+            &format!("{path}?action=attachment-remove&managed-id={managed_id_2}"),
+            "",
+        )
+        .await
+        .with_status(StatusCode::BAD_REQUEST);
+
+    // Clients cannot forge managed ids or alter their sizes
+    let forged_id = "ab".repeat(managed_id_3.len() / 2);
+    let ical = get_event(&client, path)
+        .await
+        .replace(&format!("SIZE={}", ATTACHMENT_3.len()), "SIZE=1")
+        .replacen(
+            "END:VEVENT",
+            &format!(
+                "ATTACH;MANAGED-ID={forged_id};SIZE=1:https://example.com/forged\r\nEND:VEVENT"
+            ),
+            1,
+        );
+    client
+        .request_with_headers(
+            "PUT",
+            path,
+            [("content-type", "text/calendar; charset=utf-8")],
+            ical,
+        )
+        .await
+        .with_status(StatusCode::NO_CONTENT);
+    let ical = get_event(&client, path).await;
+    assert!(!ical.contains(&forged_id), "{ical}");
+    assert!(ical.contains("https://example.com/forged"), "{ical}");
+    assert_eq!(ical.matches("SIZE=").count(), 2, "{ical}");
+    assert_eq!(
+        ical.matches(&format!("SIZE={}", ATTACHMENT_3.len()))
+            .count(),
+        2,
+        "{ical}"
+    );
+    get_attachment(&client, path, &managed_id_3)
+        .await
+        .with_status(StatusCode::OK)
+        .with_body(ATTACHMENT_3);
+
+    // Attachments count against the quota
+    let client = test.account("mike@example.com").webdav_client();
+    let calendar_path = "/dav/cal/mike%40example.com/default/";
+    let path = format!("{calendar_path}attachments.ics");
+    client
+        .request_with_headers(
+            "PUT",
+            &path,
+            [("content-type", "text/calendar; charset=utf-8")],
+            TEST_ICAL_SMALL.replace('\n', "\r\n"),
+        )
+        .await
+        .with_status(StatusCode::CREATED);
+    let available = client.available_quota(calendar_path).await;
+    let (managed_id, _) = add_attachment(&client, &path, "", ATTACHMENT_1, "first.txt").await;
+    let available_after_add = client.available_quota(calendar_path).await;
+    assert!(
+        available_after_add + ATTACHMENT_1.len() as u64 <= available,
+        "{available_after_add} {available}"
+    );
+    client
+        .request(
+            "POST",
+            &format!("{path}?action=attachment-remove&managed-id={managed_id}"),
+            "",
+        )
+        .await
+        .with_status(StatusCode::NO_CONTENT);
+    assert!(
+        client.available_quota(calendar_path).await
+            >= available_after_add + ATTACHMENT_1.len() as u64
+    );
+    client
+        .request(
+            "POST",
+            &format!("{path}?action=attachment-add"),
+            "x".repeat(available as usize + 1),
+        )
+        .await
+        .with_status(StatusCode::PRECONDITION_FAILED)
+        .with_failed_precondition("D:quota-not-exceeded", "");
+
+    client.delete_default_containers().await;
+    test.account("john@example.com")
+        .webdav_client()
+        .delete_default_containers()
+        .await;
+    test.assert_is_empty().await;
+}
+
+async fn add_attachment(
+    client: &DummyWebDavClient,
+    path: &str,
+    params: &str,
+    contents: &str,
+    filename: &str,
+) -> (String, String) {
+    let disposition = format!("attachment; filename=\"{filename}\"");
+    let response = client
+        .request_with_headers(
+            "POST",
+            &format!("{path}?action=attachment-add{params}"),
+            [
+                ("content-type", "text/plain"),
+                ("content-disposition", disposition.as_str()),
+            ],
+            contents,
+        )
+        .await
+        .with_status(StatusCode::CREATED);
+    (
+        response.header("cal-managed-id").to_string(),
+        unfold(response.expect_body()),
+    )
+}
+
+async fn get_attachment(
+    client: &DummyWebDavClient,
+    path: &str,
+    managed_id: &str,
+) -> crate::utils::webdav::DavResponse {
+    client
+        .request("GET", &format!("{path}?managed-id={managed_id}"), "")
+        .await
+}
+
+async fn get_event(client: &DummyWebDavClient, path: &str) -> String {
+    unfold(
+        client
+            .request("GET", path, "")
+            .await
+            .with_status(StatusCode::OK)
+            .expect_body(),
+    )
+}
+
+fn unfold(ical: &str) -> String {
+    ical.replace("\r\n ", "")
+}
+
+const TEST_ICAL_SMALL: &str = r#"BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:attachment-quota
+SUMMARY:Quota
+DTSTART:20250101T100000Z
+DTEND:20250101T110000Z
+END:VEVENT
+END:VCALENDAR
+"#;
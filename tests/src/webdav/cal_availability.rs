@@ -0,0 +1,192 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    utils::{jmap::JmapUtils, server::TestServer},
+    webdav::TEST_ICAL_2,
+};
+use dav_proto::schema::property::{CalDavProperty, DavProperty};
+use hyper::StatusCode;
+use serde_json::json;
+
+pub async fn test(test: &TestServer) {
+    println!("Running calendar availability tests...");
+    let account = test.account("jane@example.com");
+    let client = account.webdav_client();
+    let inbox_path = "/dav/itip/jane%40example.com/inbox/";
+    let cal_path = "/dav/cal/jane%40example.com/default/";
+    let availability = DavProperty::CalDav(CalDavProperty::CalendarAvailability);
+
+    // Only VAVAILABILITY components are accepted
+    let response = client
+        .proppatch(inbox_path, [(availability.clone(), TEST_ICAL_2)], [], [])
+        .await
+        .with_status(StatusCode::MULTI_STATUS)
+        .into_propfind_response(None);
+    response
+        .properties(inbox_path)
+        .get(availability.clone())
+        .with_status(StatusCode::PRECONDITION_FAILED)
+        .with_description("Invalid calendar availability");
+
+    // Availability can only be set on the scheduling inbox
+    client
+        .proppatch(
+            "/dav/itip/jane%40example.com/outbox/",
+            [(availability.clone(), AVAILABILITY)],
+            [],
+            [],
+        )
+        .await
+        .with_status(StatusCode::FORBIDDEN);
+
+    // Store working hours
+    client
+        .patch_and_check(
+            inbox_path,
+            [(
+                availability.clone(),
+                AVAILABILITY.replace('\n', "\r\n").as_str(),
+            )],
+        )
+        .await;
+
+    // Add a meeting during working hours
+    let event_path = format!("{cal_path}availability-event.ics");
+    client
+        .request("PUT", &event_path, EVENT)
+        .await
+        .with_status(StatusCode::CREATED);
+
+    // Time outside working hours is reported as BUSY-UNAVAILABLE
+    let response = client
+        .request("REPORT", cal_path, FREEBUSY_QUERY)
+        .await
+        .with_status(StatusCode::OK)
+        .body
+        .unwrap()
+        .replace("\r\n ", "");
+    assert!(
+        response.contains("FREEBUSY;FBTYPE=BUSY:20300107T100000Z/20300107T110000Z"),
+        "{response}"
+    );
+    assert!(
+        response.contains(concat!(
+            "FREEBUSY;FBTYPE=BUSY-UNAVAILABLE:20300107T000000Z/20300107T090000Z,",
+            "20300107T170000Z/20300108T090000Z,20300108T170000Z/20300109T000000Z"
+        )),
+        "{response}"
+    );
+
+    // Same for JMAP availability queries
+    let response = account
+        .jmap_method_calls(json!([[
+            "Principal/getAvailability",
+            {
+                "accountId": account.id_string(),
+                "id": account.id_string(),
+                "utcStart": "2030-01-07T00:00:00Z",
+                "utcEnd": "2030-01-08T00:00:00Z",
+            },
+            "0"
+        ]]))
+        .await;
+    response.list_array().assert_is_equal(json!([
+      {
+        "utcStart": "2030-01-07T10:00:00Z",
+        "utcEnd": "2030-01-07T11:00:00Z",
+        "busyStatus": "confirmed",
+        "event": null
+      },
+      {
+        "utcStart": "2030-01-07T00:00:00Z",
+        "utcEnd": "2030-01-07T09:00:00Z",
+        "busyStatus": "unavailable",
+        "event": null
+      },
+      {
+        "utcStart": "2030-01-07T17:00:00Z",
+        "utcEnd": "2030-01-08T00:00:00Z",
+        "busyStatus": "unavailable",
+        "event": null
+      }
+    ]));
+
+    // Weekends are entirely unavailable
+    let response = account
+        .jmap_method_calls(json!([[
+            "Principal/getAvailability",
+            {
+                "accountId": account.id_string(),
+                "id": account.id_string(),
+                "utcStart": "2030-01-12T00:00:00Z",
+                "utcEnd": "2030-01-14T00:00:00Z",
+            },
+            "0"
+        ]]))
+        .await;
+    response.list_array().assert_is_equal(json!([
+      {
+        "utcStart": "2030-01-12T00:00:00Z",
+        "utcEnd": "2030-01-14T00:00:00Z",
+        "busyStatus": "unavailable",
+        "event": null
+      }
+    ]));
+
+    // Removing the availability restores the previous behaviour
+    client
+        .patch_and_check(inbox_path, [(availability.clone(), "")])
+        .await;
+    let response = client
+        .request("REPORT", cal_path, FREEBUSY_QUERY)
+        .await
+        .with_status(StatusCode::OK)
+        .body
+        .unwrap();
+    assert!(!response.contains("BUSY-UNAVAILABLE"), "{response}");
+
+    client.delete_default_containers().await;
+    test.assert_is_empty().await;
+}
+
+const AVAILABILITY: &str = r#"BEGIN:VCALENDAR
+VERSION:2.0
+PRODID:-//Example Corp.//CalDAV Client//EN
+BEGIN:VAVAILABILITY
+UID:availability-1@example.com
+DTSTAMP:20300101T000000Z
+DTSTART:20300101T000000Z
+BEGIN:AVAILABLE
+UID:available-1@example.com
+DTSTAMP:20300101T000000Z
+DTSTART:20300107T090000Z
+DTEND:20300107T170000Z
+RRULE:FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR
+END:AVAILABLE
+END:VAVAILABILITY
+END:VCALENDAR
+"#;
+
+const EVENT: &str = r#"BEGIN:VCALENDAR
+VERSION:2.0
+PRODID:-//Example Corp.//CalDAV Client//EN
+BEGIN:VEVENT
+UID:availability-event@example.com
+DTSTAMP:20300101T000000Z
+DTSTART:20300107T100000Z
+DTEND:20300107T110000Z
+SUMMARY:Planning meeting
+END:VEVENT
+END:VCALENDAR
+"#;
+
+const FREEBUSY_QUERY: &str = r#"<?xml version="1.0" encoding="utf-8" ?>
+   <C:free-busy-query xmlns:C="urn:ietf:params:xml:ns:caldav">
+     <C:time-range start="20300107T000000Z"
+                     end="20300109T000000Z"/>
+   </C:free-busy-query>
+"#;
@@ -0,0 +1,278 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::server::TestServer;
+use hyper::StatusCode;
+
+pub async fn test(test: &TestServer) {
+    println!("Running SEARCH tests...");
+    let client = test.account("john@example.com").webdav_client();
+
+    // Create test data
+    let base_path = "/dav/file/john%40example.com/search";
+    let sub_path = format!("{base_path}/drafts");
+    for path in [base_path, sub_path.as_str()] {
+        client
+            .request("MKCOL", path, "")
+            .await
+            .with_status(StatusCode::CREATED);
+    }
+    let report = format!("{base_path}/Quarterly-Report.txt");
+    let notes = format!("{base_path}/notes.md");
+    let image = format!("{base_path}/logo.png");
+    let draft = format!("{sub_path}/report-draft.txt");
+    for (path, content_type, contents) in [
+        (
+            report.as_str(),
+            "text/plain",
+            "Revenue grew by twelve percent during the quarterly review period.",
+        ),
+        (notes.as_str(), "text/markdown", "Meeting notes."),
+        (image.as_str(), "image/png", IMAGE_DATA),
+        (
+            draft.as_str(),
+            "text/plain",
+            "Draft of the upcoming report.",
+        ),
+    ] {
+        client
+            .request_with_headers("PUT", path, [("content-type", content_type)], contents)
+            .await
+            .with_status(StatusCode::CREATED);
+    }
+
+    // Search by name
+    client
+        .request(
+            "SEARCH",
+            base_path,
+            SEARCH_QUERY
+                .replace("$WHERE", LIKE_NAME)
+                .replace("$DEPTH", "infinity"),
+        )
+        .await
+        .with_status(StatusCode::MULTI_STATUS)
+        .with_hrefs([report.as_str(), draft.as_str()]);
+
+    // Search by content type
+    client
+        .request(
+            "SEARCH",
+            base_path,
+            SEARCH_QUERY
+                .replace("$WHERE", EQ_CONTENT_TYPE)
+                .replace("$DEPTH", "infinity"),
+        )
+        .await
+        .with_status(StatusCode::MULTI_STATUS)
+        .with_hrefs([image.as_str()]);
+
+    // Search by size
+    client
+        .request(
+            "SEARCH",
+            base_path,
+            SEARCH_QUERY
+                .replace("$WHERE", GT_CONTENT_LENGTH)
+                .replace("$DEPTH", "infinity"),
+        )
+        .await
+        .with_status(StatusCode::MULTI_STATUS)
+        .with_hrefs([report.as_str(), image.as_str()]);
+
+    // Search by modification date
+    client
+        .request(
+            "SEARCH",
+            base_path,
+            SEARCH_QUERY
+                .replace("$WHERE", GT_LAST_MODIFIED)
+                .replace("$DEPTH", "1"),
+        )
+        .await
+        .with_status(StatusCode::MULTI_STATUS)
+        .with_hrefs([
+            format!("{base_path}/").as_str(),
+            format!("{sub_path}/").as_str(),
+            report.as_str(),
+            notes.as_str(),
+            image.as_str(),
+        ]);
+
+    // Search collections and files using a shallow scope
+    client
+        .request(
+            "SEARCH",
+            base_path,
+            SEARCH_QUERY
+                .replace("$WHERE", IS_COLLECTION)
+                .replace("$DEPTH", "infinity"),
+        )
+        .await
+        .with_status(StatusCode::MULTI_STATUS)
+        .with_hrefs([
+            format!("{base_path}/").as_str(),
+            format!("{sub_path}/").as_str(),
+        ]);
+    client
+        .request(
+            "SEARCH",
+            base_path,
+            SEARCH_QUERY
+                .replace("$WHERE", NOT_COLLECTION)
+                .replace("$DEPTH", "1"),
+        )
+        .await
+        .with_status(StatusCode::MULTI_STATUS)
+        .with_hrefs([report.as_str(), notes.as_str(), image.as_str()]);
+
+    // Sort and limit results
+    for (order, expected) in [
+        ("descending", image.as_str()),
+        ("ascending", notes.as_str()),
+    ] {
+        client
+            .request(
+                "SEARCH",
+                base_path,
+                SEARCH_ORDER_LIMIT.replace("$ORDER", order),
+            )
+            .await
+            .with_status(StatusCode::MULTI_STATUS)
+            .with_value(
+                "D:multistatus.D:response.D:error.D:number-of-matches-within-limits",
+                "",
+            )
+            .with_hrefs([expected, base_path]);
+    }
+
+    // Full-text search
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    test.wait_for_tasks().await;
+    client
+        .request(
+            "SEARCH",
+            base_path,
+            SEARCH_QUERY
+                .replace("$WHERE", CONTAINS)
+                .replace("$DEPTH", "infinity"),
+        )
+        .await
+        .with_status(StatusCode::MULTI_STATUS)
+        .with_hrefs([report.as_str()]);
+
+    // Unknown scope
+    client
+        .request(
+            "SEARCH",
+            &format!("{base_path}/missing"),
+            SEARCH_QUERY
+                .replace("$WHERE", IS_COLLECTION)
+                .replace("$DEPTH", "infinity"),
+        )
+        .await
+        .with_status(StatusCode::MULTI_STATUS)
+        .with_value(
+            "D:multistatus.D:response.D:status",
+            "HTTP/1.1 404 Not Found",
+        );
+
+    // SEARCH is only supported on file collections
+    client
+        .request(
+            "SEARCH",
+            "/dav/card/john%40example.com/default",
+            SEARCH_QUERY
+                .replace("$WHERE", IS_COLLECTION)
+                .replace("$DEPTH", "infinity"),
+        )
+        .await
+        .with_status(StatusCode::METHOD_NOT_ALLOWED);
+
+    client
+        .request("DELETE", base_path, "")
+        .await
+        .with_status(StatusCode::NO_CONTENT);
+    client.delete_default_containers().await;
+    test.assert_is_empty().await;
+}
+
+const IMAGE_DATA: &str = concat!(
+    "PNG image data placeholder used to exercise size comparisons in ",
+    "search queries, padded with enough bytes to be the largest file ",
+    "in the test collection."
+);
+
+const SEARCH_QUERY: &str = r#"<?xml version="1.0" encoding="utf-8" ?>
+<D:searchrequest xmlns:D="DAV:">
+  <D:basicsearch>
+    <D:select>
+      <D:prop><D:displayname/><D:getcontentlength/></D:prop>
+    </D:select>
+    <D:from>
+      <D:scope>
+        <D:href></D:href>
+        <D:depth>$DEPTH</D:depth>
+      </D:scope>
+    </D:from>
+    <D:where>
+      $WHERE
+    </D:where>
+  </D:basicsearch>
+</D:searchrequest>"#;
+
+const LIKE_NAME: &str = r#"<D:like caseless="yes">
+  <D:prop><D:displayname/></D:prop>
+  <D:literal>%report%</D:literal>
+</D:like>"#;
+
+const EQ_CONTENT_TYPE: &str = r#"<D:eq>
+  <D:prop><D:getcontenttype/></D:prop>
+  <D:literal>image/png</D:literal>
+</D:eq>"#;
+
+const GT_CONTENT_LENGTH: &str = r#"<D:gt>
+  <D:prop><D:getcontentlength/></D:prop>
+  <D:literal>40</D:literal>
+</D:gt>"#;
+
+const GT_LAST_MODIFIED: &str = r#"<D:gt>
+  <D:prop><D:getlastmodified/></D:prop>
+  <D:literal>Mon, 01 Jan 2001 00:00:00 GMT</D:literal>
+</D:gt>"#;
+
+const IS_COLLECTION: &str = r#"<D:is-collection/>"#;
+
+const NOT_COLLECTION: &str = r#"<D:not><D:is-collection/></D:not>"#;
+
+const CONTAINS: &str = r#"<D:contains>quarterly</D:contains>"#;
+
+const SEARCH_ORDER_LIMIT: &str = r#"<?xml version="1.0" encoding="utf-8" ?>
+<D:searchrequest xmlns:D="DAV:">
+  <D:basicsearch>
+    <D:select>
+      <D:prop><D:getcontentlength/></D:prop>
+    </D:select>
+    <D:from>
+      <D:scope>
+        <D:href>/dav/file/john%40example.com/search</D:href>
+        <D:depth>infinity</D:depth>
+      </D:scope>
+    </D:from>
+    <D:where>
+      <D:not><D:is-collection/></D:not>
+    </D:where>
+    <D:orderby>
+      <D:order>
+        <D:prop><D:getcontentlength/></D:prop>
+        <D:$ORDER/>
+      </D:order>
+    </D:orderby>
+    <D:limit>
+      <D:nresults>1</D:nresults>
+    </D:limit>
+  </D:basicsearch>
+</D:searchrequest>"#;
@@ -0,0 +1,179 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::server::TestServer;
+use hyper::StatusCode;
+
+pub async fn test(test: &TestServer) {
+    println!("Running chunked upload tests...");
+    let client = test.account("john@example.com").webdav_client();
+
+    let base_path = "/dav/file/john%40example.com/uploads";
+    let destination = format!("{base_path}/large.bin");
+    let upload_path = "/dav/uploads/john%40example.com/transfer-1";
+    client
+        .request("MKCOL", base_path, "")
+        .await
+        .with_status(StatusCode::CREATED);
+
+    // Sessions require a destination and a declared length
+    client
+        .request_with_headers(
+            "MKCOL",
+            upload_path,
+            [("destination", destination.as_str())],
+            "",
+        )
+        .await
+        .with_status(StatusCode::BAD_REQUEST);
+    client
+        .request_with_headers(
+            "MKCOL",
+            upload_path,
+            [
+                ("destination", "/dav/cal/john%40example.com/default/x.ics"),
+                ("oc-total-length", "18"),
+            ],
+            "",
+        )
+        .await
+        .with_status(StatusCode::BAD_GATEWAY);
+    client
+        .request_with_headers(
+            "MKCOL",
+            upload_path,
+            [
+                ("destination", destination.as_str()),
+                ("oc-total-length", "18"),
+            ],
+            "",
+        )
+        .await
+        .with_status(StatusCode::CREATED);
+    client
+        .request_with_headers(
+            "MKCOL",
+            upload_path,
+            [
+                ("destination", destination.as_str()),
+                ("oc-total-length", "18"),
+            ],
+            "",
+        )
+        .await
+        .with_status(StatusCode::METHOD_NOT_ALLOWED);
+
+    // Other accounts cannot use this upload area
+    client
+        .request("PROPFIND", "/dav/uploads/jane%40example.com/transfer-1", "")
+        .await
+        .with_status(StatusCode::FORBIDDEN);
+
+    // Upload chunks out of order
+    for (chunk, contents) in [("2", "chunk two "), ("1", "one, ")] {
+        client
+            .request("PUT", &format!("{upload_path}/{chunk}"), contents)
+            .await
+            .with_status(StatusCode::CREATED);
+    }
+    client
+        .request("PUT", &format!("{upload_path}/0"), "x")
+        .await
+        .with_status(StatusCode::BAD_REQUEST);
+    client
+        .request(
+            "PUT",
+            &format!("{upload_path}/4"),
+            "this chunk is larger than the upload",
+        )
+        .await
+        .with_status(StatusCode::PAYLOAD_TOO_LARGE);
+    let chunk_1 = format!("{upload_path}/1");
+    let chunk_2 = format!("{upload_path}/2");
+    client
+        .request_with_headers("PROPFIND", upload_path, [("depth", "1")], "")
+        .await
+        .with_status(StatusCode::MULTI_STATUS)
+        .with_hrefs([
+            format!("{upload_path}/").as_str(),
+            chunk_1.as_str(),
+            chunk_2.as_str(),
+        ]);
+
+    // Assembling an incomplete upload fails
+    client
+        .request_with_headers(
+            "MOVE",
+            &format!("{upload_path}/.file"),
+            [("destination", destination.as_str())],
+            "",
+        )
+        .await
+        .with_status(StatusCode::BAD_REQUEST);
+    client
+        .request("GET", &destination, "")
+        .await
+        .with_status(StatusCode::NOT_FOUND);
+
+    // Upload the last chunk and assemble the file
+    client
+        .request("PUT", &format!("{upload_path}/3"), "end")
+        .await
+        .with_status(StatusCode::CREATED);
+    client
+        .request_with_headers(
+            "MOVE",
+            &format!("{upload_path}/.file"),
+            [("destination", destination.as_str())],
+            "",
+        )
+        .await
+        .with_status(StatusCode::CREATED);
+    client
+        .request("GET", &destination, "")
+        .await
+        .with_status(StatusCode::OK)
+        .with_body("one, chunk two end");
+
+    // The session is removed after assembly
+    client
+        .request("PROPFIND", upload_path, "")
+        .await
+        .with_status(StatusCode::NOT_FOUND);
+
+    // Abort an upload
+    client
+        .request_with_headers(
+            "MKCOL",
+            upload_path,
+            [
+                ("destination", destination.as_str()),
+                ("oc-total-length", "5"),
+            ],
+            "",
+        )
+        .await
+        .with_status(StatusCode::CREATED);
+    client
+        .request("PUT", &format!("{upload_path}/1"), "hello")
+        .await
+        .with_status(StatusCode::CREATED);
+    client
+        .request("DELETE", upload_path, "")
+        .await
+        .with_status(StatusCode::NO_CONTENT);
+    client
+        .request("PUT", &format!("{upload_path}/1"), "hello")
+        .await
+        .with_status(StatusCode::NOT_FOUND);
+
+    client
+        .request("DELETE", base_path, "")
+        .await
+        .with_status(StatusCode::NO_CONTENT);
+    client.delete_default_containers().await;
+    test.assert_is_empty().await;
+}
@@ -0,0 +1,154 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::server::TestServer;
+use hyper::StatusCode;
+
+pub async fn test(test: &TestServer) {
+    println!("Running file versioning tests...");
+    let client = test.account("john@example.com").webdav_client();
+
+    // Create a file and overwrite it twice
+    let base_path = "/dav/file/john%40example.com/versions";
+    let path = format!("{base_path}/report.txt");
+    client
+        .request("MKCOL", base_path, "")
+        .await
+        .with_status(StatusCode::CREATED);
+    client
+        .request_with_headers(
+            "PUT",
+            &path,
+            [("content-type", "text/plain")],
+            "first draft",
+        )
+        .await
+        .with_status(StatusCode::CREATED);
+    for contents in ["second draft", "final version"] {
+        client
+            .request_with_headers("PUT", &path, [("content-type", "text/plain")], contents)
+            .await
+            .with_status(StatusCode::NO_CONTENT);
+    }
+
+    // Writing the same contents does not create a new version
+    client
+        .request_with_headers(
+            "PUT",
+            &path,
+            [("content-type", "text/plain")],
+            "final version",
+        )
+        .await
+        .with_status(StatusCode::NO_CONTENT);
+
+    // List versions
+    let versions_path = format!("{path}?versions");
+    let first_version = format!("{path}?version=0");
+    let second_version = format!("{path}?version=1");
+    client
+        .request_with_headers(
+            "PROPFIND",
+            &versions_path,
+            [("depth", "1")],
+            PROPFIND_VERSIONS,
+        )
+        .await
+        .with_status(StatusCode::MULTI_STATUS)
+        .with_hrefs([
+            versions_path.as_str(),
+            first_version.as_str(),
+            second_version.as_str(),
+        ]);
+    client
+        .request_with_headers(
+            "PROPFIND",
+            &versions_path,
+            [("depth", "0")],
+            PROPFIND_VERSIONS,
+        )
+        .await
+        .with_status(StatusCode::MULTI_STATUS)
+        .with_hrefs([versions_path.as_str()]);
+
+    // Fetch an old version
+    client
+        .request("GET", &first_version, "")
+        .await
+        .with_status(StatusCode::OK)
+        .with_header("content-type", "text/plain")
+        .with_body("first draft");
+    client
+        .request("GET", &format!("{path}?version=99"), "")
+        .await
+        .with_status(StatusCode::NOT_FOUND);
+
+    // Restore the first version
+    client
+        .request(
+            "POST",
+            &format!("{path}?action=restore-version&version=0"),
+            "",
+        )
+        .await
+        .with_status(StatusCode::NO_CONTENT);
+    client
+        .request("GET", &path, "")
+        .await
+        .with_status(StatusCode::OK)
+        .with_body("first draft");
+
+    // The overwritten contents are now a version and the restored one is gone
+    let third_version = format!("{path}?version=2");
+    client
+        .request_with_headers(
+            "PROPFIND",
+            &versions_path,
+            [("depth", "1")],
+            PROPFIND_VERSIONS,
+        )
+        .await
+        .with_status(StatusCode::MULTI_STATUS)
+        .with_hrefs([
+            versions_path.as_str(),
+            second_version.as_str(),
+            third_version.as_str(),
+        ]);
+    client
+        .request("GET", &third_version, "")
+        .await
+        .with_status(StatusCode::OK)
+        .with_body("final version");
+
+    // Collections have no versions
+    client
+        .request_with_headers(
+            "PROPFIND",
+            &format!("{base_path}?versions"),
+            [("depth", "1")],
+            PROPFIND_VERSIONS,
+        )
+        .await
+        .with_status(StatusCode::METHOD_NOT_ALLOWED);
+
+    // Deleting the file removes its versions
+    client
+        .request("DELETE", base_path, "")
+        .await
+        .with_status(StatusCode::NO_CONTENT);
+    client.delete_default_containers().await;
+    test.assert_is_empty().await;
+}
+
+const PROPFIND_VERSIONS: &str = r#"<?xml version="1.0" encoding="utf-8" ?>
+<D:propfind xmlns:D="DAV:">
+  <D:prop>
+    <D:getcontentlength/>
+    <D:getcontenttype/>
+    <D:getlastmodified/>
+    <D:resourcetype/>
+  </D:prop>
+</D:propfind>"#;
@@ -27,11 +27,16 @@ use std::time::Instant;
 pub mod acl;
 pub mod basic;
 pub mod cal_alarm;
+pub mod cal_attachment;
+pub mod cal_availability;
 pub mod cal_itip;
 pub mod cal_query;
 pub mod cal_scheduling;
 pub mod card_query;
 pub mod copy_move;
+pub mod file_search;
+pub mod file_upload;
+pub mod file_versions;
 pub mod lock;
 pub mod mkcol;
 pub mod multiget;
@@ -192,8 +197,13 @@ pub async fn webdav_tests() {
     card_query::test(&test).await;
     cal_query::test(&test).await;
     cal_alarm::test(&test).await;
+    cal_attachment::test(&test).await;
     cal_itip::test();
     cal_scheduling::test(&test).await;
+    cal_availability::test(&test).await;
+    file_search::test(&test).await;
+    file_versions::test(&test).await;
+    file_upload::test(&test).await;
 
     // Print elapsed time
     let elapsed = start_time.elapsed();